@@ -0,0 +1,122 @@
+// benches/search.rs
+// Hot paths of a search: tag parsing, matching, cache keys, salary conversion and formatting
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use std::hint::black_box;
+
+use common::{MemoryRelay, builder, listing};
+use criterion::{Criterion, criterion_group, criterion_main};
+use jobmcp::jobs::JobStats;
+use jobmcp::jobs::rates::ExchangeRate;
+use jobmcp::{JobListing, JobQuery};
+use nostr_sdk::prelude::*;
+
+const EVENTS: usize = 10_000;
+
+/// `EVENTS` listings cycling through a few companies, skills, types and
+/// salary currencies, like a busy relay's backlog
+fn events() -> Vec<Event> {
+    let keys = Keys::generate();
+    let companies = ["Acme", "Globex", "Initech", "Hooli", "Umbrella"];
+    let skills = ["Rust", "Python", "Go", "TypeScript", "PostgreSQL", "Kubernetes", "Tokio"];
+    let types = ["full-time", "part-time", "contract"];
+    let salaries = [["120000", "150000", "USD", "year"], ["5000000", "8000000", "sats", "month"], ["0.5", "0.8", "BTC", "year"]];
+
+    (0..EVENTS)
+        .map(|i| {
+            let salary = salaries[i % salaries.len()];
+            listing(
+                &keys,
+                &format!("job-{}", i),
+                "Engineer",
+                companies[i % companies.len()],
+                &[
+                    &["skill", skills[i % skills.len()]],
+                    &["skill", skills[(i + 3) % skills.len()]],
+                    &["employment-type", types[i % types.len()]],
+                    &["location", "Remote"],
+                    &["salary", salary[0], salary[1], salary[2], salary[3]],
+                ],
+                (i % 86_400) as u64,
+            )
+        })
+        .collect()
+}
+
+fn query() -> JobQuery {
+    JobQuery {
+        company: vec!["acme".into(), "Hooli".into()],
+        skill: vec!["rust".into(), "Tokio".into()],
+        employment_type: vec!["full-time".into()],
+        ..Default::default()
+    }
+}
+
+fn rate() -> ExchangeRate {
+    ExchangeRate {
+        fiat: "USD".into(),
+        per_btc: 60_000.0,
+        as_of: Timestamp::now(),
+    }
+}
+
+fn bench_parsing(c: &mut Criterion) {
+    let events = events();
+    c.bench_function("parse 10k listings", |b| {
+        b.iter(|| events.iter().cloned().map(JobListing::from_event).collect::<Vec<_>>())
+    });
+}
+
+fn bench_filtering(c: &mut Criterion) {
+    let listings: Vec<JobListing> = events().into_iter().map(JobListing::from_event).collect();
+    let exact = query();
+    let fuzzy = JobQuery { fuzzy_distance: Some(2), ..query() };
+
+    c.bench_function("filter 10k listings", |b| {
+        b.iter(|| listings.iter().filter(|l| exact.matches(l)).count())
+    });
+    c.bench_function("filter 10k listings (fuzzy)", |b| {
+        b.iter(|| listings.iter().filter(|l| fuzzy.matches(l)).count())
+    });
+}
+
+fn bench_cache_key(c: &mut Criterion) {
+    let query = JobQuery {
+        author: Some(Keys::generate().public_key()),
+        ..query()
+    };
+    c.bench_function("cache key", |b| b.iter(|| black_box(&query).cache_key()));
+}
+
+fn bench_salaries(c: &mut Criterion) {
+    let listings: Vec<JobListing> = events().into_iter().map(JobListing::from_event).collect();
+    let rate = rate();
+
+    c.bench_function("convert 10k salaries", |b| {
+        b.iter(|| {
+            listings
+                .iter()
+                .filter_map(|l| l.salary.as_ref())
+                .filter_map(|s| rate.convert(s))
+                .count()
+        })
+    });
+    c.bench_function("stats over 10k listings", |b| b.iter(|| JobStats::from_listings_in(&listings, Some(&rate))));
+}
+
+fn bench_summary(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let server = runtime.block_on(builder(MemoryRelay::new(Vec::new())).build()).unwrap();
+    let sats = JobListing::from_event(events().swap_remove(1));
+    let rate = rate();
+
+    c.bench_function("format_job_summary", |b| b.iter(|| server.format_job_summary(black_box(&sats), None)));
+    c.bench_function("format_job_summary (converted)", |b| {
+        b.iter(|| server.format_job_summary(black_box(&sats), Some(&rate)))
+    });
+}
+
+criterion_group!(benches, bench_parsing, bench_filtering, bench_cache_key, bench_salaries, bench_summary);
+criterion_main!(benches);
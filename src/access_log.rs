@@ -0,0 +1,191 @@
+// src/access_log.rs
+// HTTP access log: one JSON line per request, written to its own rotating file
+
+use std::path::{Path, PathBuf};
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+use futures::future::BoxFuture;
+use http::{Request, Response};
+use nostr_sdk::Timestamp;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+use tower::{Layer, Service};
+
+use crate::proxy;
+
+/// Lines waiting to be written; beyond this requests go unlogged rather than
+/// holding up responses
+const ACCESS_LOG_QUEUE: usize = 4096;
+
+/// One line of the access log (JSON Lines)
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct AccessEntry {
+    pub at: u64,
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    /// Time until the response head was ready; streamed bodies (SSE) keep
+    /// going after this
+    pub duration_ms: u64,
+    pub session_id: Option<String>,
+    pub client: Option<String>,
+}
+
+/// Handle to the access log writer. Cloning shares the writer.
+#[derive(Clone, Debug)]
+pub struct AccessLog {
+    lines: mpsc::Sender<String>,
+}
+
+impl AccessLog {
+    /// Start writing to `path`. Once the file would grow past `max_bytes` it
+    /// is renamed to `path.1` (older files shifting up, `keep` kept); 0 never
+    /// rotates.
+    pub fn open(path: PathBuf, max_bytes: u64, keep: usize) -> Self {
+        let (lines, rx) = mpsc::channel(ACCESS_LOG_QUEUE);
+        tokio::spawn(write_loop(path, max_bytes, keep, rx));
+        Self { lines }
+    }
+
+    fn record(&self, entry: &AccessEntry) {
+        let Ok(mut line) = serde_json::to_string(entry) else {
+            return;
+        };
+        line.push('\n');
+        if self.lines.try_send(line).is_err() {
+            tracing::debug!("access_log_line_dropped");
+        }
+    }
+}
+
+async fn write_loop(path: PathBuf, max_bytes: u64, keep: usize, mut lines: mpsc::Receiver<String>) {
+    let mut size = tokio::fs::metadata(&path).await.map(|m| m.len()).unwrap_or(0);
+    let mut file: Option<tokio::fs::File> = None;
+    while let Some(line) = lines.recv().await {
+        if max_bytes > 0 && size > 0 && size + line.len() as u64 > max_bytes {
+            file = None;
+            if let Err(e) = rotate(&path, keep).await {
+                tracing::error!(path = %path.display(), error = %e, "access_log_rotate_failed");
+            }
+            size = 0;
+        }
+        if file.is_none() {
+            match tokio::fs::OpenOptions::new().create(true).append(true).open(&path).await {
+                Ok(opened) => file = Some(opened),
+                Err(e) => {
+                    tracing::error!(path = %path.display(), error = %e, "access_log_write_failed");
+                    continue;
+                }
+            }
+        }
+        if let Some(open) = &mut file {
+            match open.write_all(line.as_bytes()).await {
+                Ok(()) => size += line.len() as u64,
+                Err(e) => {
+                    tracing::error!(path = %path.display(), error = %e, "access_log_write_failed");
+                    file = None;
+                }
+            }
+        }
+    }
+}
+
+/// `path` → `path.1` → `path.2` …, dropping whatever falls past `keep`
+async fn rotate(path: &Path, keep: usize) -> std::io::Result<()> {
+    if keep == 0 {
+        return tokio::fs::remove_file(path).await;
+    }
+    for n in (1..keep).rev() {
+        match tokio::fs::rename(rotated(path, n), rotated(path, n + 1)).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+    }
+    tokio::fs::rename(path, rotated(path, 1)).await
+}
+
+fn rotated(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+/// Logs every request passing through to an [`AccessLog`]
+#[derive(Clone, Debug)]
+pub struct AccessLogLayer {
+    log: AccessLog,
+    trust_forwarded: bool,
+}
+
+impl AccessLogLayer {
+    /// `trust_forwarded` takes the client address from `X-Forwarded-For`
+    pub fn new(log: AccessLog, trust_forwarded: bool) -> Self {
+        Self { log, trust_forwarded }
+    }
+}
+
+impl<S> Layer<S> for AccessLogLayer {
+    type Service = AccessLogService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AccessLogService {
+            inner,
+            log: self.log.clone(),
+            trust_forwarded: self.trust_forwarded,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AccessLogService<S> {
+    inner: S,
+    log: AccessLog,
+    trust_forwarded: bool,
+}
+
+impl<S, B, ResBody> Service<Request<B>> for AccessLogService<S>
+where
+    S: Service<Request<B>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let started = Instant::now();
+        let method = request.method().to_string();
+        let path = request.uri().path().to_string();
+        let client = proxy::client_ip_from(request.headers(), request.extensions(), self.trust_forwarded);
+        let session_id = mcp_session_id(request.headers());
+        let log = self.log.clone();
+        let response = self.inner.call(request);
+
+        Box::pin(async move {
+            let response = response.await?;
+            log.record(&AccessEntry {
+                at: Timestamp::now().as_secs(),
+                method,
+                path,
+                status: response.status().as_u16(),
+                duration_ms: started.elapsed().as_millis() as u64,
+                // A session's first request gets its id on the response
+                session_id: session_id.or_else(|| mcp_session_id(response.headers())),
+                client: client.map(|ip| ip.to_string()),
+            });
+            Ok(response)
+        })
+    }
+}
+
+fn mcp_session_id(headers: &http::HeaderMap) -> Option<String> {
+    headers
+        .get("mcp-session-id")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
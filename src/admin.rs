@@ -0,0 +1,173 @@
+// src/admin.rs
+// Admin HTTP API mirroring the admin tools, so operations tooling can clear
+// the cache, change relays and read metrics without opening an MCP session
+
+use std::time::Instant;
+
+use axum::extract::{DefaultBodyLimit, OriginalUri, Query, Request, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use nostr_sdk::Timestamp;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::auth;
+use crate::diagnostics::{AuditEntry, hash_args};
+use crate::jobs::bulk::BulkFormat;
+use crate::mcp_server::NostrJobsServer;
+use crate::proxy;
+
+/// Largest snapshot `POST /snapshot` accepts, well above the NDJSON of
+/// `MAX_STATS_SAMPLE_SIZE` listings
+const MAX_SNAPSHOT_BYTES: usize = 64 * 1024 * 1024;
+
+/// Largest payload `POST /jobs/bulk` accepts
+const MAX_BULK_BYTES: usize = 16 * 1024 * 1024;
+
+/// Body of `POST /admin/relays`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct RelayChanges {
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
+}
+
+/// Query of `POST /admin/jobs/bulk`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct BulkOptions {
+    /// `csv` or `ndjson`; detected from the body when unset
+    pub format: Option<String>,
+    pub dry_run: bool,
+}
+
+/// `POST /cache/clear`, `GET|POST /relays`, `GET /metrics`,
+/// `GET|POST /snapshot` and `POST /jobs/bulk` for one board,
+/// to be nested under `{base}/admin` (or `{base}/admin/{tenant}`). Every
+/// request needs a bearer token from `ADMIN_TOKENS`; without any configured
+/// the API is disabled.
+pub fn routes(server: NostrJobsServer) -> Router {
+    Router::new()
+        .route("/cache/clear", post(clear_cache))
+        .route("/relays", get(list_relays).post(update_relays))
+        .route("/metrics", get(metrics))
+        .route(
+            "/snapshot",
+            get(export_snapshot).post(import_snapshot).layer(DefaultBodyLimit::max(MAX_SNAPSHOT_BYTES)),
+        )
+        .route("/jobs/bulk", post(bulk_publish).layer(DefaultBodyLimit::max(MAX_BULK_BYTES)))
+        .layer(middleware::from_fn_with_state(server.clone(), require_admin))
+        .with_state(server)
+}
+
+/// Reject requests without an admin token and audit the ones let through
+async fn require_admin(State(server): State<NostrJobsServer>, request: Request, next: Next) -> Response {
+    let config = server.config();
+    let action = format!(
+        "{} {}",
+        request.method(),
+        request.extensions().get::<OriginalUri>().map(|uri| uri.path()).unwrap_or(request.uri().path())
+    );
+    let client = proxy::client_ip_from(request.headers(), request.extensions(), config.trust_forwarded_headers)
+        .map(|ip| ip.to_string());
+
+    if config.admin_tokens.is_empty() {
+        return error(StatusCode::FORBIDDEN, "admin_disabled", "Set ADMIN_TOKENS to enable the admin API");
+    }
+    if !auth::is_admin_request(request.headers(), &config.admin_tokens) {
+        tracing::warn!(action = %action, client = client.as_deref().unwrap_or("-"), "admin_api_unauthorized");
+        let mut response = error(StatusCode::UNAUTHORIZED, "unauthorized", "An admin bearer token is required");
+        response.headers_mut().insert(header::WWW_AUTHENTICATE, header::HeaderValue::from_static("Bearer"));
+        return response;
+    }
+
+    let started = Instant::now();
+    let response = next.run(request).await;
+    let status = response.status();
+    tracing::info!(action = %action, status = status.as_u16(), "admin_api_request");
+    server
+        .audit(&AuditEntry {
+            at: Timestamp::now().as_secs(),
+            tool: action,
+            args_hash: hash_args(&Value::Null),
+            session_id: None,
+            client,
+            outcome: if status.is_success() { "ok" } else { "error" }.to_string(),
+            latency_ms: started.elapsed().as_millis() as u64,
+        })
+        .await;
+    response
+}
+
+async fn clear_cache(State(server): State<NostrJobsServer>) -> Json<Value> {
+    let metrics_before = server.jobs().metrics().read().await.clone();
+    let cleared = server.jobs().clear_cache().await;
+    tracing::warn!(
+        cache_entries_cleared = cleared,
+        cache_hits_before = metrics_before.cache_hits,
+        cache_hit_rate_before = metrics_before.cache_hit_rate(),
+        "cache_cleared"
+    );
+    Json(json!({
+        "cleared_entries": cleared,
+        "cache_hits_before": metrics_before.cache_hits,
+        "hit_rate_pct_before": metrics_before.cache_hit_rate(),
+    }))
+}
+
+async fn list_relays(State(server): State<NostrJobsServer>) -> Json<Value> {
+    let relays = server.jobs().relay_reports().await;
+    Json(json!({"relays": relays.iter().map(NostrJobsServer::relay_report_json).collect::<Vec<_>>()}))
+}
+
+async fn update_relays(State(server): State<NostrJobsServer>, Json(changes): Json<RelayChanges>) -> Response {
+    match server.update_relays(&changes.add, &changes.remove).await {
+        Ok(relays) => Json(json!({"relays": relays})).into_response(),
+        Err(e) => {
+            let mut body = e.to_json();
+            body["message"] = json!(e.to_string());
+            (StatusCode::BAD_REQUEST, Json(body)).into_response()
+        }
+    }
+}
+
+async fn metrics(State(server): State<NostrJobsServer>) -> Json<Value> {
+    Json(server.metrics_json().await)
+}
+
+async fn export_snapshot(State(server): State<NostrJobsServer>) -> Response {
+    ([(header::CONTENT_TYPE, "application/x-ndjson")], server.jobs().export_snapshot().await).into_response()
+}
+
+async fn import_snapshot(State(server): State<NostrJobsServer>, body: String) -> Json<Value> {
+    let report = server.jobs().import_snapshot(&body).await;
+    Json(json!({
+        "imported": report.imported,
+        "duplicates": report.duplicates,
+        "skipped": report.skipped,
+        "invalid": report.invalid.iter().map(|(line, error)| json!({"line": line, "error": error})).collect::<Vec<_>>(),
+    }))
+}
+
+async fn bulk_publish(State(server): State<NostrJobsServer>, Query(options): Query<BulkOptions>, body: String) -> Response {
+    let format = match options.format.as_deref().map(str::parse::<BulkFormat>) {
+        Some(Ok(format)) => format,
+        Some(Err(e)) => return error(StatusCode::BAD_REQUEST, "parse_error", &e),
+        None => BulkFormat::detect(&body),
+    };
+    match server.jobs().publish_bulk(&body, format, options.dry_run).await {
+        Ok(report) => Json(NostrJobsServer::bulk_report_json(&report)).into_response(),
+        Err(e) => {
+            let mut body = e.to_json();
+            body["message"] = json!(e.to_string());
+            (StatusCode::BAD_REQUEST, Json(body)).into_response()
+        }
+    }
+}
+
+fn error(status: StatusCode, code: &str, message: &str) -> Response {
+    (status, Json(json!({"error": code, "message": message}))).into_response()
+}
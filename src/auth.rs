@@ -0,0 +1,163 @@
+// src/auth.rs
+// Session roles derived from the HTTP Authorization header, and which tools require them
+
+use nostr_sdk::hashes::{Hash, sha256};
+use rmcp::{RoleServer, service::RequestContext};
+
+use crate::config::TenantConfig;
+
+/// Tools that change server state or expose operational data
+pub const ADMIN_TOOLS: &[&str] = &[
+    "clear_cache",
+    "reset_metrics",
+    "cache_status",
+    "get_slow_queries",
+    "get_audit_log",
+    "get_api_key_usage",
+    "set_tool_enabled",
+    "reload_config",
+    "set_query_strategy",
+    "export_snapshot",
+    "import_snapshot",
+    "publish_draft",
+    "bulk_publish_jobs",
+    "employer_analytics",
+    "schedule_interview",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    User,
+    Admin,
+}
+
+impl Role {
+    pub fn can_call(&self, tool: &str) -> bool {
+        *self == Role::Admin || !is_admin_tool(tool)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::User => "user",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+pub fn is_admin_tool(tool: &str) -> bool {
+    ADMIN_TOOLS.contains(&tool)
+}
+
+/// Resolve the caller's role. With no admin tokens configured every session
+/// is an admin (single-user deployments); otherwise a matching
+/// `Authorization: Bearer <token>` header is required.
+pub fn role_for(context: &RequestContext<RoleServer>, admin_tokens: &[String]) -> Role {
+    if admin_tokens.is_empty() {
+        return Role::Admin;
+    }
+
+    match bearer_token(context) {
+        Some(token) if admin_tokens.iter().any(|t| constant_time_eq(t.as_bytes(), token.as_bytes())) => Role::Admin,
+        _ => Role::User,
+    }
+}
+
+/// Key for per-user saved state: a hash of the bearer token when the client
+/// sends one, so state follows the user across sessions, otherwise the MCP
+/// session id.
+pub(crate) fn state_owner(context: &RequestContext<RoleServer>) -> Option<String> {
+    if let Some(key) = api_key_id(context) {
+        return Some(format!("user:{}", key));
+    }
+    let parts = context.extensions.get::<http::request::Parts>()?;
+    let session = parts.headers.get("mcp-session-id")?.to_str().ok()?;
+    Some(format!("session:{}", session))
+}
+
+/// Stable, non-secret id of the caller's bearer token (API key): the first
+/// 16 hex digits of its SHA-256
+pub(crate) fn api_key_id(context: &RequestContext<RoleServer>) -> Option<String> {
+    Some(token_id(&bearer_token(context)?))
+}
+
+/// `api_key_id` of a bearer token
+pub(crate) fn token_id(token: &str) -> String {
+    let digest = sha256::Hash::hash(token.as_bytes()).to_string();
+    digest[..16].to_string()
+}
+
+/// Whether `token` is one of `tokens`, compared in constant time
+pub(crate) fn is_listed(token: &str, tokens: &[String]) -> bool {
+    tokens.iter().any(|t| constant_time_eq(t.as_bytes(), token.as_bytes()))
+}
+
+pub(crate) fn bearer_token(context: &RequestContext<RoleServer>) -> Option<String> {
+    let parts = context.extensions.get::<http::request::Parts>()?;
+    header_bearer_token(&parts.headers).map(str::to_string)
+}
+
+pub(crate) fn header_bearer_token(headers: &http::HeaderMap) -> Option<&str> {
+    let value = headers.get(http::header::AUTHORIZATION)?.to_str().ok()?;
+    let token = value.strip_prefix("Bearer ").or_else(|| value.strip_prefix("bearer "))?;
+    Some(token.trim())
+}
+
+/// Whether the request's bearer token is one of `admin_tokens`, for the
+/// admin HTTP API. Unlike `role_for`, an empty list admits nobody.
+pub fn is_admin_request(headers: &http::HeaderMap, admin_tokens: &[String]) -> bool {
+    header_bearer_token(headers)
+        .is_some_and(|token| admin_tokens.iter().any(|t| constant_time_eq(t.as_bytes(), token.as_bytes())))
+}
+
+/// Tenant whose token matches the request's bearer token, used to route
+/// plain `/mcp` requests to a tenant's board
+pub fn tenant_for_token<'a>(headers: &http::HeaderMap, tenants: &'a [TenantConfig]) -> Option<&'a str> {
+    let token = header_bearer_token(headers)?;
+    tenants
+        .iter()
+        .find(|tenant| tenant.tokens.iter().any(|t| constant_time_eq(t.as_bytes(), token.as_bytes())))
+        .map(|tenant| tenant.name.as_str())
+}
+
+/// Rewrite `{base}/mcp` to `{base}/mcp/{tenant}` when the bearer token belongs to a tenant
+pub fn route_by_tenant_token<B>(mut request: http::Request<B>, base: &str, tenants: &[TenantConfig]) -> http::Request<B> {
+    if request.uri().path().strip_prefix(base) != Some("/mcp") {
+        return request;
+    }
+    let Some(tenant) = tenant_for_token(request.headers(), tenants) else {
+        return request;
+    };
+    let path_and_query = match request.uri().query() {
+        Some(query) => format!("{}/mcp/{}?{}", base, tenant, query),
+        None => format!("{}/mcp/{}", base, tenant),
+    };
+    let mut parts = request.uri().clone().into_parts();
+    parts.path_and_query = path_and_query.parse().ok();
+    if let Ok(uri) = http::Uri::from_parts(parts) {
+        *request.uri_mut() = uri;
+    }
+    request
+}
+
+/// Headers whose values are credentials: admin tokens, API keys, tenant
+/// tokens and session cookies
+const SECRET_HEADERS: &[http::HeaderName] =
+    &[http::header::AUTHORIZATION, http::header::PROXY_AUTHORIZATION, http::header::COOKIE, http::header::SET_COOKIE];
+
+/// `headers` with credential values replaced, for logging
+pub fn redacted_headers(headers: &http::HeaderMap) -> http::HeaderMap {
+    let mut redacted = headers.clone();
+    for name in SECRET_HEADERS {
+        if let http::header::Entry::Occupied(mut entry) = redacted.entry(name) {
+            entry.insert(http::HeaderValue::from_static("[redacted]"));
+        }
+    }
+    redacted
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
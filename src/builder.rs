@@ -0,0 +1,274 @@
+// src/builder.rs
+// Programmatic construction of the server (or just its job board core)
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use nostr_sdk::Keys;
+
+use crate::config::ServerConfig;
+use crate::jobs::digest::DigestPeriod;
+use crate::jobs::rates::ExchangeRates;
+use crate::jobs::{JobsError, NostrJobsClient, QueryStrategy, RelayBackend};
+use crate::mcp_server::NostrJobsServer;
+
+/// Starts from `ServerConfig::default()` (no environment is read); use
+/// `config` to start from `ServerConfig::from_env()` instead. `build`
+/// fails on an empty relay or kind list, an invalid relay URL or an
+/// invalid author key.
+#[derive(Clone, Debug, Default)]
+pub struct NostrJobsServerBuilder {
+    config: ServerConfig,
+    keys: Option<Keys>,
+    backend: Option<Arc<dyn RelayBackend>>,
+    rates: Option<ExchangeRates>,
+}
+
+impl NostrJobsServerBuilder {
+    /// Replace every setting with `config`
+    pub fn config(mut self, config: ServerConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn relays<I, S>(mut self, relays: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.relays = relays.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Serve only cached and ingested listings, never contacting the relays
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.config.offline = offline;
+        self
+    }
+
+    pub fn kinds(mut self, kinds: impl IntoIterator<Item = u16>) -> Self {
+        self.config.kinds = kinds.into_iter().collect();
+        self
+    }
+
+    /// Only show listings from these authors (hex or npub)
+    pub fn authors<I, S>(mut self, authors: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.authors = authors.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Bearer tokens allowed to call admin tools and the admin HTTP API
+    pub fn admin_tokens<I, S>(mut self, tokens: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.admin_tokens = tokens.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn search_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.config.search_cache_ttl = ttl;
+        self
+    }
+
+    pub fn stats_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.config.stats_cache_ttl = ttl;
+        self
+    }
+
+    pub fn query_strategy(mut self, strategy: QueryStrategy) -> Self {
+        self.config.query_strategy = strategy;
+        self
+    }
+
+    pub fn max_concurrent_fetches(mut self, max: usize) -> Self {
+        self.config.max_concurrent_fetches = max;
+        self
+    }
+
+    pub fn max_queued_fetches(mut self, max: usize) -> Self {
+        self.config.max_queued_fetches = max;
+        self
+    }
+
+    /// Tool calls each API key may make per UTC day; 0 means no limit
+    pub fn daily_tool_call_quota(mut self, quota: u64) -> Self {
+        self.config.daily_tool_call_quota = quota;
+        self
+    }
+
+    /// Relay fetches each API key may cause per UTC day; 0 means no limit
+    pub fn daily_relay_fetch_quota(mut self, quota: u64) -> Self {
+        self.config.daily_relay_fetch_quota = quota;
+        self
+    }
+
+    /// Bearer tokens metered as API keys
+    pub fn api_keys<I, S>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.api_keys = keys.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// How often relays are polled for new listings while live clients are connected
+    pub fn live_poll_interval(mut self, interval: Duration) -> Self {
+        self.config.live_poll_interval = interval;
+        self
+    }
+
+    pub fn prefetch_top(mut self, top: usize) -> Self {
+        self.config.prefetch_top = top;
+        self
+    }
+
+    /// Archive and restart the metrics totals after each period; `None` keeps
+    /// them until `reset_metrics`
+    pub fn metrics_rotation(mut self, period: Option<DigestPeriod>) -> Self {
+        self.config.metrics_rotation = period;
+        self
+    }
+
+    pub fn metrics_history_size(mut self, size: usize) -> Self {
+        self.config.metrics_history_size = size.max(1);
+        self
+    }
+
+    /// Push metrics to the StatsD agent at `addr` (`host:port`) every `interval`
+    pub fn statsd(mut self, addr: impl Into<String>, interval: Duration) -> Self {
+        self.config.statsd_addr = Some(addr.into());
+        self.config.statsd_interval = interval;
+        self
+    }
+
+    /// Report panics and repeated relay/tool failures to a Sentry-compatible DSN
+    pub fn sentry_dsn(mut self, dsn: impl Into<String>) -> Self {
+        self.config.sentry_dsn = Some(dsn.into());
+        self
+    }
+
+    /// Failures in a row of one relay or tool before they are reported
+    pub fn error_report_threshold(mut self, failures: usize) -> Self {
+        self.config.error_report_threshold = failures.max(1);
+        self
+    }
+
+    pub fn statsd_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.config.statsd_prefix = prefix.into();
+        self
+    }
+
+    /// DogStatsD tags (`key:value`) added to every pushed metric
+    pub fn statsd_tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.statsd_tags = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn cache_warn_bytes(mut self, bytes: usize) -> Self {
+        self.config.cache_warn_bytes = bytes;
+        self
+    }
+
+    /// Purge cached listings posted more than `max_age` ago, and those
+    /// expired for more than `expired_grace`; `None` keeps them
+    pub fn retention(mut self, max_age: Option<Duration>, expired_grace: Option<Duration>) -> Self {
+        self.config.retention_max_age = max_age;
+        self.config.retention_expired_grace = expired_grace;
+        self
+    }
+
+    pub fn compaction_interval(mut self, interval: Duration) -> Self {
+        self.config.compaction_interval = interval;
+        self
+    }
+
+    /// Send at most one event per `interval` to each relay, retrying
+    /// rate-limited publishes up to `retries` times
+    pub fn publish_rate(mut self, interval: Duration, retries: u32) -> Self {
+        self.config.publish_interval = interval;
+        self.config.publish_retries = retries;
+        self
+    }
+
+    pub fn relay_timeout(mut self, timeout: Duration) -> Self {
+        self.config.relay_timeout = timeout;
+        self
+    }
+
+    /// Learn per-relay fetch deadlines from observed latency
+    pub fn adaptive_timeouts(mut self, enabled: bool) -> Self {
+        self.config.adaptive_timeouts = enabled;
+        self
+    }
+
+    pub fn search_timeout(mut self, timeout: Duration) -> Self {
+        self.config.search_timeout = timeout;
+        self
+    }
+
+    pub fn stats_timeout(mut self, timeout: Duration) -> Self {
+        self.config.stats_timeout = timeout;
+        self
+    }
+
+    /// Deadline for calls to `tool` (or `resource:<uri>`), replacing the
+    /// search and stats timeouts
+    pub fn tool_timeout(mut self, tool: impl Into<String>, timeout: Duration) -> Self {
+        self.config.tool_timeouts.insert(tool.into(), timeout);
+        self
+    }
+
+    /// Keys the relay client signs with, and `publish_draft` signs
+    /// listings with
+    pub fn signing_key(mut self, keys: Keys) -> Self {
+        self.keys = Some(keys);
+        self
+    }
+
+    /// Fetch and publish through `backend` instead of the nostr-sdk relay
+    /// pool (the signing key is then up to the backend)
+    pub fn backend(mut self, backend: Arc<dyn RelayBackend>) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    /// Convert sats/BTC salaries with `rates` instead of asking
+    /// `exchange_rate_url`
+    pub fn exchange_rates(mut self, rates: ExchangeRates) -> Self {
+        self.rates = Some(rates);
+        self
+    }
+
+    pub async fn build(self) -> Result<NostrJobsServer, JobsError> {
+        let config = self.config.clone();
+        let jobs = self.build_client().await?;
+        Ok(NostrJobsServer::from_parts(config, jobs))
+    }
+
+    /// Build only the job board core, for use without MCP
+    pub async fn build_client(self) -> Result<NostrJobsClient, JobsError> {
+        let client = match self.backend {
+            Some(backend) => NostrJobsClient::with_backend(self.config, backend).await?,
+            None => NostrJobsClient::connect(self.config, self.keys.clone()).await?,
+        };
+        let client = match self.keys {
+            Some(keys) => client.with_signer(keys),
+            None => client,
+        };
+        Ok(match self.rates {
+            Some(rates) => client.with_rates(rates),
+            None => client,
+        })
+    }
+}
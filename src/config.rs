@@ -0,0 +1,699 @@
+// src/config.rs
+// Server configuration loaded from environment variables (populated from .env by main.rs)
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::error_reporting::Dsn;
+use crate::i18n::Locale;
+use crate::jobs::QueryStrategy;
+use crate::jobs::digest::DigestPeriod;
+
+/// Environment file read at startup and again on every configuration reload
+pub const ENV_FILE: &str = ".env";
+
+const DEFAULT_RELAYS: &[&str] = &[
+    "wss://relay.damus.io",
+    "wss://relay.nostr.band",
+    "wss://nos.lol",
+];
+/// NIP-52 calendar event hashtags that make an event a career event
+const DEFAULT_CAREER_EVENT_TOPICS: &[&str] = &["hiring", "careers", "careerfair", "career-fair", "jobfair", "job-fair", "recruiting"];
+/// Hashtags that make a note someone's offer to work
+const DEFAULT_FOR_HIRE_TOPICS: &[&str] = &["forhire", "opentowork", "hireme", "lookingforwork"];
+/// NIP-99-style job listing kind used by this board
+pub const JOB_LISTING_KIND: u16 = 9993;
+const DEFAULT_SEARCH_CACHE_TTL_SECS: u64 = 60;
+const DEFAULT_STATS_CACHE_TTL_SECS: u64 = 120;
+const DEFAULT_RELAY_TIMEOUT_MS: u64 = 2000;
+const DEFAULT_SEARCH_TIMEOUT_MS: u64 = 2500;
+const DEFAULT_STATS_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_SHUTDOWN_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_READY_TIMEOUT_SECS: u64 = 15;
+const DEFAULT_SESSION_TTL_SECS: u64 = 3600;
+const DEFAULT_HTTP_KEEP_ALIVE_SECS: u64 = 30;
+const DEFAULT_HTTP_IDLE_TIMEOUT_SECS: u64 = 120;
+
+const DEFAULT_SLOW_QUERY_THRESHOLD_MS: u128 = 1000;
+const DEFAULT_SLOW_QUERY_LOG_SIZE: usize = 100;
+const DEFAULT_CACHE_WARN_BYTES: usize = 64 * 1024 * 1024;
+const DEFAULT_COMPACTION_INTERVAL_SECS: u64 = 3600;
+const DEFAULT_AUDIT_LOG_PATH: &str = "audit.log";
+const DEFAULT_ACCESS_LOG_MAX_BYTES: u64 = 10 * 1024 * 1024;
+const DEFAULT_ACCESS_LOG_KEEP: usize = 5;
+const DEFAULT_STATE_PATH: &str = "state.json";
+const DEFAULT_DISPLAY_CURRENCY: &str = "USD";
+const DEFAULT_EXCHANGE_RATE_URL: &str = "https://api.coingecko.com/api/v3/simple/price?ids=bitcoin&vs_currencies={fiat}";
+const DEFAULT_EXCHANGE_RATE_TTL_SECS: u64 = 600;
+/// NIP-90 text extraction
+const DEFAULT_DVM_KIND: u16 = 5000;
+const DEFAULT_DVM_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_INGEST_INTERVAL_SECS: u64 = 3600;
+const DEFAULT_PUBLISH_INTERVAL_MS: u64 = 1000;
+const DEFAULT_PUBLISH_RETRIES: u32 = 3;
+const DEFAULT_EXPIRY_REMINDER_DAYS: u64 = 3;
+const DEFAULT_APPLICATION_STALE_DAYS: u64 = 14;
+const DEFAULT_PREFETCH_TOP: usize = 10;
+const DEFAULT_MAX_CONCURRENT_FETCHES: usize = 32;
+const DEFAULT_MAX_QUEUED_FETCHES: usize = 256;
+const DEFAULT_MAX_IN_FLIGHT_CALLS: usize = 128;
+const DEFAULT_DIGEST_ARCHIVE_SIZE: usize = 30;
+const DEFAULT_METRICS_HISTORY_SIZE: usize = 30;
+const DEFAULT_STATSD_PREFIX: &str = "jobmcp";
+const DEFAULT_STATSD_INTERVAL_SECS: u64 = 10;
+const DEFAULT_ERROR_REPORT_THRESHOLD: usize = 3;
+const DEFAULT_LIVE_POLL_INTERVAL_SECS: u64 = 30;
+const DEFAULT_GEOCODER_URL: &str = "https://nominatim.openstreetmap.org/search?format=json&limit=1&q={query}";
+
+#[derive(Clone, Debug)]
+pub struct ServerConfig {
+    /// Tenant this configuration belongs to; `None` for the default board at `/mcp`
+    pub tenant: Option<String>,
+    /// Relays queried for job listings
+    pub relays: Vec<String>,
+    /// Never contact the relays; answer from the cached and ingested
+    /// listings only
+    pub offline: bool,
+    /// Event kinds treated as job listings
+    pub kinds: Vec<u16>,
+    /// Only show listings from these authors (hex or npub); empty allows everyone
+    pub authors: Vec<String>,
+    /// Additional job boards served from the same process
+    pub tenants: Vec<TenantConfig>,
+    /// How long cached search results are served before refetching
+    pub search_cache_ttl: Duration,
+    /// How long cached statistics samples are served before refetching
+    pub stats_cache_ttl: Duration,
+    /// Per-relay deadline for a single fetch; relays that send EOSE sooner finish sooner
+    pub relay_timeout: Duration,
+    /// Learn each relay's deadline from its observed latency (p95 plus
+    /// headroom, up to 3× `relay_timeout`) instead of using `relay_timeout`
+    pub adaptive_timeouts: bool,
+    /// Overall deadline for a search, job lookup or latest-listings fetch
+    pub search_timeout: Duration,
+    /// Overall deadline for paginating a statistics sample
+    pub stats_timeout: Duration,
+    /// Deadlines by tool name (or `resource:<uri>`, as in the metrics)
+    /// replacing `search_timeout` and `stats_timeout` for that call
+    pub tool_timeouts: HashMap<String, Duration>,
+    /// Tracing filter directive (e.g. `info,jobmcp=debug`); `None` keeps the startup filter
+    pub log_level: Option<String>,
+    /// Tool calls taking at least this long are recorded in the slow query log
+    pub slow_query_threshold_ms: u128,
+    /// Number of slow queries kept in memory (oldest are dropped first)
+    pub slow_query_log_size: usize,
+    /// How often the metrics totals are archived and restarted; `None` keeps
+    /// them until `reset_metrics`
+    pub metrics_rotation: Option<DigestPeriod>,
+    /// Number of archived metrics periods kept for `get_metrics_history`
+    pub metrics_history_size: usize,
+    /// StatsD (or DogStatsD) agent metrics are pushed to, as `host:port`;
+    /// `None` disables the emitter
+    pub statsd_addr: Option<String>,
+    /// Prefix of every pushed metric name (followed by the tenant, if any)
+    pub statsd_prefix: String,
+    /// DogStatsD tags (`key:value`) added to every pushed metric
+    pub statsd_tags: Vec<String>,
+    /// How often changes to the metrics are pushed
+    pub statsd_interval: Duration,
+    /// Sentry-compatible DSN panics and repeated failures are reported to;
+    /// `None` disables error reporting
+    pub sentry_dsn: Option<String>,
+    /// `environment` attached to reported errors
+    pub sentry_environment: Option<String>,
+    /// Failures in a row of one relay or tool before they are reported
+    pub error_report_threshold: usize,
+    /// Approximate cache size above which a warning is logged and reported
+    pub cache_warn_bytes: usize,
+    /// Cached listings posted longer ago than this are purged; `None` keeps them
+    pub retention_max_age: Option<Duration>,
+    /// Cached listings that expired (NIP-40) longer ago than this are
+    /// purged; `None` keeps them
+    pub retention_expired_grace: Option<Duration>,
+    /// How often the retention limits are applied to the cache
+    pub compaction_interval: Duration,
+    /// Number of most requested searches kept warm in the cache; 0 disables prefetching
+    pub prefetch_top: usize,
+    /// How a fetch spreads over the relays: all pooled and merged (default),
+    /// all at once and merged, the first answer, or one relay at a time
+    pub query_strategy: QueryStrategy,
+    /// Relay fetches (one REQ to one relay) allowed at once; others queue
+    pub max_concurrent_fetches: usize,
+    /// Relay fetches allowed to wait for a slot; beyond this requests are shed
+    pub max_queued_fetches: usize,
+    /// Tool calls handled at once; beyond this calls are shed. 0 means no limit
+    pub max_in_flight_calls: usize,
+    /// Tool calls each API key (bearer token) may make per UTC day; 0 means no limit
+    pub daily_tool_call_quota: u64,
+    /// Relay fetches each API key's tool calls may cause per UTC day; 0 means no limit
+    pub daily_relay_fetch_quota: u64,
+    /// Bearer tokens metered as API keys. Calls without one are metered per
+    /// client IP, or refused while a quota is set and this list isn't empty
+    pub api_keys: Vec<String>,
+    /// Append-only JSON Lines audit log of tool calls; `None` disables auditing
+    pub audit_log_path: Option<PathBuf>,
+    /// JSON Lines log of every HTTP request (method, path, status, duration,
+    /// session, client); `None` disables access logging
+    pub access_log_path: Option<PathBuf>,
+    /// Size at which the access log is rotated; 0 never rotates
+    pub access_log_max_bytes: u64,
+    /// Rotated access log files kept (`access.log.1` being the newest)
+    pub access_log_keep: usize,
+    /// JSON file holding bookmarks, saved searches and preferences; `None` keeps them in memory only
+    pub state_path: Option<PathBuf>,
+    /// How long an open job application goes without a status change
+    /// before it is flagged for a follow-up
+    pub application_stale_after: Duration,
+    /// Bearer tokens granting the admin role; empty means every session is admin
+    pub admin_tokens: Vec<String>,
+    /// Tools removed from the router (e.g. admin tools on a public mirror)
+    pub disabled_tools: Vec<String>,
+    /// Prompts removed from the router
+    pub disabled_prompts: Vec<String>,
+    /// How long shutdown waits for in-flight tool calls before giving up on them
+    pub shutdown_timeout: Duration,
+    /// How long startup waits for a relay to answer before reporting ready anyway
+    pub ready_timeout: Duration,
+    /// Redis URL for the shared session store; `None` keeps sessions in-process
+    pub redis_url: Option<String>,
+    /// How long an idle session stays resumable in Redis
+    pub session_ttl: Duration,
+    /// URL path prefix every endpoint is mounted under (e.g. `/nostr-jobs`);
+    /// empty serves them at the root
+    pub base_path: String,
+    /// Take the client address and scheme from `X-Forwarded-For` and
+    /// `X-Forwarded-Proto`; only safe behind a proxy that sets them
+    pub trust_forwarded_headers: bool,
+    /// Accept HTTP/2 (prior knowledge, h2c) alongside HTTP/1.1
+    pub http2: bool,
+    /// Interval of TCP keep-alive probes and HTTP/2 pings on client
+    /// connections; `None` sends neither
+    pub http_keep_alive: Option<Duration>,
+    /// How long an HTTP/1.1 connection may wait for its next request before
+    /// it is closed; `None` keeps idle connections open
+    pub http_idle_timeout: Option<Duration>,
+    /// Geocoder for place names in radius searches, `{query}` standing for
+    /// the place; `None` accepts only coordinates
+    pub geocoder_url: Option<String>,
+    /// Fiat currency salaries in sats or BTC are converted to, unless a
+    /// user's `currency` preference says otherwise
+    pub display_currency: String,
+    /// Language of the server instructions and prompt text, unless the
+    /// client asks for another
+    pub locale: Locale,
+    /// Strip emoji and box-drawing characters from tool results and
+    /// resources, unless a user's `plain_text` preference says otherwise
+    pub plain_text: bool,
+    /// CoinGecko-style BTC price endpoint, `{fiat}` standing for the
+    /// currency; `None` disables conversion
+    pub exchange_rate_url: Option<String>,
+    /// How long a fetched exchange rate is reused
+    pub exchange_rate_ttl: Duration,
+    /// NIP-90 job request kind used to extract skills from resumes (the
+    /// result is `kind + 1000`)
+    pub dvm_kind: u16,
+    /// DVM (hex or npub) resume jobs are addressed to; `None` lets any DVM answer
+    pub dvm_provider: Option<String>,
+    /// How long to wait for a DVM result
+    pub dvm_timeout: Duration,
+    /// External job boards to pull listings from: `rss:<url>`,
+    /// `greenhouse:<board token>` or `lever:<company>`
+    pub ingest_sources: Vec<String>,
+    /// How often the external boards are re-fetched
+    pub ingest_interval: Duration,
+    /// Secret key (hex or nsec) ingested listings are signed with; a fresh
+    /// key is generated on every start when unset
+    pub ingest_secret_key: Option<String>,
+    /// Also publish ingested listings to the relays, signed with
+    /// `ingest_secret_key`
+    pub bridge_publish: bool,
+    /// Secret key (hex or nsec) listings published from drafts are signed
+    /// with; `None` disables `publish_draft`
+    pub posting_secret_key: Option<String>,
+    /// Least time between two events sent to the same relay; publishes
+    /// beyond that rate wait their turn
+    pub publish_interval: Duration,
+    /// Retries of a publish a relay rate-limited or failed, backing off
+    /// from `publish_interval`
+    pub publish_retries: u32,
+    /// How far ahead `list_expiring_jobs` looks by default, and how long
+    /// before its expiration a posted listing is reminded about
+    pub expiry_reminder_window: Duration,
+    /// URL a JSON reminder is POSTed to for each posted listing about to
+    /// expire; `None` sends none
+    pub expiry_webhook_url: Option<String>,
+    /// Public key (hex or npub) sent a NIP-17 DM for each posted listing
+    /// about to expire; `None` sends none
+    pub expiry_reminder_recipient: Option<String>,
+    /// Hashtags (lowercase) a NIP-52 calendar event needs one of to be
+    /// served by `search_career_events` and `jobs://events`
+    pub career_event_topics: Vec<String>,
+    /// Hashtags (lowercase) that make a note a "for hire" post, counted as
+    /// supply by `market_balance`
+    pub for_hire_topics: Vec<String>,
+    /// How often a market digest is generated; `None` only builds one when asked
+    pub digest_period: Option<DigestPeriod>,
+    /// Number of past digests kept for `jobs://digest/{date}`
+    pub digest_archive_size: usize,
+    /// How often relays are polled for new listings while `/ws/jobs`
+    /// clients are connected
+    pub live_poll_interval: Duration,
+}
+
+/// A logical job board with its own relays, kinds and author allowlist,
+/// served at `/mcp/{name}` or selected on `/mcp` by one of its tokens.
+/// Empty lists inherit the top-level setting.
+#[derive(Clone, Debug)]
+pub struct TenantConfig {
+    pub name: String,
+    pub relays: Vec<String>,
+    pub kinds: Vec<u16>,
+    pub authors: Vec<String>,
+    /// Bearer tokens that route `/mcp` requests to this tenant
+    pub tokens: Vec<String>,
+}
+
+impl TenantConfig {
+    /// Read `TENANT_<NAME>_RELAYS`, `_KINDS`, `_AUTHORS` and `_TOKENS`
+    fn from_env(name: &str) -> Self {
+        let prefix = format!("TENANT_{}_", name.to_uppercase().replace('-', "_"));
+        let var = |suffix: &str| format!("{}{}", prefix, suffix);
+        Self {
+            name: name.to_string(),
+            relays: env_list(&var("RELAYS")),
+            kinds: env_kinds(&var("KINDS")),
+            authors: env_list(&var("AUTHORS")),
+            tokens: env_list(&var("TOKENS")),
+        }
+    }
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            tenant: None,
+            relays: DEFAULT_RELAYS.iter().map(|r| r.to_string()).collect(),
+            offline: false,
+            kinds: vec![JOB_LISTING_KIND],
+            authors: Vec::new(),
+            tenants: Vec::new(),
+            search_cache_ttl: Duration::from_secs(DEFAULT_SEARCH_CACHE_TTL_SECS),
+            stats_cache_ttl: Duration::from_secs(DEFAULT_STATS_CACHE_TTL_SECS),
+            relay_timeout: Duration::from_millis(DEFAULT_RELAY_TIMEOUT_MS),
+            adaptive_timeouts: false,
+            search_timeout: Duration::from_millis(DEFAULT_SEARCH_TIMEOUT_MS),
+            stats_timeout: Duration::from_secs(DEFAULT_STATS_TIMEOUT_SECS),
+            tool_timeouts: HashMap::new(),
+            log_level: None,
+            slow_query_threshold_ms: DEFAULT_SLOW_QUERY_THRESHOLD_MS,
+            slow_query_log_size: DEFAULT_SLOW_QUERY_LOG_SIZE,
+            metrics_rotation: None,
+            metrics_history_size: DEFAULT_METRICS_HISTORY_SIZE,
+            statsd_addr: None,
+            statsd_prefix: DEFAULT_STATSD_PREFIX.to_string(),
+            statsd_tags: Vec::new(),
+            statsd_interval: Duration::from_secs(DEFAULT_STATSD_INTERVAL_SECS),
+            sentry_dsn: None,
+            sentry_environment: None,
+            error_report_threshold: DEFAULT_ERROR_REPORT_THRESHOLD,
+            cache_warn_bytes: DEFAULT_CACHE_WARN_BYTES,
+            retention_max_age: None,
+            retention_expired_grace: None,
+            compaction_interval: Duration::from_secs(DEFAULT_COMPACTION_INTERVAL_SECS),
+            prefetch_top: DEFAULT_PREFETCH_TOP,
+            query_strategy: QueryStrategy::Pooled,
+            max_concurrent_fetches: DEFAULT_MAX_CONCURRENT_FETCHES,
+            max_queued_fetches: DEFAULT_MAX_QUEUED_FETCHES,
+            max_in_flight_calls: DEFAULT_MAX_IN_FLIGHT_CALLS,
+            daily_tool_call_quota: 0,
+            daily_relay_fetch_quota: 0,
+            api_keys: Vec::new(),
+            audit_log_path: Some(PathBuf::from(DEFAULT_AUDIT_LOG_PATH)),
+            access_log_path: None,
+            access_log_max_bytes: DEFAULT_ACCESS_LOG_MAX_BYTES,
+            access_log_keep: DEFAULT_ACCESS_LOG_KEEP,
+            state_path: Some(PathBuf::from(DEFAULT_STATE_PATH)),
+            application_stale_after: Duration::from_secs(DEFAULT_APPLICATION_STALE_DAYS * 86400),
+            admin_tokens: Vec::new(),
+            disabled_tools: Vec::new(),
+            disabled_prompts: Vec::new(),
+            shutdown_timeout: Duration::from_secs(DEFAULT_SHUTDOWN_TIMEOUT_SECS),
+            ready_timeout: Duration::from_secs(DEFAULT_READY_TIMEOUT_SECS),
+            redis_url: None,
+            session_ttl: Duration::from_secs(DEFAULT_SESSION_TTL_SECS),
+            base_path: String::new(),
+            trust_forwarded_headers: false,
+            http2: true,
+            http_keep_alive: Some(Duration::from_secs(DEFAULT_HTTP_KEEP_ALIVE_SECS)),
+            http_idle_timeout: Some(Duration::from_secs(DEFAULT_HTTP_IDLE_TIMEOUT_SECS)),
+            geocoder_url: Some(DEFAULT_GEOCODER_URL.to_string()),
+            display_currency: DEFAULT_DISPLAY_CURRENCY.to_string(),
+            locale: Locale::default(),
+            plain_text: false,
+            exchange_rate_url: Some(DEFAULT_EXCHANGE_RATE_URL.to_string()),
+            exchange_rate_ttl: Duration::from_secs(DEFAULT_EXCHANGE_RATE_TTL_SECS),
+            dvm_kind: DEFAULT_DVM_KIND,
+            dvm_provider: None,
+            dvm_timeout: Duration::from_secs(DEFAULT_DVM_TIMEOUT_SECS),
+            ingest_sources: Vec::new(),
+            ingest_interval: Duration::from_secs(DEFAULT_INGEST_INTERVAL_SECS),
+            ingest_secret_key: None,
+            bridge_publish: false,
+            posting_secret_key: None,
+            publish_interval: Duration::from_millis(DEFAULT_PUBLISH_INTERVAL_MS),
+            publish_retries: DEFAULT_PUBLISH_RETRIES,
+            expiry_reminder_window: Duration::from_secs(DEFAULT_EXPIRY_REMINDER_DAYS * 86400),
+            expiry_webhook_url: None,
+            expiry_reminder_recipient: None,
+            career_event_topics: DEFAULT_CAREER_EVENT_TOPICS.iter().map(|t| t.to_string()).collect(),
+            for_hire_topics: DEFAULT_FOR_HIRE_TOPICS.iter().map(|t| t.to_string()).collect(),
+            digest_period: Some(DigestPeriod::Daily),
+            digest_archive_size: DEFAULT_DIGEST_ARCHIVE_SIZE,
+            live_poll_interval: Duration::from_secs(DEFAULT_LIVE_POLL_INTERVAL_SECS),
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Read configuration from the environment, falling back to defaults for
+    /// unset or invalid values.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let relays = env_list("RELAYS");
+        let kinds = env_kinds("KINDS");
+        let career_event_topics: Vec<String> = env_list("CAREER_EVENT_TOPICS")
+            .into_iter()
+            .map(|t| t.trim_start_matches('#').to_lowercase())
+            .collect();
+        let for_hire_topics: Vec<String> = env_list("FOR_HIRE_TOPICS")
+            .into_iter()
+            .map(|t| t.trim_start_matches('#').to_lowercase())
+            .collect();
+        Self {
+            tenant: None,
+            relays: if relays.is_empty() { defaults.relays } else { relays },
+            offline: env_or("OFFLINE", defaults.offline),
+            kinds: if kinds.is_empty() { defaults.kinds } else { kinds },
+            authors: env_list("AUTHORS"),
+            tenants: env_list("TENANTS")
+                .iter()
+                .filter_map(|name| {
+                    let name = name.to_lowercase();
+                    if name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+                        Some(TenantConfig::from_env(&name))
+                    } else {
+                        tracing::warn!(tenant = %name, "invalid_tenant_name");
+                        None
+                    }
+                })
+                .collect(),
+            search_cache_ttl: Duration::from_secs(env_or(
+                "SEARCH_CACHE_TTL_SECS",
+                defaults.search_cache_ttl.as_secs(),
+            )),
+            stats_cache_ttl: Duration::from_secs(env_or(
+                "STATS_CACHE_TTL_SECS",
+                defaults.stats_cache_ttl.as_secs(),
+            )),
+            relay_timeout: Duration::from_millis(env_or(
+                "RELAY_TIMEOUT_MS",
+                defaults.relay_timeout.as_millis() as u64,
+            )),
+            adaptive_timeouts: env_or("ADAPTIVE_TIMEOUTS", defaults.adaptive_timeouts),
+            search_timeout: Duration::from_millis(env_or(
+                "SEARCH_TIMEOUT_MS",
+                defaults.search_timeout.as_millis() as u64,
+            )),
+            stats_timeout: Duration::from_secs(env_or(
+                "STATS_TIMEOUT_SECS",
+                defaults.stats_timeout.as_secs(),
+            )),
+            tool_timeouts: env_timeouts("TOOL_TIMEOUTS_MS"),
+            log_level: env_opt("LOG_LEVEL"),
+            slow_query_threshold_ms: env_or("SLOW_QUERY_THRESHOLD_MS", defaults.slow_query_threshold_ms),
+            slow_query_log_size: env_or("SLOW_QUERY_LOG_SIZE", defaults.slow_query_log_size),
+            metrics_rotation: match env_opt("METRICS_ROTATION") {
+                Some(value) if value.eq_ignore_ascii_case("off") => None,
+                Some(value) => value.parse().map(Some).unwrap_or_else(|e| {
+                    tracing::warn!(name = "METRICS_ROTATION", value = %value, error = %e, "invalid_config_value");
+                    defaults.metrics_rotation
+                }),
+                None => defaults.metrics_rotation,
+            },
+            metrics_history_size: env_or("METRICS_HISTORY_SIZE", defaults.metrics_history_size).max(1),
+            statsd_addr: env_opt("STATSD_ADDR"),
+            statsd_prefix: env_opt("STATSD_PREFIX").unwrap_or(defaults.statsd_prefix),
+            statsd_tags: env_list("STATSD_TAGS"),
+            statsd_interval: Duration::from_secs(
+                env_or("STATSD_INTERVAL_SECS", defaults.statsd_interval.as_secs()).max(1),
+            ),
+            sentry_dsn: env_opt("SENTRY_DSN").filter(|dsn| match Dsn::parse(dsn) {
+                Ok(_) => true,
+                Err(e) => {
+                    tracing::warn!(name = "SENTRY_DSN", error = %e, "invalid_config_value");
+                    false
+                }
+            }),
+            sentry_environment: env_opt("SENTRY_ENVIRONMENT"),
+            error_report_threshold: env_or("ERROR_REPORT_THRESHOLD", defaults.error_report_threshold).max(1),
+            cache_warn_bytes: env_or("CACHE_WARN_BYTES", defaults.cache_warn_bytes),
+            retention_max_age: env_days_or_off("RETENTION_MAX_AGE_DAYS", defaults.retention_max_age),
+            retention_expired_grace: env_days_or_off("RETENTION_EXPIRED_DAYS", defaults.retention_expired_grace),
+            compaction_interval: Duration::from_secs(
+                env_or("COMPACTION_INTERVAL_SECS", defaults.compaction_interval.as_secs()).max(1),
+            ),
+            prefetch_top: env_or("PREFETCH_TOP", defaults.prefetch_top),
+            query_strategy: match env_opt("RELAY_QUERY_STRATEGY") {
+                Some(value) => value.parse().unwrap_or_else(|e| {
+                    tracing::warn!(name = "RELAY_QUERY_STRATEGY", value = %value, error = %e, "invalid_config_value");
+                    defaults.query_strategy
+                }),
+                None => defaults.query_strategy,
+            },
+            max_concurrent_fetches: env_or("MAX_CONCURRENT_FETCHES", defaults.max_concurrent_fetches).max(1),
+            max_queued_fetches: env_or("MAX_QUEUED_FETCHES", defaults.max_queued_fetches),
+            max_in_flight_calls: env_or("MAX_IN_FLIGHT_CALLS", defaults.max_in_flight_calls),
+            daily_tool_call_quota: env_or("DAILY_TOOL_CALL_QUOTA", defaults.daily_tool_call_quota),
+            daily_relay_fetch_quota: env_or("DAILY_RELAY_FETCH_QUOTA", defaults.daily_relay_fetch_quota),
+            api_keys: env_list("API_KEYS"),
+            audit_log_path: env_path("AUDIT_LOG_PATH", defaults.audit_log_path),
+            access_log_path: env_path("ACCESS_LOG_PATH", defaults.access_log_path),
+            access_log_max_bytes: env_or("ACCESS_LOG_MAX_BYTES", defaults.access_log_max_bytes),
+            access_log_keep: env_or("ACCESS_LOG_KEEP", defaults.access_log_keep),
+            state_path: env_path("STATE_PATH", defaults.state_path),
+            application_stale_after: Duration::from_secs(
+                env_or("APPLICATION_STALE_DAYS", defaults.application_stale_after.as_secs() / 86400).max(1) * 86400,
+            ),
+            admin_tokens: env_list("ADMIN_TOKENS"),
+            disabled_tools: env_list("DISABLED_TOOLS"),
+            disabled_prompts: env_list("DISABLED_PROMPTS"),
+            shutdown_timeout: Duration::from_secs(env_or(
+                "SHUTDOWN_TIMEOUT_SECS",
+                defaults.shutdown_timeout.as_secs(),
+            )),
+            ready_timeout: Duration::from_secs(env_or("READY_TIMEOUT_SECS", defaults.ready_timeout.as_secs())),
+            redis_url: env_opt("REDIS_URL"),
+            session_ttl: Duration::from_secs(env_or(
+                "SESSION_TTL_SECS",
+                defaults.session_ttl.as_secs(),
+            )),
+            base_path: env_opt("BASE_PATH").map_or(defaults.base_path, |path| crate::proxy::normalize_base_path(&path)),
+            trust_forwarded_headers: env_or("TRUST_FORWARDED_HEADERS", defaults.trust_forwarded_headers),
+            http2: env_or("HTTP2", defaults.http2),
+            http_keep_alive: env_secs_or_off("HTTP_KEEP_ALIVE_SECS", defaults.http_keep_alive),
+            http_idle_timeout: env_secs_or_off("HTTP_IDLE_TIMEOUT_SECS", defaults.http_idle_timeout),
+            geocoder_url: env_url("GEOCODER_URL", defaults.geocoder_url),
+            display_currency: env_opt("DISPLAY_CURRENCY")
+                .map(|c| c.to_uppercase())
+                .unwrap_or(defaults.display_currency),
+            locale: env_or("LOCALE", defaults.locale),
+            plain_text: env_or("PLAIN_TEXT", defaults.plain_text),
+            exchange_rate_url: env_url("EXCHANGE_RATE_URL", defaults.exchange_rate_url),
+            exchange_rate_ttl: Duration::from_secs(env_or(
+                "EXCHANGE_RATE_TTL_SECS",
+                defaults.exchange_rate_ttl.as_secs(),
+            )),
+            dvm_kind: env_or("DVM_KIND", defaults.dvm_kind),
+            dvm_provider: env_opt("DVM_PROVIDER"),
+            dvm_timeout: Duration::from_secs(env_or("DVM_TIMEOUT_SECS", defaults.dvm_timeout.as_secs())),
+            ingest_sources: env_list("INGEST_SOURCES"),
+            ingest_interval: Duration::from_secs(env_or(
+                "INGEST_INTERVAL_SECS",
+                defaults.ingest_interval.as_secs(),
+            )),
+            ingest_secret_key: env_opt("INGEST_SECRET_KEY"),
+            bridge_publish: env_or("BRIDGE_PUBLISH", defaults.bridge_publish),
+            posting_secret_key: env_opt("POSTING_SECRET_KEY"),
+            publish_interval: Duration::from_millis(env_or(
+                "PUBLISH_INTERVAL_MS",
+                defaults.publish_interval.as_millis() as u64,
+            )),
+            publish_retries: env_or("PUBLISH_RETRIES", defaults.publish_retries),
+            expiry_reminder_window: Duration::from_secs(
+                env_or("EXPIRY_REMINDER_DAYS", defaults.expiry_reminder_window.as_secs() / 86400).max(1) * 86400,
+            ),
+            expiry_webhook_url: env_opt("EXPIRY_WEBHOOK_URL"),
+            expiry_reminder_recipient: env_opt("EXPIRY_REMINDER_NPUB"),
+            career_event_topics: if career_event_topics.is_empty() { defaults.career_event_topics } else { career_event_topics },
+            for_hire_topics: if for_hire_topics.is_empty() { defaults.for_hire_topics } else { for_hire_topics },
+            digest_period: match env_opt("DIGEST_PERIOD") {
+                Some(value) if value.eq_ignore_ascii_case("off") => None,
+                Some(value) => value.parse().map(Some).unwrap_or_else(|e| {
+                    tracing::warn!(name = "DIGEST_PERIOD", value = %value, error = %e, "invalid_config_value");
+                    defaults.digest_period
+                }),
+                None => defaults.digest_period,
+            },
+            digest_archive_size: env_or("DIGEST_ARCHIVE_SIZE", defaults.digest_archive_size).max(1),
+            live_poll_interval: Duration::from_secs(
+                env_or("LIVE_POLL_INTERVAL_SECS", defaults.live_poll_interval.as_secs()).max(1),
+            ),
+        }
+    }
+
+    /// Configuration for one tenant: this config with the tenant's relays,
+    /// kinds and authors swapped in, and per-tenant audit and state files.
+    pub fn for_tenant(&self, tenant: &TenantConfig) -> Self {
+        let mut config = self.clone();
+        config.tenant = Some(tenant.name.clone());
+        config.tenants = Vec::new();
+        if !tenant.relays.is_empty() {
+            config.relays = tenant.relays.clone();
+        }
+        if !tenant.kinds.is_empty() {
+            config.kinds = tenant.kinds.clone();
+        }
+        if !tenant.authors.is_empty() {
+            config.authors = tenant.authors.clone();
+        }
+        config.audit_log_path = self.audit_log_path.as_ref().map(|p| tenant_path(p, &tenant.name));
+        config.state_path = self.state_path.as_ref().map(|p| tenant_path(p, &tenant.name));
+        config
+    }
+
+    /// Re-read `ENV_FILE`, overriding variables loaded earlier, then rebuild
+    /// the configuration for `tenant` (or the default board). Variables removed
+    /// from the file keep their old value until restart.
+    pub fn reload(tenant: Option<&str>) -> Result<Self, String> {
+        if let Err(e) = dotenvy::from_path_override(ENV_FILE) {
+            tracing::warn!(file = ENV_FILE, error = %e, "config_reload_env_file_unreadable");
+        }
+        let config = Self::from_env();
+        match tenant {
+            None => Ok(config),
+            Some(name) => config
+                .tenants
+                .iter()
+                .find(|t| t.name == name)
+                .map(|t| config.for_tenant(t))
+                .ok_or_else(|| format!("tenant '{}' is no longer in TENANTS; restart to remove it", name)),
+        }
+    }
+}
+
+pub(crate) fn env_or<T: FromStr + std::fmt::Display>(name: &str, default: T) -> T {
+    match std::env::var(name) {
+        Ok(value) => value.trim().parse().unwrap_or_else(|_| {
+            tracing::warn!(name, value = %value, default = %default, "invalid_config_value");
+            default
+        }),
+        Err(_) => default,
+    }
+}
+
+/// Duration in seconds where 0 turns the feature off
+fn env_secs_or_off(name: &str, default: Option<Duration>) -> Option<Duration> {
+    match env_or(name, default.map_or(0, |d| d.as_secs())) {
+        0 => None,
+        secs => Some(Duration::from_secs(secs)),
+    }
+}
+
+/// Duration in days where 0 turns the feature off
+fn env_days_or_off(name: &str, default: Option<Duration>) -> Option<Duration> {
+    match env_or(name, default.map_or(0, |d| d.as_secs() / 86400)) {
+        0 => None,
+        days => Some(Duration::from_secs(days * 86400)),
+    }
+}
+
+/// Optional string; unset and blank are both `None`
+pub(crate) fn env_opt(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Comma-separated event kinds; invalid entries are logged and skipped
+fn env_kinds(name: &str) -> Vec<u16> {
+    env_list(name)
+        .iter()
+        .filter_map(|kind| {
+            kind.parse()
+                .inspect_err(|_| tracing::warn!(name, value = %kind, "invalid_config_value"))
+                .ok()
+        })
+        .collect()
+}
+
+/// Comma-separated `name=milliseconds` pairs; invalid entries are logged and skipped
+fn env_timeouts(name: &str) -> HashMap<String, Duration> {
+    env_list(name)
+        .iter()
+        .filter_map(|entry| {
+            let parsed = entry
+                .split_once('=')
+                .and_then(|(tool, ms)| Some((tool.trim(), ms.trim().parse::<u64>().ok()?)))
+                .filter(|(tool, ms)| !tool.is_empty() && *ms > 0);
+            if parsed.is_none() {
+                tracing::warn!(name, value = %entry, "invalid_config_value");
+            }
+            parsed.map(|(tool, ms)| (tool.to_string(), Duration::from_millis(ms)))
+        })
+        .collect()
+}
+
+/// `audit.log` -> `audit.<tenant>.log`
+fn tenant_path(path: &std::path::Path, tenant: &str) -> PathBuf {
+    let stem = path.file_stem().map(|s| s.to_string_lossy()).unwrap_or_default();
+    let name = match path.extension() {
+        Some(ext) => format!("{}.{}.{}", stem, tenant, ext.to_string_lossy()),
+        None => format!("{}.{}", stem, tenant),
+    };
+    path.with_file_name(name)
+}
+
+/// Optional file path; an empty value disables the feature
+fn env_path(name: &str, default: Option<PathBuf>) -> Option<PathBuf> {
+    match std::env::var(name) {
+        Ok(path) if path.trim().is_empty() => None,
+        Ok(path) => Some(PathBuf::from(path.trim())),
+        Err(_) => default,
+    }
+}
+
+/// Optional URL with a built-in default; an empty value disables the feature
+fn env_url(name: &str, default: Option<String>) -> Option<String> {
+    match std::env::var(name) {
+        Ok(url) if url.trim().is_empty() => None,
+        Ok(url) => Some(url.trim().to_string()),
+        Err(_) => default,
+    }
+}
+
+/// Comma-separated list; blank entries are dropped
+fn env_list(name: &str) -> Vec<String> {
+    std::env::var(name)
+        .map(|value| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
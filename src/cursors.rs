@@ -0,0 +1,53 @@
+// src/cursors.rs
+// Search cursors: the rest of a search cut short by its limit, behind a
+// `jobs://search/{cursor}` link the client can follow later
+
+use crate::jobs::JobQuery;
+use nostr_sdk::hashes::{Hash, sha256};
+use std::collections::VecDeque;
+
+/// Searches remembered for their `jobs://search/{cursor}` links
+pub(crate) const SEARCH_CURSOR_CAPACITY: usize = 256;
+
+/// A search and how many of its matches were already shown
+#[derive(Clone, Debug)]
+pub(crate) struct SearchCursor {
+    pub(crate) query: JobQuery,
+    pub(crate) offset: usize,
+    /// Matches the search found; every page fetches this many, so they
+    /// all page over the same cached fetch
+    pub(crate) matches: usize,
+    /// Suspicious listings are left out before paging
+    pub(crate) hide_suspicious: bool,
+}
+
+/// The most recent cursors, oldest dropped first. Cursors don't outlive
+/// the process; an unknown one means "search again".
+#[derive(Debug)]
+pub(crate) struct SearchCursors {
+    entries: VecDeque<(String, SearchCursor)>,
+    capacity: usize,
+    issued: u64,
+}
+
+impl SearchCursors {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self { entries: VecDeque::new(), capacity, issued: 0 }
+    }
+
+    /// Remember `cursor`, returning its id
+    pub(crate) fn insert(&mut self, cursor: SearchCursor) -> String {
+        self.issued += 1;
+        let seed = format!("{}:{}:{}:{:?}", self.issued, cursor.query.cache_key(), cursor.offset, std::time::SystemTime::now());
+        let id = sha256::Hash::hash(seed.as_bytes()).to_string()[..16].to_string();
+        while self.entries.len() >= self.capacity.max(1) {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((id.clone(), cursor));
+        id
+    }
+
+    pub(crate) fn get(&self, id: &str) -> Option<SearchCursor> {
+        self.entries.iter().find(|(entry, _)| entry == id).map(|(_, cursor)| cursor.clone())
+    }
+}
@@ -0,0 +1,297 @@
+// src/dashboard.rs
+// Self-contained HTML status page: relay health, cache stats, latency charts
+// and, for admins, recent calls, rendered from the same snapshot as
+// get_performance_metrics
+
+use std::fmt::Write;
+
+use nostr_sdk::Timestamp;
+use serde_json::Value;
+
+use crate::diagnostics::{AuditEntry, SlowQuery};
+use crate::jobs::RelayReport;
+use crate::mcp_server::format_bytes;
+
+/// Seconds between automatic reloads of the page
+const REFRESH_SECS: u64 = 10;
+const CHART_WIDTH: usize = 480;
+const CHART_HEIGHT: usize = 140;
+
+const STYLE: &str = "body{font:14px system-ui,sans-serif;margin:2em;color:#222;background:#fafafa}\
+h1{font-size:1.4em}h2{font-size:1.1em;margin-top:2em}\
+table{border-collapse:collapse;background:#fff}td,th{padding:.3em .8em;border-bottom:1px solid #ddd;text-align:left}\
+td.n{text-align:right;font-variant-numeric:tabular-nums}\
+.ok{color:#1a7f37}.bad{color:#cf222e}.muted{color:#666}\
+.cards{display:flex;flex-wrap:wrap;gap:1em}.card{background:#fff;border:1px solid #ddd;padding:.8em 1.2em;min-width:9em}\
+.card b{display:block;font-size:1.4em}svg{background:#fff;border:1px solid #ddd}\
+svg rect{fill:#4c8bf5}svg text{font-size:10px;fill:#444}";
+
+/// Everything the page shows, gathered by `NostrJobsServer::status_page`
+pub(crate) struct StatusSnapshot<'a> {
+    pub(crate) title: &'a str,
+    /// `PerformanceMetrics::to_json`
+    pub(crate) metrics: &'a Value,
+    pub(crate) relays: &'a [RelayReport],
+    pub(crate) relays_healthy: bool,
+    /// Recent and slow calls, shown only to admins
+    pub(crate) calls: Option<RecentCalls<'a>>,
+}
+
+/// What callers did lately, which the public page leaves out
+pub(crate) struct RecentCalls<'a> {
+    /// Most recent first
+    pub(crate) audited: &'a [AuditEntry],
+    /// Most recent first
+    pub(crate) slow: &'a [&'a SlowQuery],
+    pub(crate) slow_query_threshold_ms: u128,
+}
+
+pub(crate) fn render(snapshot: &StatusSnapshot) -> String {
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><meta http-equiv=\"refresh\" content=\"{}\">\
+         <title>{}</title><style>{}</style></head><body><h1>{}</h1><p class=\"muted\">Updated {} UTC · refreshes every {}s</p>",
+        REFRESH_SECS,
+        escape(snapshot.title),
+        STYLE,
+        escape(snapshot.title),
+        Timestamp::now().to_human_datetime(),
+        REFRESH_SECS
+    );
+
+    render_summary(&mut html, snapshot);
+    render_relays(&mut html, snapshot);
+    render_charts(&mut html, snapshot.metrics);
+    render_tools(&mut html, snapshot.metrics);
+    match &snapshot.calls {
+        Some(calls) => render_recent(&mut html, calls),
+        None => html.push_str("<p class=\"muted\">Recent and slow calls are shown with an admin bearer token.</p>"),
+    }
+
+    html.push_str("</body></html>");
+    html
+}
+
+fn render_summary(html: &mut String, snapshot: &StatusSnapshot) {
+    let m = snapshot.metrics;
+    let health = if snapshot.relays_healthy {
+        "<b class=\"ok\">healthy</b>"
+    } else {
+        "<b class=\"bad\">degraded</b>"
+    };
+    let _ = write!(
+        html,
+        "<div class=\"cards\">\
+         <div class=\"card\">Relays{}</div>\
+         <div class=\"card\">Requests<b>{}</b></div>\
+         <div class=\"card\">Cache hit rate<b>{:.1}%</b></div>\
+         <div class=\"card\">Cache entries<b>{}</b><span class=\"muted\">{} events, {}</span></div>\
+         <div class=\"card\">Time saved<b>{:.1}s</b></div>\
+         <div class=\"card\">Failed fetches<b>{}</b></div>\
+         <div class=\"card\">Shed requests<b>{}</b></div>\
+         </div>",
+        health,
+        m["requests"]["total"],
+        m["cache"]["hit_rate_pct"].as_f64().unwrap_or(0.0),
+        m["cache"]["entries"],
+        m["cache"]["events"],
+        format_bytes(m["cache"]["approx_bytes"].as_u64().unwrap_or(0) as usize),
+        m["cache"]["time_saved_ms"].as_u64().unwrap_or(0) as f64 / 1000.0,
+        m["requests"]["failed_fetches"],
+        m["requests"]["shed"],
+    );
+}
+
+fn render_relays(html: &mut String, snapshot: &StatusSnapshot) {
+    html.push_str(
+        "<h2>Relays</h2><table><tr><th>Relay</th><th>State</th><th>Latency</th><th>Fetches</th>\
+         <th>Failures</th><th>Avg fetch</th><th>p90</th><th>Events</th><th>Last event</th></tr>",
+    );
+    for relay in snapshot.relays {
+        let state = match &relay.connection {
+            Some(c) if c.connected => format!("<span class=\"ok\">{}</span>", escape(&c.status)),
+            Some(c) => format!("<span class=\"bad\">{}</span>", escape(&c.status)),
+            None => "<span class=\"muted\">unknown</span>".to_string(),
+        };
+        let latency = relay
+            .connection
+            .as_ref()
+            .and_then(|c| c.latency)
+            .map(|l| format!("{}ms", l.as_millis()))
+            .unwrap_or_else(|| "-".to_string());
+        let p90 = snapshot.metrics["relays"][&relay.url]["latency"]["p90_ms"]
+            .as_u64()
+            .map(|ms| format!("{}ms", ms))
+            .unwrap_or_else(|| "-".to_string());
+        let last_event = relay
+            .last_event
+            .map(|ago| format!("{}s ago", ago.as_secs()))
+            .unwrap_or_else(|| "never".to_string());
+        let _ = write!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td class=\"n\">{}</td><td class=\"n\">{}</td><td class=\"n\">{}</td>\
+             <td class=\"n\">{:.1}ms</td><td class=\"n\">{}</td><td class=\"n\">{}</td><td>{}</td></tr>",
+            escape(&relay.url),
+            state,
+            latency,
+            relay.fetches,
+            relay.failures,
+            relay.avg_fetch_ms,
+            p90,
+            relay.events_received,
+            last_event
+        );
+    }
+    html.push_str("</table>");
+}
+
+fn render_charts(html: &mut String, metrics: &Value) {
+    html.push_str("<h2>Latency</h2><div class=\"cards\">");
+    for (title, latency) in [
+        ("Relay fetches", &metrics["relay"]["latency"]),
+        ("Cache hits", &metrics["cache"]["latency"]),
+        ("Fetch queue wait", &metrics["queue"]["wait"]),
+    ] {
+        let _ = write!(
+            html,
+            "<div><p>{} <span class=\"muted\">p50 {}ms · p90 {}ms · p99 {}ms</span></p>{}</div>",
+            title,
+            latency["p50_ms"],
+            latency["p90_ms"],
+            latency["p99_ms"],
+            histogram_svg(latency)
+        );
+    }
+    html.push_str("</div>");
+}
+
+/// Bar chart of a latency histogram's buckets, one bar per bucket
+fn histogram_svg(latency: &Value) -> String {
+    let buckets: Vec<(&str, u64)> = latency["buckets"]
+        .as_array()
+        .map(|buckets| {
+            buckets
+                .iter()
+                .map(|b| (b["le_ms"].as_str().unwrap_or("?"), b["count"].as_u64().unwrap_or(0)))
+                .collect()
+        })
+        .unwrap_or_default();
+    if buckets.is_empty() {
+        return String::new();
+    }
+
+    let max = buckets.iter().map(|(_, n)| *n).max().unwrap_or(0).max(1);
+    let slot = CHART_WIDTH / buckets.len();
+    let plot_height = CHART_HEIGHT - 20;
+    let mut svg = format!(
+        "<svg width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" role=\"img\">",
+        CHART_WIDTH, CHART_HEIGHT, CHART_WIDTH, CHART_HEIGHT
+    );
+    for (i, (le, count)) in buckets.iter().enumerate() {
+        let height = (*count as usize * plot_height) / max as usize;
+        let x = i * slot;
+        let _ = write!(
+            svg,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"><title>≤{}ms: {}</title></rect>\
+             <text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>",
+            x + 2,
+            plot_height - height,
+            slot.saturating_sub(4),
+            height,
+            le,
+            count,
+            x + slot / 2,
+            CHART_HEIGHT - 6,
+            le
+        );
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+fn render_tools(html: &mut String, metrics: &Value) {
+    let Some(tools) = metrics["tools"].as_object().filter(|tools| !tools.is_empty()) else {
+        return;
+    };
+    let mut tools: Vec<(&String, &Value)> = tools.iter().collect();
+    tools.sort_by_key(|(_, m)| std::cmp::Reverse(m["calls"].as_u64().unwrap_or(0)));
+    let max = tools.first().and_then(|(_, m)| m["calls"].as_u64()).unwrap_or(0).max(1);
+
+    html.push_str(
+        "<h2>Tools</h2><table><tr><th>Tool</th><th>Calls</th><th></th><th>Failures</th>\
+         <th>Cache hits</th><th>p50</th><th>p90</th><th>p99</th></tr>",
+    );
+    for (tool, m) in tools {
+        let calls = m["calls"].as_u64().unwrap_or(0);
+        let _ = write!(
+            html,
+            "<tr><td>{}</td><td class=\"n\">{}</td>\
+             <td><svg width=\"120\" height=\"10\"><rect width=\"{}\" height=\"10\"/></svg></td>\
+             <td class=\"n\">{}</td><td class=\"n\">{}</td><td class=\"n\">{}ms</td><td class=\"n\">{}ms</td><td class=\"n\">{}ms</td></tr>",
+            escape(tool),
+            calls,
+            calls * 120 / max,
+            m["failures"],
+            m["cache_hits"],
+            m["latency"]["p50_ms"],
+            m["latency"]["p90_ms"],
+            m["latency"]["p99_ms"]
+        );
+    }
+    html.push_str("</table>");
+}
+
+fn render_recent(html: &mut String, calls: &RecentCalls) {
+    html.push_str("<h2>Recent calls</h2>");
+    if calls.audited.is_empty() {
+        html.push_str("<p class=\"muted\">None recorded (the audit log is empty or disabled).</p>");
+    } else {
+        html.push_str("<table><tr><th>At</th><th>Tool</th><th>Outcome</th><th>Latency</th></tr>");
+        for call in calls.audited {
+            let class = if call.outcome == "ok" { "ok" } else { "bad" };
+            let _ = write!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td class=\"{}\">{}</td><td class=\"n\">{}ms</td></tr>",
+                Timestamp::from(call.at).to_human_datetime(),
+                escape(&call.tool),
+                class,
+                escape(&call.outcome),
+                call.latency_ms
+            );
+        }
+        html.push_str("</table>");
+    }
+
+    let _ = write!(html, "<h2>Slow queries <span class=\"muted\">(≥ {}ms)</span></h2>", calls.slow_query_threshold_ms);
+    if calls.slow.is_empty() {
+        html.push_str("<p class=\"muted\">None.</p>");
+        return;
+    }
+    html.push_str("<table><tr><th>At</th><th>Tool</th><th>Duration</th><th>Cache</th><th>Relays</th></tr>");
+    for query in calls.slow {
+        let relays: Vec<String> = query
+            .trace
+            .relay_timings
+            .iter()
+            .map(|t| format!("{} {}ms{}", escape(&t.relay), t.duration_ms, if t.success { "" } else { " ✗" }))
+            .collect();
+        let _ = write!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td class=\"n\">{}ms</td><td>{}</td><td>{}</td></tr>",
+            query.at.to_human_datetime(),
+            escape(&query.tool),
+            query.duration_ms,
+            query.trace.cache.unwrap_or("not used"),
+            relays.join(", ")
+        );
+    }
+    html.push_str("</table>");
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
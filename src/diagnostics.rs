@@ -0,0 +1,290 @@
+// src/diagnostics.rs
+// Per-call tracing context, the slow query log, and the audit log
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+use nostr_sdk::Timestamp;
+use nostr_sdk::hashes::{Hash, sha256};
+use serde_json::Value;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::sync::Mutex;
+
+// ==================== Call Trace ====================
+
+/// What happened inside a single tool call, collected while it runs.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct CallTrace {
+    /// "hit", "stale", or "miss"; `None` when the tool didn't touch the cache
+    pub(crate) cache: Option<&'static str>,
+    pub(crate) relay_timings: Vec<RelayTiming>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct RelayTiming {
+    pub(crate) relay: String,
+    pub(crate) duration_ms: u128,
+    pub(crate) success: bool,
+}
+
+tokio::task_local! {
+    static CALL_TRACE: RefCell<CallTrace>;
+}
+
+/// Run `fut` with a fresh call trace, returning its output and the trace.
+pub(crate) async fn traced<F: Future>(fut: F) -> (F::Output, CallTrace) {
+    CALL_TRACE
+        .scope(RefCell::new(CallTrace::default()), async {
+            let output = fut.await;
+            let trace = CALL_TRACE.with(|t| t.take());
+            (output, trace)
+        })
+        .await
+}
+
+/// Note the cache decision for the current call (no-op outside `traced`).
+pub(crate) fn trace_cache(decision: &'static str) {
+    let _ = CALL_TRACE.try_with(|t| t.borrow_mut().cache = Some(decision));
+}
+
+/// Note one relay round-trip for the current call (no-op outside `traced`).
+pub(crate) fn trace_relay(relay: &str, duration_ms: u128, success: bool) {
+    let _ = CALL_TRACE.try_with(|t| {
+        t.borrow_mut().relay_timings.push(RelayTiming {
+            relay: relay.to_string(),
+            duration_ms,
+            success,
+        })
+    });
+}
+
+// ==================== Slow Query Log ====================
+
+#[derive(Clone, Debug)]
+pub(crate) struct SlowQuery {
+    pub(crate) at: Timestamp,
+    pub(crate) tool: String,
+    pub(crate) args: Value,
+    pub(crate) duration_ms: u128,
+    pub(crate) success: bool,
+    pub(crate) trace: CallTrace,
+}
+
+/// Bounded ring buffer of slow tool calls; the oldest entry is evicted first.
+#[derive(Debug)]
+pub(crate) struct SlowQueryLog {
+    entries: VecDeque<SlowQuery>,
+    capacity: usize,
+}
+
+impl SlowQueryLog {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity.min(1024)),
+            capacity,
+        }
+    }
+
+    /// Change the capacity, dropping the oldest entries if it shrank
+    pub(crate) fn resize(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.entries.len() > capacity {
+            self.entries.pop_front();
+        }
+    }
+
+    pub(crate) fn push(&mut self, entry: SlowQuery) {
+        if self.capacity == 0 {
+            return;
+        }
+        while self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    /// Most recent entries first
+    pub(crate) fn recent(&self, limit: usize) -> impl Iterator<Item = &SlowQuery> {
+        self.entries.iter().rev().take(limit)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl SlowQuery {
+    pub(crate) fn format(&self) -> String {
+        let relays = if self.trace.relay_timings.is_empty() {
+            "none".to_string()
+        } else {
+            self.trace
+                .relay_timings
+                .iter()
+                .map(|t| format!("{} {}ms{}", t.relay, t.duration_ms, if t.success { "" } else { " ✗" }))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        format!(
+            "🐢 {} — {}ms ({})\n   At: {}\n   Args: {}\n   Cache: {}\n   Relays: {}",
+            self.tool,
+            self.duration_ms,
+            if self.success { "ok" } else { "error" },
+            self.at.to_human_datetime(),
+            self.args,
+            self.trace.cache.unwrap_or("not used"),
+            relays
+        )
+    }
+}
+
+// ==================== Audit Log ====================
+
+/// One line of the append-only audit log (JSON Lines).
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct AuditEntry {
+    pub(crate) at: u64,
+    pub(crate) tool: String,
+    /// SHA-256 of the JSON arguments, so calls can be correlated without storing them
+    pub(crate) args_hash: String,
+    pub(crate) session_id: Option<String>,
+    /// Client IP, taken from `X-Forwarded-For` when forwarded headers are trusted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) client: Option<String>,
+    /// "ok", "tool_error" (the tool reported a failure), or "error" (the call was rejected)
+    pub(crate) outcome: String,
+    pub(crate) latency_ms: u64,
+}
+
+impl AuditEntry {
+    pub(crate) fn format(&self) -> String {
+        format!(
+            "{} | {} | {} | {}ms | session {} | client {} | args {}",
+            Timestamp::from(self.at).to_human_datetime(),
+            self.tool,
+            self.outcome,
+            self.latency_ms,
+            self.session_id.as_deref().unwrap_or("-"),
+            self.client.as_deref().unwrap_or("-"),
+            &self.args_hash[..self.args_hash.len().min(16)]
+        )
+    }
+}
+
+pub(crate) fn hash_args(args: &Value) -> String {
+    sha256::Hash::hash(args.to_string().as_bytes()).to_string()
+}
+
+/// How far back from the end of the audit log `AuditLog::recent` searches
+const AUDIT_SCAN_BYTES: u64 = 8 * 1024 * 1024;
+/// How much of the audit log is read at a time when searching it
+const AUDIT_CHUNK_BYTES: u64 = 64 * 1024;
+
+/// Append-only audit trail of tool invocations. Disabled when no path is set.
+#[derive(Debug)]
+pub(crate) struct AuditLog {
+    path: Option<PathBuf>,
+    write_lock: Mutex<()>,
+}
+
+impl AuditLog {
+    pub(crate) fn new(path: Option<PathBuf>) -> Self {
+        Self {
+            path,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.path.is_some()
+    }
+
+    pub(crate) async fn append(&self, entry: &AuditEntry) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let Ok(mut line) = serde_json::to_string(entry) else {
+            return;
+        };
+        line.push('\n');
+
+        let _guard = self.write_lock.lock().await;
+        let result = async {
+            let mut file = tokio::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .await?;
+            file.write_all(line.as_bytes()).await
+        }
+        .await;
+
+        if let Err(e) = result {
+            tracing::error!(path = %path.display(), error = %e, "audit_log_write_failed");
+        }
+    }
+
+    /// Most recent entries first, optionally restricted to one tool/session.
+    /// The file is read backwards in chunks and only its last
+    /// `AUDIT_SCAN_BYTES` are searched, so a long-lived log costs no more to
+    /// query than a fresh one.
+    pub(crate) async fn recent(
+        &self,
+        limit: usize,
+        tool: Option<&str>,
+        session_id: Option<&str>,
+    ) -> std::io::Result<Vec<AuditEntry>> {
+        let Some(path) = &self.path else {
+            return Ok(Vec::new());
+        };
+        let mut file = match tokio::fs::File::open(path).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let len = file.metadata().await?.len();
+        let floor = len.saturating_sub(AUDIT_SCAN_BYTES);
+
+        let mut entries = Vec::new();
+        // The start of the line the previous chunk ended in the middle of
+        let mut carry: Vec<u8> = Vec::new();
+        let mut end = len;
+        while end > floor && entries.len() < limit {
+            let start = end.saturating_sub(AUDIT_CHUNK_BYTES).max(floor);
+            let mut chunk = vec![0; (end - start) as usize];
+            file.seek(std::io::SeekFrom::Start(start)).await?;
+            file.read_exact(&mut chunk).await?;
+            chunk.append(&mut carry);
+            end = start;
+
+            // Unless this is the start of the file, the first line may be cut
+            // off: keep it for the next chunk (or drop it at the scan limit)
+            let complete = match start {
+                0 => &chunk[..],
+                _ => match chunk.iter().position(|&b| b == b'\n') {
+                    Some(newline) => {
+                        carry = chunk[..newline].to_vec();
+                        &chunk[newline + 1..]
+                    }
+                    None => {
+                        carry = chunk;
+                        continue;
+                    }
+                },
+            };
+            entries.extend(
+                complete
+                    .split(|&b| b == b'\n')
+                    .rev()
+                    .filter_map(|line| serde_json::from_slice::<AuditEntry>(line).ok())
+                    .filter(|e| tool.is_none_or(|t| e.tool == t))
+                    .filter(|e| session_id.is_none_or(|s| e.session_id.as_deref() == Some(s)))
+                    .take(limit - entries.len()),
+            );
+        }
+        Ok(entries)
+    }
+
+}
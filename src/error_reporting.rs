@@ -0,0 +1,214 @@
+// src/error_reporting.rs
+// Error reports to a Sentry-compatible service (Sentry, GlitchTip, ...):
+// panics, and relays or tools that keep failing, sent as envelopes to the
+// project in SENTRY_DSN. Nothing is sent while it is unset.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use nostr_sdk::Timestamp;
+use nostr_sdk::hashes::{Hash, sha256};
+use serde_json::{Value, json};
+use tokio::sync::mpsc;
+
+use crate::config::ServerConfig;
+
+const CLIENT_NAME: &str = concat!("jobmcp/", env!("CARGO_PKG_VERSION"));
+
+/// Where and how to send events, from a DSN such as
+/// `https://<public key>@o1.ingest.sentry.io/<project id>`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dsn {
+    pub public_key: String,
+    /// The project's envelope endpoint
+    pub envelope_url: String,
+}
+
+impl Dsn {
+    pub fn parse(dsn: &str) -> Result<Self, String> {
+        let url = reqwest::Url::parse(dsn).map_err(|e| format!("invalid DSN: {}", e))?;
+        let public_key = url.username();
+        if public_key.is_empty() {
+            return Err("invalid DSN: no public key before '@'".to_string());
+        }
+        let path = url.path().trim_end_matches('/');
+        let (prefix, project) = path.rsplit_once('/').unwrap_or(("", path));
+        if project.is_empty() {
+            return Err("invalid DSN: no project id".to_string());
+        }
+        let host = url.host_str().ok_or("invalid DSN: no host")?;
+        let port = url.port().map(|p| format!(":{}", p)).unwrap_or_default();
+        Ok(Self {
+            public_key: public_key.to_string(),
+            envelope_url: format!("{}://{}{}{}/api/{}/envelope/", url.scheme(), host, port, prefix, project),
+        })
+    }
+}
+
+/// Captures errors worth a human's attention and queues them for sending.
+/// Cheap to clone; clones share the failure counts and the queue.
+#[derive(Clone)]
+pub struct ErrorReporter {
+    config: Arc<std::sync::RwLock<Arc<ServerConfig>>>,
+    /// Failures in a row by `kind:name`
+    streaks: Arc<Mutex<HashMap<String, usize>>>,
+    outbox: mpsc::UnboundedSender<Value>,
+}
+
+impl std::fmt::Debug for ErrorReporter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ErrorReporter").finish_non_exhaustive()
+    }
+}
+
+impl ErrorReporter {
+    /// A reporter following the (reloadable) config behind `config`, and
+    /// the task sending its events
+    pub(crate) fn new(config: Arc<std::sync::RwLock<Arc<ServerConfig>>>) -> Self {
+        let (outbox, queue) = mpsc::unbounded_channel();
+        let reporter = Self { config, streaks: Arc::default(), outbox };
+        tokio::spawn(send_loop(reporter.config.clone(), queue));
+        reporter
+    }
+
+    fn config(&self) -> Arc<ServerConfig> {
+        self.config.read().expect("config lock poisoned").clone()
+    }
+
+    /// Note a failure of the `kind` (`relay`, `tool`) called `name`; the
+    /// `error_report_threshold`-th in a row is reported, with `context`
+    pub fn failed(&self, kind: &str, name: &str, error: &str, context: Value) {
+        let config = self.config();
+        if config.sentry_dsn.is_none() {
+            return;
+        }
+        let streak = {
+            let mut streaks = self.streaks.lock().expect("streaks lock poisoned");
+            let streak = streaks.entry(format!("{}:{}", kind, name)).or_default();
+            *streak += 1;
+            *streak
+        };
+        if streak != config.error_report_threshold.max(1) {
+            return;
+        }
+        let mut extra = context;
+        if let Some(fields) = extra.as_object_mut() {
+            fields.insert("consecutive_failures".to_string(), json!(streak));
+        }
+        self.capture(
+            "error",
+            &format!("{} {} failing repeatedly: {}", kind, name, error),
+            json!({ "kind": kind, kind: name }),
+            extra,
+        );
+    }
+
+    /// Note a success, ending any failure streak of `kind` `name`
+    pub fn succeeded(&self, kind: &str, name: &str) {
+        if let Ok(mut streaks) = self.streaks.lock() {
+            streaks.remove(&format!("{}:{}", kind, name));
+        }
+    }
+
+    /// Report a panic, with where it happened
+    pub fn panicked(&self, info: &std::panic::PanicHookInfo<'_>) {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panic".to_string());
+        let location = info.location().map(|l| format!("{}:{}", l.file(), l.line()));
+        let thread = std::thread::current().name().unwrap_or("unnamed").to_string();
+        self.capture(
+            "fatal",
+            &format!("panic: {}", message),
+            json!({ "kind": "panic" }),
+            json!({ "location": location, "thread": thread }),
+        );
+    }
+
+    fn capture(&self, level: &str, message: &str, mut tags: Value, extra: Value) {
+        let config = self.config();
+        if config.sentry_dsn.is_none() {
+            return;
+        }
+        if let (Some(tenant), Some(fields)) = (&config.tenant, tags.as_object_mut()) {
+            fields.insert("tenant".to_string(), json!(tenant));
+        }
+        let event = json!({
+            "event_id": event_id(message),
+            "timestamp": Timestamp::now().as_secs(),
+            "platform": "other",
+            "level": level,
+            "logger": "jobmcp",
+            "release": CLIENT_NAME.replace('/', "@"),
+            "environment": config.sentry_environment,
+            "message": { "formatted": message },
+            "tags": tags,
+            "extra": extra,
+        });
+        // Closed only when the send task is gone, i.e. at shutdown
+        let _ = self.outbox.send(event);
+    }
+}
+
+/// Report panics anywhere in the process through `reporter`, after the
+/// previous hook (which prints them) has run
+pub fn install_panic_hook(reporter: ErrorReporter) {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        previous(info);
+        reporter.panicked(info);
+    }));
+}
+
+/// A unique 32-hex-digit id
+fn event_id(message: &str) -> String {
+    static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+    let seed = format!(
+        "{}:{}:{:?}:{}",
+        std::process::id(),
+        SEQUENCE.fetch_add(1, Ordering::Relaxed),
+        std::time::SystemTime::now(),
+        message
+    );
+    sha256::Hash::hash(seed.as_bytes()).to_string()[..32].to_string()
+}
+
+async fn send_loop(config: Arc<std::sync::RwLock<Arc<ServerConfig>>>, mut queue: mpsc::UnboundedReceiver<Value>) {
+    let http = reqwest::Client::new();
+    while let Some(event) = queue.recv().await {
+        let Some(dsn) = config.read().expect("config lock poisoned").sentry_dsn.clone() else {
+            continue;
+        };
+        let dsn_config = match Dsn::parse(&dsn) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                tracing::warn!(error = %e, "error_report_dropped");
+                continue;
+            }
+        };
+        let envelope = format!(
+            "{}\n{}\n{}\n",
+            json!({ "event_id": event["event_id"], "dsn": dsn }),
+            json!({ "type": "event" }),
+            event
+        );
+        let sent = http
+            .post(&dsn_config.envelope_url)
+            .header(
+                "X-Sentry-Auth",
+                format!("Sentry sentry_version=7, sentry_client={}, sentry_key={}", CLIENT_NAME, dsn_config.public_key),
+            )
+            .header(reqwest::header::CONTENT_TYPE, "application/x-sentry-envelope")
+            .body(envelope)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        if let Err(e) = sent {
+            tracing::warn!(error = %e, event_id = %event["event_id"], "error_report_failed");
+        }
+    }
+}
@@ -0,0 +1,357 @@
+// src/graphql.rs
+// Read-only GraphQL schema over the job index (jobs, companies, skills,
+// stats) at `/graphql`, for frontends needing queries the MCP tools can't express
+
+use async_graphql::{Context, EmptyMutation, EmptySubscription, ErrorExtensions, Object, Schema, SimpleObject};
+use axum::Json;
+use axum::extract::State;
+use axum::routing::{MethodRouter, get};
+use http::request::Parts;
+use nostr_sdk::ToBech32;
+
+use crate::auth;
+use crate::diagnostics::traced;
+use crate::jobs::{DEFAULT_SEARCH_LIMIT, DEFAULT_STATS_SAMPLE_SIZE, JobListing, JobQuery, JobStats, JobsError, SalaryRange};
+use crate::mcp_server::{NostrJobsServer, OneOrMany, SearchJobsArgs};
+use crate::proxy;
+
+/// Most jobs, companies or skills one field returns
+const MAX_LIMIT: usize = 100;
+/// Deepest selection accepted; the schema itself is only four levels deep
+const MAX_DEPTH: usize = 8;
+/// Most work one query may ask for: each selected field costs 1, `jobs`
+/// costs its fields once per listing, and sampling statistics `STATS_COST`
+const MAX_COMPLEXITY: usize = 2000;
+/// Cost of a field that samples recent listings (`stats`, `companies`, `skills`)
+const STATS_COST: usize = 200;
+
+pub type JobsSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// The schema, resolving against `server`'s relays and cache
+pub fn schema(server: NostrJobsServer) -> JobsSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(server)
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+}
+
+#[derive(Clone)]
+struct GraphqlState {
+    schema: JobsSchema,
+    server: NostrJobsServer,
+}
+
+/// `POST` executes a GraphQL request; `GET` returns the schema as SDL
+pub fn route(server: NostrJobsServer) -> MethodRouter {
+    get(sdl).post(execute).with_state(GraphqlState { schema: schema(server.clone()), server })
+}
+
+/// Queries are admitted like tool calls: shed when the server is busy and
+/// counted toward the caller's daily quotas
+async fn execute(State(state): State<GraphqlState>, parts: Parts, Json(request): Json<async_graphql::Request>) -> Json<async_graphql::Response> {
+    let config = state.server.config();
+    let client = proxy::client_ip(&parts, config.trust_forwarded_headers);
+    let admin = auth::is_admin_request(&parts.headers, &config.admin_tokens);
+    let token = auth::header_bearer_token(&parts.headers);
+    let (_in_flight, api_key) = match state.server.admit("graphql", token, client, admin).await {
+        Ok(admitted) => admitted,
+        Err(e) => {
+            let error = with_details(e.message.to_string(), e.data.as_ref());
+            return Json(async_graphql::Response::from_errors(vec![error.into_server_error(Default::default())]));
+        }
+    };
+    let (response, trace) = traced(state.schema.execute(request)).await;
+    if let Some(key) = &api_key {
+        state.server.record_usage(key, trace.relay_timings.len() as u64).await;
+    }
+    Json(response)
+}
+
+async fn sdl(State(state): State<GraphqlState>) -> String {
+    state.schema.sdl()
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Listings matching every given filter (each list matches any of its values), newest first
+    #[allow(clippy::too_many_arguments)]
+    #[graphql(complexity = "clamp_limit(limit) * child_complexity")]
+    async fn jobs(
+        &self,
+        ctx: &Context<'_>,
+        company: Option<Vec<String>>,
+        skill: Option<Vec<String>>,
+        employment_type: Option<Vec<String>>,
+        #[graphql(desc = "Hex or npub")] author: Option<String>,
+        #[graphql(desc = "Also match values within a small edit distance", default = false)] fuzzy: bool,
+        #[graphql(default_with = "DEFAULT_SEARCH_LIMIT as i32")] limit: i32,
+    ) -> async_graphql::Result<Vec<Job>> {
+        let server = ctx.data::<NostrJobsServer>()?;
+        let args = SearchJobsArgs {
+            company: company.map(OneOrMany::Many),
+            skill: skill.map(OneOrMany::Many),
+            employment_type: employment_type.map(OneOrMany::Many),
+            author,
+            near: None,
+            lat: None,
+            lon: None,
+            radius_km: None,
+            min_salary: None,
+            limit: clamp_limit(limit),
+            fuzzy,
+            max_edit_distance: None,
+            hide_suspicious: false,
+            kinds: None,
+        };
+        let query = JobQuery::try_from(&args).map_err(graphql_error)?;
+        let results = server.jobs().search(&query).await.map_err(graphql_error)?;
+        Ok(results.listings.into_iter().map(|listing| Job::new(listing, server)).collect())
+    }
+
+    /// A listing by its job id or event id
+    async fn job(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<Option<Job>> {
+        let server = ctx.data::<NostrJobsServer>()?;
+        match server.jobs().get(&id).await {
+            Ok((listing, _)) => Ok(Some(Job::new(listing, server))),
+            Err(JobsError::NotFound { .. }) => Ok(None),
+            Err(e) => Err(graphql_error(e)),
+        }
+    }
+
+    /// Companies by number of listings among the `sampleSize` most recent
+    #[graphql(complexity = "STATS_COST + child_complexity")]
+    async fn companies(
+        &self,
+        ctx: &Context<'_>,
+        sample_size: Option<i32>,
+        #[graphql(default = 20)] limit: i32,
+    ) -> async_graphql::Result<Vec<Count>> {
+        let stats = sample_stats(ctx, sample_size).await?.0;
+        Ok(Count::top(&stats.companies, clamp_limit(limit)))
+    }
+
+    /// Skills by number of listings among the `sampleSize` most recent
+    #[graphql(complexity = "STATS_COST + child_complexity")]
+    async fn skills(
+        &self,
+        ctx: &Context<'_>,
+        sample_size: Option<i32>,
+        #[graphql(default = 20)] limit: i32,
+    ) -> async_graphql::Result<Vec<Count>> {
+        let stats = sample_stats(ctx, sample_size).await?.0;
+        Ok(Count::top(&stats.skills, clamp_limit(limit)))
+    }
+
+    /// Market statistics over the `sampleSize` most recent listings (default 500, max 5000)
+    #[graphql(complexity = "STATS_COST + child_complexity")]
+    async fn stats(&self, ctx: &Context<'_>, sample_size: Option<i32>) -> async_graphql::Result<Stats> {
+        let (stats, truncated) = sample_stats(ctx, sample_size).await?;
+        let mut median_salaries: Vec<MedianSalary> = stats
+            .median_salaries
+            .iter()
+            .map(|(group, median)| MedianSalary { group: group.clone(), median: *median })
+            .collect();
+        median_salaries.sort_by(|a, b| a.group.cmp(&b.group));
+        Ok(Stats {
+            listings: stats.listings as i32,
+            truncated,
+            employment_types: Count::top(&stats.employment_types, usize::MAX),
+            companies: Count::top(&stats.companies, usize::MAX),
+            skills: Count::top(&stats.skills, usize::MAX),
+            median_salaries,
+            salary_disclosed: stats.salary_disclosed as i32,
+            salary_transparency: stats
+                .salary_transparency
+                .iter()
+                .map(|t| SalaryTransparency {
+                    company: t.company.clone(),
+                    listings: t.listings as i32,
+                    complete: t.complete as i32,
+                    partial: t.partial as i32,
+                    score: t.score(),
+                })
+                .collect(),
+        })
+    }
+}
+
+/// A job listing
+pub struct Job {
+    listing: JobListing,
+    url: String,
+}
+
+impl Job {
+    fn new(listing: JobListing, server: &NostrJobsServer) -> Self {
+        let url = listing.njump_url(&server.config().relays);
+        Self { listing, url }
+    }
+}
+
+#[Object]
+impl Job {
+    /// The `job-id` tag, falling back to the event id
+    async fn job_id(&self) -> &str {
+        &self.listing.job_id
+    }
+
+    async fn event_id(&self) -> String {
+        self.listing.event.id.to_hex()
+    }
+
+    async fn title(&self) -> Option<&str> {
+        self.listing.title.as_deref()
+    }
+
+    async fn company(&self) -> Option<&str> {
+        self.listing.company.as_deref()
+    }
+
+    async fn location(&self) -> Option<&str> {
+        self.listing.location.as_deref()
+    }
+
+    async fn employment_types(&self) -> &[String] {
+        &self.listing.employment_types
+    }
+
+    async fn skills(&self) -> &[String] {
+        &self.listing.skills
+    }
+
+    async fn salary(&self) -> Option<Salary> {
+        self.listing.salary.as_ref().map(Salary::from)
+    }
+
+    /// Full listing text
+    async fn description(&self) -> &str {
+        &self.listing.description
+    }
+
+    /// Poster's public key, as npub
+    async fn author(&self) -> String {
+        self.listing.author.to_bech32().unwrap_or_else(|_| self.listing.author.to_hex())
+    }
+
+    /// Unix timestamp
+    async fn posted_at(&self) -> u64 {
+        self.listing.created_at.as_secs()
+    }
+
+    /// Unix timestamp of the NIP-40 expiration, if any
+    async fn expires_at(&self) -> Option<u64> {
+        self.listing.expires_at.map(|at| at.as_secs())
+    }
+
+    /// Link to the listing on njump.me
+    async fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct Salary {
+    pub min: f64,
+    pub max: f64,
+    pub currency: String,
+    pub period: String,
+}
+
+impl From<&SalaryRange> for Salary {
+    fn from(salary: &SalaryRange) -> Self {
+        Self {
+            min: salary.min,
+            max: salary.max,
+            currency: salary.currency.clone(),
+            period: salary.period.clone(),
+        }
+    }
+}
+
+/// A tag value and the number of listings carrying it
+#[derive(SimpleObject)]
+pub struct Count {
+    pub name: String,
+    pub count: i32,
+}
+
+impl Count {
+    /// The `limit` most common, ties by name
+    fn top(counts: &std::collections::HashMap<String, usize>, limit: usize) -> Vec<Count> {
+        let mut counts: Vec<(&String, &usize)> = counts.iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        counts
+            .into_iter()
+            .take(limit)
+            .map(|(name, count)| Count { name: name.clone(), count: *count as i32 })
+            .collect()
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct MedianSalary {
+    /// `CURRENCY/period`
+    pub group: String,
+    pub median: f64,
+}
+
+#[derive(SimpleObject)]
+pub struct Stats {
+    /// Number of listings analyzed
+    pub listings: i32,
+    /// The relays were slow and the sample is smaller than asked for
+    pub truncated: bool,
+    pub employment_types: Vec<Count>,
+    pub companies: Vec<Count>,
+    pub skills: Vec<Count>,
+    pub median_salaries: Vec<MedianSalary>,
+    /// Listings with a complete salary tag
+    pub salary_disclosed: i32,
+    /// Per company, most transparent first
+    pub salary_transparency: Vec<SalaryTransparency>,
+}
+
+/// How openly one company states salaries
+#[derive(SimpleObject)]
+pub struct SalaryTransparency {
+    pub company: String,
+    pub listings: i32,
+    /// Listings with a minimum, maximum, currency and period
+    pub complete: i32,
+    /// Listings with a salary tag missing some of them
+    pub partial: i32,
+    /// Share of listings with a complete salary, 0 to 1
+    pub score: f64,
+}
+
+async fn sample_stats(ctx: &Context<'_>, sample_size: Option<i32>) -> async_graphql::Result<(JobStats, bool)> {
+    let server = ctx.data::<NostrJobsServer>()?;
+    let sample_size = sample_size.map_or(DEFAULT_STATS_SAMPLE_SIZE, |n| n.max(1) as usize);
+    let sample = server.jobs().sample(sample_size, None).await.map_err(graphql_error)?;
+    Ok((sample.stats(), sample.truncated))
+}
+
+fn clamp_limit(limit: i32) -> usize {
+    (limit.max(1) as usize).min(MAX_LIMIT)
+}
+
+/// The error message, with the `JobsError` code and details as extensions
+fn graphql_error(e: JobsError) -> async_graphql::Error {
+    with_details(e.to_string(), Some(&e.to_json()))
+}
+
+/// `message` with `details` (a `JobsError::to_json`) as extensions
+fn with_details(message: String, details: Option<&serde_json::Value>) -> async_graphql::Error {
+    async_graphql::Error::new(message).extend_with(|_, extensions| {
+        if let Some(details) = details.and_then(|d| d.as_object()) {
+            for (key, value) in details {
+                if let Ok(value) = async_graphql::Value::from_json(value.clone()) {
+                    extensions.set(if key == "error" { "code" } else { key.as_str() }, value);
+                }
+            }
+        }
+    })
+}
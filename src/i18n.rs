@@ -0,0 +1,962 @@
+// src/i18n.rs
+// Localized text per language: the server instructions, prompt templates
+// and response strings (errors, cache banners, report headers), English
+// being the fallback for anything not translated
+
+use std::str::FromStr;
+
+/// A language text can be served in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+    De,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 3] = [Locale::En, Locale::Es, Locale::De];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+            Locale::De => "de",
+        }
+    }
+
+    /// The most preferred supported language of an `Accept-Language` header
+    /// (`de-CH, fr;q=0.9, en;q=0.8`), by quality; `None` if it names none
+    pub fn from_accept_language(header: &str) -> Option<Locale> {
+        let mut ranges: Vec<(&str, f32)> = header
+            .split(',')
+            .filter_map(|range| {
+                let mut parts = range.split(';').map(str::trim);
+                let tag = parts.next().filter(|t| !t.is_empty() && *t != "*")?;
+                let quality = parts
+                    .find_map(|p| p.strip_prefix("q="))
+                    .map_or(Some(1.0), |q| q.parse::<f32>().ok())?;
+                (quality > 0.0).then_some((tag, quality))
+            })
+            .collect();
+        // Stable, so equal qualities keep the header's order
+        ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranges.into_iter().find_map(|(tag, _)| tag.parse().ok())
+    }
+
+    fn table(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => EN,
+            Locale::Es => ES,
+            Locale::De => DE,
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    /// A language tag or POSIX locale (`es`, `es-MX`, `de_DE.UTF-8`); only
+    /// the language counts
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let language = value.trim().split(['-', '_', '.']).next().unwrap_or_default().to_lowercase();
+        Locale::ALL.into_iter().find(|l| l.as_str() == language).ok_or_else(|| {
+            format!("unsupported locale '{}'; use {}", value.trim(), Locale::ALL.map(|l| l.as_str()).join(", "))
+        })
+    }
+}
+
+impl std::fmt::Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The text of `key` in `locale`, else in English, else the key itself
+pub fn text(locale: Locale, key: &str) -> &str {
+    let lookup = |table: &'static [(&'static str, &'static str)]| table.iter().find(|(k, _)| *k == key).map(|(_, v)| *v);
+    lookup(locale.table()).or_else(|| lookup(EN)).unwrap_or(key)
+}
+
+/// `text` with each `{name}` placeholder replaced by its value
+pub fn text_with(locale: Locale, key: &str, values: &[(&str, &dyn std::fmt::Display)]) -> String {
+    values
+        .iter()
+        .fold(text(locale, key).to_string(), |text, (name, value)| text.replace(&format!("{{{}}}", name), &value.to_string()))
+}
+
+/// English keys `locale` has no text for, falling back to English
+pub fn untranslated(locale: Locale) -> Vec<&'static str> {
+    EN.iter().map(|(k, _)| *k).filter(|k| !locale.table().iter().any(|(l, _)| l == k)).collect()
+}
+
+/// Entries of each instructions section, described under `info.<name>`
+/// with `name` the first one listed
+const INSTRUCTION_TOOLS: &[&str] = &[
+    "search_jobs",
+    "get_job_details",
+    "get_raw_event",
+    "bookmark_job / remove_bookmark",
+    "set_application_status / list_applications",
+    "schedule_interview / list_upcoming_interviews",
+    "search_career_events",
+    "recommend_jobs",
+    "save_search / run_saved_search / delete_saved_search",
+    "set_preferences / get_preferences",
+    "set_preference / get_saved_state",
+    "explain_query",
+    "get_performance_metrics",
+    "get_slow_queries",
+    "get_audit_log",
+    "get_api_key_usage",
+    "clear_cache",
+    "cache_status",
+    "reset_metrics",
+    "get_metrics_history",
+    "set_tool_enabled",
+    "reload_config",
+    "set_query_strategy",
+    "export_snapshot / import_snapshot",
+    "list_relays",
+    "get_stats",
+    "list_tag_vocabulary",
+    "get_company_profile",
+    "skill_cooccurrence",
+    "market_balance",
+    "compare_stats",
+];
+const INSTRUCTION_PROMPTS: &[&str] = &["job_search_assistant", "analyze_job_market", "hiring_trends_forecast"];
+const INSTRUCTION_RESOURCES: &[&str] = &[
+    "jobs://latest",
+    "jobs://stats",
+    "jobs://stats/geo",
+    "jobs://digest/latest",
+    "jobs://digest/archive",
+    "jobs://health",
+    "jobs://events",
+    "jobs://mine",
+    "jobs://job/{id}/full",
+    "jobs://search/{cursor}",
+];
+
+/// The server instructions sent to clients on initialize
+pub fn instructions(locale: Locale) -> String {
+    let section = |heading: &str, names: &[&str]| {
+        let entries: Vec<String> = names
+            .iter()
+            .map(|name| {
+                let key = format!("info.{}", name.split(' ').next().unwrap_or(name));
+                format!("• {} - {}", name, text(locale, &key))
+            })
+            .collect();
+        format!("{}:\n{}", text(locale, heading), entries.join("\n"))
+    };
+    let performance: Vec<String> =
+        (1..=4).map(|i| format!("• {}", text(locale, &format!("info.performance.{}", i)))).collect();
+    [
+        text(locale, "info.intro").to_string(),
+        section("info.tools", INSTRUCTION_TOOLS),
+        section("info.prompts", INSTRUCTION_PROMPTS),
+        section("info.resources", INSTRUCTION_RESOURCES),
+        format!("{}:\n{}", text(locale, "info.performance"), performance.join("\n")),
+    ]
+    .join("\n\n")
+}
+
+const EN: &[(&str, &str)] = &[
+    ("info.intro", "Nostr Jobs MCP Server - Access decentralized job listings from the Nostr network."),
+    ("info.tools", "Tools"),
+    ("info.prompts", "Prompts"),
+    ("info.resources", "Resources"),
+    ("info.performance", "Performance Features"),
+    ("info.search_jobs", "Search for jobs by company, skill, or employment type"),
+    ("info.get_job_details", "Get detailed information about a specific job, with warnings when it looks like a scam"),
+    ("info.get_raw_event", "Full signed event JSON behind a listing, with a signature check"),
+    ("info.bookmark_job", "Keep track of interesting listings"),
+    ("info.set_application_status", "Track applications (applied, replied, interviewing, rejected, offer) and stale ones to follow up"),
+    ("info.schedule_interview", "Interviews as NIP-52 calendar events"),
+    ("info.search_career_events", "Career fairs, hiring meetups and networking events"),
+    ("info.recommend_jobs", "Unseen listings similar to the ones viewed or bookmarked"),
+    ("info.save_search", "Reusable searches"),
+    ("info.set_preferences", "Default search filters (skills, salary, locations, excluded companies)"),
+    ("info.set_preference", "Preferences, and everything saved so far"),
+    ("info.explain_query", "Show how a search would be executed (cache key, filter, relays)"),
+    ("info.get_performance_metrics", "View cache performance and efficiency gains"),
+    ("info.get_slow_queries", "Inspect tool calls that exceeded the latency threshold"),
+    ("info.get_audit_log", "Review the audit trail of tool invocations"),
+    ("info.get_api_key_usage", "Today's tool calls and relay fetches per API key"),
+    ("info.clear_cache", "Clear cache and see impact on performance"),
+    ("info.cache_status", "Show cache memory usage and largest entries"),
+    ("info.reset_metrics", "Reset performance tracking"),
+    ("info.get_metrics_history", "Performance metrics of past periods"),
+    ("info.set_tool_enabled", "Enable or disable a tool at runtime"),
+    ("info.reload_config", "Reload configuration without restarting"),
+    ("info.set_query_strategy", "Switch how relays are queried"),
+    ("info.export_snapshot", "Copy the indexed listings between instances"),
+    ("info.list_relays", "Show relay connection state and what each relay served"),
+    ("info.get_stats", "Get statistics about job listings"),
+    ("info.list_tag_vocabulary", "Every tag name used in listings, with counts and example values"),
+    ("info.get_company_profile", "One company's listings, skills, salary transparency, reputation and possible impersonators"),
+    ("info.skill_cooccurrence", "Skills most often asked for together, overall or with one skill"),
+    ("info.market_balance", "Listings vs people for hire per skill: a buyers' or sellers' market?"),
+    ("info.compare_stats", "Compare market statistics between two time windows"),
+    ("info.job_search_assistant", "Get help searching for jobs"),
+    ("info.analyze_job_market", "Analyze current job market trends"),
+    ("info.hiring_trends_forecast", "Forecast hiring from the server's weekly postings, trending skills and salary movement"),
+    ("info.jobs://latest", "Latest job listings; ?limit=, ?page= and search_jobs filters (?skill=rust,go) narrow them"),
+    ("info.jobs://stats", "Job market statistics"),
+    ("info.jobs://stats/geo", "Listings by region, location and geohash cell, remote ones apart"),
+    ("info.jobs://digest/latest", "Latest daily/weekly market digest"),
+    ("info.jobs://digest/archive", "Past digests, each at jobs://digest/{date}"),
+    ("info.jobs://health", "Relay status, cache freshness and index size, to explain degraded answers"),
+    ("info.jobs://events", "Upcoming career fairs and hiring events (NIP-52 calendar events)"),
+    ("info.jobs://mine", "The operator's own listings, applications, engagement and expirations (admins, with a posting key)"),
+    ("info.jobs://job/{id}/full", "One listing with its full description, linked from search results"),
+    ("info.jobs://search/{cursor}", "The next page of a search cut short by its limit, linked from search_jobs"),
+    ("info.performance.1", "Automatic caching with 60s TTL"),
+    ("info.performance.2", "Detailed metrics tracking"),
+    ("info.performance.3", "Cache hit/miss analytics"),
+    ("info.performance.4", "Response time comparison"),
+    (
+        "prompt.search.intro",
+        "I'm your Nostr job search assistant. I'll help you find relevant job listings on the decentralized Nostr network.",
+    ),
+    ("prompt.search.skills", "Required skills: {skills}"),
+    (
+        "prompt.search.request",
+        "Search Query: {query}\n{skills}\n\nPlease help me find relevant job listings and provide recommendations based on this query.",
+    ),
+    ("prompt.search.description", "Job search assistance for: {query}"),
+    ("prompt.market.intro", "I'll analyze the current job market on Nostr and provide insights."),
+    (
+        "prompt.market.request",
+        "Please analyze the job listings available on Nostr. What are the trending skills? Which companies are hiring? What's the salary range for different positions?",
+    ),
+    ("prompt.market.description", "Analysis of the Nostr job market"),
+    ("prompt.forecast.intro", "I'll forecast where hiring on Nostr is heading, using only the data this server collected."),
+    ("prompt.forecast.week_in_progress", " (week in progress)"),
+    ("prompt.forecast.no_skills", "  (no skills listed)"),
+    ("prompt.forecast.skill", "  • {skill}: {count} (was {previous}, {change})"),
+    ("prompt.forecast.no_salaries", "  (not enough salaries in both halves to compare)"),
+    ("prompt.forecast.salary", "  • {group}: {earlier} → {later} ({change}%, from {earlier_count} and {later_count} salaries)"),
+    ("prompt.forecast.truncated", "; the relay deadline cut the sample short, so older weeks may be undercounted"),
+    (
+        "prompt.forecast.data",
+        "Data: {listings} job listing(s) posted on Nostr over the last {weeks} weeks (weeks start Monday UTC){truncated}\n\n\
+        Postings per week:\n{postings}\n\n\
+        Trending skills (listings asking for each in the last {later} weeks, against the {earlier} before):\n{skills}\n\n\
+        Salary movement (median salary midpoint, same halves):\n{salaries}",
+    ),
+    (
+        "prompt.forecast.request",
+        "{data}\n\n\
+        Based on this data, write a forward-looking analysis of the Nostr job market for the next 4 to 8 weeks:\n\
+        1. Posting volume: is hiring speeding up, slowing down or flat? The current week is incomplete, so don't read its low count as a drop.\n\
+        2. Skills: which are gaining or losing demand, and which to learn now.\n\
+        3. Salaries: which way they are moving, per currency and period.\n\
+        4. Confidence: say how sure each call is given the sample sizes, and where the data is too thin to say.\n\
+        Cite the numbers above for every claim and don't invent figures beyond them.",
+    ),
+    ("prompt.forecast.description", "Hiring trends forecast from {listings} listing(s) over {weeks} weeks"),
+    ("error.timeout", "Relays did not answer within {ms}ms"),
+    ("error.relay", "Relay {relay} failed: {message}"),
+    ("error.relay_unknown", "Relay error: {message}"),
+    ("error.not_found", "No job found with ID: {job_id}"),
+    ("error.parse", "Invalid {what} '{input}': {message}"),
+    ("error.geocoding", "Could not geocode '{place}': {message}"),
+    ("error.exchange_rate", "No BTC/{fiat} exchange rate: {message}"),
+    ("error.ingest", "Could not ingest {source}: {message}"),
+    ("error.dvm", "DVM job {request_id} failed: {message}"),
+    ("error.overloaded", "Server is busy; retry in {secs}s"),
+    ("error.quota", "Daily {quota} quota of {limit} used up for this API key; resets in {hours}h{minutes}m (midnight UTC)"),
+    ("error.empty", "{field} must not be empty"),
+    ("error.range", "{field} must be between {min} and {max}"),
+    ("error.draft_title", "A new draft needs a title (or pass the job_id of a draft to edit)"),
+    ("error.no_draft", "No draft with Job ID '{job_id}'; create one with draft_job"),
+    ("error.no_saved_search", "No saved search named '{name}'"),
+    ("error.metrics_format", "Unknown metrics format '{format}': expected \"text\" or \"json\""),
+    ("error.audit_log", "Failed to read audit log"),
+    ("error.disable_self", "set_tool_enabled cannot disable itself"),
+    ("error.resource_not_found", "Resource not found"),
+    ("error.resource_query", "Invalid query on {uri}: {message}"),
+    ("error.search_cursor", "Search cursor '{cursor}' has expired or never existed; run search_jobs again"),
+    ("error.no_digest", "No digest for that date"),
+    ("error.no_owner", "Saved state needs an MCP session or an Authorization bearer token"),
+    ("error.shutting_down", "Server is shutting down"),
+    ("error.admin_tool", "Tool '{tool}' requires admin privileges"),
+    ("error.api_key", "Calls need an API key from API_KEYS while quotas are set"),
+    ("error.admin_resource", "{uri} needs an admin token"),
+    ("error.resource_disabled", "{uri} is unavailable while the {tool} tool is disabled"),
+    ("banner.cached", "⚡ [CACHED]"),
+    ("banner.offline", "💾 [OFFLINE]"),
+    ("banner.stale", "📦 [CACHED - STALE, {age} old: relays unreachable]"),
+    ("banner.fresh", "🌐 [FRESH]"),
+    ("banner.partial", "⚠️ partial: relay deadline reached"),
+    ("report.search.none", "No job listings found matching your criteria."),
+    ("report.search.did_you_mean", "🤔 Did you mean:"),
+    ("report.search.found", "Found {count} job listing(s){banner}:"),
+    ("report.search.more", "Showing {shown} of {total} matches; the rest are at {uri}"),
+    ("report.search.page", "Matches {first}-{last} of {total}{banner}:"),
+    ("link.job.full", "Full listing with description"),
+    ("link.search.more", "More search results"),
+    ("report.job.details", "📄 Full Job Details:"),
+    ("report.history.title", "🏢 Posting history for {npub}{banner}"),
+    ("report.company.also_spelled", " (also spelled {spellings})"),
+    ("report.company.listings", "Listings: {listings} ({active} active, {expired} expired) by {posters} poster(s)"),
+    ("report.company.posters", "🪪 Posters:"),
+    ("report.company.salary_transparency", "💸 Salary transparency:"),
+    ("report.company.reputation", "🗣️ Reputation:"),
+    ("report.company.recent", "Recent listings:"),
+    ("report.stats.title", "📊 Nostr Job Listings Statistics{banner}"),
+    ("report.stats.sample", "Sample: {listings} most recent listing(s) analyzed (requested {requested}){partial}"),
+    ("report.stats.employment_types", "Employment Types:"),
+    ("report.stats.top_companies", "Top Companies:"),
+    ("report.stats.top_skills", "Top Skills:"),
+    ("report.stats.locations", "Locations:"),
+    ("report.stats.salary_transparency", "Salary Transparency:"),
+    ("report.stats.salary_complete", "{disclosed} of {listings} listing(s) ({share}%) state a complete salary"),
+    ("report.comparison.title", "📈 Job Market Comparison"),
+    ("report.comparison.current", "Current window: {since} → {until}"),
+    ("report.comparison.previous", "Previous window: {since} → {until}"),
+    ("report.comparison.listings", "Listings: {previous} → {current} ({delta})"),
+    ("report.comparison.top_skills", "Top Skills (previous → current):"),
+    ("report.comparison.median_salaries", "Median Salaries (previous → current):"),
+    ("report.digest.daily", "📰 Daily Job Market Digest - {date}"),
+    ("report.digest.weekly", "📰 Weekly Job Market Digest - {date}"),
+    ("report.digest.period", "Period: {since} → {until}{partial}"),
+    ("report.digest.listings", "Listings: {listings} ({previous} the period before, {delta})"),
+    ("report.digest.new", "New Listings:"),
+    ("report.digest.notable_salaries", "Notable Salaries ({currency}):"),
+    ("report.digest.trending_skills", "Trending Skills (previous → current):"),
+    ("report.digest.generated", "Generated: {at}"),
+    ("report.converted", "(sats/BTC salaries converted at {per_btc} {fiat}/BTC as of {as_of})"),
+    ("report.preferences_applied", "⚙️ Your preferences applied: {defaults}"),
+    ("report.resume.skills", "🧠 Skills found by DVM {provider}: {skills}"),
+    ("report.resume.none", "No job listings ask for any of these skills."),
+    ("report.recommend.none", "No unseen listings among the most recent ones share those skills or companies."),
+    ("report.bookmark.removed", "Removed bookmark for job {job_id}"),
+    ("report.bookmark.missing", "Job {job_id} was not bookmarked"),
+    ("report.application.new", "📨 Tracking new application"),
+    ("report.application.moved", "📨 Application moved from {from} to {to}"),
+    ("report.application.updated", "📨 Application updated"),
+    ("report.follow.added", "⭐ Now following {name}"),
+    ("report.follow.updated", "⭐ Updated followed employer {name}"),
+    ("report.follow.removed", "No longer following {name}"),
+    ("report.follow.missing", "{name} was not followed"),
+    ("report.saved_search.created", "💾 Created saved search '{name}'"),
+    ("report.saved_search.replaced", "💾 Replaced saved search '{name}'"),
+    ("report.saved_search.deleted", "Deleted saved search '{name}'"),
+    ("report.saved_search.missing", "No saved search named '{name}'"),
+    ("report.preference.set", "Set preference {key} = {value}"),
+    ("report.preference.removed", "Removed preference {key}"),
+    ("report.expiring.none", "✅ No posted listings expire in the next {days} day(s)"),
+    ("report.snapshot", "📦 Snapshot of {listings} listing(s), NDJSON with one signed event per line:"),
+    ("report.skill_pairs.with", "🔗 Skills listed with {skill} ({listings} listing(s))"),
+    ("report.geo.unspecified", "Not specified"),
+    ("report.raw_event.title", "🧾 Raw event for {job_id} (kind {kind}, signature {signature}){banner}"),
+    ("report.raw_event.valid", "valid ✅"),
+    ("report.raw_event.invalid", "INVALID ❌"),
+    ("report.raw_event.ingested", "Signed by the ingestion bridge for a {source} posting"),
+    ("report.tag_vocabulary.title", "🏷️ Tag vocabulary{banner}"),
+    ("report.tag_vocabulary.names", "{names} tag name(s) in use"),
+    ("report.search.source", "📡 Source: {source}"),
+    ("report.search.partial", "Partial results from {answered} of {relays} relays:"),
+    ("report.search.hidden", "🛡️ {hidden} suspicious listing(s) hidden"),
+    ("report.explain", "🔍 Query Explanation\n\nCache key: {cache_key}\nCache: {cache}\n\nRelay filter:\n{filter}\n\nClient-side matching (AND across fields, case-insensitive substring{fuzzy}):\n• company: {company}\n• skill: {skill}\n• employment-type: {employment_type}\n• author: {author}\n• near: {near}\n• min salary: {min_salary}\n• limit: {limit}\n\nRelays queried ({relay_count}):\n{relays}"),
+    ("report.explain.hit", "HIT ({freshness}) — entry is {age}s old (TTL {ttl}s), {events} raw event(s) cached"),
+    ("report.explain.fresh", "fresh"),
+    ("report.explain.stale", "stale"),
+    ("report.explain.miss", "MISS — would fetch from relays (per-relay timeout {relay_ms}ms, overall deadline {search_ms}ms)"),
+    ("report.explain.fuzzy", ", fuzzy ≤ {edits} edits"),
+    ("report.explain.near", "{lat}, {lon} within {km} km (geohash cells {cells})"),
+    ("report.explain.near_place", "{place} within {km} km (geocoded when searching)"),
+    ("report.explain.min_salary", "{amount} {currency} (sats/BTC salaries converted when searching)"),
+    ("report.explain.any", "any"),
+    ("report.explain.anywhere", "anywhere"),
+    ("report.scam.likely", "⚠️ Likely scam"),
+    ("report.scam.possible", "⚠️ Possible scam signals"),
+    ("report.scam.warning", "{heading} (score {score}/100):\n{signals}\nBe careful before sharing personal details or paying anything."),
+    ("report.scam.pay_to_apply", "Asks applicants for money (\"{phrase}\"); legitimate employers don't charge to apply"),
+    ("report.scam.messenger_only", "The only contact is a messenger ({messenger}), with no email or website"),
+    ("report.scam.salary_above_market", "Pays {factor}× the median {group} salary of {median}"),
+    ("report.scam.new_key_no_profile", "Posted from a key first seen {days} day(s) ago, with no profile"),
+    ("report.status.deleted", "❌ Deleted by its author (NIP-09)"),
+    ("report.status.expired", "⌛ Expired on {date} (NIP-40)"),
+    ("report.status.gone", "❓ No longer found on any relay"),
+    ("report.status.active", "✅ Active"),
+    ("report.status.job", "{title} at {company} (🆔 {job_id})"),
+    ("report.status.body", "{verdict}\n\nJob: {job}\nSeen on: {seen_on}\nMissing from: {missing_from}"),
+    ("report.status.unreachable", "Unreachable: {relays}"),
+    ("report.status.checked", "Last seen: {last_seen}\nChecked: {checked}"),
+    ("report.status.never", "never"),
+    ("report.status.none", "none"),
+    ("report.preferences.saved", "✅ Preferences saved"),
+    ("report.preferences.title", "⚙️ Preferences"),
+    ("report.preferences.body", "{title}\n\n• Skills: {skills}\n• Minimum salary: {min_salary}\n• Locations: {locations}\n• Excluded companies: {excluded}"),
+    ("report.preferences.any", "any"),
+    ("report.preferences.none", "none"),
+    ("report.saved_state.empty", "No saved state yet. Use bookmark_job, save_search, follow_employer, set_preference or set_application_status."),
+    ("report.saved_state.title", "🗂️ Saved State"),
+    ("report.saved_state.section_empty", "{title}: none"),
+    ("report.saved_state.bookmarks", "🔖 Bookmarks"),
+    ("report.saved_state.searches", "💾 Saved searches"),
+    ("report.saved_state.followed", "⭐ Followed employers"),
+    ("report.saved_state.viewed", "👀 Recently viewed"),
+    ("report.saved_state.drafts", "📝 Drafts"),
+    ("report.saved_state.applications", "📨 Applications"),
+    ("report.saved_state.bookmark", "{job_id} (saved {at})"),
+    ("report.saved_state.follow", "{name}{npub} (since {at})"),
+    ("report.saved_state.view", "{job_id} (viewed {at})"),
+    ("report.saved_state.draft", "{job_id} - {title} (edited {at})"),
+    ("report.saved_state.application", "{job_id} - {status} (updated {at})"),
+    ("report.saved_state.follow_up", " ⏰ follow up?"),
+    ("report.draft.saved", "📝 Draft saved"),
+    ("report.draft.preview", "👀 Draft preview"),
+    ("report.draft.ready", "✅ Ready to publish with publish_draft"),
+    ("report.draft.fix", "⚠️ Fix before publishing:"),
+    ("report.draft.body", "{title} (not signed or published; the event link is assigned on publishing)\n\n{summary}\n\n{details}\n{description}\n\n{status}"),
+    ("report.draft.no_description", "(no description)"),
+    ("report.interview.scheduled", "🗓️ Interview scheduled as event {event_id}"),
+    ("report.interview.upcoming", "🗓️ {count} upcoming interview(s):"),
+    ("report.interview.none", "No upcoming interviews"),
+    ("report.tool.enabled", "✅ Tool '{tool}' enabled. Connected clients have been notified to refresh their tool list."),
+    ("report.tool.disabled", "✅ Tool '{tool}' disabled. Connected clients have been notified to refresh their tool list."),
+    ("report.tool.already_enabled", "Tool '{tool}' is already enabled."),
+    ("report.tool.already_disabled", "Tool '{tool}' is already disabled."),
+    ("report.strategy.unchanged", "Relay query strategy is already {strategy}."),
+    ("report.strategy.switched", "✅ Relay query strategy switched from {previous} to {strategy}. Until the next reload_config; set RELAY_QUERY_STRATEGY to keep it."),
+    ("report.metrics.reset", "✅ Performance metrics have been reset; the previous totals are kept in get_metrics_history."),
+    ("report.cache.cleared", "🗑️  Cache Cleared Successfully\n\nCache statistics before clear:\n• Total cached queries: {entries}\n• Cache hits: {hits}\n• Cache hit rate: {rate}%\n\n⚠️  Next queries will fetch fresh data from relays.\n💡 Use get_performance_metrics to track the impact."),
+];
+
+const ES: &[(&str, &str)] = &[
+    ("info.intro", "Servidor MCP de Nostr Jobs - Acceso a ofertas de empleo descentralizadas de la red Nostr."),
+    ("info.tools", "Herramientas"),
+    ("info.prompts", "Prompts"),
+    ("info.resources", "Recursos"),
+    ("info.performance", "Rendimiento"),
+    ("info.search_jobs", "Buscar empleos por empresa, habilidad o tipo de contrato"),
+    ("info.get_job_details", "Ver los detalles de una oferta, con avisos cuando parece una estafa"),
+    ("info.get_raw_event", "JSON completo del evento firmado de una oferta, con verificación de la firma"),
+    ("info.bookmark_job", "Guardar las ofertas interesantes"),
+    ("info.set_application_status", "Seguir las candidaturas (enviada, respondida, entrevista, rechazada, oferta) y las que conviene reactivar"),
+    ("info.schedule_interview", "Entrevistas como eventos de calendario NIP-52"),
+    ("info.search_career_events", "Ferias de empleo, encuentros de contratación y eventos de networking"),
+    ("info.recommend_jobs", "Ofertas no vistas parecidas a las consultadas o guardadas"),
+    ("info.save_search", "Búsquedas reutilizables"),
+    ("info.set_preferences", "Filtros de búsqueda por defecto (habilidades, salario, ubicaciones, empresas excluidas)"),
+    ("info.set_preference", "Preferencias, y todo lo guardado hasta ahora"),
+    ("info.explain_query", "Mostrar cómo se ejecutaría una búsqueda (clave de caché, filtro, relays)"),
+    ("info.get_performance_metrics", "Ver el rendimiento de la caché y la eficiencia ganada"),
+    ("info.get_slow_queries", "Revisar las llamadas que superaron el umbral de latencia"),
+    ("info.get_audit_log", "Consultar el registro de auditoría de las llamadas"),
+    ("info.get_api_key_usage", "Llamadas y consultas a relays de hoy por clave de API"),
+    ("info.clear_cache", "Vaciar la caché y ver el efecto en el rendimiento"),
+    ("info.cache_status", "Memoria usada por la caché y sus entradas más grandes"),
+    ("info.reset_metrics", "Reiniciar las métricas de rendimiento"),
+    ("info.get_metrics_history", "Métricas de rendimiento de periodos anteriores"),
+    ("info.set_tool_enabled", "Activar o desactivar una herramienta en caliente"),
+    ("info.reload_config", "Recargar la configuración sin reiniciar"),
+    ("info.set_query_strategy", "Cambiar cómo se consultan los relays"),
+    ("info.export_snapshot", "Copiar las ofertas indexadas entre instancias"),
+    ("info.list_relays", "Estado de conexión de cada relay y lo que ha servido"),
+    ("info.get_stats", "Estadísticas de las ofertas de empleo"),
+    ("info.list_tag_vocabulary", "Todas las etiquetas usadas en las ofertas, con recuentos y valores de ejemplo"),
+    ("info.get_company_profile", "Ofertas, habilidades, transparencia salarial, reputación y posibles suplantadores de una empresa"),
+    ("info.skill_cooccurrence", "Habilidades que más se piden juntas, en general o con una habilidad"),
+    ("info.market_balance", "Ofertas frente a candidatos por habilidad: ¿mercado de empleadores o de candidatos?"),
+    ("info.compare_stats", "Comparar las estadísticas del mercado entre dos periodos"),
+    ("info.job_search_assistant", "Ayuda para buscar empleo"),
+    ("info.analyze_job_market", "Analizar las tendencias actuales del mercado laboral"),
+    ("info.hiring_trends_forecast", "Prever la contratación a partir de las ofertas semanales, las habilidades en auge y la evolución salarial"),
+    ("info.jobs://latest", "Últimas ofertas de empleo; ?limit=, ?page= y los filtros de search_jobs (?skill=rust,go) las acotan"),
+    ("info.jobs://stats", "Estadísticas del mercado laboral"),
+    ("info.jobs://stats/geo", "Ofertas por región, ubicación y celda geohash, las remotas aparte"),
+    ("info.jobs://digest/latest", "Último resumen diario/semanal del mercado"),
+    ("info.jobs://digest/archive", "Resúmenes anteriores, cada uno en jobs://digest/{date}"),
+    ("info.jobs://health", "Estado de los relays, frescura de la caché y tamaño del índice, para explicar respuestas degradadas"),
+    ("info.jobs://events", "Próximas ferias y eventos de contratación (eventos de calendario NIP-52)"),
+    ("info.jobs://mine", "Las ofertas, candidaturas, interacción y caducidades del propio operador (administradores, con clave de publicación)"),
+    ("info.jobs://job/{id}/full", "Una oferta con su descripción completa, enlazada desde los resultados de búsqueda"),
+    ("info.jobs://search/{cursor}", "La siguiente página de una búsqueda recortada por su límite, enlazada desde search_jobs"),
+    ("info.performance.1", "Caché automática con TTL de 60 s"),
+    ("info.performance.2", "Métricas detalladas"),
+    ("info.performance.3", "Análisis de aciertos y fallos de caché"),
+    ("info.performance.4", "Comparación de tiempos de respuesta"),
+    (
+        "prompt.search.intro",
+        "Soy tu asistente de búsqueda de empleo en Nostr. Te ayudaré a encontrar ofertas relevantes en la red descentralizada Nostr.",
+    ),
+    ("prompt.search.skills", "Habilidades requeridas: {skills}"),
+    (
+        "prompt.search.request",
+        "Búsqueda: {query}\n{skills}\n\nAyúdame a encontrar ofertas de empleo relevantes y dame recomendaciones basadas en esta búsqueda.",
+    ),
+    ("prompt.search.description", "Ayuda para buscar empleo: {query}"),
+    ("prompt.market.intro", "Analizaré el mercado laboral actual en Nostr y te daré conclusiones."),
+    (
+        "prompt.market.request",
+        "Analiza las ofertas de empleo disponibles en Nostr. ¿Qué habilidades están en auge? ¿Qué empresas están contratando? ¿Cuál es el rango salarial de los distintos puestos?",
+    ),
+    ("prompt.market.description", "Análisis del mercado laboral de Nostr"),
+    ("prompt.forecast.intro", "Haré una previsión de la contratación en Nostr usando solo los datos que ha recogido este servidor."),
+    ("prompt.forecast.week_in_progress", " (semana en curso)"),
+    ("prompt.forecast.no_skills", "  (no se indicaron habilidades)"),
+    ("prompt.forecast.skill", "  • {skill}: {count} (antes {previous}, {change})"),
+    ("prompt.forecast.no_salaries", "  (no hay salarios suficientes en ambas mitades para comparar)"),
+    ("prompt.forecast.salary", "  • {group}: {earlier} → {later} ({change}%, de {earlier_count} y {later_count} salarios)"),
+    ("prompt.forecast.truncated", "; el plazo de los relays recortó la muestra, así que las semanas más antiguas pueden estar infrarrepresentadas"),
+    (
+        "prompt.forecast.data",
+        "Datos: {listings} oferta(s) de empleo publicadas en Nostr en las últimas {weeks} semanas (las semanas empiezan el lunes, UTC){truncated}\n\n\
+        Ofertas por semana:\n{postings}\n\n\
+        Habilidades en tendencia (ofertas que piden cada una en las últimas {later} semanas, frente a las {earlier} anteriores):\n{skills}\n\n\
+        Evolución salarial (mediana del punto medio del salario, mismas mitades):\n{salaries}",
+    ),
+    (
+        "prompt.forecast.request",
+        "{data}\n\n\
+        A partir de estos datos, escribe un análisis prospectivo del mercado laboral de Nostr para las próximas 4 a 8 semanas:\n\
+        1. Volumen de ofertas: ¿la contratación se acelera, se frena o se mantiene? La semana actual está incompleta, así que no interpretes su recuento bajo como una caída.\n\
+        2. Habilidades: cuáles ganan o pierden demanda, y cuáles conviene aprender ahora.\n\
+        3. Salarios: hacia dónde se mueven, por moneda y periodo.\n\
+        4. Confianza: indica la seguridad de cada previsión según el tamaño de las muestras, y dónde los datos son demasiado escasos para afirmar nada.\n\
+        Cita las cifras anteriores en cada afirmación y no inventes datos más allá de ellas. Responde en español.",
+    ),
+    ("prompt.forecast.description", "Previsión de contratación a partir de {listings} oferta(s) en {weeks} semanas"),
+    ("error.timeout", "Los relays no respondieron en {ms} ms"),
+    ("error.relay", "Falló el relay {relay}: {message}"),
+    ("error.relay_unknown", "Error de relay: {message}"),
+    ("error.not_found", "No hay ninguna oferta con el ID: {job_id}"),
+    ("error.parse", "{what} no válido '{input}': {message}"),
+    ("error.geocoding", "No se pudo geolocalizar '{place}': {message}"),
+    ("error.exchange_rate", "No hay tipo de cambio BTC/{fiat}: {message}"),
+    ("error.ingest", "No se pudo importar {source}: {message}"),
+    ("error.dvm", "Falló el trabajo DVM {request_id}: {message}"),
+    ("error.overloaded", "El servidor está ocupado; vuelve a intentarlo en {secs} s"),
+    ("error.quota", "Se agotó la cuota diaria de {quota} ({limit}) de esta clave de API; se renueva en {hours}h{minutes}m (medianoche UTC)"),
+    ("error.empty", "{field} no puede estar vacío"),
+    ("error.range", "{field} debe estar entre {min} y {max}"),
+    ("error.draft_title", "Un borrador nuevo necesita un título (o indica el job_id de un borrador para editarlo)"),
+    ("error.no_draft", "No hay ningún borrador con el ID '{job_id}'; créalo con draft_job"),
+    ("error.no_saved_search", "No hay ninguna búsqueda guardada llamada '{name}'"),
+    ("error.metrics_format", "Formato de métricas desconocido '{format}': se esperaba \"text\" o \"json\""),
+    ("error.audit_log", "No se pudo leer el registro de auditoría"),
+    ("error.disable_self", "set_tool_enabled no puede desactivarse a sí misma"),
+    ("error.resource_not_found", "Recurso no encontrado"),
+    ("error.resource_query", "Consulta no válida en {uri}: {message}"),
+    ("error.search_cursor", "El cursor de búsqueda '{cursor}' ha caducado o nunca existió; ejecuta search_jobs de nuevo"),
+    ("error.no_digest", "No hay resumen para esa fecha"),
+    ("error.no_owner", "Guardar estado requiere una sesión MCP o un token Bearer en Authorization"),
+    ("error.shutting_down", "El servidor se está apagando"),
+    ("error.admin_tool", "La herramienta '{tool}' requiere privilegios de administrador"),
+    ("error.api_key", "Las llamadas necesitan una clave de API de API_KEYS mientras haya cuotas"),
+    ("error.admin_resource", "{uri} requiere un token de administrador"),
+    ("error.resource_disabled", "{uri} no está disponible mientras la herramienta {tool} esté desactivada"),
+    ("banner.cached", "⚡ [EN CACHÉ]"),
+    ("banner.offline", "💾 [SIN CONEXIÓN]"),
+    ("banner.stale", "📦 [EN CACHÉ - DESACTUALIZADO, de hace {age}: relays inaccesibles]"),
+    ("banner.fresh", "🌐 [ACTUAL]"),
+    ("banner.partial", "⚠️ parcial: se alcanzó el plazo de los relays"),
+    ("report.search.none", "No se encontraron ofertas de empleo con esos criterios."),
+    ("report.search.did_you_mean", "🤔 Quizás quisiste decir:"),
+    ("report.search.found", "{count} oferta(s) de empleo encontrada(s){banner}:"),
+    ("report.search.more", "Mostrando {shown} de {total} coincidencias; el resto está en {uri}"),
+    ("report.search.page", "Coincidencias {first}-{last} de {total}{banner}:"),
+    ("link.job.full", "Oferta completa con descripción"),
+    ("link.search.more", "Más resultados de búsqueda"),
+    ("report.job.details", "📄 Detalles completos de la oferta:"),
+    ("report.history.title", "🏢 Historial de publicaciones de {npub}{banner}"),
+    ("report.company.also_spelled", " (también escrito {spellings})"),
+    ("report.company.listings", "Ofertas: {listings} ({active} activas, {expired} caducadas) de {posters} publicador(es)"),
+    ("report.company.posters", "🪪 Publicadores:"),
+    ("report.company.salary_transparency", "💸 Transparencia salarial:"),
+    ("report.company.reputation", "🗣️ Reputación:"),
+    ("report.company.recent", "Ofertas recientes:"),
+    ("report.stats.title", "📊 Estadísticas de ofertas de empleo en Nostr{banner}"),
+    ("report.stats.sample", "Muestra: {listings} oferta(s) más reciente(s) analizada(s) (se pidieron {requested}){partial}"),
+    ("report.stats.employment_types", "Tipos de contrato:"),
+    ("report.stats.top_companies", "Empresas principales:"),
+    ("report.stats.top_skills", "Habilidades principales:"),
+    ("report.stats.locations", "Ubicaciones:"),
+    ("report.stats.salary_transparency", "Transparencia salarial:"),
+    ("report.stats.salary_complete", "{disclosed} de {listings} oferta(s) ({share}%) indican un salario completo"),
+    ("report.comparison.title", "📈 Comparación del mercado laboral"),
+    ("report.comparison.current", "Periodo actual: {since} → {until}"),
+    ("report.comparison.previous", "Periodo anterior: {since} → {until}"),
+    ("report.comparison.listings", "Ofertas: {previous} → {current} ({delta})"),
+    ("report.comparison.top_skills", "Habilidades principales (anterior → actual):"),
+    ("report.comparison.median_salaries", "Salarios medianos (anterior → actual):"),
+    ("report.digest.daily", "📰 Resumen diario del mercado laboral - {date}"),
+    ("report.digest.weekly", "📰 Resumen semanal del mercado laboral - {date}"),
+    ("report.digest.period", "Periodo: {since} → {until}{partial}"),
+    ("report.digest.listings", "Ofertas: {listings} ({previous} el periodo anterior, {delta})"),
+    ("report.digest.new", "Ofertas nuevas:"),
+    ("report.digest.notable_salaries", "Salarios destacados ({currency}):"),
+    ("report.digest.trending_skills", "Habilidades en tendencia (anterior → actual):"),
+    ("report.digest.generated", "Generado: {at}"),
+    ("report.converted", "(salarios en sats/BTC convertidos a {per_btc} {fiat}/BTC a fecha de {as_of})"),
+    ("report.preferences_applied", "⚙️ Tus preferencias aplicadas: {defaults}"),
+    ("report.resume.skills", "🧠 Habilidades encontradas por el DVM {provider}: {skills}"),
+    ("report.resume.none", "Ninguna oferta de empleo pide alguna de estas habilidades."),
+    ("report.recommend.none", "Ninguna oferta no vista entre las más recientes comparte esas habilidades o empresas."),
+    ("report.bookmark.removed", "Marcador eliminado de la oferta {job_id}"),
+    ("report.bookmark.missing", "La oferta {job_id} no estaba en marcadores"),
+    ("report.application.new", "📨 Siguiendo una nueva candidatura"),
+    ("report.application.moved", "📨 Candidatura movida de {from} a {to}"),
+    ("report.application.updated", "📨 Candidatura actualizada"),
+    ("report.follow.added", "⭐ Ahora sigues a {name}"),
+    ("report.follow.updated", "⭐ Empleador seguido actualizado: {name}"),
+    ("report.follow.removed", "Ya no sigues a {name}"),
+    ("report.follow.missing", "No seguías a {name}"),
+    ("report.saved_search.created", "💾 Búsqueda guardada '{name}' creada"),
+    ("report.saved_search.replaced", "💾 Búsqueda guardada '{name}' reemplazada"),
+    ("report.saved_search.deleted", "Búsqueda guardada '{name}' eliminada"),
+    ("report.saved_search.missing", "No hay ninguna búsqueda guardada llamada '{name}'"),
+    ("report.preference.set", "Preferencia {key} = {value} establecida"),
+    ("report.preference.removed", "Preferencia {key} eliminada"),
+    ("report.expiring.none", "✅ Ninguna oferta publicada caduca en los próximos {days} día(s)"),
+    ("report.snapshot", "📦 Instantánea de {listings} oferta(s), NDJSON con un evento firmado por línea:"),
+    ("report.skill_pairs.with", "🔗 Habilidades listadas junto a {skill} ({listings} oferta(s))"),
+    ("report.geo.unspecified", "Sin especificar"),
+    ("report.raw_event.title", "🧾 Evento original de {job_id} (kind {kind}, firma {signature}){banner}"),
+    ("report.raw_event.valid", "válida ✅"),
+    ("report.raw_event.invalid", "NO VÁLIDA ❌"),
+    ("report.raw_event.ingested", "Firmado por el puente de importación para una oferta de {source}"),
+    ("report.tag_vocabulary.title", "🏷️ Vocabulario de etiquetas{banner}"),
+    ("report.tag_vocabulary.names", "{names} nombre(s) de etiqueta en uso"),
+    ("report.search.source", "📡 Origen: {source}"),
+    ("report.search.partial", "Resultados parciales de {answered} de {relays} relays:"),
+    ("report.search.hidden", "🛡️ {hidden} oferta(s) sospechosa(s) oculta(s)"),
+    ("report.explain", "🔍 Explicación de la consulta\n\nClave de caché: {cache_key}\nCaché: {cache}\n\nFiltro de relay:\n{filter}\n\nCoincidencia en el cliente (Y entre campos, subcadena sin distinguir mayúsculas{fuzzy}):\n• empresa: {company}\n• habilidad: {skill}\n• tipo de contrato: {employment_type}\n• autor: {author}\n• cerca de: {near}\n• salario mínimo: {min_salary}\n• límite: {limit}\n\nRelays consultados ({relay_count}):\n{relays}"),
+    ("report.explain.hit", "ACIERTO ({freshness}) — la entrada tiene {age}s (TTL {ttl}s), {events} evento(s) en caché"),
+    ("report.explain.fresh", "vigente"),
+    ("report.explain.stale", "caducada"),
+    ("report.explain.miss", "FALLO — se consultarían los relays (tiempo límite por relay {relay_ms}ms, plazo total {search_ms}ms)"),
+    ("report.explain.fuzzy", ", aproximada ≤ {edits} ediciones"),
+    ("report.explain.near", "{lat}, {lon} a menos de {km} km (celdas geohash {cells})"),
+    ("report.explain.near_place", "{place} a menos de {km} km (se geocodifica al buscar)"),
+    ("report.explain.min_salary", "{amount} {currency} (los salarios en sats/BTC se convierten al buscar)"),
+    ("report.explain.any", "cualquiera"),
+    ("report.explain.anywhere", "en cualquier lugar"),
+    ("report.scam.likely", "⚠️ Probable estafa"),
+    ("report.scam.possible", "⚠️ Posibles señales de estafa"),
+    ("report.scam.warning", "{heading} (puntuación {score}/100):\n{signals}\nTen cuidado antes de compartir datos personales o pagar algo."),
+    ("report.scam.pay_to_apply", "Pide dinero a los candidatos (\"{phrase}\"); los empleadores legítimos no cobran por postularse"),
+    ("report.scam.messenger_only", "El único contacto es un mensajero ({messenger}), sin correo ni sitio web"),
+    ("report.scam.salary_above_market", "Paga {factor}× la mediana salarial {group} de {median}"),
+    ("report.scam.new_key_no_profile", "Publicada desde una clave vista por primera vez hace {days} día(s), sin perfil"),
+    ("report.status.deleted", "❌ Eliminada por su autor (NIP-09)"),
+    ("report.status.expired", "⌛ Caducó el {date} (NIP-40)"),
+    ("report.status.gone", "❓ Ya no está en ningún relay"),
+    ("report.status.active", "✅ Activa"),
+    ("report.status.job", "{title} en {company} (🆔 {job_id})"),
+    ("report.status.body", "{verdict}\n\nOferta: {job}\nVista en: {seen_on}\nAusente de: {missing_from}"),
+    ("report.status.unreachable", "Inaccesibles: {relays}"),
+    ("report.status.checked", "Vista por última vez: {last_seen}\nComprobada: {checked}"),
+    ("report.status.never", "nunca"),
+    ("report.status.none", "ninguno"),
+    ("report.preferences.saved", "✅ Preferencias guardadas"),
+    ("report.preferences.title", "⚙️ Preferencias"),
+    ("report.preferences.body", "{title}\n\n• Habilidades: {skills}\n• Salario mínimo: {min_salary}\n• Ubicaciones: {locations}\n• Empresas excluidas: {excluded}"),
+    ("report.preferences.any", "cualquiera"),
+    ("report.preferences.none", "ninguna"),
+    ("report.saved_state.empty", "Todavía no hay nada guardado. Usa bookmark_job, save_search, follow_employer, set_preference o set_application_status."),
+    ("report.saved_state.title", "🗂️ Estado guardado"),
+    ("report.saved_state.section_empty", "{title}: ninguno"),
+    ("report.saved_state.bookmarks", "🔖 Marcadores"),
+    ("report.saved_state.searches", "💾 Búsquedas guardadas"),
+    ("report.saved_state.followed", "⭐ Empleadores seguidos"),
+    ("report.saved_state.viewed", "👀 Vistas recientemente"),
+    ("report.saved_state.drafts", "📝 Borradores"),
+    ("report.saved_state.applications", "📨 Candidaturas"),
+    ("report.saved_state.bookmark", "{job_id} (guardada el {at})"),
+    ("report.saved_state.follow", "{name}{npub} (desde {at})"),
+    ("report.saved_state.view", "{job_id} (vista el {at})"),
+    ("report.saved_state.draft", "{job_id} - {title} (editado el {at})"),
+    ("report.saved_state.application", "{job_id} - {status} (actualizada el {at})"),
+    ("report.saved_state.follow_up", " ⏰ ¿hacer seguimiento?"),
+    ("report.draft.saved", "📝 Borrador guardado"),
+    ("report.draft.preview", "👀 Vista previa del borrador"),
+    ("report.draft.ready", "✅ Lista para publicar con publish_draft"),
+    ("report.draft.fix", "⚠️ Corrige antes de publicar:"),
+    ("report.draft.body", "{title} (sin firmar ni publicar; el enlace del evento se asigna al publicar)\n\n{summary}\n\n{details}\n{description}\n\n{status}"),
+    ("report.draft.no_description", "(sin descripción)"),
+    ("report.interview.scheduled", "🗓️ Entrevista programada como evento {event_id}"),
+    ("report.interview.upcoming", "🗓️ {count} entrevista(s) próxima(s):"),
+    ("report.interview.none", "No hay entrevistas próximas"),
+    ("report.tool.enabled", "✅ Herramienta '{tool}' activada. Se avisó a los clientes conectados para que actualicen su lista de herramientas."),
+    ("report.tool.disabled", "✅ Herramienta '{tool}' desactivada. Se avisó a los clientes conectados para que actualicen su lista de herramientas."),
+    ("report.tool.already_enabled", "La herramienta '{tool}' ya está activada."),
+    ("report.tool.already_disabled", "La herramienta '{tool}' ya está desactivada."),
+    ("report.strategy.unchanged", "La estrategia de consulta a relays ya es {strategy}."),
+    ("report.strategy.switched", "✅ Estrategia de consulta a relays cambiada de {previous} a {strategy}. Hasta el próximo reload_config; define RELAY_QUERY_STRATEGY para conservarla."),
+    ("report.metrics.reset", "✅ Se reiniciaron las métricas de rendimiento; los totales anteriores se conservan en get_metrics_history."),
+    ("report.cache.cleared", "🗑️  Caché vaciada correctamente\n\nEstadísticas de la caché antes de vaciarla:\n• Consultas en caché: {entries}\n• Aciertos de caché: {hits}\n• Tasa de aciertos: {rate}%\n\n⚠️  Las próximas consultas obtendrán datos nuevos de los relays.\n💡 Usa get_performance_metrics para seguir el efecto."),
+];
+
+const DE: &[(&str, &str)] = &[
+    ("info.intro", "Nostr Jobs MCP-Server - Zugriff auf dezentrale Stellenangebote aus dem Nostr-Netzwerk."),
+    ("info.tools", "Werkzeuge"),
+    ("info.prompts", "Prompts"),
+    ("info.resources", "Ressourcen"),
+    ("info.performance", "Leistungsmerkmale"),
+    ("info.search_jobs", "Stellen nach Firma, Fähigkeit oder Anstellungsart suchen"),
+    ("info.get_job_details", "Details zu einer Stelle abrufen, mit Warnung bei Betrugsverdacht"),
+    ("info.get_raw_event", "Vollständiges signiertes Event-JSON einer Stelle, mit Signaturprüfung"),
+    ("info.bookmark_job", "Interessante Stellen merken"),
+    ("info.set_application_status", "Bewerbungen verfolgen (beworben, geantwortet, Gespräch, abgelehnt, Angebot) und liegengebliebene nachfassen"),
+    ("info.schedule_interview", "Vorstellungsgespräche als NIP-52-Kalendereinträge"),
+    ("info.search_career_events", "Jobmessen, Recruiting-Treffen und Networking-Events"),
+    ("info.recommend_jobs", "Ungesehene Stellen ähnlich den angesehenen oder gemerkten"),
+    ("info.save_search", "Wiederverwendbare Suchen"),
+    ("info.set_preferences", "Standard-Suchfilter (Fähigkeiten, Gehalt, Orte, ausgeschlossene Firmen)"),
+    ("info.set_preference", "Einstellungen und alles bisher Gespeicherte"),
+    ("info.explain_query", "Zeigen, wie eine Suche ausgeführt würde (Cache-Schlüssel, Filter, Relays)"),
+    ("info.get_performance_metrics", "Cache-Leistung und Effizienzgewinne ansehen"),
+    ("info.get_slow_queries", "Aufrufe prüfen, die die Latenzschwelle überschritten haben"),
+    ("info.get_audit_log", "Das Audit-Protokoll der Aufrufe durchsehen"),
+    ("info.get_api_key_usage", "Heutige Aufrufe und Relay-Abfragen je API-Schlüssel"),
+    ("info.clear_cache", "Cache leeren und die Auswirkung auf die Leistung sehen"),
+    ("info.cache_status", "Speicherverbrauch des Caches und seine größten Einträge"),
+    ("info.reset_metrics", "Leistungsmessung zurücksetzen"),
+    ("info.get_metrics_history", "Leistungskennzahlen vergangener Zeiträume"),
+    ("info.set_tool_enabled", "Ein Werkzeug zur Laufzeit ein- oder ausschalten"),
+    ("info.reload_config", "Konfiguration ohne Neustart neu laden"),
+    ("info.set_query_strategy", "Umstellen, wie Relays abgefragt werden"),
+    ("info.export_snapshot", "Die indizierten Stellen zwischen Instanzen kopieren"),
+    ("info.list_relays", "Verbindungsstatus der Relays und was jedes geliefert hat"),
+    ("info.get_stats", "Statistiken zu den Stellenangeboten"),
+    ("info.list_tag_vocabulary", "Alle in Stellen verwendeten Tag-Namen, mit Häufigkeit und Beispielwerten"),
+    ("info.get_company_profile", "Stellen, Fähigkeiten, Gehaltstransparenz, Ruf und mögliche Nachahmer einer Firma"),
+    ("info.skill_cooccurrence", "Fähigkeiten, die am häufigsten zusammen verlangt werden, insgesamt oder mit einer Fähigkeit"),
+    ("info.market_balance", "Stellen gegenüber Jobsuchenden je Fähigkeit: Arbeitgeber- oder Bewerbermarkt?"),
+    ("info.compare_stats", "Marktstatistiken zweier Zeiträume vergleichen"),
+    ("info.job_search_assistant", "Hilfe bei der Stellensuche"),
+    ("info.analyze_job_market", "Aktuelle Trends am Arbeitsmarkt analysieren"),
+    ("info.hiring_trends_forecast", "Einstellungsprognose aus wöchentlichen Stellen, gefragten Fähigkeiten und Gehaltsentwicklung"),
+    ("info.jobs://latest", "Neueste Stellenangebote; ?limit=, ?page= und die Filter von search_jobs (?skill=rust,go) grenzen sie ein"),
+    ("info.jobs://stats", "Arbeitsmarktstatistiken"),
+    ("info.jobs://stats/geo", "Stellen nach Region, Ort und Geohash-Zelle, Remote-Stellen getrennt"),
+    ("info.jobs://digest/latest", "Neueste tägliche/wöchentliche Marktübersicht"),
+    ("info.jobs://digest/archive", "Frühere Übersichten, jeweils unter jobs://digest/{date}"),
+    ("info.jobs://health", "Relay-Status, Aktualität des Caches und Indexgröße, um eingeschränkte Antworten zu erklären"),
+    ("info.jobs://events", "Anstehende Jobmessen und Recruiting-Events (NIP-52-Kalendereinträge)"),
+    ("info.jobs://mine", "Eigene Stellen, Bewerbungen, Resonanz und Abläufe des Betreibers (Admins, mit Veröffentlichungsschlüssel)"),
+    ("info.jobs://job/{id}/full", "Ein Stellenangebot mit vollständiger Beschreibung, verlinkt aus den Suchergebnissen"),
+    ("info.jobs://search/{cursor}", "Die nächste Seite einer durch ihr Limit gekürzten Suche, verlinkt aus search_jobs"),
+    ("info.performance.1", "Automatisches Caching mit 60 s TTL"),
+    ("info.performance.2", "Detaillierte Kennzahlen"),
+    ("info.performance.3", "Auswertung von Cache-Treffern und -Fehlgriffen"),
+    ("info.performance.4", "Vergleich der Antwortzeiten"),
+    (
+        "prompt.search.intro",
+        "Ich bin dein Assistent für die Stellensuche auf Nostr. Ich helfe dir, passende Stellenangebote im dezentralen Nostr-Netzwerk zu finden.",
+    ),
+    ("prompt.search.skills", "Geforderte Fähigkeiten: {skills}"),
+    (
+        "prompt.search.request",
+        "Suchanfrage: {query}\n{skills}\n\nBitte hilf mir, passende Stellenangebote zu finden, und gib mir Empfehlungen zu dieser Suche.",
+    ),
+    ("prompt.search.description", "Hilfe bei der Stellensuche: {query}"),
+    ("prompt.market.intro", "Ich analysiere den aktuellen Arbeitsmarkt auf Nostr und fasse die Erkenntnisse zusammen."),
+    (
+        "prompt.market.request",
+        "Bitte analysiere die auf Nostr verfügbaren Stellenangebote. Welche Fähigkeiten sind gefragt? Welche Firmen stellen ein? Wie sind die Gehaltsspannen der verschiedenen Positionen?",
+    ),
+    ("prompt.market.description", "Analyse des Nostr-Arbeitsmarkts"),
+    ("prompt.forecast.intro", "Ich erstelle eine Prognose der Einstellungen auf Nostr, ausschließlich auf Basis der Daten dieses Servers."),
+    ("prompt.forecast.week_in_progress", " (laufende Woche)"),
+    ("prompt.forecast.no_skills", "  (keine Fähigkeiten angegeben)"),
+    ("prompt.forecast.skill", "  • {skill}: {count} (vorher {previous}, {change})"),
+    ("prompt.forecast.no_salaries", "  (nicht genug Gehälter in beiden Hälften für einen Vergleich)"),
+    ("prompt.forecast.salary", "  • {group}: {earlier} → {later} ({change} %, aus {earlier_count} und {later_count} Gehältern)"),
+    ("prompt.forecast.truncated", "; die Relay-Frist hat die Stichprobe gekürzt, ältere Wochen sind daher womöglich unterzählt"),
+    (
+        "prompt.forecast.data",
+        "Daten: {listings} Stellenangebot(e), auf Nostr veröffentlicht in den letzten {weeks} Wochen (Wochen beginnen montags, UTC){truncated}\n\n\
+        Stellen pro Woche:\n{postings}\n\n\
+        Gefragte Fähigkeiten (Stellen, die sie in den letzten {later} Wochen verlangen, gegenüber den {earlier} davor):\n{skills}\n\n\
+        Gehaltsentwicklung (Median der Gehaltsmitte, gleiche Hälften):\n{salaries}",
+    ),
+    (
+        "prompt.forecast.request",
+        "{data}\n\n\
+        Schreibe auf Basis dieser Daten eine vorausschauende Analyse des Nostr-Arbeitsmarkts für die nächsten 4 bis 8 Wochen:\n\
+        1. Stellenvolumen: Beschleunigen sich die Einstellungen, verlangsamen sie sich oder bleiben sie gleich? Die aktuelle Woche ist unvollständig, ihre niedrige Zahl ist also kein Rückgang.\n\
+        2. Fähigkeiten: Welche gewinnen oder verlieren an Nachfrage, und welche lohnt es sich jetzt zu lernen?\n\
+        3. Gehälter: In welche Richtung bewegen sie sich, je Währung und Zeitraum?\n\
+        4. Verlässlichkeit: Gib an, wie sicher jede Aussage angesichts der Stichprobengrößen ist und wo die Daten zu dünn sind.\n\
+        Belege jede Aussage mit den obigen Zahlen und erfinde keine weiteren. Antworte auf Deutsch.",
+    ),
+    ("prompt.forecast.description", "Einstellungsprognose aus {listings} Stellenangebot(en) über {weeks} Wochen"),
+    ("error.timeout", "Die Relays haben nicht innerhalb von {ms} ms geantwortet"),
+    ("error.relay", "Relay {relay} ist fehlgeschlagen: {message}"),
+    ("error.relay_unknown", "Relay-Fehler: {message}"),
+    ("error.not_found", "Keine Stelle mit der ID: {job_id}"),
+    ("error.parse", "Ungültige(r) {what} '{input}': {message}"),
+    ("error.geocoding", "'{place}' konnte nicht geokodiert werden: {message}"),
+    ("error.exchange_rate", "Kein BTC/{fiat}-Wechselkurs: {message}"),
+    ("error.ingest", "{source} konnte nicht importiert werden: {message}"),
+    ("error.dvm", "DVM-Auftrag {request_id} ist fehlgeschlagen: {message}"),
+    ("error.overloaded", "Der Server ist ausgelastet; erneut versuchen in {secs} s"),
+    ("error.quota", "Tageskontingent {quota} von {limit} für diesen API-Schlüssel aufgebraucht; wird in {hours}h{minutes}m zurückgesetzt (Mitternacht UTC)"),
+    ("error.empty", "{field} darf nicht leer sein"),
+    ("error.range", "{field} muss zwischen {min} und {max} liegen"),
+    ("error.draft_title", "Ein neuer Entwurf braucht einen Titel (oder die job_id eines Entwurfs zum Bearbeiten)"),
+    ("error.no_draft", "Kein Entwurf mit der ID '{job_id}'; lege ihn mit draft_job an"),
+    ("error.no_saved_search", "Keine gespeicherte Suche namens '{name}'"),
+    ("error.metrics_format", "Unbekanntes Kennzahlenformat '{format}': erwartet \"text\" oder \"json\""),
+    ("error.audit_log", "Das Audit-Protokoll konnte nicht gelesen werden"),
+    ("error.disable_self", "set_tool_enabled kann sich nicht selbst deaktivieren"),
+    ("error.resource_not_found", "Ressource nicht gefunden"),
+    ("error.resource_query", "Ungültige Abfrage in {uri}: {message}"),
+    ("error.search_cursor", "Der Suchcursor '{cursor}' ist abgelaufen oder existierte nie; führe search_jobs erneut aus"),
+    ("error.no_digest", "Keine Übersicht für dieses Datum"),
+    ("error.no_owner", "Gespeicherter Zustand braucht eine MCP-Sitzung oder ein Bearer-Token im Authorization-Header"),
+    ("error.shutting_down", "Der Server wird heruntergefahren"),
+    ("error.admin_tool", "Das Werkzeug '{tool}' erfordert Administratorrechte"),
+    ("error.api_key", "Aufrufe brauchen einen API-Schlüssel aus API_KEYS, solange Kontingente gelten"),
+    ("error.admin_resource", "{uri} erfordert ein Admin-Token"),
+    ("error.resource_disabled", "{uri} ist nicht verfügbar, solange das Tool {tool} deaktiviert ist"),
+    ("banner.cached", "⚡ [AUS DEM CACHE]"),
+    ("banner.offline", "💾 [OFFLINE]"),
+    ("banner.stale", "📦 [AUS DEM CACHE - VERALTET, {age} alt: Relays nicht erreichbar]"),
+    ("banner.fresh", "🌐 [AKTUELL]"),
+    ("banner.partial", "⚠️ unvollständig: Relay-Frist erreicht"),
+    ("report.search.none", "Keine Stellenangebote gefunden, die den Kriterien entsprechen."),
+    ("report.search.did_you_mean", "🤔 Meintest du:"),
+    ("report.search.found", "{count} Stellenangebot(e) gefunden{banner}:"),
+    ("report.search.more", "{shown} von {total} Treffern angezeigt; die übrigen unter {uri}"),
+    ("report.search.page", "Treffer {first}-{last} von {total}{banner}:"),
+    ("link.job.full", "Vollständiges Stellenangebot mit Beschreibung"),
+    ("link.search.more", "Weitere Suchergebnisse"),
+    ("report.job.details", "📄 Alle Details zur Stelle:"),
+    ("report.history.title", "🏢 Veröffentlichungen von {npub}{banner}"),
+    ("report.company.also_spelled", " (auch geschrieben {spellings})"),
+    ("report.company.listings", "Stellen: {listings} ({active} aktiv, {expired} abgelaufen) von {posters} Absender(n)"),
+    ("report.company.posters", "🪪 Absender:"),
+    ("report.company.salary_transparency", "💸 Gehaltstransparenz:"),
+    ("report.company.reputation", "🗣️ Ruf:"),
+    ("report.company.recent", "Neueste Stellen:"),
+    ("report.stats.title", "📊 Statistiken der Nostr-Stellenangebote{banner}"),
+    ("report.stats.sample", "Stichprobe: {listings} neueste(s) Stellenangebot(e) ausgewertet ({requested} angefragt){partial}"),
+    ("report.stats.employment_types", "Anstellungsarten:"),
+    ("report.stats.top_companies", "Top-Firmen:"),
+    ("report.stats.top_skills", "Top-Fähigkeiten:"),
+    ("report.stats.locations", "Orte:"),
+    ("report.stats.salary_transparency", "Gehaltstransparenz:"),
+    ("report.stats.salary_complete", "{disclosed} von {listings} Stellenangebot(en) ({share} %) nennen ein vollständiges Gehalt"),
+    ("report.comparison.title", "📈 Arbeitsmarktvergleich"),
+    ("report.comparison.current", "Aktueller Zeitraum: {since} → {until}"),
+    ("report.comparison.previous", "Vorheriger Zeitraum: {since} → {until}"),
+    ("report.comparison.listings", "Stellen: {previous} → {current} ({delta})"),
+    ("report.comparison.top_skills", "Top-Fähigkeiten (vorher → jetzt):"),
+    ("report.comparison.median_salaries", "Mediangehälter (vorher → jetzt):"),
+    ("report.digest.daily", "📰 Tägliche Arbeitsmarktübersicht - {date}"),
+    ("report.digest.weekly", "📰 Wöchentliche Arbeitsmarktübersicht - {date}"),
+    ("report.digest.period", "Zeitraum: {since} → {until}{partial}"),
+    ("report.digest.listings", "Stellen: {listings} ({previous} im Zeitraum davor, {delta})"),
+    ("report.digest.new", "Neue Stellen:"),
+    ("report.digest.notable_salaries", "Bemerkenswerte Gehälter ({currency}):"),
+    ("report.digest.trending_skills", "Gefragte Fähigkeiten (vorher → jetzt):"),
+    ("report.digest.generated", "Erstellt: {at}"),
+    ("report.converted", "(sats/BTC-Gehälter umgerechnet zu {per_btc} {fiat}/BTC, Stand {as_of})"),
+    ("report.preferences_applied", "⚙️ Deine Einstellungen angewendet: {defaults}"),
+    ("report.resume.skills", "🧠 Vom DVM {provider} gefundene Fähigkeiten: {skills}"),
+    ("report.resume.none", "Kein Stellenangebot verlangt eine dieser Fähigkeiten."),
+    ("report.recommend.none", "Keine ungesehenen Stellen unter den neuesten teilen diese Fähigkeiten oder Firmen."),
+    ("report.bookmark.removed", "Lesezeichen für Stelle {job_id} entfernt"),
+    ("report.bookmark.missing", "Stelle {job_id} war nicht gemerkt"),
+    ("report.application.new", "📨 Neue Bewerbung wird verfolgt"),
+    ("report.application.moved", "📨 Bewerbung von {from} auf {to} verschoben"),
+    ("report.application.updated", "📨 Bewerbung aktualisiert"),
+    ("report.follow.added", "⭐ Du folgst jetzt {name}"),
+    ("report.follow.updated", "⭐ Gefolgter Arbeitgeber aktualisiert: {name}"),
+    ("report.follow.removed", "Du folgst {name} nicht mehr"),
+    ("report.follow.missing", "Du bist {name} nicht gefolgt"),
+    ("report.saved_search.created", "💾 Gespeicherte Suche '{name}' angelegt"),
+    ("report.saved_search.replaced", "💾 Gespeicherte Suche '{name}' ersetzt"),
+    ("report.saved_search.deleted", "Gespeicherte Suche '{name}' gelöscht"),
+    ("report.saved_search.missing", "Keine gespeicherte Suche namens '{name}'"),
+    ("report.preference.set", "Einstellung {key} = {value} gesetzt"),
+    ("report.preference.removed", "Einstellung {key} entfernt"),
+    ("report.expiring.none", "✅ Keine veröffentlichte Stelle läuft in den nächsten {days} Tag(en) ab"),
+    ("report.snapshot", "📦 Snapshot von {listings} Stelle(n), NDJSON mit einem signierten Event pro Zeile:"),
+    ("report.skill_pairs.with", "🔗 Zusammen mit {skill} genannte Fähigkeiten ({listings} Stelle(n))"),
+    ("report.geo.unspecified", "Nicht angegeben"),
+    ("report.raw_event.title", "🧾 Original-Event für {job_id} (Kind {kind}, Signatur {signature}){banner}"),
+    ("report.raw_event.valid", "gültig ✅"),
+    ("report.raw_event.invalid", "UNGÜLTIG ❌"),
+    ("report.raw_event.ingested", "Von der Import-Bridge für eine {source}-Stelle signiert"),
+    ("report.tag_vocabulary.title", "🏷️ Tag-Vokabular{banner}"),
+    ("report.tag_vocabulary.names", "{names} Tag-Name(n) in Gebrauch"),
+    ("report.search.source", "📡 Quelle: {source}"),
+    ("report.search.partial", "Teilergebnisse von {answered} von {relays} Relays:"),
+    ("report.search.hidden", "🛡️ {hidden} verdächtige(s) Stellenangebot(e) ausgeblendet"),
+    ("report.explain", "🔍 Abfrageerklärung\n\nCache-Schlüssel: {cache_key}\nCache: {cache}\n\nRelay-Filter:\n{filter}\n\nAbgleich im Client (UND über alle Felder, Teilzeichenfolge ohne Groß-/Kleinschreibung{fuzzy}):\n• Firma: {company}\n• Fähigkeit: {skill}\n• Anstellungsart: {employment_type}\n• Autor: {author}\n• in der Nähe: {near}\n• Mindestgehalt: {min_salary}\n• Limit: {limit}\n\nAbgefragte Relays ({relay_count}):\n{relays}"),
+    ("report.explain.hit", "TREFFER ({freshness}) — Eintrag ist {age}s alt (TTL {ttl}s), {events} Rohereignis(se) im Cache"),
+    ("report.explain.fresh", "frisch"),
+    ("report.explain.stale", "veraltet"),
+    ("report.explain.miss", "FEHLTREFFER — würde von den Relays laden (Timeout pro Relay {relay_ms}ms, Gesamtfrist {search_ms}ms)"),
+    ("report.explain.fuzzy", ", unscharf ≤ {edits} Änderungen"),
+    ("report.explain.near", "{lat}, {lon} im Umkreis von {km} km (Geohash-Zellen {cells})"),
+    ("report.explain.near_place", "{place} im Umkreis von {km} km (wird bei der Suche geokodiert)"),
+    ("report.explain.min_salary", "{amount} {currency} (Gehälter in sats/BTC werden bei der Suche umgerechnet)"),
+    ("report.explain.any", "beliebig"),
+    ("report.explain.anywhere", "überall"),
+    ("report.scam.likely", "⚠️ Wahrscheinlich Betrug"),
+    ("report.scam.possible", "⚠️ Mögliche Betrugsanzeichen"),
+    ("report.scam.warning", "{heading} (Wert {score}/100):\n{signals}\nSei vorsichtig, bevor du persönliche Daten teilst oder etwas bezahlst."),
+    ("report.scam.pay_to_apply", "Verlangt Geld von Bewerbern (\"{phrase}\"); seriöse Arbeitgeber verlangen nichts für eine Bewerbung"),
+    ("report.scam.messenger_only", "Einziger Kontakt ist ein Messenger ({messenger}), ohne E-Mail oder Website"),
+    ("report.scam.salary_above_market", "Zahlt das {factor}-fache des {group}-Mediangehalts von {median}"),
+    ("report.scam.new_key_no_profile", "Von einem Schlüssel veröffentlicht, der erst vor {days} Tag(en) auftauchte, ohne Profil"),
+    ("report.status.deleted", "❌ Vom Autor gelöscht (NIP-09)"),
+    ("report.status.expired", "⌛ Abgelaufen am {date} (NIP-40)"),
+    ("report.status.gone", "❓ Auf keinem Relay mehr zu finden"),
+    ("report.status.active", "✅ Aktiv"),
+    ("report.status.job", "{title} bei {company} (🆔 {job_id})"),
+    ("report.status.body", "{verdict}\n\nStelle: {job}\nGesehen auf: {seen_on}\nFehlt auf: {missing_from}"),
+    ("report.status.unreachable", "Nicht erreichbar: {relays}"),
+    ("report.status.checked", "Zuletzt gesehen: {last_seen}\nGeprüft: {checked}"),
+    ("report.status.never", "nie"),
+    ("report.status.none", "keine"),
+    ("report.preferences.saved", "✅ Einstellungen gespeichert"),
+    ("report.preferences.title", "⚙️ Einstellungen"),
+    ("report.preferences.body", "{title}\n\n• Fähigkeiten: {skills}\n• Mindestgehalt: {min_salary}\n• Orte: {locations}\n• Ausgeschlossene Firmen: {excluded}"),
+    ("report.preferences.any", "beliebig"),
+    ("report.preferences.none", "keine"),
+    ("report.saved_state.empty", "Noch nichts gespeichert. Nutze bookmark_job, save_search, follow_employer, set_preference oder set_application_status."),
+    ("report.saved_state.title", "🗂️ Gespeicherter Zustand"),
+    ("report.saved_state.section_empty", "{title}: keine"),
+    ("report.saved_state.bookmarks", "🔖 Lesezeichen"),
+    ("report.saved_state.searches", "💾 Gespeicherte Suchen"),
+    ("report.saved_state.followed", "⭐ Gefolgte Arbeitgeber"),
+    ("report.saved_state.viewed", "👀 Zuletzt angesehen"),
+    ("report.saved_state.drafts", "📝 Entwürfe"),
+    ("report.saved_state.applications", "📨 Bewerbungen"),
+    ("report.saved_state.bookmark", "{job_id} (gespeichert am {at})"),
+    ("report.saved_state.follow", "{name}{npub} (seit {at})"),
+    ("report.saved_state.view", "{job_id} (angesehen am {at})"),
+    ("report.saved_state.draft", "{job_id} - {title} (bearbeitet am {at})"),
+    ("report.saved_state.application", "{job_id} - {status} (aktualisiert am {at})"),
+    ("report.saved_state.follow_up", " ⏰ nachfassen?"),
+    ("report.draft.saved", "📝 Entwurf gespeichert"),
+    ("report.draft.preview", "👀 Entwurfsvorschau"),
+    ("report.draft.ready", "✅ Bereit zur Veröffentlichung mit publish_draft"),
+    ("report.draft.fix", "⚠️ Vor dem Veröffentlichen beheben:"),
+    ("report.draft.body", "{title} (nicht signiert oder veröffentlicht; der Event-Link wird beim Veröffentlichen vergeben)\n\n{summary}\n\n{details}\n{description}\n\n{status}"),
+    ("report.draft.no_description", "(keine Beschreibung)"),
+    ("report.interview.scheduled", "🗓️ Vorstellungsgespräch als Event {event_id} geplant"),
+    ("report.interview.upcoming", "🗓️ {count} anstehende(s) Vorstellungsgespräch(e):"),
+    ("report.interview.none", "Keine anstehenden Vorstellungsgespräche"),
+    ("report.tool.enabled", "✅ Tool '{tool}' aktiviert. Verbundene Clients wurden benachrichtigt, ihre Tool-Liste zu aktualisieren."),
+    ("report.tool.disabled", "✅ Tool '{tool}' deaktiviert. Verbundene Clients wurden benachrichtigt, ihre Tool-Liste zu aktualisieren."),
+    ("report.tool.already_enabled", "Tool '{tool}' ist bereits aktiviert."),
+    ("report.tool.already_disabled", "Tool '{tool}' ist bereits deaktiviert."),
+    ("report.strategy.unchanged", "Die Relay-Abfragestrategie ist bereits {strategy}."),
+    ("report.strategy.switched", "✅ Relay-Abfragestrategie von {previous} auf {strategy} umgestellt. Bis zum nächsten reload_config; setze RELAY_QUERY_STRATEGY, um sie beizubehalten."),
+    ("report.metrics.reset", "✅ Leistungsmetriken wurden zurückgesetzt; die bisherigen Summen bleiben in get_metrics_history erhalten."),
+    ("report.cache.cleared", "🗑️  Cache erfolgreich geleert\n\nCache-Statistik vor dem Leeren:\n• Gecachte Abfragen: {entries}\n• Cache-Treffer: {hits}\n• Trefferquote: {rate}%\n\n⚠️  Die nächsten Abfragen laden frische Daten von den Relays.\n💡 Mit get_performance_metrics lässt sich die Wirkung verfolgen."),
+];
@@ -0,0 +1,214 @@
+// src/jobs/backend.rs
+// The relay access NostrJobsClient depends on, so other event sources can be injected
+
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream, StreamExt};
+use nostr_sdk::prelude::*;
+
+/// Live connection state of one relay, as far as the backend knows it
+#[derive(Clone, Debug, PartialEq)]
+pub struct RelayConnection {
+    pub connected: bool,
+    /// Backend-specific state name (e.g. `Connected`, `Sleeping`)
+    pub status: String,
+    /// Average round-trip time, once enough messages were timed
+    pub latency: Option<Duration>,
+    /// When the current connection was established
+    pub connected_at: Option<Timestamp>,
+}
+
+/// Source and sink of Nostr events. `NostrRelays` talks to real relays;
+/// tests and alternative stores (a local database, a fixture set)
+/// implement this to drive the job board without the network.
+///
+/// Relays are addressed by the URLs in the configuration.
+pub trait RelayBackend: std::fmt::Debug + Send + Sync {
+    /// Start using `url`; fails on a URL the backend can't use
+    fn add_relay<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<(), String>>;
+
+    fn remove_relay<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<(), String>>;
+
+    /// Connect to every added relay; may return before all are connected
+    fn connect(&self) -> BoxFuture<'_, ()>;
+
+    /// Events matching `filter` stored on `url`, waiting at most `timeout`
+    fn fetch_events<'a>(
+        &'a self,
+        url: &'a str,
+        filter: Filter,
+        timeout: Duration,
+    ) -> BoxFuture<'a, Result<Vec<Event>, String>>;
+
+    /// Events matching `filter` stored on `url`, yielded as they arrive. The
+    /// stream ends when the relay sends EOSE or `timeout` passes; callers
+    /// keep whatever arrived before their own deadline. Defaults to a
+    /// single `fetch_events`.
+    fn stream_events<'a>(
+        &'a self,
+        url: &'a str,
+        filter: Filter,
+        timeout: Duration,
+    ) -> BoxFuture<'a, Result<BoxStream<'a, Event>, String>> {
+        Box::pin(async move {
+            let events = self.fetch_events(url, filter, timeout).await?;
+            Ok(stream::iter(events).boxed())
+        })
+    }
+
+    /// Connection state of `url`; `None` when the backend doesn't track it
+    fn connection<'a>(&'a self, _url: &'a str) -> BoxFuture<'a, Option<RelayConnection>> {
+        Box::pin(async { None })
+    }
+
+    /// Send a signed event to every relay
+    fn publish<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, Result<EventId, String>>;
+
+    /// Send a signed event to `url` only, failing with the relay's
+    /// rejection message. Defaults to `publish`, for backends that don't
+    /// tell relays apart.
+    fn publish_to<'a>(&'a self, _url: &'a str, event: &'a Event) -> BoxFuture<'a, Result<EventId, String>> {
+        self.publish(event)
+    }
+
+    /// The limits `url` advertises in its NIP-11 information document;
+    /// `None` when it advertises none or the backend doesn't know
+    fn relay_limits<'a>(&'a self, _url: &'a str) -> BoxFuture<'a, Option<Limitation>> {
+        Box::pin(async { None })
+    }
+
+    fn shutdown(&self) -> BoxFuture<'_, ()>;
+}
+
+/// How long fetching a relay's NIP-11 document may take
+const RELAY_INFO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// `RelayBackend` over the nostr-sdk relay pool
+#[derive(Clone, Debug, Default)]
+pub struct NostrRelays {
+    client: Client,
+    /// Fetches NIP-11 relay information documents
+    http: reqwest::Client,
+}
+
+impl NostrRelays {
+    /// A relay pool that signs with `keys`, when given
+    pub fn new(keys: Option<Keys>) -> Self {
+        let client = match keys {
+            Some(keys) => Client::builder().signer(keys).build(),
+            None => Client::default(),
+        };
+        Self { client, http: reqwest::Client::new() }
+    }
+}
+
+impl From<Client> for NostrRelays {
+    fn from(client: Client) -> Self {
+        Self { client, http: reqwest::Client::new() }
+    }
+}
+
+impl RelayBackend for NostrRelays {
+    fn add_relay<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            self.client.add_relay(url).await.map_err(|e| e.to_string())?;
+            Ok(())
+        })
+    }
+
+    fn remove_relay<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move { self.client.remove_relay(url).await.map_err(|e| e.to_string()) })
+    }
+
+    fn connect(&self) -> BoxFuture<'_, ()> {
+        Box::pin(self.client.connect())
+    }
+
+    fn fetch_events<'a>(
+        &'a self,
+        url: &'a str,
+        filter: Filter,
+        timeout: Duration,
+    ) -> BoxFuture<'a, Result<Vec<Event>, String>> {
+        Box::pin(async move {
+            self.client
+                .fetch_events_from([url], filter, timeout)
+                .await
+                .map(|events| events.into_iter().collect())
+                .map_err(|e| e.to_string())
+        })
+    }
+
+    fn stream_events<'a>(
+        &'a self,
+        url: &'a str,
+        filter: Filter,
+        timeout: Duration,
+    ) -> BoxFuture<'a, Result<BoxStream<'a, Event>, String>> {
+        Box::pin(async move {
+            self.client
+                .stream_events_from([url], filter, timeout)
+                .await
+                .map(StreamExt::boxed)
+                .map_err(|e| e.to_string())
+        })
+    }
+
+    fn connection<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Option<RelayConnection>> {
+        Box::pin(async move {
+            let relay = self.client.relay(url).await.ok()?;
+            let status = relay.status();
+            let connected = status == RelayStatus::Connected;
+            let stats = relay.stats();
+            Some(RelayConnection {
+                connected,
+                status: status.to_string(),
+                latency: stats.latency(),
+                connected_at: (connected && stats.connected_at().as_secs() > 0).then(|| stats.connected_at()),
+            })
+        })
+    }
+
+    fn publish<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, Result<EventId, String>> {
+        Box::pin(async move {
+            self.client
+                .send_event(event)
+                .await
+                .map(|output| output.val)
+                .map_err(|e| e.to_string())
+        })
+    }
+
+    fn publish_to<'a>(&'a self, url: &'a str, event: &'a Event) -> BoxFuture<'a, Result<EventId, String>> {
+        Box::pin(async move {
+            let output = self.client.send_event_to([url], event).await.map_err(|e| e.to_string())?;
+            match output.failed.into_values().next() {
+                Some(message) if output.success.is_empty() => Err(message),
+                _ => Ok(output.val),
+            }
+        })
+    }
+
+    fn relay_limits<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Option<Limitation>> {
+        Box::pin(async move {
+            // NIP-11: the document is served over HTTP(S) at the relay's address
+            let http_url = url.replacen("wss://", "https://", 1).replacen("ws://", "http://", 1);
+            let response = self
+                .http
+                .get(http_url)
+                .header("Accept", "application/nostr+json")
+                .timeout(RELAY_INFO_TIMEOUT)
+                .send()
+                .await
+                .inspect_err(|e| tracing::debug!(relay = %url, error = %e, "relay_info_failed"))
+                .ok()?;
+            let document = RelayInformationDocument::from_json(response.text().await.ok()?).ok()?;
+            document.limitation
+        })
+    }
+
+    fn shutdown(&self) -> BoxFuture<'_, ()> {
+        Box::pin(self.client.shutdown())
+    }
+}
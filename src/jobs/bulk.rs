@@ -0,0 +1,286 @@
+// src/jobs/bulk.rs
+// Many listings at once: CSV or NDJSON rows parsed into drafts, validated and
+// reported on row by row
+
+use std::str::FromStr;
+
+use nostr_sdk::prelude::*;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use super::draft::{JobDraft, MAX_EXPIRY_DAYS, slug};
+use super::model::SalaryRange;
+
+/// Columns (CSV) or keys (NDJSON) a row may have
+const FIELDS: &[&str] = &[
+    "job_id",
+    "title",
+    "company",
+    "location",
+    "employment_types",
+    "skills",
+    "salary_min",
+    "salary_max",
+    "salary_currency",
+    "salary_period",
+    "description",
+    "expires_at",
+    "expires_in_days",
+];
+/// Fields holding numbers
+const NUMBER_FIELDS: &[&str] = &["salary_min", "salary_max", "expires_at", "expires_in_days"];
+/// Fields holding lists, `;`-separated in CSV
+const LIST_FIELDS: &[&str] = &["employment_types", "skills"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BulkFormat {
+    /// A header row naming the columns, then one listing per record
+    Csv,
+    /// One JSON object per line
+    Ndjson,
+}
+
+impl BulkFormat {
+    /// NDJSON when the payload starts with `{`, CSV otherwise
+    pub fn detect(payload: &str) -> Self {
+        match payload.trim_start().starts_with('{') {
+            true => BulkFormat::Ndjson,
+            false => BulkFormat::Csv,
+        }
+    }
+}
+
+impl FromStr for BulkFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "csv" => Ok(BulkFormat::Csv),
+            "ndjson" | "jsonl" => Ok(BulkFormat::Ndjson),
+            other => Err(format!("unknown format '{}', expected csv or ndjson", other)),
+        }
+    }
+}
+
+impl std::fmt::Display for BulkFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            BulkFormat::Csv => "csv",
+            BulkFormat::Ndjson => "ndjson",
+        })
+    }
+}
+
+/// One listing of a bulk payload; unknown fields are rejected
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BulkRow {
+    /// Defaults to the company and title, so re-sending a payload keeps ids
+    job_id: Option<String>,
+    /// Left to `JobDraft::problems` when missing, so the row keeps its id
+    #[serde(default)]
+    title: String,
+    company: Option<String>,
+    location: Option<String>,
+    #[serde(default)]
+    employment_types: Vec<String>,
+    #[serde(default)]
+    skills: Vec<String>,
+    salary_min: Option<f64>,
+    salary_max: Option<f64>,
+    salary_currency: Option<String>,
+    salary_period: Option<String>,
+    #[serde(default)]
+    description: String,
+    /// NIP-40 expiration in seconds since the epoch
+    expires_at: Option<u64>,
+    expires_in_days: Option<u64>,
+}
+
+impl BulkRow {
+    /// Fails when `expires_in_days` is past `MAX_EXPIRY_DAYS`, as in `draft_job`
+    fn into_draft(self, now: u64) -> Result<JobDraft, String> {
+        let expires_in = match self.expires_in_days.filter(|d| *d > 0) {
+            Some(days) if days > MAX_EXPIRY_DAYS => {
+                return Err(format!("expires_in_days must be between 0 and {}, got {}", MAX_EXPIRY_DAYS, days));
+            }
+            Some(days) => Some(now + days * 86_400),
+            None => None,
+        };
+        let clean = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        let clean_all = |values: Vec<String>| -> Vec<String> {
+            values.into_iter().map(|v| v.trim().to_string()).filter(|v| !v.is_empty()).collect()
+        };
+        let company = clean(self.company);
+        let job_id = clean(self.job_id).unwrap_or_else(|| {
+            slug(&format!("{} {}", company.as_deref().unwrap_or_default(), self.title))
+        });
+        Ok(JobDraft {
+            job_id,
+            title: self.title.trim().to_string(),
+            company,
+            location: clean(self.location),
+            employment_types: clean_all(self.employment_types),
+            skills: clean_all(self.skills),
+            salary: self.salary_min.filter(|min| *min != 0.0).map(|min| SalaryRange {
+                min,
+                max: self.salary_max.unwrap_or(min),
+                currency: clean(self.salary_currency).map_or_else(|| "USD".to_string(), |c| c.to_uppercase()),
+                period: clean(self.salary_period).map_or_else(|| "year".to_string(), |p| p.to_lowercase()),
+            }),
+            description: self.description.trim().to_string(),
+            expires_at: self.expires_at.or(expires_in),
+            updated_at: now,
+        })
+    }
+}
+
+/// What became of one row of a bulk payload
+#[derive(Clone, Debug, PartialEq)]
+pub enum RowOutcome {
+    Published(EventId),
+    /// Would be published; a dry run stops here
+    Valid,
+    /// Doesn't parse or fails validation; not published
+    Invalid(String),
+    /// Valid, but no relay accepted it
+    Failed(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowReport {
+    /// From 1: the line of an NDJSON payload, the record after the header of a CSV one
+    pub row: usize,
+    pub job_id: Option<String>,
+    pub outcome: RowOutcome,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BulkReport {
+    pub rows: Vec<RowReport>,
+    pub dry_run: bool,
+}
+
+impl BulkReport {
+    pub fn count(&self, matches: impl Fn(&RowOutcome) -> bool) -> usize {
+        self.rows.iter().filter(|r| matches(&r.outcome)).count()
+    }
+}
+
+/// Rows ready to publish, as drafts with their row number, and the
+/// reports of the rows that aren't
+pub type ParsedRows = (Vec<(usize, JobDraft)>, Vec<RowReport>);
+
+/// The rows of `payload`, those that aren't ready reported as `Invalid`.
+/// Fails only when the payload as a whole can't be read (an unterminated
+/// CSV quote, an unknown column).
+pub fn parse_rows(payload: &str, format: BulkFormat) -> Result<ParsedRows, String> {
+    let objects: Vec<(usize, Result<Value, String>)> = match format {
+        BulkFormat::Ndjson => payload
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| (i + 1, serde_json::from_str(line.trim()).map_err(|e| e.to_string())))
+            .collect(),
+        BulkFormat::Csv => {
+            let mut records = parse_csv(payload)?.into_iter();
+            let header: Vec<String> = records.next().unwrap_or_default().iter().map(|h| h.trim().to_lowercase()).collect();
+            if let Some(unknown) = header.iter().find(|name| !FIELDS.contains(&name.as_str())) {
+                return Err(format!("unknown column '{}', expected some of {}", unknown, FIELDS.join(", ")));
+            }
+            records
+                .enumerate()
+                .map(|(i, record)| (i + 1, csv_object(&header, record)))
+                .collect()
+        }
+    };
+
+    let now = Timestamp::now().as_secs();
+    let mut seen = std::collections::HashSet::new();
+    let mut drafts = Vec::new();
+    let mut invalid = Vec::new();
+    for (row, object) in objects {
+        let parsed = object.and_then(|value| serde_json::from_value::<BulkRow>(value).map_err(|e| e.to_string()));
+        let draft = match parsed.and_then(|parsed| parsed.into_draft(now)) {
+            Ok(draft) => draft,
+            Err(e) => {
+                invalid.push(RowReport { row, job_id: None, outcome: RowOutcome::Invalid(e) });
+                continue;
+            }
+        };
+        let problems = draft.problems();
+        let problem = if !problems.is_empty() {
+            Some(problems.join("; "))
+        } else if !seen.insert(draft.job_id.clone()) {
+            Some(format!("job_id '{}' appears earlier in this batch", draft.job_id))
+        } else {
+            None
+        };
+        match problem {
+            Some(problem) => invalid.push(RowReport { row, job_id: Some(draft.job_id), outcome: RowOutcome::Invalid(problem) }),
+            None => drafts.push((row, draft)),
+        }
+    }
+    Ok((drafts, invalid))
+}
+
+/// One CSV record as the JSON object an NDJSON line would hold
+fn csv_object(header: &[String], record: Vec<String>) -> Result<Value, String> {
+    if record.len() > header.len() {
+        return Err(format!("{} fields, but the header names {}", record.len(), header.len()));
+    }
+    let mut object = Map::new();
+    for (name, cell) in header.iter().zip(record) {
+        let cell = cell.trim();
+        if cell.is_empty() {
+            continue;
+        }
+        let value = if NUMBER_FIELDS.contains(&name.as_str()) {
+            let number: f64 = cell.parse().map_err(|_| format!("{} '{}' is not a number", name, cell))?;
+            if name.starts_with("salary") { Value::from(number) } else { Value::from(number as u64) }
+        } else if LIST_FIELDS.contains(&name.as_str()) {
+            Value::from(cell.split(';').map(str::to_string).collect::<Vec<_>>())
+        } else {
+            Value::from(cell)
+        };
+        object.insert(name.clone(), value);
+    }
+    Ok(Value::Object(object))
+}
+
+/// Records of `csv` per RFC 4180: quoted fields may hold commas, line
+/// breaks and quotes doubled as `""`. Blank lines are skipped.
+fn parse_csv(csv: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = csv.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') => {}
+            (false, '\n') => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quoted CSV field".to_string());
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records.retain(|r: &Vec<String>| !(r.len() == 1 && r[0].trim().is_empty()));
+    Ok(records)
+}
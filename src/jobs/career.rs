@@ -0,0 +1,128 @@
+// src/jobs/career.rs
+// Career fairs, hiring meetups and other public NIP-52 calendar events,
+// found by their topic hashtags
+
+use std::collections::HashMap;
+
+use nostr_sdk::prelude::*;
+
+use super::ingest::days_from_civil;
+use super::interview::{CALENDAR_EVENT_KIND, INTERVIEW_HASHTAG};
+
+/// NIP-52 date-based calendar event (all-day or multi-day, dates without times)
+pub const DATE_EVENT_KIND: u16 = 31922;
+
+/// A public calendar event with one of the configured career topics
+#[derive(Clone, Debug, PartialEq)]
+pub struct CareerEvent {
+    /// The `d` tag
+    pub id: String,
+    pub title: String,
+    /// The `summary` tag, else the start of the content
+    pub summary: Option<String>,
+    pub description: String,
+    pub start: Timestamp,
+    /// Exclusive; for date-based events the day after the last one
+    pub end: Option<Timestamp>,
+    /// Date-based: `start` and `end` are UTC midnights
+    pub all_day: bool,
+    pub location: Option<String>,
+    /// `t` tags, lowercased
+    pub topics: Vec<String>,
+    pub author: PublicKey,
+    pub event_id: EventId,
+    pub kind: Kind,
+    pub created_at: Timestamp,
+}
+
+impl CareerEvent {
+    /// `None` for events that aren't calendar events, lack a `d` or a
+    /// readable `start`, or are interviews (private to their participants)
+    pub fn from_event(event: &Event) -> Option<Self> {
+        let all_day = match event.kind.as_u16() {
+            DATE_EVENT_KIND => true,
+            CALENDAR_EVENT_KIND => false,
+            _ => return None,
+        };
+        let tag = |name: &str| {
+            event
+                .tags
+                .iter()
+                .find(|t| t.kind() == TagKind::custom(name))
+                .and_then(|t| t.content())
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let topics: Vec<String> = event
+            .tags
+            .iter()
+            .filter(|t| t.kind() == TagKind::custom("t"))
+            .filter_map(|t| t.content())
+            .map(str::to_lowercase)
+            .collect();
+        if topics.iter().any(|t| t == INTERVIEW_HASHTAG) {
+            return None;
+        }
+        let time = |value: String| match all_day {
+            true => parse_day(&value),
+            false => value.parse::<u64>().ok().map(Timestamp::from),
+        };
+        Some(CareerEvent {
+            id: tag("d")?,
+            // `name` is the deprecated spelling of `title`
+            title: tag("title").or_else(|| tag("name")).unwrap_or_else(|| "Untitled event".to_string()),
+            summary: tag("summary"),
+            description: event.content.clone(),
+            start: tag("start").and_then(time)?,
+            end: tag("end").and_then(time),
+            all_day,
+            location: tag("location"),
+            topics,
+            author: event.pubkey,
+            event_id: event.id,
+            kind: event.kind,
+            created_at: event.created_at,
+        })
+    }
+
+    /// The NIP-19 `naddr` calendar clients open the event with
+    pub fn naddr(&self) -> Option<String> {
+        let coordinate = Coordinate::new(self.kind, self.author).identifier(&self.id);
+        Nip19Coordinate::new(coordinate, Vec::<RelayUrl>::new()).to_bech32().ok()
+    }
+
+    /// When the event is over: its end, else the end of its start day
+    /// (date-based) or its start (time-based)
+    pub fn ends_at(&self) -> Timestamp {
+        self.end.unwrap_or_else(|| match self.all_day {
+            true => Timestamp::from(self.start.as_secs().saturating_add(86_400)),
+            false => self.start,
+        })
+    }
+}
+
+/// `YYYY-MM-DD` as that day's UTC midnight
+fn parse_day(value: &str) -> Option<Timestamp> {
+    let mut parts = value.split('-').map(|p| p.parse::<i64>().ok());
+    let (year, month, day) = (parts.next()??, parts.next()??, parts.next()??);
+    let days = days_from_civil(year, month, day)?;
+    u64::try_from(days.checked_mul(86_400)?).ok().map(Timestamp::from)
+}
+
+/// Career events among `events` not over yet at `now`, the newest version
+/// of each (kind, author, id) only, soonest first
+pub fn upcoming(events: &[Event], now: Timestamp) -> Vec<CareerEvent> {
+    let mut newest: HashMap<(Kind, PublicKey, String), CareerEvent> = HashMap::new();
+    for event in events.iter().filter_map(CareerEvent::from_event) {
+        let key = (event.kind, event.author, event.id.clone());
+        match newest.get(&key) {
+            Some(known) if known.created_at >= event.created_at => {}
+            _ => {
+                newest.insert(key, event);
+            }
+        }
+    }
+    let mut upcoming: Vec<CareerEvent> = newest.into_values().filter(|e| e.ends_at() > now).collect();
+    upcoming.sort_by_key(|e| e.start);
+    upcoming
+}
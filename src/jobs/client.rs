@@ -0,0 +1,2593 @@
+// src/jobs/client.rs
+// Relay access and the query cache behind the typed search/get/stats API
+
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::StreamExt;
+use nostr_sdk::prelude::*;
+use tokio::sync::{Mutex, OwnedSemaphorePermit, RwLock, Semaphore, broadcast};
+use tokio::time::{timeout, timeout_at};
+
+use super::backend::{NostrRelays, RelayBackend, RelayConnection};
+use super::digest::{Digest, DigestPeriod};
+use super::bulk::{self, BulkFormat, BulkReport, RowOutcome, RowReport};
+use super::draft::JobDraft;
+use super::career::{self, CareerEvent, DATE_EVENT_KIND};
+use super::dvm::{self, ResumeAnalysis};
+use super::reputation::{self, Reputation};
+use super::scam::{self, ScamAssessment};
+use super::market::{self, Candidate};
+use super::interview::{self, CALENDAR_EVENT_KIND, INTERVIEW_HASHTAG, Interview, InterviewPlan};
+use super::engagement::{self, ApplicationMessage, DayEngagement, EngagementLog, Interaction, ListingAnalytics};
+use super::error::JobsError;
+use super::health::{HEALTH_CHECK_INTERVAL, ProbeSchedule, RelayProbe};
+use super::ingest::{BridgeReport, ExternalSource, IngestSource, IngestStore, SourceStatus};
+use super::model::JobListing;
+use super::publish::{self, Delivery, PublishQueue};
+use super::crosspost;
+use super::query::{JobQuery, ListingSource, Suggestion};
+use super::rates::{CoinGeckoRates, ExchangeRate, ExchangeRates};
+use super::snapshot::{self, ImportReport};
+use super::stats::{JobStats, tag_counts};
+use super::strategy::QueryStrategy;
+use super::supervisor::{Supervisor, TaskReport};
+use crate::config::ServerConfig;
+use crate::diagnostics::{trace_cache, trace_relay};
+use crate::error_reporting::{Dsn, ErrorReporter};
+use crate::metrics::{CacheUsage, MetricsHistory, MetricsPeriod, PerformanceMetrics};
+
+#[allow(dead_code)]
+const RELAY_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const WARM_UP_RETRY: Duration = Duration::from_secs(1);
+const STATS_PAGE_SIZE: usize = 100;
+const DVM_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// How often the digest schedule is re-checked (and a failed digest retried)
+const DIGEST_CHECK_INTERVAL: Duration = Duration::from_secs(300);
+/// How often the metrics rotation schedule is re-checked
+const METRICS_ROTATION_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// Requests within `POPULAR_WINDOW` a search needs before it is prefetched
+const PREFETCH_MIN_HITS: usize = 2;
+/// Searches not requested for this long stop being prefetched
+const POPULAR_WINDOW: Duration = Duration::from_secs(3600);
+/// New listings buffered per live subscriber before it starts missing some
+const LIVE_FEED_CAPACITY: usize = 256;
+/// Most listings taken from one live poll
+const LIVE_POLL_LIMIT: usize = 100;
+/// Cache entry holding imported snapshot events
+const SNAPSHOT_CACHE_KEY: &str = "snapshot";
+/// How often posted listings are checked for expiry reminders
+const EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(3600);
+/// Deadline of one expiry webhook call
+const EXPIRY_WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+/// Notes, reactions and reports asked of the relays per kind of source of
+/// a company's reputation
+const REPUTATION_SAMPLE_SIZE: usize = 500;
+/// Room left over the slowest adaptive relay timeout before a search gives up
+const ADAPTIVE_SEARCH_MARGIN: Duration = Duration::from_millis(500);
+pub const DEFAULT_STATS_SAMPLE_SIZE: usize = 500;
+pub const MAX_STATS_SAMPLE_SIZE: usize = 5000;
+
+tokio::task_local! {
+    static CALL_TIMEOUT: Duration;
+}
+
+/// Run `fut` with `deadline` (when given) replacing the configured search
+/// and stats timeouts of every `NostrJobsClient` call it makes, for callers
+/// that can wait longer, or less, than the board's defaults
+pub async fn with_call_timeout<F: Future>(deadline: Option<Duration>, fut: F) -> F::Output {
+    match deadline {
+        Some(deadline) => CALL_TIMEOUT.scope(deadline, fut).await,
+        None => fut.await,
+    }
+}
+
+// ==================== Cache Types ====================
+
+#[derive(Clone, Debug)]
+struct CachedEvents {
+    events: Vec<Event>,
+    timestamp: std::time::Instant,
+    /// Approximate footprint: the serialized JSON size of the cached events
+    size_bytes: usize,
+}
+
+impl CachedEvents {
+    fn new(events: Vec<Event>) -> Self {
+        let size_bytes = events.iter().map(|e| e.as_json().len()).sum();
+        Self {
+            events,
+            timestamp: std::time::Instant::now(),
+            size_bytes,
+        }
+    }
+}
+
+/// What one pass of the retention policy dropped from the cache
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Compaction {
+    /// Events posted longer ago than `retention_max_age`
+    pub purged_old: usize,
+    /// Events whose expiration passed longer ago than `retention_expired_grace`
+    pub purged_expired: usize,
+    /// Entries left empty and removed
+    pub entries_removed: usize,
+}
+
+/// Counts one relay fetch as queued for as long as it is held
+struct QueuedFetch<'a>(&'a std::sync::atomic::AtomicUsize);
+
+impl<'a> QueuedFetch<'a> {
+    fn new(count: &'a std::sync::atomic::AtomicUsize) -> Self {
+        count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Self(count)
+    }
+}
+
+impl Drop for QueuedFetch<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+/// A search recently asked for, with the filter needed to refetch it
+#[derive(Clone, Debug)]
+struct PopularQuery {
+    filter: Filter,
+    hits: usize,
+    last_hit: std::time::Instant,
+    /// Empty results aren't cached, so this keeps them from being refetched every check
+    prefetched_at: Option<std::time::Instant>,
+}
+
+fn cache_usage(cache: &HashMap<String, CachedEvents>) -> CacheUsage {
+    CacheUsage {
+        entries: cache.len(),
+        events: cache.values().map(|c| c.events.len()).sum(),
+        bytes: cache.values().map(|c| c.size_bytes).sum(),
+    }
+}
+
+/// Summary of one cache entry, for diagnostics
+#[derive(Clone, Debug)]
+pub(crate) struct CacheEntry {
+    pub(crate) key: String,
+    pub(crate) events: usize,
+    pub(crate) size_bytes: usize,
+    pub(crate) age: Duration,
+}
+
+// ==================== Results ====================
+
+/// Where a result came from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    Cache,
+    /// Answered from the cached and ingested listings in offline mode
+    Offline,
+    /// Cached `age` ago, longer than the configured TTL, and served because
+    /// the relays couldn't be reached to refresh it
+    StaleCache { age: Duration },
+    Relays,
+}
+
+impl Source {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Source::Cache => "cache",
+            Source::Offline => "offline",
+            Source::StaleCache { .. } => "stale_cache",
+            Source::Relays => "relays",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SearchResults {
+    /// Matching listings, newest first, at most `limit`
+    pub listings: Vec<JobListing>,
+    /// Listings that matched before the cap, so callers can tell when
+    /// `listings` was cut short
+    pub total: usize,
+    /// Other representations of a listing in `listings` (the same job as
+    /// another kind or from the ingestion bridge), by its job id
+    pub crossposts: HashMap<String, Vec<JobListing>>,
+    /// Near misses, only computed when nothing matched
+    pub suggestions: Vec<Suggestion>,
+    pub source: Source,
+}
+
+/// Search results from the relays that answered so far, reported while
+/// the others are still being read
+#[derive(Clone, Debug)]
+pub struct PartialResults {
+    pub results: SearchResults,
+    /// Relays that have answered
+    pub answered: usize,
+    /// Relays queried
+    pub relays: usize,
+}
+
+/// Called with the events gathered so far after each relay but the last
+/// answers, and how many of how many relays that is
+type OnPartial<'a> = &'a (dyn Fn(Vec<Event>, usize, usize) + Send + Sync);
+
+/// How one configured relay is doing: its connection and what it served
+/// this session
+#[derive(Clone, Debug)]
+pub struct RelayReport {
+    pub url: String,
+    /// `None` when the backend doesn't track connections
+    pub connection: Option<RelayConnection>,
+    pub fetches: usize,
+    pub failures: usize,
+    pub events_received: usize,
+    pub avg_fetch_ms: f64,
+    /// How long ago a fetch last returned events from it
+    pub last_event: Option<Duration>,
+    /// How long ago it last answered a fetch, with or without events
+    pub last_answer: Option<Duration>,
+    /// Per-fetch deadline it gets now: `relay_timeout`, or the learned one
+    /// with adaptive timeouts on
+    pub timeout: Duration,
+    /// Last health probe; `None` until it was probed
+    pub probe: Option<RelayProbe>,
+}
+
+/// The most recent listings, as analyzed by the statistics
+#[derive(Clone, Debug)]
+pub struct Sample {
+    pub listings: Vec<JobListing>,
+    /// Pagination stopped at the deadline before the sample was complete
+    pub truncated: bool,
+    pub source: Source,
+}
+
+impl Sample {
+    pub fn stats(&self) -> JobStats {
+        JobStats::from_listings(&self.listings)
+    }
+
+    /// `stats`, with salaries in sats or BTC converted at `rate`
+    pub fn stats_in(&self, rate: Option<&ExchangeRate>) -> JobStats {
+        JobStats::from_listings_in(&self.listings, rate)
+    }
+
+    /// Listings per value of the tag `name`, e.g. `location` or `currency`
+    pub fn tag_counts(&self, name: &str) -> HashMap<String, usize> {
+        tag_counts(&self.listings, name)
+    }
+}
+
+/// Outcome of re-checking one listing against every relay
+#[derive(Clone, Debug)]
+pub struct ListingStatus {
+    /// The listing, from the relays or else from the cache
+    pub listing: Option<JobListing>,
+    /// Relays that returned the listing
+    pub seen_on: Vec<String>,
+    /// Relays that answered without it
+    pub missing_from: Vec<String>,
+    /// Relays that failed or timed out
+    pub unreachable: Vec<String>,
+    /// Its author published a NIP-09 deletion for it
+    pub deleted: bool,
+    pub checked_at: Timestamp,
+    /// Now when a relay returned it, else when it was cached
+    pub last_seen: Option<Timestamp>,
+}
+
+impl ListingStatus {
+    /// Still on a relay, not deleted and not expired
+    pub fn is_active(&self) -> bool {
+        !self.seen_on.is_empty() && !self.deleted && !self.listing.as_ref().is_some_and(JobListing::is_expired)
+    }
+}
+
+/// Where the live feed has got to: listings are fetched from `since` on
+/// (inclusive), skipping the ones already sent at exactly that second
+#[derive(Debug)]
+struct LiveCursor {
+    since: Timestamp,
+    seen: std::collections::HashSet<EventId>,
+}
+
+impl Default for LiveCursor {
+    fn default() -> Self {
+        Self { since: Timestamp::now(), seen: Default::default() }
+    }
+}
+
+impl LiveCursor {
+    /// The events not sent before, moving the cursor past them
+    fn advance(&mut self, events: Vec<Event>) -> Vec<Event> {
+        let fresh: Vec<Event> = events
+            .into_iter()
+            .filter(|e| e.created_at >= self.since && !self.seen.contains(&e.id))
+            .collect();
+        if let Some(newest) = fresh.iter().map(|e| e.created_at).max() {
+            if newest > self.since {
+                self.since = newest;
+                self.seen.clear();
+            }
+            self.seen.extend(fresh.iter().filter(|e| e.created_at == newest).map(|e| e.id));
+        }
+        fresh
+    }
+}
+
+// ==================== Client ====================
+
+/// Fetches job listings from the configured relays, caching results per
+/// query and tracking relay health and performance metrics.
+#[derive(Clone, Debug)]
+pub struct NostrJobsClient {
+    backend: Arc<dyn RelayBackend>,
+    cache: Arc<RwLock<HashMap<String, CachedEvents>>>,
+    /// Slots for concurrent relay fetches, `max_concurrent_fetches` of them
+    fetch_slots: Arc<Semaphore>,
+    /// Relay fetches currently waiting for a slot
+    queued_fetches: Arc<std::sync::atomic::AtomicUsize>,
+    /// Search cache keys by how often they were requested, for the prefetcher
+    popular: Arc<Mutex<HashMap<String, PopularQuery>>>,
+    relay_healthy: Arc<Mutex<bool>>,
+    /// Health probe state of each configured relay
+    probes: Arc<Mutex<HashMap<String, ProbeSchedule>>>,
+    metrics: Arc<RwLock<PerformanceMetrics>>,
+    /// Metrics totals archived by rotation or reset
+    metrics_history: Arc<RwLock<MetricsHistory>>,
+    /// Current configuration; swapped wholesale on reload
+    config: Arc<std::sync::RwLock<Arc<ServerConfig>>>,
+    /// Reports relays that keep failing (and, from the server, tools and panics)
+    errors: ErrorReporter,
+    /// BTC prices for converting sats/BTC salaries; `None` when disabled
+    rates: Option<ExchangeRates>,
+    /// Listings pulled from external job boards, merged into every fetch
+    ingest: IngestStore,
+    /// Generated market digests, oldest first
+    digests: Arc<RwLock<VecDeque<Digest>>>,
+    /// Newly seen listings, for live subscribers
+    live: broadcast::Sender<JobListing>,
+    /// Restarts the background loops when they panic
+    tasks: Supervisor,
+    /// Paces publishes to each relay and remembers its NIP-11 limits
+    publishing: Arc<PublishQueue>,
+    /// Signs listings published from drafts; `None` disables publishing them
+    signer: Option<Keys>,
+    /// Listing events an expiry reminder was already sent for
+    reminded: Arc<Mutex<HashSet<EventId>>>,
+    /// Impressions and views of the listings signed with the posting key
+    engagement: Arc<EngagementLog>,
+}
+
+impl NostrJobsClient {
+    /// Connect to the configured relays through nostr-sdk; `keys`, when
+    /// given, become the relay client's signer.
+    pub async fn connect(config: ServerConfig, keys: Option<Keys>) -> Result<Self, JobsError> {
+        Self::with_backend(config, Arc::new(NostrRelays::new(keys))).await
+    }
+
+    /// Validate the configuration, add the configured relays to `backend`,
+    /// start connecting in the background and start the periodic relay
+    /// health check.
+    pub async fn with_backend(config: ServerConfig, backend: Arc<dyn RelayBackend>) -> Result<Self, JobsError> {
+        if config.relays.is_empty() && !config.offline {
+            return Err(JobsError::Config("At least one relay is required".to_string()));
+        }
+        if config.kinds.is_empty() {
+            return Err(JobsError::Config("At least one event kind is required".to_string()));
+        }
+        for author in &config.authors {
+            PublicKey::parse(author).map_err(|e| JobsError::ParseError {
+                what: "author",
+                input: author.clone(),
+                message: e.to_string(),
+            })?;
+        }
+
+        for source in &config.ingest_sources {
+            IngestSource::parse(source)?;
+        }
+        if let Some(dsn) = &config.sentry_dsn {
+            Dsn::parse(dsn).map_err(|e| JobsError::Config(format!("SENTRY_DSN: {}", e)))?;
+        }
+        let ingest_keys = match &config.ingest_secret_key {
+            Some(key) => Keys::parse(key).map_err(|e| JobsError::ParseError {
+                what: "ingest key",
+                input: String::new(),
+                message: e.to_string(),
+            })?,
+            None if config.bridge_publish => {
+                return Err(JobsError::Config(
+                    "BRIDGE_PUBLISH needs INGEST_SECRET_KEY, so republished listings keep one author across restarts".to_string(),
+                ));
+            }
+            None => Keys::generate(),
+        };
+        let signer = match &config.posting_secret_key {
+            Some(key) => Some(Keys::parse(key).map_err(|e| JobsError::ParseError {
+                what: "posting key",
+                input: String::new(),
+                message: e.to_string(),
+            })?),
+            None => None,
+        };
+        if let Some(recipient) = &config.expiry_reminder_recipient {
+            PublicKey::parse(recipient).map_err(|e| JobsError::ParseError {
+                what: "reminder recipient",
+                input: recipient.clone(),
+                message: e.to_string(),
+            })?;
+        }
+
+        for relay in &config.relays {
+            backend
+                .add_relay(relay)
+                .await
+                .map_err(|message| JobsError::ParseError { what: "relay", input: relay.clone(), message })?;
+        }
+
+        let rates = config
+            .exchange_rate_url
+            .as_ref()
+            .map(|url| ExchangeRates::new(Arc::new(CoinGeckoRates::new(url)), config.exchange_rate_ttl));
+        let probes = config.relays.iter().map(|url| (url.clone(), ProbeSchedule::new(HEALTH_CHECK_INTERVAL))).collect();
+        let fetch_slots = Arc::new(Semaphore::new(config.max_concurrent_fetches));
+        let config = Arc::new(std::sync::RwLock::new(Arc::new(config)));
+        let jobs = Self {
+            backend,
+            cache: Arc::new(RwLock::new(HashMap::new())),
+            fetch_slots,
+            queued_fetches: Arc::default(),
+            popular: Arc::new(Mutex::new(HashMap::new())),
+            relay_healthy: Arc::new(Mutex::new(false)),
+            probes: Arc::new(Mutex::new(probes)),
+            metrics: Arc::new(RwLock::new(PerformanceMetrics::default())),
+            metrics_history: Arc::default(),
+            errors: ErrorReporter::new(config.clone()),
+            config,
+            rates,
+            ingest: IngestStore::new(ingest_keys),
+            digests: Arc::new(RwLock::new(VecDeque::new())),
+            live: broadcast::channel(LIVE_FEED_CAPACITY).0,
+            tasks: Supervisor::default(),
+            publishing: Arc::default(),
+            signer,
+            reminded: Arc::default(),
+            engagement: Arc::default(),
+        };
+
+        jobs.connect_in_background();
+        jobs.supervise("health_check", |jobs| async move { jobs.health_check_loop().await });
+        jobs.supervise("ingest", |jobs| async move { jobs.ingest_loop().await });
+        jobs.supervise("digest", |jobs| async move { jobs.digest_loop().await });
+        jobs.supervise("metrics_rotation", |jobs| async move { jobs.metrics_rotation_loop().await });
+        jobs.supervise("statsd", crate::statsd::emit_loop);
+        jobs.supervise("prefetch", |jobs| async move { jobs.prefetch_loop().await });
+        jobs.supervise("live", |jobs| async move { jobs.live_loop().await });
+        jobs.supervise("compaction", |jobs| async move { jobs.compaction_loop().await });
+        jobs.supervise("expiry_reminders", |jobs| async move { jobs.expiry_reminder_loop().await });
+
+        Ok(jobs)
+    }
+
+    /// Run a background loop on a clone of this client, restarted with
+    /// backoff if it panics
+    fn supervise<F, Fut>(&self, name: &'static str, run: F)
+    where
+        F: Fn(NostrJobsClient) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let jobs = self.clone();
+        self.tasks.supervise(name, move || run(jobs.clone()));
+    }
+
+    /// Supervise a loop that isn't the client's own, such as the MCP
+    /// server's, so it is reported with the others
+    pub(crate) fn supervise_task<F, Fut>(&self, name: &'static str, start: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.tasks.supervise(name, start);
+    }
+
+    /// The supervised background loops, and one-shot tasks that panicked
+    pub fn task_reports(&self) -> Vec<TaskReport> {
+        self.tasks.reports()
+    }
+
+    /// Probe each relay when its schedule says so: every
+    /// `HEALTH_CHECK_INTERVAL` while it answers, backing off while it
+    /// doesn't, and often right after it recovers
+    async fn health_check_loop(&self) {
+        loop {
+            let next = self.probes.lock().await.values().map(ProbeSchedule::next_at).min();
+            // Relays added meanwhile are picked up within one interval
+            let wake = next.unwrap_or_else(|| std::time::Instant::now() + HEALTH_CHECK_INTERVAL);
+            tokio::time::sleep_until(wake.min(std::time::Instant::now() + HEALTH_CHECK_INTERVAL).into()).await;
+            self.probe_relays_where(|schedule| schedule.is_due(std::time::Instant::now())).await;
+        }
+    }
+
+    /// Probe every relay now, regardless of schedule
+    pub async fn probe_relays(&self) {
+        self.probe_relays_where(|_| true).await;
+    }
+
+    async fn probe_relays_where(&self, due: impl Fn(&ProbeSchedule) -> bool) {
+        let config = self.config();
+        if config.offline {
+            return;
+        }
+        let relays: Vec<String> = {
+            let mut probes = self.probes.lock().await;
+            probes.retain(|url, _| config.relays.contains(url));
+            config
+                .relays
+                .iter()
+                .filter(|url| due(probes.entry(url.to_string()).or_insert_with(|| ProbeSchedule::new(Duration::ZERO))))
+                .cloned()
+                .collect()
+        };
+        if relays.is_empty() {
+            return;
+        }
+
+        let outcomes = futures::future::join_all(relays.iter().map(|url| self.probe(url))).await;
+        let mut probes = self.probes.lock().await;
+        for (url, outcome) in relays.iter().zip(outcomes) {
+            let schedule = probes.entry(url.clone()).or_insert_with(|| ProbeSchedule::new(Duration::ZERO));
+            let error = outcome.as_ref().err().cloned();
+            if schedule.record(outcome) {
+                match error {
+                    None => tracing::info!(relay = %url, "relay_probe_recovered"),
+                    Some(e) => tracing::warn!(relay = %url, error = %e, "relay_probe_failed"),
+                }
+            }
+        }
+        let healthy = probes.values().any(ProbeSchedule::is_healthy);
+        drop(probes);
+
+        let was_healthy = std::mem::replace(&mut *self.relay_healthy.lock().await, healthy);
+        if healthy && !was_healthy {
+            tracing::info!("relay_health_recovered");
+        } else if !healthy && was_healthy {
+            tracing::warn!("relay_health_degraded");
+        }
+    }
+
+    /// Ask `url` for one listing of the board's kinds within `relay_timeout`
+    async fn probe(&self, url: &str) -> Result<(), String> {
+        let deadline = self.config().relay_timeout;
+        match timeout(deadline, self.backend.fetch_events(url, self.listing_filter().limit(1), deadline)).await {
+            Ok(Ok(events)) if events.is_empty() => Err("no job listings".to_string()),
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(format!("no answer within {}ms", deadline.as_millis())),
+        }
+    }
+
+    /// Snapshot of the current configuration
+    pub fn config(&self) -> Arc<ServerConfig> {
+        self.config.read().expect("config lock poisoned").clone()
+    }
+
+    /// A `Config` error saying `action` needs the relays when `offline` is set
+    fn ensure_online(&self, action: &str) -> Result<(), JobsError> {
+        match self.config().offline {
+            true => Err(JobsError::Config(format!("{} needs the relays, but OFFLINE is set", action))),
+            false => Ok(()),
+        }
+    }
+
+    /// Deadline for a search, job lookup or latest-listings fetch: the call's
+    /// own timeout inside `with_call_timeout`, else `search_timeout`, raised
+    /// to fit the slowest relay's adaptive timeout
+    async fn search_timeout(&self) -> Duration {
+        if let Ok(deadline) = CALL_TIMEOUT.try_with(|t| *t) {
+            return deadline;
+        }
+        let config = self.config();
+        if !config.adaptive_timeouts {
+            return config.search_timeout;
+        }
+        let slowest = self.relay_timeouts(&config.relays).await.into_iter().max().unwrap_or_default();
+        config.search_timeout.max(slowest + ADAPTIVE_SEARCH_MARGIN)
+    }
+
+    /// Per-fetch deadline of each of `relays`: learned from their latency
+    /// with adaptive timeouts on, else `relay_timeout`
+    async fn relay_timeouts(&self, relays: &[String]) -> Vec<Duration> {
+        let config = self.config();
+        if !config.adaptive_timeouts {
+            return vec![config.relay_timeout; relays.len()];
+        }
+        let metrics = self.metrics.read().await;
+        relays.iter().map(|url| metrics.relay_timeout(url, config.relay_timeout)).collect()
+    }
+
+    /// Deadline for paginating a statistics sample, overridden like `search_timeout`
+    fn stats_timeout(&self) -> Duration {
+        CALL_TIMEOUT.try_with(|t| *t).unwrap_or_else(|_| self.config().stats_timeout)
+    }
+
+    /// Reporter for panics and repeated failures, sending to `sentry_dsn`
+    pub fn error_reporter(&self) -> &ErrorReporter {
+        &self.errors
+    }
+
+    pub(crate) fn set_config(&self, config: Arc<ServerConfig>) {
+        *self.config.write().expect("config lock poisoned") = config;
+    }
+
+    /// Connection state and session totals for each configured relay
+    pub async fn relay_reports(&self) -> Vec<RelayReport> {
+        let relays = self.config().relays.clone();
+        let connections = futures::future::join_all(relays.iter().map(|url| self.backend.connection(url))).await;
+        let timeouts = self.relay_timeouts(&relays).await;
+        let probes = self.probes.lock().await;
+        let metrics = self.metrics.read().await;
+        relays
+            .into_iter()
+            .zip(connections)
+            .zip(timeouts)
+            .map(|((url, connection), timeout)| {
+                let m = metrics.relays.get(&url).cloned().unwrap_or_default();
+                RelayReport {
+                    connection,
+                    fetches: m.fetches,
+                    failures: m.failures,
+                    events_received: m.events_received,
+                    avg_fetch_ms: m.avg_fetch_time(),
+                    last_event: m.last_event_at.map(|at| at.elapsed()),
+                    last_answer: m.last_answer_at.map(|at| at.elapsed()),
+                    timeout,
+                    probe: probes.get(&url).and_then(ProbeSchedule::report),
+                    url,
+                }
+            })
+            .collect()
+    }
+
+    /// Disconnect from `removed` relays and connect to `added` ones. The
+    /// relay list itself comes from the config.
+    pub(crate) async fn update_relays(&self, added: &[&String], removed: &[&String]) {
+        {
+            let mut probes = self.probes.lock().await;
+            for relay in removed {
+                probes.remove(*relay);
+            }
+            for relay in added {
+                probes.insert(relay.to_string(), ProbeSchedule::new(Duration::ZERO));
+            }
+        }
+        for relay in removed {
+            if let Err(e) = self.backend.remove_relay(relay).await {
+                tracing::warn!(relay = %relay, error = %e, "relay_remove_failed");
+            }
+        }
+        for relay in added {
+            if let Err(e) = self.backend.add_relay(relay).await {
+                tracing::warn!(relay = %relay, error = %e, "relay_add_failed");
+            }
+        }
+        if !added.is_empty() {
+            self.connect_in_background();
+        }
+    }
+
+    /// Start connecting to the configured relays, unless offline
+    pub(crate) fn connect_in_background(&self) {
+        if self.config().offline {
+            return;
+        }
+        let backend = self.backend.clone();
+        tokio::spawn(async move {
+            let _ = timeout(Duration::from_secs(15), backend.connect()).await;
+        });
+    }
+
+    /// Re-fetch the external job boards every `ingest_interval`, starting
+    /// now. Sources are re-read from the config each time, so a reload can
+    /// add or remove them.
+    async fn ingest_loop(&self) {
+        loop {
+            if !self.config().ingest_sources.is_empty() {
+                self.refresh_ingest().await;
+                if self.config().bridge_publish
+                    && let Err(e) = self.publish_bridged().await
+                {
+                    tracing::warn!(error = %e, "bridge_publish_failed");
+                }
+            }
+            tokio::time::sleep(self.config().ingest_interval).await;
+        }
+    }
+
+    /// Fetch every configured external job board now. Invalid sources are
+    /// logged and skipped.
+    pub async fn refresh_ingest(&self) -> Vec<SourceStatus> {
+        let config = self.config();
+        let sources: Vec<IngestSource> = config
+            .ingest_sources
+            .iter()
+            .filter_map(|s| {
+                IngestSource::parse(s)
+                    .inspect_err(|e| tracing::warn!(source = %s, error = %e, "invalid_ingest_source"))
+                    .ok()
+            })
+            .collect();
+        self.ingest.refresh(&sources, self.listing_kind()).await
+    }
+
+    /// Publish the ingested listings to the relays. Postings already there
+    /// (same event id) are skipped; a posting that changed at the source is
+    /// republished and its old event deleted (NIP-09), as are bridged
+    /// listings whose posting is gone. Close dates become NIP-40
+    /// expirations.
+    pub async fn publish_bridged(&self) -> Result<BridgeReport, JobsError> {
+        let bridge = self.ingest.public_key();
+        let key = format!("bridge:{}", bridge.to_hex());
+        let filter = self.kinds_filter().author(bridge).limit(MAX_STATS_SAMPLE_SIZE);
+        let on_relays: Vec<JobListing> =
+            self.fetch_from_relays(filter, &key, None).await?.into_iter().map(JobListing::from).collect();
+
+        let current: Vec<JobListing> = self.ingest.events().await.into_iter().map(JobListing::from).collect();
+        let published_ids: std::collections::HashSet<EventId> = on_relays.iter().map(|l| l.event.id).collect();
+        let mut report = BridgeReport { ran_at: Some(Timestamp::now()), ..Default::default() };
+
+        // Job ids whose current version is on the relays, so older versions can go
+        let mut live: std::collections::HashSet<&str> = std::collections::HashSet::new();
+        for listing in &current {
+            if published_ids.contains(&listing.event.id) {
+                report.unchanged += 1;
+                live.insert(&listing.job_id);
+                continue;
+            }
+            let replaces = on_relays.iter().any(|l| l.job_id == listing.job_id);
+            match self.publish(&listing.event).await {
+                Ok(_) => {
+                    live.insert(&listing.job_id);
+                    if replaces {
+                        report.updated += 1;
+                    } else {
+                        report.published += 1;
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(job_id = %listing.job_id, error = %e, "bridge_publish_failed");
+                    report.failed += 1;
+                }
+            }
+        }
+
+        let mut stale = Vec::new();
+        for listing in &on_relays {
+            if current.iter().any(|c| c.event.id == listing.event.id) || listing.is_expired() {
+                continue;
+            }
+            if live.contains(listing.job_id.as_str()) {
+                stale.push(listing.event.id);
+            } else if !current.iter().any(|c| c.job_id == listing.job_id) {
+                report.withdrawn += 1;
+                stale.push(listing.event.id);
+            }
+        }
+        if !stale.is_empty() {
+            let deletion = self.ingest.deletion(stale.iter().copied())?;
+            if let Err(e) = self.publish(&deletion).await {
+                tracing::warn!(events = stale.len(), error = %e, "bridge_delete_failed");
+                report.failed += 1;
+            }
+        }
+
+        tracing::info!(
+            published = report.published,
+            updated = report.updated,
+            withdrawn = report.withdrawn,
+            unchanged = report.unchanged,
+            failed = report.failed,
+            "bridge_published"
+        );
+        self.ingest.record_bridge(report.clone()).await;
+        Ok(report)
+    }
+
+    /// Outcome of the last `publish_bridged`, if any
+    pub async fn last_bridge(&self) -> Option<BridgeReport> {
+        self.ingest.last_bridge().await
+    }
+
+    /// Status of each external job board as of its last refresh
+    pub async fn ingest_status(&self) -> Vec<SourceStatus> {
+        self.ingest.status().await
+    }
+
+    /// The key ingested listings are signed with; add it to `AUTHORS` to
+    /// show them on an allowlisted board
+    pub fn ingest_public_key(&self) -> PublicKey {
+        self.ingest.public_key()
+    }
+
+    /// Where `listing` came from, when it was ingested from an external
+    /// job board rather than published on Nostr
+    pub fn external_source(&self, listing: &JobListing) -> Option<ExternalSource> {
+        self.ingest.source_of(listing)
+    }
+
+    /// Sign listings published from drafts with `keys` instead of
+    /// `posting_secret_key`
+    pub fn with_signer(mut self, keys: Keys) -> Self {
+        self.signer = Some(keys);
+        self
+    }
+
+    /// Convert sats/BTC salaries with `rates` instead of the configured
+    /// exchange rate endpoint
+    pub fn with_rates(mut self, rates: ExchangeRates) -> Self {
+        self.rates = Some(rates);
+        self
+    }
+
+    /// The BTC price in `fiat`, for converting salaries quoted in sats or
+    /// BTC; `None` when conversion is disabled or no rate is available
+    pub async fn exchange_rate(&self, fiat: &str) -> Option<ExchangeRate> {
+        self.rates.as_ref()?.rate(fiat).await.ok()
+    }
+
+    /// Whether the relays answered the last health check or fetch
+    pub async fn is_healthy(&self) -> bool {
+        *self.relay_healthy.lock().await
+    }
+
+    pub(crate) fn metrics(&self) -> &RwLock<PerformanceMetrics> {
+        &self.metrics
+    }
+
+    /// Send a signed event (e.g. a new listing) to the relays, each in its
+    /// turn under `publish_interval`. Relays whose NIP-11 limits the event
+    /// breaks are skipped. Succeeds when at least one relay accepted it.
+    pub async fn publish(&self, event: &Event) -> Result<EventId, JobsError> {
+        self.ensure_online("Publishing")?;
+        let relays = self.config().relays.clone();
+        let deliveries = futures::future::join_all(relays.iter().map(|url| self.publish_to(url, event))).await;
+
+        let mut accepted = false;
+        let mut errors = Vec::new();
+        for (url, delivery) in relays.iter().zip(deliveries) {
+            match delivery {
+                Delivery::Accepted => accepted = true,
+                Delivery::Skipped(reason) => {
+                    tracing::info!(relay = %url, event_id = %event.id, reason = %reason, "publish_skipped");
+                    self.metrics.write().await.record_skipped_publish();
+                    errors.push(format!("{} skipped: {}", url, reason));
+                }
+                Delivery::Failed(message) => {
+                    tracing::warn!(relay = %url, event_id = %event.id, error = %message, "publish_failed");
+                    errors.push(format!("{}: {}", url, message));
+                }
+            }
+        }
+        if accepted {
+            return Ok(event.id);
+        }
+        let message = match errors.is_empty() {
+            true => "No relays configured".to_string(),
+            false => errors.join("; "),
+        };
+        Err(JobsError::RelayError { relay: None, message })
+    }
+
+    /// Sign `draft` as a listing of the board's first kind and publish it.
+    /// Fails without a posting key, and on a draft with `problems`.
+    pub async fn publish_draft(&self, draft: &JobDraft) -> Result<JobListing, JobsError> {
+        let problems = draft.problems();
+        if !problems.is_empty() {
+            return Err(JobsError::ParseError { what: "draft", input: draft.job_id.clone(), message: problems.join("; ") });
+        }
+        let Some(keys) = &self.signer else {
+            return Err(JobsError::Config("Publishing listings needs POSTING_SECRET_KEY".to_string()));
+        };
+        let event = draft
+            .to_event_builder(self.listing_kind())?
+            .sign_with_keys(keys)
+            .map_err(|e| JobsError::Config(format!("Could not sign the listing: {}", e)))?;
+        self.publish(&event).await?;
+        tracing::info!(job_id = %draft.job_id, event_id = %event.id, "draft_published");
+        Ok(JobListing::from_event(event))
+    }
+
+    /// Validate every row of a CSV or NDJSON `payload` and publish the valid
+    /// ones one after another, each paced per relay like any publish; a
+    /// `dry_run` only validates. Fails when the payload can't be read at
+    /// all, or there is no posting key to publish with.
+    pub async fn publish_bulk(&self, payload: &str, format: BulkFormat, dry_run: bool) -> Result<BulkReport, JobsError> {
+        let (drafts, mut rows) = bulk::parse_rows(payload, format).map_err(|message| JobsError::ParseError {
+            what: "bulk payload",
+            input: format.to_string(),
+            message,
+        })?;
+        if !dry_run && self.signer.is_none() && !drafts.is_empty() {
+            return Err(JobsError::Config("Publishing listings needs POSTING_SECRET_KEY".to_string()));
+        }
+
+        for (row, draft) in drafts {
+            let outcome = match dry_run {
+                true => RowOutcome::Valid,
+                false => match self.publish_draft(&draft).await {
+                    Ok(listing) => RowOutcome::Published(listing.event.id),
+                    Err(e) => RowOutcome::Failed(e.to_string()),
+                },
+            };
+            rows.push(RowReport { row, job_id: Some(draft.job_id), outcome });
+        }
+        rows.sort_by_key(|r| r.row);
+
+        let report = BulkReport { rows, dry_run };
+        tracing::info!(
+            format = %format,
+            dry_run,
+            published = report.count(|o| matches!(o, RowOutcome::Published(_))),
+            invalid = report.count(|o| matches!(o, RowOutcome::Invalid(_))),
+            failed = report.count(|o| matches!(o, RowOutcome::Failed(_))),
+            "bulk_published"
+        );
+        Ok(report)
+    }
+
+    /// The listings signed with the posting key whose NIP-40 expiration
+    /// falls within `within` from now, soonest first. Only the newest event
+    /// of each job id counts, so a listing republished with a later
+    /// expiration drops out.
+    pub async fn expiring_listings(&self, within: Duration) -> Result<Vec<JobListing>, JobsError> {
+        let now = Timestamp::now();
+        let until = Timestamp::from(now.as_secs() + within.as_secs());
+        let mut expiring: Vec<JobListing> = Self::newest_per_job(self.posted_events().await?)
+            .into_iter()
+            .filter(|l| l.expires_at.is_some_and(|at| at > now && at <= until))
+            .collect();
+        expiring.sort_by_key(|l| l.expires_at);
+        Ok(expiring)
+    }
+
+    /// Every listing event signed with the posting key, renewals included
+    async fn posted_events(&self) -> Result<Vec<Event>, JobsError> {
+        let Some(keys) = &self.signer else {
+            return Err(JobsError::Config("Tracking posted listings needs POSTING_SECRET_KEY".to_string()));
+        };
+        let author = keys.public_key();
+        let filter = self.kinds_filter().author(author);
+        let key = format!("posted:{}", author.to_hex());
+        let (events, _, _) = self.cached_or_paginated(filter, MAX_STATS_SAMPLE_SIZE, key).await?;
+        Ok(events)
+    }
+
+    /// The newest event of each job id among `events`
+    fn newest_per_job(events: Vec<Event>) -> Vec<JobListing> {
+        let mut newest: HashMap<String, JobListing> = HashMap::new();
+        for listing in events.into_iter().map(JobListing::from) {
+            match newest.get(&listing.job_id) {
+                Some(known) if known.created_at >= listing.created_at => {}
+                _ => {
+                    newest.insert(listing.job_id.clone(), listing);
+                }
+            }
+        }
+        newest.into_values().collect()
+    }
+
+    /// Public key of the posting key listings are signed with, when one is configured
+    pub fn posting_public_key(&self) -> Option<PublicKey> {
+        self.signer.as_ref().map(Keys::public_key)
+    }
+
+    /// NIP-17 direct messages to the posting key sent since `since`, newest
+    /// first, each matched to the first job id of `job_ids` it mentions.
+    /// Gift wraps that don't open with the key, or aren't messages, are skipped.
+    pub async fn application_messages(&self, since: Timestamp, job_ids: &[String]) -> Result<Vec<ApplicationMessage>, JobsError> {
+        let Some(keys) = &self.signer else {
+            return Err(JobsError::Config("Reading applications needs POSTING_SECRET_KEY".to_string()));
+        };
+        // Gift wraps are backdated by up to two days to hide when they were sent
+        let filter = Filter::new()
+            .kind(Kind::GiftWrap)
+            .pubkey(keys.public_key())
+            .since(Timestamp::from(since.as_secs().saturating_sub(2 * 86_400)))
+            .limit(MAX_STATS_SAMPLE_SIZE);
+        let wraps = timeout(self.stats_timeout(), self.fetch_with_ingested(filter, "applications", None))
+            .await
+            .map_err(|_| JobsError::Timeout { after: self.stats_timeout() })??;
+
+        let mut messages = Vec::new();
+        for wrap in &wraps {
+            let Ok(gift) = UnwrappedGift::from_gift_wrap(keys, wrap).await else {
+                continue;
+            };
+            let rumor = gift.rumor;
+            if rumor.kind != Kind::PrivateDirectMessage || rumor.created_at < since || gift.sender == keys.public_key() {
+                continue;
+            }
+            let lowercase = rumor.content.to_lowercase();
+            let job_id = job_ids.iter().find(|id| lowercase.contains(&id.to_lowercase())).cloned();
+            messages.push(ApplicationMessage { sender: gift.sender, sent_at: rumor.created_at, content: rumor.content, job_id });
+        }
+        messages.sort_by_key(|m| std::cmp::Reverse(m.sent_at));
+        Ok(messages)
+    }
+
+    /// Sign `plan` as a NIP-52 calendar event with the posting key and
+    /// publish it; an interview with the same id is rescheduled
+    pub async fn schedule_interview(&self, plan: &InterviewPlan) -> Result<Interview, JobsError> {
+        let problems = plan.problems(Timestamp::now());
+        if !problems.is_empty() {
+            return Err(JobsError::ParseError { what: "interview", input: plan.id.clone(), message: problems.join("; ") });
+        }
+        let Some(keys) = &self.signer else {
+            return Err(JobsError::Config("Scheduling interviews needs POSTING_SECRET_KEY".to_string()));
+        };
+        let event = plan
+            .to_event_builder()?
+            .sign_with_keys(keys)
+            .map_err(|e| JobsError::Config(format!("Could not sign the interview: {}", e)))?;
+        self.publish(&event).await?;
+        tracing::info!(job_id = %plan.job_id, interview = %plan.id, event_id = %event.id, "interview_scheduled");
+        Interview::from_event(&event).ok_or_else(|| JobsError::Config("Published interview could not be read back".to_string()))
+    }
+
+    /// Interviews not over yet, soonest first: those `participant` is
+    /// invited to, or without one those scheduled with the posting key.
+    /// `job_id` narrows them to one listing.
+    pub async fn upcoming_interviews(&self, participant: Option<PublicKey>, job_id: Option<&str>) -> Result<Vec<Interview>, JobsError> {
+        let mut filter = Filter::new()
+            .kind(Kind::from(CALENDAR_EVENT_KIND))
+            .hashtag(INTERVIEW_HASHTAG)
+            .limit(MAX_STATS_SAMPLE_SIZE);
+        filter = match (participant, &self.signer) {
+            (Some(participant), _) => filter.pubkey(participant),
+            (None, Some(keys)) => filter.author(keys.public_key()),
+            (None, None) => {
+                return Err(JobsError::Config(
+                    "Listing interviews needs a participant, or POSTING_SECRET_KEY for those the board scheduled".to_string(),
+                ));
+            }
+        };
+        if let Some(job_id) = job_id {
+            filter = filter.custom_tag(SingleLetterTag::lowercase(Alphabet::J), job_id);
+        }
+        let events = timeout(self.stats_timeout(), self.fetch_from_relays(filter, "interviews", None))
+            .await
+            .map_err(|_| JobsError::Timeout { after: self.stats_timeout() })??;
+        Ok(interview::upcoming(&events, Timestamp::now()))
+    }
+
+    /// Public career fairs, hiring meetups and the like not over yet,
+    /// soonest first: NIP-52 calendar events tagged with one of
+    /// `career_event_topics`, cached like statistics
+    pub async fn career_events(&self) -> Result<Vec<CareerEvent>, JobsError> {
+        let topics = self.config().career_event_topics.clone();
+        if topics.is_empty() {
+            return Ok(Vec::new());
+        }
+        let filter = Filter::new()
+            .kinds([Kind::from(DATE_EVENT_KIND), Kind::from(CALENDAR_EVENT_KIND)])
+            .hashtags(topics.iter().cloned());
+        let key = format!("career_events:{}", topics.join(","));
+        let (events, _, _) = self.cached_or_paginated(filter, MAX_STATS_SAMPLE_SIZE, key).await?;
+        Ok(career::upcoming(&events, Timestamp::now()))
+    }
+
+    /// People offering to work: up to `sample_size` (capped at
+    /// `MAX_STATS_SAMPLE_SIZE`) of the newest notes tagged with one of
+    /// `for_hire_topics`, one candidate per author, cached like statistics.
+    /// `known_skills` (lowercase) are recognised in untagged text.
+    pub async fn for_hire_candidates(&self, sample_size: usize, known_skills: &BTreeSet<String>) -> Result<(Vec<Candidate>, bool), JobsError> {
+        let topics = self.config().for_hire_topics.clone();
+        if topics.is_empty() {
+            return Ok((Vec::new(), false));
+        }
+        let sample_size = sample_size.clamp(1, MAX_STATS_SAMPLE_SIZE);
+        let filter = Filter::new().kind(Kind::TextNote).hashtags(topics.iter().cloned());
+        let key = format!("for_hire:{}:{}", topics.join(","), sample_size);
+        let (events, truncated, _) = self.cached_or_paginated(filter, sample_size, key).await?;
+        Ok((market::candidates(&events, &topics, known_skills), truncated))
+    }
+
+    /// What Nostr says about the company called `name`: notes naming it,
+    /// notes and reports tagging its `posters`, and reactions to and
+    /// reports of its `listings`, fetched together
+    pub async fn company_reputation(&self, name: &str, posters: &[PublicKey], listings: &[EventId]) -> Result<Reputation, JobsError> {
+        let mut filters = vec![Filter::new().kind(Kind::TextNote).search(name).limit(REPUTATION_SAMPLE_SIZE)];
+        if !listings.is_empty() {
+            filters.push(Filter::new().kinds([Kind::Reaction, Kind::Reporting]).events(listings.iter().copied()).limit(REPUTATION_SAMPLE_SIZE));
+        }
+        if !posters.is_empty() {
+            filters.push(Filter::new().kinds([Kind::TextNote, Kind::Reporting]).pubkeys(posters.iter().copied()).limit(REPUTATION_SAMPLE_SIZE));
+        }
+        let fetches = filters.into_iter().map(|filter| self.fetch_from_relays(filter, "reputation", None));
+        let fetched = timeout(self.stats_timeout(), futures::future::try_join_all(fetches))
+            .await
+            .map_err(|_| JobsError::Timeout { after: self.stats_timeout() })??;
+        let events: Vec<Event> = fetched.into_iter().flatten().collect();
+        Ok(reputation::summarize(&events, name, posters, listings))
+    }
+
+    /// The kind 0 profiles of `pubkeys` found on the relays, possibly
+    /// several versions of each
+    pub async fn profiles(&self, pubkeys: &[PublicKey]) -> Result<Vec<Event>, JobsError> {
+        if pubkeys.is_empty() {
+            return Ok(Vec::new());
+        }
+        let filter = Filter::new().kind(Kind::Metadata).authors(pubkeys.iter().copied()).limit(pubkeys.len());
+        timeout(self.stats_timeout(), self.fetch_from_relays(filter, "profiles", None))
+            .await
+            .map_err(|_| JobsError::Timeout { after: self.stats_timeout() })?
+    }
+
+    /// Scam signals for each of `listings`, by job id. Their authors'
+    /// profiles and the statistics sample's median salaries are fetched
+    /// first; a check whose data couldn't be fetched is skipped rather than
+    /// failing the rest.
+    pub async fn scam_assessments(&self, listings: &[JobListing]) -> HashMap<String, ScamAssessment> {
+        let authors: Vec<PublicKey> = listings.iter().map(|l| l.author).collect::<HashSet<_>>().into_iter().collect();
+        let (profiles, market) = tokio::join!(self.profiles(&authors), self.sample(DEFAULT_STATS_SAMPLE_SIZE, None));
+
+        let market = market.ok();
+        let histories = match profiles {
+            Ok(profiles) => {
+                let seen: Vec<JobListing> = market.iter().flat_map(|m| m.listings.iter()).chain(listings).cloned().collect();
+                scam::poster_histories(&seen, &profiles)
+            }
+            _ => HashMap::new(),
+        };
+        let stats = market.as_ref().map(Sample::stats);
+        let now = Timestamp::now();
+        listings
+            .iter()
+            .map(|l| (l.job_id.clone(), scam::assess(l, stats.as_ref(), histories.get(&l.author), now)))
+            .collect()
+    }
+
+    /// Count an impression or view of each of `listings` signed with the
+    /// posting key; other listings aren't tracked
+    pub fn record_engagement<'a>(&self, interaction: Interaction, listings: impl IntoIterator<Item = &'a JobListing>) {
+        let Some(keys) = &self.signer else {
+            return;
+        };
+        let now = Timestamp::now();
+        for listing in listings.into_iter().filter(|l| l.author == keys.public_key()) {
+            self.engagement.record(interaction, &listing.job_id, now);
+        }
+    }
+
+    /// Engagement with each listing signed with the posting key over the
+    /// last `days` UTC days (today included): search impressions and detail
+    /// views counted by this server, zaps and comments found on the relays.
+    /// Most engaged first.
+    pub async fn employer_analytics(&self, days: u64) -> Result<Vec<ListingAnalytics>, JobsError> {
+        let days = days.clamp(1, engagement::ENGAGEMENT_DAYS);
+        let since = Timestamp::from(engagement::day_of(Timestamp::now()) - (days - 1) * 86_400);
+        let events = self.posted_events().await?;
+        let job_ids: HashMap<EventId, String> = events.iter().map(|e| (e.id, JobListing::from_event(e.clone()).job_id)).collect();
+
+        let mut daily: HashMap<String, std::collections::BTreeMap<u64, DayEngagement>> = HashMap::new();
+        if !job_ids.is_empty() {
+            let filter = Filter::new()
+                .kinds([Kind::ZapReceipt, Kind::TextNote, Kind::Comment])
+                .events(job_ids.keys().copied())
+                .since(since)
+                .limit(MAX_STATS_SAMPLE_SIZE);
+            let reactions = timeout(self.stats_timeout(), self.fetch_with_ingested(filter, "engagement", None))
+                .await
+                .map_err(|_| JobsError::Timeout { after: self.stats_timeout() })??;
+            engagement::tally_reactions(&reactions, &job_ids, &mut daily);
+        }
+
+        let mut analytics: Vec<ListingAnalytics> = Self::newest_per_job(events)
+            .into_iter()
+            .map(|listing| {
+                let mut days = daily.remove(&listing.job_id).unwrap_or_default();
+                for (day, counted) in self.engagement.days(&listing.job_id, since) {
+                    days.entry(day).or_default().add(&counted);
+                }
+                let mut total = DayEngagement::default();
+                days.values().for_each(|day| total.add(day));
+                ListingAnalytics { listing, total, daily: days }
+            })
+            .collect();
+        analytics.sort_by_key(|a| {
+            let t = a.total;
+            std::cmp::Reverse((t.views + t.zaps + t.comments, t.impressions, a.listing.created_at))
+        });
+        Ok(analytics)
+    }
+
+    async fn expiry_reminder_loop(&self) {
+        loop {
+            tokio::time::sleep(EXPIRY_CHECK_INTERVAL).await;
+            self.send_expiry_reminders().await;
+        }
+    }
+
+    /// Remind the employer of each posted listing that entered
+    /// `expiry_reminder_window`, through `expiry_webhook_url` and a NIP-17
+    /// DM to `expiry_reminder_recipient`, once per listing event. Returns
+    /// how many listings were reminded about; without a posting key or
+    /// either channel configured, none are.
+    pub async fn send_expiry_reminders(&self) -> usize {
+        let config = self.config();
+        let recipient = config.expiry_reminder_recipient.as_deref().and_then(|r| PublicKey::parse(r).ok());
+        let Some(keys) = &self.signer else {
+            return 0;
+        };
+        if config.expiry_webhook_url.is_none() && recipient.is_none() {
+            return 0;
+        }
+        let listings = match self.expiring_listings(config.expiry_reminder_window).await {
+            Ok(listings) => listings,
+            Err(e) => {
+                tracing::warn!(error = %e, "expiry_check_failed");
+                return 0;
+            }
+        };
+
+        let http = reqwest::Client::new();
+        let mut reminded = self.reminded.lock().await;
+        let mut sent = 0;
+        for listing in &listings {
+            if reminded.contains(&listing.event.id) {
+                continue;
+            }
+            let mut delivered = false;
+            if let Some(url) = &config.expiry_webhook_url {
+                match Self::post_expiry_webhook(&http, url, listing).await {
+                    Ok(()) => delivered = true,
+                    Err(e) => tracing::warn!(job_id = %listing.job_id, error = %e, "expiry_webhook_failed"),
+                }
+            }
+            if let Some(recipient) = recipient {
+                match self.send_expiry_dm(keys, recipient, listing).await {
+                    Ok(()) => delivered = true,
+                    Err(e) => tracing::warn!(job_id = %listing.job_id, error = %e, "expiry_dm_failed"),
+                }
+            }
+            if delivered {
+                tracing::info!(job_id = %listing.job_id, event_id = %listing.event.id, "expiry_reminder_sent");
+                reminded.insert(listing.event.id);
+                sent += 1;
+            }
+        }
+        sent
+    }
+
+    async fn post_expiry_webhook(http: &reqwest::Client, url: &str, listing: &JobListing) -> Result<(), String> {
+        let expires_at = listing.expires_at.map(|at| at.as_secs());
+        let body = serde_json::json!({
+            "type": "listing_expiring",
+            "job_id": listing.job_id,
+            "title": listing.title,
+            "company": listing.company,
+            "event_id": listing.event.id.to_hex(),
+            "expires_at": expires_at,
+            "expires_in_secs": expires_at.map(|at| at.saturating_sub(Timestamp::now().as_secs())),
+        });
+        let response = http
+            .post(url)
+            .timeout(EXPIRY_WEBHOOK_TIMEOUT)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        match response.status().is_success() {
+            true => Ok(()),
+            false => Err(format!("HTTP {}", response.status())),
+        }
+    }
+
+    async fn send_expiry_dm(&self, keys: &Keys, recipient: PublicKey, listing: &JobListing) -> Result<(), JobsError> {
+        let expires_at = listing.expires_at.map(|at| at.to_human_datetime()).unwrap_or_default();
+        let message = format!(
+            "Your listing \"{}\" ({}) expires at {}. Publish it again to renew it, or retract it.",
+            listing.title.as_deref().unwrap_or("Untitled"),
+            listing.job_id,
+            expires_at
+        );
+        let event = EventBuilder::private_msg(keys, recipient, message, [])
+            .await
+            .map_err(|e| JobsError::Config(format!("Could not wrap the reminder: {}", e)))?;
+        self.publish(&event).await.map(|_| ())
+    }
+
+    /// Kind new listings are published as: the first configured kind
+    pub fn listing_kind(&self) -> Kind {
+        Kind::from(self.config().kinds.first().copied().unwrap_or(crate::config::JOB_LISTING_KIND))
+    }
+
+    /// Send `event` to `url` when its turn comes, unless it breaks the
+    /// relay's advertised limits, retrying while the relay rate-limits or
+    /// fails it
+    async fn publish_to(&self, url: &str, event: &Event) -> Delivery {
+        let config = self.config();
+        if let Some(limits) = self.relay_limits(url).await
+            && let Some(reason) = publish::limit_violation(event, &limits)
+        {
+            return Delivery::Skipped(reason);
+        }
+
+        let mut attempt = 0;
+        loop {
+            self.publishing.wait_turn(url, config.publish_interval).await;
+            match self.backend.publish_to(url, event).await {
+                Ok(_) => return Delivery::Accepted,
+                Err(message) if publish::is_duplicate(&message) => return Delivery::Accepted,
+                Err(message) if attempt < config.publish_retries && publish::is_retryable(&message) => {
+                    attempt += 1;
+                    let backoff = publish::retry_backoff(config.publish_interval, attempt);
+                    tracing::debug!(relay = %url, attempt, backoff_ms = backoff.as_millis() as u64, error = %message, "publish_retry");
+                    self.metrics.write().await.record_publish_retry();
+                    self.publishing.back_off(url, backoff).await;
+                }
+                Err(message) => return Delivery::Failed(message),
+            }
+        }
+    }
+
+    /// The NIP-11 limits of `url`, fetched at most once per hour
+    async fn relay_limits(&self, url: &str) -> Option<Limitation> {
+        if let Some(limits) = self.publishing.cached_limits(url).await {
+            return limits;
+        }
+        let limits = self.backend.relay_limits(url).await;
+        self.publishing.store_limits(url, limits.clone()).await;
+        limits
+    }
+
+    /// Disconnect from all relays
+    pub async fn shutdown(&self) {
+        self.backend.shutdown().await;
+    }
+
+    /// Run the default search until a relay answers, so relays are known to
+    /// work and its results are cached before traffic arrives. Returns the
+    /// number of listings found; gives up with the last error at `deadline`.
+    pub async fn warm_up(&self, deadline: Duration) -> Result<usize, JobsError> {
+        let give_up = tokio::time::Instant::now() + deadline;
+        loop {
+            match timeout_at(give_up, self.search(&JobQuery::default())).await {
+                Ok(Ok(results)) => return Ok(results.listings.len()),
+                Ok(Err(e)) if tokio::time::Instant::now() + WARM_UP_RETRY < give_up => {
+                    tracing::debug!(error = %e, "warm_up_retry");
+                    tokio::time::sleep(WARM_UP_RETRY).await;
+                }
+                Ok(Err(e)) => return Err(e),
+                Err(_) => return Err(JobsError::Timeout { after: deadline }),
+            }
+        }
+    }
+
+    // ==================== Queries ====================
+
+    /// Listings matching `query`, from the cache while fresh, otherwise from
+    /// the relays; a stale cache entry is served when the relays fail.
+    /// Listings by `query.preferred_authors` are ranked first.
+    pub async fn search(&self, query: &JobQuery) -> Result<SearchResults, JobsError> {
+        self.search_reporting(query, None).await
+    }
+
+    /// `search`, calling `on_partial` with the results so far each time a
+    /// relay answers while others are still pending. Cache hits answer at
+    /// once, without partial results.
+    pub async fn search_with_progress(
+        &self,
+        query: &JobQuery,
+        on_partial: impl Fn(PartialResults) + Send + Sync,
+    ) -> Result<SearchResults, JobsError> {
+        self.search_reporting(query, Some(&on_partial)).await
+    }
+
+    async fn search_reporting(
+        &self,
+        query: &JobQuery,
+        on_partial: Option<&(dyn Fn(PartialResults) + Send + Sync)>,
+    ) -> Result<SearchResults, JobsError> {
+        let resolved;
+        let query = match query.sources.is_empty() {
+            true => query,
+            false => {
+                resolved = self.resolve_sources(query)?;
+                &resolved
+            }
+        };
+        let key = query.cache_key();
+        self.record_popular(&key, query).await;
+        let partial = on_partial.map(|on_partial| {
+            move |events: Vec<Event>, answered: usize, relays: usize| {
+                let results = self.search_results(query, events, Source::Relays);
+                on_partial(PartialResults { results, answered, relays });
+            }
+        });
+        let fetch = async {
+            let partial = partial.as_ref().map(|p| p as OnPartial<'_>);
+            let fetch = self.fetch_events_fast(self.search_filter(query), key.clone(), partial);
+            let deadline = self.search_timeout().await;
+            let events = timeout(deadline, fetch)
+                .await
+                .map_err(|_| JobsError::Timeout { after: deadline })??;
+            Ok((events, false))
+        };
+        let (events, _, source) = self.cached_or_fetch(&key, self.config().search_cache_ttl, fetch).await?;
+        Ok(self.search_results(query, events, source))
+    }
+
+    /// `query` with the bridge key filled in, once its sources are checked
+    /// against the kinds this board fetches. All of them come from the one
+    /// relay filter, so narrowing the sources doesn't change what's cached.
+    fn resolve_sources(&self, query: &JobQuery) -> Result<JobQuery, JobsError> {
+        let kinds = &self.config().kinds;
+        let unserved = query.sources.iter().find(|source| matches!(source, ListingSource::Kind(kind) if !kinds.contains(kind)));
+        if let Some(source) = unserved {
+            return Err(JobsError::ParseError {
+                what: "listing source",
+                input: source.to_string(),
+                message: format!(
+                    "this board serves kinds {} and ingested listings",
+                    kinds.iter().map(u16::to_string).collect::<Vec<_>>().join(", ")
+                ),
+            });
+        }
+        Ok(JobQuery { bridge: Some(self.ingest_public_key()), ..query.clone() })
+    }
+
+    /// Match, rank, fold crossposts and cap `events` for `query`
+    fn search_results(&self, query: &JobQuery, events: Vec<Event>, source: Source) -> SearchResults {
+        let all: Vec<JobListing> = events.into_iter().map(JobListing::from).collect();
+        let mut matches: Vec<&JobListing> = all.iter().filter(|l| query.matches(l)).collect();
+        // Stable, so preferred and other listings each stay newest first
+        matches.sort_by_key(|l| !query.is_preferred(l));
+        let (matches, mut folded) = crosspost::fold(matches, &self.ingest_public_key());
+        let total = matches.len();
+        let listings: Vec<JobListing> = matches.into_iter().take(query.limit).cloned().collect();
+        let crossposts = listings
+            .iter()
+            .filter_map(|l| Some((l.job_id.clone(), folded.remove(&l.job_id)?.into_iter().cloned().collect())))
+            .collect();
+        let suggestions = if listings.is_empty() { query.suggestions(&all) } else { Vec::new() };
+        SearchResults { listings, total, crossposts, suggestions, source }
+    }
+
+    /// The relay filter `search` uses for `query`. Matching happens client
+    /// side, so this is the board's listing filter, narrowed to
+    /// `query.author` when set (and allowed).
+    pub fn search_filter(&self, query: &JobQuery) -> Filter {
+        let filter = match &query.author {
+            Some(author) if self.author_allowed(author) => self.kinds_filter().author(*author),
+            _ => self.listing_filter(),
+        };
+        filter.limit(100)
+    }
+
+    /// Look up a listing by its `job-id` tag or event id; `NotFound` when
+    /// no relay has it
+    pub async fn get(&self, job_id: &str) -> Result<(JobListing, Source), JobsError> {
+        let key = format!("job:{}", job_id);
+        let fetch = async {
+            let deadline = self.search_timeout().await;
+            let events = timeout(deadline, self.fetch_events_fast(self.lookup_filter(job_id), key.clone(), None))
+                .await
+                .map_err(|_| JobsError::Timeout { after: deadline })??;
+            Ok((events, false))
+        };
+        let (events, _, source) = self.cached_or_fetch(&key, self.config().search_cache_ttl, fetch).await?;
+        let event = events.into_iter().next().ok_or_else(|| JobsError::NotFound { job_id: job_id.to_string() })?;
+        Ok((event.into(), source))
+    }
+
+    /// Ask every relay for the listing again, bypassing the cache, and check
+    /// for a NIP-09 deletion by its author. `NotFound` when neither the
+    /// relays nor the cache have ever had it.
+    pub async fn verify(&self, job_id: &str) -> Result<ListingStatus, JobsError> {
+        self.ensure_online("Verifying a listing")?;
+        self.check_fetch_queue().await?;
+        let config = self.config();
+        let filter = self.lookup_filter(job_id);
+        let relay_timeout = config.relay_timeout;
+        let per_relay = config.relays.iter().map(|url| {
+            let filter = filter.clone();
+            async move {
+                let _slot = self.fetch_slot(url).await;
+                let relay_start = std::time::Instant::now();
+                let outcome = timeout(relay_timeout, self.backend.fetch_events(url, filter, relay_timeout.mul_f32(0.75))).await;
+                (url, relay_start.elapsed().as_millis(), outcome)
+            }
+        });
+        let outcomes = futures::future::join_all(per_relay).await;
+
+        let checked_at = Timestamp::now();
+        let mut status = ListingStatus {
+            listing: None,
+            seen_on: Vec::new(),
+            missing_from: Vec::new(),
+            unreachable: Vec::new(),
+            deleted: false,
+            checked_at,
+            last_seen: None,
+        };
+        let mut found: Option<Event> = None;
+        {
+            let mut metrics = self.metrics.write().await;
+            for (url, relay_ms, outcome) in outcomes {
+                match outcome {
+                    Ok(Ok(events)) => {
+                        metrics.record_relay_fetch(url, relay_ms, Some(events.len()));
+                        trace_relay(url, relay_ms, true);
+                        match events.into_iter().max_by_key(|e| e.created_at) {
+                            Some(event) => {
+                                status.seen_on.push(url.clone());
+                                if found.as_ref().is_none_or(|f| event.created_at > f.created_at) {
+                                    found = Some(event);
+                                }
+                            }
+                            None => status.missing_from.push(url.clone()),
+                        }
+                    }
+                    _ => {
+                        metrics.record_relay_fetch(url, relay_ms, None);
+                        trace_relay(url, relay_ms, false);
+                        status.unreachable.push(url.clone());
+                    }
+                }
+            }
+        }
+
+        let key = format!("job:{}", job_id);
+        let event = match found {
+            Some(event) => {
+                status.last_seen = Some(checked_at);
+                event
+            }
+            None => {
+                let cache = self.cache.read().await;
+                let cached = cache.get(&key).and_then(|c| Some((c.events.first()?.clone(), c.timestamp.elapsed())));
+                match cached {
+                    Some((event, age)) => {
+                        status.last_seen = Some(Timestamp::from(checked_at.as_secs().saturating_sub(age.as_secs())));
+                        event
+                    }
+                    None if status.missing_from.is_empty() => return Err(JobsError::Timeout { after: relay_timeout }),
+                    None => return Err(JobsError::NotFound { job_id: job_id.to_string() }),
+                }
+            }
+        };
+
+        let deletions = Filter::new().kind(Kind::EventDeletion).author(event.pubkey).event(event.id);
+        match self.fetch_from_relays(deletions, &format!("deletion:{}", event.id), None).await {
+            Ok(events) => status.deleted = !events.is_empty(),
+            Err(e) => tracing::warn!(job_id = %job_id, error = %e, "deletion_check_failed"),
+        }
+        status.listing = Some(event.into());
+        Ok(status)
+    }
+
+    /// The `limit` most recent listings, always fetched from the relays
+    pub async fn latest(&self, limit: usize) -> Result<Vec<JobListing>, JobsError> {
+        let filter = self.listing_filter().limit(limit);
+        let deadline = self.search_timeout().await;
+        let events = timeout(deadline, self.fetch_events_fast(filter, format!("latest:{}", limit), None))
+            .await
+            .map_err(|_| JobsError::Timeout { after: deadline })??;
+        Ok(events.into_iter().take(limit).map(JobListing::from).collect())
+    }
+
+    /// Up to `sample_size` (capped at `MAX_STATS_SAMPLE_SIZE`) of the most
+    /// recent listings, optionally restricted to `[since, until)`.
+    pub async fn sample(&self, sample_size: usize, window: Option<(Timestamp, Timestamp)>) -> Result<Sample, JobsError> {
+        let sample_size = sample_size.clamp(1, MAX_STATS_SAMPLE_SIZE);
+        let (filter, key) = match window {
+            None => (self.listing_filter(), format!("stats:all:{}", sample_size)),
+            Some((since, until)) => (
+                self.listing_filter().since(since).until(until),
+                format!("compare:{}:{}:{}", since.as_secs(), until.as_secs(), sample_size),
+            ),
+        };
+
+        let (events, truncated, source) = self.cached_or_paginated(filter, sample_size, key).await?;
+
+        // A relay may ignore `until`, so re-apply the window locally
+        let listings = events
+            .into_iter()
+            .filter(|e| window.is_none_or(|(since, until)| e.created_at >= since && e.created_at < until))
+            .map(JobListing::from)
+            .collect();
+        Ok(Sample { listings, truncated, source })
+    }
+
+    /// Statistics over the `sample_size` most recent listings
+    pub async fn stats(&self, sample_size: usize) -> Result<JobStats, JobsError> {
+        Ok(self.sample(sample_size, None).await?.stats())
+    }
+
+    /// Up to `sample_size` of `author`'s most recent listings, expired ones
+    /// included. Empty when an author allowlist is configured without them.
+    pub async fn employer_listings(&self, author: &PublicKey, sample_size: usize) -> Result<Sample, JobsError> {
+        let sample_size = sample_size.clamp(1, MAX_STATS_SAMPLE_SIZE);
+        if !self.author_allowed(author) {
+            return Ok(Sample { listings: Vec::new(), truncated: false, source: Source::Relays });
+        }
+
+        let filter = self.kinds_filter().author(*author);
+        let key = format!("employer:{}:{}", author.to_hex(), sample_size);
+        let (events, truncated, source) = self.cached_or_paginated(filter, sample_size, key).await?;
+        let listings = events.into_iter().map(JobListing::from).collect();
+        Ok(Sample { listings, truncated, source })
+    }
+
+    /// Events for a statistics-sized query: from the cache within
+    /// `stats_cache_ttl`, otherwise paginated from the relays
+    async fn cached_or_paginated(
+        &self,
+        filter: Filter,
+        sample_size: usize,
+        key: String,
+    ) -> Result<(Vec<Event>, bool, Source), JobsError> {
+        let fetch = self.fetch_events_paginated(filter, sample_size, key.clone(), self.stats_timeout());
+        self.cached_or_fetch(&key, self.config().stats_cache_ttl, fetch).await
+    }
+
+    /// Listings requiring any of `skills`, those sharing the most skills
+    /// first, each with the skills it shares
+    pub async fn match_skills(&self, skills: &[String], limit: usize) -> Result<Vec<(JobListing, Vec<String>)>, JobsError> {
+        let query = JobQuery { skill: skills.to_vec(), limit: STATS_PAGE_SIZE, ..Default::default() };
+        let mut matches: Vec<(JobListing, Vec<String>)> = self
+            .search(&query)
+            .await?
+            .listings
+            .into_iter()
+            .map(|listing| {
+                let shared = listing.matching_skills(skills);
+                (listing, shared)
+            })
+            .collect();
+        // Stable, so equally good matches stay newest first
+        matches.sort_by_key(|(_, shared)| std::cmp::Reverse(shared.len()));
+        matches.truncate(limit);
+        Ok(matches)
+    }
+
+    // ==================== Metrics Rotation ====================
+
+    /// Archive the metrics totals whenever a `metrics_rotation` period has
+    /// ended since they started
+    async fn metrics_rotation_loop(&self) {
+        loop {
+            if let Some(period) = self.config().metrics_rotation {
+                let since = self.metrics_history.read().await.since;
+                if period.start_of(Timestamp::now()) > since {
+                    self.rotate_metrics("scheduled", false).await;
+                }
+            }
+            tokio::time::sleep(METRICS_ROTATION_CHECK_INTERVAL).await;
+        }
+    }
+
+    /// Close the current metrics period: archive its totals and start from
+    /// zero. The rolling windows carry over unless `clear_windows`.
+    pub(crate) async fn rotate_metrics(&self, reason: &'static str, clear_windows: bool) -> MetricsPeriod {
+        let cache = self.cache_usage().await;
+        let mut closed = {
+            let mut metrics = self.metrics.write().await;
+            let recent = if clear_windows { Default::default() } else { std::mem::take(&mut metrics.recent) };
+            std::mem::replace(&mut *metrics, PerformanceMetrics { recent, ..Default::default() })
+        };
+        closed.recent = Default::default();
+
+        let now = Timestamp::now();
+        let mut history = self.metrics_history.write().await;
+        let period = MetricsPeriod { since: history.since, until: now, reason, metrics: closed, cache };
+        history.since = now;
+        history.periods.push_back(period.clone());
+        while history.periods.len() > self.config().metrics_history_size {
+            history.periods.pop_front();
+        }
+        tracing::info!(
+            reason = reason,
+            since = period.since.as_secs(),
+            total_requests = period.metrics.total_requests,
+            cache_hit_rate = period.metrics.cache_hit_rate(),
+            "metrics_rotated"
+        );
+        period
+    }
+
+    /// When the current metrics period started, and the archived ones,
+    /// newest first
+    pub(crate) async fn metrics_history(&self) -> (Timestamp, Vec<MetricsPeriod>) {
+        let history = self.metrics_history.read().await;
+        (history.since, history.periods.iter().rev().cloned().collect())
+    }
+
+    // ==================== Digests ====================
+
+    /// Build the digest of the last complete period whenever it is missing
+    /// from the archive, i.e. shortly after each period ends
+    async fn digest_loop(&self) {
+        loop {
+            if let Some(period) = self.config().digest_period {
+                let window = period.last_complete(Timestamp::now());
+                if !self.has_digest(period, window.0).await
+                    && let Err(e) = self.generate_digest(period, window).await
+                {
+                    tracing::warn!(period = %period, error = %e, "digest_failed");
+                }
+            }
+            tokio::time::sleep(DIGEST_CHECK_INTERVAL).await;
+        }
+    }
+
+    async fn has_digest(&self, period: DigestPeriod, since: Timestamp) -> bool {
+        self.digests.read().await.iter().any(|d| d.period == period && d.since == since)
+    }
+
+    /// Build the digest of `window` (compared with the window before it) and
+    /// add it to the archive, replacing one for the same period
+    pub async fn generate_digest(&self, period: DigestPeriod, window: (Timestamp, Timestamp)) -> Result<Digest, JobsError> {
+        let previous = (Timestamp::from(window.0.as_secs().saturating_sub(period.secs())), window.0);
+        let current = self.sample(DEFAULT_STATS_SAMPLE_SIZE, Some(window)).await?;
+        let before = self.sample(DEFAULT_STATS_SAMPLE_SIZE, Some(previous)).await?;
+
+        let currency = self.config().display_currency.clone();
+        let crypto = current
+            .listings
+            .iter()
+            .any(|l| l.salary.as_ref().is_some_and(|s| super::rates::btc_per_unit(&s.currency).is_some()));
+        let rate = match crypto {
+            true => self.exchange_rate(&currency).await,
+            false => None,
+        };
+        let mut digest = Digest::from_listings(period, window, &current.listings, &before.listings, &currency, rate.as_ref());
+        digest.truncated = current.truncated || before.truncated;
+
+        let mut digests = self.digests.write().await;
+        digests.retain(|d| !(d.period == period && d.since == digest.since));
+        digests.push_back(digest.clone());
+        while digests.len() > self.config().digest_archive_size {
+            digests.pop_front();
+        }
+        tracing::info!(period = %period, date = %digest.date(), listings = digest.listings, "digest_generated");
+        Ok(digest)
+    }
+
+    /// The most recent digest, building one for the last complete period
+    /// (daily unless configured otherwise) if none exists yet
+    pub async fn latest_digest(&self) -> Result<Digest, JobsError> {
+        if let Some(digest) = self.digests.read().await.back() {
+            return Ok(digest.clone());
+        }
+        let period = self.config().digest_period.unwrap_or(DigestPeriod::Daily);
+        self.generate_digest(period, period.last_complete(Timestamp::now())).await
+    }
+
+    /// Archived digests, newest first
+    pub async fn digest_archive(&self) -> Vec<Digest> {
+        self.digests.read().await.iter().rev().cloned().collect()
+    }
+
+    // ==================== DVM ====================
+
+    /// Submit `resume` to a NIP-90 DVM (`dvm_kind`, addressed to
+    /// `dvm_provider` when set, encrypted to it) and wait up to `dvm_timeout`
+    /// for the skills it extracts. The request is signed with a throwaway key.
+    pub async fn analyze_resume(&self, resume: &str) -> Result<ResumeAnalysis, JobsError> {
+        let config = self.config();
+        if !(5000..6000).contains(&config.dvm_kind) {
+            return Err(JobsError::Config(format!("DVM_KIND must be a NIP-90 request kind (5000-5999), not {}", config.dvm_kind)));
+        }
+        let provider = config
+            .dvm_provider
+            .as_deref()
+            .map(|p| {
+                PublicKey::parse(p).map_err(|e| JobsError::ParseError {
+                    what: "DVM provider",
+                    input: p.to_string(),
+                    message: e.to_string(),
+                })
+            })
+            .transpose()?;
+
+        let keys = Keys::generate();
+        let request = dvm::resume_request(config.dvm_kind, resume, provider.as_ref(), &keys)?;
+        self.publish(&request).await?;
+        tracing::info!(request_id = %request.id, kind = config.dvm_kind, "dvm_request_published");
+
+        let key = format!("dvm:{}", request.id);
+        let deadline = tokio::time::Instant::now() + config.dvm_timeout;
+        loop {
+            // Relays may lag or fail while the DVM works; keep polling until the deadline
+            if let Ok(events) = self.fetch_from_relays(dvm::response_filter(&request, provider.as_ref()), &key, None).await
+                && let Some(outcome) = dvm::outcome(&request, &events, provider.as_ref(), &keys)
+            {
+                return outcome;
+            }
+            if tokio::time::Instant::now() + DVM_POLL_INTERVAL >= deadline {
+                return Err(JobsError::Dvm {
+                    request_id: request.id.to_hex(),
+                    message: format!("no result within {}s", config.dvm_timeout.as_secs()),
+                });
+            }
+            tokio::time::sleep(DVM_POLL_INTERVAL).await;
+        }
+    }
+
+    // ==================== Cache ====================
+
+    /// Events for `key` from the cache while younger than `ttl`, otherwise
+    /// from `fetch` (with whether it was cut short). A stale entry is kept
+    /// as the fallback: when the fetch fails in a way a retry could fix, it
+    /// is served instead of the error, labeled with its age.
+    async fn cached_or_fetch(
+        &self,
+        key: &str,
+        ttl: Duration,
+        fetch: impl Future<Output = Result<(Vec<Event>, bool), JobsError>>,
+    ) -> Result<(Vec<Event>, bool, Source), JobsError> {
+        let start = std::time::Instant::now();
+        let cached = self.cache.read().await.get(key).map(|cached| (cached.events.clone(), cached.timestamp.elapsed()));
+        let duration_ms = start.elapsed().as_millis();
+        let stale = match cached {
+            Some((events, age)) if age < ttl => {
+                self.record_cache_hit(key, &events, duration_ms, true).await;
+                return Ok((events, false, Source::Cache));
+            }
+            stale => stale,
+        };
+        tracing::debug!(cache_key = %key, stale = stale.is_some(), "cache_miss");
+
+        match (fetch.await, stale) {
+            (Ok((events, truncated)), _) if self.config().offline => Ok((events, truncated, Source::Offline)),
+            (Ok((events, truncated)), _) => Ok((events, truncated, Source::Relays)),
+            (Err(e), Some((events, age))) if e.is_retryable() => {
+                tracing::warn!(cache_key = %key, age_secs = age.as_secs(), error = %e, "serving_stale_cache");
+                self.record_cache_hit(key, &events, duration_ms, false).await;
+                Ok((events, false, Source::StaleCache { age }))
+            }
+            (Err(e), _) => Err(e),
+        }
+    }
+
+    async fn record_cache_hit(&self, key: &str, events: &[Event], duration_ms: u128, is_fresh: bool) {
+        tracing::info!(
+            cache_key = %key,
+            duration_ms = duration_ms,
+            event_count = events.len(),
+            source = "cache",
+            is_fresh = is_fresh,
+            "cache_hit"
+        );
+
+        self.metrics.write().await.record_cache_hit(duration_ms);
+        trace_cache(if is_fresh { "hit" } else { "stale" });
+    }
+
+    /// Count a request for the search behind `key`
+    async fn record_popular(&self, key: &str, query: &JobQuery) {
+        if self.config().prefetch_top == 0 {
+            return;
+        }
+        let mut popular = self.popular.lock().await;
+        let now = std::time::Instant::now();
+        popular.retain(|_, p| now.duration_since(p.last_hit) < POPULAR_WINDOW);
+        let entry = popular.entry(key.to_string()).or_insert_with(|| PopularQuery {
+            filter: self.search_filter(query),
+            hits: 0,
+            last_hit: now,
+            prefetched_at: None,
+        });
+        entry.hits += 1;
+        entry.last_hit = now;
+    }
+
+    // ==================== Live Feed ====================
+
+    /// Listings first seen after subscribing, oldest first. Relays are only
+    /// polled while someone is subscribed.
+    pub fn subscribe(&self) -> broadcast::Receiver<JobListing> {
+        self.live.subscribe()
+    }
+
+    /// Poll for listings newer than the last one seen, every
+    /// `live_poll_interval`, and send them to the live subscribers
+    async fn live_loop(&self) {
+        let mut cursor = LiveCursor::default();
+        loop {
+            tokio::time::sleep(self.config().live_poll_interval).await;
+            if self.live.receiver_count() == 0 {
+                // Nobody listening: start over from now once someone is
+                cursor = LiveCursor::default();
+                continue;
+            }
+            let filter = self.listing_filter().since(cursor.since).limit(LIVE_POLL_LIMIT);
+            let events = match self.fetch_with_ingested(filter, "live", None).await {
+                Ok(events) => events,
+                Err(e) => {
+                    tracing::debug!(error = %e, "live_poll_failed");
+                    continue;
+                }
+            };
+            let mut fresh = cursor.advance(events);
+            fresh.sort_by_key(|e| e.created_at);
+            for event in fresh {
+                // Only fails when the last subscriber just left
+                let _ = self.live.send(event.into());
+            }
+        }
+    }
+
+    /// Check for expiring popular searches several times per TTL
+    async fn prefetch_loop(&self) {
+        loop {
+            let interval = (self.config().search_cache_ttl / 4).clamp(Duration::from_secs(1), Duration::from_secs(30));
+            tokio::time::sleep(interval).await;
+            self.prefetch_popular(interval * 2).await;
+        }
+    }
+
+    /// Refetch the `prefetch_top` most requested searches whose cache entry
+    /// is missing or expires within `lead`, so they stay warm. Returns the
+    /// refreshed cache keys.
+    pub async fn prefetch_popular(&self, lead: Duration) -> Vec<String> {
+        let config = self.config();
+        let mut candidates: Vec<(String, PopularQuery)> = self
+            .popular
+            .lock()
+            .await
+            .iter()
+            .filter(|(_, p)| p.hits >= PREFETCH_MIN_HITS && p.last_hit.elapsed() < POPULAR_WINDOW)
+            .map(|(key, p)| (key.clone(), p.clone()))
+            .collect();
+        candidates.sort_by(|a, b| b.1.hits.cmp(&a.1.hits).then_with(|| a.0.cmp(&b.0)));
+        candidates.truncate(config.prefetch_top);
+
+        let mut refreshed = Vec::new();
+        for (key, popular) in candidates {
+            let cached_age = self.cache.read().await.get(&key).map(|c| c.timestamp.elapsed());
+            let age = cached_age.or(popular.prefetched_at.map(|at| at.elapsed()));
+            if age.is_some_and(|age| age + lead < config.search_cache_ttl) {
+                continue;
+            }
+            if let Some(p) = self.popular.lock().await.get_mut(&key) {
+                p.prefetched_at = Some(std::time::Instant::now());
+            }
+            match timeout(config.search_timeout, self.fetch_events_fast(popular.filter, key.clone(), None)).await {
+                Ok(fetched) => {
+                    self.metrics.write().await.record_prefetch();
+                    match fetched {
+                        Ok(_) => {
+                            tracing::debug!(cache_key = %key, hits = popular.hits, "cache_prefetched");
+                            refreshed.push(key);
+                        }
+                        Err(e) => tracing::warn!(cache_key = %key, error = %e, "cache_prefetch_failed"),
+                    }
+                }
+                Err(_) => tracing::warn!(cache_key = %key, "cache_prefetch_timeout"),
+            }
+        }
+        refreshed
+    }
+
+    pub(crate) async fn cache_entry(&self, key: &str) -> Option<CacheEntry> {
+        self.cache.read().await.get(key).map(|cached| CacheEntry {
+            key: key.to_string(),
+            events: cached.events.len(),
+            size_bytes: cached.size_bytes,
+            age: cached.timestamp.elapsed(),
+        })
+    }
+
+    /// All cache entries, largest first
+    pub(crate) async fn cache_entries(&self) -> Vec<CacheEntry> {
+        let mut entries: Vec<CacheEntry> = self
+            .cache
+            .read()
+            .await
+            .iter()
+            .map(|(key, cached)| CacheEntry {
+                key: key.clone(),
+                events: cached.events.len(),
+                size_bytes: cached.size_bytes,
+                age: cached.timestamp.elapsed(),
+            })
+            .collect();
+        entries.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then_with(|| a.key.cmp(&b.key)));
+        entries
+    }
+
+    /// How long the cache entry under `key` is served before refetching
+    pub(crate) fn cache_ttl(&self, key: &str) -> Duration {
+        let config = self.config();
+        // Statistics samples and employer histories are paginated like stats
+        if key.starts_with("stats:") || key.starts_with("employer:") {
+            config.stats_cache_ttl
+        } else {
+            config.search_cache_ttl
+        }
+    }
+
+    pub(crate) async fn cache_usage(&self) -> CacheUsage {
+        cache_usage(&*self.cache.read().await)
+    }
+
+    /// Drop every cached result; returns how many entries were removed
+    pub async fn clear_cache(&self) -> usize {
+        let mut cache = self.cache.write().await;
+        let entries = cache.len();
+        cache.clear();
+        entries
+    }
+
+    /// Apply the retention limits every `compaction_interval`
+    async fn compaction_loop(&self) {
+        loop {
+            tokio::time::sleep(self.config().compaction_interval).await;
+            self.compact_cache().await;
+        }
+    }
+
+    /// Drop the cached events the retention limits no longer keep: posted
+    /// more than `retention_max_age` ago, or expired more than
+    /// `retention_expired_grace` ago. Entries left empty are removed.
+    pub async fn compact_cache(&self) -> Compaction {
+        let config = self.config();
+        let now = Timestamp::now().as_secs();
+        let posted_before = config.retention_max_age.map(|age| Timestamp::from(now.saturating_sub(age.as_secs())));
+        let expired_before = config.retention_expired_grace.map(|grace| Timestamp::from(now.saturating_sub(grace.as_secs())));
+        let mut compaction = Compaction::default();
+        if posted_before.is_none() && expired_before.is_none() {
+            return compaction;
+        }
+
+        let mut cache = self.cache.write().await;
+        cache.retain(|_, cached| {
+            let before = cached.events.len();
+            cached.events.retain(|e| {
+                if posted_before.is_some_and(|cutoff| e.created_at < cutoff) {
+                    compaction.purged_old += 1;
+                    false
+                } else if expired_before.is_some_and(|cutoff| e.is_expired_at(&cutoff)) {
+                    compaction.purged_expired += 1;
+                    false
+                } else {
+                    true
+                }
+            });
+            if cached.events.len() != before {
+                cached.size_bytes = cached.events.iter().map(|e| e.as_json().len()).sum();
+            }
+            if cached.events.is_empty() {
+                compaction.entries_removed += 1;
+            }
+            !cached.events.is_empty()
+        });
+        drop(cache);
+
+        if compaction != Compaction::default() {
+            self.metrics.write().await.record_purge(compaction.purged_old, compaction.purged_expired);
+            tracing::info!(
+                purged_old = compaction.purged_old,
+                purged_expired = compaction.purged_expired,
+                entries_removed = compaction.entries_removed,
+                "cache_compacted"
+            );
+        }
+        compaction
+    }
+
+    /// Every cached and ingested listing of this board, newest first: the
+    /// contents of a snapshot
+    pub async fn snapshot_events(&self) -> Vec<Event> {
+        let filter = self.listing_filter();
+        let mut events = self.local_events(&filter).await;
+        let seen: std::collections::HashSet<EventId> = events.iter().map(|e| e.id).collect();
+        events.extend(self.ingest.matching(&filter).await.into_iter().filter(|e| !seen.contains(&e.id)));
+        events.sort_by_key(|e| std::cmp::Reverse(e.created_at));
+        events
+    }
+
+    /// The snapshot as NDJSON, one signed event per line
+    pub async fn export_snapshot(&self) -> String {
+        snapshot::to_ndjson(&self.snapshot_events().await)
+    }
+
+    /// Add the board's listings from an NDJSON snapshot to the cache, where
+    /// offline mode serves them and later snapshots include them. Events
+    /// whose signature doesn't verify are rejected.
+    pub async fn import_snapshot(&self, ndjson: &str) -> ImportReport {
+        let (events, invalid) = snapshot::parse_ndjson(ndjson);
+        let filter = self.listing_filter();
+        let mut report = ImportReport { invalid, ..Default::default() };
+
+        let mut cache = self.cache.write().await;
+        let mut known: std::collections::HashSet<EventId> =
+            cache.values().flat_map(|cached| &cached.events).map(|e| e.id).collect();
+        let mut imported = cache.remove(SNAPSHOT_CACHE_KEY).map(|cached| cached.events).unwrap_or_default();
+        for event in events {
+            if !filter.match_event(&event, MatchEventOptions::new()) {
+                report.skipped += 1;
+            } else if !known.insert(event.id) {
+                report.duplicates += 1;
+            } else {
+                report.imported += 1;
+                imported.push(event);
+            }
+        }
+        imported.sort_by_key(|e| std::cmp::Reverse(e.created_at));
+        if !imported.is_empty() {
+            cache.insert(SNAPSHOT_CACHE_KEY.to_string(), CachedEvents::new(imported));
+        }
+
+        tracing::info!(
+            imported = report.imported,
+            duplicates = report.duplicates,
+            skipped = report.skipped,
+            invalid = report.invalid.len(),
+            "snapshot_imported"
+        );
+        report
+    }
+
+    // ==================== Relay Fetching ====================
+
+    /// Base filter for this board's listings: its kinds and, when an
+    /// allowlist is configured, only those authors
+    fn listing_filter(&self) -> Filter {
+        let authors = Self::parse_authors(&self.config().authors);
+        if authors.is_empty() {
+            self.kinds_filter()
+        } else {
+            self.kinds_filter().authors(authors)
+        }
+    }
+
+    /// This board's kinds, from any author
+    fn kinds_filter(&self) -> Filter {
+        Filter::new().kinds(self.config().kinds.iter().copied().map(Kind::from))
+    }
+
+    /// Whether the author allowlist, if any, includes `author`
+    fn author_allowed(&self, author: &PublicKey) -> bool {
+        let authors = &self.config().authors;
+        authors.is_empty() || Self::parse_authors(authors).contains(author)
+    }
+
+    /// Matches a listing by event id, or else by its `j` (job id) tag
+    fn lookup_filter(&self, job_id: &str) -> Filter {
+        if let Ok(event_id) = EventId::from_hex(job_id) {
+            self.listing_filter().id(event_id)
+        } else {
+            self.listing_filter()
+                .custom_tag(SingleLetterTag::lowercase(Alphabet::J), job_id.to_string())
+        }
+    }
+
+    /// Parse hex or npub author keys, skipping (and logging) invalid entries
+    fn parse_authors(authors: &[String]) -> Vec<PublicKey> {
+        authors
+            .iter()
+            .filter_map(|author| {
+                PublicKey::parse(author)
+                    .inspect_err(|e| tracing::warn!(author = %author, error = %e, "invalid_author_in_allowlist"))
+                    .ok()
+            })
+            .collect()
+    }
+
+    async fn fetch_events_fast(
+        &self,
+        filter: Filter,
+        cache_key: String,
+        on_partial: Option<OnPartial<'_>>,
+    ) -> Result<Vec<Event>, JobsError> {
+        trace_cache("miss");
+        let events = self.fetch_with_ingested(filter, &cache_key, on_partial).await?;
+        if !events.is_empty() {
+            self.cache_events(cache_key, events.clone());
+        }
+        Ok(events)
+    }
+
+    fn cache_events(&self, cache_key: String, events: Vec<Event>) {
+        let cache = self.cache.clone();
+        let cached = CachedEvents::new(events);
+        let warn_bytes = self.config().cache_warn_bytes;
+        self.tasks.spawn_once("cache_insert", async move {
+            let mut cache = cache.write().await;
+            cache.insert(cache_key, cached);
+
+            let usage = cache_usage(&cache);
+            if usage.bytes >= warn_bytes {
+                tracing::warn!(
+                    cache_entries = usage.entries,
+                    cache_events = usage.events,
+                    cache_bytes = usage.bytes,
+                    warn_bytes = warn_bytes,
+                    "cache_size_above_threshold"
+                );
+            }
+        });
+    }
+
+    /// Walk backwards through the listing history in pages of
+    /// `STATS_PAGE_SIZE`, until `sample_size` unique events are collected, the
+    /// relays run out of events, or `deadline` passes. Returns the sample and
+    /// whether it was cut short by the deadline.
+    async fn fetch_events_paginated(
+        &self,
+        filter: Filter,
+        sample_size: usize,
+        cache_key: String,
+        deadline: Duration,
+    ) -> Result<(Vec<Event>, bool), JobsError> {
+        trace_cache("miss");
+        let started = std::time::Instant::now();
+        let relay_timeout = self.config().relay_timeout;
+        let mut seen = std::collections::HashSet::new();
+        let mut sample: Vec<Event> = Vec::new();
+        let mut until: Option<Timestamp> = None;
+        let mut truncated = false;
+
+        while sample.len() < sample_size {
+            if started.elapsed() + relay_timeout > deadline {
+                truncated = true;
+                break;
+            }
+
+            let page_size = STATS_PAGE_SIZE.min(sample_size - sample.len());
+            let mut page_filter = filter.clone().limit(page_size);
+            if let Some(until) = until {
+                page_filter = page_filter.until(until);
+            }
+
+            let page = match self.fetch_with_ingested(page_filter, &cache_key, None).await {
+                Ok(page) => page,
+                Err(e) if sample.is_empty() => return Err(e),
+                Err(_) => {
+                    truncated = true;
+                    break;
+                }
+            };
+
+            let page_len = page.len();
+            let mut added = 0;
+            for event in page {
+                until = Some(until.map_or(event.created_at, |u| u.min(event.created_at)));
+                if seen.insert(event.id) {
+                    sample.push(event);
+                    added += 1;
+                }
+            }
+
+            if page_len < page_size || added == 0 {
+                break;
+            }
+        }
+
+        sample.truncate(sample_size);
+        tracing::info!(
+            cache_key = %cache_key,
+            sample_size = sample.len(),
+            requested = sample_size,
+            truncated = truncated,
+            duration_ms = started.elapsed().as_millis(),
+            "fetch_events_paginated"
+        );
+
+        if !sample.is_empty() {
+            self.cache_events(cache_key, sample.clone());
+        }
+        Ok((sample, truncated))
+    }
+
+    /// Wait for one of the `max_concurrent_fetches` relay fetch slots,
+    /// recording the wait when all were taken
+    async fn fetch_slot(&self, relay: &str) -> OwnedSemaphorePermit {
+        if let Ok(slot) = self.fetch_slots.clone().try_acquire_owned() {
+            return slot;
+        }
+        tracing::debug!(relay = %relay, "relay_fetch_queued");
+        let started = std::time::Instant::now();
+        let queued = QueuedFetch::new(&self.queued_fetches);
+        let slot = self.fetch_slots.clone().acquire_owned().await.expect("fetch slots are never closed");
+        drop(queued);
+        self.metrics.write().await.record_queue_wait(started.elapsed().as_millis());
+        slot
+    }
+
+    /// Refuse new relay work while `max_queued_fetches` fetches are already
+    /// waiting, with a hint of when slots should be free again
+    async fn check_fetch_queue(&self) -> Result<(), JobsError> {
+        let config = self.config();
+        let queued = self.queued_fetches.load(std::sync::atomic::Ordering::SeqCst);
+        if queued < config.max_queued_fetches {
+            return Ok(());
+        }
+        // Every slot turns over at least once per relay timeout
+        let rounds = (queued / config.max_concurrent_fetches + 1) as u32;
+        let retry_after = (config.relay_timeout * rounds).max(Duration::from_secs(1));
+        let retry_after = Duration::from_secs(retry_after.as_secs_f64().ceil() as u64);
+        tracing::warn!(queued = queued, retry_after_secs = retry_after.as_secs(), "relay_fetch_shed");
+        self.metrics.write().await.record_shed();
+        Err(JobsError::Overloaded { retry_after })
+    }
+
+    /// Read `filter` from one relay until EOSE or `relay_timeout`, first
+    /// taking a fetch slot when `pooled`. `Err(())` is a timeout with nothing
+    /// received; otherwise the events and whether the relay sent EOSE.
+    async fn fetch_relay(
+        &self,
+        url: &str,
+        filter: Filter,
+        relay_timeout: Duration,
+        pooled: bool,
+    ) -> (u128, Result<Result<(Vec<Event>, bool), String>, ()>) {
+        let _slot = if pooled { Some(self.fetch_slot(url).await) } else { None };
+        let relay_start = std::time::Instant::now();
+        let deadline = tokio::time::Instant::now() + relay_timeout;
+        let outcome = match timeout_at(deadline, self.backend.stream_events(url, filter, relay_timeout)).await {
+            Ok(Ok(mut stream)) => {
+                let mut events = Vec::new();
+                let complete = loop {
+                    match timeout_at(deadline, stream.next()).await {
+                        Ok(Some(event)) => events.push(event),
+                        Ok(None) => break true,
+                        Err(_) => break false,
+                    }
+                };
+                // A relay that sent nothing before the deadline timed out
+                if complete || !events.is_empty() { Ok(Ok((events, complete))) } else { Err(()) }
+            }
+            Ok(Err(e)) => Ok(Err(e)),
+            Err(_) => Err(()),
+        };
+        (relay_start.elapsed().as_millis(), outcome)
+    }
+
+    /// The cached events matching `filter`, newest first and up to its
+    /// limit: what offline mode answers instead of the relays
+    async fn local_events(&self, filter: &Filter) -> Vec<Event> {
+        let cache = self.cache.read().await;
+        let mut seen = std::collections::HashSet::new();
+        let mut events: Vec<Event> = cache
+            .values()
+            .flat_map(|cached| &cached.events)
+            .filter(|e| filter.match_event(e, MatchEventOptions::new()) && seen.insert(e.id))
+            .cloned()
+            .collect();
+        events.sort_by_key(|e| std::cmp::Reverse(e.created_at));
+        events.truncate(filter.limit.unwrap_or(usize::MAX));
+        events
+    }
+
+    /// `fetch_from_relays` plus the ingested external listings matching
+    /// `filter`, as if they were on one more relay
+    async fn fetch_with_ingested(
+        &self,
+        filter: Filter,
+        cache_key: &str,
+        on_partial: Option<OnPartial<'_>>,
+    ) -> Result<Vec<Event>, JobsError> {
+        let ingested = self.ingest.matching(&filter).await;
+        let merge = |mut events: Vec<Event>| {
+            if !ingested.is_empty() {
+                let seen: std::collections::HashSet<EventId> = events.iter().map(|e| e.id).collect();
+                events.extend(ingested.iter().filter(|e| !seen.contains(&e.id)).cloned());
+                events.sort_by_key(|e| std::cmp::Reverse(e.created_at));
+            }
+            events
+        };
+        let partial = on_partial.map(|on_partial| {
+            move |events: Vec<Event>, answered: usize, relays: usize| on_partial(merge(events), answered, relays)
+        });
+        let partial = partial.as_ref().map(|p| p as OnPartial<'_>);
+        let events = self.fetch_from_relays(filter, cache_key, partial).await?;
+        Ok(merge(events))
+    }
+
+    /// Fetch `filter` from the relays as `query_strategy` says and merge
+    /// what they sent, recording latency, failures, and event yield per
+    /// relay and per strategy. Each relay is read until it sends EOSE, or
+    /// until `relay_timeout`, keeping what it streamed so far. With a merging
+    /// strategy, `on_partial` sees the merged events each time a relay
+    /// answers while others are still pending.
+    async fn fetch_from_relays(
+        &self,
+        filter: Filter,
+        cache_key: &str,
+        on_partial: Option<OnPartial<'_>>,
+    ) -> Result<Vec<Event>, JobsError> {
+        if self.config().offline {
+            return Ok(self.local_events(&filter).await);
+        }
+        self.check_fetch_queue().await?;
+        let start = std::time::Instant::now();
+
+        let config = self.config();
+        let relays = config.relays.clone();
+        let relay_timeouts = self.relay_timeouts(&relays).await;
+        let strategy = config.query_strategy;
+        // Only the pooled strategy waits for fetch slots
+        let pooled = strategy != QueryStrategy::ParallelMerge;
+        let per_relay = relays.iter().zip(relay_timeouts.iter().copied()).map(|(url, relay_timeout)| {
+            let filter = filter.clone();
+            async move {
+                let (relay_ms, outcome) = self.fetch_relay(url, filter, relay_timeout, pooled).await;
+                (url, relay_ms, outcome)
+            }
+        });
+
+        let mut outcomes = Vec::with_capacity(relays.len());
+        match strategy {
+            QueryStrategy::Pooled | QueryStrategy::ParallelMerge | QueryStrategy::RaceFirst => {
+                let mut pending: futures::stream::FuturesUnordered<_> = per_relay.collect();
+                let mut streamed: Vec<Event> = Vec::new();
+                while let Some(outcome) = pending.next().await {
+                    let answered = matches!(&outcome.2, Ok(Ok((events, _))) if !events.is_empty());
+                    if strategy.merges()
+                        && let (Some(on_partial), (_, _, Ok(Ok((events, _))))) = (on_partial, &outcome)
+                        && !pending.is_empty()
+                    {
+                        for event in events {
+                            if !streamed.iter().any(|e| e.id == event.id) {
+                                streamed.push(event.clone());
+                            }
+                        }
+                        let mut so_far = streamed.clone();
+                        so_far.sort_by_key(|e| std::cmp::Reverse(e.created_at));
+                        on_partial(so_far, outcomes.len() + 1, relays.len());
+                    }
+                    outcomes.push(outcome);
+                    if strategy == QueryStrategy::RaceFirst && answered {
+                        // Dropping the rest cancels their fetches
+                        break;
+                    }
+                }
+            }
+            QueryStrategy::PrimaryWithFallback => {
+                for fetch in per_relay {
+                    let outcome = fetch.await;
+                    let answered = matches!(outcome.2, Ok(Ok(_)));
+                    outcomes.push(outcome);
+                    if answered {
+                        break;
+                    }
+                }
+            }
+        }
+        let relays_queried = outcomes.len();
+
+        let mut seen = std::collections::HashSet::new();
+        let mut events_vec: Vec<Event> = Vec::new();
+        let mut any_success = false;
+        let mut last_error: Option<(&String, String)> = None;
+        {
+            let mut metrics = self.metrics.write().await;
+            for (url, relay_ms, outcome) in outcomes {
+                match outcome {
+                    Ok(Ok((events, complete))) => {
+                        let event_count = events.len();
+                        tracing::debug!(
+                            cache_key = %cache_key,
+                            relay = %url,
+                            duration_ms = relay_ms,
+                            event_count = event_count,
+                            eose = complete,
+                            success = true,
+                            "relay_fetch_success"
+                        );
+                        metrics.record_relay_fetch(url, relay_ms, Some(event_count));
+                        trace_relay(url, relay_ms, true);
+                        self.errors.succeeded("relay", url);
+                        any_success = true;
+                        for event in events {
+                            if seen.insert(event.id) {
+                                events_vec.push(event);
+                            }
+                        }
+                    }
+                    Ok(Err(e)) => {
+                        tracing::debug!(
+                            cache_key = %cache_key,
+                            relay = %url,
+                            duration_ms = relay_ms,
+                            error = %e,
+                            success = false,
+                            "relay_fetch_error"
+                        );
+                        metrics.record_relay_fetch(url, relay_ms, None);
+                        trace_relay(url, relay_ms, false);
+                        self.errors.failed(
+                            "relay",
+                            url,
+                            &e,
+                            serde_json::json!({"relay": url, "cache_key": cache_key, "duration_ms": relay_ms as u64}),
+                        );
+                        last_error = Some((url, e));
+                    }
+                    Err(_) => {
+                        tracing::debug!(
+                            cache_key = %cache_key,
+                            relay = %url,
+                            duration_ms = relay_ms,
+                            success = false,
+                            reason = "timeout",
+                            "relay_fetch_timeout"
+                        );
+                        metrics.record_relay_fetch(url, relay_ms, None);
+                        trace_relay(url, relay_ms, false);
+                        self.errors.failed(
+                            "relay",
+                            url,
+                            "timed out",
+                            serde_json::json!({"relay": url, "cache_key": cache_key, "duration_ms": relay_ms as u64}),
+                        );
+                    }
+                }
+            }
+        }
+        events_vec.sort_by_key(|e| std::cmp::Reverse(e.created_at));
+
+        let duration_ms = start.elapsed().as_millis();
+        if any_success {
+            tracing::info!(
+                cache_key = %cache_key,
+                duration_ms = duration_ms,
+                event_count = events_vec.len(),
+                source = "relay",
+                strategy = strategy.as_str(),
+                relays_queried = relays_queried,
+                success = true,
+                "fetch_events_success"
+            );
+            
+            if !events_vec.is_empty() {
+                *self.relay_healthy.lock().await = true;
+            }
+            
+            let mut metrics = self.metrics.write().await;
+            metrics.record_cache_miss(duration_ms, true);
+            metrics.record_strategy_fetch(strategy.as_str(), duration_ms, relays_queried, Some(events_vec.len()));
+            return Ok(events_vec);
+        }
+
+        {
+            let mut metrics = self.metrics.write().await;
+            metrics.record_cache_miss(duration_ms, false);
+            metrics.record_strategy_fetch(strategy.as_str(), duration_ms, relays_queried, None);
+        }
+        *self.relay_healthy.lock().await = false;
+
+        match last_error {
+            Some((relay, message)) => {
+                tracing::warn!(
+                    cache_key = %cache_key,
+                    duration_ms = duration_ms,
+                    relay = %relay,
+                    error = %message,
+                    source = "relay",
+                    success = false,
+                    "fetch_events_error"
+                );
+                Err(JobsError::RelayError { relay: Some(relay.clone()), message })
+            }
+            None => {
+                tracing::warn!(
+                    cache_key = %cache_key,
+                    duration_ms = duration_ms,
+                    source = "relay",
+                    success = false,
+                    reason = "timeout",
+                    "fetch_events_timeout"
+                );
+                Err(JobsError::Timeout { after: relay_timeouts.into_iter().max().unwrap_or(config.relay_timeout) })
+            }
+        }
+    }
+}
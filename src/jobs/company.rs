@@ -0,0 +1,297 @@
+// src/jobs/company.rs
+// Per-company aggregates: company names folded to one canonical key, how
+// openly each company states salaries, and whether the keys posting as a
+// company agree on who they are
+
+use std::collections::{BTreeMap, HashMap};
+
+use nostr_sdk::prelude::*;
+
+use super::model::{JobListing, SalaryRange};
+use super::rates::ExchangeRate;
+
+/// Legal-form words dropped from company names, so "Acme Inc." and "ACME"
+/// are one company
+const LEGAL_SUFFIXES: &[&str] = &[
+    "inc", "incorporated", "llc", "ltd", "limited", "corp", "corporation", "co", "gmbh", "ag", "sa", "sas", "bv", "plc", "pty", "oy", "ab",
+];
+
+/// The key listings of one company share: lowercase alphanumeric words, legal
+/// form suffixes dropped. Empty for a name with nothing else in it.
+pub fn canonical_company(name: &str) -> String {
+    let mut words: Vec<String> = name
+        .split(|c: char| !c.is_alphanumeric() && c != '&')
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    while words.len() > 1 && words.last().is_some_and(|w| LEGAL_SUFFIXES.contains(&w.as_str())) {
+        words.pop();
+    }
+    words.join(" ")
+}
+
+/// How much of a salary a listing states
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SalaryDisclosure {
+    /// A `salary` tag with a minimum, a maximum, a currency and a period
+    Complete,
+    /// A `salary` tag missing some of them
+    Partial,
+    Missing,
+}
+
+pub fn salary_disclosure(listing: &JobListing) -> SalaryDisclosure {
+    let tags: Vec<&[String]> = listing.event.tags.iter().map(|t| t.as_slice()).filter(|t| t[0] == "salary").collect();
+    if tags.is_empty() {
+        return SalaryDisclosure::Missing;
+    }
+    let number = |v: Option<&String>| v.is_some_and(|v| v.replace([',', '_', ' '], "").parse::<f64>().is_ok());
+    let text = |v: Option<&String>| v.is_some_and(|v| !v.trim().is_empty());
+    match tags.iter().any(|t| number(t.get(1)) && number(t.get(2)) && text(t.get(3)) && text(t.get(4))) {
+        true => SalaryDisclosure::Complete,
+        false => SalaryDisclosure::Partial,
+    }
+}
+
+/// How openly one company states salaries across its listings
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompanyTransparency {
+    /// The most used spelling of the company's name
+    pub company: String,
+    pub canonical: String,
+    pub listings: usize,
+    pub complete: usize,
+    pub partial: usize,
+    /// Median minimum and maximum per "CURRENCY/period", of the listings
+    /// stating a salary
+    pub typical_ranges: BTreeMap<String, SalaryRange>,
+}
+
+impl CompanyTransparency {
+    /// Share of listings with a complete salary, 0 to 1
+    pub fn score(&self) -> f64 {
+        match self.listings {
+            0 => 0.0,
+            n => self.complete as f64 / n as f64,
+        }
+    }
+}
+
+/// Salary transparency of every company among `listings`, most transparent
+/// first (then most listings). Salaries in sats or BTC are grouped under
+/// `rate`'s fiat currency.
+pub fn salary_transparency(listings: &[JobListing], rate: Option<&ExchangeRate>) -> Vec<CompanyTransparency> {
+    let mut companies: HashMap<String, Vec<&JobListing>> = HashMap::new();
+    for listing in listings {
+        let Some(name) = listing.company.as_deref() else {
+            continue;
+        };
+        let canonical = canonical_company(name);
+        if !canonical.is_empty() {
+            companies.entry(canonical).or_default().push(listing);
+        }
+    }
+
+    let mut transparency: Vec<CompanyTransparency> = companies
+        .into_iter()
+        .map(|(canonical, listings)| {
+            let mut spellings: HashMap<&str, usize> = HashMap::new();
+            for name in listings.iter().filter_map(|l| l.company.as_deref()) {
+                *spellings.entry(name.trim()).or_default() += 1;
+            }
+            let company = spellings
+                .into_iter()
+                .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+                .map(|(name, _)| name.to_string())
+                .unwrap_or_else(|| canonical.clone());
+            let disclosures: Vec<SalaryDisclosure> = listings.iter().map(|l| salary_disclosure(l)).collect();
+
+            let mut groups: BTreeMap<String, (Vec<f64>, Vec<f64>)> = BTreeMap::new();
+            for salary in listings.iter().filter_map(|l| l.salary.as_ref()) {
+                let salary = rate.and_then(|r| r.convert(salary)).unwrap_or_else(|| salary.clone());
+                let (mins, maxes) = groups.entry(format!("{}/{}", salary.currency, salary.period)).or_default();
+                mins.push(salary.min);
+                maxes.push(salary.max);
+            }
+            let typical_ranges = groups
+                .into_iter()
+                .map(|(group, (mins, maxes))| {
+                    let (currency, period) = group.split_once('/').unwrap_or((&group, ""));
+                    let range = SalaryRange {
+                        min: median(mins),
+                        max: median(maxes),
+                        currency: currency.to_string(),
+                        period: period.to_string(),
+                    };
+                    (group.clone(), range)
+                })
+                .collect();
+
+            CompanyTransparency {
+                company,
+                canonical,
+                listings: listings.len(),
+                complete: disclosures.iter().filter(|d| **d == SalaryDisclosure::Complete).count(),
+                partial: disclosures.iter().filter(|d| **d == SalaryDisclosure::Partial).count(),
+                typical_ranges,
+            }
+        })
+        .collect();
+    transparency.sort_by(|a, b| {
+        b.score()
+            .total_cmp(&a.score())
+            .then_with(|| b.listings.cmp(&a.listings))
+            .then_with(|| a.canonical.cmp(&b.canonical))
+    });
+    transparency
+}
+
+/// Who a key posting as a company says it is, from its kind 0 profile
+#[derive(Clone, Debug, PartialEq)]
+pub struct PosterIdentity {
+    pub pubkey: PublicKey,
+    /// Listings it posted as the company
+    pub listings: usize,
+    /// False when no profile was found; the fields below are then empty
+    pub has_profile: bool,
+    /// `display_name`, else `name`
+    pub name: Option<String>,
+    pub nip05: Option<String>,
+    pub website: Option<String>,
+}
+
+impl PosterIdentity {
+    /// The domain the key ties itself to: its NIP-05's, else its website's
+    pub fn domain(&self) -> Option<String> {
+        let nip05 = self.nip05.as_deref().and_then(|n| n.rsplit_once('@').map(|(_, d)| d).or(Some(n)));
+        nip05.or(self.website.as_deref()).and_then(host)
+    }
+}
+
+/// How the keys posting as one company disagree about who they are
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdentityConflict {
+    /// `domains` (they claim different domains), `unverified` (some claim
+    /// none while others do) or `name` (a profile named after someone else)
+    pub kind: &'static str,
+    pub message: String,
+    /// The keys that look out of place
+    pub pubkeys: Vec<PublicKey>,
+}
+
+/// The identities of the keys behind `listings`, from the newest of their
+/// `profiles` (kind 0 events); most listings first
+pub fn poster_identities(listings: &[JobListing], profiles: &[Event]) -> Vec<PosterIdentity> {
+    let mut counts: HashMap<PublicKey, usize> = HashMap::new();
+    for listing in listings {
+        *counts.entry(listing.author).or_default() += 1;
+    }
+    let mut newest: HashMap<PublicKey, &Event> = HashMap::new();
+    for profile in profiles.iter().filter(|e| e.kind == Kind::Metadata && counts.contains_key(&e.pubkey)) {
+        if newest.get(&profile.pubkey).is_none_or(|known| known.created_at < profile.created_at) {
+            newest.insert(profile.pubkey, profile);
+        }
+    }
+
+    let text = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+    let mut identities: Vec<PosterIdentity> = counts
+        .into_iter()
+        .map(|(pubkey, listings)| {
+            let metadata = newest.get(&pubkey).and_then(|e| Metadata::from_json(&e.content).ok());
+            let has_profile = metadata.is_some();
+            let metadata = metadata.unwrap_or_default();
+            PosterIdentity {
+                pubkey,
+                listings,
+                has_profile,
+                name: text(metadata.display_name).or_else(|| text(metadata.name)),
+                nip05: text(metadata.nip05),
+                website: text(metadata.website),
+            }
+        })
+        .collect();
+    identities.sort_by(|a, b| b.listings.cmp(&a.listings).then_with(|| a.pubkey.cmp(&b.pubkey)));
+    identities
+}
+
+/// Conflicts among the `identities` of the keys posting as `company`:
+/// different domains, keys with no domain next to keys with one, and
+/// profiles named after another company. One key can't conflict.
+pub fn identity_conflicts(company: &str, identities: &[PosterIdentity]) -> Vec<IdentityConflict> {
+    if identities.len() < 2 {
+        return Vec::new();
+    }
+    let canonical = canonical_company(company);
+    let mut conflicts = Vec::new();
+
+    let mut domains: BTreeMap<String, Vec<&PosterIdentity>> = BTreeMap::new();
+    for identity in identities {
+        if let Some(domain) = identity.domain() {
+            domains.entry(domain).or_default().push(identity);
+        }
+    }
+    // The company's own domain: the one named like it, else the busiest
+    let squashed: String = canonical.chars().filter(|c| c.is_alphanumeric()).collect();
+    let official = domains
+        .iter()
+        .max_by_key(|(domain, keys)| {
+            let label: String = domain.split('.').next().unwrap_or_default().chars().filter(|c| c.is_alphanumeric()).collect();
+            (label == squashed, keys.iter().map(|k| k.listings).sum::<usize>())
+        })
+        .map(|(domain, _)| domain.clone());
+
+    if domains.len() > 1 {
+        let official = official.clone().unwrap_or_default();
+        conflicts.push(IdentityConflict {
+            kind: "domains",
+            message: format!(
+                "Keys posting as {} claim different domains: {}",
+                company,
+                domains.iter().map(|(domain, keys)| format!("{} ({} key(s))", domain, keys.len())).collect::<Vec<_>>().join(", ")
+            ),
+            pubkeys: domains.iter().filter(|(d, _)| **d != official).flat_map(|(_, keys)| keys.iter().map(|k| k.pubkey)).collect(),
+        });
+    }
+
+    let unverified: Vec<PublicKey> = identities.iter().filter(|i| i.domain().is_none()).map(|i| i.pubkey).collect();
+    if let Some(official) = official.filter(|_| !unverified.is_empty()) {
+        conflicts.push(IdentityConflict {
+            kind: "unverified",
+            message: format!("{} key(s) post as {} with no NIP-05 or website, while others use {}", unverified.len(), company, official),
+            pubkeys: unverified,
+        });
+    }
+
+    for identity in identities {
+        let Some(name) = &identity.name else {
+            continue;
+        };
+        let named = canonical_company(name);
+        if !named.is_empty() && !named.contains(&canonical) && !canonical.contains(&named) {
+            conflicts.push(IdentityConflict {
+                kind: "name",
+                message: format!("A key posting as {} has a profile named \"{}\" (a recruiter, or an impersonator)", company, name),
+                pubkeys: vec![identity.pubkey],
+            });
+        }
+    }
+    conflicts
+}
+
+/// The lowercase host of a URL or bare domain, without `www.`
+fn host(value: &str) -> Option<String> {
+    let value = value.trim().to_lowercase();
+    let value = value.split_once("://").map(|(_, rest)| rest).unwrap_or(&value);
+    let host = value.split(['/', '?', '#', ':']).next().unwrap_or_default();
+    let host = host.strip_prefix("www.").unwrap_or(host);
+    host.contains('.').then(|| host.to_string())
+}
+
+fn median(mut values: Vec<f64>) -> f64 {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    match values.len() % 2 {
+        0 => (values[mid - 1] + values[mid]) / 2.0,
+        _ => values[mid],
+    }
+}
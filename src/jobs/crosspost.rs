@@ -0,0 +1,52 @@
+// src/jobs/crosspost.rs
+// The same job posted more than once across sources: as a kind 9993
+// listing, a kind 30402 classified, or through the ingestion bridge. Shown
+// as one listing with several representations instead of as duplicates.
+
+use std::collections::HashMap;
+
+use nostr_sdk::PublicKey;
+
+use super::model::JobListing;
+use super::query::ListingSource;
+
+/// `listings` with crossposts folded into the first (highest ranked) of
+/// each: the listings kept, in order, and the other representations of
+/// each kept listing by its job id. Listings are the same job when their
+/// company and title match, ignoring case and spacing; a repeat from the
+/// same source is a repost, not a crosspost, and stays separate.
+pub fn fold<'a>(listings: Vec<&'a JobListing>, bridge: &PublicKey) -> (Vec<&'a JobListing>, HashMap<String, Vec<&'a JobListing>>) {
+    let mut kept: Vec<&JobListing> = Vec::with_capacity(listings.len());
+    let mut groups: HashMap<(String, String), Vec<usize>> = HashMap::new();
+    let mut alternates: HashMap<String, Vec<&JobListing>> = HashMap::new();
+    for listing in listings {
+        let Some(key) = identity(listing) else {
+            kept.push(listing);
+            continue;
+        };
+        let source = ListingSource::of(listing, Some(bridge));
+        let group = groups.entry(key).or_default();
+        // The first kept listing this one would be a new representation of
+        let home = group.iter().copied().find(|&i| {
+            let shown = kept[i];
+            ListingSource::of(shown, Some(bridge)) != source
+                && !alternates.get(&shown.job_id).into_iter().flatten().any(|other| ListingSource::of(other, Some(bridge)) == source)
+        });
+        match home {
+            Some(i) => alternates.entry(kept[i].job_id.clone()).or_default().push(listing),
+            None => {
+                group.push(kept.len());
+                kept.push(listing);
+            }
+        }
+    }
+    (kept, alternates)
+}
+
+/// Normalized company and title; listings missing either are never folded
+fn identity(listing: &JobListing) -> Option<(String, String)> {
+    let normalize = |value: &str| value.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    let company = normalize(listing.company.as_deref()?);
+    let title = normalize(listing.title.as_deref()?);
+    (!company.is_empty() && !title.is_empty()).then_some((company, title))
+}
@@ -0,0 +1,200 @@
+// src/jobs/digest.rs
+// Periodic market digests: new listings, notable salaries and trending skills per day or week
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use nostr_sdk::Timestamp;
+
+use super::model::{JobListing, SalaryRange};
+use super::rates::ExchangeRate;
+
+const DAY_SECS: u64 = 86_400;
+const NEWEST_LISTINGS: usize = 10;
+const NOTABLE_SALARIES: usize = 5;
+const TRENDING_SKILLS: usize = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DigestPeriod {
+    /// UTC calendar days
+    Daily,
+    /// Weeks starting Monday 00:00 UTC
+    Weekly,
+}
+
+impl DigestPeriod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DigestPeriod::Daily => "daily",
+            DigestPeriod::Weekly => "weekly",
+        }
+    }
+
+    pub fn secs(&self) -> u64 {
+        match self {
+            DigestPeriod::Daily => DAY_SECS,
+            DigestPeriod::Weekly => 7 * DAY_SECS,
+        }
+    }
+
+    /// Start of the period containing `at`
+    pub fn start_of(&self, at: Timestamp) -> Timestamp {
+        let day = at.as_secs() / DAY_SECS;
+        let start_day = match self {
+            DigestPeriod::Daily => day,
+            // The epoch fell on a Thursday, three days after a Monday
+            DigestPeriod::Weekly => day - (day + 3) % 7,
+        };
+        Timestamp::from(start_day * DAY_SECS)
+    }
+
+    /// The last complete period before `now`
+    pub fn last_complete(&self, now: Timestamp) -> (Timestamp, Timestamp) {
+        let until = self.start_of(now);
+        (Timestamp::from(until.as_secs() - self.secs()), until)
+    }
+}
+
+impl FromStr for DigestPeriod {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "daily" | "day" => Ok(DigestPeriod::Daily),
+            "weekly" | "week" => Ok(DigestPeriod::Weekly),
+            other => Err(format!("unknown digest period '{}'; use daily or weekly", other)),
+        }
+    }
+}
+
+impl std::fmt::Display for DigestPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A listing mentioned in a digest
+#[derive(Clone, Debug, PartialEq)]
+pub struct DigestEntry {
+    pub job_id: String,
+    pub title: Option<String>,
+    pub company: Option<String>,
+    /// As listed, or converted to the digest currency when quoted in sats/BTC
+    pub salary: Option<SalaryRange>,
+}
+
+impl DigestEntry {
+    fn new(listing: &JobListing, salary: Option<SalaryRange>) -> Self {
+        Self {
+            job_id: listing.job_id.clone(),
+            title: listing.title.clone(),
+            company: listing.company.clone(),
+            salary,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrendingSkill {
+    pub skill: String,
+    /// Listings asking for it this period
+    pub count: usize,
+    /// ... and the period before
+    pub previous: usize,
+}
+
+/// Summary of one period's listings, compared with the period before
+#[derive(Clone, Debug, PartialEq)]
+pub struct Digest {
+    pub period: DigestPeriod,
+    pub since: Timestamp,
+    pub until: Timestamp,
+    pub generated_at: Timestamp,
+    /// Listings posted during the period
+    pub listings: usize,
+    pub previous_listings: usize,
+    pub newest: Vec<DigestEntry>,
+    /// Highest salaries (top of range) in `currency`
+    pub notable_salaries: Vec<DigestEntry>,
+    pub currency: String,
+    /// Skills ranked by how much more often they were asked for
+    pub trending_skills: Vec<TrendingSkill>,
+    /// Rate sats/BTC salaries were converted at, if any were
+    pub exchange_rate: Option<ExchangeRate>,
+    /// Whether a relay deadline cut either sample short
+    pub truncated: bool,
+}
+
+impl Digest {
+    /// Digest of `current` (listings posted between `since` and `until`)
+    /// against `previous` (the period before)
+    pub fn from_listings(
+        period: DigestPeriod,
+        (since, until): (Timestamp, Timestamp),
+        current: &[JobListing],
+        previous: &[JobListing],
+        currency: &str,
+        rate: Option<&ExchangeRate>,
+    ) -> Self {
+        let newest = current.iter().take(NEWEST_LISTINGS).map(|l| DigestEntry::new(l, l.salary.clone())).collect();
+
+        let mut salaried: Vec<DigestEntry> = current
+            .iter()
+            .filter_map(|l| {
+                let salary = l.salary.as_ref()?;
+                let salary = rate.and_then(|r| r.convert(salary)).unwrap_or_else(|| salary.clone());
+                salary.currency.eq_ignore_ascii_case(currency).then(|| DigestEntry::new(l, Some(salary)))
+            })
+            .collect();
+        salaried.sort_by(|a, b| {
+            let max = |e: &DigestEntry| e.salary.as_ref().map_or(0.0, |s| s.max);
+            max(b).total_cmp(&max(a))
+        });
+        salaried.truncate(NOTABLE_SALARIES);
+        let converted = rate.filter(|r| {
+            current.iter().filter_map(|l| l.salary.as_ref()).any(|s| r.convert(s).is_some())
+        });
+
+        let skills = |listings: &[JobListing]| {
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            for skill in listings.iter().flat_map(|l| &l.skills) {
+                *counts.entry(skill.clone()).or_default() += 1;
+            }
+            counts
+        };
+        let previous_skills = skills(previous);
+        let mut trending: Vec<TrendingSkill> = skills(current)
+            .into_iter()
+            .map(|(skill, count)| TrendingSkill {
+                previous: previous_skills.get(&skill).copied().unwrap_or(0),
+                skill,
+                count,
+            })
+            .collect();
+        trending.sort_by(|a, b| {
+            let growth = |t: &TrendingSkill| t.count as i64 - t.previous as i64;
+            growth(b).cmp(&growth(a)).then(b.count.cmp(&a.count)).then_with(|| a.skill.cmp(&b.skill))
+        });
+        trending.truncate(TRENDING_SKILLS);
+
+        Self {
+            period,
+            since,
+            until,
+            generated_at: Timestamp::now(),
+            listings: current.len(),
+            previous_listings: previous.len(),
+            newest,
+            notable_salaries: salaried,
+            currency: currency.to_string(),
+            trending_skills: trending,
+            exchange_rate: converted.cloned(),
+            truncated: false,
+        }
+    }
+
+    /// `YYYY-MM-DD` of the period start, which names it in the archive
+    pub fn date(&self) -> String {
+        self.since.to_human_datetime().chars().take(10).collect()
+    }
+}
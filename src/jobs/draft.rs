@@ -0,0 +1,117 @@
+// src/jobs/draft.rs
+// Job listings being composed: validated and previewed before they are signed and published
+
+use nostr_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::error::JobsError;
+use super::model::{JobListing, SalaryRange};
+
+/// Longest expiration a draft can set, in days
+pub(crate) const MAX_EXPIRY_DAYS: u64 = 3650;
+
+/// A listing not yet published, edited field by field with `draft_job`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct JobDraft {
+    /// The `job-id` tag the published listing will carry
+    pub job_id: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub company: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub employment_types: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub salary: Option<SalaryRange>,
+    /// Full listing text, the event content
+    #[serde(default)]
+    pub description: String,
+    /// NIP-40 expiration, in seconds since the epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    pub updated_at: u64,
+}
+
+impl JobDraft {
+    /// What has to change before this draft can be published; empty when
+    /// it is ready
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.title.trim().is_empty() {
+            problems.push("title is empty".to_string());
+        }
+        if self.description.trim().is_empty() {
+            problems.push("description is empty".to_string());
+        }
+        if let Some(salary) = &self.salary {
+            if salary.min < 0.0 || salary.max < salary.min {
+                problems.push(format!("salary range {} - {} is invalid", salary.min, salary.max));
+            }
+            if salary.currency.trim().is_empty() || salary.period.trim().is_empty() {
+                problems.push("salary needs a currency and a period".to_string());
+            }
+        }
+        if self.expires_at.is_some_and(|at| at <= Timestamp::now().as_secs()) {
+            problems.push("expiration is in the past".to_string());
+        }
+        problems
+    }
+
+    /// The unsigned listing event of `kind`, tagged like every other
+    /// listing on the board
+    pub fn to_event_builder(&self, kind: Kind) -> Result<EventBuilder, JobsError> {
+        let mut tags: Vec<Vec<String>> = vec![
+            vec!["job-id".into(), self.job_id.clone()],
+            vec!["j".into(), self.job_id.clone()],
+            vec!["title".into(), self.title.clone()],
+        ];
+        let optional = [("company", &self.company), ("location", &self.location)];
+        tags.extend(optional.into_iter().filter_map(|(name, value)| Some(vec![name.to_string(), value.clone()?])));
+        tags.extend(self.employment_types.iter().map(|t| vec!["employment-type".into(), t.clone()]));
+        tags.extend(self.skills.iter().map(|s| vec!["skill".into(), s.clone()]));
+        if let Some(salary) = &self.salary {
+            tags.push(vec![
+                "salary".into(),
+                salary.min.to_string(),
+                salary.max.to_string(),
+                salary.currency.clone(),
+                salary.period.clone(),
+            ]);
+        }
+        if let Some(expires_at) = self.expires_at {
+            tags.push(vec!["expiration".into(), expires_at.to_string()]);
+        }
+
+        let tags = tags.into_iter().map(Tag::parse).collect::<Result<Vec<_>, _>>().map_err(|e| JobsError::ParseError {
+            what: "draft",
+            input: self.job_id.clone(),
+            message: e.to_string(),
+        })?;
+        Ok(EventBuilder::new(kind, &self.description).tags(tags))
+    }
+
+    /// The listing exactly as it would be published, but signed with a
+    /// throwaway key so it can be rendered; it never leaves the server
+    pub fn preview(&self, kind: Kind) -> Result<JobListing, JobsError> {
+        let event = self
+            .to_event_builder(kind)?
+            .sign_with_keys(&Keys::generate())
+            .map_err(|e| JobsError::Config(format!("Could not render the draft: {}", e)))?;
+        Ok(JobListing::from_event(event))
+    }
+}
+
+/// Lowercase letters and digits of `text`, other runs joined by `-`, for
+/// job ids derived from titles
+pub(crate) fn slug(text: &str) -> String {
+    let slug = text
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() { "listing".to_string() } else { slug }
+}
@@ -0,0 +1,127 @@
+// src/jobs/dvm.rs
+// NIP-90 data vending machine jobs, used to extract skills from resume text
+
+use nostr_sdk::prelude::*;
+use serde_json::Value;
+
+use super::error::JobsError;
+
+/// NIP-90 job feedback (status updates, errors, payment requests)
+const FEEDBACK_KIND: u16 = 7000;
+
+/// Skills a DVM extracted from a resume
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResumeAnalysis {
+    /// The job request event
+    pub request_id: EventId,
+    /// The DVM that answered
+    pub provider: PublicKey,
+    pub skills: Vec<String>,
+    /// The result event's content, as returned
+    pub output: String,
+}
+
+/// A job request of `kind` with `resume` as text input, signed by `keys`.
+/// Addressed to `provider` when given, with the input encrypted to it
+/// (NIP-90 encrypted params); otherwise any DVM may take it and the resume
+/// is public on the relays.
+pub fn resume_request(kind: u16, resume: &str, provider: Option<&PublicKey>, keys: &Keys) -> Result<Event, JobsError> {
+    let parse_error = |e: tag::Error| JobsError::ParseError {
+        what: "resume",
+        input: String::new(),
+        message: e.to_string(),
+    };
+    let inputs = [Tag::parse(["i", resume, "text"]), Tag::parse(["param", "task", "extract-skills"])]
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(parse_error)?;
+    let output = Tag::parse(["output", "application/json"]).map_err(parse_error)?;
+    let builder = match provider {
+        Some(provider) => {
+            let params = serde_json::to_string(&inputs.iter().map(Tag::as_slice).collect::<Vec<_>>()).unwrap_or_default();
+            let content = nip04::encrypt(keys.secret_key(), provider, params)
+                .map_err(|e| JobsError::Config(format!("Could not encrypt the DVM request: {}", e)))?;
+            let encrypted = Tag::custom(TagKind::custom("encrypted"), Vec::<String>::new());
+            EventBuilder::new(Kind::from(kind), content).tags([output, Tag::public_key(*provider), encrypted])
+        }
+        None => EventBuilder::new(Kind::from(kind), "").tags(inputs.into_iter().chain([output])),
+    };
+    builder
+        .sign_with_keys(keys)
+        .map_err(|e| JobsError::Config(format!("Could not sign the DVM request: {}", e)))
+}
+
+/// Results (`kind + 1000`) and feedback referring to `request`, only from
+/// `provider` when one is configured
+pub fn response_filter(request: &Event, provider: Option<&PublicKey>) -> Filter {
+    let filter = Filter::new()
+        .kinds([Kind::from(request.kind.as_u16() + 1000), Kind::from(FEEDBACK_KIND)])
+        .event(request.id);
+    match provider {
+        Some(provider) => filter.author(*provider),
+        None => filter,
+    }
+}
+
+/// The answer to `request` among `events`: its result, an error if a DVM
+/// gave up on it, or `None` while it is still pending. Only `provider`'s
+/// events count when one is configured; an encrypted result is decrypted
+/// with the request's `keys`.
+pub fn outcome(request: &Event, events: &[Event], provider: Option<&PublicKey>, keys: &Keys) -> Option<Result<ResumeAnalysis, JobsError>> {
+    let result_kind = Kind::from(request.kind.as_u16() + 1000);
+    let events: Vec<&Event> = events.iter().filter(|e| provider.is_none_or(|p| e.pubkey == *p)).collect();
+    if let Some(result) = events.iter().find(|e| e.kind == result_kind) {
+        let output = if result.tags.iter().any(|t| t.kind() == TagKind::custom("encrypted")) {
+            match nip04::decrypt(keys.secret_key(), &result.pubkey, &result.content) {
+                Ok(output) => output,
+                Err(e) => {
+                    return Some(Err(JobsError::Dvm {
+                        request_id: request.id.to_hex(),
+                        message: format!("could not decrypt the result: {}", e),
+                    }));
+                }
+            }
+        } else {
+            result.content.clone()
+        };
+        return Some(Ok(ResumeAnalysis {
+            request_id: request.id,
+            provider: result.pubkey,
+            skills: parse_skills(&output),
+            output,
+        }));
+    }
+    events
+        .iter()
+        .filter(|e| e.kind == Kind::from(FEEDBACK_KIND))
+        .find_map(|e| {
+            let status = e.tags.iter().find(|t| t.kind() == TagKind::custom("status"))?.as_slice();
+            (status.get(1).map(String::as_str) == Some("error")).then(|| {
+                let message = status.get(2).cloned().filter(|m| !m.is_empty()).unwrap_or_else(|| e.content.clone());
+                Err(JobsError::Dvm { request_id: request.id.to_hex(), message })
+            })
+        })
+}
+
+/// Skills from a DVM result: a JSON list, an object with a `skills` list,
+/// or plain text with one skill per line or comma
+pub fn parse_skills(output: &str) -> Vec<String> {
+    let values: Vec<String> = match serde_json::from_str::<Value>(output) {
+        Ok(Value::Array(items)) => items.iter().filter_map(|v| v.as_str().map(str::to_string)).collect(),
+        Ok(Value::Object(object)) => object
+            .get("skills")
+            .and_then(Value::as_array)
+            .map(|items| items.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+            .unwrap_or_default(),
+        _ => output.split([',', '\n']).map(str::to_string).collect(),
+    };
+
+    let mut skills: Vec<String> = Vec::new();
+    for value in values {
+        let skill = value.trim().trim_start_matches(['-', '*', '•']).trim();
+        if !skill.is_empty() && !skills.iter().any(|s| s.eq_ignore_ascii_case(skill)) {
+            skills.push(skill.to_string());
+        }
+    }
+    skills
+}
@@ -0,0 +1,168 @@
+// src/jobs/engagement.rs
+// How candidates come across the operator's own listings: search impressions
+// and detail views counted here, zaps, comments and application DMs read from the relays
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use nostr_sdk::prelude::*;
+
+use super::model::JobListing;
+
+const DAY_SECS: u64 = 86_400;
+/// Days of impressions and views kept per listing
+pub const ENGAGEMENT_DAYS: u64 = 30;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interaction {
+    /// Returned in `search_jobs` results
+    Impression,
+    /// Opened with `get_job_details`
+    View,
+}
+
+/// Engagement of one listing over one UTC day (or a whole window)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DayEngagement {
+    pub impressions: usize,
+    pub views: usize,
+    /// NIP-57 zap receipts
+    pub zaps: usize,
+    pub zapped_sats: u64,
+    /// Replies (kind 1) and NIP-22 comments
+    pub comments: usize,
+}
+
+impl DayEngagement {
+    pub fn add(&mut self, other: &DayEngagement) {
+        self.impressions += other.impressions;
+        self.views += other.views;
+        self.zaps += other.zaps;
+        self.zapped_sats += other.zapped_sats;
+        self.comments += other.comments;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == DayEngagement::default()
+    }
+}
+
+/// One listing's engagement over the days asked for
+#[derive(Clone, Debug)]
+pub struct ListingAnalytics {
+    /// The newest event of the job id
+    pub listing: JobListing,
+    pub total: DayEngagement,
+    /// By the start of each UTC day with any engagement, oldest first
+    pub daily: BTreeMap<u64, DayEngagement>,
+}
+
+/// A NIP-17 direct message to the posting key, typically an application
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApplicationMessage {
+    pub sender: PublicKey,
+    /// When the sender wrote it (the rumor's time, not the gift wrap's)
+    pub sent_at: Timestamp,
+    pub content: String,
+    /// The first of the operator's job ids the message mentions
+    pub job_id: Option<String>,
+}
+
+/// Impressions and views per job id and UTC day, kept in memory for the
+/// last `ENGAGEMENT_DAYS`
+#[derive(Debug, Default)]
+pub struct EngagementLog {
+    days: Mutex<HashMap<String, BTreeMap<u64, DayEngagement>>>,
+}
+
+impl EngagementLog {
+    pub fn record(&self, interaction: Interaction, job_id: &str, at: Timestamp) {
+        let day = day_of(at);
+        let mut days = self.days.lock().expect("engagement lock poisoned");
+        let listing = days.entry(job_id.to_string()).or_default();
+        let counts = listing.entry(day).or_default();
+        match interaction {
+            Interaction::Impression => counts.impressions += 1,
+            Interaction::View => counts.views += 1,
+        }
+        let oldest = day.saturating_sub((ENGAGEMENT_DAYS - 1) * DAY_SECS);
+        listing.retain(|d, _| *d >= oldest);
+    }
+
+    /// Days of `job_id` from `since` on
+    pub fn days(&self, job_id: &str, since: Timestamp) -> BTreeMap<u64, DayEngagement> {
+        let days = self.days.lock().expect("engagement lock poisoned");
+        let since = day_of(since);
+        days.get(job_id).map(|d| d.range(since..).map(|(d, e)| (*d, *e)).collect()).unwrap_or_default()
+    }
+}
+
+/// Start of the UTC day containing `at`
+pub fn day_of(at: Timestamp) -> u64 {
+    at.as_secs() / DAY_SECS * DAY_SECS
+}
+
+/// Count the zap receipts and comments among `reactions` on the day they
+/// were made, for the job id of the listing event they reference
+pub fn tally_reactions(
+    reactions: &[Event],
+    job_ids: &HashMap<EventId, String>,
+    daily: &mut HashMap<String, BTreeMap<u64, DayEngagement>>,
+) {
+    for event in reactions {
+        let referenced = event.tags.iter().find_map(|tag| match tag.as_slice() {
+            [name, id, ..] if name == "e" || name == "E" => EventId::parse(id).ok().and_then(|id| job_ids.get(&id)),
+            _ => None,
+        });
+        let Some(job_id) = referenced else {
+            continue;
+        };
+        let counts = daily.entry(job_id.clone()).or_default().entry(day_of(event.created_at)).or_default();
+        if event.kind == Kind::ZapReceipt {
+            counts.zaps += 1;
+            counts.zapped_sats += zap_sats(event);
+        } else {
+            counts.comments += 1;
+        }
+    }
+}
+
+/// Sats a zap receipt is for: the zap request's `amount` (msats), else the
+/// amount encoded in the bolt11 invoice
+fn zap_sats(receipt: &Event) -> u64 {
+    let tag = |name: &str| receipt.tags.iter().find(|t| t.kind() == TagKind::custom(name)).and_then(|t| t.content());
+    let requested = tag("description")
+        .and_then(|description| serde_json::from_str::<Event>(description).ok())
+        .and_then(|request| {
+            request
+                .tags
+                .iter()
+                .find(|t| t.kind() == TagKind::Amount)
+                .and_then(|t| t.content()?.parse::<u64>().ok())
+        })
+        .map(|msats| msats / 1000);
+    requested.or_else(|| tag("bolt11").and_then(bolt11_sats)).unwrap_or(0)
+}
+
+/// The amount of a bolt11 invoice in sats, from its human-readable part
+/// (`lnbc2500u1...` is 2500 µBTC)
+fn bolt11_sats(invoice: &str) -> Option<u64> {
+    let invoice = invoice.to_lowercase();
+    let hrp = &invoice[..invoice.rfind('1')?];
+    let amount = hrp.trim_start_matches("ln").trim_start_matches(|c: char| c.is_ascii_alphabetic());
+    let (digits, multiplier) = match amount.chars().last()? {
+        c if c.is_ascii_digit() => (amount, None),
+        c => (&amount[..amount.len() - 1], Some(c)),
+    };
+    let value: u64 = digits.parse().ok()?;
+    // Sats per unit of the multiplier, as a fraction
+    let (numerator, denominator) = match multiplier {
+        None => (100_000_000, 1),
+        Some('m') => (100_000, 1),
+        Some('u') => (100, 1),
+        Some('n') => (1, 10),
+        Some('p') => (1, 10_000),
+        Some(_) => return None,
+    };
+    Some(value * numerator / denominator)
+}
@@ -0,0 +1,141 @@
+// src/jobs/error.rs
+// Failure causes of the job board API, kept machine-readable for MCP error data
+
+use std::time::Duration;
+
+use serde_json::{Value, json};
+
+use crate::i18n::{self, Locale};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum JobsError {
+    /// No relay answered before the deadline
+    Timeout { after: Duration },
+    /// Relays answered with errors; `relay` is the last one that failed, if known
+    RelayError { relay: Option<String>, message: String },
+    /// No listing with this job id or event id
+    NotFound { job_id: String },
+    /// Input that couldn't be parsed, e.g. a relay URL, author key or date
+    ParseError { what: &'static str, input: String, message: String },
+    /// The geocoder could not be reached or answered with an error
+    Geocoding { place: String, message: String },
+    /// No BTC price could be fetched for `fiat`
+    ExchangeRate { fiat: String, message: String },
+    /// An external job board (RSS, Greenhouse, Lever) could not be fetched or parsed
+    Ingest { source: String, message: String },
+    /// A NIP-90 DVM reported an error for a job request, or never answered
+    Dvm { request_id: String, message: String },
+    /// Too much work already queued; shed instead of timing out later
+    Overloaded { retry_after: Duration },
+    /// The caller's API key used up a daily quota (`tool_calls` or `relay_fetches`)
+    QuotaExceeded { quota: &'static str, limit: u64, resets_in: Duration },
+    /// Quotas are metered per API key and the caller sent none from `API_KEYS`
+    UnknownApiKey,
+    /// Settings that can't work together, e.g. an empty relay list
+    Config(String),
+}
+
+impl JobsError {
+    /// Stable identifier for the kind of failure
+    pub fn code(&self) -> &'static str {
+        match self {
+            JobsError::Timeout { .. } => "timeout",
+            JobsError::RelayError { .. } => "relay_error",
+            JobsError::NotFound { .. } => "not_found",
+            JobsError::ParseError { .. } => "parse_error",
+            JobsError::Geocoding { .. } => "geocoding_error",
+            JobsError::ExchangeRate { .. } => "exchange_rate_error",
+            JobsError::Ingest { .. } => "ingest_error",
+            JobsError::Dvm { .. } => "dvm_error",
+            JobsError::Overloaded { .. } => "overloaded",
+            JobsError::QuotaExceeded { .. } => "quota_exceeded",
+            JobsError::UnknownApiKey => "unknown_api_key",
+            JobsError::Config(_) => "config_error",
+        }
+    }
+
+    /// Whether retrying the same request later may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            JobsError::Timeout { .. }
+                | JobsError::RelayError { .. }
+                | JobsError::Geocoding { .. }
+                | JobsError::ExchangeRate { .. }
+                | JobsError::Ingest { .. }
+                | JobsError::Dvm { .. }
+                | JobsError::Overloaded { .. }
+        )
+    }
+
+    /// The error code plus its details, for structured error payloads
+    pub fn to_json(&self) -> Value {
+        let mut data = match self {
+            JobsError::Timeout { after } => json!({"timeout_ms": after.as_millis() as u64}),
+            JobsError::RelayError { relay, message } => json!({"relay": relay, "message": message}),
+            JobsError::NotFound { job_id } => json!({"job_id": job_id}),
+            JobsError::ParseError { what, input, message } => {
+                json!({"field": what, "input": input, "message": message})
+            }
+            JobsError::Geocoding { place, message } => json!({"place": place, "message": message}),
+            JobsError::ExchangeRate { fiat, message } => json!({"fiat": fiat, "message": message}),
+            JobsError::Ingest { source, message } => json!({"source": source, "message": message}),
+            JobsError::Dvm { request_id, message } => json!({"request_id": request_id, "message": message}),
+            JobsError::Overloaded { retry_after } => json!({"retry_after_secs": retry_after.as_secs()}),
+            JobsError::QuotaExceeded { quota, limit, resets_in } => {
+                json!({"quota": quota, "limit": limit, "resets_in_secs": resets_in.as_secs()})
+            }
+            JobsError::UnknownApiKey => json!({}),
+            JobsError::Config(message) => json!({"message": message}),
+        };
+        data["error"] = json!(self.code());
+        data["retryable"] = json!(self.is_retryable());
+        data
+    }
+}
+
+impl JobsError {
+    /// What went wrong, for people reading `locale`
+    pub fn message(&self, locale: Locale) -> String {
+        match self {
+            JobsError::Timeout { after } => i18n::text_with(locale, "error.timeout", &[("ms", &after.as_millis())]),
+            JobsError::RelayError { relay: Some(relay), message } => {
+                i18n::text_with(locale, "error.relay", &[("relay", relay), ("message", message)])
+            }
+            JobsError::RelayError { relay: None, message } => i18n::text_with(locale, "error.relay_unknown", &[("message", message)]),
+            JobsError::NotFound { job_id } => i18n::text_with(locale, "error.not_found", &[("job_id", job_id)]),
+            JobsError::ParseError { what, input, message } => {
+                i18n::text_with(locale, "error.parse", &[("what", what), ("input", input), ("message", message)])
+            }
+            JobsError::Geocoding { place, message } => i18n::text_with(locale, "error.geocoding", &[("place", place), ("message", message)]),
+            JobsError::ExchangeRate { fiat, message } => {
+                i18n::text_with(locale, "error.exchange_rate", &[("fiat", fiat), ("message", message)])
+            }
+            JobsError::Ingest { source, message } => i18n::text_with(locale, "error.ingest", &[("source", source), ("message", message)]),
+            JobsError::Dvm { request_id, message } => {
+                i18n::text_with(locale, "error.dvm", &[("request_id", request_id), ("message", message)])
+            }
+            JobsError::Overloaded { retry_after } => i18n::text_with(locale, "error.overloaded", &[("secs", &retry_after.as_secs())]),
+            JobsError::QuotaExceeded { quota, limit, resets_in } => i18n::text_with(
+                locale,
+                "error.quota",
+                &[
+                    ("quota", &quota.replace('_', " ")),
+                    ("limit", limit),
+                    ("hours", &(resets_in.as_secs() / 3600)),
+                    ("minutes", &format!("{:02}", resets_in.as_secs() % 3600 / 60)),
+                ],
+            ),
+            JobsError::UnknownApiKey => i18n::text(locale, "error.api_key").to_string(),
+            JobsError::Config(message) => message.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for JobsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message(Locale::En))
+    }
+}
+
+impl std::error::Error for JobsError {}
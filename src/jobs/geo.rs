@@ -0,0 +1,214 @@
+// src/jobs/geo.rs
+// Geohash cells, distances and place-name geocoding for radius search
+
+use std::time::Duration;
+
+use serde_json::Value;
+
+use super::error::JobsError;
+
+const BASE32: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+const EARTH_RADIUS_KM: f64 = 6371.0;
+const KM_PER_DEGREE: f64 = 111.32;
+const MAX_PRECISION: usize = 9;
+pub const DEFAULT_RADIUS_KM: f64 = 25.0;
+const GEOCODER_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GeoPoint {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+impl GeoPoint {
+    /// Great-circle (haversine) distance
+    pub fn distance_km(&self, other: &GeoPoint) -> f64 {
+        let (lat1, lat2) = (self.lat.to_radians(), other.lat.to_radians());
+        let dlat = lat2 - lat1;
+        let dlon = (other.lon - self.lon).to_radians();
+        let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+}
+
+/// A geohash cell: its center and half its height and width in degrees
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GeoCell {
+    pub center: GeoPoint,
+    pub lat_error: f64,
+    pub lon_error: f64,
+}
+
+impl GeoCell {
+    /// Distance from the center to a corner
+    pub fn radius_km(&self) -> f64 {
+        self.center.distance_km(&GeoPoint {
+            lat: (self.center.lat + self.lat_error).clamp(-90.0, 90.0),
+            lon: self.center.lon + self.lon_error,
+        })
+    }
+}
+
+/// Everything within `radius_km` of `center`
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeoRadius {
+    pub center: GeoPoint,
+    pub radius_km: f64,
+}
+
+impl GeoRadius {
+    /// The center's geohash cell at the finest precision whose cells are
+    /// still at least `radius_km` across, plus its eight neighbours; together
+    /// they cover the circle.
+    pub fn cells(&self) -> Vec<String> {
+        let precision = (1..=MAX_PRECISION)
+            .take_while(|&p| {
+                let (height, width) = cell_size(p);
+                let width_km = width * KM_PER_DEGREE * self.center.lat.to_radians().cos();
+                height * KM_PER_DEGREE >= self.radius_km && width_km >= self.radius_km
+            })
+            .last()
+            .unwrap_or(1);
+        let (height, width) = cell_size(precision);
+
+        let mut cells = Vec::with_capacity(9);
+        for dlat in [-height, 0.0, height] {
+            for dlon in [-width, 0.0, width] {
+                let point = GeoPoint {
+                    lat: (self.center.lat + dlat).clamp(-90.0, 90.0),
+                    lon: wrap_longitude(self.center.lon + dlon),
+                };
+                let cell = encode(&point, precision);
+                if !cells.contains(&cell) {
+                    cells.push(cell);
+                }
+            }
+        }
+        cells
+    }
+
+    /// Distance from the center to the nearest of `geohashes` that shares a
+    /// prefix with the covering cells and lies within the radius (allowing
+    /// for the imprecision of a short geohash)
+    pub fn distance_km<'a>(&self, geohashes: impl IntoIterator<Item = &'a str>) -> Option<f64> {
+        let cells = self.cells();
+        geohashes
+            .into_iter()
+            .map(str::to_lowercase)
+            .filter(|hash| cells.iter().any(|cell| cell.starts_with(hash.as_str()) || hash.starts_with(cell.as_str())))
+            .filter_map(|hash| decode(&hash))
+            .filter_map(|cell| {
+                let distance = self.center.distance_km(&cell.center);
+                (distance <= self.radius_km + cell.radius_km()).then_some(distance)
+            })
+            .min_by(f64::total_cmp)
+    }
+}
+
+pub fn encode(point: &GeoPoint, precision: usize) -> String {
+    let (mut lat_range, mut lon_range) = ((-90.0, 90.0), (-180.0, 180.0));
+    let mut hash = String::with_capacity(precision);
+    let mut bits = 0;
+    let mut index = 0;
+    let mut even = true;
+    while hash.len() < precision {
+        let (range, value) = if even { (&mut lon_range, point.lon) } else { (&mut lat_range, point.lat) };
+        let mid = (range.0 + range.1) / 2.0;
+        index <<= 1;
+        if value >= mid {
+            index |= 1;
+            range.0 = mid;
+        } else {
+            range.1 = mid;
+        }
+        even = !even;
+        bits += 1;
+        if bits == 5 {
+            hash.push(BASE32[index] as char);
+            bits = 0;
+            index = 0;
+        }
+    }
+    hash
+}
+
+/// `None` for an empty or non-geohash string
+pub fn decode(geohash: &str) -> Option<GeoCell> {
+    if geohash.is_empty() {
+        return None;
+    }
+    let (mut lat_range, mut lon_range) = ((-90.0f64, 90.0f64), (-180.0f64, 180.0f64));
+    let mut even = true;
+    for c in geohash.bytes() {
+        let index = BASE32.iter().position(|&b| b == c.to_ascii_lowercase())?;
+        for shift in (0..5).rev() {
+            let range = if even { &mut lon_range } else { &mut lat_range };
+            let mid = (range.0 + range.1) / 2.0;
+            if index >> shift & 1 == 1 {
+                range.0 = mid;
+            } else {
+                range.1 = mid;
+            }
+            even = !even;
+        }
+    }
+    Some(GeoCell {
+        center: GeoPoint {
+            lat: (lat_range.0 + lat_range.1) / 2.0,
+            lon: (lon_range.0 + lon_range.1) / 2.0,
+        },
+        lat_error: (lat_range.1 - lat_range.0) / 2.0,
+        lon_error: (lon_range.1 - lon_range.0) / 2.0,
+    })
+}
+
+/// Height and width in degrees of a cell at `precision`
+fn cell_size(precision: usize) -> (f64, f64) {
+    let bits = precision * 5;
+    let lon_bits = bits.div_ceil(2);
+    let lat_bits = bits / 2;
+    (180.0 / (1u64 << lat_bits) as f64, 360.0 / (1u64 << lon_bits) as f64)
+}
+
+fn wrap_longitude(lon: f64) -> f64 {
+    (lon + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Resolve `place` with the geocoder at `url_template` (`{query}` is replaced
+/// by the URL-encoded place). The response must be Nominatim-style JSON: a
+/// list of results with `lat` and `lon`, as numbers or strings.
+pub async fn geocode(url_template: &str, place: &str) -> Result<GeoPoint, JobsError> {
+    let failed = |message: String| JobsError::Geocoding { place: place.to_string(), message };
+    let url = url_template.replace("{query}", &encode_query(place));
+    let response = reqwest::Client::new()
+        .get(&url)
+        .header(reqwest::header::USER_AGENT, concat!("jobmcp/", env!("CARGO_PKG_VERSION")))
+        .timeout(GEOCODER_TIMEOUT)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| failed(e.to_string()))?;
+    let body: Value = response.json().await.map_err(|e| failed(e.to_string()))?;
+
+    let coordinate = |value: &Value| value.as_f64().or_else(|| value.as_str()?.parse().ok());
+    let first = body.as_array().and_then(|results| results.first()).unwrap_or(&body);
+    match (first.get("lat").and_then(coordinate), first.get("lon").and_then(coordinate)) {
+        (Some(lat), Some(lon)) => Ok(GeoPoint { lat, lon }),
+        _ => Err(JobsError::ParseError {
+            what: "place",
+            input: place.to_string(),
+            message: "the geocoder found no match".to_string(),
+        }),
+    }
+}
+
+/// Percent-encode everything but unreserved characters
+fn encode_query(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
@@ -0,0 +1,118 @@
+// src/jobs/health.rs
+// Per-relay health probes: when each relay is checked next, backing off from
+// dead relays and checking often on ones that just came back
+
+use std::time::{Duration, Instant};
+
+use nostr_sdk::Timestamp;
+
+/// Between probes of a relay that keeps answering
+pub(crate) const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// Longest wait between probes of a relay that keeps failing; the wait
+/// doubles from `HEALTH_CHECK_INTERVAL` with every failure up to this
+const MAX_HEALTH_CHECK_BACKOFF: Duration = Duration::from_secs(600);
+/// Between probes of a relay that just recovered
+const RECOVERY_PROBE_INTERVAL: Duration = Duration::from_secs(5);
+/// Quick probes a recovered relay gets before the normal interval resumes
+const RECOVERY_PROBES: u32 = 3;
+
+/// The last health probe of one relay: a fetch for one listing of the
+/// board's kinds, so healthy means it serves job events
+#[derive(Clone, Debug, PartialEq)]
+pub struct RelayProbe {
+    pub healthy: bool,
+    /// Failed probes in a row
+    pub consecutive_failures: u32,
+    /// Probed at the quick recovery interval after failing
+    pub recovering: bool,
+    pub checked_at: Timestamp,
+    pub last_error: Option<String>,
+    /// Until the next scheduled probe
+    pub next_probe_in: Duration,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct ProbeSchedule {
+    healthy: bool,
+    consecutive_failures: u32,
+    /// Quick probes left after recovering
+    recovery_left: u32,
+    recovering: bool,
+    checked_at: Option<Timestamp>,
+    last_error: Option<String>,
+    next_at: Instant,
+}
+
+impl ProbeSchedule {
+    /// A relay first probed after `delay`
+    pub(crate) fn new(delay: Duration) -> Self {
+        Self {
+            healthy: false,
+            consecutive_failures: 0,
+            recovery_left: 0,
+            recovering: false,
+            checked_at: None,
+            last_error: None,
+            next_at: Instant::now() + delay,
+        }
+    }
+
+    pub(crate) fn is_due(&self, now: Instant) -> bool {
+        self.next_at <= now
+    }
+
+    pub(crate) fn next_at(&self) -> Instant {
+        self.next_at
+    }
+
+    pub(crate) fn is_healthy(&self) -> bool {
+        self.healthy
+    }
+
+    /// Note a probe's outcome and schedule the next one. Returns whether
+    /// the relay changed between healthy and not.
+    pub(crate) fn record(&mut self, outcome: Result<(), String>) -> bool {
+        let was_healthy = self.healthy;
+        let interval = match outcome {
+            Ok(()) => {
+                if self.consecutive_failures > 0 {
+                    self.recovery_left = RECOVERY_PROBES;
+                }
+                self.healthy = true;
+                self.consecutive_failures = 0;
+                self.last_error = None;
+                self.recovering = self.recovery_left > 0;
+                if self.recovering {
+                    self.recovery_left -= 1;
+                    RECOVERY_PROBE_INTERVAL
+                } else {
+                    HEALTH_CHECK_INTERVAL
+                }
+            }
+            Err(e) => {
+                self.healthy = false;
+                self.consecutive_failures += 1;
+                self.recovery_left = 0;
+                self.recovering = false;
+                self.last_error = Some(e);
+                let doublings = (self.consecutive_failures - 1).min(16);
+                (HEALTH_CHECK_INTERVAL * 2u32.pow(doublings)).min(MAX_HEALTH_CHECK_BACKOFF)
+            }
+        };
+        self.checked_at = Some(Timestamp::now());
+        self.next_at = Instant::now() + interval;
+        was_healthy != self.healthy
+    }
+
+    /// `None` until the first probe
+    pub(crate) fn report(&self) -> Option<RelayProbe> {
+        self.checked_at.map(|checked_at| RelayProbe {
+            healthy: self.healthy,
+            consecutive_failures: self.consecutive_failures,
+            recovering: self.recovering,
+            checked_at,
+            last_error: self.last_error.clone(),
+            next_probe_in: self.next_at.saturating_duration_since(Instant::now()),
+        })
+    }
+}
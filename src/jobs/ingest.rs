@@ -0,0 +1,590 @@
+// src/jobs/ingest.rs
+// Listings pulled from conventional job boards (RSS, Greenhouse, Lever) and served alongside Nostr events
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use nostr_sdk::nips::nip09::EventDeletionRequest;
+use nostr_sdk::prelude::*;
+use serde_json::Value;
+use tokio::sync::RwLock;
+
+use super::error::JobsError;
+use super::model::{JobListing, SalaryRange};
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(15);
+/// Latest year a date may name; later ones are typos or hostile input
+pub(crate) const MAX_YEAR: i64 = 9999;
+/// Tag naming where an ingested listing came from: `["source", kind, url]`
+const SOURCE_TAG: &str = "source";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SourceKind {
+    /// An RSS 2.0 or Atom feed
+    Rss,
+    /// A Greenhouse job board, by board token
+    Greenhouse,
+    /// A Lever job site, by company slug
+    Lever,
+}
+
+impl SourceKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SourceKind::Rss => "rss",
+            SourceKind::Greenhouse => "greenhouse",
+            SourceKind::Lever => "lever",
+        }
+    }
+}
+
+/// One configured feed, written `rss:<url>`, `greenhouse:<board token>`
+/// or `lever:<company>`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IngestSource {
+    pub kind: SourceKind,
+    pub target: String,
+}
+
+impl IngestSource {
+    pub fn parse(spec: &str) -> Result<Self, JobsError> {
+        let invalid = |message: &str| JobsError::ParseError {
+            what: "ingest source",
+            input: spec.to_string(),
+            message: message.to_string(),
+        };
+        let (kind, target) = spec.trim().split_once(':').ok_or_else(|| invalid("expected rss:<url>, greenhouse:<board> or lever:<company>"))?;
+        let kind = match kind.to_lowercase().as_str() {
+            "rss" | "atom" => SourceKind::Rss,
+            "greenhouse" => SourceKind::Greenhouse,
+            "lever" => SourceKind::Lever,
+            _ => return Err(invalid("unknown source type; use rss, greenhouse or lever")),
+        };
+        let target = target.trim();
+        let valid = match kind {
+            SourceKind::Rss => target.starts_with("http://") || target.starts_with("https://"),
+            _ => !target.is_empty() && target.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+        };
+        if !valid {
+            return Err(invalid(match kind {
+                SourceKind::Rss => "expected an http(s) feed URL",
+                _ => "expected a board or company name",
+            }));
+        }
+        Ok(Self { kind, target: target.to_string() })
+    }
+
+    /// Where the listings are fetched from
+    pub fn url(&self) -> String {
+        match self.kind {
+            SourceKind::Rss => self.target.clone(),
+            SourceKind::Greenhouse => format!("https://boards-api.greenhouse.io/v1/boards/{}/jobs?content=true", self.target),
+            SourceKind::Lever => format!("https://api.lever.co/v0/postings/{}?mode=json", self.target),
+        }
+    }
+}
+
+impl std::fmt::Display for IngestSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.kind.as_str(), self.target)
+    }
+}
+
+/// A posting from an external board, normalized to the listing tags
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExternalListing {
+    /// Unique within the source, e.g. the Greenhouse job id or RSS guid
+    pub external_id: String,
+    pub title: String,
+    pub company: Option<String>,
+    pub location: Option<String>,
+    pub employment_types: Vec<String>,
+    pub skills: Vec<String>,
+    pub salary: Option<SalaryRange>,
+    pub description: String,
+    /// The posting on the source site
+    pub url: Option<String>,
+    pub posted_at: Option<Timestamp>,
+    /// When the source stops accepting applications
+    pub closes_at: Option<Timestamp>,
+}
+
+impl ExternalListing {
+    /// A listing event of `kind` signed by `keys`. The job id is prefixed
+    /// with the source so it can't collide with Nostr-native listings, and
+    /// the event id stays the same as long as the posting doesn't change.
+    pub fn to_event(&self, source: &IngestSource, kind: Kind, keys: &Keys) -> Result<Event, JobsError> {
+        let job_id = format!("{}:{}", source, self.external_id);
+        let mut tags: Vec<Vec<String>> = vec![
+            vec!["job-id".into(), job_id.clone()],
+            vec!["j".into(), job_id],
+            vec!["title".into(), self.title.clone()],
+        ];
+        let optional = [("company", &self.company), ("location", &self.location)];
+        tags.extend(optional.into_iter().filter_map(|(name, value)| Some(vec![name.to_string(), value.clone()?])));
+        tags.extend(self.employment_types.iter().map(|t| vec!["employment-type".into(), t.clone()]));
+        tags.extend(self.skills.iter().map(|s| vec!["skill".into(), s.clone()]));
+        if let Some(salary) = &self.salary {
+            tags.push(vec![
+                "salary".into(),
+                salary.min.to_string(),
+                salary.max.to_string(),
+                salary.currency.clone(),
+                salary.period.clone(),
+            ]);
+        }
+        if let Some(closes_at) = self.closes_at {
+            tags.push(vec!["expiration".into(), closes_at.as_secs().to_string()]);
+        }
+        let mut source_tag = vec![SOURCE_TAG.to_string(), source.kind.as_str().to_string()];
+        source_tag.extend(self.url.clone());
+        tags.push(source_tag);
+
+        let tags = tags.into_iter().map(Tag::parse).collect::<Result<Vec<_>, _>>().map_err(|e| JobsError::ParseError {
+            what: "ingested listing",
+            input: self.external_id.clone(),
+            message: e.to_string(),
+        })?;
+        EventBuilder::new(kind, &self.description)
+            .tags(tags)
+            .custom_created_at(self.posted_at.unwrap_or_else(Timestamp::now))
+            .sign_with_keys(keys)
+            .map_err(|e| JobsError::Config(format!("Could not sign an ingested listing: {}", e)))
+    }
+}
+
+/// Where an ingested listing came from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExternalSource {
+    pub kind: String,
+    /// The posting on the source site, if it has one
+    pub url: Option<String>,
+}
+
+/// Outcome of the last refresh of one source
+#[derive(Clone, Debug, PartialEq)]
+pub struct SourceStatus {
+    pub source: String,
+    pub listings: usize,
+    /// Last successful refresh
+    pub refreshed_at: Option<Timestamp>,
+    /// Error from the last attempt, if it failed (earlier listings are kept)
+    pub error: Option<String>,
+}
+
+/// What the last bridge run did on the relays
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BridgeReport {
+    /// Postings published for the first time
+    pub published: usize,
+    /// Postings that changed at the source, republished with the old event deleted
+    pub updated: usize,
+    /// Bridged listings deleted because the posting is gone from its source
+    pub withdrawn: usize,
+    /// Postings already on the relays
+    pub unchanged: usize,
+    /// Events the relays refused
+    pub failed: usize,
+    pub ran_at: Option<Timestamp>,
+}
+
+/// One source's listings as signed events
+#[derive(Clone, Debug)]
+struct Ingested {
+    events: Vec<Event>,
+    status: SourceStatus,
+}
+
+/// Ingested listings per source
+#[derive(Clone, Debug)]
+pub struct IngestStore {
+    keys: Keys,
+    sources: Arc<RwLock<HashMap<String, Ingested>>>,
+    last_bridge: Arc<RwLock<Option<BridgeReport>>>,
+}
+
+impl IngestStore {
+    pub fn new(keys: Keys) -> Self {
+        Self {
+            keys,
+            sources: Arc::new(RwLock::new(HashMap::new())),
+            last_bridge: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// The key ingested listings are signed with
+    pub fn public_key(&self) -> PublicKey {
+        self.keys.public_key()
+    }
+
+    /// The source of `listing` if it was ingested here
+    pub fn source_of(&self, listing: &JobListing) -> Option<ExternalSource> {
+        if listing.author != self.public_key() {
+            return None;
+        }
+        let tag = listing.event.tags.iter().find(|t| t.kind() == TagKind::custom(SOURCE_TAG))?.as_slice();
+        Some(ExternalSource { kind: tag.get(1)?.clone(), url: tag.get(2).cloned() })
+    }
+
+    /// Ingested events matching `filter`, newest first, at most its limit
+    pub async fn matching(&self, filter: &Filter) -> Vec<Event> {
+        let sources = self.sources.read().await;
+        let mut events: Vec<Event> = sources
+            .values()
+            .flat_map(|source| &source.events)
+            .filter(|e| filter.match_event(e, MatchEventOptions::new()))
+            .cloned()
+            .collect();
+        events.sort_by_key(|e| std::cmp::Reverse(e.created_at));
+        events.truncate(filter.limit.unwrap_or(usize::MAX));
+        events
+    }
+
+    /// Fetch every source in `sources` and replace its listings, signed as
+    /// events of `kind`. A source that fails keeps its previous listings;
+    /// sources no longer configured are dropped.
+    pub async fn refresh(&self, sources: &[IngestSource], kind: Kind) -> Vec<SourceStatus> {
+        let fetched = futures::future::join_all(sources.iter().map(|source| async move { (source, fetch(source).await) })).await;
+
+        let mut store = self.sources.write().await;
+        store.retain(|name, _| sources.iter().any(|s| s.to_string() == *name));
+        for (source, outcome) in fetched {
+            let name = source.to_string();
+            let entry = store.entry(name.clone()).or_insert_with(|| Ingested {
+                events: Vec::new(),
+                status: SourceStatus { source: name.clone(), listings: 0, refreshed_at: None, error: None },
+            });
+            match outcome {
+                Ok(listings) => {
+                    let events: Vec<Event> = listings
+                        .iter()
+                        .filter_map(|l| {
+                            l.to_event(source, kind, &self.keys)
+                                .inspect_err(|e| tracing::warn!(source = %name, error = %e, "ingest_listing_skipped"))
+                                .ok()
+                        })
+                        .collect();
+                    tracing::info!(source = %name, listings = events.len(), "ingest_refreshed");
+                    entry.status = SourceStatus {
+                        source: name,
+                        listings: events.len(),
+                        refreshed_at: Some(Timestamp::now()),
+                        error: None,
+                    };
+                    entry.events = events;
+                }
+                Err(e) => {
+                    tracing::warn!(source = %name, error = %e, "ingest_refresh_failed");
+                    entry.status.error = Some(e.to_string());
+                }
+            }
+        }
+        drop(store);
+        self.status().await
+    }
+
+    /// Per-source status of the last refresh
+    pub async fn status(&self) -> Vec<SourceStatus> {
+        let mut status: Vec<SourceStatus> = self.sources.read().await.values().map(|s| s.status.clone()).collect();
+        status.sort_by(|a, b| a.source.cmp(&b.source));
+        status
+    }
+
+    /// Every ingested listing event, across sources
+    pub async fn events(&self) -> Vec<Event> {
+        self.sources.read().await.values().flat_map(|s| s.events.clone()).collect()
+    }
+
+    /// A NIP-09 deletion of `ids`, signed with the ingest key
+    pub fn deletion(&self, ids: impl IntoIterator<Item = EventId>) -> Result<Event, JobsError> {
+        EventBuilder::delete(EventDeletionRequest::new().ids(ids).reason("no longer listed at the source"))
+            .sign_with_keys(&self.keys)
+            .map_err(|e| JobsError::Config(format!("Could not sign a bridge deletion: {}", e)))
+    }
+
+    pub(crate) async fn record_bridge(&self, report: BridgeReport) {
+        *self.last_bridge.write().await = Some(report);
+    }
+
+    /// Outcome of the last bridge run, if bridging is on
+    pub async fn last_bridge(&self) -> Option<BridgeReport> {
+        self.last_bridge.read().await.clone()
+    }
+}
+
+/// Download and normalize the current postings of `source`
+pub async fn fetch(source: &IngestSource) -> Result<Vec<ExternalListing>, JobsError> {
+    let failed = |message: String| JobsError::Ingest { source: source.to_string(), message };
+    let body = reqwest::Client::new()
+        .get(source.url())
+        .header(reqwest::header::USER_AGENT, concat!("jobmcp/", env!("CARGO_PKG_VERSION")))
+        .timeout(FETCH_TIMEOUT)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| failed(e.to_string()))?
+        .text()
+        .await
+        .map_err(|e| failed(e.to_string()))?;
+    match source.kind {
+        SourceKind::Rss => parse_feed(&body),
+        SourceKind::Greenhouse => parse_greenhouse(&source.target, &body),
+        SourceKind::Lever => parse_lever(&source.target, &body),
+    }
+    .map_err(failed)
+}
+
+/// Items of an RSS 2.0 feed or entries of an Atom feed. The feed title is
+/// used as the company; item categories become skills.
+pub fn parse_feed(body: &str) -> Result<Vec<ExternalListing>, String> {
+    let document = roxmltree::Document::parse(body).map_err(|e| e.to_string())?;
+    let root = document.root_element();
+    let child_text = |node: roxmltree::Node, name: &str| {
+        node.children()
+            .find(|c| c.tag_name().name() == name)
+            .and_then(|c| c.text())
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+    };
+    let channel = match root.tag_name().name() {
+        "rss" => root.children().find(|c| c.tag_name().name() == "channel").ok_or("RSS feed without a channel")?,
+        "feed" => root,
+        other => return Err(format!("expected an RSS or Atom feed, found <{}>", other)),
+    };
+    let company = child_text(channel, "title");
+
+    let listings = channel
+        .children()
+        .filter(|c| matches!(c.tag_name().name(), "item" | "entry"))
+        .filter_map(|item| {
+            let title = child_text(item, "title")?;
+            let url = child_text(item, "link").or_else(|| {
+                item.children()
+                    .find(|c| c.tag_name().name() == "link")
+                    .and_then(|c| c.attribute("href"))
+                    .map(str::to_string)
+            });
+            let external_id = child_text(item, "guid").or_else(|| child_text(item, "id")).or_else(|| url.clone())?;
+            let description = ["description", "content", "summary"]
+                .iter()
+                .find_map(|name| child_text(item, name))
+                .map(|html| strip_html(&html))
+                .unwrap_or_default();
+            let posted_at = ["pubDate", "published", "updated"].iter().find_map(|name| {
+                let value = child_text(item, name)?;
+                parse_rfc2822(&value).or_else(|| parse_rfc3339(&value))
+            });
+            let skills = item
+                .children()
+                .filter(|c| c.tag_name().name() == "category")
+                .filter_map(|c| c.text().or_else(|| c.attribute("term")))
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect();
+            Some(ExternalListing {
+                external_id,
+                title,
+                company: company.clone(),
+                skills,
+                description,
+                url,
+                posted_at,
+                closes_at: child_text(item, "expires").and_then(|v| parse_rfc2822(&v).or_else(|| parse_rfc3339(&v))),
+                ..Default::default()
+            })
+        })
+        .collect();
+    Ok(listings)
+}
+
+/// The `jobs` of a Greenhouse job board API response (`?content=true`)
+pub fn parse_greenhouse(board: &str, body: &str) -> Result<Vec<ExternalListing>, String> {
+    let body: Value = serde_json::from_str(body).map_err(|e| e.to_string())?;
+    let jobs = body["jobs"].as_array().ok_or("response has no jobs list")?;
+    Ok(jobs
+        .iter()
+        .filter_map(|job| {
+            Some(ExternalListing {
+                external_id: json_id(&job["id"])?,
+                title: job["title"].as_str()?.trim().to_string(),
+                company: Some(job["company_name"].as_str().unwrap_or(board).to_string()),
+                location: job["location"]["name"].as_str().map(str::to_string),
+                description: job["content"].as_str().map(strip_html).unwrap_or_default(),
+                url: job["absolute_url"].as_str().map(str::to_string),
+                posted_at: job["first_published"]
+                    .as_str()
+                    .or(job["updated_at"].as_str())
+                    .and_then(parse_rfc3339),
+                ..Default::default()
+            })
+        })
+        .collect())
+}
+
+/// A Lever postings API response (`?mode=json`)
+pub fn parse_lever(company: &str, body: &str) -> Result<Vec<ExternalListing>, String> {
+    let body: Value = serde_json::from_str(body).map_err(|e| e.to_string())?;
+    let postings = body.as_array().ok_or("expected a list of postings")?;
+    Ok(postings
+        .iter()
+        .filter_map(|posting| {
+            let categories = &posting["categories"];
+            let salary = &posting["salaryRange"];
+            Some(ExternalListing {
+                external_id: json_id(&posting["id"])?,
+                title: posting["text"].as_str()?.trim().to_string(),
+                company: Some(company.to_string()),
+                location: categories["location"].as_str().map(str::to_string),
+                employment_types: categories["commitment"].as_str().map(|c| vec![c.to_string()]).unwrap_or_default(),
+                skills: posting["tags"]
+                    .as_array()
+                    .map(|tags| tags.iter().filter_map(|t| t.as_str().map(str::to_string)).collect())
+                    .unwrap_or_default(),
+                salary: match (salary["min"].as_f64(), salary["max"].as_f64(), salary["currency"].as_str()) {
+                    (Some(min), Some(max), Some(currency)) => Some(SalaryRange {
+                        min,
+                        max,
+                        currency: currency.to_string(),
+                        // e.g. `per-year-salary`, `per-hour-wage`
+                        period: salary["interval"]
+                            .as_str()
+                            .and_then(|i| i.trim_start_matches("per-").split('-').next())
+                            .unwrap_or("year")
+                            .to_string(),
+                    }),
+                    _ => None,
+                },
+                description: posting["descriptionPlain"]
+                    .as_str()
+                    .map(|d| d.trim().to_string())
+                    .or_else(|| posting["description"].as_str().map(strip_html))
+                    .unwrap_or_default(),
+                url: posting["hostedUrl"].as_str().map(str::to_string),
+                posted_at: posting["createdAt"].as_u64().map(|ms| Timestamp::from(ms / 1000)),
+                ..Default::default()
+            })
+        })
+        .collect())
+}
+
+/// A string or numeric id as text
+fn json_id(value: &Value) -> Option<String> {
+    match value {
+        Value::String(id) => Some(id.clone()),
+        Value::Number(id) => Some(id.to_string()),
+        _ => None,
+    }
+}
+
+/// Plain text of an HTML fragment, which may itself be entity-escaped (as
+/// Greenhouse content is)
+fn strip_html(html: &str) -> String {
+    let html = unescape_html(html);
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = unescape_html(&text);
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn unescape_html(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// `Mon, 02 Jan 2006 15:04:05 -0700` (the weekday and seconds are optional)
+fn parse_rfc2822(value: &str) -> Option<Timestamp> {
+    let value = value.split_once(',').map_or(value, |(_, rest)| rest);
+    let parts: Vec<&str> = value.split_whitespace().collect();
+    let [day, month, year, time, zone @ ..] = parts.as_slice() else {
+        return None;
+    };
+    const MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+    let month = MONTHS.iter().position(|m| month.to_lowercase().starts_with(m))? as i64 + 1;
+    let offset = match zone.first().copied().unwrap_or("GMT") {
+        "GMT" | "UT" | "UTC" | "Z" => 0,
+        // Obsolete names like EST are rare in feeds; read them as UTC
+        zone => parse_offset(zone).unwrap_or(0),
+    };
+    to_timestamp(year.parse().ok()?, month, day.parse().ok()?, time, offset)
+}
+
+/// `2024-01-15T10:00:00-05:00`, `2024-01-15T10:00:00.123Z` or a bare date
+pub(crate) fn parse_rfc3339(value: &str) -> Option<Timestamp> {
+    let (date, time) = value.trim().split_once(['T', ' ']).unwrap_or((value.trim(), "00:00:00Z"));
+    let mut date = date.split('-').map(|p| p.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+    let (time, offset) = match time.find(['Z', 'z', '+', '-']) {
+        Some(at) if time[at..].eq_ignore_ascii_case("z") => (&time[..at], 0),
+        Some(at) => (&time[..at], parse_offset(&time[at..].replace(':', ""))?),
+        None => (time, 0),
+    };
+    let time = time.split('.').next()?;
+    to_timestamp(year, month, day, time, offset)
+}
+
+/// `+0100` or `-0530` as seconds east of UTC
+fn parse_offset(zone: &str) -> Option<i64> {
+    let sign = match zone.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    let digits = zone.get(1..5)?;
+    let hours: i64 = digits.get(..2)?.parse().ok()?;
+    let minutes: i64 = digits.get(2..)?.parse().ok()?;
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Seconds since the unix epoch; `None` for a clock reading out of range or
+/// a date before the epoch. Feeds are untrusted, so nothing may overflow.
+fn to_timestamp(year: i64, month: i64, day: i64, time: &str, offset: i64) -> Option<Timestamp> {
+    let mut clock = time.split(':').map(|p| p.parse::<i64>().ok());
+    let hours = clock.next().flatten()?;
+    let minutes = clock.next().flatten().unwrap_or(0);
+    // 60 for a leap second
+    let seconds = clock.next().flatten().unwrap_or(0);
+    if !(0..=23).contains(&hours) || !(0..=59).contains(&minutes) || !(0..=60).contains(&seconds) {
+        return None;
+    }
+    let days = days_from_civil(year, month, day)?;
+    let secs = days
+        .checked_mul(86_400)?
+        .checked_add(hours * 3600 + minutes * 60 + seconds)?
+        .checked_sub(offset)?;
+    u64::try_from(secs).ok().map(Timestamp::from)
+}
+
+/// Days since the unix epoch (Howard Hinnant's days_from_civil); `None`
+/// for an impossible month or day, or a year outside 0-9999
+pub(crate) fn days_from_civil(year: i64, month: i64, day: i64) -> Option<i64> {
+    if !(0..=MAX_YEAR).contains(&year) || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    Some(era * 146_097 + doe - 719_468)
+}
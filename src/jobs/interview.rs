@@ -0,0 +1,148 @@
+// src/jobs/interview.rs
+// Interviews as NIP-52 time-based calendar events, tied to a listing by its job id
+
+use std::collections::HashMap;
+
+use nostr_sdk::prelude::*;
+
+use super::error::JobsError;
+
+/// NIP-52 time-based calendar event (addressable, so a reschedule replaces it)
+pub const CALENDAR_EVENT_KIND: u16 = 31923;
+/// The `t` tag marking a calendar event as an interview
+pub const INTERVIEW_HASHTAG: &str = "interview";
+
+/// An interview to publish with `NostrJobsClient::schedule_interview`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InterviewPlan {
+    /// The `d` tag; publishing the same id again reschedules the interview
+    pub id: String,
+    /// The listing (and so the application) the interview is for
+    pub job_id: String,
+    pub title: String,
+    pub start: Timestamp,
+    pub end: Option<Timestamp>,
+    /// A room, an address or a video call link
+    pub location: Option<String>,
+    /// Candidates and interviewers, `p`-tagged so their clients see it
+    pub participants: Vec<PublicKey>,
+    pub description: String,
+}
+
+impl InterviewPlan {
+    /// What has to change before this interview can be published; empty
+    /// when it is ready
+    pub fn problems(&self, now: Timestamp) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.title.trim().is_empty() {
+            problems.push("title is empty".to_string());
+        }
+        if self.start <= now {
+            problems.push("start is in the past".to_string());
+        }
+        if self.end.is_some_and(|end| end <= self.start) {
+            problems.push("end is not after start".to_string());
+        }
+        if self.participants.is_empty() {
+            problems.push("no participants".to_string());
+        }
+        problems
+    }
+
+    /// The unsigned calendar event
+    pub fn to_event_builder(&self) -> Result<EventBuilder, JobsError> {
+        let mut tags: Vec<Vec<String>> = vec![
+            vec!["d".into(), self.id.clone()],
+            vec!["title".into(), self.title.clone()],
+            vec!["start".into(), self.start.as_secs().to_string()],
+            vec!["j".into(), self.job_id.clone()],
+            vec!["t".into(), INTERVIEW_HASHTAG.into()],
+        ];
+        if let Some(end) = self.end {
+            tags.push(vec!["end".into(), end.as_secs().to_string()]);
+        }
+        if let Some(location) = &self.location {
+            tags.push(vec!["location".into(), location.clone()]);
+        }
+        tags.extend(self.participants.iter().map(|p| vec!["p".into(), p.to_hex(), String::new(), "participant".into()]));
+        let tags = tags.into_iter().map(Tag::parse).collect::<Result<Vec<_>, _>>().map_err(|e| JobsError::ParseError {
+            what: "interview",
+            input: self.id.clone(),
+            message: e.to_string(),
+        })?;
+        Ok(EventBuilder::new(Kind::from(CALENDAR_EVENT_KIND), self.description.clone()).tags(tags))
+    }
+}
+
+/// A published interview, as read back from a calendar event
+#[derive(Clone, Debug, PartialEq)]
+pub struct Interview {
+    pub plan: InterviewPlan,
+    /// Who scheduled it
+    pub author: PublicKey,
+    pub event_id: EventId,
+    pub created_at: Timestamp,
+}
+
+impl Interview {
+    /// `None` for events that aren't interview calendar events, or lack a
+    /// `d`, `start` or `j` tag
+    pub fn from_event(event: &Event) -> Option<Self> {
+        if event.kind != Kind::from(CALENDAR_EVENT_KIND) {
+            return None;
+        }
+        let tag = |name: &str| {
+            event.tags.iter().find(|t| t.kind() == TagKind::custom(name)).and_then(|t| t.content()).map(str::to_string)
+        };
+        let is_interview = event.tags.iter().any(|t| t.kind() == TagKind::custom("t") && t.content() == Some(INTERVIEW_HASHTAG));
+        if !is_interview {
+            return None;
+        }
+        let participants = event
+            .tags
+            .iter()
+            .filter(|t| t.kind() == TagKind::custom("p"))
+            .filter_map(|t| PublicKey::from_hex(t.content()?).ok())
+            .collect();
+        Some(Interview {
+            plan: InterviewPlan {
+                id: tag("d")?,
+                job_id: tag("j")?,
+                title: tag("title").unwrap_or_default(),
+                start: Timestamp::from(tag("start")?.parse::<u64>().ok()?),
+                end: tag("end").and_then(|end| end.parse::<u64>().ok()).map(Timestamp::from),
+                location: tag("location"),
+                participants,
+                description: event.content.clone(),
+            },
+            author: event.pubkey,
+            event_id: event.id,
+            created_at: event.created_at,
+        })
+    }
+
+    /// The NIP-19 `naddr` calendar clients open the interview with
+    pub fn naddr(&self) -> Option<String> {
+        let coordinate = Coordinate::new(Kind::from(CALENDAR_EVENT_KIND), self.author).identifier(&self.plan.id);
+        Nip19Coordinate::new(coordinate, Vec::<RelayUrl>::new()).to_bech32().ok()
+    }
+}
+
+/// Interviews among `events` not over yet at `now`, the newest version of
+/// each (author, id) only, soonest first
+pub fn upcoming(events: &[Event], now: Timestamp) -> Vec<Interview> {
+    let mut newest: HashMap<(PublicKey, String), Interview> = HashMap::new();
+    for interview in events.iter().filter_map(Interview::from_event) {
+        let key = (interview.author, interview.plan.id.clone());
+        match newest.get(&key) {
+            Some(known) if known.created_at >= interview.created_at => {}
+            _ => {
+                newest.insert(key, interview);
+            }
+        }
+    }
+    let mut interviews: Vec<Interview> =
+        newest.into_values().filter(|i| i.plan.end.unwrap_or(i.plan.start) >= now).collect();
+    interviews.sort_by_key(|i| i.plan.start);
+    interviews
+}
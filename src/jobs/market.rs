@@ -0,0 +1,138 @@
+// src/jobs/market.rs
+// Supply and demand per skill: job listings asking for it against people
+// posting that they are for hire with it
+
+use std::collections::{BTreeSet, HashMap};
+
+use nostr_sdk::prelude::*;
+
+use super::model::JobListing;
+
+/// Listings per candidate above which candidates are in demand
+const SELLERS_RATIO: f64 = 1.5;
+/// Listings per candidate below which employers can choose
+const BUYERS_RATIO: f64 = 1.0 / SELLERS_RATIO;
+
+/// Someone offering to work, from their newest "for hire" note
+#[derive(Clone, Debug, PartialEq)]
+pub struct Candidate {
+    pub author: PublicKey,
+    pub event_id: EventId,
+    pub created_at: Timestamp,
+    /// Lowercased: `skill` tags, and known skills named or hashtagged in the
+    /// note
+    pub skills: BTreeSet<String>,
+}
+
+/// One candidate per author among `events` (notes tagged with one of
+/// `topics`), from their newest note. Besides `skill` tags, `known_skills`
+/// (lowercase) are looked for in the text and hashtags, since most notes
+/// name skills without tagging them; other hashtags (#nostr, #bitcoin) aren't
+/// skills.
+pub fn candidates(events: &[Event], topics: &[String], known_skills: &BTreeSet<String>) -> Vec<Candidate> {
+    let mut newest: HashMap<PublicKey, &Event> = HashMap::new();
+    for event in events.iter().filter(|e| e.kind == Kind::TextNote) {
+        if newest.get(&event.pubkey).is_none_or(|known| known.created_at < event.created_at) {
+            newest.insert(event.pubkey, event);
+        }
+    }
+
+    let mut candidates: Vec<Candidate> = newest
+        .into_values()
+        .filter_map(|event| {
+            let tagged = |name: &str| -> BTreeSet<String> {
+                event
+                    .tags
+                    .iter()
+                    .filter(|t| t.kind() == TagKind::custom(name))
+                    .filter_map(|t| t.content())
+                    .map(|v| v.trim().to_lowercase())
+                    .filter(|v| !v.is_empty())
+                    .collect()
+            };
+            let hashtags = tagged("t");
+            if !hashtags.iter().any(|t| topics.contains(t)) {
+                return None;
+            }
+            let content = event.content.to_lowercase();
+            let words: BTreeSet<&str> = content
+                .split(|c: char| !c.is_alphanumeric() && !"+#.-".contains(c))
+                .map(|w| w.trim_start_matches('#').trim_end_matches(['.', '-']))
+                .filter(|w| !w.is_empty())
+                .collect();
+            let mut skills = tagged("skill");
+            skills.extend(known_skills.iter().filter(|s| hashtags.contains(*s) || words.contains(s.as_str())).cloned());
+            Some(Candidate { author: event.pubkey, event_id: event.id, created_at: event.created_at, skills })
+        })
+        .collect();
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.created_at));
+    candidates
+}
+
+/// Who has the upper hand for one skill
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarketSide {
+    /// More listings than candidates: candidates choose
+    Sellers,
+    Balanced,
+    /// More candidates than listings: employers choose
+    Buyers,
+}
+
+impl MarketSide {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarketSide::Sellers => "sellers",
+            MarketSide::Balanced => "balanced",
+            MarketSide::Buyers => "buyers",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SkillBalance {
+    /// Lowercased
+    pub skill: String,
+    /// Listings asking for the skill
+    pub demand: usize,
+    /// Candidates offering it
+    pub supply: usize,
+}
+
+impl SkillBalance {
+    /// Listings per candidate; `None` without candidates
+    pub fn ratio(&self) -> Option<f64> {
+        (self.supply > 0).then(|| self.demand as f64 / self.supply as f64)
+    }
+
+    pub fn side(&self) -> MarketSide {
+        match self.ratio() {
+            None => MarketSide::Sellers,
+            Some(r) if r > SELLERS_RATIO => MarketSide::Sellers,
+            Some(r) if r < BUYERS_RATIO => MarketSide::Buyers,
+            Some(_) => MarketSide::Balanced,
+        }
+    }
+}
+
+/// Demand and supply of every skill asked for by `listings` or offered by
+/// `candidates`, the most active (demand plus supply) first
+pub fn balance(listings: &[JobListing], candidates: &[Candidate]) -> Vec<SkillBalance> {
+    let mut counts: HashMap<String, (usize, usize)> = HashMap::new();
+    for listing in listings {
+        let skills: BTreeSet<String> = listing.skills.iter().map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()).collect();
+        for skill in skills {
+            counts.entry(skill).or_default().0 += 1;
+        }
+    }
+    for skill in candidates.iter().flat_map(|c| &c.skills) {
+        counts.entry(skill.clone()).or_default().1 += 1;
+    }
+
+    let mut balances: Vec<SkillBalance> = counts
+        .into_iter()
+        .map(|(skill, (demand, supply))| SkillBalance { skill, demand, supply })
+        .collect();
+    balances.sort_by(|a, b| (b.demand + b.supply).cmp(&(a.demand + a.supply)).then_with(|| a.skill.cmp(&b.skill)));
+    balances
+}
@@ -0,0 +1,48 @@
+// src/jobs/mod.rs
+// Job board core: relay fetching, caching, parsing and statistics, independent of MCP.
+// `NostrJobsServer` wraps this; other programs can embed `NostrJobsClient` directly.
+
+mod backend;
+pub mod bulk;
+pub mod career;
+mod client;
+pub mod company;
+pub mod crosspost;
+pub mod digest;
+mod draft;
+pub mod dvm;
+pub mod engagement;
+mod error;
+pub mod geo;
+mod health;
+pub mod ingest;
+pub mod interview;
+pub mod market;
+mod model;
+mod publish;
+mod query;
+pub mod rates;
+mod recommend;
+pub mod reputation;
+pub mod scam;
+pub mod snapshot;
+mod stats;
+mod strategy;
+mod supervisor;
+pub mod trends;
+
+pub use backend::{NostrRelays, RelayBackend, RelayConnection};
+pub use client::{
+    Compaction, DEFAULT_STATS_SAMPLE_SIZE, ListingStatus, MAX_STATS_SAMPLE_SIZE, NostrJobsClient, PartialResults, RelayReport, Sample, SearchResults,
+    Source, with_call_timeout,
+};
+pub use draft::JobDraft;
+pub(crate) use draft::{MAX_EXPIRY_DAYS, slug};
+pub use error::JobsError;
+pub use health::RelayProbe;
+pub use model::{JobListing, SalaryRange};
+pub use recommend::{InterestProfile, Recommendation};
+pub use query::{DEFAULT_FUZZY_DISTANCE, DEFAULT_SEARCH_LIMIT, JobQuery, ListingSource, SalaryFloor, Suggestion};
+pub use stats::{GEO_CELL_PRECISION, GeoDistribution, JobStats, PostingHistory, SkillPair, TagUsage, skill_pairs, tag_counts, tag_vocabulary};
+pub use strategy::QueryStrategy;
+pub use supervisor::{Supervisor, TaskReport};
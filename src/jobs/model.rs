@@ -0,0 +1,129 @@
+// src/jobs/model.rs
+// Typed view of a job listing event
+
+use nostr_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A job listing parsed from its Nostr event
+#[derive(Clone, Debug)]
+pub struct JobListing {
+    /// The `job-id` tag, falling back to the event id
+    pub job_id: String,
+    pub title: Option<String>,
+    pub company: Option<String>,
+    pub location: Option<String>,
+    pub employment_types: Vec<String>,
+    pub skills: Vec<String>,
+    pub salary: Option<SalaryRange>,
+    /// Full listing text (the event content)
+    pub description: String,
+    pub author: PublicKey,
+    pub created_at: Timestamp,
+    /// The NIP-40 `expiration` tag
+    pub expires_at: Option<Timestamp>,
+    /// The underlying event, for tags not covered above
+    pub event: Event,
+}
+
+impl JobListing {
+    pub fn from_event(event: Event) -> Self {
+        let first = |name: &str| tag_values(&event, name).next().map(str::to_string);
+        let all = |name: &str| tag_values(&event, name).map(str::to_string).collect();
+
+        Self {
+            job_id: first("job-id").unwrap_or_else(|| event.id.to_hex()),
+            title: first("title"),
+            company: first("company"),
+            location: first("location"),
+            employment_types: all("employment-type"),
+            skills: all("skill"),
+            salary: parse_salary(&event),
+            description: event.content.clone(),
+            author: event.pubkey,
+            created_at: event.created_at,
+            expires_at: first("expiration").and_then(|v| v.parse::<u64>().ok()).map(Timestamp::from),
+            event,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|at| at <= Timestamp::now())
+    }
+
+    /// NIP-19 `nevent` for the listing, with up to two of `relays` as hints
+    pub fn nevent(&self, relays: &[String]) -> String {
+        Nip19Event::from(&self.event)
+            .relays(relays.iter().filter_map(|r| RelayUrl::parse(r).ok()).take(2))
+            .to_bech32()
+            .unwrap_or_else(|_| self.event.id.to_hex())
+    }
+
+    /// Link that opens the listing in a browser via njump.me
+    pub fn njump_url(&self, relays: &[String]) -> String {
+        format!("https://njump.me/{}", self.nevent(relays))
+    }
+
+    /// Those of `skills` this listing asks for (case-insensitive substring
+    /// match, as in search)
+    pub fn matching_skills(&self, skills: &[String]) -> Vec<String> {
+        skills
+            .iter()
+            .filter(|skill| {
+                let skill = skill.to_lowercase();
+                self.skills.iter().any(|s| s.to_lowercase().contains(&skill))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Every value of the tag `name`, in tag order
+    pub fn tag_values<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> {
+        tag_values(&self.event, name)
+    }
+}
+
+impl From<Event> for JobListing {
+    fn from(event: Event) -> Self {
+        Self::from_event(event)
+    }
+}
+
+fn tag_values<'a>(event: &'a Event, name: &'a str) -> impl Iterator<Item = &'a str> {
+    event.tags.iter().filter_map(move |t| {
+        let slice = t.as_slice();
+        (slice.len() >= 2 && slice[0] == name).then(|| slice[1].as_str())
+    })
+}
+
+/// A parsed `["salary", min, max, currency, period]` tag
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SalaryRange {
+    pub min: f64,
+    pub max: f64,
+    pub currency: String,
+    pub period: String,
+}
+
+impl SalaryRange {
+    pub fn midpoint(&self) -> f64 {
+        (self.min + self.max) / 2.0
+    }
+}
+
+fn parse_salary(event: &Event) -> Option<SalaryRange> {
+    event.tags.iter().find_map(|t| {
+        let slice = t.as_slice();
+        if slice.len() < 5 || slice[0] != "salary" {
+            return None;
+        }
+        let parse = |v: &str| v.replace([',', '_', ' '], "").parse::<f64>().ok();
+        let min = parse(&slice[1])?;
+        let max = parse(&slice[2]).unwrap_or(min);
+        Some(SalaryRange {
+            min,
+            max: max.max(min),
+            currency: slice[3].to_uppercase(),
+            period: slice[4].to_lowercase(),
+        })
+    })
+}
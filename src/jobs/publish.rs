@@ -0,0 +1,134 @@
+// src/jobs/publish.rs
+// Outbound publishing per relay: spacing events out, honoring the limits each
+// relay advertises in its NIP-11 document and backing off when it rate-limits us
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use nostr_sdk::prelude::*;
+use tokio::sync::Mutex;
+
+/// How long a relay's NIP-11 limits are trusted before being fetched again
+const LIMITS_TTL: Duration = Duration::from_secs(3600);
+/// Wait before the first retry of a publish; doubles with every retry
+const MIN_RETRY_BACKOFF: Duration = Duration::from_millis(250);
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(60);
+/// NIP-01 `OK` prefixes of rejections a retry won't change
+const PERMANENT_REJECTIONS: &[&str] = &["blocked:", "invalid:", "pow:", "restricted:", "auth-required:", "mute:"];
+
+/// What became of an event sent to one relay
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Delivery {
+    Accepted,
+    /// Not sent: it breaks the relay's advertised limits
+    Skipped(String),
+    /// Rejected, or still failing after the retries
+    Failed(String),
+}
+
+#[derive(Debug, Default)]
+struct RelayTurns {
+    /// When the next event may go to this relay
+    next_send: Option<Instant>,
+    /// NIP-11 limits and when they were fetched; `None` inside when the
+    /// relay advertises none
+    limits: Option<(Instant, Option<Limitation>)>,
+}
+
+/// Publish turns and cached NIP-11 limits of each relay
+#[derive(Debug, Default)]
+pub(crate) struct PublishQueue {
+    relays: Mutex<HashMap<String, RelayTurns>>,
+}
+
+impl PublishQueue {
+    /// Wait for the next turn to send to `url`, `interval` after the turn
+    /// before it; callers are served in the order they asked
+    pub(crate) async fn wait_turn(&self, url: &str, interval: Duration) {
+        let at = {
+            let mut relays = self.relays.lock().await;
+            let turns = relays.entry(url.to_string()).or_default();
+            let now = Instant::now();
+            let at = turns.next_send.map_or(now, |next| next.max(now));
+            turns.next_send = Some(at + interval);
+            at
+        };
+        tokio::time::sleep_until(at.into()).await;
+    }
+
+    /// Hold back turns on `url` for `delay`, after it failed or rate-limited us
+    pub(crate) async fn back_off(&self, url: &str, delay: Duration) {
+        let mut relays = self.relays.lock().await;
+        let turns = relays.entry(url.to_string()).or_default();
+        let until = Instant::now() + delay;
+        turns.next_send = Some(turns.next_send.map_or(until, |next| next.max(until)));
+    }
+
+    /// `url`'s limits if fetched within `LIMITS_TTL`; `None` when they need fetching
+    pub(crate) async fn cached_limits(&self, url: &str) -> Option<Option<Limitation>> {
+        let relays = self.relays.lock().await;
+        let (fetched_at, limits) = relays.get(url)?.limits.as_ref()?;
+        (fetched_at.elapsed() < LIMITS_TTL).then(|| limits.clone())
+    }
+
+    pub(crate) async fn store_limits(&self, url: &str, limits: Option<Limitation>) {
+        self.relays.lock().await.entry(url.to_string()).or_default().limits = Some((Instant::now(), limits));
+    }
+}
+
+/// Why a relay advertising `limits` would refuse `event`, if it would
+pub(crate) fn limit_violation(event: &Event, limits: &Limitation) -> Option<String> {
+    let exceeds = |limit: Option<i32>, value: usize| limit.is_some_and(|limit| limit >= 0 && value > limit as usize);
+    if limits.payment_required == Some(true) {
+        return Some("relay requires payment".to_string());
+    }
+    let content = event.content.chars().count();
+    if exceeds(limits.max_content_length, content) {
+        return Some(format!("content is {} characters, relay allows {}", content, limits.max_content_length.unwrap_or_default()));
+    }
+    if exceeds(limits.max_event_tags, event.tags.len()) {
+        return Some(format!("{} tags, relay allows {}", event.tags.len(), limits.max_event_tags.unwrap_or_default()));
+    }
+    // The `["EVENT", ...]` message wrapping the event
+    let message = event.as_json().len() + 11;
+    if exceeds(limits.max_message_length, message) {
+        return Some(format!("message is {} bytes, relay allows {}", message, limits.max_message_length.unwrap_or_default()));
+    }
+    if let Some(difficulty) = limits.min_pow_difficulty.filter(|d| *d > 0)
+        && !event.id.check_pow(difficulty as u8)
+    {
+        return Some(format!("relay requires proof of work of difficulty {}", difficulty));
+    }
+    // NIP-11 gives these as seconds before and after now
+    let now = Timestamp::now().as_secs();
+    if let Some(lower) = limits.created_at_lower_limit
+        && event.created_at.as_secs() < now.saturating_sub(lower.as_secs())
+    {
+        return Some(format!("created more than {}s ago", lower.as_secs()));
+    }
+    if let Some(upper) = limits.created_at_upper_limit
+        && event.created_at.as_secs() > now + upper.as_secs()
+    {
+        return Some(format!("created more than {}s ahead", upper.as_secs()));
+    }
+    None
+}
+
+/// Whether the relay already has the event, which counts as accepted
+pub(crate) fn is_duplicate(message: &str) -> bool {
+    message.contains("duplicate:")
+}
+
+/// Whether a failed publish may succeed when retried: rate limiting and
+/// connection trouble may, a relay refusing the event won't
+pub(crate) fn is_retryable(message: &str) -> bool {
+    !PERMANENT_REJECTIONS.iter().any(|prefix| message.contains(prefix))
+}
+
+/// Wait before retry `attempt` (from 1), doubling from `interval`
+pub(crate) fn retry_backoff(interval: Duration, attempt: u32) -> Duration {
+    interval
+        .max(MIN_RETRY_BACKOFF)
+        .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+        .min(MAX_RETRY_BACKOFF)
+}
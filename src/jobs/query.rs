@@ -0,0 +1,297 @@
+// src/jobs/query.rs
+// Search criteria and client-side matching of listings
+
+use nostr_sdk::PublicKey;
+
+use super::geo::GeoRadius;
+use super::model::JobListing;
+use super::rates::ExchangeRate;
+
+pub const DEFAULT_FUZZY_DISTANCE: usize = 2;
+pub const DEFAULT_SEARCH_LIMIT: usize = 20;
+
+/// Criteria for `NostrJobsClient::search`. Each list holds alternatives
+/// (any-of) and the lists combine with AND. Values match as case-insensitive
+/// substrings of the listing's tags.
+#[derive(Clone, Debug)]
+pub struct JobQuery {
+    pub company: Vec<String>,
+    pub skill: Vec<String>,
+    pub employment_type: Vec<String>,
+    /// Any-of matches on the `location` tag
+    pub location: Vec<String>,
+    /// Listings at these companies never match
+    pub exclude_company: Vec<String>,
+    pub limit: usize,
+    /// Also match values within this many edits (typo tolerance)
+    pub fuzzy_distance: Option<usize>,
+    /// Maximum edit distance for "did you mean" suggestions
+    pub suggestion_distance: usize,
+    /// Only listings by this author, filtered relay-side
+    pub author: Option<PublicKey>,
+    /// Only listings whose `g` (geohash) tag falls within this radius
+    pub near: Option<GeoRadius>,
+    /// Only listings whose salary range reaches this amount
+    pub min_salary: Option<SalaryFloor>,
+    /// Matches posted by these authors come first (e.g. followed employers)
+    pub preferred_authors: Vec<PublicKey>,
+    /// Only listings of these kinds or from the ingestion bridge; all the
+    /// board serves when empty
+    pub sources: Vec<ListingSource>,
+    /// The ingestion bridge's key, telling ingested listings apart from
+    /// native ones of the same kind. Filled in by `NostrJobsClient::search`.
+    pub bridge: Option<PublicKey>,
+}
+
+impl Default for JobQuery {
+    fn default() -> Self {
+        Self {
+            company: Vec::new(),
+            skill: Vec::new(),
+            employment_type: Vec::new(),
+            location: Vec::new(),
+            exclude_company: Vec::new(),
+            limit: DEFAULT_SEARCH_LIMIT,
+            fuzzy_distance: None,
+            suggestion_distance: DEFAULT_FUZZY_DISTANCE,
+            author: None,
+            near: None,
+            min_salary: None,
+            preferred_authors: Vec::new(),
+            sources: Vec::new(),
+            bridge: None,
+        }
+    }
+}
+
+/// Where a listing comes from: a Nostr event of some kind (9993 job
+/// listings, 30402 NIP-99 classifieds, ...), or the ingestion bridge
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ListingSource {
+    Kind(u16),
+    Ingested,
+}
+
+impl ListingSource {
+    /// `listing`'s source, counting listings signed by `bridge` as ingested
+    pub fn of(listing: &JobListing, bridge: Option<&PublicKey>) -> Self {
+        match bridge {
+            Some(bridge) if listing.author == *bridge => ListingSource::Ingested,
+            _ => ListingSource::Kind(listing.event.kind.as_u16()),
+        }
+    }
+}
+
+impl std::str::FromStr for ListingSource {
+    type Err = String;
+
+    /// A kind number, or `ingested` (also `bridge`)
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "ingested" | "bridge" => Ok(ListingSource::Ingested),
+            kind => kind
+                .parse()
+                .map(ListingSource::Kind)
+                .map_err(|_| format!("expected an event kind (e.g. 9993, 30402) or 'ingested', got '{}'", value)),
+        }
+    }
+}
+
+impl std::fmt::Display for ListingSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ListingSource::Kind(kind) => write!(f, "kind {}", kind),
+            ListingSource::Ingested => f.write_str("ingested"),
+        }
+    }
+}
+
+/// Lowest acceptable salary, in `currency`. Listings quoted in sats or BTC
+/// are converted at `rate` (when its fiat is `currency`); listings in any
+/// other currency, or without a salary, don't qualify.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SalaryFloor {
+    pub amount: f64,
+    pub currency: String,
+    pub rate: Option<ExchangeRate>,
+}
+
+impl SalaryFloor {
+    pub fn admits(&self, listing: &JobListing) -> bool {
+        let Some(salary) = &listing.salary else {
+            return false;
+        };
+        let salary = self.rate.as_ref().and_then(|r| r.convert(salary)).unwrap_or_else(|| salary.clone());
+        salary.currency.eq_ignore_ascii_case(&self.currency) && salary.max >= self.amount
+    }
+}
+
+/// A near miss for a value that matched nothing
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suggestion {
+    /// Tag name, e.g. `skill`
+    pub field: &'static str,
+    pub value: String,
+    pub suggestion: String,
+}
+
+impl JobQuery {
+    /// `(tag name, any-of values)` pairs
+    pub fn criteria(&self) -> [(&'static str, &[String]); 4] {
+        [
+            ("company", self.company.as_slice()),
+            ("skill", self.skill.as_slice()),
+            ("employment-type", self.employment_type.as_slice()),
+            ("location", self.location.as_slice()),
+        ]
+    }
+
+    /// Order-insensitive, case-insensitive key for the cached relay results
+    pub fn cache_key(&self) -> String {
+        let key = format!(
+            "{}:{}:{}:{}",
+            cache_key_part(&self.company),
+            cache_key_part(&self.skill),
+            cache_key_part(&self.employment_type),
+            self.limit
+        );
+        let key = match self.location.is_empty() {
+            true => key,
+            false => format!("{}:in:{}", key, cache_key_part(&self.location)),
+        };
+        match &self.author {
+            Some(author) => format!("{}:by:{}", key, author.to_hex()),
+            None => key,
+        }
+    }
+
+    pub fn matches(&self, listing: &JobListing) -> bool {
+        self.author.is_none_or(|author| listing.author == author)
+            && (self.near.is_none() || self.distance_km(listing).is_some())
+            && self.min_salary.as_ref().is_none_or(|floor| floor.admits(listing))
+            && (self.sources.is_empty() || self.sources.contains(&ListingSource::of(listing, self.bridge.as_ref())))
+            && !self.exclude_company.iter().any(|company| tag_matches(listing, "company", company, None))
+            && self.criteria().iter().all(|(name, needles)| {
+            needles.is_empty()
+                || needles
+                    .iter()
+                    .any(|needle| tag_matches(listing, name, needle, self.fuzzy_distance))
+        })
+    }
+
+    /// Distance from the `near` center to the listing, when both are known
+    /// and the listing is within the radius
+    pub fn distance_km(&self, listing: &JobListing) -> Option<f64> {
+        self.near.as_ref()?.distance_km(listing.tag_values("g"))
+    }
+
+    pub fn is_preferred(&self, listing: &JobListing) -> bool {
+        self.preferred_authors.contains(&listing.author)
+    }
+
+    /// The closest existing tag value for each criterion value, among
+    /// those within `suggestion_distance` edits
+    pub fn suggestions(&self, listings: &[JobListing]) -> Vec<Suggestion> {
+        self.criteria()
+            .into_iter()
+            .flat_map(|(name, needles)| needles.iter().map(move |needle| (name, needle)))
+            .filter_map(|(name, needle)| {
+                suggest_tag_value(listings, name, needle, self.suggestion_distance).map(|suggestion| Suggestion {
+                    field: name,
+                    value: needle.clone(),
+                    suggestion,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Order-insensitive, case-insensitive key segment for an any-of filter
+fn cache_key_part(values: &[String]) -> String {
+    if values.is_empty() {
+        return "*".to_string();
+    }
+    let mut parts: Vec<String> = values.iter().map(|v| v.to_lowercase()).collect();
+    parts.sort();
+    parts.dedup();
+    parts.join("|")
+}
+
+/// Case-insensitive substring match, optionally tolerating typos within
+/// `fuzzy_distance` edits of the whole value or any of its words. Short
+/// needles get fewer edits (see `fuzzy_budget`), and candidates too short to
+/// come within the budget are skipped.
+fn tag_matches(listing: &JobListing, name: &str, needle: &str, fuzzy_distance: Option<usize>) -> bool {
+    let needle = needle.to_lowercase();
+    let needle_len = needle.chars().count();
+    let budget = fuzzy_distance.map(|max| fuzzy_budget(needle_len, max)).filter(|&budget| budget > 0);
+    listing.tag_values(name).any(|value| {
+        let value = value.to_lowercase();
+        if value.contains(&needle) {
+            return true;
+        }
+        match budget {
+            Some(max) => std::iter::once(value.as_str())
+                .chain(value.split_whitespace())
+                .filter(|candidate| candidate.chars().count() + max >= needle_len)
+                .any(|candidate| edit_distance(candidate, &needle) <= max),
+            None => false,
+        }
+    })
+}
+
+/// Edits a fuzzy match may spend on a needle of `len` characters, capped at
+/// `max`: none up to 3, one up to 6, two beyond. Any two-letter value is
+/// within two edits of "go", so short needles only match exactly.
+fn fuzzy_budget(len: usize, max: usize) -> usize {
+    let scaled = match len {
+        0..=3 => 0,
+        4..=6 => 1,
+        _ => 2,
+    };
+    scaled.min(max)
+}
+
+/// Closest tag value to `needle` within `max_distance` edits, if any.
+fn suggest_tag_value(listings: &[JobListing], name: &str, needle: &str, max_distance: usize) -> Option<String> {
+    let needle = needle.to_lowercase();
+    listings
+        .iter()
+        .flat_map(|listing| listing.tag_values(name))
+        .filter_map(|value| {
+            let lower = value.to_lowercase();
+            let distance = std::iter::once(lower.as_str())
+                .chain(lower.split_whitespace())
+                .map(|candidate| edit_distance(candidate, &needle))
+                .min()?;
+            (distance > 0 && distance <= max_distance).then(|| (distance, value.to_string()))
+        })
+        .min_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)))
+        .map(|(_, value)| value)
+}
+
+/// Edit distance between two strings, counted in chars: insertions,
+/// deletions, substitutions and swaps of adjacent chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut before = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+            // Swapped neighbours ("pyhton") are one typo, not two
+            if i > 0 && j > 0 && *ca == b[j - 1] && a[i - 1] == *cb {
+                curr[j + 1] = curr[j + 1].min(before[j - 1] + 1);
+            }
+        }
+        std::mem::swap(&mut before, &mut prev);
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
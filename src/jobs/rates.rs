@@ -0,0 +1,131 @@
+// src/jobs/rates.rs
+// Bitcoin exchange rates, so salaries quoted in sats or BTC can be shown in fiat
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use nostr_sdk::Timestamp;
+use serde_json::Value;
+use tokio::sync::RwLock;
+
+use super::error::JobsError;
+use super::model::SalaryRange;
+
+const RATE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How many BTC one unit of a crypto currency is; `None` for fiat
+pub fn btc_per_unit(currency: &str) -> Option<f64> {
+    match currency.to_uppercase().as_str() {
+        "BTC" | "XBT" => Some(1.0),
+        "SAT" | "SATS" | "SATOSHI" | "SATOSHIS" => Some(1e-8),
+        "MSAT" | "MSATS" => Some(1e-11),
+        _ => None,
+    }
+}
+
+/// The price of one bitcoin in `fiat`, as of `as_of`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExchangeRate {
+    pub fiat: String,
+    pub per_btc: f64,
+    pub as_of: Timestamp,
+}
+
+impl ExchangeRate {
+    /// `salary` in this rate's fiat; `None` unless it is quoted in sats or BTC
+    pub fn convert(&self, salary: &SalaryRange) -> Option<SalaryRange> {
+        let factor = btc_per_unit(&salary.currency)? * self.per_btc;
+        Some(SalaryRange {
+            min: (salary.min * factor).round(),
+            max: (salary.max * factor).round(),
+            currency: self.fiat.clone(),
+            period: salary.period.clone(),
+        })
+    }
+}
+
+/// Source of BTC prices. `CoinGeckoRates` asks an HTTP API; tests and
+/// offline deployments can supply fixed rates instead.
+pub trait RateProvider: std::fmt::Debug + Send + Sync {
+    /// Price of one bitcoin in `fiat` (an uppercase ISO code)
+    fn btc_price<'a>(&'a self, fiat: &'a str) -> BoxFuture<'a, Result<f64, JobsError>>;
+}
+
+/// `RateProvider` over a CoinGecko-compatible `simple/price` endpoint
+#[derive(Clone, Debug)]
+pub struct CoinGeckoRates {
+    /// `{fiat}` is replaced by the lowercase currency code
+    url: String,
+}
+
+impl CoinGeckoRates {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+}
+
+impl RateProvider for CoinGeckoRates {
+    fn btc_price<'a>(&'a self, fiat: &'a str) -> BoxFuture<'a, Result<f64, JobsError>> {
+        Box::pin(async move {
+            let fiat = fiat.to_lowercase();
+            let failed = |message: String| JobsError::ExchangeRate { fiat: fiat.to_uppercase(), message };
+            let body: Value = reqwest::Client::new()
+                .get(self.url.replace("{fiat}", &fiat))
+                .timeout(RATE_TIMEOUT)
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(|e| failed(e.to_string()))?
+                .json()
+                .await
+                .map_err(|e| failed(e.to_string()))?;
+            body["bitcoin"][&fiat]
+                .as_f64()
+                .ok_or_else(|| failed(format!("no bitcoin price in {}", fiat.to_uppercase())))
+        })
+    }
+}
+
+/// Rates per fiat currency, refetched from the provider after `ttl`
+#[derive(Clone, Debug)]
+pub struct ExchangeRates {
+    provider: Arc<dyn RateProvider>,
+    ttl: Duration,
+    cache: Arc<RwLock<HashMap<String, ExchangeRate>>>,
+}
+
+impl ExchangeRates {
+    pub fn new(provider: Arc<dyn RateProvider>, ttl: Duration) -> Self {
+        Self {
+            provider,
+            ttl,
+            cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// The current BTC price in `fiat`. When the provider fails, the last
+    /// known rate is returned however old it is (its `as_of` says so).
+    pub async fn rate(&self, fiat: &str) -> Result<ExchangeRate, JobsError> {
+        let fiat = fiat.trim().to_uppercase();
+        let cached = self.cache.read().await.get(&fiat).cloned();
+        if let Some(rate) = &cached
+            && Timestamp::now().as_secs().saturating_sub(rate.as_of.as_secs()) < self.ttl.as_secs()
+        {
+            return Ok(rate.clone());
+        }
+
+        match self.provider.btc_price(&fiat).await {
+            Ok(per_btc) => {
+                let rate = ExchangeRate { fiat: fiat.clone(), per_btc, as_of: Timestamp::now() };
+                self.cache.write().await.insert(fiat, rate.clone());
+                Ok(rate)
+            }
+            Err(e) => {
+                tracing::warn!(fiat = %fiat, error = %e, "exchange_rate_fetch_failed");
+                cached.ok_or(e)
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+// src/jobs/recommend.rs
+// Interest profiles built from the listings someone viewed or bookmarked, and
+// ranking of listings they haven't seen yet against them
+
+use std::collections::{HashMap, HashSet};
+
+use super::model::JobListing;
+
+/// Skills and companies someone showed interest in, weighted by how often
+/// and how strongly (a bookmark counts more than a view). Keys are lowercase.
+#[derive(Clone, Debug, Default)]
+pub struct InterestProfile {
+    skills: HashMap<String, f64>,
+    companies: HashMap<String, f64>,
+}
+
+/// A listing worth showing, and why
+#[derive(Clone, Debug)]
+pub struct Recommendation {
+    pub listing: JobListing,
+    pub score: f64,
+    /// The listing's skills found in the profile
+    pub skills: Vec<String>,
+    /// The listing is at a company from the profile
+    pub same_company: bool,
+}
+
+impl InterestProfile {
+    /// Count interest in a listing with these skills and company
+    pub fn add(&mut self, skills: &[String], company: Option<&str>, weight: f64) {
+        for skill in skills {
+            *self.skills.entry(skill.trim().to_lowercase()).or_default() += weight;
+        }
+        if let Some(company) = company {
+            *self.companies.entry(company.trim().to_lowercase()).or_default() += weight;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.skills.is_empty() && self.companies.is_empty()
+    }
+
+    /// The `limit` strongest skill interests, strongest first
+    pub fn top_skills(&self, limit: usize) -> Vec<String> {
+        top(&self.skills, limit)
+    }
+
+    /// The `limit` strongest company interests, strongest first
+    pub fn top_companies(&self, limit: usize) -> Vec<String> {
+        top(&self.companies, limit)
+    }
+
+    /// How well `listing` fits: the weights of its skills in the profile
+    /// plus that of its company. `None` when nothing overlaps.
+    pub fn score(&self, listing: &JobListing) -> Option<Recommendation> {
+        let mut score = 0.0;
+        let mut skills = Vec::new();
+        for skill in &listing.skills {
+            if let Some(weight) = self.skills.get(&skill.trim().to_lowercase()) {
+                score += weight;
+                skills.push(skill.clone());
+            }
+        }
+        let company_weight = listing
+            .company
+            .as_ref()
+            .and_then(|company| self.companies.get(&company.trim().to_lowercase()))
+            .copied()
+            .unwrap_or(0.0);
+        score += company_weight;
+
+        (score > 0.0).then(|| Recommendation {
+            listing: listing.clone(),
+            score,
+            skills,
+            same_company: company_weight > 0.0,
+        })
+    }
+
+    /// The best `limit` of `listings` not in `seen` (job ids or event ids)
+    /// and not expired, best first; ties go to the newer listing
+    pub fn recommend(&self, listings: &[JobListing], seen: &HashSet<String>, limit: usize) -> Vec<Recommendation> {
+        let mut recommendations: Vec<Recommendation> = listings
+            .iter()
+            .filter(|l| !l.is_expired() && !seen.contains(&l.job_id) && !seen.contains(&l.event.id.to_hex()))
+            .filter_map(|l| self.score(l))
+            .collect();
+        recommendations.sort_by(|a, b| {
+            b.score.total_cmp(&a.score).then_with(|| b.listing.created_at.cmp(&a.listing.created_at))
+        });
+        recommendations.truncate(limit);
+        recommendations
+    }
+}
+
+fn top(weights: &HashMap<String, f64>, limit: usize) -> Vec<String> {
+    let mut weights: Vec<(&String, &f64)> = weights.iter().collect();
+    weights.sort_by(|a, b| b.1.total_cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    weights.into_iter().take(limit).map(|(name, _)| name.clone()).collect()
+}
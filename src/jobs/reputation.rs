@@ -0,0 +1,170 @@
+// src/jobs/reputation.rs
+// What Nostr says about a company: reactions to and reports of its listings,
+// and notes naming it or its posters, each kept with its event so claims can
+// be checked
+
+use std::collections::{BTreeMap, HashSet};
+
+use nostr_sdk::prelude::*;
+
+/// Hashtags that make a note mentioning a company a review of it
+const REVIEW_HASHTAGS: &[&str] = &["review", "companyreview", "jobreview", "employerreview"];
+/// Characters of a note kept as its excerpt
+const EXCERPT_CHARS: usize = 160;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SourceKind {
+    /// A note about the company tagged or worded as a review
+    Review,
+    /// Any other note naming the company or tagging a poster
+    Mention,
+    /// A NIP-56 report of a poster or listing, with its report type
+    Report(String),
+}
+
+/// One event a reputation summary is built from
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReputationSource {
+    pub kind: SourceKind,
+    pub event_id: EventId,
+    pub author: PublicKey,
+    pub created_at: Timestamp,
+    /// The start of the note, or the report's reason
+    pub excerpt: String,
+}
+
+impl ReputationSource {
+    /// NIP-19 `nevent` of the source, with up to two of `relays` as hints
+    pub fn nevent(&self, relays: &[String]) -> String {
+        Nip19Event::new(self.event_id)
+            .author(self.author)
+            .relays(relays.iter().filter_map(|r| RelayUrl::parse(r).ok()).take(2))
+            .to_bech32()
+            .unwrap_or_else(|_| self.event_id.to_hex())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Reputation {
+    /// NIP-25 reactions to the company's listings other than `-`
+    pub likes: usize,
+    /// `-` and 👎 reactions
+    pub dislikes: usize,
+    pub reviews: usize,
+    pub mentions: usize,
+    /// Reports by NIP-56 type (`spam`, `impersonation`, ...)
+    pub reports: BTreeMap<String, usize>,
+    /// Distinct people behind all of the above
+    pub voices: usize,
+    /// Reviews, reports and mentions, newest first
+    pub sources: Vec<ReputationSource>,
+}
+
+impl Reputation {
+    pub fn is_empty(&self) -> bool {
+        self.likes == 0 && self.dislikes == 0 && self.sources.is_empty()
+    }
+
+    pub fn report_count(&self) -> usize {
+        self.reports.values().sum()
+    }
+}
+
+/// The reputation of the company called `name` that posted `listings`
+/// from `posters`, from `events` (notes, reactions and reports). Events by
+/// the posters themselves, and notes that neither name the company nor
+/// tag a poster, don't count.
+pub fn summarize(events: &[Event], name: &str, posters: &[PublicKey], listings: &[EventId]) -> Reputation {
+    let name = name.trim().to_lowercase();
+    let posters: HashSet<&PublicKey> = posters.iter().collect();
+    let listings: HashSet<&EventId> = listings.iter().collect();
+    let mut seen = HashSet::new();
+    let mut voices = HashSet::new();
+    let mut reputation = Reputation::default();
+
+    for event in events {
+        if posters.contains(&event.pubkey) || !seen.insert(event.id) {
+            continue;
+        }
+        let tagged = |letter: &str| {
+            event.tags.iter().map(|t| t.as_slice()).find(|t| {
+                t.len() > 1
+                    && t[0] == letter
+                    && match letter {
+                        "p" => PublicKey::from_hex(&t[1]).is_ok_and(|p| posters.contains(&p)),
+                        _ => EventId::from_hex(&t[1]).is_ok_and(|id| listings.contains(&id)),
+                    }
+            })
+        };
+        let counted = match event.kind {
+            Kind::Reaction if tagged("e").is_some() => {
+                match event.content.trim() {
+                    "-" | "👎" => reputation.dislikes += 1,
+                    _ => reputation.likes += 1,
+                }
+                true
+            }
+            Kind::Reporting => match tagged("p").or_else(|| tagged("e")) {
+                Some(tag) => {
+                    let report_type = tag.get(2).map(|t| t.trim().to_lowercase()).filter(|t| !t.is_empty()).unwrap_or_else(|| "other".to_string());
+                    *reputation.reports.entry(report_type.clone()).or_default() += 1;
+                    reputation.sources.push(source(event, SourceKind::Report(report_type)));
+                    true
+                }
+                None => false,
+            },
+            Kind::TextNote => {
+                let content = event.content.to_lowercase();
+                let names_company = !name.is_empty() && mentions_word(&content, &name);
+                if names_company || tagged("p").is_some() {
+                    let review = event
+                        .tags
+                        .iter()
+                        .filter(|t| t.kind() == TagKind::custom("t"))
+                        .filter_map(|t| t.content())
+                        .any(|t| REVIEW_HASHTAGS.contains(&t.to_lowercase().as_str()));
+                    let kind = match review || content.contains("review") {
+                        true => {
+                            reputation.reviews += 1;
+                            SourceKind::Review
+                        }
+                        false => {
+                            reputation.mentions += 1;
+                            SourceKind::Mention
+                        }
+                    };
+                    reputation.sources.push(source(event, kind));
+                    true
+                } else {
+                    false
+                }
+            }
+            _ => false,
+        };
+        if counted {
+            voices.insert(event.pubkey);
+        }
+    }
+    reputation.voices = voices.len();
+    reputation.sources.sort_by_key(|s| std::cmp::Reverse(s.created_at));
+    reputation
+}
+
+fn source(event: &Event, kind: SourceKind) -> ReputationSource {
+    let text = event.content.split_whitespace().collect::<Vec<_>>().join(" ");
+    let excerpt = match text.chars().count() > EXCERPT_CHARS {
+        true => format!("{}…", text.chars().take(EXCERPT_CHARS).collect::<String>()),
+        false => text,
+    };
+    ReputationSource { kind, event_id: event.id, author: event.pubkey, created_at: event.created_at, excerpt }
+}
+
+/// Whether `text` contains `word` not as part of a longer word ("acme" in
+/// "acme's" but not in "acmeville")
+fn mentions_word(text: &str, word: &str) -> bool {
+    text.match_indices(word).any(|(at, _)| {
+        let before = text[..at].chars().next_back();
+        let after = text[at + word.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
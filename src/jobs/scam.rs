@@ -0,0 +1,168 @@
+// src/jobs/scam.rs
+// Heuristics for listings that look like scams: asking applicants to pay,
+// contact only through a messenger, pay far above the market, or come from a
+// brand-new key with no profile. Signals, not verdicts.
+
+use std::collections::HashMap;
+
+use nostr_sdk::prelude::*;
+
+use crate::i18n::{self, Locale};
+
+use super::model::JobListing;
+use super::stats::JobStats;
+
+/// Scores at or above this hide a listing from `hide_suspicious` searches
+pub const SUSPICIOUS_SCORE: u8 = 50;
+/// Salary midpoints more than this many times the market median are suspect
+const SALARY_MARKET_FACTOR: f64 = 3.0;
+/// A key first seen within this many days counts as brand new
+const NEW_KEY_DAYS: u64 = 7;
+
+/// Phrases asking applicants for money
+const PAY_TO_APPLY: &[&str] = &[
+    "application fee",
+    "registration fee",
+    "processing fee",
+    "training fee",
+    "starter kit",
+    "pay to apply",
+    "pay a fee",
+    "refundable deposit",
+    "security deposit",
+    "upfront payment",
+    "pay upfront",
+    "send a deposit",
+];
+/// Messenger contacts scammers move applicants to
+const MESSENGERS: &[&str] = &["t.me/", "telegram", "whatsapp", "wa.me/", "signal.me/"];
+
+/// One pattern found in a listing
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScamSignal {
+    /// Stable identifier: `pay_to_apply`, `messenger_only`,
+    /// `salary_above_market` or `new_key_no_profile`
+    pub code: &'static str,
+    /// What the listing matched, filled into its `report.scam.<code>` text
+    pub details: Vec<(&'static str, String)>,
+    /// Contribution to the score
+    pub weight: u8,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ScamAssessment {
+    /// 0 (nothing found) to 100
+    pub score: u8,
+    pub signals: Vec<ScamSignal>,
+}
+
+impl ScamSignal {
+    /// Why the listing matched, for people
+    pub fn message(&self, locale: Locale) -> String {
+        let values: Vec<(&str, &dyn std::fmt::Display)> = self.details.iter().map(|(name, value)| (*name, value as &dyn std::fmt::Display)).collect();
+        i18n::text_with(locale, &format!("report.scam.{}", self.code), &values)
+    }
+}
+
+impl ScamAssessment {
+    pub fn is_suspicious(&self) -> bool {
+        self.score >= SUSPICIOUS_SCORE
+    }
+}
+
+/// What is known about the key a listing was posted from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PosterHistory {
+    /// A kind 0 profile was found
+    pub has_profile: bool,
+    /// The oldest event seen from the key: its profile or any listing
+    pub first_seen: Timestamp,
+}
+
+/// Check `listing` for scam patterns. `market` supplies the median salaries
+/// to compare against and `poster` what is known about its key; without
+/// them those checks are skipped.
+pub fn assess(listing: &JobListing, market: Option<&JobStats>, poster: Option<&PosterHistory>, now: Timestamp) -> ScamAssessment {
+    let mut signals = Vec::new();
+    let text = listing_text(listing);
+
+    if let Some(phrase) = PAY_TO_APPLY.iter().find(|p| text.contains(*p)) {
+        signals.push(ScamSignal {
+            code: "pay_to_apply",
+            details: vec![("phrase", phrase.to_string())],
+            weight: 50,
+        });
+    }
+
+    if let Some(messenger) = MESSENGERS.iter().find(|m| text.contains(*m))
+        && !has_other_contact(&text)
+    {
+        signals.push(ScamSignal {
+            code: "messenger_only",
+            details: vec![("messenger", messenger.trim_end_matches(['/', '.']).to_string())],
+            weight: 30,
+        });
+    }
+
+    if let (Some(salary), Some(market)) = (&listing.salary, market) {
+        let salary = market.exchange_rate.as_ref().and_then(|r| r.convert(salary)).unwrap_or_else(|| salary.clone());
+        let group = format!("{}/{}", salary.currency, salary.period);
+        if let Some(median) = market.median_salaries.get(&group).filter(|m| **m > 0.0) {
+            let factor = salary.midpoint() / median;
+            if factor > SALARY_MARKET_FACTOR {
+                signals.push(ScamSignal {
+                    code: "salary_above_market",
+                    details: vec![("factor", format!("{:.0}", factor)), ("group", group), ("median", format!("{:.0}", median))],
+                    weight: 30,
+                });
+            }
+        }
+    }
+
+    if let Some(poster) = poster.filter(|p| !p.has_profile) {
+        let age_days = now.as_secs().saturating_sub(poster.first_seen.as_secs()) / 86_400;
+        if age_days < NEW_KEY_DAYS {
+            signals.push(ScamSignal {
+                code: "new_key_no_profile",
+                details: vec![("days", age_days.to_string())],
+                weight: 25,
+            });
+        }
+    }
+
+    let score = signals.iter().map(|s| s.weight as u32).sum::<u32>().min(100) as u8;
+    ScamAssessment { score, signals }
+}
+
+/// What each author of `listings` has to show: a profile among `profiles`
+/// (kind 0 events) and the oldest of those and the listings
+pub fn poster_histories(listings: &[JobListing], profiles: &[Event]) -> HashMap<PublicKey, PosterHistory> {
+    let mut histories: HashMap<PublicKey, PosterHistory> = HashMap::new();
+    let seen = listings.iter().map(|l| (l.author, l.created_at, false));
+    let profiles = profiles.iter().filter(|e| e.kind == Kind::Metadata).map(|e| (e.pubkey, e.created_at, true));
+    for (author, at, profile) in seen.chain(profiles) {
+        let history = histories.entry(author).or_insert(PosterHistory { has_profile: false, first_seen: at });
+        history.has_profile |= profile;
+        history.first_seen = history.first_seen.min(at);
+    }
+    histories
+}
+
+/// The listing's content and tag values, lowercased
+fn listing_text(listing: &JobListing) -> String {
+    let tags = listing.event.tags.iter().flat_map(|t| t.as_slice().iter().skip(1));
+    std::iter::once(&listing.description).chain(tags).map(|s| s.to_lowercase()).collect::<Vec<_>>().join("\n")
+}
+
+/// Whether `text` offers an email address or a link that isn't a messenger
+fn has_other_contact(text: &str) -> bool {
+    text.split(|c: char| c.is_whitespace() || "()<>[]\"',;".contains(c)).any(|word| {
+        let word = word.trim_end_matches(['.', '!', '?', ':']);
+        let email = word
+            .split_once('@')
+            .is_some_and(|(user, domain)| !user.is_empty() && domain.contains('.') && !domain.starts_with('.'));
+        let link = (word.starts_with("http://") || word.starts_with("https://") || word.starts_with("www."))
+            && !MESSENGERS.iter().any(|m| word.contains(m));
+        email || link
+    })
+}
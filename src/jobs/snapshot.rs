@@ -0,0 +1,44 @@
+// src/jobs/snapshot.rs
+// Portable copies of the indexed listings: NDJSON, one signed event per line,
+// for migrating between instances, seeding offline ones and test fixtures
+
+use nostr_sdk::prelude::*;
+
+/// What an import did with each line of a snapshot
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImportReport {
+    /// New events added to the index
+    pub imported: usize,
+    /// Events the index already had
+    pub duplicates: usize,
+    /// Valid events that aren't this board's listings (other kinds or authors)
+    pub skipped: usize,
+    /// Lines that aren't signed events, or whose signature doesn't verify,
+    /// with their line number (from 1)
+    pub invalid: Vec<(usize, String)>,
+}
+
+/// `events` as NDJSON, one event per line
+pub fn to_ndjson<'a>(events: impl IntoIterator<Item = &'a Event>) -> String {
+    events.into_iter().map(|e| format!("{}\n", e.as_json())).collect()
+}
+
+/// The events in `ndjson` whose id and signature verify, and the lines that
+/// don't parse or verify. Blank lines are ignored.
+pub fn parse_ndjson(ndjson: &str) -> (Vec<Event>, Vec<(usize, String)>) {
+    let mut events = Vec::new();
+    let mut invalid = Vec::new();
+    for (i, line) in ndjson.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let parsed = Event::from_json(line.trim())
+            .map_err(|e| e.to_string())
+            .and_then(|event| event.verify().map(|()| event).map_err(|e| e.to_string()));
+        match parsed {
+            Ok(event) => events.push(event),
+            Err(e) => invalid.push((i + 1, e)),
+        }
+    }
+    (events, invalid)
+}
@@ -0,0 +1,302 @@
+// src/jobs/stats.rs
+// Aggregate statistics over a sample of listings
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::time::Duration;
+
+use nostr_sdk::Timestamp;
+
+use super::company::{self, CompanyTransparency, SalaryDisclosure};
+use super::model::JobListing;
+use super::rates::ExchangeRate;
+
+#[derive(Clone, Debug, Default)]
+pub struct JobStats {
+    /// Number of listings analyzed
+    pub listings: usize,
+    pub employment_types: HashMap<String, usize>,
+    pub companies: HashMap<String, usize>,
+    pub skills: HashMap<String, usize>,
+    /// Median salary midpoint per "CURRENCY/period" group
+    pub median_salaries: HashMap<String, f64>,
+    /// The rate sats/BTC salaries were converted at, if any were
+    pub exchange_rate: Option<ExchangeRate>,
+    /// Listings with a complete salary tag
+    pub salary_disclosed: usize,
+    /// Per canonical company, most transparent first
+    pub salary_transparency: Vec<CompanyTransparency>,
+    /// Where the listings are, remote work as its own bucket
+    pub geo: GeoDistribution,
+}
+
+impl JobStats {
+    pub fn from_listings(listings: &[JobListing]) -> Self {
+        Self::from_listings_in(listings, None)
+    }
+
+    /// Salaries in sats or BTC are grouped under `rate`'s fiat currency
+    pub fn from_listings_in(listings: &[JobListing], rate: Option<&ExchangeRate>) -> Self {
+        let mut stats = Self {
+            listings: listings.len(),
+            median_salaries: median_salaries(listings, rate),
+            exchange_rate: rate
+                .filter(|r| listings.iter().filter_map(|l| l.salary.as_ref()).any(|s| r.convert(s).is_some()))
+                .cloned(),
+            salary_disclosed: listings.iter().filter(|l| company::salary_disclosure(l) == SalaryDisclosure::Complete).count(),
+            salary_transparency: company::salary_transparency(listings, rate),
+            geo: GeoDistribution::from_listings(listings),
+            ..Default::default()
+        };
+
+        for (name, counts) in [
+            ("employment-type", &mut stats.employment_types),
+            ("company", &mut stats.companies),
+            ("skill", &mut stats.skills),
+        ] {
+            *counts = tag_counts(listings, name);
+        }
+
+        stats
+    }
+}
+
+/// Geohash precision of `GeoDistribution::cells`: cells of about 1250 × 625 km
+pub const GEO_CELL_PRECISION: usize = 2;
+
+/// Where listings are: remote ones, and the places of the others by
+/// location, region and geohash cell
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GeoDistribution {
+    /// Listings with a location mentioning remote work or a truthy `remote` tag
+    pub remote: usize,
+    /// Other listings with neither a location nor a geohash
+    pub unspecified: usize,
+    /// Locations of the other listings, as written
+    pub locations: HashMap<String, usize>,
+    /// The last comma-separated part of those locations, usually the
+    /// country ("Berlin, Germany" is in "Germany")
+    pub regions: HashMap<String, usize>,
+    /// Listings per geohash cell (`g` tags, cut to `GEO_CELL_PRECISION`)
+    pub cells: HashMap<String, usize>,
+}
+
+impl GeoDistribution {
+    pub fn from_listings(listings: &[JobListing]) -> Self {
+        let mut geo = Self::default();
+        for listing in listings {
+            let cell = listing
+                .tag_values("g")
+                .map(|g| g.trim().to_lowercase())
+                .find(|g| g.chars().count() >= GEO_CELL_PRECISION)
+                .map(|g| g.chars().take(GEO_CELL_PRECISION).collect::<String>());
+            if let Some(cell) = &cell {
+                *geo.cells.entry(cell.clone()).or_default() += 1;
+            }
+
+            let location = listing.location.as_deref().map(str::trim).filter(|l| !l.is_empty());
+            let remote = location.is_some_and(|l| l.to_lowercase().contains("remote"))
+                || listing.tag_values("remote").any(|v| matches!(v.trim().to_lowercase().as_str(), "true" | "yes" | "1" | "remote"));
+            match location {
+                _ if remote => geo.remote += 1,
+                Some(location) => {
+                    *geo.locations.entry(location.to_string()).or_default() += 1;
+                    let region = location.rsplit(',').map(str::trim).find(|r| !r.is_empty()).unwrap_or(location);
+                    *geo.regions.entry(region.to_string()).or_default() += 1;
+                }
+                None if cell.is_none() => geo.unspecified += 1,
+                None => {}
+            }
+        }
+        geo
+    }
+}
+
+/// How often each value of the tag `name` occurs across `listings`
+pub fn tag_counts(listings: &[JobListing], name: &str) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for value in listings.iter().flat_map(|l| l.tag_values(name)) {
+        *counts.entry(value.to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// How one tag name is used across listings, for the de facto schema
+#[derive(Clone, Debug, PartialEq)]
+pub struct TagUsage {
+    pub name: String,
+    /// Listings with at least one such tag
+    pub listings: usize,
+    /// Tags with this name in all
+    pub occurrences: usize,
+    /// Most values after the name in one tag (e.g. 4 for `salary`)
+    pub max_values: usize,
+    /// The most common values, each tag's values joined with spaces
+    pub examples: Vec<String>,
+}
+
+/// Every tag name used across `listings`, the most widely used first, with
+/// up to `examples` of its most common values
+pub fn tag_vocabulary(listings: &[JobListing], examples: usize) -> Vec<TagUsage> {
+    let mut usages: HashMap<String, (TagUsage, HashMap<String, usize>)> = HashMap::new();
+    for listing in listings {
+        let mut seen = HashSet::new();
+        for tag in listing.event.tags.iter() {
+            let Some((name, values)) = tag.as_slice().split_first() else {
+                continue;
+            };
+            let (usage, values_seen) = usages.entry(name.clone()).or_insert_with(|| {
+                let usage = TagUsage { name: name.clone(), listings: 0, occurrences: 0, max_values: 0, examples: Vec::new() };
+                (usage, HashMap::new())
+            });
+            usage.occurrences += 1;
+            usage.max_values = usage.max_values.max(values.len());
+            if seen.insert(name.clone()) {
+                usage.listings += 1;
+            }
+            *values_seen.entry(values.join(" ")).or_insert(0) += 1;
+        }
+    }
+    let mut vocabulary: Vec<TagUsage> = usages
+        .into_values()
+        .map(|(mut usage, values)| {
+            let mut values: Vec<(String, usize)> = values.into_iter().filter(|(v, _)| !v.is_empty()).collect();
+            values.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            usage.examples = values.into_iter().take(examples).map(|(v, _)| v).collect();
+            usage
+        })
+        .collect();
+    vocabulary.sort_by(|a, b| b.listings.cmp(&a.listings).then_with(|| a.name.cmp(&b.name)));
+    vocabulary
+}
+
+/// Two skills asked for by the same listings
+#[derive(Clone, Debug, PartialEq)]
+pub struct SkillPair {
+    pub skill: String,
+    pub with: String,
+    /// Listings asking for both
+    pub listings: usize,
+    /// Of the listings asking for `skill`, the share also asking for `with`, 0 to 1
+    pub share: f64,
+}
+
+/// Listings per skill (lowercased), and the pairs of skills asked for
+/// together, most common first. With `skill`, only the pairs including it,
+/// as `skill` → other; otherwise every pair once, led by its more common
+/// skill.
+pub fn skill_pairs(listings: &[JobListing], skill: Option<&str>) -> (HashMap<String, usize>, Vec<SkillPair>) {
+    let mut singles: HashMap<String, usize> = HashMap::new();
+    let mut together: HashMap<(String, String), usize> = HashMap::new();
+    for listing in listings {
+        let skills: BTreeSet<String> = listing.skills.iter().map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()).collect();
+        let skills: Vec<String> = skills.into_iter().collect();
+        for (i, a) in skills.iter().enumerate() {
+            *singles.entry(a.clone()).or_default() += 1;
+            for b in &skills[i + 1..] {
+                *together.entry((a.clone(), b.clone())).or_default() += 1;
+            }
+        }
+    }
+
+    let focus = skill.map(|s| s.trim().to_lowercase());
+    let mut pairs: Vec<SkillPair> = together
+        .into_iter()
+        .filter_map(|((a, b), listings)| {
+            let (skill, with) = match &focus {
+                Some(f) if *f == a => (a, b),
+                Some(f) if *f == b => (b, a),
+                Some(_) => return None,
+                None if singles[&b] > singles[&a] => (b, a),
+                None => (a, b),
+            };
+            let share = listings as f64 / singles[&skill] as f64;
+            Some(SkillPair { skill, with, listings, share })
+        })
+        .collect();
+    pairs.sort_by(|a, b| {
+        b.listings
+            .cmp(&a.listings)
+            .then_with(|| b.share.total_cmp(&a.share))
+            .then_with(|| (&a.skill, &a.with).cmp(&(&b.skill, &b.with)))
+    });
+    (singles, pairs)
+}
+
+/// How often one author posts, to tell steady hiring from reposting
+#[derive(Clone, Debug, Default)]
+pub struct PostingHistory {
+    pub listings: usize,
+    pub active: usize,
+    pub expired: usize,
+    pub first_posted: Option<Timestamp>,
+    pub last_posted: Option<Timestamp>,
+    /// Mean time between consecutive postings
+    pub mean_interval: Option<Duration>,
+    /// Listings whose company and title repeat an earlier listing
+    pub reposts: usize,
+}
+
+impl PostingHistory {
+    pub fn from_listings(listings: &[JobListing]) -> Self {
+        let mut posted: Vec<Timestamp> = listings.iter().map(|l| l.created_at).collect();
+        posted.sort();
+        let expired = listings.iter().filter(|l| l.is_expired()).count();
+
+        let mean_interval = match (posted.first(), posted.last()) {
+            (Some(first), Some(last)) if posted.len() > 1 => Some(Duration::from_secs(
+                (last.as_secs() - first.as_secs()) / (posted.len() as u64 - 1),
+            )),
+            _ => None,
+        };
+
+        // Oldest first, so the original posting isn't counted as the repeat
+        let mut by_age: Vec<&JobListing> = listings.iter().collect();
+        by_age.sort_by_key(|l| l.created_at);
+        let mut seen = HashSet::new();
+        let reposts = by_age
+            .into_iter()
+            .filter(|l| {
+                let key = (
+                    l.company.as_deref().unwrap_or_default().to_lowercase(),
+                    l.title.as_deref().unwrap_or_default().to_lowercase(),
+                );
+                !seen.insert(key)
+            })
+            .count();
+
+        Self {
+            listings: listings.len(),
+            active: listings.len() - expired,
+            expired,
+            first_posted: posted.first().copied(),
+            last_posted: posted.last().copied(),
+            mean_interval,
+            reposts,
+        }
+    }
+}
+
+fn median_salaries(listings: &[JobListing], rate: Option<&ExchangeRate>) -> HashMap<String, f64> {
+    let mut groups: HashMap<String, Vec<f64>> = HashMap::new();
+    for salary in listings.iter().filter_map(|l| l.salary.as_ref()) {
+        let salary = &rate.and_then(|r| r.convert(salary)).unwrap_or_else(|| salary.clone());
+        groups
+            .entry(format!("{}/{}", salary.currency, salary.period))
+            .or_default()
+            .push(salary.midpoint());
+    }
+
+    groups
+        .into_iter()
+        .map(|(group, mut values)| {
+            values.sort_by(|a, b| a.total_cmp(b));
+            let mid = values.len() / 2;
+            let median = if values.len() % 2 == 0 {
+                (values[mid - 1] + values[mid]) / 2.0
+            } else {
+                values[mid]
+            };
+            (group, median)
+        })
+        .collect()
+}
@@ -0,0 +1,63 @@
+// src/jobs/strategy.rs
+// How a fetch spreads over the configured relays
+
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum QueryStrategy {
+    /// Every relay at once, each fetch waiting for a `max_concurrent_fetches`
+    /// slot, results merged
+    #[default]
+    Pooled,
+    /// Every relay at once regardless of the fetch slots, results merged
+    ParallelMerge,
+    /// Every relay at once; the first to answer with listings wins and the
+    /// others are abandoned
+    RaceFirst,
+    /// Relays one at a time in configured order, stopping at the first that answers
+    PrimaryWithFallback,
+}
+
+impl QueryStrategy {
+    pub const ALL: [QueryStrategy; 4] = [
+        QueryStrategy::Pooled,
+        QueryStrategy::ParallelMerge,
+        QueryStrategy::RaceFirst,
+        QueryStrategy::PrimaryWithFallback,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QueryStrategy::Pooled => "pooled",
+            QueryStrategy::ParallelMerge => "parallel-merge",
+            QueryStrategy::RaceFirst => "race-first",
+            QueryStrategy::PrimaryWithFallback => "primary-with-fallback",
+        }
+    }
+
+    /// Whether every relay's answer ends up in the result
+    pub fn merges(&self) -> bool {
+        matches!(self, QueryStrategy::Pooled | QueryStrategy::ParallelMerge)
+    }
+}
+
+impl FromStr for QueryStrategy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim().to_lowercase().replace('_', "-");
+        QueryStrategy::ALL.into_iter().find(|s| s.as_str() == value).ok_or_else(|| {
+            format!(
+                "unknown query strategy '{}'; use {}",
+                value,
+                QueryStrategy::ALL.map(|s| s.as_str()).join(", ")
+            )
+        })
+    }
+}
+
+impl std::fmt::Display for QueryStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,139 @@
+// src/jobs/supervisor.rs
+// Background tasks that are restarted with backoff when they panic, and
+// one-shot tasks whose panics are logged instead of vanishing
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use nostr_sdk::Timestamp;
+
+/// Wait before the first restart; doubles with each panic in a row
+const RESTART_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(300);
+/// A task that ran this long before panicking restarts after `RESTART_BACKOFF` again
+const STABLE_RUN: Duration = Duration::from_secs(300);
+
+/// State of one supervised task (or kind of one-shot task)
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaskReport {
+    pub name: &'static str,
+    /// A loop restarted when it panics, rather than one-shot tasks
+    pub supervised: bool,
+    /// `false` while a loop waits to be restarted, and for one-shot tasks
+    pub running: bool,
+    pub panics: u32,
+    pub last_panic: Option<String>,
+    pub last_panic_at: Option<Timestamp>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Supervisor {
+    tasks: Arc<Mutex<BTreeMap<&'static str, TaskReport>>>,
+    backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl Default for Supervisor {
+    fn default() -> Self {
+        Self::with_backoff(RESTART_BACKOFF, MAX_RESTART_BACKOFF)
+    }
+}
+
+impl Supervisor {
+    pub fn with_backoff(backoff: Duration, max_backoff: Duration) -> Self {
+        Self { tasks: Arc::default(), backoff, max_backoff }
+    }
+
+    /// Run the loop `start` creates, starting it again after a backoff
+    /// whenever it panics. A loop that returns is done and not restarted.
+    pub fn supervise<F, Fut>(&self, name: &'static str, start: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.update(name, |task| {
+            task.supervised = true;
+            task.running = true;
+        });
+        let supervisor = self.clone();
+        tokio::spawn(async move {
+            let mut backoff = supervisor.backoff;
+            loop {
+                let started = Instant::now();
+                let Err(e) = tokio::spawn(start()).await else {
+                    tracing::info!(task = name, "background_task_finished");
+                    supervisor.update(name, |task| task.running = false);
+                    return;
+                };
+                if !e.is_panic() {
+                    // Cancelled: the runtime is shutting down
+                    return;
+                }
+                if started.elapsed() >= STABLE_RUN {
+                    backoff = supervisor.backoff;
+                }
+                let message = panic_message(e.into_panic());
+                tracing::error!(task = name, error = %message, restart_in_ms = backoff.as_millis() as u64, "background_task_panicked");
+                supervisor.record_panic(name, message);
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(supervisor.max_backoff);
+                tracing::info!(task = name, "background_task_restarted");
+                supervisor.update(name, |task| task.running = true);
+            }
+        });
+    }
+
+    /// Run `task` once, logging (and counting under `name`) a panic
+    pub fn spawn_once<Fut>(&self, name: &'static str, task: Fut)
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let supervisor = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = tokio::spawn(task).await
+                && e.is_panic()
+            {
+                let message = panic_message(e.into_panic());
+                tracing::error!(task = name, error = %message, "background_task_panicked");
+                supervisor.record_panic(name, message);
+            }
+        });
+    }
+
+    /// Every task supervised or panicked so far, by name
+    pub fn reports(&self) -> Vec<TaskReport> {
+        self.tasks.lock().expect("task lock poisoned").values().cloned().collect()
+    }
+
+    fn record_panic(&self, name: &'static str, message: String) {
+        self.update(name, |task| {
+            task.running = false;
+            task.panics += 1;
+            task.last_panic = Some(message);
+            task.last_panic_at = Some(Timestamp::now());
+        });
+    }
+
+    fn update(&self, name: &'static str, change: impl FnOnce(&mut TaskReport)) {
+        let mut tasks = self.tasks.lock().expect("task lock poisoned");
+        let task = tasks.entry(name).or_insert_with(|| TaskReport {
+            name,
+            supervised: false,
+            running: false,
+            panics: 0,
+            last_panic: None,
+            last_panic_at: None,
+        });
+        change(task);
+    }
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panic".to_string())
+}
@@ -0,0 +1,160 @@
+// src/jobs/trends.rs
+// Week-by-week hiring time series: postings per week, skills gaining ground
+// and how salaries moved, the data behind forecasts
+
+use std::collections::{BTreeMap, HashMap};
+
+use nostr_sdk::Timestamp;
+
+use super::digest::{DigestPeriod, TrendingSkill};
+use super::model::JobListing;
+use super::rates::ExchangeRate;
+
+const TRENDING_SKILLS: usize = 10;
+/// Of those, kept for the skills losing the most ground
+const DECLINING_SKILLS: usize = 3;
+
+/// Listings posted in one week (Monday 00:00 UTC onwards)
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeekCount {
+    pub start: Timestamp,
+    pub listings: usize,
+    /// The week isn't over yet
+    pub partial: bool,
+}
+
+/// Median salary midpoint of one "CURRENCY/period" group in the earlier and
+/// the later half of the weeks
+#[derive(Clone, Debug, PartialEq)]
+pub struct SalaryMovement {
+    pub group: String,
+    pub earlier: f64,
+    pub later: f64,
+    /// Salaries behind each median
+    pub earlier_count: usize,
+    pub later_count: usize,
+}
+
+impl SalaryMovement {
+    /// Change from `earlier` to `later`, in percent
+    pub fn change_pct(&self) -> f64 {
+        match self.earlier {
+            e if e > 0.0 => (self.later - e) * 100.0 / e,
+            _ => 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HiringTrends {
+    /// Oldest first, empty weeks included; the last is the current week
+    pub weeks: Vec<WeekCount>,
+    /// Skills asked for in the later half of the weeks against the earlier,
+    /// most grown first, dropped ones included
+    pub trending_skills: Vec<TrendingSkill>,
+    /// Groups with salaries in both halves
+    pub salary_movement: Vec<SalaryMovement>,
+    /// Listings in the series
+    pub listings: usize,
+}
+
+impl HiringTrends {
+    /// The window `weeks` weeks long (at least 2) ending at `now`: from the
+    /// start of the week `weeks - 1` weeks before the current one
+    pub fn window(weeks: usize, now: Timestamp) -> (Timestamp, Timestamp) {
+        let weeks = weeks.max(2) as u64;
+        let current = DigestPeriod::Weekly.start_of(now).as_secs();
+        let since = current.saturating_sub((weeks - 1) * DigestPeriod::Weekly.secs());
+        (Timestamp::from(since), now)
+    }
+
+    /// Trends of `listings` over the `weeks` weeks ending at `now`; listings
+    /// outside them are ignored. Salaries in sats or BTC are converted at
+    /// `rate`.
+    pub fn from_listings(listings: &[JobListing], weeks: usize, now: Timestamp, rate: Option<&ExchangeRate>) -> Self {
+        let (since, _) = Self::window(weeks, now);
+        let week_secs = DigestPeriod::Weekly.secs();
+        let mut counts = vec![0usize; weeks.max(2)];
+        let half = counts.len() / 2;
+        let in_window: Vec<(usize, &JobListing)> = listings
+            .iter()
+            .filter(|l| l.created_at >= since && l.created_at <= now)
+            .map(|l| (((l.created_at.as_secs() - since.as_secs()) / week_secs) as usize, l))
+            .filter(|(week, _)| *week < counts.len())
+            .collect();
+        for (week, _) in &in_window {
+            counts[*week] += 1;
+        }
+        let last = counts.len() - 1;
+        let weeks = counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, listings)| WeekCount {
+                start: Timestamp::from(since.as_secs() + i as u64 * week_secs),
+                listings,
+                partial: i == last,
+            })
+            .collect();
+
+        // The later half gets the odd week out, the current one
+        let earlier: Vec<&JobListing> = in_window.iter().filter(|(week, _)| *week < half).map(|(_, l)| *l).collect();
+        let later: Vec<&JobListing> = in_window.iter().filter(|(week, _)| *week >= half).map(|(_, l)| *l).collect();
+
+        let skills = |listings: &[&JobListing]| {
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            for skill in listings.iter().flat_map(|l| &l.skills) {
+                *counts.entry(skill.trim().to_lowercase()).or_default() += 1;
+            }
+            counts
+        };
+        // Skills no longer asked for stay in, as the steepest losers
+        let mut previous = skills(&earlier);
+        let mut trending_skills: Vec<TrendingSkill> = skills(&later)
+            .into_iter()
+            .map(|(skill, count)| TrendingSkill { previous: previous.remove(&skill).unwrap_or(0), skill, count })
+            .collect();
+        trending_skills.extend(previous.into_iter().map(|(skill, previous)| TrendingSkill { skill, count: 0, previous }));
+        trending_skills.sort_by(|a, b| {
+            let growth = |t: &TrendingSkill| t.count as i64 - t.previous as i64;
+            growth(b).cmp(&growth(a)).then(b.count.cmp(&a.count)).then_with(|| a.skill.cmp(&b.skill))
+        });
+        // The biggest gainers, and the steepest losers at the end
+        if trending_skills.len() > TRENDING_SKILLS {
+            trending_skills.drain(TRENDING_SKILLS - DECLINING_SKILLS..trending_skills.len() - DECLINING_SKILLS);
+        }
+
+        let salaries = |listings: &[&JobListing]| {
+            let mut groups: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+            for salary in listings.iter().filter_map(|l| l.salary.as_ref()) {
+                let salary = rate.and_then(|r| r.convert(salary)).unwrap_or_else(|| salary.clone());
+                groups.entry(format!("{}/{}", salary.currency, salary.period)).or_default().push(salary.midpoint());
+            }
+            groups
+        };
+        let mut later_salaries = salaries(&later);
+        let salary_movement = salaries(&earlier)
+            .into_iter()
+            .filter_map(|(group, earlier)| {
+                let later = later_salaries.remove(&group)?;
+                Some(SalaryMovement {
+                    earlier_count: earlier.len(),
+                    later_count: later.len(),
+                    earlier: median(earlier),
+                    later: median(later),
+                    group,
+                })
+            })
+            .collect();
+
+        Self { weeks, trending_skills, salary_movement, listings: in_window.len() }
+    }
+}
+
+fn median(mut values: Vec<f64>) -> f64 {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    match values.len() % 2 {
+        0 => (values[mid - 1] + values[mid]) / 2.0,
+        _ => values[mid],
+    }
+}
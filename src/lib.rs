@@ -1,7 +1,33 @@
 // src/lib.rs
 #![allow(unused_mut)]
 
+pub mod access_log;
+pub mod admin;
+pub mod auth;
+pub mod builder;
+pub mod config;
+mod cursors;
+mod dashboard;
+mod diagnostics;
+pub mod error_reporting;
+pub mod graphql;
+pub mod i18n;
+pub mod jobs;
+pub mod live;
+pub mod load;
 pub mod mcp_server;
+mod metrics;
+pub mod plain_text;
+pub mod proxy;
+mod quota;
+pub mod sessions;
+mod shutdown;
+mod state;
+mod statsd;
+pub mod systemd;
 
 // Re-export
+pub use builder::NostrJobsServerBuilder;
+pub use config::{ServerConfig, TenantConfig};
+pub use jobs::{JobListing, JobQuery, JobsError, NostrJobsClient};
 pub use mcp_server::NostrJobsServer;
@@ -0,0 +1,124 @@
+// src/live.rs
+// `/ws/jobs`: newly seen listings pushed as JSON over WebSocket, for live
+// job-board frontends that speak neither MCP nor polling
+
+use std::time::Duration;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{MethodRouter, get};
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::jobs::JobQuery;
+use crate::mcp_server::{NostrJobsServer, OneOrMany, SearchJobsArgs};
+
+/// Keep-alive ping interval, also how soon a socket notices shutdown
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Query parameters narrowing the feed, as in `search_jobs`; list values
+/// are comma-separated (`?skill=rust,go&employment_type=full-time`)
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct LiveParams {
+    pub company: Option<String>,
+    pub skill: Option<String>,
+    pub employment_type: Option<String>,
+    pub author: Option<String>,
+    pub lat: Option<f64>,
+    pub lon: Option<f64>,
+    pub radius_km: Option<f64>,
+    pub fuzzy: bool,
+}
+
+impl TryFrom<&LiveParams> for JobQuery {
+    type Error = crate::JobsError;
+
+    fn try_from(params: &LiveParams) -> Result<Self, Self::Error> {
+        let list = |value: &Option<String>| {
+            value.as_ref().map(|v| OneOrMany::Many(v.split(',').map(str::to_string).collect()))
+        };
+        let args = SearchJobsArgs {
+            company: list(&params.company),
+            skill: list(&params.skill),
+            employment_type: list(&params.employment_type),
+            author: params.author.clone(),
+            near: None,
+            lat: params.lat,
+            lon: params.lon,
+            radius_km: params.radius_km,
+            min_salary: None,
+            limit: crate::jobs::DEFAULT_SEARCH_LIMIT,
+            fuzzy: params.fuzzy,
+            max_edit_distance: None,
+            hide_suspicious: false,
+            kinds: None,
+        };
+        JobQuery::try_from(&args)
+    }
+}
+
+/// `GET` handler upgrading to a WebSocket that streams `server`'s new listings
+pub fn route(server: NostrJobsServer) -> MethodRouter {
+    get(upgrade).with_state(server)
+}
+
+async fn upgrade(
+    State(server): State<NostrJobsServer>,
+    Query(params): Query<LiveParams>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let query = match JobQuery::try_from(&params) {
+        Ok(query) => query,
+        Err(e) => return (StatusCode::BAD_REQUEST, axum::Json(e.to_json())).into_response(),
+    };
+    if server.is_shutting_down() {
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    }
+    ws.on_upgrade(move |socket| stream(socket, server, query))
+}
+
+/// Send each new listing matching `query` as `{"type": "listing", "listing": ...}`,
+/// and `{"type": "lagged", "skipped": n}` when the client fell behind
+async fn stream(mut socket: WebSocket, server: NostrJobsServer, query: JobQuery) {
+    let mut listings = server.jobs().subscribe();
+    let mut ping = tokio::time::interval(PING_INTERVAL);
+    ping.tick().await;
+    tracing::debug!("live_client_connected");
+
+    loop {
+        let message = tokio::select! {
+            received = listings.recv() => match received {
+                Ok(listing) if query.matches(&listing) => {
+                    json!({"type": "listing", "listing": server.listing_json(&listing, None)})
+                }
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => json!({"type": "lagged", "skipped": skipped}),
+                Err(RecvError::Closed) => break,
+            },
+            incoming = socket.recv() => match incoming {
+                // Pings are answered by axum; clients have nothing else to say
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+            _ = ping.tick() => {
+                if server.is_shutting_down() {
+                    break;
+                }
+                if socket.send(Message::Ping(Default::default())).await.is_err() {
+                    break;
+                }
+                continue;
+            }
+        };
+        if socket.send(Message::Text(message.to_string().into())).await.is_err() {
+            break;
+        }
+    }
+
+    let _ = socket.send(Message::Close(None)).await;
+    tracing::debug!("live_client_disconnected");
+}
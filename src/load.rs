@@ -0,0 +1,377 @@
+// src/load.rs
+// Load/soak testing: a weighted mix of tool calls replayed against a running
+// instance by concurrent MCP clients, reported as throughput and latency percentiles
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use futures::future::BoxFuture;
+use nostr_sdk::prelude::*;
+use rmcp::ServiceExt;
+use rmcp::model::CallToolRequestParam;
+use rmcp::transport::StreamableHttpClientTransport;
+use rmcp::transport::streamable_http_client::StreamableHttpClientTransportConfig;
+use serde_json::{Map, Value, json};
+
+use crate::config::{ServerConfig, env_opt, env_or};
+use crate::jobs::RelayBackend;
+use crate::metrics::LatencyHistogram;
+
+const DEFAULT_CLIENTS: usize = 16;
+const DEFAULT_DURATION_SECS: u64 = 30;
+const DEFAULT_LISTINGS: usize = 5_000;
+const DEFAULT_RELAY_DELAY_MS: u64 = 50;
+const DEFAULT_MIX: &str = "search_jobs:6,get_job_details:2,get_stats:1,count_jobs_by_tag:1,list_relays:1";
+
+/// Relays the local instance fans out to; all served by the same `SyntheticRelay`
+pub const SYNTHETIC_RELAYS: &[&str] = &["wss://load-1.invalid", "wss://load-2.invalid", "wss://load-3.invalid"];
+
+const COMPANIES: &[&str] = &["Acme", "Globex", "Initech", "Hooli", "Umbrella", "Stark", "Wayne", "Tyrell"];
+const SKILLS: &[&str] = &["Rust", "Python", "Go", "TypeScript", "PostgreSQL", "Kubernetes", "Tokio", "React", "Nostr"];
+const EMPLOYMENT_TYPES: &[&str] = &["full-time", "part-time", "contract"];
+const LOCATIONS: &[&str] = &["Remote", "Berlin", "Lisbon", "New York", "Singapore"];
+
+/// A tool in the load mix and how often it is called relative to the others
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoadStep {
+    pub tool: String,
+    pub weight: usize,
+}
+
+/// Settings for `run`, read by `from_env` from the `LOAD_*` variables
+#[derive(Clone, Debug)]
+pub struct LoadConfig {
+    /// MCP endpoint to load; `None` starts a local instance serving synthetic listings
+    pub target: Option<String>,
+    /// Bearer token sent by every client
+    pub token: Option<String>,
+    /// Concurrent MCP sessions, each calling tools back to back
+    pub clients: usize,
+    pub duration: Duration,
+    pub mix: Vec<LoadStep>,
+    /// Listings the local instance's synthetic relays hold
+    pub listings: usize,
+    /// Simulated per-fetch latency of the synthetic relays
+    pub relay_delay: Duration,
+}
+
+impl Default for LoadConfig {
+    fn default() -> Self {
+        Self {
+            target: None,
+            token: None,
+            clients: DEFAULT_CLIENTS,
+            duration: Duration::from_secs(DEFAULT_DURATION_SECS),
+            mix: parse_mix(DEFAULT_MIX),
+            listings: DEFAULT_LISTINGS,
+            relay_delay: Duration::from_millis(DEFAULT_RELAY_DELAY_MS),
+        }
+    }
+}
+
+impl LoadConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let mix = parse_mix(&env_opt("LOAD_MIX").unwrap_or_default());
+        Self {
+            target: env_opt("LOAD_TARGET"),
+            token: env_opt("LOAD_TOKEN"),
+            clients: env_or("LOAD_CLIENTS", defaults.clients).max(1),
+            duration: Duration::from_secs(env_or("LOAD_DURATION_SECS", defaults.duration.as_secs()).max(1)),
+            mix: if mix.is_empty() { defaults.mix } else { mix },
+            listings: env_or("LOAD_LISTINGS", defaults.listings).max(1),
+            relay_delay: Duration::from_millis(env_or("LOAD_RELAY_DELAY_MS", defaults.relay_delay.as_millis() as u64)),
+        }
+    }
+
+    /// `base` adjusted for a local instance: synthetic relays, and nothing
+    /// written to disk or fetched from outside
+    pub fn local_server_config(&self, base: ServerConfig) -> ServerConfig {
+        ServerConfig {
+            relays: SYNTHETIC_RELAYS.iter().map(|r| r.to_string()).collect(),
+            authors: Vec::new(),
+            tenants: Vec::new(),
+            audit_log_path: None,
+            access_log_path: None,
+            state_path: None,
+            redis_url: None,
+            base_path: String::new(),
+            geocoder_url: None,
+            exchange_rate_url: None,
+            ingest_sources: Vec::new(),
+            bridge_publish: false,
+            digest_period: None,
+            ..base
+        }
+    }
+}
+
+/// `tool:weight` pairs, comma-separated; a missing or invalid weight counts as 1
+pub fn parse_mix(mix: &str) -> Vec<LoadStep> {
+    mix.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| match entry.split_once(':') {
+            Some((tool, weight)) => LoadStep {
+                tool: tool.trim().to_string(),
+                weight: weight.trim().parse().unwrap_or(1),
+            },
+            None => LoadStep { tool: entry.to_string(), weight: 1 },
+        })
+        .filter(|step| step.weight > 0)
+        .collect()
+}
+
+/// Relay backend holding generated listings (`load-0`, `load-1`, ...), each
+/// fetch taking `delay`, so a load run needs no network
+#[derive(Debug)]
+pub struct SyntheticRelay {
+    events: Vec<Event>,
+    delay: Duration,
+    fetches: AtomicUsize,
+}
+
+impl SyntheticRelay {
+    pub fn new(listings: usize, delay: Duration) -> Self {
+        let keys = Keys::generate();
+        let now = Timestamp::now().as_secs();
+        let events = (0..listings)
+            .filter_map(|i| {
+                let tags = [
+                    vec!["job-id".to_string(), format!("load-{}", i)],
+                    vec!["j".to_string(), format!("load-{}", i)],
+                    vec!["title".to_string(), format!("{} Engineer", SKILLS[i % SKILLS.len()])],
+                    vec!["company".to_string(), COMPANIES[i % COMPANIES.len()].to_string()],
+                    vec!["location".to_string(), LOCATIONS[i % LOCATIONS.len()].to_string()],
+                    vec!["employment-type".to_string(), EMPLOYMENT_TYPES[i % EMPLOYMENT_TYPES.len()].to_string()],
+                    vec!["skill".to_string(), SKILLS[i % SKILLS.len()].to_string()],
+                    vec!["skill".to_string(), SKILLS[(i + 4) % SKILLS.len()].to_string()],
+                    vec!["salary".into(), format!("{}", 60_000 + (i % 50) * 2_000), format!("{}", 90_000 + (i % 50) * 2_000), "USD".into(), "year".into()],
+                ];
+                EventBuilder::new(Kind::from(crate::config::JOB_LISTING_KIND), format!("Synthetic listing {}", i))
+                    .tags(tags.into_iter().filter_map(|tag| Tag::parse(tag).ok()))
+                    .custom_created_at(Timestamp::from(now.saturating_sub(i as u64 * 60)))
+                    .sign_with_keys(&keys)
+                    .ok()
+            })
+            .collect();
+        Self { events, delay, fetches: AtomicUsize::new(0) }
+    }
+
+    /// Fetches served so far
+    pub fn fetches(&self) -> usize {
+        self.fetches.load(Ordering::Relaxed)
+    }
+}
+
+impl RelayBackend for SyntheticRelay {
+    fn add_relay<'a>(&'a self, _url: &'a str) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn remove_relay<'a>(&'a self, _url: &'a str) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn connect(&self) -> BoxFuture<'_, ()> {
+        Box::pin(async {})
+    }
+
+    fn fetch_events<'a>(
+        &'a self,
+        _url: &'a str,
+        filter: Filter,
+        _timeout: Duration,
+    ) -> BoxFuture<'a, Result<Vec<Event>, String>> {
+        Box::pin(async move {
+            self.fetches.fetch_add(1, Ordering::Relaxed);
+            tokio::time::sleep(self.delay).await;
+            let limit = filter.limit.unwrap_or(usize::MAX);
+            // Events are generated newest first, so the first matches are the ones a relay would return
+            Ok(self
+                .events
+                .iter()
+                .filter(|e| filter.match_event(e, MatchEventOptions::new()))
+                .take(limit)
+                .cloned()
+                .collect())
+        })
+    }
+
+    fn publish<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, Result<EventId, String>> {
+        Box::pin(async move { Ok(event.id) })
+    }
+
+    fn shutdown(&self) -> BoxFuture<'_, ()> {
+        Box::pin(async {})
+    }
+}
+
+/// Arguments for the `n`th call of `tool`, varied so searches don't all hit one cache entry
+fn arguments(tool: &str, n: usize, listings: usize) -> Map<String, Value> {
+    let args = match tool {
+        "search_jobs" if n.is_multiple_of(3) => json!({"skill": SKILLS[n % SKILLS.len()], "company": COMPANIES[n % COMPANIES.len()]}),
+        "search_jobs" => json!({"skill": SKILLS[n % SKILLS.len()], "employment_type": EMPLOYMENT_TYPES[n % EMPLOYMENT_TYPES.len()]}),
+        "explain_query" => json!({"skill": SKILLS[n % SKILLS.len()]}),
+        "get_job_details" | "verify_listing_active" | "get_raw_event" => json!({"job_id": format!("load-{}", n % listings.max(1))}),
+        "get_stats" => json!({"sample_size": 500}),
+        "count_jobs_by_tag" => json!({"tag": "location"}),
+        _ => json!({}),
+    };
+    match args {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    }
+}
+
+/// Calls, failures and latency of one tool during a run
+#[derive(Clone, Debug, Default)]
+pub struct ToolLoad {
+    pub calls: usize,
+    pub errors: usize,
+    latency: LatencyHistogram,
+}
+
+impl ToolLoad {
+    /// Estimated `q`-quantile latency in ms
+    pub fn percentile(&self, q: f64) -> u128 {
+        self.latency.percentile(q)
+    }
+
+    fn record(&mut self, duration_ms: u128, success: bool) {
+        self.calls += 1;
+        self.errors += usize::from(!success);
+        self.latency.record(duration_ms);
+    }
+
+    fn merge(&mut self, other: &ToolLoad) {
+        self.calls += other.calls;
+        self.errors += other.errors;
+        self.latency.merge(&other.latency);
+    }
+}
+
+/// Outcome of a load run
+#[derive(Clone, Debug, Default)]
+pub struct LoadReport {
+    pub clients: usize,
+    pub elapsed: Duration,
+    pub tools: BTreeMap<String, ToolLoad>,
+}
+
+impl LoadReport {
+    pub fn calls(&self) -> usize {
+        self.tools.values().map(|t| t.calls).sum()
+    }
+
+    pub fn errors(&self) -> usize {
+        self.tools.values().map(|t| t.errors).sum()
+    }
+
+    /// Completed calls per second
+    pub fn throughput(&self) -> f64 {
+        self.calls() as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// All tools together
+    pub fn overall(&self) -> ToolLoad {
+        let mut overall = ToolLoad::default();
+        for load in self.tools.values() {
+            overall.merge(load);
+        }
+        overall
+    }
+}
+
+impl fmt::Display for LoadReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "📊 Load Test Report\n\n• Clients: {}\n• Duration: {:.1}s\n• Calls: {} ({} failed)\n• Throughput: {:.1} calls/s\n",
+            self.clients,
+            self.elapsed.as_secs_f64(),
+            self.calls(),
+            self.errors(),
+            self.throughput()
+        )?;
+        let width = self.tools.keys().map(String::len).max().unwrap_or(4).max(7);
+        writeln!(f, "  {:<width$}  {:>7}  {:>6}  {:>8}  {:>8}  {:>8}", "tool", "calls", "errors", "p50", "p90", "p99")?;
+        let row = |f: &mut fmt::Formatter<'_>, tool: &str, load: &ToolLoad| {
+            writeln!(
+                f,
+                "  {:<width$}  {:>7}  {:>6}  {:>6}ms  {:>6}ms  {:>6}ms",
+                tool,
+                load.calls,
+                load.errors,
+                load.percentile(0.50),
+                load.percentile(0.90),
+                load.percentile(0.99)
+            )
+        };
+        for (tool, load) in &self.tools {
+            row(f, tool, load)?;
+        }
+        row(f, "overall", &self.overall())
+    }
+}
+
+/// Run `config.clients` MCP sessions against `target`, each calling tools
+/// from the mix back to back until `config.duration` has passed
+pub async fn run(config: &LoadConfig, target: &str) -> Result<LoadReport, String> {
+    let total_weight: usize = config.mix.iter().map(|s| s.weight).sum();
+    if total_weight == 0 {
+        return Err("empty load mix".to_string());
+    }
+
+    let started = Instant::now();
+    let deadline = started + config.duration;
+    let clients = (0..config.clients).map(|client| async move {
+        let mut transport = StreamableHttpClientTransportConfig::with_uri(target.to_string());
+        if let Some(token) = &config.token {
+            transport = transport.auth_header(token.clone());
+        }
+        let session = ()
+            .serve(StreamableHttpClientTransport::from_config(transport))
+            .await
+            .map_err(|e| format!("client {}: {}", client, e))?;
+
+        let mut tools: BTreeMap<String, ToolLoad> = BTreeMap::new();
+        // Clients start at different points of the mix so the tools interleave
+        let mut n = client;
+        while Instant::now() < deadline {
+            let tool = pick(&config.mix, n % total_weight);
+            let call = CallToolRequestParam {
+                name: tool.to_string().into(),
+                arguments: Some(arguments(tool, n, config.listings)),
+            };
+            let call_started = Instant::now();
+            let success = matches!(session.call_tool(call).await, Ok(result) if result.is_error != Some(true));
+            tools.entry(tool.to_string()).or_default().record(call_started.elapsed().as_millis(), success);
+            n += 1;
+        }
+        let _ = session.cancel().await;
+        Ok::<_, String>(tools)
+    });
+
+    let mut report = LoadReport { clients: config.clients, ..Default::default() };
+    for tools in futures::future::join_all(clients).await {
+        for (tool, load) in tools? {
+            report.tools.entry(tool).or_default().merge(&load);
+        }
+    }
+    report.elapsed = started.elapsed();
+    Ok(report)
+}
+
+/// The tool whose weight range holds `slot` (0..total weight)
+fn pick(mix: &[LoadStep], mut slot: usize) -> &str {
+    for step in mix {
+        if slot < step.weight {
+            return &step.tool;
+        }
+        slot -= step.weight;
+    }
+    &mix[mix.len() - 1].tool
+}
@@ -1,17 +1,43 @@
 // src/main.rs - Updated for HTTP Streamable Transport with .env support
 
 use rmcp::transport::streamable_http_server::{
+    SessionManager,
     StreamableHttpService,
     session::local::LocalSessionManager
 };
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
-use jobmcp::NostrJobsServer;
-use std::net::SocketAddr;
+use tracing_subscriber::{EnvFilter, layer::SubscriberExt, reload, util::SubscriberInitExt};
+use axum::body::Body;
+use http::Request;
+use hyper::body::Incoming;
+use hyper_util::rt::{TokioExecutor, TokioIo, TokioTimer};
+use hyper_util::server::conn::auto;
+use hyper_util::server::graceful::GracefulShutdown;
+use hyper_util::service::TowerToHyperService;
+use jobmcp::access_log::{AccessLog, AccessLogLayer};
+use jobmcp::jobs::{MAX_STATS_SAMPLE_SIZE, snapshot};
+use jobmcp::load::{self, LoadConfig, SyntheticRelay};
+use jobmcp::{NostrJobsServer, admin, auth, error_reporting, graphql, live, systemd};
+use tower::Layer;
+use tower::util::MapRequestLayer;
+use tower_http::compression::CompressionLayer;
+use jobmcp::config::{ENV_FILE, ServerConfig};
+use jobmcp::proxy::PeerAddr;
+use jobmcp::sessions::RedisSessionManager;
+use futures::StreamExt;
+use socket2::{Domain, SockRef, Socket, TcpKeepalive, Type};
+use std::convert::Infallible;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::path::Path;
 use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
 
 const DEFAULT_PORT: u16 = 9993;
-const ENV_FILE: &str = ".env";
+const DEFAULT_BIND_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+const LISTEN_BACKLOG: i32 = 1024;
+const CONNECTION_CLOSE_GRACE: Duration = Duration::from_secs(2);
 
 /// Load port from .env file, creating it with default if it doesn't exist
 fn load_or_create_port() -> anyhow::Result<u16> {
@@ -50,50 +76,429 @@ fn load_or_create_port() -> anyhow::Result<u16> {
     }
 }
 
+/// Addresses from BIND_ADDRESSES (comma-separated IPs or `ip:port`, IPv6 as
+/// `[::1]:9993`); bare IPs use `port`. Defaults to localhost only.
+fn load_bind_addresses(port: u16) -> Vec<SocketAddr> {
+    let mut addresses = Vec::new();
+    for entry in std::env::var("BIND_ADDRESSES").unwrap_or_default().split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let address = entry
+            .parse::<SocketAddr>()
+            .or_else(|_| entry.trim_matches(['[', ']']).parse::<IpAddr>().map(|ip| SocketAddr::new(ip, port)));
+        match address {
+            Ok(address) if !addresses.contains(&address) => addresses.push(address),
+            Ok(_) => {}
+            Err(_) => eprintln!("⚠️  Invalid BIND_ADDRESSES entry in {}: '{}'. Skipping", ENV_FILE, entry),
+        }
+    }
+    if addresses.is_empty() {
+        addresses.push(SocketAddr::new(DEFAULT_BIND_ADDRESS, port));
+    }
+    addresses
+}
+
+/// Listen on `address`. IPv6 sockets are made IPv6-only so `0.0.0.0` and
+/// `::` can share a port.
+fn bind(address: SocketAddr) -> std::io::Result<TcpListener> {
+    let socket = Socket::new(Domain::for_address(address), Type::STREAM, None)?;
+    if address.is_ipv6() {
+        socket.set_only_v6(true)?;
+    }
+    socket.set_reuse_address(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&address.into())?;
+    socket.listen(LISTEN_BACKLOG)?;
+    TcpListener::from_std(socket.into())
+}
+
+/// MCP endpoint, Prometheus scrape endpoint, HTML status page, admin API,
+/// live WebSocket feed and GraphQL endpoint for one board, each at `{base}/<endpoint>{suffix}`
+/// (`suffix` being `/{tenant}` for tenants). Sessions live in-process unless
+/// REDIS_URL points at a shared store.
+async fn board_routes(server: &NostrJobsServer, base: &str, suffix: &str) -> anyhow::Result<axum::Router> {
+    let mcp_path = format!("{}/mcp{}", base, suffix);
+    let router = match server.config().redis_url.clone() {
+        Some(url) => {
+            let sessions = RedisSessionManager::connect(&url, server.clone(), server.config().session_ttl).await?;
+            axum::Router::new().nest_service(&mcp_path, mcp_service(server, Arc::new(sessions)))
+        }
+        None => axum::Router::new().nest_service(&mcp_path, mcp_service(server, Arc::new(LocalSessionManager::default()))),
+    };
+
+    let router = router
+        .nest(&format!("{}/admin{}", base, suffix), admin::routes(server.clone()))
+        .route(&format!("{}/ws/jobs{}", base, suffix), live::route(server.clone()))
+        .route(&format!("{}/graphql{}", base, suffix), graphql::route(server.clone()));
+
+    let status_server = server.clone();
+    let router = router.route(
+        &format!("{}/status{}", base, suffix),
+        axum::routing::get(move |headers: http::HeaderMap| {
+            let server = status_server.clone();
+            // What callers did lately is for admins only
+            let admin = auth::is_admin_request(&headers, &server.config().admin_tokens);
+            async move { axum::response::Html(server.status_page(admin).await) }
+        }),
+    );
+
+    let metrics_server = server.clone();
+    Ok(router.route(
+        &format!("{}/metrics{}", base, suffix),
+        axum::routing::get(move || {
+            let server = metrics_server.clone();
+            async move {
+                (
+                    [(http::header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+                    server.prometheus_metrics().await,
+                )
+            }
+        }),
+    ))
+}
+
+/// MCP endpoint backed by `sessions`; new sessions are refused once shutdown has begun
+fn mcp_service<M: SessionManager>(
+    server: &NostrJobsServer,
+    sessions: Arc<M>,
+) -> StreamableHttpService<NostrJobsServer, M> {
+    let session_server = server.clone();
+    StreamableHttpService::new(
+        move || {
+            if session_server.is_shutting_down() {
+                Err(std::io::Error::other("server is shutting down"))
+            } else {
+                Ok(session_server.clone())
+            }
+        },
+        sessions,
+        Default::default(),
+    )
+}
+
+/// HTTP/1.1 and, unless disabled, HTTP/2 with the configured keep-alive and
+/// idle timeout
+fn connection_builder(config: &ServerConfig) -> auto::Builder<TokioExecutor> {
+    let mut builder = auto::Builder::new(TokioExecutor::new());
+    builder
+        .http1()
+        .timer(TokioTimer::new())
+        .header_read_timeout(config.http_idle_timeout);
+    builder
+        .http2()
+        .timer(TokioTimer::new())
+        .keep_alive_interval(config.http_keep_alive);
+    if config.http2 { builder } else { builder.http1_only() }
+}
+
+/// Accept connections on every listener until `stopped` is cancelled, then
+/// wait for open connections to finish. Open SSE streams never end on their
+/// own, so they get a short grace period before being dropped. `heartbeat`
+/// beats while the accept loop runs.
+async fn serve<S>(
+    listeners: Vec<TcpListener>,
+    app: S,
+    config: &ServerConfig,
+    stopped: CancellationToken,
+    heartbeat: systemd::Heartbeat,
+)
+where
+    S: tower::Service<Request<Body>, Response = axum::response::Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    let builder = connection_builder(config);
+    let keep_alive = config.http_keep_alive.map(|interval| TcpKeepalive::new().with_time(interval).with_interval(interval));
+    let graceful = GracefulShutdown::new();
+    let mut accepts = futures::stream::select_all(listeners.into_iter().map(|listener| {
+        futures::stream::unfold(listener, |listener| async move {
+            let accepted = listener.accept().await;
+            Some((accepted, listener))
+        })
+        .boxed()
+    }));
+    let mut beats = tokio::time::interval(systemd::HEARTBEAT_INTERVAL);
+    loop {
+        let (stream, peer) = tokio::select! {
+            _ = beats.tick() => {
+                heartbeat.beat();
+                continue;
+            }
+            Some(accepted) = accepts.next() => match accepted {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    // Usually out of file descriptors; give connections time to close
+                    tracing::warn!(error = %e, "accept_failed");
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    continue;
+                }
+            },
+            _ = stopped.cancelled() => break,
+        };
+        let _ = stream.set_nodelay(true);
+        if let Some(keep_alive) = &keep_alive
+            && let Err(e) = SockRef::from(&stream).set_tcp_keepalive(keep_alive)
+        {
+            tracing::debug!(error = %e, "tcp_keepalive_unavailable");
+        }
+
+        let service = TowerToHyperService::new(
+            tower::ServiceExt::map_request(app.clone(), move |mut request: Request<Incoming>| {
+                request.extensions_mut().insert(PeerAddr(peer));
+                request.map(Body::new)
+            }),
+        );
+        let connection = graceful.watch(builder.serve_connection_with_upgrades(TokioIo::new(stream), service).into_owned());
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                tracing::debug!(error = %e, "connection_closed_with_error");
+            }
+        });
+    }
+
+    tokio::select! {
+        _ = graceful.shutdown() => {}
+        _ = tokio::time::sleep(CONNECTION_CLOSE_GRACE) => println!("⏱️  Closing remaining connections"),
+    }
+}
+
+/// Resolves on Ctrl+C, or SIGTERM on Unix
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to listen for ctrl-c");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
+/// `--selftest-load`: replay the LOAD_MIX of tool calls against LOAD_TARGET,
+/// or against a local instance on an ephemeral port whose relays serve
+/// synthetic listings, then print throughput and latency percentiles
+async fn selftest_load() -> anyhow::Result<()> {
+    dotenvy::dotenv().ok();
+    let load = LoadConfig::from_env();
+
+    let (target, relay) = match load.target.clone() {
+        Some(target) => (target, None),
+        None => {
+            let relay = Arc::new(SyntheticRelay::new(load.listings, load.relay_delay));
+            let config = load.local_server_config(ServerConfig::from_env());
+            let server = NostrJobsServer::builder().config(config.clone()).backend(relay.clone()).build().await?;
+            let router = board_routes(&server, "", "").await?;
+            let listener = bind(SocketAddr::new(DEFAULT_BIND_ADDRESS, 0))?;
+            let address = listener.local_addr()?;
+            tokio::spawn(async move { serve(vec![listener], router, &config, CancellationToken::new(), Default::default()).await });
+            println!(
+                "🧪 Local instance with {} synthetic listings ({}ms per relay fetch)",
+                load.listings,
+                load.relay_delay.as_millis()
+            );
+            (format!("http://{}/mcp", address), Some(relay))
+        }
+    };
+
+    let mix: Vec<String> = load.mix.iter().map(|step| format!("{}×{}", step.tool, step.weight)).collect();
+    println!("🔥 Loading {} with {} clients for {}s: {}", target, load.clients, load.duration.as_secs(), mix.join(", "));
+    let report = load::run(&load, &target).await.map_err(anyhow::Error::msg)?;
+    println!("\n{}", report);
+    if let Some(relay) = relay {
+        println!("• Relay fetches: {}", relay.fetches());
+    }
+    // The local instance stops with the process; cancelling it first would
+    // race the clients' session teardown
+    Ok(())
+}
+
+/// The value after `flag` on the command line, e.g. `--import-snapshot FILE`
+fn arg_value(flag: &str) -> Option<String> {
+    let mut args = std::env::args();
+    args.find(|arg| arg == flag)?;
+    args.next()
+}
+
+/// `--export-snapshot FILE`: fetch up to MAX_STATS_SAMPLE_SIZE listings
+/// from the relays and write them to FILE as an NDJSON snapshot
+async fn export_snapshot(path: &str) -> anyhow::Result<()> {
+    dotenvy::dotenv().ok();
+    let server = NostrJobsServer::new().await?;
+    let sample = server.jobs().sample(MAX_STATS_SAMPLE_SIZE, None).await?;
+    fs::write(path, snapshot::to_ndjson(sample.listings.iter().map(|l| &l.event)))?;
+    println!(
+        "📦 Wrote {} listing(s) to {}{}",
+        sample.listings.len(),
+        path,
+        if sample.truncated { " (partial: STATS_TIMEOUT_SECS reached)" } else { "" }
+    );
+    server.jobs().shutdown().await;
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // Initialize logging
+    let selftest_load_mode = std::env::args().any(|arg| arg == "--selftest-load");
+    let export_path = arg_value("--export-snapshot");
+
+    // Initialize logging; the filter is reloadable so LOG_LEVEL can change at runtime.
+    // A load run only logs warnings unless asked, so the report stays readable.
+    let default_filter = if selftest_load_mode { "warn" } else { "info,jobmcp=debug" };
+    let (filter, filter_handle) = reload::Layer::new(
+        EnvFilter::try_from_default_env()
+            .unwrap_or_else(|_| default_filter.to_string().into()),
+    );
     tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "info,jobmcp=debug".to_string().into()),
-        )
+        .with(filter)
         .with(tracing_subscriber::fmt::layer())
         .init();
 
+    if selftest_load_mode {
+        return selftest_load().await;
+    }
+    if let Some(path) = export_path {
+        return export_snapshot(&path).await;
+    }
+
     println!("🚀 Starting Nostr Jobs MCP Server (HTTP Streamable)");
     
     // Load port from .env
     let port = load_or_create_port()?;
-    let bind_address = format!("127.0.0.1:{}", port);
-    
-    println!("📡 Binding to: {}", bind_address);
-    println!("🔗 MCP endpoint: http://{}/mcp", bind_address);
+    let addresses = load_bind_addresses(port);
+    let bind_address = addresses[0];
+
+    for address in &addresses {
+        println!("📡 Binding to: {}", address);
+    }
     println!();
     println!("💡 Connecting to Nostr relays...");
     
-    // Create the HTTP service with factory closure that returns Result<NostrJobsServer, io::Error>
-    let service = StreamableHttpService::new(
-        || {
-            tokio::task::block_in_place(|| {
-                tokio::runtime::Handle::current().block_on(async {
-                    Ok(NostrJobsServer::new().await)
-                })
-            })
-        },
-        LocalSessionManager::default().into(),
-        Default::default(),
-    );
+    // One server (relay pool, cache, metrics) shared by every MCP session,
+    // plus one per configured tenant so their caches and metrics stay separate
+    let server = NostrJobsServer::new().await?;
+    let mut tenants = Vec::new();
+    for tenant in &server.config().tenants {
+        let tenant_server = NostrJobsServer::with_config(server.config().for_tenant(tenant))
+            .await
+            .map_err(|e| anyhow::anyhow!("tenant {}: {}", tenant.name, e))?;
+        tenants.push((tenant.name.clone(), tenant_server));
+    }
+    if let Some(path) = arg_value("--import-snapshot") {
+        let ndjson = fs::read_to_string(&path).map_err(|e| anyhow::anyhow!("{}: {}", path, e))?;
+        let report = server.jobs().import_snapshot(&ndjson).await;
+        println!(
+            "📥 Imported {} listing(s) from {} ({} already indexed, {} not for this board, {} invalid)",
+            report.imported,
+            path,
+            report.duplicates,
+            report.skipped,
+            report.invalid.len()
+        );
+    }
+    // Panics go to the default board's SENTRY_DSN, when set
+    error_reporting::install_panic_hook(server.jobs().error_reporter().clone());
+    let all_servers: Vec<NostrJobsServer> = std::iter::once(server.clone())
+        .chain(tenants.iter().map(|(_, s)| s.clone()))
+        .collect();
+
+    for board in &all_servers {
+        let filter_handle = filter_handle.clone();
+        board.set_log_level_hook(move |level| {
+            let filter = EnvFilter::try_new(level).map_err(|e| e.to_string())?;
+            filter_handle.reload(filter).map_err(|e| e.to_string())
+        });
+    }
 
-    // Create axum router and mount the MCP service at /mcp
-    let router = axum::Router::new()
-        .nest_service("/mcp", service);
+    // SIGHUP re-reads .env and applies it in place
+    #[cfg(unix)]
+    {
+        let reload_servers = all_servers.clone();
+        tokio::spawn(async move {
+            let mut hangups = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+                Ok(signal) => signal,
+                Err(e) => {
+                    tracing::warn!(error = %e, "sighup_handler_unavailable");
+                    return;
+                }
+            };
+            while hangups.recv().await.is_some() {
+                println!("🔄 SIGHUP received, reloading {}", ENV_FILE);
+                for board in &reload_servers {
+                    println!("{}", board.reload_config().await);
+                }
+            }
+        });
+    }
 
-    // Parse the bind address
-    let addr: SocketAddr = bind_address.parse()?;
-    
-    // Create the TCP listener
-    let listener = tokio::net::TcpListener::bind(addr).await?;
+    // Default board at /mcp, /metrics, ..., tenants at /mcp/{tenant},
+    // /metrics/{tenant}, ..., all under BASE_PATH when set
+    let base = server.config().base_path.clone();
+    let mut router = board_routes(&server, &base, "").await?;
+    println!("🔗 MCP endpoint: http://{}{}/mcp", bind_address, base);
+    println!("📈 Metrics endpoint: http://{}{}/metrics", bind_address, base);
+    println!("🖥️  Status dashboard: http://{}{}/status", bind_address, base);
+    println!("📡 Live feed: ws://{}{}/ws/jobs", bind_address, base);
+    println!("🔎 GraphQL: http://{}{}/graphql", bind_address, base);
+    if !server.config().admin_tokens.is_empty() {
+        println!("🔐 Admin API: http://{}{}/admin", bind_address, base);
+    }
+    for (name, tenant_server) in &tenants {
+        router = router.merge(board_routes(tenant_server, &base, &format!("/{}", name)).await?);
+        println!("🏢 Tenant '{}': http://{}{}/mcp/{}", name, bind_address, base, name);
+    }
+
+    if server.config().redis_url.is_some() {
+        println!("🗄️  Sessions stored in Redis");
+    }
+
+    // gzip/br per Accept-Encoding. The default predicate leaves SSE streams
+    // and tiny bodies alone, so streamed MCP responses still flush per event.
+    let router = router.layer(CompressionLayer::new());
+
+    // Plain /mcp requests carrying a tenant token are routed to that tenant.
+    // This has to wrap the router: layers added to it run after routing.
+    let tenant_configs = server.config().tenants.clone();
+    let app = MapRequestLayer::new(move |request: Request<Body>| auth::route_by_tenant_token(request, &base, &tenant_configs)).layer(router);
+
+    // Outermost, so the access log sees the path the client asked for
+    let access_log = server.config().access_log_path.clone().map(|path| {
+        println!("📝 Access log: {}", path.display());
+        let config = server.config();
+        AccessLogLayer::new(
+            AccessLog::open(path, config.access_log_max_bytes, config.access_log_keep),
+            config.trust_forwarded_headers,
+        )
+    });
+    let app = tower::util::option_layer(access_log).layer(app);
+
+    // One listener per address, all serving the same router and sessions;
+    // under systemd socket activation, the sockets it passed instead
+    let activated = systemd::listen_fds();
+    let listeners = if activated.is_empty() {
+        addresses
+            .iter()
+            .map(|&address| bind(address).map_err(|e| anyhow::anyhow!("bind {}: {}", address, e)))
+            .collect::<anyhow::Result<Vec<_>>>()?
+    } else {
+        println!("📡 Using {} socket(s) passed by systemd", activated.len());
+        activated
+            .into_iter()
+            .map(|listener| {
+                listener.set_nonblocking(true)?;
+                TcpListener::from_std(listener)
+            })
+            .collect::<std::io::Result<Vec<_>>>()?
+    };
+    let http_config = server.config().clone();
     
     println!("✅ Server is running!");
     println!("📋 Available tools:");
@@ -109,15 +514,54 @@ async fn main() -> anyhow::Result<()> {
     println!("Press Ctrl+C to stop the server...");
     println!();
 
-    // Serve with graceful shutdown
-    axum::serve(listener, router)
-        .with_graceful_shutdown(async {
-            tokio::signal::ctrl_c()
-                .await
-                .expect("failed to listen for ctrl-c");
-            println!("\n🛑 Shutting down server...");
-        })
-        .await?;
+    // Ready once a relay has answered (the default search is cached by
+    // then), or after READY_TIMEOUT_SECS either way; systemd is told so
+    let ready_server = server.clone();
+    tokio::spawn(async move {
+        let status = match ready_server.jobs().warm_up(ready_server.config().ready_timeout).await {
+            Ok(listings) => format!("Relays answering, {} listings cached", listings),
+            Err(e) => {
+                tracing::warn!(error = %e, "warm_up_failed");
+                format!("Serving without a relay answer yet ({})", e)
+            }
+        };
+        println!("🟢 Ready: {}", status);
+        systemd::notify(&format!("READY=1\nSTATUS={}", status));
+    });
+    // The watchdog is only fed while the listener and background loops are
+    // alive, so systemd restarts a wedged server
+    let heartbeat = systemd::Heartbeat::default();
+    if let Some(interval) = systemd::watchdog_interval() {
+        let heartbeat = heartbeat.clone();
+        let watched = all_servers.clone();
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+            loop {
+                ticks.tick().await;
+                let tasks: Vec<_> = watched.iter().flat_map(|s| s.jobs().task_reports()).collect();
+                match systemd::liveness(&heartbeat, interval, &tasks) {
+                    Ok(()) => {
+                        systemd::notify("WATCHDOG=1");
+                    }
+                    Err(reason) => tracing::warn!(reason = %reason, "watchdog_withheld"),
+                }
+            }
+        });
+    }
+
+    // On Ctrl+C/SIGTERM: refuse new sessions, drain in-flight tool calls,
+    // disconnect relays, then stop the listener.
+    let stopped = CancellationToken::new();
+    let shutdown_done = stopped.clone();
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        systemd::notify("STOPPING=1");
+        println!("\n🛑 Shutting down server (draining in-flight requests)...");
+        futures::future::join_all(all_servers.iter().map(NostrJobsServer::shutdown)).await;
+        shutdown_done.cancel();
+    });
+
+    serve(listeners, app, &http_config, stopped, heartbeat).await;
     
     println!("✅ Server stopped");
     Ok(())
@@ -1,6 +1,7 @@
 // src/mcp_server.rs
-// Standalone MCP Server for Nostr Job Listings (Kind 9993) with Performance Metrics
+// Standalone MCP Server for Nostr Job Listings (Kind 9993 by default) with Performance Metrics
 
+use std::net::IpAddr;
 use std::sync::Arc;
 use std::time::Duration;
 use nostr_sdk::prelude::*;
@@ -8,202 +9,340 @@ use rmcp::{
     ErrorData as McpError, RoleServer, ServerHandler,
     handler::server::{
         router::{prompt::PromptRouter, tool::ToolRouter},
+        tool::ToolCallContext,
         wrapper::Parameters,
     },
     model::*,
     prompt, prompt_handler, prompt_router, schemars,
-    service::RequestContext,
-    tool, tool_handler, tool_router,
+    service::{NotificationContext, Peer, RequestContext},
+    tool, tool_router,
 };
 use serde_json::json;
 use tokio::sync::{Mutex, RwLock};
-use tokio::time::timeout;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::auth::{self, Role};
+use crate::builder::NostrJobsServerBuilder;
+use crate::config::ServerConfig;
+use crate::cursors::{SEARCH_CURSOR_CAPACITY, SearchCursor, SearchCursors};
+use crate::i18n::{self, Locale};
+use crate::diagnostics::{AuditEntry, AuditLog, SlowQuery, SlowQueryLog, hash_args, traced};
+use crate::jobs::bulk::{BulkFormat, BulkReport, RowOutcome};
+use crate::jobs::career::CareerEvent;
+use crate::jobs::company::{self, CompanyTransparency, IdentityConflict, PosterIdentity};
+use crate::jobs::reputation::{Reputation, SourceKind};
+use crate::jobs::scam::ScamAssessment;
+use crate::jobs::digest::{Digest, DigestEntry, DigestPeriod};
+use crate::jobs::engagement::{Interaction, ListingAnalytics};
+use crate::jobs::geo::{self, DEFAULT_RADIUS_KM, GeoPoint, GeoRadius};
+use crate::jobs::MAX_EXPIRY_DAYS;
+use crate::jobs::ingest::{MAX_YEAR, days_from_civil, parse_rfc3339};
+use crate::jobs::interview::{Interview, InterviewPlan};
+use crate::jobs::market::{self, MarketSide, SkillBalance};
+use crate::jobs::rates::{self, ExchangeRate};
+use crate::jobs::snapshot;
+use crate::jobs::trends::HiringTrends;
+use crate::jobs::{
+    DEFAULT_FUZZY_DISTANCE, DEFAULT_STATS_SAMPLE_SIZE, GEO_CELL_PRECISION, GeoDistribution, InterestProfile, JobDraft, JobListing, JobQuery, JobStats,
+    JobsError, ListingSource, ListingStatus, MAX_STATS_SAMPLE_SIZE, NostrJobsClient, PartialResults, PostingHistory, QueryStrategy, RelayReport, SalaryFloor, SalaryRange, SearchResults, SkillPair, Source, TagUsage, skill_pairs, slug, tag_vocabulary, with_call_timeout,
+};
+use crate::dashboard::{self, RecentCalls, StatusSnapshot};
+use crate::plain_text;
+use crate::proxy;
+use crate::quota::{Quotas, UsageLog};
+use crate::shutdown::{Drain, DrainGuard};
+use crate::state::{
+    Application, ApplicationStatus, Bookmark, CandidateProfile, FollowedEmployer, SavedSearch, StateStore, StatusChange, ViewedJob,
+};
 
 // ==================== Configuration ====================
 
-#[allow(dead_code)]
-const RELAY_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
-const RELAY_FETCH_TIMEOUT: Duration = Duration::from_secs(2);
-const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
-
-// ==================== Performance Metrics ====================
-
-#[derive(Clone, Debug, Default)]
-struct PerformanceMetrics {
-    total_requests: usize,
-    cache_hits: usize,
-    cache_misses: usize,
-    relay_fetches: usize,
-    failed_fetches: usize,
-    total_fetch_time_ms: u128,
-    total_cache_time_ms: u128,
-    fastest_fetch_ms: Option<u128>,
-    slowest_fetch_ms: Option<u128>,
-    fastest_cache_ms: Option<u128>,
-    slowest_cache_ms: Option<u128>,
-}
-
-impl PerformanceMetrics {
-    fn record_cache_hit(&mut self, duration_ms: u128) {
-        self.total_requests += 1;
-        self.cache_hits += 1;
-        self.total_cache_time_ms += duration_ms;
-        
-        self.fastest_cache_ms = Some(
-            self.fastest_cache_ms.map_or(duration_ms, |f| f.min(duration_ms))
-        );
-        self.slowest_cache_ms = Some(
-            self.slowest_cache_ms.map_or(duration_ms, |s| s.max(duration_ms))
-        );
-    }
+const DEFAULT_COMPARE_WINDOW_DAYS: u64 = 30;
+/// Ten years; longer windows reach past the Unix epoch
+const MAX_COMPARE_WINDOW_DAYS: u64 = 3650;
+/// A day; longer interviews are a mistake in the arguments
+const MAX_INTERVIEW_MINUTES: u64 = 24 * 60;
+/// Weeks of history the `hiring_trends_forecast` prompt embeds
+const DEFAULT_TREND_WEEKS: usize = 8;
+const MAX_TREND_WEEKS: usize = 26;
+/// Listings per page of `jobs://latest`, unless `?limit=` says otherwise
+const DEFAULT_LATEST_LIMIT: usize = 20;
+const MAX_LATEST_LIMIT: usize = 100;
+/// Recent and slow calls listed on the `/status` page
+const STATUS_RECENT_CALLS: usize = 20;
+/// How much a view and a bookmark count towards recommendations
+const VIEW_WEIGHT: f64 = 1.0;
+const BOOKMARK_WEIGHT: f64 = 2.0;
+/// Most recent bookmarks looked up to build a recommendation profile
+const RECOMMEND_BOOKMARK_LOOKUPS: usize = 20;
+/// Longest deadline a call may ask for with `timeout_ms`
+pub const MAX_CALL_TIMEOUT: Duration = Duration::from_secs(60);
 
-    fn record_cache_miss(&mut self, duration_ms: u128, success: bool) {
-        self.total_requests += 1;
-        self.cache_misses += 1;
-        
-        if success {
-            self.relay_fetches += 1;
-            self.total_fetch_time_ms += duration_ms;
-            
-            self.fastest_fetch_ms = Some(
-                self.fastest_fetch_ms.map_or(duration_ms, |f| f.min(duration_ms))
-            );
-            self.slowest_fetch_ms = Some(
-                self.slowest_fetch_ms.map_or(duration_ms, |s| s.max(duration_ms))
-            );
-        } else {
-            self.failed_fetches += 1;
-        }
-    }
+// ==================== Request/Response Types ====================
 
-    fn cache_hit_rate(&self) -> f64 {
-        if self.total_requests == 0 {
-            0.0
-        } else {
-            (self.cache_hits as f64 / self.total_requests as f64) * 100.0
-        }
-    }
+/// A filter value given either as a single string or as a list (any-of)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(untagged)]
+pub enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
 
-    fn avg_cache_time(&self) -> f64 {
-        if self.cache_hits == 0 {
-            0.0
-        } else {
-            self.total_cache_time_ms as f64 / self.cache_hits as f64
-        }
+impl OneOrMany {
+    /// Trimmed, de-quoted, non-empty values
+    pub fn values(&self) -> Vec<String> {
+        let raw: Vec<&String> = match self {
+            OneOrMany::One(v) => vec![v],
+            OneOrMany::Many(vs) => vs.iter().collect(),
+        };
+        raw.into_iter()
+            .map(|v| v.trim().trim_matches('"').to_string())
+            .filter(|v| !v.is_empty())
+            .collect()
     }
+}
 
-    fn avg_fetch_time(&self) -> f64 {
-        if self.relay_fetches == 0 {
-            0.0
-        } else {
-            self.total_fetch_time_ms as f64 / self.relay_fetches as f64
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+pub struct SearchJobsArgs {
+    /// Company name, or a list of names (matches any)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub company: Option<OneOrMany>,
+    
+    /// Skill, or a list of skills (matches any)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill: Option<OneOrMany>,
+    
+    /// Employment type, or a list of types (matches any)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub employment_type: Option<OneOrMany>,
+
+    /// Only listings posted by this public key (hex or npub)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+
+    /// Place name to search around (resolved by the geocoder); ignored when lat and lon are given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub near: Option<String>,
+
+    /// Latitude of the search center, in degrees
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lat: Option<f64>,
+
+    /// Longitude of the search center, in degrees
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lon: Option<f64>,
+
+    /// Search radius around near or lat/lon, in km (default 25)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub radius_km: Option<f64>,
+
+    /// Minimum salary (top of the listed range) in your preferred currency; sats/BTC salaries are converted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_salary: Option<f64>,
+    
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+
+    /// Also match filter values within a small edit distance (typo tolerance)
+    #[serde(default)]
+    pub fuzzy: bool,
+
+    /// Maximum edit distance used for fuzzy matching and suggestions (default 2).
+    /// Fuzzy matching allows none for values up to 3 characters and one up to 6.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_edit_distance: Option<usize>,
+
+    /// Leave out listings that look like scams (pay-to-apply, messenger-only contact, ...)
+    #[serde(default)]
+    pub hide_suspicious: bool,
+
+    /// Event kinds (e.g. 9993, 30402) and "ingested" for listings from the ingestion bridge (matches any); default every kind the board serves
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kinds: Option<Vec<KindArg>>,
+}
+
+/// An event kind, given as a number or a string, or "ingested"
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(untagged)]
+pub enum KindArg {
+    Number(u16),
+    Name(String),
+}
+
+impl KindArg {
+    fn parse(&self) -> Result<ListingSource, JobsError> {
+        match self {
+            KindArg::Number(kind) => Ok(ListingSource::Kind(*kind)),
+            KindArg::Name(name) => name.parse().map_err(|message| JobsError::ParseError { what: "listing source", input: name.clone(), message }),
         }
     }
+}
 
-    fn time_saved_ms(&self) -> u128 {
-        if self.cache_hits == 0 || self.relay_fetches == 0 {
-            return 0;
-        }
-        
-        let avg_fetch = self.avg_fetch_time();
-        let avg_cache = self.avg_cache_time();
-        let time_saved_per_hit = (avg_fetch - avg_cache).max(0.0);
-        
-        (time_saved_per_hit * self.cache_hits as f64) as u128
+impl SearchJobsArgs {
+    fn radius_km(&self) -> f64 {
+        self.radius_km.filter(|r| *r > 0.0).unwrap_or(DEFAULT_RADIUS_KM)
     }
+}
 
-    fn format_report(&self) -> String {
-        format!(
-            "📊 Performance Metrics Report\n\
-            ═══════════════════════════════════════════════════════════\n\n\
-            🔢 Request Statistics:\n\
-            • Total Requests: {}\n\
-            • Cache Hits: {} ({}%)\n\
-            • Cache Misses: {}\n\
-            • Relay Fetches: {}\n\
-            • Failed Fetches: {}\n\n\
-            ⚡ Cache Performance:\n\
-            • Average Cache Response: {:.2}ms\n\
-            • Fastest Cache Hit: {}ms\n\
-            • Slowest Cache Hit: {}ms\n\n\
-            🌐 Relay Performance:\n\
-            • Average Relay Fetch: {:.2}ms\n\
-            • Fastest Fetch: {}ms\n\
-            • Slowest Fetch: {}ms\n\n\
-            💡 Performance Gains:\n\
-            • Cache Hit Rate: {:.1}%\n\
-            • Time Saved by Cache: {:.2}s\n\
-            • Speed Improvement: {:.1}x faster with cache\n\n\
-            📈 Efficiency Metrics:\n\
-            • Relay Load Reduction: {:.1}%\n\
-            • Success Rate: {:.1}%",
-            self.total_requests,
-            self.cache_hits,
-            self.cache_hit_rate(),
-            self.cache_misses,
-            self.relay_fetches,
-            self.failed_fetches,
-            self.avg_cache_time(),
-            self.fastest_cache_ms.unwrap_or(0),
-            self.slowest_cache_ms.unwrap_or(0),
-            self.avg_fetch_time(),
-            self.fastest_fetch_ms.unwrap_or(0),
-            self.slowest_fetch_ms.unwrap_or(0),
-            self.cache_hit_rate(),
-            self.time_saved_ms() as f64 / 1000.0,
-            if self.avg_cache_time() > 0.0 { 
-                self.avg_fetch_time() / self.avg_cache_time() 
-            } else { 
-                1.0 
-            },
-            if self.total_requests > 0 {
-                (self.cache_hits as f64 / self.total_requests as f64) * 100.0
-            } else {
-                0.0
-            },
-            if self.total_requests > 0 {
-                (self.relay_fetches as f64 / self.total_requests as f64) * 100.0
-            } else {
-                0.0
+fn default_limit() -> usize {
+    crate::jobs::DEFAULT_SEARCH_LIMIT
+}
+
+impl TryFrom<&SearchJobsArgs> for JobQuery {
+    type Error = JobsError;
+
+    fn try_from(args: &SearchJobsArgs) -> Result<Self, JobsError> {
+        let distance = args.max_edit_distance.unwrap_or(DEFAULT_FUZZY_DISTANCE);
+        let author = args
+            .author
+            .as_deref()
+            .map(|author| {
+                PublicKey::parse(author.trim()).map_err(|e| JobsError::ParseError {
+                    what: "author",
+                    input: author.to_string(),
+                    message: e.to_string(),
+                })
+            })
+            .transpose()?;
+        let sources = args.kinds.iter().flatten().map(KindArg::parse).collect::<Result<Vec<_>, _>>()?;
+        let near = match (args.lat, args.lon) {
+            (Some(lat), Some(lon)) if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon) => {
+                Some(GeoRadius { center: GeoPoint { lat, lon }, radius_km: args.radius_km() })
             }
-        )
+            (None, None) => None,
+            (lat, lon) => {
+                return Err(JobsError::ParseError {
+                    what: "coordinates",
+                    input: format!("{:?}, {:?}", lat, lon),
+                    message: "expected both lat (-90..90) and lon (-180..180)".to_string(),
+                });
+            }
+        };
+        Ok(Self {
+            company: args.company.as_ref().map(OneOrMany::values).unwrap_or_default(),
+            skill: args.skill.as_ref().map(OneOrMany::values).unwrap_or_default(),
+            employment_type: args.employment_type.as_ref().map(OneOrMany::values).unwrap_or_default(),
+            location: Vec::new(),
+            exclude_company: Vec::new(),
+            limit: args.limit,
+            fuzzy_distance: args.fuzzy.then_some(distance),
+            suggestion_distance: distance,
+            author,
+            near,
+            min_salary: None,
+            preferred_authors: Vec::new(),
+            sources,
+            bridge: None,
+        })
     }
 }
 
-// ==================== Cache Types ====================
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct GetStatsArgs {
+    /// Number of most recent listings to analyze (default 500, max 5000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_size: Option<usize>,
+}
 
-#[derive(Clone, Debug)]
-struct CachedEvents {
-    events: Vec<Event>,
-    timestamp: std::time::Instant,
+#[derive(Debug, Clone, serde::Deserialize, schemars::JsonSchema)]
+pub struct TimeWindow {
+    /// Window start, as YYYY-MM-DD or a unix timestamp (inclusive)
+    pub since: String,
+    /// Window end, as YYYY-MM-DD or a unix timestamp (exclusive)
+    pub until: String,
 }
 
-impl CachedEvents {
-    fn is_fresh(&self, ttl: Duration) -> bool {
-        self.timestamp.elapsed() < ttl
-    }
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct CompareStatsArgs {
+    /// Current window (default: the last `window_days` days)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current: Option<TimeWindow>,
+
+    /// Window to compare against (default: the equally long window right before `current`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous: Option<TimeWindow>,
+
+    /// Length of the default windows in days (default 30, max 3650)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window_days: Option<u64>,
+
+    /// Maximum listings analyzed per window (default 500, max 5000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_size: Option<usize>,
 }
 
-// ==================== Request/Response Types ====================
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct MetricsArgs {
+    /// Output format: "text" (default, human-readable report) or "json" (structured)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+}
 
-#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
-pub struct SearchJobsArgs {
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct MetricsHistoryArgs {
+    /// Maximum number of periods to return, newest first (default 10)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub company: Option<String>,
-    
+    pub limit: Option<usize>,
+
+    /// Output format: "text" (default, one summary per period) or "json" (full snapshots)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub skill: Option<String>,
-    
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct SlowQueriesArgs {
+    /// Maximum number of entries to return, most recent first (default 20)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub employment_type: Option<String>,
-    
-    #[serde(default = "default_limit")]
-    pub limit: usize,
+    pub limit: Option<usize>,
 }
 
-fn default_limit() -> usize {
-    20
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct AuditLogArgs {
+    /// Maximum number of entries to return, most recent first (default 50)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+
+    /// Only include calls to this tool
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+
+    /// Only include calls from this MCP session
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct SetToolEnabledArgs {
+    /// Name of the tool to toggle
+    pub tool: String,
+    /// true to enable, false to disable
+    pub enabled: bool,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct SetQueryStrategyArgs {
+    /// pooled, parallel-merge, race-first or primary-with-fallback
+    pub strategy: String,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct ImportSnapshotArgs {
+    /// Snapshot from export_snapshot: NDJSON, one signed event per line
+    pub ndjson: String,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct BulkPublishArgs {
+    /// Listings as CSV (a header row naming the columns; skills and employment_types separated by ";") or NDJSON (one object per line). Fields: job_id, title, company, location, employment_types, skills, salary_min, salary_max, salary_currency, salary_period, description, expires_at (unix seconds) or expires_in_days
+    pub payload: String,
+
+    /// "csv" or "ndjson"; detected from the payload when omitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+
+    /// Only validate the rows, publishing nothing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<bool>,
 }
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
@@ -212,617 +351,5174 @@ pub struct GetJobArgs {
 }
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
-pub struct JobAnalysisArgs {
-    pub query: String,
-    
+pub struct BookmarkArgs {
+    pub job_id: String,
+
+    /// Optional note to keep with the bookmark
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub skills: Option<Vec<String>>,
+    pub note: Option<String>,
 }
 
-// ==================== Nostr Jobs MCP Server ====================
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct ApplicationStatusArgs {
+    /// Job ID of the listing applied to
+    pub job_id: String,
 
-#[derive(Clone, Debug)]
-pub struct NostrJobsServer {
-    client: Arc<Mutex<Client>>,
-    relays: Vec<String>,
-    cache: Arc<RwLock<HashMap<String, CachedEvents>>>,
-    relay_healthy: Arc<Mutex<bool>>,
-    metrics: Arc<RwLock<PerformanceMetrics>>,
-    pub tool_router: ToolRouter<NostrJobsServer>,
-    pub prompt_router: PromptRouter<NostrJobsServer>,
+    /// applied, replied, interviewing, rejected or offer; a new application defaults to applied, an existing one keeps its status
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+
+    /// Optional note to keep with the application (contact, next step, ...); an empty note clears it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
 }
 
-#[tool_router]
-impl NostrJobsServer {
-    pub async fn new() -> Self {
-        let client = Client::default();
-        
-        let relays = vec![
-            "wss://relay.damus.io".to_string(),
-            "wss://relay.nostr.band".to_string(),
-            "wss://nos.lol".to_string(),
-        ];
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct ListApplicationsArgs {
+    /// Only applications with this status (applied, replied, interviewing, rejected or offer)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
 
-        tracing::info!(
-            relay_count = relays.len(),
-            relays = ?relays,
-            "initializing_nostr_mcp_server"
-        );
+    /// Only open applications that are due a follow-up
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stale_only: Option<bool>,
+}
 
-        for relay in &relays {
-            let _ = client.add_relay(relay).await;
-        }
-        
-        let client_clone = client.clone();
-        tokio::spawn(async move {
-            let _ = timeout(Duration::from_secs(15), client_clone.connect()).await;
-        });
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct FollowEmployerArgs {
+    /// The job poster's public key (hex or npub)
+    pub pubkey: String,
 
-        let server = Self {
-            client: Arc::new(Mutex::new(client)),
-            relays,
-            cache: Arc::new(RwLock::new(HashMap::new())),
-            relay_healthy: Arc::new(Mutex::new(false)),
-            metrics: Arc::new(RwLock::new(PerformanceMetrics::default())),
-            tool_router: Self::tool_router(),
-            prompt_router: Self::prompt_router(),
-        };
-
-        let server_clone = server.clone();
-        tokio::spawn(async move {
-            server_clone.health_check_loop().await;
-        });
+    /// Optional name to show for this employer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
 
-        tracing::info!("nostr_mcp_server_initialized");
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct EmployerArgs {
+    /// The job poster's public key (hex or npub)
+    pub pubkey: String,
+}
 
-        server
-    }
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct DraftJobArgs {
+    /// Job ID of the draft to edit; omit to start a new draft (its Job ID is derived from the title)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_id: Option<String>,
 
-    async fn health_check_loop(&self) {
-        loop {
-            tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
-            
-            let client = self.client.lock().await;
-            let filter = Filter::new().limit(1);
-            
-            match timeout(Duration::from_secs(5), client.fetch_events(filter, Duration::from_secs(3))).await {
-                Ok(Ok(_)) => {
-                    let was_healthy = *self.relay_healthy.lock().await;
-                    *self.relay_healthy.lock().await = true;
-                    
-                    if !was_healthy {
-                        tracing::info!("relay_health_recovered");
-                    }
-                }
-                _ => {
-                    let was_healthy = *self.relay_healthy.lock().await;
-                    *self.relay_healthy.lock().await = false;
-                    
-                    if was_healthy {
-                        tracing::warn!("relay_health_degraded");
-                    }
-                }
-            }
-        }
-    }
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
 
-    // ==================== Helper Methods ====================
+    /// Company name; "" clears
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub company: Option<String>,
 
-    fn format_job_summary(&self, event: &Event) -> String {
-        let tags: Vec<_> = event.tags.iter().collect();
-        
-        let title = Self::find_tag_value(&tags, "title").unwrap_or_else(|| "Untitled".to_string());
-        let company = Self::find_tag_value(&tags, "company").unwrap_or_else(|| "Unknown".to_string());
-        let location = Self::find_tag_value(&tags, "location").unwrap_or_else(|| "Remote".to_string());
-        let job_id = Self::find_tag_value(&tags, "job-id").unwrap_or_else(|| event.id.to_hex());
-        
-        let skills: Vec<_> = tags
-            .iter()
-            .filter_map(|t| {
-                let slice = t.as_slice();
-                if slice.len() >= 2 && slice[0] == "skill" {
-                    Some(slice[1].to_string())
-                } else {
-                    None
-                }
-            })
-            .collect();
+    /// Location, e.g. a city or "Remote"; "" clears
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
 
-        let employment_types: Vec<_> = tags
-            .iter()
-            .filter_map(|t| {
-                let slice = t.as_slice();
-                if slice.len() >= 2 && slice[0] == "employment-type" {
-                    Some(slice[1].to_string())
-                } else {
-                    None
-                }
-            })
-            .collect();
+    /// Employment types, e.g. full-time, contract; [] clears
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub employment_types: Option<Vec<String>>,
 
-        let salary = tags.iter().find(|t| {
-            let slice = t.as_slice();
-            !slice.is_empty() && slice[0] == "salary"
-        }).and_then(|tag| {
-            let slice = tag.as_slice();
-            if slice.len() >= 5 {
-                Some(format!("${} - ${} {} per {}", slice[1], slice[2], slice[3], slice[4]))
-            } else {
-                None
-            }
-        });
+    /// Required skills; [] clears
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skills: Option<Vec<String>>,
 
-        format!(
-            "🏢 {} - {}\n📍 Location: {}\n💼 Type: {}\n🛠️  Skills: {}\n{}\n🆔 Job ID: {}\n📅 Posted: {}",
-            company,
-            title,
-            location,
-            if employment_types.is_empty() { "Not specified".to_string() } else { employment_types.join(", ") },
-            if skills.is_empty() { "Not specified".to_string() } else { skills.join(", ") },
-            salary.map(|s| format!("💰 Salary: {}", s)).unwrap_or_default(),
-            job_id,
-            event.created_at.to_human_datetime()
-        )
-    }
+    /// Lower end of the salary range; 0 removes the salary
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub salary_min: Option<f64>,
 
-    fn find_tag_value(tags: &[&Tag], name: &str) -> Option<String> {
-        tags.iter().find_map(|t| {
-            let slice = t.as_slice();
-            if slice.len() >= 2 && slice[0] == name {
-                Some(slice[1].to_string())
-            } else {
-                None
-            }
-        })
-    }
+    /// Upper end of the salary range (defaults to salary_min)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub salary_max: Option<f64>,
 
-    fn build_filter(_company: Option<&str>, _skill: Option<&str>, _employment_type: Option<&str>, _limit: usize) -> Filter {
-        Filter::new()
-            .kind(Kind::from(9993u16))
-            .limit(100)
-    }
+    /// Salary currency, e.g. USD, EUR, SATS (default USD)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub salary_currency: Option<String>,
 
-    fn cache_key(company: Option<&str>, skill: Option<&str>, employment_type: Option<&str>, limit: usize) -> String {
-        format!("{}:{}:{}:{}", 
-            company.unwrap_or("*"),
-            skill.unwrap_or("*"),
-            employment_type.unwrap_or("*"),
-            limit
-        )
-    }
+    /// Salary period, e.g. year, month, hour (default year)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub salary_period: Option<String>,
 
-    async fn fetch_events_fast(
-        &self,
-        filter: Filter,
-        cache_key: String,
-    ) -> Result<Vec<Event>, String> {
-        let start = std::time::Instant::now();
-        let client = self.client.lock().await;
-        
-        match timeout(RELAY_FETCH_TIMEOUT, client.fetch_events(filter, Duration::from_millis(1500))).await {
-            Ok(Ok(events)) => {
-                let duration_ms = start.elapsed().as_millis();
-                let events_vec: Vec<Event> = events.into_iter().collect();
-                
-                tracing::info!(
-                    cache_key = %cache_key,
-                    duration_ms = duration_ms,
-                    event_count = events_vec.len(),
-                    source = "relay",
-                    success = true,
-                    "fetch_events_success"
-                );
-                
-                if !events_vec.is_empty() {
-                    let cache = self.cache.clone();
-                    let cached = CachedEvents {
-                        events: events_vec.clone(),
-                        timestamp: std::time::Instant::now(),
-                    };
-                    tokio::spawn(async move {
-                        cache.write().await.insert(cache_key, cached);
-                    });
-                    *self.relay_healthy.lock().await = true;
-                }
-                
-                self.metrics.write().await.record_cache_miss(duration_ms, true);
-                Ok(events_vec)
-            }
-            Ok(Err(e)) => {
-                let duration_ms = start.elapsed().as_millis();
-                
-                tracing::warn!(
-                    cache_key = %cache_key,
-                    duration_ms = duration_ms,
-                    error = %e,
-                    source = "relay",
-                    success = false,
-                    "fetch_events_error"
-                );
-                
-                self.metrics.write().await.record_cache_miss(duration_ms, false);
-                *self.relay_healthy.lock().await = false;
-                Err(format!("Fetch error: {}", e))
-            }
-            Err(_) => {
-                let duration_ms = start.elapsed().as_millis();
-                
-                tracing::warn!(
-                    cache_key = %cache_key,
-                    duration_ms = duration_ms,
-                    source = "relay",
-                    success = false,
-                    reason = "timeout",
-                    "fetch_events_timeout"
-                );
-                
-                self.metrics.write().await.record_cache_miss(duration_ms, false);
-                *self.relay_healthy.lock().await = false;
-                Err("Relay timeout".to_string())
-            }
-        }
-    }
+    /// Full listing text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 
-    // ==================== Tools ====================
+    /// Days from now until the listing expires (NIP-40), at most 3650; 0 removes the expiration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_in_days: Option<u64>,
+}
 
-    #[tool(description = "Search for job listings on Nostr. You can filter by company, skill, or employment type.")]
-    pub async fn search_jobs(
-        &self,
-        Parameters(args): Parameters<SearchJobsArgs>,
-    ) -> Result<CallToolResult, McpError> {
-        let clean_company = args.company.as_ref().map(|s| s.trim_matches('"').to_string());
-        let clean_skill = args.skill.as_ref().map(|s| s.trim_matches('"').to_string());
-        let clean_employment_type = args.employment_type.as_ref().map(|s| s.trim_matches('"').to_string());
-        
-        let filter = Self::build_filter(
-            clean_company.as_deref(),
-            clean_skill.as_deref(),
-            clean_employment_type.as_deref(),
-            args.limit,
-        );
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct DraftIdArgs {
+    /// Job ID of a draft created with draft_job
+    pub job_id: String,
+}
 
-        let key = Self::cache_key(
-            clean_company.as_deref(),
-            clean_skill.as_deref(),
-            clean_employment_type.as_deref(),
-            args.limit,
-        );
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct EmployerAnalyticsArgs {
+    /// UTC days to cover, today included (default 7, max 30)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days: Option<u64>,
+}
 
-        // Check cache first
-        {
-            let start = std::time::Instant::now();
-            let cache = self.cache.read().await;
-            if let Some(cached) = cache.get(&key) {
-                let duration_ms = start.elapsed().as_millis();
-                let is_fresh = cached.is_fresh(Duration::from_secs(60));
-                
-                tracing::info!(
-                    cache_key = %key,
-                    duration_ms = duration_ms,
-                    event_count = cached.events.len(),
-                    source = "cache",
-                    is_fresh = is_fresh,
-                    "cache_hit"
-                );
-                
-                self.metrics.write().await.record_cache_hit(duration_ms);
-                
-                let mut results = format!("Found {} job listing(s){}:\n\n", 
-                    cached.events.len(),
-                    if is_fresh { " ⚡ [CACHED]" } else { " 📦 [CACHED - STALE]" }
-                );
-                for (i, event) in cached.events.iter().enumerate() {
-                    results.push_str(&format!("{}. {}\n\n", i + 1, self.format_job_summary(event)));
-                }
-                return Ok(CallToolResult::success(vec![Content::text(results)]));
-            } else {
-                tracing::debug!(
-                    cache_key = %key,
-                    "cache_miss"
-                );
-            }
-        }
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct ScheduleInterviewArgs {
+    /// Job ID of the listing the interview is for
+    pub job_id: String,
 
-        // Try fresh fetch
-        match timeout(Duration::from_millis(2500), self.fetch_events_fast(filter, key.clone())).await {
-            Ok(Ok(mut events)) => {
-                events.retain(|event| {
-                    let tags: Vec<_> = event.tags.iter().collect();
-                    
-                    let matches_company = if let Some(comp) = &clean_company {
-                        tags.iter().any(|t| {
-                            let slice = t.as_slice();
-                            slice.len() >= 2 && slice[0] == "company" && 
-                            slice[1].to_lowercase().contains(&comp.to_lowercase())
-                        })
-                    } else {
-                        true
-                    };
-                    
-                    let matches_skill = if let Some(sk) = &clean_skill {
-                        tags.iter().any(|t| {
-                            let slice = t.as_slice();
-                            slice.len() >= 2 && slice[0] == "skill" && 
-                            slice[1].to_lowercase().contains(&sk.to_lowercase())
-                        })
-                    } else {
-                        true
-                    };
-                    
-                    let matches_employment = if let Some(et) = &clean_employment_type {
-                        tags.iter().any(|t| {
-                            let slice = t.as_slice();
-                            slice.len() >= 2 && slice[0] == "employment-type" && 
-                            slice[1].to_lowercase().contains(&et.to_lowercase())
-                        })
-                    } else {
-                        true
-                    };
-                    
-                    matches_company && matches_skill && matches_employment
-                });
-                
-                events.truncate(args.limit);
-                
-                if events.is_empty() {
-                    return Ok(CallToolResult::success(vec![Content::text(
-                        "No job listings found matching your criteria.".to_string()
-                    )]));
-                }
+    /// Start time, as RFC 3339 (2025-03-14T15:00:00+01:00) or a unix timestamp
+    pub start: String,
 
-                let mut results = format!("Found {} job listing(s) 🌐 [FRESH]:\n\n", events.len());
-                for (i, event) in events.iter().enumerate() {
-                    results.push_str(&format!("{}. {}\n\n", i + 1, self.format_job_summary(event)));
+    /// Length in minutes, at most a day (default 60; 0 leaves the end open)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_minutes: Option<u64>,
+
+    /// Public keys (hex or npub) of the candidate and interviewers
+    pub participants: Vec<String>,
+
+    /// A room, an address or a video call link
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+
+    /// Defaults to "Interview: <listing title>"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// Agenda or preparation notes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Id of an interview to reschedule; omit to schedule a new one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interview_id: Option<String>,
+}
+
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct UpcomingInterviewsArgs {
+    /// Only interviews this public key (hex or npub) is invited to; default: those the board scheduled with its posting key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub participant: Option<String>,
+
+    /// Only interviews for this Job ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_id: Option<String>,
+}
+
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct SearchCareerEventsArgs {
+    /// Words to look for in the title, summary and description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+
+    /// Only events with this hashtag (e.g. careerfair, hiring)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+
+    /// Only events whose location contains this (e.g. Berlin, online)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+
+    /// Only events starting within this many days (1-365)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub within_days: Option<u64>,
+
+    /// Maximum number of events (default 20)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+/// Query parameters on resource URIs (`jobs://latest?skill=rust&page=2`),
+/// for hosts that read resources but never call tools. Filters are as in
+/// `search_jobs`, with lists comma-separated; each resource reads the ones
+/// that apply to it.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResourceParams {
+    /// Listings or events per page
+    pub limit: Option<usize>,
+    /// 1-based page of `limit` listings (`jobs://latest`)
+    pub page: Option<usize>,
+    pub company: Option<String>,
+    pub skill: Option<String>,
+    pub employment_type: Option<String>,
+    pub author: Option<String>,
+    pub kinds: Option<String>,
+    pub fuzzy: bool,
+    /// Listings analyzed (`jobs://stats`)
+    pub sample_size: Option<usize>,
+    /// Event search words, hashtag, location and horizon (`jobs://events`)
+    pub query: Option<String>,
+    pub topic: Option<String>,
+    pub location: Option<String>,
+    pub within_days: Option<u64>,
+}
+
+impl ResourceParams {
+    /// `uri` without its query string, and the parameters in it
+    pub fn parse(uri: &str) -> Result<(&str, Self), String> {
+        let Some((path, query)) = uri.split_once('?') else {
+            return Ok((uri, Self::default()));
+        };
+        let query_uri: http::Uri = format!("/?{}", query).parse().map_err(|e: http::uri::InvalidUri| e.to_string())?;
+        let axum::extract::Query(params) = axum::extract::Query::<Self>::try_from_uri(&query_uri).map_err(|e| e.body_text())?;
+        Ok((path, params))
+    }
+
+    fn has_filters(&self) -> bool {
+        self.company.is_some() || self.skill.is_some() || self.employment_type.is_some() || self.author.is_some() || self.kinds.is_some()
+    }
+
+    /// The `search_jobs` arguments for the listing filters, wide enough for
+    /// the first `limit` matches
+    fn search_args(&self, limit: usize) -> SearchJobsArgs {
+        let list = |value: &Option<String>| value.as_ref().map(|v| OneOrMany::Many(v.split(',').map(str::to_string).collect()));
+        SearchJobsArgs {
+            company: list(&self.company),
+            skill: list(&self.skill),
+            employment_type: list(&self.employment_type),
+            author: self.author.clone(),
+            near: None,
+            lat: None,
+            lon: None,
+            radius_km: None,
+            min_salary: None,
+            limit,
+            fuzzy: self.fuzzy,
+            max_edit_distance: None,
+            hide_suspicious: false,
+            kinds: self.kinds.as_ref().map(|v| v.split(',').map(|kind| KindArg::Name(kind.to_string())).collect()),
+        }
+    }
+}
+
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct ExpiringJobsArgs {
+    /// How many days ahead to look (default EXPIRY_REMINDER_DAYS, 3 unless configured)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub within_days: Option<u64>,
+}
+
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct TagVocabularyArgs {
+    /// Number of most recent listings to analyze (default 500, max 5000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_size: Option<usize>,
+
+    /// Example values per tag, most common first (default 3, max 10)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<usize>,
+
+    /// Leave out tags on fewer listings than this (default 1)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_listings: Option<usize>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct CountByTagArgs {
+    /// Tag name to group by, e.g. "location", "remote", "currency", "language"
+    pub tag: String,
+
+    /// Number of most recent listings to analyze (default 500, max 5000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_size: Option<usize>,
+
+    /// Maximum number of values to list, most common first (default 20)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct SkillCooccurrenceArgs {
+    /// Only the skills listed together with this one (case-insensitive); all pairs when omitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill: Option<String>,
+
+    /// Number of most recent listings to analyze (default 500, max 5000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_size: Option<usize>,
+
+    /// Leave out pairs found in fewer listings than this (default 2)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_listings: Option<usize>,
+
+    /// Maximum number of pairs to list, most common first (default 20)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct MarketBalanceArgs {
+    /// Only this skill (case-insensitive); every skill when omitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill: Option<String>,
+
+    /// Number of most recent listings, and of "for hire" notes, to analyze (default 500, max 5000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_size: Option<usize>,
+
+    /// Maximum number of skills to list, most active first (default 20)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct EmployerListingsArgs {
+    /// The job poster's public key (hex or npub)
+    pub pubkey: String,
+
+    /// Maximum listings to fetch, most recent first (default 500, max 5000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_size: Option<usize>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct CompanyProfileArgs {
+    /// Company name; spellings differing in case, punctuation or legal form (Inc, Ltd, GmbH, ...) are the same company
+    pub company: String,
+
+    /// Number of most recent listings to look through (default 500, max 5000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_size: Option<usize>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct MatchResumeArgs {
+    /// Resume or CV as plain text
+    pub resume: String,
+
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct RecommendJobsArgs {
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct SaveSearchArgs {
+    /// Name to save the search under (replaces an existing search with the same name)
+    pub name: String,
+
+    #[serde(flatten)]
+    pub search: SearchJobsArgs,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct SavedSearchArgs {
+    pub name: String,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct SetPreferenceArgs {
+    pub key: String,
+
+    /// Any JSON value; omit to remove the preference
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct SetPreferencesArgs {
+    /// Skills searched for when search_jobs is given none; [] clears
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skills: Option<Vec<String>>,
+
+    /// Minimum salary, in your display currency, when search_jobs is given none; 0 clears
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_salary: Option<f64>,
+
+    /// Locations (any-of, matched against the listing's location) when search_jobs is given none; [] clears
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locations: Option<Vec<String>>,
+
+    /// Companies whose listings are never shown in searches or recommendations; [] clears
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excluded_companies: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct JobAnalysisArgs {
+    pub query: String,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skills: Option<Vec<String>>,
+
+    /// Language of the prompt (en, es, de); defaults to the client's Accept-Language, else the server's
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+}
+
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct MarketAnalysisArgs {
+    /// Language of the prompt (en, es, de); defaults to the client's Accept-Language, else the server's
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+}
+
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct HiringTrendsArgs {
+    /// Number of weeks of history, the current one included (default 8, 2 to 26)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weeks: Option<usize>,
+
+    /// Maximum listings analyzed (default 500, max 5000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_size: Option<usize>,
+
+    /// Language of the prompt (en, es, de); defaults to the client's Accept-Language, else the server's
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+}
+
+/// Applies a tracing filter directive at runtime; installed by main.rs,
+/// which owns the subscriber
+#[derive(Clone)]
+struct LogLevelHook(Arc<LogLevelFn>);
+
+type LogLevelFn = dyn Fn(&str) -> Result<(), String> + Send + Sync;
+
+impl std::fmt::Debug for LogLevelHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("LogLevelHook")
+    }
+}
+
+// ==================== Nostr Jobs MCP Server ====================
+
+#[derive(Clone, Debug)]
+pub struct NostrJobsServer {
+    /// Relay fetching, caching and parsing; this type adds the MCP surface
+    jobs: NostrJobsClient,
+    log_level_hook: Arc<std::sync::RwLock<Option<LogLevelHook>>>,
+    /// In-flight tool calls, drained on shutdown
+    drain: Drain,
+    /// Bookmarks, saved searches and preferences per user or session
+    state: Arc<StateStore>,
+    slow_queries: Arc<RwLock<SlowQueryLog>>,
+    /// Searches cut short by their limit, behind `jobs://search/{cursor}`
+    search_cursors: Arc<Mutex<SearchCursors>>,
+    audit_log: Arc<AuditLog>,
+    /// Today's tool calls and relay fetches per API key, for quotas
+    usage: Arc<Mutex<UsageLog>>,
+    /// Connected client peers, for server-initiated notifications
+    peers: Arc<Mutex<Vec<Peer<RoleServer>>>>,
+    pub tool_router: Arc<RwLock<ToolRouter<NostrJobsServer>>>,
+    pub prompt_router: PromptRouter<NostrJobsServer>,
+}
+
+#[tool_router]
+impl NostrJobsServer {
+    /// Server configured from the environment
+    pub async fn new() -> Result<Self, JobsError> {
+        Self::with_config(ServerConfig::from_env()).await
+    }
+
+    pub async fn with_config(config: ServerConfig) -> Result<Self, JobsError> {
+        Self::builder().config(config).build().await
+    }
+
+    /// Configure relays, caching, timeouts and signing in code
+    pub fn builder() -> NostrJobsServerBuilder {
+        NostrJobsServerBuilder::default()
+    }
+
+    pub(crate) fn from_parts(config: ServerConfig, jobs: NostrJobsClient) -> Self {
+        tracing::info!(
+            tenant = config.tenant.as_deref().unwrap_or("-"),
+            relay_count = config.relays.len(),
+            relays = ?config.relays,
+            kinds = ?config.kinds,
+            "initializing_nostr_mcp_server"
+        );
+
+        let server = Self {
+            slow_queries: Arc::new(RwLock::new(SlowQueryLog::new(config.slow_query_log_size))),
+            search_cursors: Arc::new(Mutex::new(SearchCursors::new(SEARCH_CURSOR_CAPACITY))),
+            audit_log: Arc::new(AuditLog::new(config.audit_log_path.clone())),
+            usage: Arc::new(Mutex::new(UsageLog::default())),
+            peers: Arc::new(Mutex::new(Vec::new())),
+            tool_router: Arc::new(RwLock::new(Self::enabled_tool_router(&config.disabled_tools))),
+            prompt_router: Self::enabled_prompt_router(&config.disabled_prompts),
+            state: Arc::new(StateStore::load(config.state_path.clone())),
+            jobs,
+            log_level_hook: Arc::new(std::sync::RwLock::new(None)),
+            drain: Drain::default(),
+        };
+
+        let state = server.state.clone();
+        let session_ttl = server.config().session_ttl;
+        server.jobs.supervise_task("state_flush", move || {
+            let state = state.clone();
+            async move { state.flush_loop(session_ttl).await }
+        });
+
+        if server.config().admin_tokens.is_empty() {
+            tracing::warn!(
+                admin_tools = ?auth::ADMIN_TOOLS,
+                "admin_tools_unprotected: set ADMIN_TOKENS to restrict them"
+            );
+        }
+
+        tracing::info!("nostr_mcp_server_initialized");
+
+        server
+    }
+
+    /// The full tool router minus the tools disabled in config
+    fn enabled_tool_router(disabled: &[String]) -> ToolRouter<Self> {
+        let mut router = Self::tool_router();
+        for name in disabled {
+            if router.has_route(name) {
+                router.remove_route(name);
+                tracing::info!(tool = %name, "tool_disabled");
+            } else {
+                tracing::warn!(tool = %name, "unknown_tool_in_disabled_tools");
+            }
+        }
+        router
+    }
+
+    /// Enable or disable a tool at runtime. Returns whether the enabled set
+    /// changed; connected clients are told to refresh their tool list if so.
+    pub async fn set_tool_enabled_at_runtime(&self, name: &str, enabled: bool) -> Result<bool, String> {
+        let full = Self::tool_router();
+        let Some(route) = full.map.get(name) else {
+            return Err(format!("Unknown tool '{}'", name));
+        };
+
+        let changed = {
+            let mut router = self.tool_router.write().await;
+            match (enabled, router.has_route(name)) {
+                (true, false) => {
+                    router.add_route(route.clone());
+                    true
+                }
+                (false, true) => {
+                    router.remove_route(name);
+                    true
+                }
+                _ => false,
+            }
+        };
+
+        if changed {
+            tracing::info!(tool = %name, enabled = enabled, "tool_toggled");
+            self.notify_tool_list_changed().await;
+        }
+        Ok(changed)
+    }
+
+    /// Send `notifications/tools/list_changed` to every connected client,
+    /// dropping peers whose transport has closed.
+    async fn notify_tool_list_changed(&self) {
+        let mut peers = self.peers.lock().await;
+        peers.retain(|peer| !peer.is_transport_closed());
+
+        let mut notified = 0;
+        for peer in peers.iter() {
+            match peer.notify_tool_list_changed().await {
+                Ok(()) => notified += 1,
+                Err(e) => tracing::debug!(error = %e, "tool_list_changed_notify_failed"),
+            }
+        }
+        tracing::info!(peers = notified, "tool_list_changed_notified");
+    }
+
+    /// The full prompt router minus the prompts disabled in config
+    fn enabled_prompt_router(disabled: &[String]) -> PromptRouter<Self> {
+        let mut router = Self::prompt_router();
+        for name in disabled {
+            if router.has_route(name) {
+                router.remove_route(name);
+                tracing::info!(prompt = %name, "prompt_disabled");
+            } else {
+                tracing::warn!(prompt = %name, "unknown_prompt_in_disabled_prompts");
+            }
+        }
+        router
+    }
+
+    // ==================== Configuration Reload ====================
+
+    /// Snapshot of the current configuration
+    pub fn config(&self) -> Arc<ServerConfig> {
+        self.jobs.config()
+    }
+
+    /// The job board core this server exposes over MCP
+    pub fn jobs(&self) -> &NostrJobsClient {
+        &self.jobs
+    }
+
+    /// Install the callback used to apply `LOG_LEVEL`, applying the current
+    /// value immediately if one is configured.
+    pub fn set_log_level_hook<F>(&self, hook: F)
+    where
+        F: Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+    {
+        let hook = LogLevelHook(Arc::new(hook));
+        if let Some(level) = &self.config().log_level
+            && let Err(e) = (hook.0)(level)
+        {
+            tracing::warn!(level = %level, error = %e, "invalid_log_level");
+        }
+        *self.log_level_hook.write().expect("log level hook lock poisoned") = Some(hook);
+    }
+
+    /// Re-read the env file and apply what can change at runtime: relays,
+    /// cache TTLs, slow query settings, admin tokens, disabled tools and the
+    /// log level. Sessions and the HTTP listener are untouched. Returns a
+    /// human-readable summary of what changed.
+    pub async fn reload_config(&self) -> String {
+        let old = self.config();
+        let new = match ServerConfig::reload(old.tenant.as_deref()) {
+            Ok(config) => Arc::new(config),
+            Err(e) => {
+                tracing::warn!(error = %e, "config_reload_failed");
+                return format!("Configuration reload failed: {}", e);
+            }
+        };
+        let mut changes = Vec::new();
+
+        let removed: Vec<&String> = old.relays.iter().filter(|r| !new.relays.contains(r)).collect();
+        let added: Vec<&String> = new.relays.iter().filter(|r| !old.relays.contains(r)).collect();
+        if !removed.is_empty() || !added.is_empty() {
+            self.jobs.update_relays(&added, &removed).await;
+            changes.push(format!(
+                "relays: +{} -{} (now {})",
+                added.len(),
+                removed.len(),
+                new.relays.join(", ")
+            ));
+        }
+
+        if old.kinds != new.kinds || old.authors != new.authors {
+            // Cached results were fetched with the old filter
+            self.jobs.clear_cache().await;
+            changes.push(format!(
+                "listing filter: kinds {:?}, {} allowed author(s); cache cleared",
+                new.kinds,
+                new.authors.len()
+            ));
+        }
+
+        if old.search_cache_ttl != new.search_cache_ttl || old.stats_cache_ttl != new.stats_cache_ttl {
+            changes.push(format!(
+                "cache TTLs: search {}s, stats {}s",
+                new.search_cache_ttl.as_secs(),
+                new.stats_cache_ttl.as_secs()
+            ));
+        }
+        if old.offline != new.offline {
+            changes.push(format!("offline mode: {}", if new.offline { "on" } else { "off" }));
+        }
+        if old.adaptive_timeouts != new.adaptive_timeouts {
+            changes.push(format!("adaptive timeouts: {}", if new.adaptive_timeouts { "on" } else { "off" }));
+        }
+        if old.relay_timeout != new.relay_timeout
+            || old.search_timeout != new.search_timeout
+            || old.stats_timeout != new.stats_timeout
+        {
+            changes.push(format!(
+                "timeouts: relay {}ms, search {}ms, stats {}s",
+                new.relay_timeout.as_millis(),
+                new.search_timeout.as_millis(),
+                new.stats_timeout.as_secs()
+            ));
+        }
+        if old.tool_timeouts != new.tool_timeouts {
+            let mut timeouts: Vec<String> =
+                new.tool_timeouts.iter().map(|(tool, t)| format!("{}={}ms", tool, t.as_millis())).collect();
+            timeouts.sort();
+            changes.push(format!("tool timeouts: [{}]", timeouts.join(", ")));
+        }
+        if old.query_strategy != new.query_strategy {
+            changes.push(format!("relay query strategy: {}", new.query_strategy));
+        }
+        if old.slow_query_threshold_ms != new.slow_query_threshold_ms {
+            changes.push(format!("slow query threshold: {}ms", new.slow_query_threshold_ms));
+        }
+        if old.slow_query_log_size != new.slow_query_log_size {
+            self.slow_queries.write().await.resize(new.slow_query_log_size);
+            changes.push(format!("slow query log size: {}", new.slow_query_log_size));
+        }
+        if old.cache_warn_bytes != new.cache_warn_bytes {
+            changes.push(format!("cache warning threshold: {}", format_bytes(new.cache_warn_bytes)));
+        }
+        if old.retention_max_age != new.retention_max_age
+            || old.retention_expired_grace != new.retention_expired_grace
+            || old.compaction_interval != new.compaction_interval
+        {
+            let days = |limit: Option<Duration>| limit.map_or("off".to_string(), |d| format!("{}d", d.as_secs() / 86400));
+            changes.push(format!(
+                "retention: posted within {}, expired within {}, compacted every {}s",
+                days(new.retention_max_age),
+                days(new.retention_expired_grace),
+                new.compaction_interval.as_secs()
+            ));
+        }
+        if old.publish_interval != new.publish_interval || old.publish_retries != new.publish_retries {
+            changes.push(format!(
+                "publishing: one event per relay every {}ms, {} retries",
+                new.publish_interval.as_millis(),
+                new.publish_retries
+            ));
+        }
+        if old.expiry_reminder_window != new.expiry_reminder_window
+            || old.expiry_webhook_url != new.expiry_webhook_url
+            || old.expiry_reminder_recipient != new.expiry_reminder_recipient
+        {
+            let channels: Vec<&str> = [
+                new.expiry_webhook_url.as_ref().map(|_| "webhook"),
+                new.expiry_reminder_recipient.as_ref().map(|_| "DM"),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            changes.push(format!(
+                "expiry reminders: {}d ahead, via {}",
+                new.expiry_reminder_window.as_secs() / 86400,
+                if channels.is_empty() { "nothing".to_string() } else { channels.join(" and ") }
+            ));
+        }
+        if old.career_event_topics != new.career_event_topics {
+            changes.push(format!("career event topics: {}", new.career_event_topics.join(", ")));
+        }
+        if old.for_hire_topics != new.for_hire_topics {
+            changes.push(format!("for hire topics: {}", new.for_hire_topics.join(", ")));
+        }
+        if old.locale != new.locale {
+            changes.push(format!("locale: {}", new.locale));
+        }
+        if old.plain_text != new.plain_text {
+            changes.push(format!("plain text output: {}", if new.plain_text { "on" } else { "off" }));
+        }
+        if old.application_stale_after != new.application_stale_after {
+            changes.push(format!("application follow-ups: after {}d", new.application_stale_after.as_secs() / 86400));
+        }
+        if old.admin_tokens != new.admin_tokens {
+            changes.push(format!("admin tokens: {} configured", new.admin_tokens.len()));
+        }
+        if old.api_keys != new.api_keys {
+            changes.push(format!("API keys: {} configured", new.api_keys.len()));
+        }
+
+        if old.log_level != new.log_level {
+            match (&new.log_level, &*self.log_level_hook.read().expect("log level hook lock poisoned")) {
+                (Some(level), Some(hook)) => match (hook.0)(level) {
+                    Ok(()) => changes.push(format!("log level: {}", level)),
+                    Err(e) => {
+                        tracing::warn!(level = %level, error = %e, "invalid_log_level");
+                        changes.push(format!("log level: '{}' rejected ({})", level, e));
+                    }
+                },
+                (None, _) => changes.push("log level: unset, keeping current filter".to_string()),
+                (Some(_), None) => changes.push("log level: no reload hook installed".to_string()),
+            }
+        }
+
+        let mut restart_needed = Vec::new();
+        if old.audit_log_path != new.audit_log_path {
+            restart_needed.push("AUDIT_LOG_PATH");
+        }
+        if old.disabled_prompts != new.disabled_prompts {
+            restart_needed.push("DISABLED_PROMPTS");
+        }
+        if old.max_concurrent_fetches != new.max_concurrent_fetches {
+            restart_needed.push("MAX_CONCURRENT_FETCHES");
+        }
+        if old.access_log_path != new.access_log_path {
+            restart_needed.push("ACCESS_LOG_PATH");
+        }
+        if old.access_log_max_bytes != new.access_log_max_bytes {
+            restart_needed.push("ACCESS_LOG_MAX_BYTES");
+        }
+        if old.access_log_keep != new.access_log_keep {
+            restart_needed.push("ACCESS_LOG_KEEP");
+        }
+        if old.base_path != new.base_path {
+            restart_needed.push("BASE_PATH");
+        }
+        if old.http2 != new.http2 {
+            restart_needed.push("HTTP2");
+        }
+        if old.http_keep_alive != new.http_keep_alive {
+            restart_needed.push("HTTP_KEEP_ALIVE_SECS");
+        }
+        if old.http_idle_timeout != new.http_idle_timeout {
+            restart_needed.push("HTTP_IDLE_TIMEOUT_SECS");
+        }
+
+        let tools_changed = old.disabled_tools != new.disabled_tools;
+        self.jobs.set_config(new.clone());
+        if old.offline && !new.offline {
+            self.jobs.connect_in_background();
+        }
+
+        if tools_changed {
+            // The config list is authoritative: runtime toggles are replaced
+            *self.tool_router.write().await = Self::enabled_tool_router(&new.disabled_tools);
+            self.notify_tool_list_changed().await;
+            changes.push(format!("disabled tools: [{}]", new.disabled_tools.join(", ")));
+        }
+
+        tracing::info!(changes = changes.len(), restart_needed = ?restart_needed, "config_reloaded");
+
+        let mut summary = if changes.is_empty() {
+            "Configuration reloaded: no changes".to_string()
+        } else {
+            format!(
+                "Configuration reloaded:\n{}",
+                changes.iter().map(|c| format!("  • {}", c)).collect::<Vec<_>>().join("\n")
+            )
+        };
+        if !restart_needed.is_empty() {
+            summary.push_str(&format!("\nRestart required to apply: {}", restart_needed.join(", ")));
+        }
+        summary
+    }
+
+    /// Add and remove relays at runtime, for the admin HTTP API. Not written
+    /// back to the env file, so a later `reload_config` returns to `RELAYS`.
+    /// Returns the relays now configured.
+    pub async fn update_relays(&self, add: &[String], remove: &[String]) -> Result<Vec<String>, JobsError> {
+        for relay in add {
+            RelayUrl::parse(relay).map_err(|e| JobsError::ParseError {
+                what: "relay",
+                input: relay.clone(),
+                message: e.to_string(),
+            })?;
+        }
+        let old = self.config();
+        let mut relays: Vec<String> = old.relays.iter().filter(|r| !remove.contains(r)).cloned().collect();
+        for relay in add {
+            if !relays.contains(relay) {
+                relays.push(relay.clone());
+            }
+        }
+        if relays.is_empty() {
+            return Err(JobsError::Config("At least one relay is required".to_string()));
+        }
+
+        let removed: Vec<&String> = old.relays.iter().filter(|r| !relays.contains(r)).collect();
+        let added: Vec<&String> = relays.iter().filter(|r| !old.relays.contains(r)).collect();
+        if !removed.is_empty() || !added.is_empty() {
+            self.jobs.update_relays(&added, &removed).await;
+            tracing::info!(added = ?added, removed = ?removed, "relays_updated");
+        }
+        self.jobs.set_config(Arc::new(ServerConfig { relays: relays.clone(), ..(*old).clone() }));
+        Ok(relays)
+    }
+
+    /// Switch the relay query strategy at runtime. Like `update_relays`, a
+    /// later `reload_config` returns to `RELAY_QUERY_STRATEGY`. Returns the
+    /// previous strategy.
+    pub fn set_query_strategy(&self, strategy: QueryStrategy) -> QueryStrategy {
+        let old = self.config();
+        if old.query_strategy != strategy {
+            tracing::info!(from = old.query_strategy.as_str(), to = strategy.as_str(), "query_strategy_changed");
+            self.jobs.set_config(Arc::new(ServerConfig { query_strategy: strategy, ..(*old).clone() }));
+        }
+        old.query_strategy
+    }
+
+    /// Turn offline mode on or off at runtime, reconnecting to the relays
+    /// when it goes off. Like `set_query_strategy`, a later `reload_config`
+    /// returns to `OFFLINE`. Returns the previous setting.
+    pub fn set_offline(&self, offline: bool) -> bool {
+        let old = self.config();
+        if old.offline != offline {
+            tracing::info!(offline, "offline_mode_changed");
+            self.jobs.set_config(Arc::new(ServerConfig { offline, ..(*old).clone() }));
+            if !offline {
+                self.jobs.connect_in_background();
+            }
+        }
+        old.offline
+    }
+
+    // ==================== Shutdown ====================
+
+    /// True once `shutdown` has started; new sessions and tool calls are refused
+    pub fn is_shutting_down(&self) -> bool {
+        self.drain.is_shutting_down()
+    }
+
+    /// Write saved state changed since the last write (listing views,
+    /// expired sessions) now rather than at the next periodic flush
+    pub async fn flush_state(&self) {
+        self.state.flush_changes().await;
+    }
+
+    /// Forget the saved state of sessions unchanged for `session_ttl`;
+    /// returns how many were removed
+    pub async fn expire_sessions(&self) -> usize {
+        self.state.expire_sessions(self.config().session_ttl).await
+    }
+
+    /// Refuse new tool calls, wait up to `shutdown_timeout` for running ones,
+    /// then log final metrics and disconnect from relays.
+    pub async fn shutdown(&self) {
+        self.drain.begin();
+        let deadline = self.config().shutdown_timeout;
+        tracing::info!(
+            in_flight = self.drain.in_flight(),
+            timeout_secs = deadline.as_secs(),
+            "shutdown_draining"
+        );
+
+        let abandoned = self.drain.wait(deadline).await;
+        if abandoned > 0 {
+            tracing::warn!(abandoned, "shutdown_deadline_reached");
+        }
+
+        {
+            let metrics = self.jobs.metrics().read().await;
+            let usage = self.jobs.cache_usage().await;
+            tracing::info!(
+                total_requests = metrics.total_requests,
+                cache_hits = metrics.cache_hits,
+                cache_misses = metrics.cache_misses,
+                failed_fetches = metrics.failed_fetches,
+                cache_entries = usage.entries,
+                cache_bytes = usage.bytes,
+                "final_metrics"
+            );
+        }
+
+        self.state.flush().await;
+        self.jobs.shutdown().await;
+        tracing::info!("relays_disconnected");
+    }
+
+    // ==================== Helper Methods ====================
+
+    fn role(&self, context: &RequestContext<RoleServer>) -> Role {
+        auth::role_for(context, &self.config().admin_tokens)
+    }
+
+    /// Owner key for saved state, or an error when the caller can't be identified
+    fn state_owner(&self, context: &RequestContext<RoleServer>) -> Result<String, McpError> {
+        auth::state_owner(context).ok_or_else(|| {
+            McpError::invalid_request(i18n::text(self.config().locale, "error.no_owner").to_string(), None)
+        })
+    }
+
+    /// Language to answer in: `requested` (a prompt's `locale`), else the
+    /// client's Accept-Language if it names a supported one, else `LOCALE`
+    fn locale_for(&self, requested: Option<&str>, context: Option<&RequestContext<RoleServer>>) -> Result<Locale, McpError> {
+        if let Some(requested) = requested {
+            return requested.parse().map_err(|e: String| McpError::invalid_params(e, Some(json!({ "locale": requested }))));
+        }
+        let accepted = context
+            .and_then(|c| c.extensions.get::<http::request::Parts>())
+            .and_then(|parts| parts.headers.get(http::header::ACCEPT_LANGUAGE))
+            .and_then(|value| value.to_str().ok())
+            .and_then(Locale::from_accept_language);
+        Ok(accepted.unwrap_or(self.config().locale))
+    }
+
+    /// Server info with the instructions in `locale`
+    pub fn info_in(&self, locale: Locale) -> ServerInfo {
+        ServerInfo {
+            protocol_version: ProtocolVersion::V_2024_11_05,
+            capabilities: ServerCapabilities::builder()
+                .enable_prompts()
+                .enable_resources()
+                .enable_tools()
+                .enable_tool_list_changed()
+                .build(),
+            server_info: Implementation::from_build_env(),
+            instructions: Some(i18n::instructions(locale)),
+        }
+    }
+
+    /// Deadline for a call to `tool` (or `resource:<uri>`): the `timeout_ms`
+    /// it asked for, up to `MAX_CALL_TIMEOUT`, else `TOOL_TIMEOUTS_MS`'s entry
+    /// for it. `None` keeps the search and stats timeouts.
+    pub fn call_timeout(&self, tool: &str, requested_ms: Option<&serde_json::Value>) -> Result<Option<Duration>, JobsError> {
+        match requested_ms {
+            Some(value) => match value.as_u64() {
+                Some(ms) if ms > 0 && ms <= MAX_CALL_TIMEOUT.as_millis() as u64 => Ok(Some(Duration::from_millis(ms))),
+                _ => Err(JobsError::ParseError {
+                    what: "timeout_ms",
+                    input: value.to_string(),
+                    message: format!("expected milliseconds between 1 and {}", MAX_CALL_TIMEOUT.as_millis()),
+                }),
+            },
+            None => Ok(self.config().tool_timeouts.get(tool).copied()),
+        }
+    }
+
+    /// Who a call is metered as: the id of its bearer token when that is
+    /// one of `API_KEYS`, otherwise its client IP, so made-up tokens don't
+    /// each get fresh quotas.
+    /// With a quota set and `API_KEYS` configured, other callers are refused.
+    pub fn quota_key(&self, token: Option<&str>, client: Option<IpAddr>) -> Result<Option<String>, JobsError> {
+        let config = self.config();
+        if let Some(token) = token.filter(|token| auth::is_listed(token, &config.api_keys)) {
+            return Ok(Some(auth::token_id(token)));
+        }
+        let metered = config.daily_tool_call_quota > 0 || config.daily_relay_fetch_quota > 0;
+        if metered && !config.api_keys.is_empty() {
+            return Err(JobsError::UnknownApiKey);
+        }
+        Ok(client.map(|ip| format!("ip:{}", ip)))
+    }
+
+    /// Let in one request, a tool call, a resource read (`tool` being its
+    /// `resource:` metrics name) or a GraphQL query (`tool` being
+    /// `graphql`): refused while shutting down, shed beyond
+    /// `max_in_flight_calls`, and unless from an `admin` counted toward the
+    /// daily quotas of the caller (see `quota_key`). Returns the guard to
+    /// hold until the request is done, and who it is metered as.
+    pub(crate) async fn admit(
+        &self,
+        tool: &str,
+        token: Option<&str>,
+        client: Option<IpAddr>,
+        admin: bool,
+    ) -> Result<(DrainGuard, Option<String>), McpError> {
+        let Some(in_flight) = self.drain.enter() else {
+            return Err(McpError::internal_error(
+                i18n::text(self.config().locale, "error.shutting_down").to_string(),
+                Some(json!({"tool": tool})),
+            ));
+        };
+        let max_in_flight = self.config().max_in_flight_calls;
+        if max_in_flight > 0 && self.drain.in_flight() > max_in_flight {
+            // Calls ahead of this one finish within a search timeout or so
+            let retry_after = std::time::Duration::from_secs(self.config().search_timeout.as_secs_f64().ceil().max(1.0) as u64);
+            tracing::warn!(
+                tool = %tool,
+                in_flight = self.drain.in_flight(),
+                client = ?client,
+                "tool_call_shed"
+            );
+            self.jobs.metrics().write().await.record_shed();
+            return Err(with_error_data(JobsError::Overloaded { retry_after }.into(), "tool", json!(tool)));
+        }
+        if admin {
+            return Ok((in_flight, None));
+        }
+        let api_key = match self.quota_key(token, client) {
+            Ok(key) => key,
+            Err(e) => {
+                tracing::warn!(tool = %tool, client = ?client, "tool_call_unknown_api_key");
+                return Err(with_error_data(e.into(), "tool", json!(tool)));
+            }
+        };
+        if let Some(key) = &api_key
+            && let Err(e) = self.check_quota(key).await
+        {
+            tracing::warn!(tool = %tool, api_key = %key, client = ?client, error = %e, "tool_call_over_quota");
+            return Err(with_error_data(e.into(), "tool", json!(tool)));
+        }
+        Ok((in_flight, api_key))
+    }
+
+    /// Count a call from `key` toward its daily quotas, or refuse it once
+    /// one is used up
+    pub async fn check_quota(&self, key: &str) -> Result<(), JobsError> {
+        let quotas = Quotas {
+            tool_calls: self.config().daily_tool_call_quota,
+            relay_fetches: self.config().daily_relay_fetch_quota,
+        };
+        self.usage.lock().await.reserve(key, quotas, Timestamp::now().as_secs())
+    }
+
+    /// Count the relay fetches a finished call from `key` made
+    pub async fn record_usage(&self, key: &str, relay_fetches: u64) {
+        self.usage.lock().await.record(key, relay_fetches, Timestamp::now().as_secs());
+    }
+
+    /// `rate` adds the fiat equivalent of salaries quoted in sats or BTC
+    pub fn format_job_summary(&self, listing: &JobListing, rate: Option<&ExchangeRate>) -> String {
+        let salary = listing.salary.as_ref().map(|s| {
+            let converted = rate
+                .and_then(|r| Some((r, r.convert(s)?)))
+                .map(|(r, c)| {
+                    format!(
+                        " (≈ ${} - ${} {} per {} at {} {}/BTC as of {})",
+                        c.min, c.max, c.currency, c.period, r.per_btc, r.fiat, r.as_of.to_human_datetime()
+                    )
+                })
+                .unwrap_or_default();
+            format!("${} - ${} {} per {}{}", s.min, s.max, s.currency, s.period, converted)
+        });
+        // Ingested listings link to the original posting; they aren't on any relay
+        let link = match self.jobs.external_source(listing) {
+            Some(source) => format!(
+                "📰 From {} (not on Nostr): {}",
+                source.kind,
+                source.url.as_deref().unwrap_or("no link")
+            ),
+            None => format!("🔗 {}", listing.njump_url(&self.config().relays)),
+        };
+
+        format!(
+            "🏢 {} - {}\n📍 Location: {}\n💼 Type: {}\n🛠️  Skills: {}\n{}\n🆔 Job ID: {}\n📅 Posted: {}\n{}",
+            listing.company.as_deref().unwrap_or("Unknown"),
+            listing.title.as_deref().unwrap_or("Untitled"),
+            listing.location.as_deref().unwrap_or("Remote"),
+            if listing.employment_types.is_empty() { "Not specified".to_string() } else { listing.employment_types.join(", ") },
+            if listing.skills.is_empty() { "Not specified".to_string() } else { listing.skills.join(", ") },
+            salary.map(|s| format!("💰 Salary: {}", s)).unwrap_or_default(),
+            listing.job_id,
+            listing.created_at.to_human_datetime(),
+            link
+        )
+    }
+
+    /// Structured form of a listing, for `structured_content`
+    pub(crate) fn listing_json(&self, listing: &JobListing, rate: Option<&ExchangeRate>) -> serde_json::Value {
+        let relays = &self.config().relays;
+        let converted = rate.and_then(|r| Some((r, r.convert(listing.salary.as_ref()?)?)));
+        let external = self.jobs.external_source(listing);
+        json!({
+            "job_id": listing.job_id,
+            "event_id": listing.event.id.to_hex(),
+            "title": listing.title,
+            "company": listing.company,
+            "location": listing.location,
+            "employment_types": listing.employment_types,
+            "skills": listing.skills,
+            "salary": listing.salary.as_ref().map(|s| json!({
+                "min": s.min,
+                "max": s.max,
+                "currency": s.currency,
+                "period": s.period,
+            })),
+            "salary_converted": converted.map(|(r, c)| json!({
+                "min": c.min,
+                "max": c.max,
+                "currency": c.currency,
+                "period": c.period,
+                "btc_price": r.per_btc,
+                "as_of": r.as_of.as_secs(),
+            })),
+            "author": listing.author.to_bech32().unwrap_or_else(|_| listing.author.to_hex()),
+            "created_at": listing.created_at.as_secs(),
+            "expires_at": listing.expires_at.map(|at| at.as_secs()),
+            "kind": listing.event.kind.as_u16(),
+            "source_kind": self.listing_source(listing).to_string(),
+            "origin": external.as_ref().map_or("nostr", |s| s.kind.as_str()),
+            "source_url": external.as_ref().and_then(|s| s.url.as_deref()),
+            "nevent": external.is_none().then(|| listing.nevent(relays)),
+            "njump_url": external.is_none().then(|| listing.njump_url(relays)),
+        })
+    }
+
+    /// Format the matches or, when nothing matched, the "did you mean"
+    /// suggestions. Listings by the query's preferred (followed) authors are
+    /// starred, and distances shown for radius searches.
+    fn render_search_results(&self, results: &SearchResults, query: &JobQuery, rate: Option<&ExchangeRate>) -> String {
+        let locale = self.config().locale;
+        if results.listings.is_empty() {
+            let mut text = i18n::text(locale, "report.search.none").to_string();
+            if !results.suggestions.is_empty() {
+                text.push_str(&format!(
+                    "\n\n{}\n{}",
+                    i18n::text(locale, "report.search.did_you_mean"),
+                    results
+                        .suggestions
+                        .iter()
+                        .map(|s| format!("  • {} \"{}\" → \"{}\"", s.field, s.value, s.suggestion))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                ));
+            }
+            return text;
+        }
+
+        let mut text = i18n::text_with(
+            locale,
+            "report.search.found",
+            &[("count", &results.listings.len()), ("banner", &source_banner(results.source, locale))],
+        );
+        text.push_str("\n\n");
+        let mixed = self.mixes_sources(&results.listings);
+        for (i, listing) in results.listings.iter().enumerate() {
+            let star = if query.is_preferred(listing) { "⭐ " } else { "" };
+            let distance = query
+                .distance_km(listing)
+                .map(|d| format!("\n📏 Distance: {:.1} km", d))
+                .unwrap_or_default();
+            let source = match mixed {
+                true => format!("\n{}", i18n::text_with(locale, "report.search.source", &[("source", &self.listing_source(listing))])),
+                false => String::new(),
+            };
+            let crossposts = self.format_crossposts(results.crossposts.get(&listing.job_id)).map(|c| format!("\n{}", c)).unwrap_or_default();
+            text.push_str(&format!("{}. {}{}{}{}{}\n\n", i + 1, star, self.format_job_summary(listing, rate), distance, source, crossposts));
+        }
+        text
+    }
+
+    /// Where `listing` comes from: its event kind, or the ingestion bridge
+    fn listing_source(&self, listing: &JobListing) -> ListingSource {
+        ListingSource::of(listing, Some(&self.jobs.ingest_public_key()))
+    }
+
+    /// The other representations of a listing, when it was crossposted
+    fn format_crossposts(&self, others: Option<&Vec<JobListing>>) -> Option<String> {
+        let others = others.filter(|others| !others.is_empty())?;
+        let list = others.iter().map(|l| format!("{} ({})", self.listing_source(l), l.job_id)).collect::<Vec<_>>().join(", ");
+        Some(format!("🔀 Also posted as: {}", list))
+    }
+
+    /// Structured form of a listing's other representations
+    fn crossposts_json(&self, others: Option<&Vec<JobListing>>) -> serde_json::Value {
+        json!(others.into_iter().flatten().map(|l| json!({
+            "job_id": l.job_id,
+            "event_id": l.event.id.to_hex(),
+            "kind": l.event.kind.as_u16(),
+            "source_kind": self.listing_source(l).to_string(),
+        })).collect::<Vec<_>>())
+    }
+
+    /// Whether `listings` come from more than one kind or the bridge, so
+    /// each needs its source labeled
+    fn mixes_sources<'a>(&self, listings: impl IntoIterator<Item = &'a JobListing>) -> bool {
+        listings.into_iter().map(|l| self.listing_source(l)).collect::<BTreeSet<_>>().len() > 1
+    }
+
+    /// A `jobs://search/{cursor}` URI for the matches after the first
+    /// `shown`, or `None` when `shown` and the `hidden` suspicious ones
+    /// were all `total` the search found
+    async fn more_results_uri(&self, query: &JobQuery, hide_suspicious: bool, shown: usize, hidden: usize, total: usize) -> Option<String> {
+        if shown + hidden >= total {
+            return None;
+        }
+        let cursor = SearchCursor { query: query.clone(), offset: shown, matches: total, hide_suspicious };
+        Some(format!("jobs://search/{}", self.search_cursors.lock().await.insert(cursor)))
+    }
+
+    /// Leave the listings that look like scams out of `listings`,
+    /// returning how many
+    async fn hide_suspicious(&self, listings: &mut Vec<JobListing>) -> usize {
+        if listings.is_empty() {
+            return 0;
+        }
+        let assessments = self.jobs.scam_assessments(listings).await;
+        let before = listings.len();
+        listings.retain(|l| !assessments.get(&l.job_id).is_some_and(ScamAssessment::is_suspicious));
+        before - listings.len()
+    }
+
+    /// A resource link to `uri`, described by the `description_key` text
+    fn resource_link(&self, uri: &str, name: &str, description_key: &str) -> Content {
+        let mut resource = RawResource::new(uri, name.to_string());
+        resource.description = Some(i18n::text(self.config().locale, description_key).to_string());
+        resource.mime_type = Some("text/plain".to_string());
+        Content::resource_link(resource)
+    }
+
+    /// Links to `jobs://job/{id}/full` for listings whose description a
+    /// summary left out
+    fn full_listing_links(&self, listings: &[JobListing]) -> Vec<Content> {
+        listings
+            .iter()
+            .filter(|l| !l.description.trim().is_empty())
+            .map(|l| self.resource_link(&format!("jobs://job/{}/full", l.job_id), l.title.as_deref().unwrap_or("Untitled"), "link.job.full"))
+            .collect()
+    }
+
+    /// Contents of `jobs://search/{cursor}`: the next page of the search
+    /// behind `cursor`, with a link onward when there are more
+    pub async fn read_search_page(&self, cursor: &str) -> Result<String, McpError> {
+        let locale = self.config().locale;
+        let Some(SearchCursor { query, offset, matches, hide_suspicious }) = self.search_cursors.lock().await.get(cursor) else {
+            let message = i18n::text_with(locale, "error.search_cursor", &[("cursor", &cursor)]);
+            return Err(McpError::resource_not_found(message, Some(json!({ "uri": format!("jobs://search/{}", cursor) }))));
+        };
+        let page = query.limit;
+        let everything = JobQuery { limit: matches, ..query.clone() };
+        let mut results = match self.jobs.search(&everything).await {
+            Ok(results) => results,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+        if hide_suspicious {
+            self.hide_suspicious(&mut results.listings).await;
+            results.total = results.listings.len();
+        }
+        let listings: Vec<&JobListing> = results.listings.iter().skip(offset).take(page).collect();
+        if listings.is_empty() {
+            return Ok(i18n::text(locale, "report.search.none").to_string());
+        }
+        let fiat = self.display_currency(None).await;
+        let rate = self.salary_rate(&fiat, listings.iter().copied()).await;
+        let mut text = i18n::text_with(
+            locale,
+            "report.search.page",
+            &[
+                ("first", &(offset + 1)),
+                ("last", &(offset + listings.len())),
+                ("total", &results.total),
+                ("banner", &source_banner(results.source, locale)),
+            ],
+        );
+        text.push_str("\n\n");
+        let mixed = self.mixes_sources(listings.iter().copied());
+        for (i, listing) in listings.iter().enumerate() {
+            let star = if query.is_preferred(listing) { "⭐ " } else { "" };
+            text.push_str(&format!("{}. {}{}\n", offset + i + 1, star, self.format_job_summary(listing, rate.as_ref())));
+            if mixed {
+                text.push_str(&format!("{}\n", i18n::text_with(locale, "report.search.source", &[("source", &self.listing_source(listing))])));
+            }
+            if let Some(crossposts) = self.format_crossposts(results.crossposts.get(&listing.job_id)) {
+                text.push_str(&format!("{}\n", crossposts));
+            }
+            if !listing.description.trim().is_empty() {
+                text.push_str(&format!("📄 jobs://job/{}/full\n", listing.job_id));
+            }
+            text.push('\n');
+        }
+        let shown = offset + listings.len();
+        if shown < results.total {
+            let next = SearchCursor { query, offset: shown, matches, hide_suspicious };
+            let uri = format!("jobs://search/{}", self.search_cursors.lock().await.insert(next));
+            text.push_str(&i18n::text_with(locale, "report.search.more", &[("shown", &shown), ("total", &results.total), ("uri", &uri)]));
+        }
+        Ok(text)
+    }
+
+    /// The fiat `owner` wants salaries in: their `currency` preference, or
+    /// `DISPLAY_CURRENCY`
+    async fn display_currency(&self, owner: Option<&str>) -> String {
+        let preferred = match owner {
+            Some(owner) => self.state.get(owner).await.preferences.get("currency").and_then(|v| v.as_str().map(str::to_string)),
+            None => None,
+        };
+        preferred
+            .map(|c| c.trim().to_uppercase())
+            .filter(|c| !c.is_empty())
+            .unwrap_or_else(|| self.config().display_currency.clone())
+    }
+
+    /// Whether `owner` gets plain text: their `plain_text` preference (a
+    /// boolean, or "true"/"false"), else `PLAIN_TEXT`
+    async fn wants_plain_text(&self, owner: Option<&str>) -> bool {
+        let preferred = match owner {
+            Some(owner) => self.state.get(owner).await.preferences.get("plain_text").and_then(|v| match v {
+                serde_json::Value::Bool(b) => Some(*b),
+                serde_json::Value::String(s) => s.trim().parse().ok(),
+                _ => None,
+            }),
+            None => None,
+        };
+        preferred.unwrap_or(self.config().plain_text)
+    }
+
+    /// `result` as `owner` wants it: with emoji and box drawing stripped from
+    /// its text when they asked for plain text
+    pub async fn plain_text_for(&self, owner: Option<&str>, mut result: CallToolResult) -> CallToolResult {
+        if self.wants_plain_text(owner).await {
+            for content in &mut result.content {
+                if let RawContent::Text(text) = &mut content.raw {
+                    text.text = plain_text::strip(&text.text);
+                }
+            }
+        }
+        result
+    }
+
+    /// The BTC price in `fiat`, fetched only when some of `listings` quote
+    /// their salary in sats or BTC
+    async fn salary_rate<'a>(&self, fiat: &str, listings: impl IntoIterator<Item = &'a JobListing>) -> Option<ExchangeRate> {
+        let crypto = listings
+            .into_iter()
+            .any(|l| l.salary.as_ref().is_some_and(|s| rates::btc_per_unit(&s.currency).is_some()));
+        match crypto {
+            true => self.jobs.exchange_rate(fiat).await,
+            false => None,
+        }
+    }
+
+    /// Public keys of the employers `owner` follows
+    async fn followed_employers(&self, owner: &str) -> Vec<PublicKey> {
+        self.state
+            .get(owner)
+            .await
+            .followed_employers
+            .iter()
+            .filter_map(|f| PublicKey::from_hex(&f.pubkey).ok())
+            .collect()
+    }
+
+    /// Fill the filters a search left unset from `profile`, and exclude its
+    /// companies. Returns a description of each default applied.
+    fn apply_profile(profile: &CandidateProfile, query: &mut JobQuery, min_salary: &mut Option<f64>) -> Vec<String> {
+        let mut applied = Vec::new();
+        if query.skill.is_empty() && !profile.skills.is_empty() {
+            query.skill = profile.skills.clone();
+            applied.push(format!("skills {}", profile.skills.join(", ")));
+        }
+        if query.location.is_empty() && !profile.locations.is_empty() {
+            query.location = profile.locations.clone();
+            applied.push(format!("locations {}", profile.locations.join(", ")));
+        }
+        if min_salary.is_none()
+            && let Some(amount) = profile.min_salary
+        {
+            *min_salary = Some(amount);
+            applied.push(format!("minimum salary {}", amount));
+        }
+        if !profile.excluded_companies.is_empty() {
+            query.exclude_company = profile.excluded_companies.clone();
+            applied.push(format!("excluding {}", profile.excluded_companies.join(", ")));
+        }
+        applied
+    }
+
+    /// Invalid params for a number outside `min..=max`
+    fn out_of_range(&self, field: &str, min: u64, max: u64) -> McpError {
+        let message = i18n::text_with(self.config().locale, "error.range", &[("field", &field), ("min", &min), ("max", &max)]);
+        McpError::invalid_params(message, Some(json!({ "field": field, "min": min, "max": max })))
+    }
+
+    /// `e` as an MCP error, noting relay health on retryable failures so
+    /// clients can tell slow relays from relays that are still connecting
+    async fn jobs_error(&self, e: JobsError) -> McpError {
+        let retryable = e.is_retryable();
+        let error = mcp_error(e, self.config().locale);
+        if retryable {
+            with_error_data(error, "relays_healthy", json!(self.jobs.is_healthy().await))
+        } else {
+            error
+        }
+    }
+
+    // ==================== Tools ====================
+
+    #[tool(description = "Search for job listings on Nostr. You can filter by company, skill, or employment type; each accepts a single value or a list (any-of), and filters combine with AND. Set author (npub or hex) to only see one poster's listings. For local work, pass near (a place name) or lat/lon plus radius_km; listings are matched by their geohash (g) tags. Set fuzzy=true to tolerate typos, and hide_suspicious=true to leave out listings that look like scams. Set kinds to search several event kinds at once (e.g. [9993, 30402]) or only listings from the ingestion bridge (\"ingested\"); results from more than one are labeled with their source. A job crossposted across kinds or through the bridge (same company and title) is shown once, with its other representations. With a progress token, results from the first relays to answer arrive as progress notifications before the full response. Each listing links to jobs://job/{id}/full for its description, and a search cut short by its limit links to jobs://search/{cursor} for the rest.")]
+    pub async fn search_jobs(
+        &self,
+        args: Parameters<SearchJobsArgs>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let owner = auth::state_owner(&context);
+        let Some(token) = context.meta.get_progress_token() else {
+            return self.search_jobs_for(owner.as_deref(), args).await;
+        };
+
+        // Forward partial results to the client as they come in
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<ProgressNotificationParam>();
+        let peer = context.peer.clone();
+        let forwarder = tokio::spawn(async move {
+            while let Some(progress) = receiver.recv().await {
+                if let Err(e) = peer.notify_progress(progress).await {
+                    tracing::debug!(error = %e, "progress_notify_failed");
+                }
+            }
+        });
+        let result = self.search_jobs_with_progress(owner.as_deref(), args, Some((token, sender))).await;
+        let _ = forwarder.await;
+        result
+    }
+
+    /// `search_jobs` on behalf of `owner`, whose followed employers are
+    /// listed first
+    pub async fn search_jobs_for(
+        &self,
+        owner: Option<&str>,
+        args: Parameters<SearchJobsArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        self.search_jobs_with_progress(owner, args, None).await
+    }
+
+    /// `search_jobs_for`, sending a progress notification with `token` and
+    /// the results so far each time a relay answers on a cache miss
+    pub async fn search_jobs_with_progress(
+        &self,
+        owner: Option<&str>,
+        Parameters(args): Parameters<SearchJobsArgs>,
+        progress: Option<(ProgressToken, tokio::sync::mpsc::UnboundedSender<ProgressNotificationParam>)>,
+    ) -> Result<CallToolResult, McpError> {
+        let mut query = JobQuery::try_from(&args)?;
+        if let (None, Some(place)) = (&query.near, &args.near) {
+            let Some(url) = self.config().geocoder_url.clone() else {
+                return Err(JobsError::Config("Place names need GEOCODER_URL; pass lat and lon instead".to_string()).into());
+            };
+            let center = geo::geocode(&url, place).await?;
+            query.near = Some(GeoRadius { center, radius_km: args.radius_km() });
+        }
+        let mut min_salary = args.min_salary;
+        let mut defaults = Vec::new();
+        if let Some(owner) = owner {
+            query.preferred_authors = self.followed_employers(owner).await;
+            defaults = Self::apply_profile(&self.state.get(owner).await.profile, &mut query, &mut min_salary);
+        }
+        let fiat = self.display_currency(owner).await;
+        if let Some(amount) = min_salary {
+            query.min_salary = Some(SalaryFloor { amount, currency: fiat.clone(), rate: self.jobs.exchange_rate(&fiat).await });
+        }
+        let searched = match progress {
+            Some((token, sender)) => {
+                // Partial results are filtered like the final ones, which
+                // takes a lookup, so they're rendered as they arrive here
+                let (partials, mut arriving) = tokio::sync::mpsc::unbounded_channel::<PartialResults>();
+                let search = self.jobs.search_with_progress(&query, move |partial| {
+                    let _ = partials.send(partial);
+                });
+                let notify = async {
+                    while let Some(mut partial) = arriving.recv().await {
+                        if args.hide_suspicious {
+                            self.hide_suspicious(&mut partial.results.listings).await;
+                        }
+                        // Partial results skip the BTC rate lookup unless a salary floor already fetched it
+                        let rate = query.min_salary.as_ref().and_then(|floor| floor.rate.as_ref());
+                        let _ = sender.send(ProgressNotificationParam {
+                            progress_token: token.clone(),
+                            progress: partial.answered as f64,
+                            total: Some(partial.relays as f64),
+                            message: Some(format!(
+                                "{}\n\n{}",
+                                i18n::text_with(
+                                    self.config().locale,
+                                    "report.search.partial",
+                                    &[("answered", &partial.answered), ("relays", &partial.relays)],
+                                ),
+                                self.render_search_results(&partial.results, &query, rate)
+                            )),
+                        });
+                    }
+                };
+                tokio::join!(search, notify).0
+            }
+            None => self.jobs.search(&query).await,
+        };
+        match searched {
+            Ok(mut results) => {
+                let hidden = match args.hide_suspicious {
+                    true => self.hide_suspicious(&mut results.listings).await,
+                    false => 0,
+                };
+                self.jobs.record_engagement(Interaction::Impression, &results.listings);
+                let rate = match &query.min_salary {
+                    Some(floor) => floor.rate.clone(),
+                    None => self.salary_rate(&fiat, &results.listings).await,
+                };
+                let mut text = self.render_search_results(&results, &query, rate.as_ref());
+                if hidden > 0 {
+                    let note = i18n::text_with(self.config().locale, "report.search.hidden", &[("hidden", &hidden)]);
+                    text = format!("{}\n\n{}", text.trim_end(), note);
+                }
+                if !defaults.is_empty() {
+                    let applied = i18n::text_with(self.config().locale, "report.preferences_applied", &[("defaults", &defaults.join("; "))]);
+                    text = format!("{}\n\n{}", applied, text);
+                }
+                let shown = results.listings.len();
+                let more = self.more_results_uri(&query, args.hide_suspicious, shown, hidden, results.total).await;
+                if let Some(uri) = &more {
+                    let note = i18n::text_with(self.config().locale, "report.search.more", &[("shown", &shown), ("total", &results.total), ("uri", uri)]);
+                    text = format!("{}\n\n{}", text.trim_end(), note);
+                }
+                let mut content = vec![Content::text(text)];
+                content.extend(self.full_listing_links(&results.listings));
+                content.extend(more.as_ref().map(|uri| self.resource_link(uri, "Search Results", "link.search.more")));
+                let mut output = CallToolResult::success(content);
+                output.structured_content = Some(json!({
+                    "source": results.source.as_str(),
+                    "total_matches": results.total,
+                    "more": more,
+                    "cache_age_secs": stale_age_secs(results.source),
+                    "preferences_applied": defaults,
+                    "hidden_suspicious": hidden,
+                    "listings": results
+                        .listings
+                        .iter()
+                        .map(|l| {
+                            let mut listing = self.listing_json(l, rate.as_ref());
+                            if let Some(distance) = query.distance_km(l) {
+                                listing["distance_km"] = json!(distance);
+                            }
+                            listing["crossposts"] = self.crossposts_json(results.crossposts.get(&l.job_id));
+                            listing
+                        })
+                        .collect::<Vec<_>>(),
+                    "suggestions": results.suggestions.iter().map(|s| json!({
+                        "field": s.field,
+                        "value": s.value,
+                        "suggestion": s.suggestion,
+                    })).collect::<Vec<_>>(),
+                }));
+                Ok(output)
+            }
+            Err(e) => Err(self.jobs_error(e).await),
+        }
+    }
+
+    #[tool(description = "Explain how a search_jobs call would be executed without running it: the cache key, whether it would hit cache (and the entry's age), the relay filter, client-side matching criteria, and which relays would be queried")]
+    pub async fn explain_query(
+        &self,
+        Parameters(args): Parameters<SearchJobsArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let query = JobQuery::try_from(&args)?;
+        let cache_key = query.cache_key();
+        let ttl = self.config().search_cache_ttl;
+
+        let locale = self.config().locale;
+        let cache_status = match self.jobs.cache_entry(&cache_key).await {
+            Some(entry) => i18n::text_with(
+                locale,
+                "report.explain.hit",
+                &[
+                    ("freshness", &i18n::text(locale, if entry.age < ttl { "report.explain.fresh" } else { "report.explain.stale" })),
+                    ("age", &format!("{:.1}", entry.age.as_secs_f64())),
+                    ("ttl", &ttl.as_secs()),
+                    ("events", &entry.events),
+                ],
+            ),
+            None => i18n::text_with(
+                locale,
+                "report.explain.miss",
+                &[
+                    ("relay_ms", &self.config().relay_timeout.as_millis()),
+                    ("search_ms", &self.config().search_timeout.as_millis()),
+                ],
+            ),
+        };
+
+        let any = i18n::text(locale, "report.explain.any");
+        let describe = |values: &[String]| {
+            if values.is_empty() {
+                any.to_string()
+            } else {
+                values.join(" OR ")
+            }
+        };
+        let near = match (&query.near, &args.near) {
+            (Some(near), _) => i18n::text_with(
+                locale,
+                "report.explain.near",
+                &[
+                    ("lat", &format!("{:.4}", near.center.lat)),
+                    ("lon", &format!("{:.4}", near.center.lon)),
+                    ("km", &near.radius_km),
+                    ("cells", &near.cells().join(", ")),
+                ],
+            ),
+            (None, Some(place)) => i18n::text_with(locale, "report.explain.near_place", &[("place", place), ("km", &args.radius_km())]),
+            (None, None) => i18n::text(locale, "report.explain.anywhere").to_string(),
+        };
+        let min_salary = args
+            .min_salary
+            .map(|amount| {
+                i18n::text_with(locale, "report.explain.min_salary", &[("amount", &amount), ("currency", &self.config().display_currency)])
+            })
+            .unwrap_or_else(|| any.to_string());
+
+        let report = i18n::text_with(
+            locale,
+            "report.explain",
+            &[
+                ("cache_key", &cache_key),
+                ("cache", &cache_status),
+                ("filter", &self.jobs.search_filter(&query).as_json()),
+                (
+                    "fuzzy",
+                    &query.fuzzy_distance.map(|d| i18n::text_with(locale, "report.explain.fuzzy", &[("edits", &d)])).unwrap_or_default(),
+                ),
+                ("company", &describe(&query.company)),
+                ("skill", &describe(&query.skill)),
+                ("employment_type", &describe(&query.employment_type)),
+                ("author", &query.author.map(|a| a.to_bech32().unwrap_or_else(|_| a.to_hex())).unwrap_or_else(|| any.to_string())),
+                ("near", &near),
+                ("min_salary", &min_salary),
+                ("limit", &query.limit),
+                ("relay_count", &self.config().relays.len()),
+                ("relays", &self.config().relays.iter().map(|r| format!("  • {}", r)).collect::<Vec<_>>().join("\n")),
+            ],
+        );
+
+        Ok(CallToolResult::success(vec![Content::text(report)]))
+    }
+
+    #[tool(name = "get_job_details", description = "Get detailed information about a specific job listing by its Job ID or Event ID, with warnings when it shows common scam patterns (pay-to-apply, messenger-only contact, salary far above market, a brand-new key with no profile)")]
+    pub async fn get_job_details_tool(
+        &self,
+        args: Parameters<GetJobArgs>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        self.get_job_details_for(auth::state_owner(&context).as_deref(), args).await
+    }
+
+    pub async fn get_job_details(&self, args: Parameters<GetJobArgs>) -> Result<CallToolResult, McpError> {
+        self.get_job_details_for(None, args).await
+    }
+
+    /// `get_job_details` on behalf of `owner`, whose view feeds `recommend_jobs`
+    pub async fn get_job_details_for(
+        &self,
+        owner: Option<&str>,
+        Parameters(args): Parameters<GetJobArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        match self.jobs.get(&args.job_id).await {
+            Ok((listing, source)) => {
+                self.jobs.record_engagement(Interaction::View, [&listing]);
+                if let Some(owner) = owner {
+                    let view = ViewedJob {
+                        job_id: listing.job_id.clone(),
+                        company: listing.company.clone(),
+                        skills: listing.skills.clone(),
+                        viewed_at: Timestamp::now().as_secs(),
+                    };
+                    self.state.record(owner, |state| state.record_view(view)).await;
+                }
+                let fiat = self.display_currency(None).await;
+                let rate = self.salary_rate(&fiat, [&listing]).await;
+                let assessment = self.jobs.scam_assessments(std::slice::from_ref(&listing)).await.remove(&listing.job_id).unwrap_or_default();
+                let locale = self.config().locale;
+                let mut result = self.format_job_summary(&listing, rate.as_ref());
+                result.push_str(&format!("\n\n{}", source_banner(source, locale).trim_start()));
+                if !assessment.signals.is_empty() {
+                    result.push_str(&format!("\n\n{}", Self::format_scam_warning(&assessment, locale)));
+                }
+                result.push_str(&format!("\n\n{}\n", i18n::text(self.config().locale, "report.job.details")));
+                result.push_str(&listing.description);
+
+                let mut structured = self.listing_json(&listing, rate.as_ref());
+                structured["description"] = json!(listing.description);
+                structured["scam_assessment"] = json!({
+                    "score": assessment.score,
+                    "suspicious": assessment.is_suspicious(),
+                    "signals": assessment.signals.iter().map(|s| json!({
+                        "code": s.code,
+                        "message": s.message(locale),
+                        "weight": s.weight,
+                    })).collect::<Vec<_>>(),
+                });
+                structured["source"] = json!(source.as_str());
+                structured["cache_age_secs"] = json!(stale_age_secs(source));
+                let mut output = CallToolResult::success(vec![Content::text(result)]);
+                output.structured_content = Some(structured);
+                Ok(output)
+            }
+            Err(e) => Err(self.jobs_error(e).await),
+        }
+    }
+
+    #[tool(description = "Get the full signed Nostr event behind a job listing (id, pubkey, created_at, kind, tags, content, sig) as NIP-01 JSON, by Job ID or Event ID, with whether its signature checks out. For debugging tag conventions, or to re-verify or re-broadcast the event yourself.")]
+    pub async fn get_raw_event(&self, Parameters(args): Parameters<GetJobArgs>) -> Result<CallToolResult, McpError> {
+        let (listing, source) = match self.jobs.get(&args.job_id).await {
+            Ok(found) => found,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+        let event = &listing.event;
+        let signature_valid = event.verify().is_ok();
+        let raw = serde_json::to_value(event).unwrap_or_default();
+        let ingested = self.jobs.external_source(&listing).map(|s| s.kind);
+        let locale = self.config().locale;
+        let signature = i18n::text(locale, if signature_valid { "report.raw_event.valid" } else { "report.raw_event.invalid" });
+        let mut text = i18n::text_with(
+            locale,
+            "report.raw_event.title",
+            &[
+                ("job_id", &listing.job_id),
+                ("kind", &event.kind.as_u16()),
+                ("signature", &signature),
+                ("banner", &source_banner(source, locale)),
+            ],
+        );
+        if let Some(kind) = &ingested {
+            text.push_str(&format!("\n{}", i18n::text_with(locale, "report.raw_event.ingested", &[("source", kind)])));
+        }
+        text.push_str(&format!("\n\n{}", serde_json::to_string_pretty(&raw).unwrap_or_default()));
+        let mut output = CallToolResult::success(vec![Content::text(text)]);
+        output.structured_content = Some(json!({
+            "event": raw,
+            "signature_valid": signature_valid,
+            "ingested_from": ingested,
+            "source": source.as_str(),
+            "cache_age_secs": stale_age_secs(source),
+        }));
+        Ok(output)
+    }
+
+    fn format_scam_warning(assessment: &ScamAssessment, locale: Locale) -> String {
+        let heading = match assessment.is_suspicious() {
+            true => i18n::text(locale, "report.scam.likely"),
+            false => i18n::text(locale, "report.scam.possible"),
+        };
+        let signals: Vec<String> = assessment.signals.iter().map(|s| format!("  • {}", s.message(locale))).collect();
+        i18n::text_with(
+            locale,
+            "report.scam.warning",
+            &[("heading", &heading), ("score", &assessment.score), ("signals", &signals.join("\n"))],
+        )
+    }
+
+    #[tool(description = "Re-check a job listing (by Job ID or Event ID) on every relay, bypassing the cache, to confirm it still exists, hasn't been deleted (NIP-09) or expired (NIP-40). Reports when and where it was last seen. Use before advising someone to apply.")]
+    pub async fn verify_listing_active(
+        &self,
+        Parameters(args): Parameters<GetJobArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        match self.jobs.verify(&args.job_id).await {
+            Ok(status) => Ok(CallToolResult::success(vec![Content::text(Self::format_listing_status(&status, self.config().locale))])),
+            Err(e) => Err(self.jobs_error(e).await),
+        }
+    }
+
+    fn format_listing_status(status: &ListingStatus, locale: Locale) -> String {
+        let verdict = match &status.listing {
+            _ if status.deleted => i18n::text(locale, "report.status.deleted").to_string(),
+            Some(listing) if listing.is_expired() => i18n::text_with(
+                locale,
+                "report.status.expired",
+                &[("date", &listing.expires_at.map(|at| at.to_human_datetime()).unwrap_or_default())],
+            ),
+            _ if status.seen_on.is_empty() => i18n::text(locale, "report.status.gone").to_string(),
+            _ => i18n::text(locale, "report.status.active").to_string(),
+        };
+        let relays = |relays: &[String]| match relays.is_empty() {
+            true => i18n::text(locale, "report.status.none").to_string(),
+            false => relays.join(", "),
+        };
+        let job = status
+            .listing
+            .as_ref()
+            .map(|l| {
+                i18n::text_with(
+                    locale,
+                    "report.status.job",
+                    &[
+                        ("title", &l.title.as_deref().unwrap_or("Untitled")),
+                        ("company", &l.company.as_deref().unwrap_or("Unknown")),
+                        ("job_id", &l.job_id),
+                    ],
+                )
+            })
+            .unwrap_or_default();
+
+        let mut text = i18n::text_with(
+            locale,
+            "report.status.body",
+            &[
+                ("verdict", &verdict),
+                ("job", &job),
+                ("seen_on", &relays(&status.seen_on)),
+                ("missing_from", &relays(&status.missing_from)),
+            ],
+        );
+        if !status.unreachable.is_empty() {
+            text.push_str(&format!("\n{}", i18n::text_with(locale, "report.status.unreachable", &[("relays", &relays(&status.unreachable))])));
+        }
+        let last_seen = status.last_seen.map(|at| at.to_human_datetime()).unwrap_or_else(|| i18n::text(locale, "report.status.never").to_string());
+        text.push_str(&format!(
+            "\n{}",
+            i18n::text_with(locale, "report.status.checked", &[("last_seen", &last_seen), ("checked", &status.checked_at.to_human_datetime())])
+        ));
+        text
+    }
+
+    #[tool(description = "List every job posting by one author (npub or hex pubkey), active and expired, with posting cadence: how often they post and how many listings repeat an earlier title. Helps judge whether a company actually hires or just reposts.")]
+    pub async fn get_employer_listings(
+        &self,
+        Parameters(args): Parameters<EmployerListingsArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let author = parse_pubkey(&args.pubkey)?;
+        let sample_size = args.sample_size.unwrap_or(DEFAULT_STATS_SAMPLE_SIZE).clamp(1, MAX_STATS_SAMPLE_SIZE);
+
+        let sample = match self.jobs.employer_listings(&author, sample_size).await {
+            Ok(sample) => sample,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+        let npub = author.to_bech32().unwrap_or_else(|_| author.to_hex());
+        if sample.listings.is_empty() {
+            return Ok(CallToolResult::success(vec![Content::text(format!(
+                "No job listings found from {}",
+                npub
+            ))]));
+        }
+
+        let history = PostingHistory::from_listings(&sample.listings);
+        let listings = sample
+            .listings
+            .iter()
+            .enumerate()
+            .map(|(i, listing)| {
+                format!(
+                    "{}. {} — {} at {}{} (🆔 {})",
+                    i + 1,
+                    listing.created_at.to_human_datetime(),
+                    listing.title.as_deref().unwrap_or("Untitled"),
+                    listing.company.as_deref().unwrap_or("Unknown"),
+                    if listing.is_expired() { " [EXPIRED]" } else { "" },
+                    listing.job_id
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let locale = self.config().locale;
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "{}\n\n{}{}\n\n{}",
+            i18n::text_with(locale, "report.history.title", &[("npub", &npub), ("banner", &source_banner(sample.source, locale))]),
+            Self::format_posting_history(&history),
+            if sample.truncated { format!("\n{}", i18n::text(locale, "banner.partial")) } else { String::new() },
+            listings
+        ))]))
+    }
+
+    #[tool(description = "Profile one company across recent listings, whatever the spelling of its name: how many listings and posters, whether the keys posting as it agree on its name, NIP-05 domain and website (flagging possible impersonators), their skills and employment types, its salary transparency (the share of listings stating a complete salary, with typical ranges), and its reputation on Nostr (reactions, reports and review-style notes, each linked to its event)")]
+    pub async fn get_company_profile(&self, Parameters(args): Parameters<CompanyProfileArgs>) -> Result<CallToolResult, McpError> {
+        let canonical = company::canonical_company(&args.company);
+        if canonical.is_empty() {
+            return Err(McpError::invalid_params(i18n::text_with(self.config().locale, "error.empty", &[("field", &"company")]), None));
+        }
+        let sample_size = args.sample_size.unwrap_or(DEFAULT_STATS_SAMPLE_SIZE).clamp(1, MAX_STATS_SAMPLE_SIZE);
+        let sample = match self.jobs.sample(sample_size, None).await {
+            Ok(sample) => sample,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+        let listings: Vec<JobListing> = sample
+            .listings
+            .iter()
+            .filter(|l| l.company.as_deref().is_some_and(|name| company::canonical_company(name) == canonical))
+            .cloned()
+            .collect();
+        if listings.is_empty() {
+            return Ok(CallToolResult::success(vec![Content::text(format!(
+                "No recent job listings from {} among the {} most recent",
+                args.company.trim(),
+                sample.listings.len()
+            ))]));
+        }
+
+        let rate = self.salary_rate(&self.config().display_currency, &listings).await;
+        let stats = JobStats::from_listings_in(&listings, rate.as_ref());
+        let history = PostingHistory::from_listings(&listings);
+        let Some(transparency) = stats.salary_transparency.first() else {
+            return Err(McpError::internal_error("company listings without a company", None));
+        };
+        let mut spellings: Vec<&String> = stats.companies.keys().collect();
+        spellings.sort();
+        let posters: HashSet<PublicKey> = listings.iter().map(|l| l.author).collect();
+        let recent = listings
+            .iter()
+            .take(5)
+            .map(|l| {
+                format!(
+                    "  • {}{} (🆔 {}) - {}",
+                    l.title.as_deref().unwrap_or("Untitled"),
+                    if l.is_expired() { " [EXPIRED]" } else { "" },
+                    l.job_id,
+                    l.created_at.to_human_datetime()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        // Reputation and identities are extra: a failed fetch still leaves the profile
+        let poster_keys: Vec<PublicKey> = posters.iter().copied().collect();
+        let listing_ids: Vec<EventId> = listings.iter().map(|l| l.event.id).collect();
+        let (reputation, profiles) = tokio::join!(
+            self.jobs.company_reputation(&transparency.company, &poster_keys, &listing_ids),
+            self.jobs.profiles(&poster_keys)
+        );
+        let identities = profiles.map(|profiles| {
+            let identities = company::poster_identities(&listings, &profiles);
+            let conflicts = company::identity_conflicts(&transparency.company, &identities);
+            (identities, conflicts)
+        });
+        let relays = self.config().relays.clone();
+
+        let locale = self.config().locale;
+        let heading = |key: &'static str| i18n::text(locale, key);
+        let mut output = CallToolResult::success(vec![Content::text(format!(
+            "🏢 {}{}{}\n\n{}\n\n{}\n{}\n\n{}\n{}\n\n{}\n{}\n\n{}\n{}\n\n{}\n{}\n\n{}\n{}",
+            transparency.company,
+            match spellings.iter().filter(|s| ***s != transparency.company).map(|s| s.as_str()).collect::<Vec<_>>() {
+                others if others.is_empty() => String::new(),
+                others => i18n::text_with(locale, "report.company.also_spelled", &[("spellings", &others.join(", "))]),
+            },
+            source_banner(sample.source, locale),
+            i18n::text_with(
+                locale,
+                "report.company.listings",
+                &[
+                    ("listings", &history.listings),
+                    ("active", &history.active),
+                    ("expired", &history.expired),
+                    ("posters", &posters.len()),
+                ],
+            ),
+            heading("report.company.posters"),
+            match &identities {
+                Ok((identities, conflicts)) => format_identities(identities, conflicts),
+                Err(e) => format!("  Unavailable: {}", e),
+            },
+            heading("report.company.salary_transparency"),
+            format_transparency(transparency),
+            heading("report.company.reputation"),
+            match &reputation {
+                Ok(reputation) => format_reputation(reputation, &relays),
+                Err(e) => format!("  Unavailable: {}", e),
+            },
+            heading("report.stats.top_skills"),
+            format_top_items(&stats.skills, 10),
+            heading("report.stats.employment_types"),
+            format_top_items(&stats.employment_types, 5),
+            heading("report.company.recent"),
+            recent
+        ))]);
+        output.structured_content = Some(json!({
+            "company": transparency.company,
+            "canonical": canonical,
+            "spellings": spellings,
+            "listings": history.listings,
+            "active": history.active,
+            "expired": history.expired,
+            "posters": posters.iter().map(|p| p.to_bech32().unwrap_or_else(|_| p.to_hex())).collect::<Vec<_>>(),
+            "identities": match &identities {
+                Ok((identities, conflicts)) => identity_json(identities, conflicts),
+                Err(e) => json!({ "error": e.to_string() }),
+            },
+            "salary_transparency": transparency_json(transparency),
+            "reputation": match &reputation {
+                Ok(reputation) => reputation_json(reputation, &relays),
+                Err(e) => json!({ "error": e.to_string() }),
+            },
+            "skills": stats.skills,
+            "employment_types": stats.employment_types,
+        }));
+        Ok(output)
+    }
+
+    #[tool(description = "Match a resume against current job listings. The resume text is sent to a Nostr data vending machine (NIP-90) that extracts its skills; listings asking for the most of those skills are shown first. The request is published to public relays: unless the board has a DVM_PROVIDER, to which it is encrypted, anyone can read the resume text. The DVM may take up to DVM_TIMEOUT_SECS to answer.")]
+    pub async fn match_resume(
+        &self,
+        Parameters(args): Parameters<MatchResumeArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        if args.resume.trim().is_empty() {
+            return Err(McpError::invalid_params(i18n::text_with(self.config().locale, "error.empty", &[("field", &"resume")]), None));
+        }
+        let analysis = match self.jobs.analyze_resume(&args.resume).await {
+            Ok(analysis) => analysis,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+        let provider = analysis.provider.to_bech32().unwrap_or_else(|_| analysis.provider.to_hex());
+        if analysis.skills.is_empty() {
+            return Ok(CallToolResult::success(vec![Content::text(format!(
+                "The DVM ({}) found no skills in the resume. Its answer was:\n{}",
+                provider, analysis.output
+            ))]));
+        }
+
+        let matches = match self.jobs.match_skills(&analysis.skills, args.limit).await {
+            Ok(matches) => matches,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+        let fiat = self.display_currency(None).await;
+        let rate = self.salary_rate(&fiat, matches.iter().map(|(l, _)| l)).await;
+
+        let locale = self.config().locale;
+        let skills = analysis.skills.join(", ");
+        let mut text = format!("{}\n\n", i18n::text_with(locale, "report.resume.skills", &[("provider", &provider), ("skills", &skills)]));
+        if matches.is_empty() {
+            text.push_str(i18n::text(locale, "report.resume.none"));
+        } else {
+            text.push_str(&format!("Found {} matching listing(s):\n\n", matches.len()));
+            for (i, (listing, shared)) in matches.iter().enumerate() {
+                text.push_str(&format!(
+                    "{}. ✅ Matches {}/{}: {}\n{}\n\n",
+                    i + 1,
+                    shared.len(),
+                    analysis.skills.len(),
+                    shared.join(", "),
+                    self.format_job_summary(listing, rate.as_ref())
+                ));
+            }
+        }
+
+        let mut output = CallToolResult::success(vec![Content::text(text)]);
+        output.structured_content = Some(json!({
+            "dvm_request_id": analysis.request_id.to_hex(),
+            "dvm_provider": provider,
+            "skills": analysis.skills,
+            "listings": matches
+                .iter()
+                .map(|(listing, shared)| {
+                    let mut json = self.listing_json(listing, rate.as_ref());
+                    json["matched_skills"] = json!(shared);
+                    json
+                })
+                .collect::<Vec<_>>(),
+        }));
+        Ok(output)
+    }
+
+    #[tool(description = "Recommend listings similar to the ones viewed (get_job_details) or bookmarked so far: sharing their skills or companies, excluding listings already seen. Improves as more listings are viewed.")]
+    pub async fn recommend_jobs(
+        &self,
+        args: Parameters<RecommendJobsArgs>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let owner = self.state_owner(&context)?;
+        self.recommend_jobs_for(&owner, args).await
+    }
+
+    /// `recommend_jobs` for `owner`'s views and bookmarks
+    pub async fn recommend_jobs_for(
+        &self,
+        owner: &str,
+        Parameters(args): Parameters<RecommendJobsArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let state = self.state.get(owner).await;
+        let mut profile = InterestProfile::default();
+        let mut seen: HashSet<String> = HashSet::new();
+        for view in &state.viewed {
+            profile.add(&view.skills, view.company.as_deref(), VIEW_WEIGHT);
+            seen.insert(view.job_id.clone());
+        }
+        let lookups = state.bookmarks.iter().rev().take(RECOMMEND_BOOKMARK_LOOKUPS).map(|b| self.jobs.get(&b.job_id));
+        for (listing, _) in futures::future::join_all(lookups).await.into_iter().flatten() {
+            profile.add(&listing.skills, listing.company.as_deref(), BOOKMARK_WEIGHT);
+        }
+        seen.extend(state.bookmarks.iter().map(|b| b.job_id.clone()));
+
+        if profile.is_empty() {
+            return Ok(CallToolResult::success(vec![Content::text(
+                "No viewed or bookmarked listings to go on yet. Open a few with get_job_details or bookmark_job, then ask again.",
+            )]));
+        }
+
+        let sample = match self.jobs.sample(DEFAULT_STATS_SAMPLE_SIZE, None).await {
+            Ok(sample) => sample,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+        let excluded = JobQuery { exclude_company: state.profile.excluded_companies.clone(), ..Default::default() };
+        let candidates: Vec<JobListing> = sample.listings.into_iter().filter(|l| excluded.matches(l)).collect();
+        let recommendations = profile.recommend(&candidates, &seen, args.limit);
+        let fiat = self.display_currency(Some(owner)).await;
+        let rate = self.salary_rate(&fiat, recommendations.iter().map(|r| &r.listing)).await;
+
+        let interests: Vec<String> = profile.top_skills(5).into_iter().chain(profile.top_companies(2)).collect();
+        let mut text = format!(
+            "🎯 Recommendations from {} viewed and {} bookmarked listing(s)
+Top interests: {}
+
+",
+            state.viewed.len(),
+            state.bookmarks.len(),
+            interests.join(", ")
+        );
+        if recommendations.is_empty() {
+            text.push_str(i18n::text(self.config().locale, "report.recommend.none"));
+        }
+        for (i, recommendation) in recommendations.iter().enumerate() {
+            let mut why = Vec::new();
+            if !recommendation.skills.is_empty() {
+                why.push(format!("skills you looked at: {}", recommendation.skills.join(", ")));
+            }
+            if recommendation.same_company {
+                why.push("a company you looked at".to_string());
+            }
+            text.push_str(&format!(
+                "{}. {}
+💡 Why: {}
+
+",
+                i + 1,
+                self.format_job_summary(&recommendation.listing, rate.as_ref()),
+                why.join("; ")
+            ));
+        }
+
+        let mut output = CallToolResult::success(vec![Content::text(text)]);
+        output.structured_content = Some(json!({
+            "interests": interests,
+            "listings": recommendations
+                .iter()
+                .map(|r| {
+                    let mut json = self.listing_json(&r.listing, rate.as_ref());
+                    json["score"] = json!(r.score);
+                    json["matched_skills"] = json!(r.skills);
+                    json["same_company"] = json!(r.same_company);
+                    json
+                })
+                .collect::<Vec<_>>(),
+        }));
+        Ok(output)
+    }
+
+    fn format_posting_history(history: &PostingHistory) -> String {
+        let date = |ts: Option<Timestamp>| ts.map(|ts| ts.to_human_datetime()).unwrap_or_default();
+        format!(
+            "Listings: {} ({} active, {} expired)\n\
+            First posted: {}\n\
+            Last posted: {}\n\
+            Cadence: {}\n\
+            Reposts: {} listing(s) repeat an earlier company and title",
+            history.listings,
+            history.active,
+            history.expired,
+            date(history.first_posted),
+            date(history.last_posted),
+            match history.mean_interval {
+                Some(interval) => format!("one listing every {:.1} days on average", interval.as_secs_f64() / 86_400.0),
+                None => "a single listing".to_string(),
+            },
+            history.reposts
+        )
+    }
+
+    #[tool(description = "Bookmark a job listing by Job ID or Event ID, with an optional note. Bookmarks are kept across reconnects (per bearer token, or per session).")]
+    pub async fn bookmark_job(
+        &self,
+        Parameters(args): Parameters<BookmarkArgs>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let owner = self.state_owner(&context)?;
+        let updated = self
+            .state
+            .update(&owner, |state| {
+                let bookmark = Bookmark {
+                    job_id: args.job_id.clone(),
+                    note: args.note.clone(),
+                    saved_at: Timestamp::now().as_secs(),
+                };
+                match state.bookmarks.iter_mut().find(|b| b.job_id == args.job_id) {
+                    Some(existing) => {
+                        *existing = bookmark;
+                        true
+                    }
+                    None => {
+                        state.bookmarks.push(bookmark);
+                        false
+                    }
+                }
+            })
+            .await;
+
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "🔖 {} bookmark for job {}",
+            if updated { "Updated" } else { "Added" },
+            args.job_id
+        ))]))
+    }
+
+    #[tool(description = "Remove a bookmarked job")]
+    pub async fn remove_bookmark(
+        &self,
+        Parameters(args): Parameters<GetJobArgs>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let owner = self.state_owner(&context)?;
+        let removed = self
+            .state
+            .update(&owner, |state| {
+                let before = state.bookmarks.len();
+                state.bookmarks.retain(|b| b.job_id != args.job_id);
+                before != state.bookmarks.len()
+            })
+            .await;
+
+        let key = if removed { "report.bookmark.removed" } else { "report.bookmark.missing" };
+        Ok(CallToolResult::success(vec![Content::text(i18n::text_with(self.config().locale, key, &[("job_id", &args.job_id)]))]))
+    }
+
+    #[tool(description = "Track a job application: record that you applied to a listing, or move it to replied, interviewing, rejected or offer, with an optional note. Open applications with no update for APPLICATION_STALE_DAYS are flagged for a follow-up by list_applications.")]
+    pub async fn set_application_status(
+        &self,
+        args: Parameters<ApplicationStatusArgs>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let owner = self.state_owner(&context)?;
+        self.set_application_status_for(&owner, args).await
+    }
+
+    /// `set_application_status` for `owner`
+    pub async fn set_application_status_for(
+        &self,
+        owner: &str,
+        Parameters(args): Parameters<ApplicationStatusArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let job_id = args.job_id.trim().to_string();
+        if job_id.is_empty() {
+            return Err(McpError::invalid_params(i18n::text_with(self.config().locale, "error.empty", &[("field", &"job_id")]), None));
+        }
+        let status = args
+            .status
+            .as_deref()
+            .map(str::parse::<ApplicationStatus>)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?;
+        let note = args.note.map(|n| n.trim().to_string());
+
+        // Title and company are looked up once, for new applications only;
+        // a listing that can't be fetched is still tracked by its id
+        let known = self.state.get(owner).await.applications.iter().any(|a| a.job_id == job_id);
+        let listing = match known {
+            true => None,
+            false => self.jobs.get(&job_id).await.ok().map(|(listing, _)| listing),
+        };
+
+        let now = Timestamp::now().as_secs();
+        let (application, previous) = self
+            .state
+            .update(owner, |state| {
+                let index = match state.applications.iter().position(|a| a.job_id == job_id) {
+                    Some(index) => index,
+                    None => {
+                        state.applications.push(Application {
+                            job_id: job_id.clone(),
+                            title: listing.as_ref().and_then(|l| l.title.clone()),
+                            company: listing.as_ref().and_then(|l| l.company.clone()),
+                            status: ApplicationStatus::Applied,
+                            note: None,
+                            applied_at: now,
+                            updated_at: now,
+                            history: vec![StatusChange { status: ApplicationStatus::Applied, at: now }],
+                        });
+                        state.applications.len() - 1
+                    }
+                };
+                let application = &mut state.applications[index];
+                let previous = application.status;
+                if let Some(status) = status.filter(|s| *s != previous) {
+                    application.status = status;
+                    application.history.push(StatusChange { status, at: now });
+                }
+                if let Some(note) = note {
+                    application.note = Some(note).filter(|n| !n.is_empty());
+                }
+                application.updated_at = now;
+                (application.clone(), previous)
+            })
+            .await;
+
+        let stale_after = self.config().application_stale_after.as_secs();
+        let locale = self.config().locale;
+        let headline = if !known {
+            i18n::text(locale, "report.application.new").to_string()
+        } else if previous != application.status {
+            i18n::text_with(locale, "report.application.moved", &[("from", &previous), ("to", &application.status)])
+        } else {
+            i18n::text(locale, "report.application.updated").to_string()
+        };
+        let mut output = CallToolResult::success(vec![Content::text(format!(
+            "{}\n\n{}",
+            headline,
+            application_line(&application, now, stale_after)
+        ))]);
+        output.structured_content = Some(application_json(&application, now, stale_after));
+        Ok(output)
+    }
+
+    #[tool(description = "List your tracked job applications, most recently updated first, optionally only one status or only those due a follow-up. Open applications (applied, replied, interviewing) with no update for APPLICATION_STALE_DAYS are flagged.")]
+    pub async fn list_applications(
+        &self,
+        args: Parameters<ListApplicationsArgs>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let owner = self.state_owner(&context)?;
+        self.list_applications_for(&owner, args).await
+    }
+
+    /// `list_applications` for `owner`
+    pub async fn list_applications_for(
+        &self,
+        owner: &str,
+        Parameters(args): Parameters<ListApplicationsArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let status = args
+            .status
+            .as_deref()
+            .map(str::parse::<ApplicationStatus>)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?;
+        let stale_only = args.stale_only.unwrap_or(false);
+        let now = Timestamp::now().as_secs();
+        let stale_after = self.config().application_stale_after.as_secs();
+
+        let all = self.state.get(owner).await.applications;
+        let counts: serde_json::Map<String, Value> = ApplicationStatus::ALL
+            .iter()
+            .map(|s| (s.to_string(), json!(all.iter().filter(|a| a.status == *s).count())))
+            .collect();
+        let mut applications: Vec<&Application> = all
+            .iter()
+            .filter(|a| status.is_none_or(|s| a.status == s))
+            .filter(|a| !stale_only || a.is_stale(now, stale_after))
+            .collect();
+        applications.sort_by_key(|a| std::cmp::Reverse(a.updated_at));
+        let stale = all.iter().filter(|a| a.is_stale(now, stale_after)).count();
+
+        let text = if all.is_empty() {
+            "No applications tracked yet. Use set_application_status after applying to a listing.".to_string()
+        } else {
+            let summary = ApplicationStatus::ALL
+                .iter()
+                .filter_map(|s| match all.iter().filter(|a| a.status == *s).count() {
+                    0 => None,
+                    n => Some(format!("{} {}", n, s)),
+                })
+                .collect::<Vec<_>>()
+                .join(" · ");
+            let mut text = format!("📨 {} application(s): {}", all.len(), summary);
+            if stale > 0 {
+                text.push_str(&format!("\n⏰ {} due a follow-up", stale));
+            }
+            if applications.is_empty() {
+                text.push_str("\n\nNone match.");
+            }
+            for application in &applications {
+                text.push_str("\n\n");
+                text.push_str(&application_line(application, now, stale_after));
+            }
+            text
+        };
+
+        let mut output = CallToolResult::success(vec![Content::text(text)]);
+        output.structured_content = Some(json!({
+            "stale_after_days": stale_after / 86400,
+            "counts": counts,
+            "stale": stale,
+            "applications": applications.iter().map(|a| application_json(a, now, stale_after)).collect::<Vec<_>>(),
+        }));
+        Ok(output)
+    }
+
+    #[tool(description = "Follow a job poster by public key (hex or npub). Their listings are shown first, starred, in your search_jobs results.")]
+    pub async fn follow_employer(
+        &self,
+        Parameters(args): Parameters<FollowEmployerArgs>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let owner = self.state_owner(&context)?;
+        let pubkey = parse_pubkey(&args.pubkey)?;
+        let updated = self
+            .state
+            .update(&owner, |state| {
+                let followed = FollowedEmployer {
+                    pubkey: pubkey.to_hex(),
+                    name: args.name.clone(),
+                    followed_at: Timestamp::now().as_secs(),
+                };
+                match state.followed_employers.iter_mut().find(|f| f.pubkey == followed.pubkey) {
+                    Some(existing) => {
+                        *existing = followed;
+                        true
+                    }
+                    None => {
+                        state.followed_employers.push(followed);
+                        false
+                    }
+                }
+            })
+            .await;
+
+        let key = if updated { "report.follow.updated" } else { "report.follow.added" };
+        let name = args.name.as_deref().unwrap_or(&args.pubkey);
+        Ok(CallToolResult::success(vec![Content::text(i18n::text_with(self.config().locale, key, &[("name", &name)]))]))
+    }
+
+    #[tool(description = "Stop following a job poster")]
+    pub async fn unfollow_employer(
+        &self,
+        Parameters(args): Parameters<EmployerArgs>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let owner = self.state_owner(&context)?;
+        let pubkey = parse_pubkey(&args.pubkey)?.to_hex();
+        let removed = self
+            .state
+            .update(&owner, |state| {
+                let before = state.followed_employers.len();
+                state.followed_employers.retain(|f| f.pubkey != pubkey);
+                before != state.followed_employers.len()
+            })
+            .await;
+
+        let key = if removed { "report.follow.removed" } else { "report.follow.missing" };
+        Ok(CallToolResult::success(vec![Content::text(i18n::text_with(self.config().locale, key, &[("name", &args.pubkey)]))]))
+    }
+
+    #[tool(description = "Save a search_jobs query under a name so it can be re-run later with run_saved_search. Accepts the same filters as search_jobs.")]
+    pub async fn save_search(
+        &self,
+        Parameters(args): Parameters<SaveSearchArgs>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let owner = self.state_owner(&context)?;
+        let name = args.name.trim().to_string();
+        if name.is_empty() {
+            return Err(McpError::invalid_params(i18n::text_with(self.config().locale, "error.empty", &[("field", &"name")]), None));
+        }
+
+        let replaced = self
+            .state
+            .update(&owner, |state| {
+                let before = state.saved_searches.len();
+                state.saved_searches.retain(|s| s.name != name);
+                state.saved_searches.push(SavedSearch {
+                    name: name.clone(),
+                    search: args.search,
+                    saved_at: Timestamp::now().as_secs(),
+                });
+                before == state.saved_searches.len()
+            })
+            .await;
+
+        let key = if replaced { "report.saved_search.replaced" } else { "report.saved_search.created" };
+        Ok(CallToolResult::success(vec![Content::text(i18n::text_with(self.config().locale, key, &[("name", &name)]))]))
+    }
+
+    #[tool(description = "Run a search previously stored with save_search")]
+    pub async fn run_saved_search(
+        &self,
+        Parameters(args): Parameters<SavedSearchArgs>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let owner = self.state_owner(&context)?;
+        let state = self.state.get(&owner).await;
+        let Some(saved) = state.saved_searches.into_iter().find(|s| s.name == args.name) else {
+            return Err(McpError::invalid_params(
+                i18n::text_with(self.config().locale, "error.no_saved_search", &[("name", &args.name)]),
+                Some(json!({"name": args.name})),
+            ));
+        };
+        self.search_jobs_for(Some(&owner), Parameters(saved.search)).await
+    }
+
+    #[tool(description = "Delete a saved search")]
+    pub async fn delete_saved_search(
+        &self,
+        Parameters(args): Parameters<SavedSearchArgs>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let owner = self.state_owner(&context)?;
+        let removed = self
+            .state
+            .update(&owner, |state| {
+                let before = state.saved_searches.len();
+                state.saved_searches.retain(|s| s.name != args.name);
+                before != state.saved_searches.len()
+            })
+            .await;
+
+        let key = if removed { "report.saved_search.deleted" } else { "report.saved_search.missing" };
+        Ok(CallToolResult::success(vec![Content::text(i18n::text_with(self.config().locale, key, &[("name", &args.name)]))]))
+    }
+
+    #[tool(description = "Set a client preference (any JSON value), or remove it by omitting value. Preferences are returned by get_saved_state. Known keys: currency (fiat salaries are shown in) and plain_text (true strips emoji and box drawing from results, for screen readers).")]
+    pub async fn set_preference(
+        &self,
+        Parameters(args): Parameters<SetPreferenceArgs>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let owner = self.state_owner(&context)?;
+        self.set_preference_for(&owner, Parameters(args)).await
+    }
+
+    /// `set_preference` for `owner`
+    pub async fn set_preference_for(&self, owner: &str, Parameters(args): Parameters<SetPreferenceArgs>) -> Result<CallToolResult, McpError> {
+        let locale = self.config().locale;
+        let message = match &args.value {
+            Some(value) => i18n::text_with(locale, "report.preference.set", &[("key", &args.key), ("value", value)]),
+            None => i18n::text_with(locale, "report.preference.removed", &[("key", &args.key)]),
+        };
+        self.state
+            .update(owner, |state| match args.value {
+                Some(value) => {
+                    state.preferences.insert(args.key, value);
+                }
+                None => {
+                    state.preferences.remove(&args.key);
+                }
+            })
+            .await;
+
+        Ok(CallToolResult::success(vec![Content::text(message)]))
+    }
+
+    #[tool(description = "Set your candidate profile: preferred skills, minimum salary and locations, used by search_jobs (and saved searches) whenever a search doesn't set them, and companies to leave out of searches and recommendations. Omitted fields are unchanged; an empty list or 0 clears one.")]
+    pub async fn set_preferences(
+        &self,
+        args: Parameters<SetPreferencesArgs>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let owner = self.state_owner(&context)?;
+        self.set_preferences_for(&owner, args).await
+    }
+
+    /// `set_preferences` for `owner`
+    pub async fn set_preferences_for(
+        &self,
+        owner: &str,
+        Parameters(args): Parameters<SetPreferencesArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let clean = |values: Vec<String>| -> Vec<String> {
+            values.into_iter().map(|v| v.trim().to_string()).filter(|v| !v.is_empty()).collect()
+        };
+        let profile = self
+            .state
+            .update(owner, |state| {
+                let profile = &mut state.profile;
+                if let Some(skills) = args.skills {
+                    profile.skills = clean(skills);
+                }
+                if let Some(amount) = args.min_salary {
+                    profile.min_salary = (amount > 0.0).then_some(amount);
+                }
+                if let Some(locations) = args.locations {
+                    profile.locations = clean(locations);
+                }
+                if let Some(companies) = args.excluded_companies {
+                    profile.excluded_companies = clean(companies);
+                }
+                profile.clone()
+            })
+            .await;
+        Ok(self.format_profile("report.preferences.saved", &profile, owner).await)
+    }
+
+    #[tool(description = "Show your candidate profile (preferred skills, minimum salary, locations, excluded companies) as set with set_preferences")]
+    pub async fn get_preferences(&self, context: RequestContext<RoleServer>) -> Result<CallToolResult, McpError> {
+        let owner = self.state_owner(&context)?;
+        let profile = self.state.get(&owner).await.profile;
+        Ok(self.format_profile("report.preferences.title", &profile, &owner).await)
+    }
+
+    /// The profile under the catalog text `title`
+    async fn format_profile(&self, title: &str, profile: &CandidateProfile, owner: &str) -> CallToolResult {
+        let locale = self.config().locale;
+        let any = i18n::text(locale, "report.preferences.any");
+        let list = |values: &[String]| if values.is_empty() { any.to_string() } else { values.join(", ") };
+        let fiat = self.display_currency(Some(owner)).await;
+        let text = i18n::text_with(
+            locale,
+            "report.preferences.body",
+            &[
+                ("title", &i18n::text(locale, title)),
+                ("skills", &list(&profile.skills)),
+                ("min_salary", &profile.min_salary.map(|amount| format!("{} {}", amount, fiat)).unwrap_or_else(|| any.to_string())),
+                ("locations", &list(&profile.locations)),
+                (
+                    "excluded",
+                    &match profile.excluded_companies.is_empty() {
+                        true => i18n::text(locale, "report.preferences.none").to_string(),
+                        false => profile.excluded_companies.join(", "),
+                    },
+                ),
+            ],
+        );
+        let mut output = CallToolResult::success(vec![Content::text(text)]);
+        output.structured_content = Some(json!({
+            "skills": profile.skills,
+            "min_salary": profile.min_salary,
+            "salary_currency": fiat,
+            "locations": profile.locations,
+            "excluded_companies": profile.excluded_companies,
+        }));
+        output
+    }
+
+    #[tool(description = "Show your bookmarks, saved searches, followed employers and preferences, so a reconnecting client can resume where it left off")]
+    pub async fn get_saved_state(
+        &self,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let owner = self.state_owner(&context)?;
+        let state = self.state.get(&owner).await;
+        let locale = self.config().locale;
+        if state.is_empty() {
+            return Ok(CallToolResult::success(vec![Content::text(i18n::text(locale, "report.saved_state.empty"))]));
+        }
+        let at = |secs: u64| Timestamp::from(secs).to_human_datetime();
+
+        let bookmarks = state
+            .bookmarks
+            .iter()
+            .map(|b| {
+                format!(
+                    "  • {}{}",
+                    i18n::text_with(locale, "report.saved_state.bookmark", &[("job_id", &b.job_id), ("at", &at(b.saved_at))]),
+                    b.note.as_ref().map(|n| format!(" - {}", n)).unwrap_or_default()
+                )
+            })
+            .collect::<Vec<_>>();
+        let searches = state
+            .saved_searches
+            .iter()
+            .map(|s| format!("  • {}: {}", s.name, serde_json::to_string(&s.search).unwrap_or_default()))
+            .collect::<Vec<_>>();
+        let followed = state
+            .followed_employers
+            .iter()
+            .map(|f| {
+                let follow = i18n::text_with(
+                    locale,
+                    "report.saved_state.follow",
+                    &[
+                        ("name", &f.name.as_ref().map(|n| format!("{} - ", n)).unwrap_or_default()),
+                        ("npub", &PublicKey::from_hex(&f.pubkey).ok().and_then(|pk| pk.to_bech32().ok()).unwrap_or_else(|| f.pubkey.clone())),
+                        ("at", &at(f.followed_at)),
+                    ],
+                );
+                format!("  • {}", follow)
+            })
+            .collect::<Vec<_>>();
+        let preferences = state
+            .preferences
+            .iter()
+            .map(|(key, value)| format!("  • {} = {}", key, value))
+            .collect::<Vec<_>>();
+        let viewed = state
+            .viewed
+            .iter()
+            .rev()
+            .map(|v| format!("  • {}", i18n::text_with(locale, "report.saved_state.view", &[("job_id", &v.job_id), ("at", &at(v.viewed_at))])))
+            .collect::<Vec<_>>();
+
+        let drafts = state
+            .drafts
+            .iter()
+            .map(|d| {
+                let draft = i18n::text_with(locale, "report.saved_state.draft", &[("job_id", &d.job_id), ("title", &d.title), ("at", &at(d.updated_at))]);
+                format!("  • {}", draft)
+            })
+            .collect::<Vec<_>>();
+
+        let now = Timestamp::now().as_secs();
+        let stale_after = self.config().application_stale_after.as_secs();
+        let applications = state
+            .applications
+            .iter()
+            .map(|a| {
+                format!(
+                    "  • {}{}",
+                    i18n::text_with(
+                        locale,
+                        "report.saved_state.application",
+                        &[("job_id", &a.job_id), ("status", &a.status), ("at", &at(a.updated_at))],
+                    ),
+                    if a.is_stale(now, stale_after) { i18n::text(locale, "report.saved_state.follow_up") } else { "" }
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let section = |key: &str, lines: Vec<String>| {
+            let title = i18n::text(locale, key);
+            if lines.is_empty() {
+                i18n::text_with(locale, "report.saved_state.section_empty", &[("title", &title)])
+            } else {
+                format!("{} ({}):\n{}", title, lines.len(), lines.join("\n"))
+            }
+        };
+
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "{}\n\n{}\n\n{}\n\n{}\n\n{}\n\n{}\n\n{}\n\n{}",
+            i18n::text(locale, "report.saved_state.title"),
+            section("report.saved_state.bookmarks", bookmarks),
+            section("report.saved_state.searches", searches),
+            section("report.saved_state.followed", followed),
+            section("report.preferences.title", preferences),
+            section("report.saved_state.viewed", viewed),
+            section("report.saved_state.drafts", drafts),
+            section("report.saved_state.applications", applications)
+        ))]))
+    }
+
+    #[tool(description = "Write or edit an unpublished job listing. Omit job_id to start a new draft; pass it to change only the fields given. Returns a preview and anything that must be fixed before publish_draft. Nothing is signed or sent to relays.")]
+    pub async fn draft_job(
+        &self,
+        args: Parameters<DraftJobArgs>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let owner = self.state_owner(&context)?;
+        self.draft_job_for(&owner, args).await
+    }
+
+    /// `draft_job` for `owner`
+    pub async fn draft_job_for(&self, owner: &str, Parameters(args): Parameters<DraftJobArgs>) -> Result<CallToolResult, McpError> {
+        let clean = |value: String| Some(value.trim().to_string()).filter(|v| !v.is_empty());
+        let clean_all = |values: Vec<String>| -> Vec<String> { values.into_iter().filter_map(clean).collect() };
+        let now = Timestamp::now().as_secs();
+        let job_id = args.job_id.as_deref().and_then(|id| clean(id.to_string()));
+        if job_id.is_none() && args.title.as_deref().and_then(|t| clean(t.to_string())).is_none() {
+            return Err(McpError::invalid_params(i18n::text(self.config().locale, "error.draft_title").to_string(), None));
+        }
+        if args.expires_in_days.is_some_and(|days| days > MAX_EXPIRY_DAYS) {
+            return Err(self.out_of_range("expires_in_days", 0, MAX_EXPIRY_DAYS));
+        }
+
+        let draft = self
+            .state
+            .update(owner, |state| {
+                let index = match job_id.as_ref().and_then(|id| state.drafts.iter().position(|d| &d.job_id == id)) {
+                    Some(index) => index,
+                    None => {
+                        let title = args.title.as_deref().unwrap_or("listing");
+                        state.drafts.push(JobDraft {
+                            job_id: job_id.clone().unwrap_or_else(|| format!("{}-{}", slug(title), now)),
+                            ..Default::default()
+                        });
+                        state.drafts.len() - 1
+                    }
+                };
+                let draft = &mut state.drafts[index];
+                if let Some(title) = args.title {
+                    draft.title = title.trim().to_string();
+                }
+                if let Some(company) = args.company {
+                    draft.company = clean(company);
+                }
+                if let Some(location) = args.location {
+                    draft.location = clean(location);
+                }
+                if let Some(types) = args.employment_types {
+                    draft.employment_types = clean_all(types);
+                }
+                if let Some(skills) = args.skills {
+                    draft.skills = clean_all(skills);
+                }
+                if args.salary_min == Some(0.0) {
+                    draft.salary = None;
+                } else if args.salary_min.is_some() || args.salary_max.is_some() || args.salary_currency.is_some() || args.salary_period.is_some() {
+                    let current = draft.salary.take();
+                    let min = args.salary_min.or(current.as_ref().map(|s| s.min)).unwrap_or_default();
+                    draft.salary = Some(SalaryRange {
+                        min,
+                        max: args.salary_max.or(current.as_ref().map(|s| s.max)).unwrap_or(min),
+                        currency: args
+                            .salary_currency
+                            .map(|c| c.trim().to_uppercase())
+                            .or(current.as_ref().map(|s| s.currency.clone()))
+                            .unwrap_or_else(|| "USD".to_string()),
+                        period: args
+                            .salary_period
+                            .map(|p| p.trim().to_lowercase())
+                            .or(current.map(|s| s.period))
+                            .unwrap_or_else(|| "year".to_string()),
+                    });
+                }
+                if let Some(description) = args.description {
+                    draft.description = description.trim().to_string();
+                }
+                if let Some(days) = args.expires_in_days {
+                    draft.expires_at = (days > 0).then(|| now + days * 86_400);
+                }
+                draft.updated_at = now;
+                draft.clone()
+            })
+            .await;
+        self.render_draft(owner, &draft, "report.draft.saved").await
+    }
+
+    #[tool(description = "Show a draft exactly as candidates would see it once published (the search result summary and full details), with anything that must be fixed before publish_draft")]
+    pub async fn preview_job(
+        &self,
+        args: Parameters<DraftIdArgs>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let owner = self.state_owner(&context)?;
+        self.preview_job_for(&owner, args).await
+    }
+
+    /// `preview_job` for `owner`
+    pub async fn preview_job_for(&self, owner: &str, Parameters(args): Parameters<DraftIdArgs>) -> Result<CallToolResult, McpError> {
+        let draft = self.find_draft(owner, &args.job_id).await?;
+        self.render_draft(owner, &draft, "report.draft.preview").await
+    }
+
+    #[tool(description = "Sign a draft with the board's posting key and publish it to the relays, rate-limited per relay. The draft is removed once a relay accepts it.")]
+    pub async fn publish_draft(
+        &self,
+        args: Parameters<DraftIdArgs>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let owner = self.state_owner(&context)?;
+        self.publish_draft_for(&owner, args).await
+    }
+
+    /// `publish_draft` for `owner`
+    pub async fn publish_draft_for(&self, owner: &str, Parameters(args): Parameters<DraftIdArgs>) -> Result<CallToolResult, McpError> {
+        let draft = self.find_draft(owner, &args.job_id).await?;
+        let listing = match self.jobs.publish_draft(&draft).await {
+            Ok(listing) => listing,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+        self.state.update(owner, |state| state.drafts.retain(|d| d.job_id != draft.job_id)).await;
+
+        let fiat = self.display_currency(Some(owner)).await;
+        let rate = self.salary_rate(&fiat, [&listing]).await;
+        let mut output = CallToolResult::success(vec![Content::text(format!(
+            "🚀 Published job {} as event {}\n\n{}",
+            listing.job_id,
+            listing.event.id.to_hex(),
+            self.format_job_summary(&listing, rate.as_ref())
+        ))]);
+        output.structured_content = Some(self.listing_json(&listing, rate.as_ref()));
+        Ok(output)
+    }
+
+    #[tool(description = "List the listings published with the board's posting key (publish_draft, bulk_publish_jobs) whose NIP-40 expiration is near, soonest first, so they can be renewed (published again) or retracted before they silently vanish")]
+    pub async fn list_expiring_jobs(&self, Parameters(args): Parameters<ExpiringJobsArgs>) -> Result<CallToolResult, McpError> {
+        let within = match args.within_days {
+            Some(days) => Duration::from_secs(days.clamp(1, 365) * 86_400),
+            None => self.config().expiry_reminder_window,
+        };
+        let days = within.as_secs() / 86_400;
+        let listings = match self.jobs.expiring_listings(within).await {
+            Ok(listings) => listings,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+
+        let now = Timestamp::now().as_secs();
+        let text = match listings.is_empty() {
+            true => i18n::text_with(self.config().locale, "report.expiring.none", &[("days", &days)]),
+            false => format!(
+                "⏳ {} posted listing(s) expire in the next {} day(s):\n\n{}",
+                listings.len(),
+                days,
+                listings
+                    .iter()
+                    .enumerate()
+                    .map(|(i, listing)| {
+                        let expires_at = listing.expires_at.unwrap_or_default();
+                        let left = expires_at.as_secs().saturating_sub(now);
+                        format!(
+                            "{}. {} at {} (🆔 {})\n   Expires {} (in {}d {}h)",
+                            i + 1,
+                            listing.title.as_deref().unwrap_or("Untitled"),
+                            listing.company.as_deref().unwrap_or("Unknown"),
+                            listing.job_id,
+                            expires_at.to_human_datetime(),
+                            left / 86_400,
+                            left % 86_400 / 3600
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ),
+        };
+        let mut output = CallToolResult::success(vec![Content::text(text)]);
+        output.structured_content = Some(json!({
+            "within_days": days,
+            "listings": listings.iter().map(|l| json!({
+                "job_id": l.job_id,
+                "title": l.title,
+                "company": l.company,
+                "event_id": l.event.id.to_hex(),
+                "expires_at": l.expires_at.map(|at| at.as_secs()),
+            })).collect::<Vec<_>>(),
+        }));
+        Ok(output)
+    }
+
+    #[tool(description = "Schedule (or reschedule) an interview for an application to a listing: a NIP-52 calendar event with the time, participants and location or call link, signed with the board's posting key and published to the relays, so the participants' calendar clients pick it up")]
+    pub async fn schedule_interview(&self, Parameters(args): Parameters<ScheduleInterviewArgs>) -> Result<CallToolResult, McpError> {
+        let job_id = args.job_id.trim().to_string();
+        if job_id.is_empty() {
+            return Err(McpError::invalid_params(i18n::text_with(self.config().locale, "error.empty", &[("field", &"job_id")]), None));
+        }
+        let start = match args.start.trim().parse::<u64>() {
+            Ok(secs) => Timestamp::from(secs),
+            Err(_) => parse_rfc3339(&args.start).ok_or_else(|| {
+                McpError::from(JobsError::ParseError {
+                    what: "start",
+                    input: args.start.clone(),
+                    message: "expected RFC 3339 or a unix timestamp".to_string(),
+                })
+            })?,
+        };
+        let duration = args.duration_minutes.unwrap_or(60);
+        if duration > MAX_INTERVIEW_MINUTES {
+            return Err(self.out_of_range("duration_minutes", 0, MAX_INTERVIEW_MINUTES));
+        }
+        let participants = args.participants.iter().map(|p| parse_pubkey(p)).collect::<Result<Vec<_>, _>>()?;
+        let clean = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        let title = match clean(args.title) {
+            Some(title) => title,
+            None => match self.jobs.get(&job_id).await {
+                Ok((listing, _)) => format!("Interview: {}", listing.title.as_deref().unwrap_or(&job_id)),
+                Err(_) => format!("Interview: {}", job_id),
+            },
+        };
+        let plan = InterviewPlan {
+            id: clean(args.interview_id).unwrap_or_else(|| format!("interview-{}-{}", slug(&job_id), start.as_secs())),
+            job_id,
+            title,
+            start,
+            end: match duration {
+                0 => None,
+                minutes => Some(Timestamp::from(start.as_secs().saturating_add(minutes * 60))),
+            },
+            location: clean(args.location),
+            participants,
+            description: clean(args.description).unwrap_or_default(),
+        };
+        let interview = match self.jobs.schedule_interview(&plan).await {
+            Ok(interview) => interview,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+
+        let mut output = CallToolResult::success(vec![Content::text(format!(
+            "{}\n\n{}",
+            i18n::text_with(self.config().locale, "report.interview.scheduled", &[("event_id", &interview.event_id.to_hex())]),
+            format_interview(&interview, None)
+        ))]);
+        output.structured_content = Some(interview_json(&interview, None));
+        Ok(output)
+    }
+
+    #[tool(description = "List upcoming interviews (NIP-52 calendar events), soonest first: those a participant is invited to, or by default those the board scheduled. Interviews for listings you track with set_application_status show that application's status.")]
+    pub async fn list_upcoming_interviews(
+        &self,
+        args: Parameters<UpcomingInterviewsArgs>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        self.list_upcoming_interviews_for(auth::state_owner(&context).as_deref(), args).await
+    }
+
+    /// `list_upcoming_interviews` for `owner`, whose tracked applications
+    /// are matched to the interviews
+    pub async fn list_upcoming_interviews_for(
+        &self,
+        owner: Option<&str>,
+        Parameters(args): Parameters<UpcomingInterviewsArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let participant = args.participant.as_deref().map(parse_pubkey).transpose()?;
+        let job_id = args.job_id.as_deref().map(str::trim).filter(|id| !id.is_empty());
+        let interviews = match self.jobs.upcoming_interviews(participant, job_id).await {
+            Ok(interviews) => interviews,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+        let applications = match owner {
+            Some(owner) => self.state.get(owner).await.applications,
+            None => Vec::new(),
+        };
+        let application_of = |interview: &Interview| applications.iter().find(|a| a.job_id == interview.plan.job_id);
+
+        let locale = self.config().locale;
+        let text = match interviews.is_empty() {
+            true => i18n::text(locale, "report.interview.none").to_string(),
+            false => format!(
+                "{}\n\n{}",
+                i18n::text_with(locale, "report.interview.upcoming", &[("count", &interviews.len())]),
+                interviews
+                    .iter()
+                    .enumerate()
+                    .map(|(i, interview)| format!("{}. {}", i + 1, format_interview(interview, application_of(interview))))
+                    .collect::<Vec<_>>()
+                    .join("\n\n")
+            ),
+        };
+        let mut output = CallToolResult::success(vec![Content::text(text)]);
+        output.structured_content = Some(json!({
+            "interviews": interviews.iter().map(|i| interview_json(i, application_of(i))).collect::<Vec<_>>(),
+        }));
+        Ok(output)
+    }
+
+    #[tool(description = "Search upcoming career fairs, hiring meetups and other public NIP-52 calendar events tagged with career topics (CAREER_EVENT_TOPICS), soonest first, by words, topic, location or how soon they start")]
+    pub async fn search_career_events(&self, Parameters(args): Parameters<SearchCareerEventsArgs>) -> Result<CallToolResult, McpError> {
+        let events = match self.jobs.career_events().await {
+            Ok(events) => events,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+        let lower = |value: &Option<String>| value.as_deref().map(|v| v.trim().trim_start_matches('#').to_lowercase()).filter(|v| !v.is_empty());
+        let (query, topic, location) = (lower(&args.query), lower(&args.topic), lower(&args.location));
+        let before = args.within_days.map(|days| Timestamp::now().as_secs() + days.clamp(1, 365) * 86_400);
+        let limit = args.limit.unwrap_or(20).clamp(1, 100);
+
+        let matching: Vec<&CareerEvent> = events
+            .iter()
+            .filter(|e| {
+                query.as_ref().is_none_or(|query| {
+                    let text = format!("{} {} {}", e.title, e.summary.as_deref().unwrap_or_default(), e.description).to_lowercase();
+                    query.split_whitespace().all(|word| text.contains(word))
+                })
+            })
+            .filter(|e| topic.as_ref().is_none_or(|topic| e.topics.contains(topic)))
+            .filter(|e| location.as_ref().is_none_or(|l| e.location.as_deref().is_some_and(|at| at.to_lowercase().contains(l))))
+            .filter(|e| before.is_none_or(|before| e.start.as_secs() <= before))
+            .take(limit)
+            .collect();
+
+        let text = match matching.is_empty() {
+            true => "No upcoming career events found".to_string(),
+            false => format!(
+                "🎪 {} upcoming career event(s):\n\n{}",
+                matching.len(),
+                matching
+                    .iter()
+                    .enumerate()
+                    .map(|(i, event)| format!("{}. {}", i + 1, format_career_event(event)))
+                    .collect::<Vec<_>>()
+                    .join("\n\n")
+            ),
+        };
+        let mut output = CallToolResult::success(vec![Content::text(text)]);
+        output.structured_content = Some(json!({
+            "events": matching.iter().map(|e| json!({
+                "id": e.id,
+                "title": e.title,
+                "summary": e.summary,
+                "start": e.start.as_secs(),
+                "end": e.end.map(|end| end.as_secs()),
+                "all_day": e.all_day,
+                "location": e.location,
+                "topics": e.topics,
+                "organizer": e.author.to_bech32().unwrap_or_else(|_| e.author.to_hex()),
+                "event_id": e.event_id.to_hex(),
+                "naddr": e.naddr(),
+            })).collect::<Vec<_>>(),
+        }));
+        Ok(output)
+    }
+
+    #[tool(description = "Engagement with each listing published with the board's posting key over the last days (default 7, max 30): how often it was returned by search_jobs, opened with get_job_details, zapped (NIP-57) and commented on, in total and per day. Most engaged first. Impressions and views are counted since this server started.")]
+    pub async fn employer_analytics(&self, Parameters(args): Parameters<EmployerAnalyticsArgs>) -> Result<CallToolResult, McpError> {
+        let days = args.days.unwrap_or(7).clamp(1, 30);
+        let analytics = match self.jobs.employer_analytics(days).await {
+            Ok(analytics) => analytics,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+        if analytics.is_empty() {
+            return Ok(CallToolResult::success(vec![Content::text(
+                "No listings published with the posting key yet".to_string(),
+            )]));
+        }
+
+        let text = format!(
+            "📈 Engagement over the last {} day(s), {} listing(s):\n\n{}",
+            days,
+            analytics.len(),
+            analytics.iter().enumerate().map(|(i, a)| Self::format_listing_analytics(i + 1, a)).collect::<Vec<_>>().join("\n\n")
+        );
+        let counts = |e: &crate::jobs::engagement::DayEngagement| {
+            json!({
+                "impressions": e.impressions,
+                "views": e.views,
+                "zaps": e.zaps,
+                "zapped_sats": e.zapped_sats,
+                "comments": e.comments,
+            })
+        };
+        let mut output = CallToolResult::success(vec![Content::text(text)]);
+        output.structured_content = Some(json!({
+            "days": days,
+            "listings": analytics.iter().map(|a| {
+                let mut listing = counts(&a.total);
+                listing["job_id"] = json!(a.listing.job_id);
+                listing["title"] = json!(a.listing.title);
+                listing["expired"] = json!(a.listing.is_expired());
+                listing["daily"] = a.daily.iter().map(|(day, e)| {
+                    let mut entry = counts(e);
+                    entry["date"] = json!(Timestamp::from(*day).to_human_datetime().chars().take(10).collect::<String>());
+                    entry
+                }).collect();
+                listing
+            }).collect::<Vec<_>>(),
+        }));
+        Ok(output)
+    }
+
+    fn format_listing_analytics(rank: usize, analytics: &ListingAnalytics) -> String {
+        let total = &analytics.total;
+        let daily = analytics
+            .daily
+            .iter()
+            .filter(|(_, e)| !e.is_empty())
+            .map(|(day, e)| {
+                format!(
+                    "   {}: {} impressions, {} views, {} zaps, {} comments",
+                    Timestamp::from(*day).to_human_datetime().chars().take(10).collect::<String>(),
+                    e.impressions,
+                    e.views,
+                    e.zaps,
+                    e.comments
+                )
+            })
+            .collect::<Vec<_>>();
+        format!(
+            "{}. {}{} (🆔 {})\n   🔎 {} impressions · 👀 {} views · ⚡ {} zaps ({} sats) · 💬 {} comments{}",
+            rank,
+            analytics.listing.title.as_deref().unwrap_or("Untitled"),
+            if analytics.listing.is_expired() { " [EXPIRED]" } else { "" },
+            analytics.listing.job_id,
+            total.impressions,
+            total.views,
+            total.zaps,
+            total.zapped_sats,
+            total.comments,
+            if daily.is_empty() { String::new() } else { format!("\n{}", daily.join("\n")) }
+        )
+    }
+
+    async fn find_draft(&self, owner: &str, job_id: &str) -> Result<JobDraft, McpError> {
+        self.state
+            .get(owner)
+            .await
+            .drafts
+            .into_iter()
+            .find(|d| d.job_id == job_id.trim())
+            .ok_or_else(|| McpError::invalid_params(i18n::text_with(self.config().locale, "error.no_draft", &[("job_id", &job_id)]), None))
+    }
+
+    /// A draft rendered like a published listing, followed by what blocks publishing it
+    /// `draft` previewed under the catalog text `title`
+    async fn render_draft(&self, owner: &str, draft: &JobDraft, title: &str) -> Result<CallToolResult, McpError> {
+        let locale = self.config().locale;
+        let listing = draft.preview(self.jobs.listing_kind()).map_err(McpError::from)?;
+        let fiat = self.display_currency(Some(owner)).await;
+        let rate = self.salary_rate(&fiat, [&listing]).await;
+        let problems = draft.problems();
+        let status = match problems.is_empty() {
+            true => i18n::text(locale, "report.draft.ready").to_string(),
+            false => format!(
+                "{}\n{}",
+                i18n::text(locale, "report.draft.fix"),
+                problems.iter().map(|p| format!("  • {}", p)).collect::<Vec<_>>().join("\n")
+            ),
+        };
+        let text = i18n::text_with(
+            locale,
+            "report.draft.body",
+            &[
+                ("title", &i18n::text(locale, title)),
+                ("summary", &self.format_job_summary(&listing, rate.as_ref())),
+                ("details", &i18n::text(locale, "report.job.details")),
+                (
+                    "description",
+                    &match draft.description.is_empty() {
+                        true => i18n::text(locale, "report.draft.no_description"),
+                        false => draft.description.as_str(),
+                    },
+                ),
+                ("status", &status),
+            ],
+        );
+        let mut output = CallToolResult::success(vec![Content::text(text)]);
+        output.structured_content = Some(json!({
+            "draft": draft,
+            "problems": problems,
+            "ready": problems.is_empty(),
+        }));
+        Ok(output)
+    }
+
+    /// Metrics in the Prometheus text exposition format, for the `/metrics` endpoint
+    pub async fn prometheus_metrics(&self) -> String {
+        let usage = self.jobs.cache_usage().await;
+        self.jobs.metrics().read().await.format_prometheus(&usage)
+    }
+
+    /// The `get_performance_metrics` JSON snapshot, for the admin HTTP API
+    pub async fn metrics_json(&self) -> serde_json::Value {
+        let usage = self.jobs.cache_usage().await;
+        self.jobs.metrics().read().await.to_json(&usage)
+    }
+
+    /// Contents of `jobs://mine`: for the operator's posting key, its active
+    /// listings with a week of engagement, the application DMs of the last
+    /// week, and the listings expiring within `expiry_reminder_window`, for
+    /// a daily review
+    pub async fn operator_dashboard_json(&self) -> Result<serde_json::Value, McpError> {
+        let Some(operator) = self.jobs.posting_public_key() else {
+            return Err(JobsError::Config("jobs://mine needs POSTING_SECRET_KEY".to_string()).into());
+        };
+        let window = self.config().expiry_reminder_window;
+        let analytics = match self.jobs.employer_analytics(7).await {
+            Ok(analytics) => analytics,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+        let job_ids: Vec<String> = analytics.iter().map(|a| a.listing.job_id.clone()).collect();
+        let since = Timestamp::from(Timestamp::now().as_secs().saturating_sub(7 * 86_400));
+        let (messages, expiring) = match tokio::try_join!(
+            self.jobs.application_messages(since, &job_ids),
+            self.jobs.expiring_listings(window)
+        ) {
+            Ok(found) => found,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+
+        let active: Vec<&ListingAnalytics> = analytics.iter().filter(|a| !a.listing.is_expired()).collect();
+        let mut engagement = crate::jobs::engagement::DayEngagement::default();
+        active.iter().for_each(|a| engagement.add(&a.total));
+        let preview = |content: &str| content.chars().take(280).collect::<String>();
+        Ok(json!({
+            "operator": operator.to_bech32().unwrap_or_else(|_| operator.to_hex()),
+            "generated_at": Timestamp::now().as_secs(),
+            "summary": {
+                "active_listings": active.len(),
+                "applications_7d": messages.len(),
+                "expiring_soon": expiring.len(),
+                "impressions_7d": engagement.impressions,
+                "views_7d": engagement.views,
+                "zaps_7d": engagement.zaps,
+                "zapped_sats_7d": engagement.zapped_sats,
+                "comments_7d": engagement.comments,
+            },
+            "active_listings": active.iter().map(|a| json!({
+                "job_id": a.listing.job_id,
+                "title": a.listing.title,
+                "posted_at": a.listing.created_at.as_secs(),
+                "expires_at": a.listing.expires_at.map(|at| at.as_secs()),
+                "applications_7d": messages.iter().filter(|m| m.job_id.as_ref() == Some(&a.listing.job_id)).count(),
+                "impressions_7d": a.total.impressions,
+                "views_7d": a.total.views,
+                "zaps_7d": a.total.zaps,
+                "comments_7d": a.total.comments,
+            })).collect::<Vec<_>>(),
+            "applications": messages.iter().map(|m| json!({
+                "from": m.sender.to_bech32().unwrap_or_else(|_| m.sender.to_hex()),
+                "sent_at": m.sent_at.as_secs(),
+                "job_id": m.job_id,
+                "message": preview(&m.content),
+            })).collect::<Vec<_>>(),
+            "expiring": expiring.iter().map(|l| json!({
+                "job_id": l.job_id,
+                "title": l.title,
+                "expires_at": l.expires_at.map(|at| at.as_secs()),
+            })).collect::<Vec<_>>(),
+            "expiring_within_days": window.as_secs() / 86_400,
+        }))
+    }
+
+    /// Contents of `jobs://health`: overall status (`ok`, `degraded` when
+    /// some relay fails its probe or is disconnected or a background loop
+    /// is waiting to restart, `down` when no relay answers, `offline` when
+    /// relays are disabled and only stopped loops count), each relay's
+    /// state, how fresh the cache is, how many listings are indexed, when
+    /// relays last answered, and the background tasks
+    pub async fn health_json(&self) -> serde_json::Value {
+        let relays = self.jobs.relay_reports().await;
+        let healthy = self.jobs.is_healthy().await;
+        let failing: Vec<&str> = relays
+            .iter()
+            .filter(|r| {
+                r.probe.as_ref().is_some_and(|p| !p.healthy) || r.connection.as_ref().is_some_and(|c| !c.connected)
+            })
+            .map(|r| r.url.as_str())
+            .collect();
+        let tasks = self.jobs.task_reports();
+        let stopped: Vec<&str> = tasks.iter().filter(|t| t.supervised && !t.running).map(|t| t.name).collect();
+        let offline = self.config().offline;
+        let status = if offline && stopped.is_empty() {
+            "offline"
+        } else if offline {
+            "degraded"
+        } else if !healthy {
+            "down"
+        } else if !failing.is_empty() || !stopped.is_empty() {
+            "degraded"
+        } else {
+            "ok"
+        };
+
+        let entries = self.jobs.cache_entries().await;
+        let stale = entries.iter().filter(|e| e.age > self.jobs.cache_ttl(&e.key)).count();
+        let usage = self.jobs.cache_usage().await;
+        let ingest = self.jobs.ingest_status().await;
+        let last_answer = relays.iter().filter_map(|r| r.last_answer).min();
+
+        json!({
+            "status": status,
+            "offline": offline,
+            "relays_healthy": healthy,
+            "failing_relays": failing,
+            "relays": relays.iter().map(Self::relay_report_json).collect::<Vec<_>>(),
+            "last_successful_fetch_secs_ago": last_answer.map(|ago| ago.as_secs()),
+            "cache": {
+                "entries": usage.entries,
+                "fresh_entries": entries.len() - stale,
+                "stale_entries": stale,
+                "newest_secs": entries.iter().map(|e| e.age.as_secs()).min(),
+                "oldest_secs": entries.iter().map(|e| e.age.as_secs()).max(),
+                "search_ttl_secs": self.config().search_cache_ttl.as_secs(),
+                "stats_ttl_secs": self.config().stats_cache_ttl.as_secs(),
+            },
+            "index": {
+                "cached_events": usage.events,
+                "approx_bytes": usage.bytes,
+                "ingested_listings": ingest.iter().map(|s| s.listings).sum::<usize>(),
+                "ingest_sources": ingest.iter().map(|s| json!({
+                    "source": s.source,
+                    "listings": s.listings,
+                    "refreshed_at": s.refreshed_at.map(|at| at.as_secs()),
+                    "error": s.error,
+                })).collect::<Vec<_>>(),
+            },
+            "background_tasks": tasks.iter().map(|t| json!({
+                "name": t.name,
+                "running": t.running,
+                "panics": t.panics,
+                "last_panic": t.last_panic,
+                "last_panic_at": t.last_panic_at.map(|at| at.as_secs()),
+            })).collect::<Vec<_>>(),
+            "stopped_tasks": stopped,
+            "checked_at": Timestamp::now().as_secs(),
+        })
+    }
+
+    /// Record an admin action taken outside an MCP session
+    pub(crate) async fn audit(&self, entry: &AuditEntry) {
+        if self.audit_log.is_enabled() {
+            self.audit_log.append(entry).await;
+        }
+    }
+
+    /// HTML status dashboard for the `/status` endpoint: relay health, cache
+    /// stats and the `get_performance_metrics` data, plus recent and slow
+    /// calls for an `admin`
+    pub async fn status_page(&self, admin: bool) -> String {
+        let metrics = self.metrics_json().await;
+        let relays = self.jobs.relay_reports().await;
+        let recent_calls = match admin {
+            true => self.audit_log.recent(STATUS_RECENT_CALLS, None, None).await.unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "audit_log_read_failed");
+                Vec::new()
+            }),
+            false => Vec::new(),
+        };
+        let slow_log = self.slow_queries.read().await;
+        let slow_queries: Vec<_> = match admin {
+            true => slow_log.recent(STATUS_RECENT_CALLS).collect(),
+            false => Vec::new(),
+        };
+        let config = self.config();
+        let title = match &config.tenant {
+            Some(tenant) => format!("Nostr Jobs MCP · {}", tenant),
+            None => "Nostr Jobs MCP".to_string(),
+        };
+
+        dashboard::render(&StatusSnapshot {
+            title: &title,
+            metrics: &metrics,
+            relays: &relays,
+            relays_healthy: self.jobs.is_healthy().await,
+            calls: admin.then_some(RecentCalls {
+                audited: &recent_calls,
+                slow: &slow_queries,
+                slow_query_threshold_ms: config.slow_query_threshold_ms,
+            }),
+        })
+    }
+
+    #[tool(description = "Get comprehensive performance metrics showing cache effectiveness, over the last 5 minutes, hour and day as well as since start. Pass format=\"json\" for a structured, machine-readable snapshot.")]
+    pub async fn get_performance_metrics(
+        &self,
+        Parameters(args): Parameters<MetricsArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let json_format = self.metrics_format(args.format.as_deref())?;
+
+        let usage = self.jobs.cache_usage().await;
+        let metrics = self.jobs.metrics().read().await;
+        
+        // Log metrics snapshot for monitoring systems
+        tracing::info!(
+            total_requests = metrics.total_requests,
+            cache_hits = metrics.cache_hits,
+            cache_misses = metrics.cache_misses,
+            cache_hit_rate = metrics.cache_hit_rate(),
+            relay_fetches = metrics.relay_fetches,
+            failed_fetches = metrics.failed_fetches,
+            avg_cache_time_ms = metrics.avg_cache_time(),
+            avg_fetch_time_ms = metrics.avg_fetch_time(),
+            p50_cache_time_ms = metrics.cache_latency.percentile(0.50),
+            p99_cache_time_ms = metrics.cache_latency.percentile(0.99),
+            p50_fetch_time_ms = metrics.fetch_latency.percentile(0.50),
+            p99_fetch_time_ms = metrics.fetch_latency.percentile(0.99),
+            total_time_saved_ms = metrics.time_saved_ms(),
+            "performance_metrics_snapshot"
+        );
+
+        for (relay, relay_metrics) in &metrics.relays {
+            tracing::info!(
+                relay = %relay,
+                fetches = relay_metrics.fetches,
+                failures = relay_metrics.failures,
+                failure_rate = relay_metrics.failure_rate(),
+                avg_fetch_time_ms = relay_metrics.avg_fetch_time(),
+                p90_fetch_time_ms = relay_metrics.latency.percentile(0.90),
+                events_received = relay_metrics.events_received,
+                "relay_metrics_snapshot"
+            );
+        }
+        
+        if json_format {
+            return Ok(CallToolResult::structured(metrics.to_json(&usage)));
+        }
+        Ok(CallToolResult::success(vec![Content::text(metrics.format_report(&usage))]))
+    }
+
+    /// Whether a metrics tool's `format` asks for JSON rather than text
+    fn metrics_format(&self, format: Option<&str>) -> Result<bool, McpError> {
+        match format.map(str::to_lowercase).as_deref() {
+            None | Some("text") => Ok(false),
+            Some("json") => Ok(true),
+            Some(other) => Err(McpError::invalid_params(
+                i18n::text_with(self.config().locale, "error.metrics_format", &[("format", &other)]),
+                Some(json!({"format": other})),
+            )),
+        }
+    }
+
+    #[tool(description = "List recent tool calls that exceeded the slow query threshold, with their arguments, cache decision, and per-relay timings")]
+    pub async fn get_slow_queries(
+        &self,
+        Parameters(args): Parameters<SlowQueriesArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let log = self.slow_queries.read().await;
+        let limit = args.limit.unwrap_or(20);
+
+        if log.len() == 0 {
+            return Ok(CallToolResult::success(vec![Content::text(format!(
+                "No slow queries recorded (threshold: {}ms).",
+                self.config().slow_query_threshold_ms
+            ))]));
+        }
+
+        let entries: Vec<String> = log.recent(limit).map(SlowQuery::format).collect();
+        let report = format!(
+            "🐢 Slow Query Log (threshold: {}ms, showing {} of {})\n\n{}",
+            self.config().slow_query_threshold_ms,
+            entries.len(),
+            log.len(),
+            entries.join("\n\n")
+        );
+        Ok(CallToolResult::success(vec![Content::text(report)]))
+    }
+
+    #[tool(description = "Show cache memory usage: entry and event counts, approximate size, and the largest entries")]
+    pub async fn cache_status(&self) -> Result<CallToolResult, McpError> {
+        let usage = self.jobs.cache_usage().await;
+        let entries = self.jobs.cache_entries().await;
+        let ttl = self.config().search_cache_ttl;
+        let largest = if entries.is_empty() {
+            "  (cache is empty)".to_string()
+        } else {
+            entries
+                .iter()
+                .take(10)
+                .map(|entry| {
+                    format!(
+                        "  • {} — {} event(s), {}, {:.0}s old{}",
+                        entry.key,
+                        entry.events,
+                        format_bytes(entry.size_bytes),
+                        entry.age.as_secs_f64(),
+                        if entry.age < ttl { "" } else { " (stale)" }
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let warn_bytes = self.config().cache_warn_bytes;
+        let report = format!(
+            "🗄️  Cache Status\n\n\
+            • Entries: {}\n\
+            • Cached events: {}\n\
+            • Approximate size: {} (warning threshold {}){}\n\n\
+            Largest entries:\n{}",
+            usage.entries,
+            usage.events,
+            format_bytes(usage.bytes),
+            format_bytes(warn_bytes),
+            if usage.bytes >= warn_bytes { "\n⚠️  Cache is above the warning threshold; consider clear_cache" } else { "" },
+            largest
+        );
+        Ok(CallToolResult::success(vec![Content::text(report)]))
+    }
+
+    #[tool(description = "Read the most recent entries of the tool invocation audit log (tool, args hash, session, outcome, latency), optionally filtered by tool or session id")]
+    pub async fn get_audit_log(
+        &self,
+        Parameters(args): Parameters<AuditLogArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        if !self.audit_log.is_enabled() {
+            return Ok(CallToolResult::success(vec![Content::text(
+                "Audit logging is disabled (set AUDIT_LOG_PATH to enable it)."
+            )]));
+        }
+
+        let limit = args.limit.unwrap_or(50);
+        let entries = self.audit_log
+            .recent(limit, args.tool.as_deref(), args.session_id.as_deref())
+            .await
+            .map_err(|e| McpError::internal_error(
+                i18n::text(self.config().locale, "error.audit_log").to_string(),
+                Some(json!({"error": e.to_string()})),
+            ))?;
+
+        if entries.is_empty() {
+            return Ok(CallToolResult::success(vec![Content::text(
+                "No matching audit log entries."
+            )]));
+        }
+
+        let report = format!(
+            "📜 Audit Log ({} most recent entr{})\n\n{}",
+            entries.len(),
+            if entries.len() == 1 { "y" } else { "ies" },
+            entries.iter().map(AuditEntry::format).collect::<Vec<_>>().join("\n")
+        );
+        Ok(CallToolResult::success(vec![Content::text(report)]))
+    }
+
+    #[tool(description = "Show today's tool calls and relay fetches per API key (identified by a hash of the bearer token, or ip:<address> for callers without one from API_KEYS) against the daily quotas")]
+    pub async fn get_api_key_usage(&self) -> Result<CallToolResult, McpError> {
+        let keys = self.usage.lock().await.snapshot(Timestamp::now().as_secs());
+        let config = self.config();
+        let limit = |quota: u64| if quota == 0 { "unlimited".to_string() } else { quota.to_string() };
+        let header = format!(
+            "🔑 API Key Usage today (UTC); quotas per key: {} tool calls, {} relay fetches",
+            limit(config.daily_tool_call_quota),
+            limit(config.daily_relay_fetch_quota)
+        );
+        if keys.is_empty() {
+            return Ok(CallToolResult::success(vec![Content::text(format!(
+                "{}\n\nNo API key has called a tool today.",
+                header
+            ))]));
+        }
+
+        let lines: Vec<String> = keys
+            .iter()
+            .map(|(key, usage)| {
+                format!(
+                    "  • {} — {} call(s), {} relay fetch(es), last used {}",
+                    key,
+                    usage.tool_calls,
+                    usage.relay_fetches,
+                    &Timestamp::from(usage.last_used).to_human_datetime()[11..19]
+                )
+            })
+            .collect();
+        Ok(CallToolResult::success(vec![Content::text(format!("{}\n\n{}", header, lines.join("\n")))]))
+    }
+
+    #[tool(description = "Enable or disable a tool at runtime; connected clients receive a tools/list_changed notification")]
+    pub async fn set_tool_enabled(
+        &self,
+        Parameters(args): Parameters<SetToolEnabledArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        if args.tool == "set_tool_enabled" && !args.enabled {
+            return Err(McpError::invalid_params(
+                i18n::text(self.config().locale, "error.disable_self").to_string(),
+                Some(json!({"tool": args.tool})),
+            ));
+        }
+
+        let locale = self.config().locale;
+        let reply = |key: &str| Ok(CallToolResult::success(vec![Content::text(i18n::text_with(locale, key, &[("tool", &args.tool)]))]));
+        match self.set_tool_enabled_at_runtime(&args.tool, args.enabled).await {
+            Ok(true) => reply(if args.enabled { "report.tool.enabled" } else { "report.tool.disabled" }),
+            Ok(false) => reply(if args.enabled { "report.tool.already_enabled" } else { "report.tool.already_disabled" }),
+            Err(e) => Err(McpError::invalid_params(e, Some(json!({"tool": args.tool})))),
+        }
+    }
+
+    #[tool(name = "set_query_strategy", description = "Switch how relays are queried: pooled (all relays through the fetch slots, merged), parallel-merge (all at once, merged), race-first (first relay with listings wins) or primary-with-fallback (one at a time in configured order). get_performance_metrics compares strategies.")]
+    pub async fn set_query_strategy_tool(
+        &self,
+        Parameters(args): Parameters<SetQueryStrategyArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let strategy: QueryStrategy = args
+            .strategy
+            .parse()
+            .map_err(|e: String| McpError::invalid_params(e, Some(json!({"strategy": args.strategy}))))?;
+        let previous = self.set_query_strategy(strategy);
+        let locale = self.config().locale;
+        let text = if previous == strategy {
+            i18n::text_with(locale, "report.strategy.unchanged", &[("strategy", &strategy)])
+        } else {
+            i18n::text_with(locale, "report.strategy.switched", &[("previous", &previous), ("strategy", &strategy)])
+        };
+        Ok(CallToolResult::success(vec![Content::text(text)]))
+    }
+
+    #[tool(description = "Export every cached and ingested listing as a snapshot: NDJSON, one signed event per line, for import_snapshot on another instance")]
+    pub async fn export_snapshot(&self) -> Result<CallToolResult, McpError> {
+        let events = self.jobs.snapshot_events().await;
+        let summary = i18n::text_with(self.config().locale, "report.snapshot", &[("listings", &events.len())]);
+        Ok(CallToolResult::success(vec![
+            Content::text(summary),
+            Content::text(snapshot::to_ndjson(&events)),
+        ]))
+    }
+
+    #[tool(description = "Import a snapshot from export_snapshot into the cache, where offline mode serves it. Events with bad signatures and other boards' listings are skipped.")]
+    pub async fn import_snapshot(&self, Parameters(args): Parameters<ImportSnapshotArgs>) -> Result<CallToolResult, McpError> {
+        let report = self.jobs.import_snapshot(&args.ndjson).await;
+        let mut text = format!(
+            "📥 Snapshot imported: {} new listing(s), {} already indexed, {} not for this board, {} invalid",
+            report.imported,
+            report.duplicates,
+            report.skipped,
+            report.invalid.len()
+        );
+        for (line, error) in report.invalid.iter().take(10) {
+            text.push_str(&format!("\n  • line {}: {}", line, error));
+        }
+        Ok(CallToolResult::success(vec![Content::text(text)]))
+    }
+
+    #[tool(description = "Publish many job listings at once from a CSV or NDJSON payload. Every row is validated like a draft; valid rows are signed with the board's posting key and published one after another, rate-limited per relay. Reports each row's outcome. Set dry_run to only validate.")]
+    pub async fn bulk_publish_jobs(&self, Parameters(args): Parameters<BulkPublishArgs>) -> Result<CallToolResult, McpError> {
+        let format = match args.format.as_deref() {
+            Some(format) => format.parse().map_err(|e: String| McpError::invalid_params(e, None))?,
+            None => BulkFormat::detect(&args.payload),
+        };
+        let report = match self.jobs.publish_bulk(&args.payload, format, args.dry_run.unwrap_or(false)).await {
+            Ok(report) => report,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+
+        let mut text = match report.dry_run {
+            true => format!(
+                "🧪 Dry run: {} row(s) valid, {} invalid",
+                report.count(|o| matches!(o, RowOutcome::Valid)),
+                report.count(|o| matches!(o, RowOutcome::Invalid(_)))
+            ),
+            false => format!(
+                "📦 Bulk publish: {} published, {} invalid, {} failed",
+                report.count(|o| matches!(o, RowOutcome::Published(_))),
+                report.count(|o| matches!(o, RowOutcome::Invalid(_))),
+                report.count(|o| matches!(o, RowOutcome::Failed(_)))
+            ),
+        };
+        for row in &report.rows {
+            let job_id = row.job_id.as_deref().unwrap_or("-");
+            text.push_str(&match &row.outcome {
+                RowOutcome::Published(id) => format!("\n  ✅ row {} ({}): event {}", row.row, job_id, id.to_hex()),
+                RowOutcome::Valid => format!("\n  ✅ row {} ({}): valid", row.row, job_id),
+                RowOutcome::Invalid(e) => format!("\n  ❌ row {} ({}): invalid: {}", row.row, job_id, e),
+                RowOutcome::Failed(e) => format!("\n  ❌ row {} ({}): {}", row.row, job_id, e),
+            });
+        }
+        let mut output = CallToolResult::success(vec![Content::text(text)]);
+        output.structured_content = Some(Self::bulk_report_json(&report));
+        Ok(output)
+    }
+
+    /// A bulk publish report, for `bulk_publish_jobs` and the admin HTTP API
+    pub(crate) fn bulk_report_json(report: &BulkReport) -> serde_json::Value {
+        let rows: Vec<serde_json::Value> = report
+            .rows
+            .iter()
+            .map(|row| {
+                let (status, event_id, error) = match &row.outcome {
+                    RowOutcome::Published(id) => ("published", Some(id.to_hex()), None),
+                    RowOutcome::Valid => ("valid", None, None),
+                    RowOutcome::Invalid(e) => ("invalid", None, Some(e)),
+                    RowOutcome::Failed(e) => ("failed", None, Some(e)),
+                };
+                json!({"row": row.row, "job_id": row.job_id, "status": status, "event_id": event_id, "error": error})
+            })
+            .collect();
+        json!({
+            "dry_run": report.dry_run,
+            "published": report.count(|o| matches!(o, RowOutcome::Published(_))),
+            "valid": report.count(|o| matches!(o, RowOutcome::Valid)),
+            "invalid": report.count(|o| matches!(o, RowOutcome::Invalid(_))),
+            "failed": report.count(|o| matches!(o, RowOutcome::Failed(_))),
+            "rows": rows,
+        })
+    }
+
+    #[tool(name = "reload_config", description = "Reload configuration from the .env file (relays, cache TTLs, log level, ...) without dropping sessions")]
+    pub async fn reload_config_tool(&self) -> Result<CallToolResult, McpError> {
+        Ok(CallToolResult::success(vec![Content::text(self.reload_config().await)]))
+    }
+
+    #[tool(description = "Reset performance metrics (useful for testing); the previous totals stay available in get_metrics_history")]
+    pub async fn reset_metrics(&self) -> Result<CallToolResult, McpError> {
+        let old_metrics = self.jobs.rotate_metrics("reset", true).await.metrics;
+        
+        tracing::info!(
+            old_total_requests = old_metrics.total_requests,
+            old_cache_hit_rate = old_metrics.cache_hit_rate(),
+            old_time_saved_ms = old_metrics.time_saved_ms(),
+            "metrics_reset"
+        );
+        
+        Ok(CallToolResult::success(vec![Content::text(i18n::text(self.config().locale, "report.metrics.reset"))]))
+    }
+
+    #[tool(description = "Performance metrics of past periods, archived by the scheduled metrics rotation or reset_metrics, newest first. Pass format=\"json\" for structured snapshots.")]
+    pub async fn get_metrics_history(
+        &self,
+        Parameters(args): Parameters<MetricsHistoryArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let json_format = self.metrics_format(args.format.as_deref())?;
+        let (since, mut periods) = self.jobs.metrics_history().await;
+        periods.truncate(args.limit.unwrap_or(10).max(1));
+        let rotation = match self.config().metrics_rotation {
+            Some(period) => period.as_str(),
+            None => "off",
+        };
+
+        if json_format {
+            return Ok(CallToolResult::structured(json!({
+                "rotation": rotation,
+                "current_since": since.as_secs(),
+                "periods": periods.iter().map(|p| p.to_json()).collect::<Vec<_>>(),
+            })));
+        }
+        if periods.is_empty() {
+            return Ok(CallToolResult::success(vec![Content::text(format!(
+                "No archived metrics yet (rotation: {}); the current totals started {}.",
+                rotation,
+                since.to_human_datetime()
+            ))]));
+        }
+        let lines: Vec<String> = periods.iter().map(|p| p.format_summary()).collect();
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "🗄️ Metrics History (rotation: {}, current totals since {}):\n{}",
+            rotation,
+            since.to_human_datetime(),
+            lines.join("\n")
+        ))]))
+    }
+
+    #[tool(description = "Clear the cache and show before/after metrics")]
+    pub async fn clear_cache(&self) -> Result<CallToolResult, McpError> {
+        let metrics_before = self.jobs.metrics().read().await.clone();
+        let cache_size = self.jobs.clear_cache().await;
+        
+        tracing::warn!(
+            cache_entries_cleared = cache_size,
+            cache_hits_before = metrics_before.cache_hits,
+            cache_hit_rate_before = metrics_before.cache_hit_rate(),
+            "cache_cleared"
+        );
+        
+        let report = i18n::text_with(
+            self.config().locale,
+            "report.cache.cleared",
+            &[
+                ("entries", &cache_size),
+                ("hits", &metrics_before.cache_hits),
+                ("rate", &format!("{:.1}", metrics_before.cache_hit_rate())),
+            ],
+        );
+        
+        Ok(CallToolResult::success(vec![Content::text(report)]))
+    }
+
+    #[tool(description = "List the configured Nostr relays with their live connection state (connected or not, round-trip latency, last event received, events served this session), and any external job boards (RSS, Greenhouse, Lever) whose listings are served alongside them")]
+    pub async fn list_relays(&self) -> Result<CallToolResult, McpError> {
+        let relays = self.jobs.relay_reports().await;
+        let connected = relays.iter().filter(|r| r.connection.as_ref().is_some_and(|c| c.connected)).count();
+        let mut relays_text = format!(
+            "{} relay(s) configured, {} connected:\n{}",
+            relays.len(),
+            connected,
+            relays.iter().map(Self::format_relay_report).collect::<Vec<_>>().join("\n")
+        );
+        let ingest = self.jobs.ingest_status().await;
+        if !ingest.is_empty() {
+            relays_text.push_str(&format!(
+                "\n\nIngested from {} external board(s), signed by {}:\n{}",
+                ingest.len(),
+                self.jobs.ingest_public_key().to_bech32().unwrap_or_default(),
+                ingest
+                    .iter()
+                    .map(|s| format!(
+                        "  • {} — {} listing(s), refreshed {}{}",
+                        s.source,
+                        s.listings,
+                        s.refreshed_at.map(|at| at.to_human_datetime()).unwrap_or_else(|| "never".to_string()),
+                        s.error.as_ref().map(|e| format!(" ⚠️ last attempt failed: {}", e)).unwrap_or_default()
+                    ))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ));
+            if let Some(bridge) = self.jobs.last_bridge().await {
+                relays_text.push_str(&format!(
+                    "\nBridged to Nostr {}: {} published, {} updated, {} withdrawn, {} unchanged, {} failed",
+                    bridge.ran_at.map(|at| at.to_human_datetime()).unwrap_or_default(),
+                    bridge.published,
+                    bridge.updated,
+                    bridge.withdrawn,
+                    bridge.unchanged,
+                    bridge.failed
+                ));
+            }
+        }
+
+        let mut output = CallToolResult::success(vec![Content::text(relays_text)]);
+        output.structured_content = Some(json!({
+            "relays": relays.iter().map(Self::relay_report_json).collect::<Vec<_>>(),
+        }));
+        Ok(output)
+    }
+
+    pub(crate) fn relay_report_json(r: &RelayReport) -> serde_json::Value {
+        json!({
+            "url": r.url,
+            "connected": r.connection.as_ref().map(|c| c.connected),
+            "status": r.connection.as_ref().map(|c| &c.status),
+            "latency_ms": r.connection.as_ref().and_then(|c| c.latency).map(|l| l.as_millis() as u64),
+            "connected_at": r.connection.as_ref().and_then(|c| c.connected_at).map(|at| at.as_secs()),
+            "fetches": r.fetches,
+            "failures": r.failures,
+            "events_received": r.events_received,
+            "avg_fetch_ms": r.avg_fetch_ms,
+            "last_event_secs_ago": r.last_event.map(|ago| ago.as_secs()),
+            "last_answer_secs_ago": r.last_answer.map(|ago| ago.as_secs()),
+            "timeout_ms": r.timeout.as_millis() as u64,
+            "probe": r.probe.as_ref().map(|p| json!({
+                "healthy": p.healthy,
+                "consecutive_failures": p.consecutive_failures,
+                "recovering": p.recovering,
+                "checked_at": p.checked_at.as_secs(),
+                "last_error": p.last_error,
+                "next_probe_secs": p.next_probe_in.as_secs(),
+            })),
+        })
+    }
+
+    fn format_relay_report(relay: &RelayReport) -> String {
+        let state = match &relay.connection {
+            Some(c) if c.connected => format!(
+                "🟢 connected{}{}",
+                c.connected_at.map(|at| format!(" since {}", at.to_human_datetime())).unwrap_or_default(),
+                c.latency.map(|l| format!(", rtt {}ms", l.as_millis())).unwrap_or_default()
+            ),
+            Some(c) => format!("🔴 {}", c.status.to_lowercase()),
+            None => "⚪ connection state unknown".to_string(),
+        };
+        let served = if relay.fetches == 0 {
+            "not queried yet".to_string()
+        } else {
+            format!(
+                "{} event(s) from {} fetch(es), {} failed, avg {:.0}ms, timeout {}ms, {}",
+                relay.events_received,
+                relay.fetches,
+                relay.failures,
+                relay.avg_fetch_ms,
+                relay.timeout.as_millis(),
+                match relay.last_event {
+                    Some(ago) => format!("last event {}s ago", ago.as_secs()),
+                    None => "⚠️ no events yet".to_string(),
+                }
+            )
+        };
+        let probe = match &relay.probe {
+            Some(p) if p.healthy => format!(
+                "\n    Probe: serves job listings{}, next check in {}s",
+                if p.recovering { " (recovering)" } else { "" },
+                p.next_probe_in.as_secs()
+            ),
+            Some(p) => format!(
+                "\n    Probe: ⚠️ {} ({} failure(s) in a row), next check in {}s",
+                p.last_error.as_deref().unwrap_or("failed"),
+                p.consecutive_failures,
+                p.next_probe_in.as_secs()
+            ),
+            None => String::new(),
+        };
+        format!("  • {} — {}\n    {}{}", relay.url, state, served, probe)
+    }
+
+    #[tool(description = "Get statistics about job listings on Nostr. sample_size controls how many of the most recent listings are analyzed. Covers employment types, companies, skills, locations (remote listings as their own bucket, then regions) and salary transparency.")]
+    pub async fn get_stats(
+        &self,
+        Parameters(args): Parameters<GetStatsArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let sample_size = args.sample_size.unwrap_or(DEFAULT_STATS_SAMPLE_SIZE).clamp(1, MAX_STATS_SAMPLE_SIZE);
+
+        match self.jobs.sample(sample_size, None).await {
+            Ok(sample) => {
+                let rate = self.salary_rate(&self.config().display_currency, &sample.listings).await;
+                let stats = sample.stats_in(rate.as_ref());
+                let locale = self.config().locale;
+                let text = Self::format_stats(&stats, sample_size, sample.truncated, &source_banner(sample.source, locale), locale);
+                let mut output = CallToolResult::success(vec![Content::text(text)]);
+                output.structured_content = Some(json!({
+                    "listings": stats.listings,
+                    "requested": sample_size,
+                    "truncated": sample.truncated,
+                    "source": sample.source.as_str(),
+                    "employment_types": stats.employment_types,
+                    "companies": stats.companies,
+                    "skills": stats.skills,
+                    "median_salaries": stats.median_salaries,
+                    "salary_disclosed": stats.salary_disclosed,
+                    "geo": geo_json(&stats.geo),
+                }));
+                Ok(output)
+            }
+            Err(e) => Err(self.jobs_error(e).await),
+        }
+    }
+
+    #[tool(description = "Count recent job listings grouped by the values of any tag (e.g. location, remote, currency, language), most common first")]
+    pub async fn count_jobs_by_tag(
+        &self,
+        Parameters(args): Parameters<CountByTagArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let tag = args.tag.trim();
+        if tag.is_empty() {
+            return Err(McpError::invalid_params(i18n::text_with(self.config().locale, "error.empty", &[("field", &"tag")]), None));
+        }
+        let sample_size = args.sample_size.unwrap_or(DEFAULT_STATS_SAMPLE_SIZE).clamp(1, MAX_STATS_SAMPLE_SIZE);
+
+        let sample = match self.jobs.sample(sample_size, None).await {
+            Ok(sample) => sample,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+        let counts = sample.tag_counts(tag);
+        let tagged = sample.listings.iter().filter(|l| l.tag_values(tag).next().is_some()).count();
+
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "🏷️ Listings by \"{}\"{}\n\n\
+            Sample: {} most recent listing(s), {} with this tag, {} distinct value(s){}\n\n{}",
+            tag,
+            source_banner(sample.source, self.config().locale),
+            sample.listings.len(),
+            tagged,
+            counts.len(),
+            partial_banner(sample.truncated, self.config().locale),
+            format_top_items(&counts, args.limit.unwrap_or(20).max(1))
+        ))]))
+    }
+
+    #[tool(description = "List every tag name used across recent job listings, with how many listings use it, how many values it carries and its most common example values. Shows the de facto schema of the board's listing kinds in the wild, for client developers and employers deciding which tags to write.")]
+    pub async fn list_tag_vocabulary(&self, Parameters(args): Parameters<TagVocabularyArgs>) -> Result<CallToolResult, McpError> {
+        let sample_size = args.sample_size.unwrap_or(DEFAULT_STATS_SAMPLE_SIZE).clamp(1, MAX_STATS_SAMPLE_SIZE);
+        let examples = args.examples.unwrap_or(3).min(10);
+        let min_listings = args.min_listings.unwrap_or(1).max(1);
+
+        let sample = match self.jobs.sample(sample_size, None).await {
+            Ok(sample) => sample,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+        let vocabulary: Vec<TagUsage> = tag_vocabulary(&sample.listings, examples).into_iter().filter(|t| t.listings >= min_listings).collect();
+        let listings = sample.listings.len().max(1);
+        let lines = match vocabulary.is_empty() {
+            true => "  (none)".to_string(),
+            false => vocabulary
+                .iter()
+                .map(|t| {
+                    let examples = match t.examples.is_empty() {
+                        true => String::new(),
+                        false => format!(", e.g. {}", t.examples.iter().map(|e| format!("\"{}\"", e)).collect::<Vec<_>>().join(", ")),
+                    };
+                    format!(
+                        "  • {}: {} listing(s) ({:.0}%), {} use(s), up to {} value(s){}",
+                        t.name,
+                        t.listings,
+                        t.listings as f64 * 100.0 / listings as f64,
+                        t.occurrences,
+                        t.max_values,
+                        examples
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+        let locale = self.config().locale;
+        let mut output = CallToolResult::success(vec![Content::text(format!(
+            "{}\n\n{}\n{}\n\n{}",
+            i18n::text_with(locale, "report.tag_vocabulary.title", &[("banner", &source_banner(sample.source, locale))]),
+            i18n::text_with(
+                locale,
+                "report.stats.sample",
+                &[("listings", &sample.listings.len()), ("requested", &sample_size), ("partial", &partial_banner(sample.truncated, locale))],
+            ),
+            i18n::text_with(locale, "report.tag_vocabulary.names", &[("names", &vocabulary.len())]),
+            lines
+        ))]);
+        output.structured_content = Some(json!({
+            "sample_size": sample.listings.len(),
+            "truncated": sample.truncated,
+            "tags": vocabulary.iter().map(|t| json!({
+                "name": t.name,
+                "listings": t.listings,
+                "occurrences": t.occurrences,
+                "max_values": t.max_values,
+                "examples": t.examples,
+            })).collect::<Vec<_>>(),
+        }));
+        Ok(output)
+    }
+
+    #[tool(description = "Which skills recent listings most often ask for together (e.g. rust with tokio, axum, postgres), as pairs with the number of listings asking for both. Pass skill to see what goes with one skill, e.g. to plan what to learn next or to widen a vague search.")]
+    pub async fn skill_cooccurrence(&self, Parameters(args): Parameters<SkillCooccurrenceArgs>) -> Result<CallToolResult, McpError> {
+        let skill = args.skill.as_deref().map(str::trim).filter(|s| !s.is_empty());
+        let sample_size = args.sample_size.unwrap_or(DEFAULT_STATS_SAMPLE_SIZE).clamp(1, MAX_STATS_SAMPLE_SIZE);
+        let min_listings = args.min_listings.unwrap_or(2).max(1);
+        let limit = args.limit.unwrap_or(20).max(1);
+
+        let sample = match self.jobs.sample(sample_size, None).await {
+            Ok(sample) => sample,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+        let (skills, pairs) = skill_pairs(&sample.listings, skill);
+        let pairs: Vec<SkillPair> = pairs.into_iter().filter(|p| p.listings >= min_listings).take(limit).collect();
+        let with_skill = skill.map(|s| skills.get(&s.to_lowercase()).copied().unwrap_or(0));
+
+        let heading = match (skill, with_skill) {
+            (Some(skill), Some(n)) => i18n::text_with(self.config().locale, "report.skill_pairs.with", &[("skill", &skill), ("listings", &n)]),
+            _ => "🔗 Skills most often listed together".to_string(),
+        };
+        let lines = match pairs.is_empty() {
+            true => format!("  No skills listed together in {} or more listing(s)", min_listings),
+            false => pairs
+                .iter()
+                .map(|p| match skill {
+                    Some(_) => format!("  • {}: {} listing(s) ({:.0}%)", p.with, p.listings, p.share * 100.0),
+                    None => format!("  • {} + {}: {} listing(s)", p.skill, p.with, p.listings),
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+        let mut output = CallToolResult::success(vec![Content::text(format!(
+            "{}{}\n\n\
+            Sample: {} most recent listing(s), {} distinct skill(s){}\n\n{}",
+            heading,
+            source_banner(sample.source, self.config().locale),
+            sample.listings.len(),
+            skills.len(),
+            partial_banner(sample.truncated, self.config().locale),
+            lines
+        ))]);
+        output.structured_content = Some(json!({
+            "skill": skill.map(str::to_lowercase),
+            "listings_with_skill": with_skill,
+            "sample": sample.listings.len(),
+            "truncated": sample.truncated,
+            "pairs": pairs.iter().map(|p| json!({
+                "skill": p.skill,
+                "with": p.with,
+                "listings": p.listings,
+                "share": p.share,
+            })).collect::<Vec<_>>(),
+        }));
+        Ok(output)
+    }
+
+    #[tool(description = "Demand vs supply per skill on Nostr: recent job listings asking for a skill against people posting that they are for hire with it (notes tagged FOR_HIRE_TOPICS, e.g. #forhire, one per account). A ratio above 1.5 listings per candidate is a sellers' market (candidates are in demand), below 0.67 a buyers' market. Pass skill to ask about one, e.g. \"go\".")]
+    pub async fn market_balance(&self, Parameters(args): Parameters<MarketBalanceArgs>) -> Result<CallToolResult, McpError> {
+        let skill = args.skill.as_deref().map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty());
+        let sample_size = args.sample_size.unwrap_or(DEFAULT_STATS_SAMPLE_SIZE).clamp(1, MAX_STATS_SAMPLE_SIZE);
+        let sample = match self.jobs.sample(sample_size, None).await {
+            Ok(sample) => sample,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+        let mut known: BTreeSet<String> = sample.listings.iter().flat_map(|l| &l.skills).map(|s| s.trim().to_lowercase()).collect();
+        known.extend(skill.clone());
+        let (candidates, candidates_truncated) = match self.jobs.for_hire_candidates(sample_size, &known).await {
+            Ok(found) => found,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+        let balances = market::balance(&sample.listings, &candidates);
+        let balances: Vec<SkillBalance> = match &skill {
+            Some(skill) => vec![balances.into_iter().find(|b| b.skill == *skill).unwrap_or(SkillBalance { skill: skill.clone(), demand: 0, supply: 0 })],
+            None => balances.into_iter().take(args.limit.unwrap_or(20).max(1)).collect(),
+        };
+
+        let verdict = |b: &SkillBalance| match (b.demand, b.supply) {
+            (0, 0) => "no listings or candidates".to_string(),
+            _ => format!(
+                "{}: {}",
+                b.ratio().map(|r| format!("{:.1} listing(s) per candidate", r)).unwrap_or_else(|| "no candidates".to_string()),
+                match b.side() {
+                    MarketSide::Sellers => "a sellers' market, candidates are in demand",
+                    MarketSide::Balanced => "balanced",
+                    MarketSide::Buyers => "a buyers' market, employers can choose",
                 }
+            ),
+        };
+        let lines = match balances.is_empty() {
+            true => "  No skills in the sample".to_string(),
+            false => balances
+                .iter()
+                .map(|b| format!("  • {}: {} listing(s), {} candidate(s) - {}", b.skill, b.demand, b.supply, verdict(b)))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+        let truncated = sample.truncated || candidates_truncated;
+        let mut output = CallToolResult::success(vec![Content::text(format!(
+            "⚖️ Market balance: listings asking for {} vs people for hire with it{}\n\n\
+            Sample: {} most recent listing(s), {} candidate(s) posting #{}{}\n\n{}",
+            skill.as_deref().unwrap_or("each skill"),
+            source_banner(sample.source, self.config().locale),
+            sample.listings.len(),
+            candidates.len(),
+            self.config().for_hire_topics.join(" #"),
+            partial_banner(truncated, self.config().locale),
+            lines
+        ))]);
+        output.structured_content = Some(json!({
+            "listings": sample.listings.len(),
+            "candidates": candidates.len(),
+            "truncated": truncated,
+            "skills": balances.iter().map(|b| json!({
+                "skill": b.skill,
+                "demand": b.demand,
+                "supply": b.supply,
+                "ratio": b.ratio(),
+                "market": b.side().as_str(),
+            })).collect::<Vec<_>>(),
+        }));
+        Ok(output)
+    }
+
+    fn format_stats(stats: &JobStats, requested: usize, truncated: bool, banner: &str, locale: Locale) -> String {
+        let heading = |key: &str| i18n::text(locale, key).to_string();
+        format!(
+            "{}\n\n{}\n\n{}\n{}\n\n{}\n{}\n\n{}\n{}\n\n{}\n{}\n\n{}\n  {}{}",
+            i18n::text_with(locale, "report.stats.title", &[("banner", &banner)]),
+            i18n::text_with(
+                locale,
+                "report.stats.sample",
+                &[("listings", &stats.listings), ("requested", &requested), ("partial", &partial_banner(truncated, locale))],
+            ),
+            heading("report.stats.employment_types"),
+            format_top_items(&stats.employment_types, 5),
+            heading("report.stats.top_companies"),
+            format_top_items(&stats.companies, 5),
+            heading("report.stats.top_skills"),
+            format_top_items(&stats.skills, 10),
+            heading("report.stats.locations"),
+            format_geo(&stats.geo, stats.listings, 5, locale),
+            heading("report.stats.salary_transparency"),
+            i18n::text_with(
+                locale,
+                "report.stats.salary_complete",
+                &[
+                    ("disclosed", &stats.salary_disclosed),
+                    ("listings", &stats.listings),
+                    ("share", &format!("{:.0}", stats.salary_disclosed as f64 * 100.0 / stats.listings.max(1) as f64)),
+                ],
+            ),
+            stats
+                .salary_transparency
+                .iter()
+                .take(5)
+                .map(|t| format!("\n  • {}: {}", t.company, format_transparency(t).trim_start()))
+                .collect::<String>()
+        )
+    }
+
+    #[tool(description = "Compare job market statistics between two time windows (default: last 30 days vs the 30 days before), reporting deltas in listing counts, top skills, and median salaries")]
+    pub async fn compare_stats(
+        &self,
+        Parameters(args): Parameters<CompareStatsArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let window_days = args.window_days.unwrap_or(DEFAULT_COMPARE_WINDOW_DAYS);
+        if !(1..=MAX_COMPARE_WINDOW_DAYS).contains(&window_days) {
+            return Err(self.out_of_range("window_days", 1, MAX_COMPARE_WINDOW_DAYS));
+        }
+        let window_secs = window_days * 86_400;
+        let sample_size = args.sample_size.unwrap_or(DEFAULT_STATS_SAMPLE_SIZE).clamp(1, MAX_STATS_SAMPLE_SIZE);
+
+        let current = match &args.current {
+            Some(window) => parse_time_window(window),
+            None => {
+                let now = Timestamp::now().as_secs();
+                Ok((Timestamp::from(now.saturating_sub(window_secs)), Timestamp::from(now)))
+            }
+        };
+        let current = match current {
+            Ok(window) => window,
+            Err(e) => return Err(with_error_data(e.into(), "window", json!("current"))),
+        };
+        let previous = match &args.previous {
+            Some(window) => parse_time_window(window),
+            None => {
+                let length = current.1.as_secs().saturating_sub(current.0.as_secs());
+                Ok((Timestamp::from(current.0.as_secs().saturating_sub(length)), current.0))
+            }
+        };
+        let previous = match previous {
+            Ok(window) => window,
+            Err(e) => return Err(with_error_data(e.into(), "window", json!("previous"))),
+        };
+
+        let mut samples = Vec::with_capacity(2);
+        for window in [current, previous] {
+            match self.jobs.sample(sample_size, Some(window)).await {
+                Ok(sample) => {
+                    let rate = self.salary_rate(&self.config().display_currency, &sample.listings).await;
+                    samples.push(sample.stats_in(rate.as_ref()));
+                }
+                Err(e) => return Err(self.jobs_error(e).await),
+            }
+        }
+
+        let report = Self::format_comparison(
+            (current, &samples[0]),
+            (previous, &samples[1]),
+            self.config().locale,
+        );
+        Ok(CallToolResult::success(vec![Content::text(report)]))
+    }
+
+    fn format_comparison(
+        (current_window, current): ((Timestamp, Timestamp), &JobStats),
+        (previous_window, previous): ((Timestamp, Timestamp), &JobStats),
+        locale: Locale,
+    ) -> String {
+        let current_skills = &current.skills;
+        let previous_skills = &previous.skills;
+
+        let mut top_skills: Vec<_> = current_skills.iter().collect();
+        top_skills.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        let mut skill_names: Vec<&String> = top_skills.iter().take(10).map(|(k, _)| *k).collect();
+        let mut previous_top: Vec<_> = previous_skills.iter().collect();
+        previous_top.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        for (skill, _) in previous_top.into_iter().take(10) {
+            if !skill_names.contains(&skill) {
+                skill_names.push(skill);
+            }
+        }
+
+        let skill_lines = if skill_names.is_empty() {
+            "  (none)".to_string()
+        } else {
+            skill_names
+                .iter()
+                .map(|skill| {
+                    let now = current_skills.get(*skill).copied().unwrap_or(0);
+                    let before = previous_skills.get(*skill).copied().unwrap_or(0);
+                    format!("  • {}: {} → {} ({})", skill, before, now, format_delta(before as f64, now as f64))
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let current_salaries = &current.median_salaries;
+        let previous_salaries = &previous.median_salaries;
+        let mut salary_groups: Vec<&String> = current_salaries.keys().chain(previous_salaries.keys()).collect();
+        salary_groups.sort();
+        salary_groups.dedup();
+        let salary_lines = if salary_groups.is_empty() {
+            "  (no salary data)".to_string()
+        } else {
+            salary_groups
+                .iter()
+                .map(|group| match (previous_salaries.get(*group), current_salaries.get(*group)) {
+                    (Some(before), Some(now)) => format!(
+                        "  • {}: {:.0} → {:.0} ({})",
+                        group, before, now, format_delta(*before, *now)
+                    ),
+                    (None, Some(now)) => format!("  • {}: n/a → {:.0}", group, now),
+                    (Some(before), None) => format!("  • {}: {:.0} → n/a", group, before),
+                    (None, None) => format!("  • {}: n/a", group),
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let window = |key: &str, (since, until): (Timestamp, Timestamp)| {
+            i18n::text_with(locale, key, &[("since", &since.to_human_datetime()), ("until", &until.to_human_datetime())])
+        };
+        format!(
+            "{}\n\n{}\n{}\n\n{}\n\n{}\n{}\n\n{}\n{}{}",
+            i18n::text(locale, "report.comparison.title"),
+            window("report.comparison.current", current_window),
+            window("report.comparison.previous", previous_window),
+            i18n::text_with(
+                locale,
+                "report.comparison.listings",
+                &[
+                    ("previous", &previous.listings),
+                    ("current", &current.listings),
+                    ("delta", &format_delta(previous.listings as f64, current.listings as f64)),
+                ],
+            ),
+            i18n::text(locale, "report.comparison.top_skills"),
+            skill_lines,
+            i18n::text(locale, "report.comparison.median_salaries"),
+            salary_lines,
+            current
+                .exchange_rate
+                .as_ref()
+                .or(previous.exchange_rate.as_ref())
+                .map(|r| format!("\n  {}", format_conversion(r, locale)))
+                .unwrap_or_default()
+        )
+    }
+
+    /// `uri` without its query string, and the parameters in it
+    fn resource_params<'a>(&self, uri: &'a str) -> Result<(&'a str, ResourceParams), McpError> {
+        ResourceParams::parse(uri).map_err(|message| {
+            let text = i18n::text_with(self.config().locale, "error.resource_query", &[("uri", &uri), ("message", &message)]);
+            McpError::invalid_params(text, Some(json!({ "uri": uri })))
+        })
+    }
+
+    /// Contents of `jobs://latest`: a page of the newest listings, narrowed
+    /// by any `search_jobs` filters in `uri`'s query string
+    pub async fn read_latest(&self, uri: &str) -> Result<String, McpError> {
+        let (path, params) = self.resource_params(uri)?;
+        let limit = params.limit.unwrap_or(DEFAULT_LATEST_LIMIT).clamp(1, MAX_LATEST_LIMIT);
+        let page = params.page.unwrap_or(1).max(1);
+        let wanted = limit.saturating_mul(page).min(MAX_STATS_SAMPLE_SIZE);
+        let fetched = match params.has_filters() {
+            true => {
+                let query = JobQuery::try_from(&params.search_args(wanted))?;
+                self.jobs.search(&query).await.map(|results| (results.listings, results.total))
+            }
+            false => self.jobs.latest(wanted).await.map(|listings| {
+                // A full fetch means there may be older listings still
+                let total = if listings.len() == wanted { wanted + 1 } else { listings.len() };
+                (listings, total)
+            }),
+        };
+        let (listings, total) = match fetched {
+            Ok(fetched) => fetched,
+            Err(e) => return Err(with_error_data(self.jobs_error(e).await, "uri", json!(uri))),
+        };
+        let offset = (page - 1) * limit;
+        let listings: Vec<&JobListing> = listings.iter().skip(offset).take(limit).collect();
+        if listings.is_empty() {
+            return Ok("No job listings found.".to_string());
+        }
+
+        let fiat = self.display_currency(None).await;
+        let rate = self.salary_rate(&fiat, listings.iter().copied()).await;
+        let mut result = match page {
+            1 => format!("Latest {} Job Listings:\n\n", listings.len()),
+            page => format!("Latest {} Job Listings (page {}):\n\n", listings.len(), page),
+        };
+        for (i, listing) in listings.iter().enumerate() {
+            result.push_str(&format!("{}. {}\n\n", offset + i + 1, self.format_job_summary(listing, rate.as_ref())));
+        }
+        if offset + listings.len() < total && wanted < MAX_STATS_SAMPLE_SIZE {
+            result.push_str(&format!("Next page: {}", with_page(path, uri, page + 1)));
+        }
+        Ok(result)
+    }
+
+    /// Contents of the resource at `uri`
+    async fn resource_contents(&self, uri: String) -> Result<ReadResourceResult, McpError> {
+        let (path, params) = self.resource_params(&uri)?;
+        let path = path.to_string();
+        match path.as_str() {
+            "jobs://latest" => {
+                let content = self.read_latest(&uri).await?;
+                Ok(ReadResourceResult {
+                    contents: vec![ResourceContents::text(&content, uri)],
+                })
+            }
+            "jobs://stats" => {
+                match self.get_stats(Parameters(GetStatsArgs { sample_size: params.sample_size })).await {
+                    Ok(stats_result) => {
+                        let mut content_text = String::new();
+                        for c in &stats_result.content {
+                            if let RawContent::Text(text_content) = &c.raw {
+                                content_text.push_str(&text_content.text);
+                                content_text.push('\n');
+                            }
+                        }
+
+                        Ok(ReadResourceResult {
+                            contents: vec![ResourceContents::text(&content_text, uri)],
+                        })
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            "jobs://stats/geo" => {
+                let text = self.read_geo_stats().await.map_err(|e| with_error_data(e, "uri", json!(uri)))?;
+                Ok(ReadResourceResult {
+                    contents: vec![ResourceContents::text(&text, uri)],
+                })
+            }
+            "jobs://events" => {
+                let args = SearchCareerEventsArgs {
+                    query: params.query,
+                    topic: params.topic,
+                    location: params.location,
+                    within_days: params.within_days,
+                    limit: params.limit,
+                };
+                let events = self.search_career_events(Parameters(args)).await?;
+                let text = events
+                    .content
+                    .iter()
+                    .filter_map(|c| match &c.raw {
+                        RawContent::Text(text) => Some(text.text.clone()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                Ok(ReadResourceResult {
+                    contents: vec![ResourceContents::text(&text, uri)],
+                })
+            }
+            "jobs://health" => {
+                let health = serde_json::to_string_pretty(&self.health_json().await).unwrap_or_default();
+                Ok(ReadResourceResult {
+                    contents: vec![ResourceContents::TextResourceContents {
+                        uri,
+                        mime_type: Some("application/json".to_string()),
+                        text: health,
+                        meta: None,
+                    }],
+                })
+            }
+            "jobs://mine" => {
+                let dashboard = serde_json::to_string_pretty(&self.operator_dashboard_json().await?).unwrap_or_default();
+                Ok(ReadResourceResult {
+                    contents: vec![ResourceContents::TextResourceContents {
+                        uri,
+                        mime_type: Some("application/json".to_string()),
+                        text: dashboard,
+                        meta: None,
+                    }],
+                })
+            }
+            other if other.starts_with("jobs://digest/") => {
+                let content = self.read_digest(&other["jobs://digest/".len()..]).await?;
+                Ok(ReadResourceResult {
+                    contents: vec![ResourceContents::text(&content, uri)],
+                })
+            }
+            other if other.starts_with("jobs://search/") => {
+                let content = self.read_search_page(&other["jobs://search/".len()..]).await?;
+                Ok(ReadResourceResult {
+                    contents: vec![ResourceContents::text(&content, uri)],
+                })
+            }
+            other if let Some(job_id) = other.strip_prefix("jobs://job/").and_then(|rest| rest.strip_suffix("/full")) => {
+                let details = self.get_job_details(Parameters(GetJobArgs { job_id: job_id.to_string() })).await;
+                let details = details.map_err(|e| with_error_data(e, "uri", json!(uri)))?;
+                let text = details
+                    .content
+                    .iter()
+                    .filter_map(|c| match &c.raw {
+                        RawContent::Text(text) => Some(text.text.clone()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                Ok(ReadResourceResult {
+                    contents: vec![ResourceContents::text(&text, uri)],
+                })
+            }
+            _ => Err(McpError::resource_not_found(
+                i18n::text(self.config().locale, "error.resource_not_found").to_string(),
+                Some(json!({ "uri": uri })),
+            )),
+        }
+    }
+
+    /// Contents of `jobs://stats/geo`: the statistics sample by region,
+    /// location and geohash cell
+    pub async fn read_geo_stats(&self) -> Result<String, McpError> {
+        let sample = match self.jobs.sample(DEFAULT_STATS_SAMPLE_SIZE, None).await {
+            Ok(sample) => sample,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+        let geo = GeoDistribution::from_listings(&sample.listings);
+        let counts = |map: &HashMap<String, usize>| match sorted_counts(map) {
+            items if items.is_empty() => "  (none)".to_string(),
+            items => items.into_iter().take(20).map(|(k, n)| format!("  • {}: {}", k, n)).collect::<Vec<_>>().join("\n"),
+        };
+        Ok(format!(
+            "🗺️ Job Listings by Location{}\n\n\
+            Sample: {} most recent listing(s){}\n\n\
+            Regions:\n{}\n\n\
+            Locations:\n{}\n\n\
+            Geohash cells (precision {}):\n{}",
+            source_banner(sample.source, self.config().locale),
+            sample.listings.len(),
+            partial_banner(sample.truncated, self.config().locale),
+            format_geo(&geo, sample.listings.len(), 20, self.config().locale),
+            counts(&geo.locations),
+            GEO_CELL_PRECISION,
+            counts(&geo.cells)
+        ))
+    }
 
-                Ok(CallToolResult::success(vec![Content::text(results)]))
+    /// Contents of `jobs://digest/{name}`: `latest`, `archive`, or a
+    /// `YYYY-MM-DD` period start
+    pub async fn read_digest(&self, name: &str) -> Result<String, McpError> {
+        match name {
+            "latest" => match self.jobs.latest_digest().await {
+                Ok(digest) => Ok(Self::format_digest(&digest, self.config().locale)),
+                Err(e) => Err(self.jobs_error(e).await),
+            },
+            "archive" => {
+                let digests = self.jobs.digest_archive().await;
+                if digests.is_empty() {
+                    return Ok("No digests generated yet; read jobs://digest/latest to build one.".to_string());
+                }
+                let lines: Vec<String> = digests
+                    .iter()
+                    .map(|d| format!(
+                        "  • jobs://digest/{} - {} digest, {} listing(s)",
+                        d.date(),
+                        d.period,
+                        d.listings
+                    ))
+                    .collect();
+                Ok(format!("🗂️ Digest Archive ({}):\n{}", digests.len(), lines.join("\n")))
             }
-            _ => {
-                let healthy = *self.relay_healthy.lock().await;
-                if healthy {
-                    Ok(CallToolResult::success(vec![Content::text(
-                        "⏳ Search in progress...\n\
-                         Relays are responding but queries are slow.\n\
-                         Please try again shortly."
-                    )]))
-                } else {
-                    Ok(CallToolResult::success(vec![Content::text(
-                        "🔄 Starting relay connection...\n\n\
-                         The Nostr relays are initializing.\n\
-                         Please try again in a moment.\n\n\
-                         💡 Tip: Results will be cached once available."
-                    )]))
+            date => {
+                let digests = self.jobs.digest_archive().await;
+                match digests.iter().find(|d| d.date() == date) {
+                    Some(digest) => Ok(Self::format_digest(digest, self.config().locale)),
+                    None => Err(McpError::resource_not_found(
+                        i18n::text(self.config().locale, "error.no_digest").to_string(),
+                        Some(json!({ "uri": format!("jobs://digest/{}", date) })),
+                    )),
                 }
             }
         }
     }
 
-    #[tool(description = "Get detailed information about a specific job listing by its Job ID or Event ID")]
-    pub async fn get_job_details(
-        &self,
-        Parameters(args): Parameters<GetJobArgs>,
-    ) -> Result<CallToolResult, McpError> {
-        let key = format!("job:{}", args.job_id);
-
-        // Check cache first - avoid relay request entirely if cached
-        {
-            let start = std::time::Instant::now();
-            let cache = self.cache.read().await;
-            if let Some(cached) = cache.get(&key) {
-                if let Some(event) = cached.events.first() {
-                    let duration_ms = start.elapsed().as_millis();
-                    self.metrics.write().await.record_cache_hit(duration_ms);
-                    
-                    let mut result = self.format_job_summary(event);
-                    result.push_str("\n\n⚡ [CACHED]\n\n📄 Full Job Details:\n");
-                    result.push_str(&event.content);
-                    return Ok(CallToolResult::success(vec![Content::text(result)]));
-                }
-            }
-        }
+    fn format_digest(digest: &Digest, locale: Locale) -> String {
+        let entry_line = |e: &DigestEntry| {
+            format!(
+                "  • {} - {}{} ({})",
+                e.company.as_deref().unwrap_or("Unknown"),
+                e.title.as_deref().unwrap_or("Untitled"),
+                e.salary
+                    .as_ref()
+                    .map(|s| format!(", ${} - ${} {} per {}", s.min, s.max, s.currency, s.period))
+                    .unwrap_or_default(),
+                e.job_id
+            )
+        };
+        let section = |lines: Vec<String>, empty: &str| match lines.is_empty() {
+            true => format!("  ({})", empty),
+            false => lines.join("\n"),
+        };
+
+        format!(
+            "{}\n\n{}\n{}\n\n{}\n{}\n\n{}\n{}\n\n{}\n{}{}\n\n{}",
+            i18n::text_with(
+                locale,
+                match digest.period {
+                    DigestPeriod::Daily => "report.digest.daily",
+                    DigestPeriod::Weekly => "report.digest.weekly",
+                },
+                &[("date", &digest.date())],
+            ),
+            i18n::text_with(
+                locale,
+                "report.digest.period",
+                &[
+                    ("since", &digest.since.to_human_datetime()),
+                    ("until", &digest.until.to_human_datetime()),
+                    ("partial", &partial_banner(digest.truncated, locale)),
+                ],
+            ),
+            i18n::text_with(
+                locale,
+                "report.digest.listings",
+                &[
+                    ("listings", &digest.listings),
+                    ("previous", &digest.previous_listings),
+                    ("delta", &format_delta(digest.previous_listings as f64, digest.listings as f64)),
+                ],
+            ),
+            i18n::text(locale, "report.digest.new"),
+            section(digest.newest.iter().map(entry_line).collect(), "none"),
+            i18n::text_with(locale, "report.digest.notable_salaries", &[("currency", &digest.currency)]),
+            section(digest.notable_salaries.iter().map(entry_line).collect(), "no salary data"),
+            i18n::text(locale, "report.digest.trending_skills"),
+            section(
+                digest
+                    .trending_skills
+                    .iter()
+                    .map(|t| format!("  • {}: {} → {}", t.skill, t.previous, t.count))
+                    .collect(),
+                "none"
+            ),
+            digest
+                .exchange_rate
+                .as_ref()
+                .map(|r| format!("\n  {}", format_conversion(r, locale)))
+                .unwrap_or_default(),
+            i18n::text_with(locale, "report.digest.generated", &[("at", &digest.generated_at.to_human_datetime())])
+        )
+    }
+}
+
+/// Result banner for where the data came from
+fn source_banner(source: Source, locale: Locale) -> String {
+    let banner = match source {
+        Source::Cache => i18n::text(locale, "banner.cached").to_string(),
+        Source::Offline => i18n::text(locale, "banner.offline").to_string(),
+        Source::StaleCache { age } => i18n::text_with(locale, "banner.stale", &[("age", &format_age(age))]),
+        Source::Relays => i18n::text(locale, "banner.fresh").to_string(),
+    };
+    format!(" {}", banner)
+}
+
+/// How sats and BTC salaries in a report were converted
+fn format_conversion(rate: &ExchangeRate, locale: Locale) -> String {
+    i18n::text_with(
+        locale,
+        "report.converted",
+        &[("per_btc", &rate.per_btc), ("fiat", &rate.fiat), ("as_of", &rate.as_of.to_human_datetime())],
+    )
+}
+
+/// Warning appended to reports built from a sample the relay deadline cut short
+fn partial_banner(truncated: bool, locale: Locale) -> String {
+    match truncated {
+        true => format!(" {}", i18n::text(locale, "banner.partial")),
+        false => String::new(),
+    }
+}
+
+/// `age` in its largest whole unit, e.g. `45s`, `12m`, `3h`, `2d`
+fn format_age(age: Duration) -> String {
+    match age.as_secs() {
+        secs @ 0..60 => format!("{}s", secs),
+        secs @ 60..3600 => format!("{}m", secs / 60),
+        secs @ 3600..86400 => format!("{}h", secs / 3600),
+        secs => format!("{}d", secs / 86400),
+    }
+}
+
+/// How old a stale cache entry served instead of fresh data is
+fn stale_age_secs(source: Source) -> Option<u64> {
+    match source {
+        Source::StaleCache { age } => Some(age.as_secs()),
+        _ => None,
+    }
+}
+
+/// Invalid input is `invalid_params`, a missing listing `resource_not_found`,
+/// relay failures `internal_error`; `data` always carries `JobsError::to_json`.
+impl From<JobsError> for McpError {
+    fn from(e: JobsError) -> Self {
+        mcp_error(e, Locale::En)
+    }
+}
+
+/// `e` as an MCP error whose message reads in `locale`
+fn mcp_error(e: JobsError, locale: Locale) -> McpError {
+    let data = Some(e.to_json());
+    let message = e.message(locale);
+    match e {
+        JobsError::NotFound { .. } => McpError::resource_not_found(message, data),
+        JobsError::ParseError { .. } | JobsError::Config(_) => McpError::invalid_params(message, data),
+        JobsError::QuotaExceeded { .. } | JobsError::UnknownApiKey => McpError::invalid_request(message, data),
+        JobsError::Timeout { .. }
+        | JobsError::RelayError { .. }
+        | JobsError::Geocoding { .. }
+        | JobsError::ExchangeRate { .. }
+        | JobsError::Ingest { .. }
+        | JobsError::Dvm { .. }
+        | JobsError::Overloaded { .. } => {
+            McpError::internal_error(message, data)
+        }
+    }
+}
+
+/// The tool whose data the resource at `path` serves, which must be
+/// enabled to read it
+fn resource_tool(path: &str) -> Option<&'static str> {
+    match path {
+        "jobs://latest" => Some("search_jobs"),
+        "jobs://stats" | "jobs://stats/geo" => Some("get_stats"),
+        "jobs://events" => Some("search_career_events"),
+        other if other.starts_with("jobs://search/") => Some("search_jobs"),
+        other if other.starts_with("jobs://job/") && other.ends_with("/full") => Some("get_job_details"),
+        _ => None,
+    }
+}
 
-        // Not in cache, fetch from relays
-        let filter = if let Ok(event_id) = EventId::from_hex(&args.job_id) {
-            Filter::new().id(event_id)
-        } else {
-            Filter::new()
-                .kind(Kind::from(9993u16))
-                .custom_tag(
-                    SingleLetterTag::lowercase(Alphabet::J),
-                    args.job_id.clone()
-                )
-        };
+/// `uri` (a resource at `path`) with its `page` parameter set to `page`
+fn with_page(path: &str, uri: &str, page: usize) -> String {
+    let query = uri.split_once('?').map_or("", |(_, query)| query);
+    let mut params: Vec<&str> = query.split('&').filter(|p| !p.is_empty() && !p.starts_with("page=")).collect();
+    let page = format!("page={}", page);
+    params.push(&page);
+    format!("{}?{}", path, params.join("&"))
+}
 
-        match timeout(Duration::from_millis(2500), self.fetch_events_fast(filter, key)).await {
-            Ok(Ok(events)) => {
-                if events.is_empty() {
-                    return Ok(CallToolResult::success(vec![Content::text(
-                        format!("No job found with ID: {}", args.job_id)
-                    )]));
-                }
+/// Add `key` to the structured data of `error`
+fn with_error_data(mut error: McpError, key: &str, value: serde_json::Value) -> McpError {
+    match &mut error.data {
+        Some(serde_json::Value::Object(data)) => {
+            data.insert(key.to_string(), value);
+        }
+        data => *data = Some(json!({ key: value })),
+    }
+    error
+}
 
-                let event = events.first().unwrap();
-                let mut result = self.format_job_summary(event);
-                result.push_str("\n\n🌐 [FRESH]\n\n📄 Full Job Details:\n");
-                result.push_str(&event.content);
+fn format_delta(before: f64, now: f64) -> String {
+    let diff = now - before;
+    if before == 0.0 {
+        return if now == 0.0 { "±0".to_string() } else { format!("{:+.0}, new", diff) };
+    }
+    format!("{:+.0}, {:+.1}%", diff, diff / before * 100.0)
+}
 
-                Ok(CallToolResult::success(vec![Content::text(result)]))
-            }
-            _ => {
-                Ok(CallToolResult::success(vec![Content::text(
-                    "⚠️ Unable to fetch job details. Relays are currently unresponsive.\n\
-                     Please try again shortly."
-                )]))
-            }
-        }
+/// Parse a `TimeWindow` into `(since, until)` timestamps.
+fn parse_time_window(window: &TimeWindow) -> Result<(Timestamp, Timestamp), JobsError> {
+    let since = parse_date(&window.since)?;
+    let until = parse_date(&window.until)?;
+    if since >= until {
+        return Err(JobsError::ParseError {
+            what: "window",
+            input: format!("{}..{}", window.since, window.until),
+            message: "start must be before end".to_string(),
+        });
     }
+    Ok((since, until))
+}
 
-    #[tool(description = "Get comprehensive performance metrics showing cache effectiveness")]
-    pub async fn get_performance_metrics(&self) -> Result<CallToolResult, McpError> {
-        let metrics = self.metrics.read().await;
-        let report = metrics.format_report();
-        
-        // Log metrics snapshot for monitoring systems
-        tracing::info!(
-            total_requests = metrics.total_requests,
-            cache_hits = metrics.cache_hits,
-            cache_misses = metrics.cache_misses,
-            cache_hit_rate = metrics.cache_hit_rate(),
-            relay_fetches = metrics.relay_fetches,
-            failed_fetches = metrics.failed_fetches,
-            avg_cache_time_ms = metrics.avg_cache_time(),
-            avg_fetch_time_ms = metrics.avg_fetch_time(),
-            total_time_saved_ms = metrics.time_saved_ms(),
-            "performance_metrics_snapshot"
-        );
-        
-        Ok(CallToolResult::success(vec![Content::text(report)]))
+/// Parse `YYYY-MM-DD` (UTC midnight) or a unix timestamp.
+fn parse_date(value: &str) -> Result<Timestamp, JobsError> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Ok(Timestamp::from(secs));
     }
 
-    #[tool(description = "Reset performance metrics (useful for testing)")]
-    pub async fn reset_metrics(&self) -> Result<CallToolResult, McpError> {
-        let old_metrics = self.metrics.read().await.clone();
-        *self.metrics.write().await = PerformanceMetrics::default();
-        
-        tracing::info!(
-            old_total_requests = old_metrics.total_requests,
-            old_cache_hit_rate = old_metrics.cache_hit_rate(),
-            old_time_saved_ms = old_metrics.time_saved_ms(),
-            "metrics_reset"
-        );
-        
-        Ok(CallToolResult::success(vec![Content::text(
-            "✅ Performance metrics have been reset.".to_string()
-        )]))
+    let parts: Vec<&str> = value.split('-').collect();
+    let invalid = || JobsError::ParseError {
+        what: "date",
+        input: value.to_string(),
+        message: "expected YYYY-MM-DD or a unix timestamp".to_string(),
+    };
+    if parts.len() != 3 {
+        return Err(invalid());
+    }
+    let year: i64 = parts[0].parse().map_err(|_| invalid())?;
+    let month: i64 = parts[1].parse().map_err(|_| invalid())?;
+    let day: i64 = parts[2].parse().map_err(|_| invalid())?;
+    if !(1970..=MAX_YEAR).contains(&year) {
+        return Err(invalid());
     }
+    let days = days_from_civil(year, month, day).ok_or_else(invalid)?;
+    let secs = days.checked_mul(86_400).and_then(|secs| u64::try_from(secs).ok()).ok_or_else(invalid)?;
 
-    #[tool(description = "Clear the cache and show before/after metrics")]
-    pub async fn clear_cache(&self) -> Result<CallToolResult, McpError> {
-        let metrics_before = self.metrics.read().await.clone();
-        let cache_size = self.cache.read().await.len();
-        self.cache.write().await.clear();
-        
-        tracing::warn!(
-            cache_entries_cleared = cache_size,
-            cache_hits_before = metrics_before.cache_hits,
-            cache_hit_rate_before = metrics_before.cache_hit_rate(),
-            "cache_cleared"
-        );
-        
-        let report = format!(
-            "🗑️  Cache Cleared Successfully\n\n\
-            Cache statistics before clear:\n\
-            • Total cached queries: {}\n\
-            • Cache hits: {}\n\
-            • Cache hit rate: {:.1}%\n\n\
-            ⚠️  Next queries will fetch fresh data from relays.\n\
-            💡 Use get_performance_metrics to track the impact.",
-            cache_size,
-            metrics_before.cache_hits,
-            metrics_before.cache_hit_rate()
-        );
-        
-        Ok(CallToolResult::success(vec![Content::text(report)]))
+    Ok(Timestamp::from(secs))
+}
+
+/// The Streamable HTTP session id the request arrived on, if any
+fn session_id(context: &RequestContext<RoleServer>) -> Option<String> {
+    context
+        .extensions
+        .get::<http::request::Parts>()
+        .and_then(|parts| parts.headers.get("mcp-session-id"))
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+
+/// A hex or npub public key, or `invalid_params`
+fn parse_pubkey(value: &str) -> Result<PublicKey, McpError> {
+    PublicKey::parse(value.trim()).map_err(|e| {
+        McpError::from(JobsError::ParseError {
+            what: "pubkey",
+            input: value.to_string(),
+            message: e.to_string(),
+        })
+    })
+}
+
+/// An application as listed by `list_applications`, with a follow-up hint
+/// once it has gone `stale_after` seconds without news
+fn application_line(application: &Application, now: u64, stale_after: u64) -> String {
+    let listing = match (&application.title, &application.company) {
+        (Some(title), Some(company)) => format!("{} at {} (🆔 {})", title, company, application.job_id),
+        (Some(title), None) => format!("{} (🆔 {})", title, application.job_id),
+        _ => format!("🆔 {}", application.job_id),
+    };
+    let mut line = format!(
+        "• {} - {} since {} (applied {})",
+        listing,
+        application.status,
+        Timestamp::from(application.updated_at).to_human_datetime(),
+        Timestamp::from(application.applied_at).to_human_datetime()
+    );
+    if let Some(note) = &application.note {
+        line.push_str(&format!("\n   📝 {}", note));
     }
+    if application.is_stale(now, stale_after) {
+        line.push_str(&format!(
+            "\n   ⏰ No update in {} days - follow up?",
+            now.saturating_sub(application.updated_at) / 86400
+        ));
+    }
+    line
+}
 
-    #[tool(description = "List all connected Nostr relays")]
-    pub async fn list_relays(&self) -> Result<CallToolResult, McpError> {
-        let relays_text = format!(
-            "Connected to {} relay(s):\n{}",
-            self.relays.len(),
-            self.relays.iter().map(|r| format!("  • {}", r)).collect::<Vec<_>>().join("\n")
-        );
-        
-        Ok(CallToolResult::success(vec![Content::text(relays_text)]))
+fn application_json(application: &Application, now: u64, stale_after: u64) -> Value {
+    let mut value = serde_json::to_value(application).unwrap_or_default();
+    value["stale"] = json!(application.is_stale(now, stale_after));
+    value["days_since_update"] = json!(now.saturating_sub(application.updated_at) / 86400);
+    value
+}
+
+/// An interview as listed by `list_upcoming_interviews`, with the status of
+/// the owner's application to the listing when there is one
+fn format_interview(interview: &Interview, application: Option<&Application>) -> String {
+    let plan = &interview.plan;
+    let mut text = format!(
+        "{} (🆔 {})\n   🕒 {}{}",
+        plan.title,
+        plan.job_id,
+        plan.start.to_human_datetime(),
+        plan.end.map(|end| format!(" - {}", end.to_human_datetime())).unwrap_or_default()
+    );
+    if let Some(location) = &plan.location {
+        text.push_str(&format!("\n   📍 {}", location));
+    }
+    let participants: Vec<String> = plan.participants.iter().map(|p| p.to_bech32().unwrap_or_else(|_| p.to_hex())).collect();
+    text.push_str(&format!("\n   👥 {}", participants.join(", ")));
+    if let Some(application) = application {
+        text.push_str(&format!("\n   📨 Your application: {}", application.status));
     }
+    if let Some(naddr) = interview.naddr() {
+        text.push_str(&format!("\n   🔗 {}", naddr));
+    }
+    text
+}
 
-    #[tool(description = "Get statistics about job listings on Nostr")]
-    pub async fn get_stats(&self) -> Result<CallToolResult, McpError> {
-        let filter = Self::build_filter(None, None, None, 100);
-        let key = "stats:all".to_string();
+fn interview_json(interview: &Interview, application: Option<&Application>) -> Value {
+    let plan = &interview.plan;
+    json!({
+        "interview_id": plan.id,
+        "job_id": plan.job_id,
+        "title": plan.title,
+        "start": plan.start.as_secs(),
+        "end": plan.end.map(|end| end.as_secs()),
+        "location": plan.location,
+        "participants": plan.participants.iter().map(|p| p.to_bech32().unwrap_or_else(|_| p.to_hex())).collect::<Vec<_>>(),
+        "description": plan.description,
+        "scheduled_by": interview.author.to_bech32().unwrap_or_else(|_| interview.author.to_hex()),
+        "event_id": interview.event_id.to_hex(),
+        "naddr": interview.naddr(),
+        "application_status": application.map(|a| a.status.to_string()),
+    })
+}
 
-        {
-            let start = std::time::Instant::now();
-            let cache = self.cache.read().await;
-            if let Some(cached) = cache.get(&key) {
-                let duration_ms = start.elapsed().as_millis();
-                self.metrics.write().await.record_cache_hit(duration_ms);
-                
-                let events = &cached.events;
-                let (employment_counts, company_counts, skill_counts) = 
-                    Self::analyze_events(events);
-
-                let stats = format!(
-                    "📊 Nostr Job Listings Statistics{}\n\n\
-                    Total Listings: {}\n\n\
-                    Employment Types:\n{}\n\n\
-                    Top Companies:\n{}\n\n\
-                    Top Skills:\n{}",
-                    if cached.is_fresh(Duration::from_secs(120)) { " ⚡ [CACHED]" } else { " 📦 [CACHED - STALE]" },
-                    events.len(),
-                    format_top_items(&employment_counts, 5),
-                    format_top_items(&company_counts, 5),
-                    format_top_items(&skill_counts, 10)
-                );
-                return Ok(CallToolResult::success(vec![Content::text(stats)]));
+/// A career event as listed by `search_career_events`; date-based events
+/// show their days only
+fn format_career_event(event: &CareerEvent) -> String {
+    let when = match event.all_day {
+        true => {
+            let day = |at: Timestamp| at.to_human_datetime().chars().take(10).collect::<String>();
+            let first = day(event.start);
+            // The end of a date-based event is exclusive
+            match event.end.map(|end| day(Timestamp::from(end.as_secs().saturating_sub(86_400)))) {
+                Some(last) if last > first => format!("{} - {}", first, last),
+                _ => first,
             }
         }
+        false => format!(
+            "{}{}",
+            event.start.to_human_datetime(),
+            event.end.map(|end| format!(" - {}", end.to_human_datetime())).unwrap_or_default()
+        ),
+    };
+    let mut text = format!("{}\n   📅 {}", event.title, when);
+    if let Some(location) = &event.location {
+        text.push_str(&format!("\n   📍 {}", location));
+    }
+    if !event.topics.is_empty() {
+        text.push_str(&format!("\n   🏷️ {}", event.topics.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" ")));
+    }
+    let summary = event.summary.clone().unwrap_or_else(|| event.description.chars().take(200).collect());
+    if !summary.trim().is_empty() {
+        text.push_str(&format!("\n   📝 {}", summary.trim()));
+    }
+    if let Some(naddr) = event.naddr() {
+        text.push_str(&format!("\n   🔗 {}", naddr));
+    }
+    text
+}
 
-        match timeout(Duration::from_millis(2500), self.fetch_events_fast(filter, key)).await {
-            Ok(Ok(events)) => {
-                let (employment_counts, company_counts, skill_counts) = 
-                    Self::analyze_events(&events);
-
-                let stats = format!(
-                    "📊 Nostr Job Listings Statistics 🌐 [FRESH]\n\n\
-                    Total Listings: {}\n\n\
-                    Employment Types:\n{}\n\n\
-                    Top Companies:\n{}\n\n\
-                    Top Skills:\n{}",
-                    events.len(),
-                    format_top_items(&employment_counts, 5),
-                    format_top_items(&company_counts, 5),
-                    format_top_items(&skill_counts, 10)
-                );
+/// A company's salary transparency: the share of complete salaries and,
+/// when any were stated, the typical range per currency and period
+fn format_transparency(transparency: &CompanyTransparency) -> String {
+    let mut text = format!(
+        "  {}/{} listing(s) with a complete salary ({:.0}%){}",
+        transparency.complete,
+        transparency.listings,
+        transparency.score() * 100.0,
+        match transparency.partial {
+            0 => String::new(),
+            partial => format!(", {} partial", partial),
+        }
+    );
+    for range in transparency.typical_ranges.values() {
+        text.push_str(&format!(" · typical ${} - ${} {} per {}", range.min, range.max, range.currency, range.period));
+    }
+    text
+}
 
-                Ok(CallToolResult::success(vec![Content::text(stats)]))
-            }
-            _ => Ok(CallToolResult::success(vec![Content::text(
-                "📊 Statistics unavailable\n\nRelays are currently unresponsive.\n\
-                 Try again shortly for cached results."
-            )]))
-        }
-    }
-
-    fn analyze_events(events: &[Event]) -> (HashMap<String, usize>, HashMap<String, usize>, HashMap<String, usize>) {
-        let mut employment_counts = HashMap::new();
-        let mut company_counts = HashMap::new();
-        let mut skill_counts = HashMap::new();
-
-        for event in events.iter() {
-            let tags: Vec<_> = event.tags.iter().collect();
-            
-            for tag in &tags {
-                let slice = tag.as_slice();
-                if slice.len() >= 2 {
-                    match slice[0].as_str() {
-                        "employment-type" => {
-                            *employment_counts.entry(slice[1].to_string()).or_insert(0) += 1;
-                        }
-                        "company" => {
-                            *company_counts.entry(slice[1].to_string()).or_insert(0) += 1;
-                        }
-                        "skill" => {
-                            *skill_counts.entry(slice[1].to_string()).or_insert(0) += 1;
-                        }
-                        _ => {}
-                    }
+fn transparency_json(transparency: &CompanyTransparency) -> Value {
+    json!({
+        "score": transparency.score(),
+        "listings": transparency.listings,
+        "complete": transparency.complete,
+        "partial": transparency.partial,
+        "typical_ranges": transparency.typical_ranges.values().collect::<Vec<_>>(),
+    })
+}
+
+/// Reputation counts and the newest sources, each with a link to check it
+fn format_reputation(reputation: &Reputation, relays: &[String]) -> String {
+    if reputation.is_empty() {
+        return "  No reactions, reports or notes about it found".to_string();
+    }
+    let report_types = match reputation.reports.is_empty() {
+        true => String::new(),
+        false => format!(
+            " ({})",
+            reputation.reports.iter().map(|(kind, n)| format!("{} {}", n, kind)).collect::<Vec<_>>().join(", ")
+        ),
+    };
+    let mut text = format!(
+        "  👍 {} · 👎 {} · 📝 {} review(s) · 💬 {} mention(s) · 🚩 {} report(s){}, from {} account(s)",
+        reputation.likes,
+        reputation.dislikes,
+        reputation.reviews,
+        reputation.mentions,
+        reputation.report_count(),
+        report_types,
+        reputation.voices
+    );
+    for source in reputation.sources.iter().take(5) {
+        let label = match &source.kind {
+            SourceKind::Review => "review".to_string(),
+            SourceKind::Mention => "mention".to_string(),
+            SourceKind::Report(kind) => format!("report: {}", kind),
+        };
+        text.push_str(&format!(
+            "\n  • [{}] {}: \"{}\"\n    https://njump.me/{}",
+            label,
+            source.created_at.to_human_datetime(),
+            source.excerpt,
+            source.nevent(relays)
+        ));
+    }
+    text
+}
+
+fn reputation_json(reputation: &Reputation, relays: &[String]) -> Value {
+    json!({
+        "likes": reputation.likes,
+        "dislikes": reputation.dislikes,
+        "reviews": reputation.reviews,
+        "mentions": reputation.mentions,
+        "reports": reputation.reports,
+        "voices": reputation.voices,
+        "sources": reputation.sources.iter().map(|s| json!({
+            "kind": match &s.kind {
+                SourceKind::Review => "review",
+                SourceKind::Mention => "mention",
+                SourceKind::Report(_) => "report",
+            },
+            "report_type": match &s.kind {
+                SourceKind::Report(kind) => Some(kind.as_str()),
+                _ => None,
+            },
+            "event_id": s.event_id.to_hex(),
+            "nevent": s.nevent(relays),
+            "author": s.author.to_bech32().unwrap_or_else(|_| s.author.to_hex()),
+            "created_at": s.created_at.as_secs(),
+            "excerpt": s.excerpt,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+fn format_identities(identities: &[PosterIdentity], conflicts: &[IdentityConflict]) -> String {
+    let mut lines: Vec<String> = identities
+        .iter()
+        .map(|identity| {
+            let mut claims: Vec<String> = identity.name.iter().cloned().collect();
+            claims.extend(identity.nip05.as_ref().map(|n| format!("NIP-05 {}", n)));
+            claims.extend(identity.website.as_ref().map(|w| format!("🌐 {}", w)));
+            format!(
+                "  • {} ({} listing(s)): {}",
+                identity.pubkey.to_bech32().unwrap_or_else(|_| identity.pubkey.to_hex()),
+                identity.listings,
+                match (identity.has_profile, claims.is_empty()) {
+                    (false, _) => "no profile".to_string(),
+                    (true, true) => "profile without name, NIP-05 or website".to_string(),
+                    (true, false) => claims.join(" · "),
                 }
-            }
-        }
+            )
+        })
+        .collect();
+    lines.extend(conflicts.iter().map(|c| format!("  ⚠️ {}", c.message)));
+    lines.join("\n")
+}
+
+fn identity_json(identities: &[PosterIdentity], conflicts: &[IdentityConflict]) -> Value {
+    let npub = |p: &PublicKey| p.to_bech32().unwrap_or_else(|_| p.to_hex());
+    json!({
+        "posters": identities.iter().map(|i| json!({
+            "pubkey": npub(&i.pubkey),
+            "listings": i.listings,
+            "has_profile": i.has_profile,
+            "name": i.name,
+            "nip05": i.nip05,
+            "website": i.website,
+            "domain": i.domain(),
+        })).collect::<Vec<_>>(),
+        "conflicts": conflicts.iter().map(|c| json!({
+            "kind": c.kind,
+            "message": c.message,
+            "pubkeys": c.pubkeys.iter().map(npub).collect::<Vec<_>>(),
+        })).collect::<Vec<_>>(),
+    })
+}
+
+pub(crate) fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
 
-        (employment_counts, company_counts, skill_counts)
+/// Remote listings, then the top `limit` regions, then what couldn't be placed
+fn format_geo(geo: &GeoDistribution, listings: usize, limit: usize, locale: Locale) -> String {
+    let share = |n: usize| n as f64 * 100.0 / listings.max(1) as f64;
+    let mut lines = vec![format!("  🌐 Remote: {} ({:.0}%)", geo.remote, share(geo.remote))];
+    lines.extend(sorted_counts(&geo.regions).into_iter().take(limit).map(|(region, n)| format!("  • {}: {} ({:.0}%)", region, n, share(n))));
+    if geo.unspecified > 0 {
+        let unspecified = i18n::text(locale, "report.geo.unspecified");
+        lines.push(format!("  ❓ {}: {} ({:.0}%)", unspecified, geo.unspecified, share(geo.unspecified)));
     }
+    lines.join("\n")
+}
+
+/// Most common first, then alphabetical
+fn sorted_counts(map: &HashMap<String, usize>) -> Vec<(&str, usize)> {
+    let mut items: Vec<(&str, usize)> = map.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+    items.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    items
+}
+
+fn geo_json(geo: &GeoDistribution) -> Value {
+    json!({
+        "remote": geo.remote,
+        "unspecified": geo.unspecified,
+        "regions": geo.regions,
+        "locations": geo.locations,
+        "geohash_precision": GEO_CELL_PRECISION,
+        "cells": sorted_counts(&geo.cells).into_iter().map(|(cell, n)| {
+            let center = geo::decode(cell).map(|c| json!({ "lat": c.center.lat, "lon": c.center.lon }));
+            json!({ "geohash": cell, "listings": n, "center": center })
+        }).collect::<Vec<_>>(),
+    })
 }
 
 fn format_top_items(map: &HashMap<String, usize>, limit: usize) -> String {
@@ -848,29 +5544,129 @@ impl NostrJobsServer {
     pub async fn job_search_assistant(
         &self,
         Parameters(args): Parameters<JobAnalysisArgs>,
-        _ctx: RequestContext<RoleServer>,
+        ctx: RequestContext<RoleServer>,
     ) -> Result<GetPromptResult, McpError> {
+        let locale = self.locale_for(args.locale.as_deref(), Some(&ctx))?;
+        Ok(self.job_search_assistant_in(locale, args))
+    }
+
+    /// The job_search_assistant prompt in `locale`
+    pub fn job_search_assistant_in(&self, locale: Locale, args: JobAnalysisArgs) -> GetPromptResult {
         let skills_text = args.skills
-            .map(|s| format!("Required skills: {}", s.join(", ")))
+            .map(|s| i18n::text_with(locale, "prompt.search.skills", &[("skills", &s.join(", "))]))
             .unwrap_or_default();
 
         let messages = vec![
-            PromptMessage::new_text(
-                PromptMessageRole::Assistant,
-                "I'm your Nostr job search assistant. I'll help you find relevant job listings on the decentralized Nostr network.",
-            ),
+            PromptMessage::new_text(PromptMessageRole::Assistant, i18n::text(locale, "prompt.search.intro")),
             PromptMessage::new_text(
                 PromptMessageRole::User,
-                format!(
-                    "Search Query: {}\n{}\n\nPlease help me find relevant job listings and provide recommendations based on this query.",
-                    args.query,
-                    skills_text
-                ),
+                i18n::text_with(locale, "prompt.search.request", &[("query", &args.query), ("skills", &skills_text)]),
             ),
         ];
 
+        GetPromptResult {
+            description: Some(i18n::text_with(locale, "prompt.search.description", &[("query", &args.query)])),
+            messages,
+        }
+    }
+
+    /// Asks for a forward-looking market analysis grounded in the server's
+    /// own week-by-week data, embedded in the message
+    #[prompt(name = "hiring_trends_forecast")]
+    pub async fn hiring_trends_forecast(
+        &self,
+        Parameters(args): Parameters<HiringTrendsArgs>,
+        ctx: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        let locale = self.locale_for(args.locale.as_deref(), Some(&ctx))?;
+        self.hiring_trends_forecast_in(locale, args).await
+    }
+
+    /// The hiring_trends_forecast prompt in `locale`
+    pub async fn hiring_trends_forecast_in(&self, locale: Locale, args: HiringTrendsArgs) -> Result<GetPromptResult, McpError> {
+        let weeks = args.weeks.unwrap_or(DEFAULT_TREND_WEEKS).clamp(2, MAX_TREND_WEEKS);
+        let sample_size = args.sample_size.unwrap_or(DEFAULT_STATS_SAMPLE_SIZE).clamp(1, MAX_STATS_SAMPLE_SIZE);
+        let now = Timestamp::now();
+        let sample = match self.jobs.sample(sample_size, Some(HiringTrends::window(weeks, now))).await {
+            Ok(sample) => sample,
+            Err(e) => return Err(self.jobs_error(e).await),
+        };
+        let rate = self.salary_rate(&self.config().display_currency, &sample.listings).await;
+        let trends = HiringTrends::from_listings(&sample.listings, weeks, now, rate.as_ref());
+        let date = |at: Timestamp| at.to_human_datetime().chars().take(10).collect::<String>();
+        let half = weeks / 2;
+
+        let postings = trends
+            .weeks
+            .iter()
+            .map(|w| format!("  {}: {}{}", date(w.start), w.listings, if w.partial { i18n::text(locale, "prompt.forecast.week_in_progress") } else { "" }))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let skills = match trends.trending_skills.is_empty() {
+            true => i18n::text(locale, "prompt.forecast.no_skills").to_string(),
+            false => trends
+                .trending_skills
+                .iter()
+                .map(|t| {
+                    let change = format!("{:+}", t.count as i64 - t.previous as i64);
+                    i18n::text_with(
+                        locale,
+                        "prompt.forecast.skill",
+                        &[("skill", &t.skill), ("count", &t.count), ("previous", &t.previous), ("change", &change)],
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+        let salaries = match trends.salary_movement.is_empty() {
+            true => i18n::text(locale, "prompt.forecast.no_salaries").to_string(),
+            false => trends
+                .salary_movement
+                .iter()
+                .map(|m| {
+                    i18n::text_with(
+                        locale,
+                        "prompt.forecast.salary",
+                        &[
+                            ("group", &m.group),
+                            ("earlier", &format!("{:.0}", m.earlier)),
+                            ("later", &format!("{:.0}", m.later)),
+                            ("change", &format!("{:+.1}", m.change_pct())),
+                            ("earlier_count", &m.earlier_count),
+                            ("later_count", &m.later_count),
+                        ],
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+        let truncated = if sample.truncated { i18n::text(locale, "prompt.forecast.truncated") } else { "" };
+        let data = i18n::text_with(
+            locale,
+            "prompt.forecast.data",
+            &[
+                ("listings", &trends.listings),
+                ("weeks", &weeks),
+                ("truncated", &truncated),
+                ("postings", &postings),
+                ("later", &(weeks - half)),
+                ("earlier", &half),
+                ("skills", &skills),
+                ("salaries", &salaries),
+            ],
+        );
+
+        let messages = vec![
+            PromptMessage::new_text(PromptMessageRole::Assistant, i18n::text(locale, "prompt.forecast.intro")),
+            PromptMessage::new_text(PromptMessageRole::User, i18n::text_with(locale, "prompt.forecast.request", &[("data", &data)])),
+        ];
+
         Ok(GetPromptResult {
-            description: Some(format!("Job search assistance for: {}", args.query)),
+            description: Some(i18n::text_with(
+                locale,
+                "prompt.forecast.description",
+                &[("listings", &trends.listings), ("weeks", &weeks)],
+            )),
             messages,
         })
     }
@@ -878,133 +5674,252 @@ impl NostrJobsServer {
     #[prompt(name = "analyze_job_market")]
     pub async fn analyze_job_market(
         &self,
-        _ctx: RequestContext<RoleServer>,
+        Parameters(args): Parameters<MarketAnalysisArgs>,
+        ctx: RequestContext<RoleServer>,
     ) -> Result<GetPromptResult, McpError> {
+        let locale = self.locale_for(args.locale.as_deref(), Some(&ctx))?;
+        Ok(self.analyze_job_market_in(locale))
+    }
+
+    /// The analyze_job_market prompt in `locale`
+    pub fn analyze_job_market_in(&self, locale: Locale) -> GetPromptResult {
         let messages = vec![
-            PromptMessage::new_text(
-                PromptMessageRole::Assistant,
-                "I'll analyze the current job market on Nostr and provide insights.",
-            ),
-            PromptMessage::new_text(
-                PromptMessageRole::User,
-                "Please analyze the job listings available on Nostr. What are the trending skills? Which companies are hiring? What's the salary range for different positions?",
-            ),
+            PromptMessage::new_text(PromptMessageRole::Assistant, i18n::text(locale, "prompt.market.intro")),
+            PromptMessage::new_text(PromptMessageRole::User, i18n::text(locale, "prompt.market.request")),
         ];
 
-        Ok(GetPromptResult {
-            description: Some("Analysis of the Nostr job market".to_string()),
+        GetPromptResult {
+            description: Some(i18n::text(locale, "prompt.market.description").to_string()),
             messages,
-        })
+        }
     }
 }
 
 // ==================== MCP Server Handler ====================
 
-#[tool_handler]
 #[prompt_handler]
 impl ServerHandler for NostrJobsServer {
-    fn get_info(&self) -> ServerInfo {
-        ServerInfo {
-            protocol_version: ProtocolVersion::V_2024_11_05,
-            capabilities: ServerCapabilities::builder()
-                .enable_prompts()
-                .enable_resources()
-                .enable_tools()
-                .build(),
-            server_info: Implementation::from_build_env(),
-            instructions: Some(
-                "Nostr Jobs MCP Server - Access decentralized job listings from the Nostr network.\n\n\
-                Tools:\n\
-                • search_jobs - Search for jobs by company, skill, or employment type\n\
-                • get_job_details - Get detailed information about a specific job\n\
-                • get_performance_metrics - View cache performance and efficiency gains\n\
-                • clear_cache - Clear cache and see impact on performance\n\
-                • reset_metrics - Reset performance tracking\n\
-                • list_relays - Show connected Nostr relays\n\
-                • get_stats - Get statistics about job listings\n\n\
-                Prompts:\n\
-                • job_search_assistant - Get help searching for jobs\n\
-                • analyze_job_market - Analyze current job market trends\n\n\
-                Resources:\n\
-                • jobs://latest - Latest job listings\n\
-                • jobs://stats - Job market statistics\n\n\
-                Performance Features:\n\
-                • Automatic caching with 60s TTL\n\
-                • Detailed metrics tracking\n\
-                • Cache hit/miss analytics\n\
-                • Response time comparison".to_string()
-            ),
+    async fn call_tool(
+        &self,
+        mut request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let tool = request.name.to_string();
+        let args = request.arguments.clone().map(serde_json::Value::Object).unwrap_or(json!({}));
+        let session_id = session_id(&context);
+        let client = proxy::request_client_ip(&context, self.config().trust_forwarded_headers);
+        let role = self.role(&context);
+        let owner = auth::state_owner(&context);
+        let started = std::time::Instant::now();
+
+        // Admin tokens aren't metered; with none configured every caller is.
+        // Denied calls are turned away before they count toward a quota.
+        let admin = role == Role::Admin && !self.config().admin_tokens.is_empty();
+        let allowed = role.can_call(&tool);
+        let (_in_flight, api_key) = match allowed {
+            true => {
+                let (in_flight, api_key) = self.admit(&tool, auth::bearer_token(&context).as_deref(), client, admin).await?;
+                (Some(in_flight), api_key)
+            }
+            false => (None, None),
+        };
+
+        // Accepted by every tool, so it's taken out before the arguments are parsed
+        let requested_timeout = request.arguments.as_mut().and_then(|args| args.remove("timeout_ms"));
+        let deadline = match self.call_timeout(&tool, requested_timeout.as_ref()) {
+            Ok(deadline) => deadline,
+            Err(e) => return Err(with_error_data(e.into(), "tool", json!(tool))),
+        };
+
+        let (result, trace) = if allowed {
+            // Clone so a runtime toggle doesn't wait on in-flight calls
+            let router = self.tool_router.read().await.clone();
+            let tcc = ToolCallContext::new(self, request, context);
+            traced(with_call_timeout(deadline, router.call(tcc))).await
+        } else {
+            tracing::warn!(
+                tool = %tool,
+                role = role.as_str(),
+                session_id = session_id.as_deref().unwrap_or("-"),
+                client = ?client,
+                "admin_tool_denied"
+            );
+            let denied = McpError::invalid_request(
+                i18n::text_with(self.config().locale, "error.admin_tool", &[("tool", &tool)]),
+                Some(json!({"tool": tool, "role": role.as_str()})),
+            );
+            (Err(denied), Default::default())
+        };
+
+        let duration_ms = started.elapsed().as_millis();
+        let success = matches!(&result, Ok(r) if r.is_error != Some(true));
+        // Client-chosen names the router doesn't serve share one label, so
+        // they can't grow the metrics without bound
+        let label = match self.tool_router.read().await.has_route(&tool) {
+            true => tool.as_str(),
+            false => "unknown",
+        };
+        self.jobs.metrics().write().await.record_tool_call(label, duration_ms, success, trace.cache);
+        match &result {
+            // Bad arguments and denied calls are the caller's problem
+            Err(e) if e.code != ErrorCode::INVALID_PARAMS && e.code != ErrorCode::INVALID_REQUEST => {
+                self.jobs.error_reporter().failed(
+                    "tool",
+                    label,
+                    &e.message,
+                    json!({
+                        "tool": tool,
+                        "error": e.data,
+                        "cache": trace.cache,
+                        "failed_relays": trace.relay_timings.iter().filter(|t| !t.success).map(|t| &t.relay).collect::<Vec<_>>(),
+                        "duration_ms": duration_ms as u64,
+                    }),
+                );
+            }
+            Err(_) => {}
+            Ok(_) => self.jobs.error_reporter().succeeded("tool", label),
+        }
+        if let Some(key) = &api_key {
+            self.record_usage(key, trace.relay_timings.len() as u64).await;
+        }
+
+        if self.audit_log.is_enabled() {
+            let entry = AuditEntry {
+                at: Timestamp::now().as_secs(),
+                tool: tool.clone(),
+                args_hash: hash_args(&args),
+                session_id,
+                client: client.map(|ip| ip.to_string()),
+                outcome: match &result {
+                    Ok(r) if r.is_error == Some(true) => "tool_error",
+                    Ok(_) => "ok",
+                    Err(_) => "error",
+                }
+                .to_string(),
+                latency_ms: duration_ms as u64,
+            };
+            self.audit_log.append(&entry).await;
+        }
+
+        if duration_ms >= self.config().slow_query_threshold_ms {
+            tracing::warn!(
+                tool = %tool,
+                duration_ms = duration_ms,
+                threshold_ms = self.config().slow_query_threshold_ms,
+                cache = trace.cache.unwrap_or("none"),
+                relay_calls = trace.relay_timings.len(),
+                "slow_query"
+            );
+            self.slow_queries.write().await.push(SlowQuery {
+                at: Timestamp::now(),
+                tool,
+                args,
+                duration_ms,
+                success,
+                trace,
+            });
         }
+
+        match result {
+            Ok(result) => Ok(self.plain_text_for(owner.as_deref(), result).await),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        let role = self.role(&context);
+        let tools = self.tool_router
+            .read()
+            .await
+            .list_all()
+            .into_iter()
+            .filter(|t| role.can_call(&t.name))
+            .collect();
+        Ok(ListToolsResult::with_all_items(tools))
+    }
+
+    fn get_info(&self) -> ServerInfo {
+        self.info_in(self.config().locale)
     }
 
     async fn list_resources(
         &self,
         _request: Option<PaginatedRequestParam>,
-        _: RequestContext<RoleServer>,
+        context: RequestContext<RoleServer>,
     ) -> Result<ListResourcesResult, McpError> {
-        Ok(ListResourcesResult {
-            resources: vec![
-                RawResource::new("jobs://latest", "Latest Job Listings".to_string()).no_annotation(),
-                RawResource::new("jobs://stats", "Job Market Statistics".to_string()).no_annotation(),
-            ],
-            next_cursor: None,
-        })
+        let mut resources = vec![
+            RawResource::new("jobs://latest", "Latest Job Listings".to_string()).no_annotation(),
+            RawResource::new("jobs://stats", "Job Market Statistics".to_string()).no_annotation(),
+            RawResource::new("jobs://stats/geo", "Job Listings by Location".to_string()).no_annotation(),
+            RawResource::new("jobs://digest/latest", "Latest Market Digest".to_string()).no_annotation(),
+            RawResource::new("jobs://digest/archive", "Market Digest Archive".to_string()).no_annotation(),
+            RawResource::new("jobs://health", "Relay and Cache Health".to_string()).no_annotation(),
+            RawResource::new("jobs://events", "Upcoming Career Events".to_string()).no_annotation(),
+        ];
+        if self.jobs.posting_public_key().is_some() && self.role(&context) == Role::Admin {
+            resources.push(RawResource::new("jobs://mine", "My Listings Dashboard".to_string()).no_annotation());
+        }
+        Ok(ListResourcesResult { resources, next_cursor: None })
     }
 
     async fn read_resource(
         &self,
         ReadResourceRequestParam { uri }: ReadResourceRequestParam,
-        _: RequestContext<RoleServer>,
+        context: RequestContext<RoleServer>,
     ) -> Result<ReadResourceResult, McpError> {
-        match uri.as_str() {
-            "jobs://latest" => {
-                let filter = Self::build_filter(None, None, None, 20);
-                
-                match timeout(Duration::from_millis(2500), self.fetch_events_fast(filter, "latest:20".to_string())).await {
-                    Ok(Ok(events)) => {
-                        let content = if events.is_empty() {
-                            "No job listings found.".to_string()
-                        } else {
-                            let mut result = format!("Latest {} Job Listings:\n\n", events.len());
-                            for (i, event) in events.iter().enumerate() {
-                                result.push_str(&format!("{}. {}\n\n", i + 1, self.format_job_summary(event)));
-                            }
-                            result
-                        };
-
-                        Ok(ReadResourceResult {
-                            contents: vec![ResourceContents::text(&content, uri)],
-                        })
-                    }
-                    _ => Err(McpError::internal_error(
-                        "Failed to read resource",
-                        Some(json!({"uri": uri}))
-                    ))
-                }
+        let path = uri.split_once('?').map_or(uri.as_str(), |(path, _)| path);
+        // Application DMs are private to the operator
+        if path == "jobs://mine" && self.role(&context) != Role::Admin {
+            let message = i18n::text_with(self.config().locale, "error.admin_resource", &[("uri", &uri)]);
+            return Err(McpError::invalid_request(message, Some(json!({ "uri": uri }))));
+        }
+        // Label by template so arbitrary URIs don't grow the metrics without bound
+        let name = match path {
+            "jobs://latest" | "jobs://stats" | "jobs://stats/geo" | "jobs://digest/latest" | "jobs://digest/archive" | "jobs://health"
+            | "jobs://mine" | "jobs://events" => {
+                format!("resource:{}", path)
             }
-            "jobs://stats" => {
-                match self.get_stats().await {
-                    Ok(stats_result) => {
-                        let mut content_text = String::new();
-                        for c in &stats_result.content {
-                            if let RawContent::Text(text_content) = &c.raw {
-                                content_text.push_str(&text_content.text);
-                                content_text.push('\n');
-                            }
-                        }
+            other if other.starts_with("jobs://digest/") => "resource:jobs://digest/{date}".to_string(),
+            other if other.starts_with("jobs://search/") => "resource:jobs://search/{cursor}".to_string(),
+            other if other.starts_with("jobs://job/") && other.ends_with("/full") => "resource:jobs://job/{id}/full".to_string(),
+            _ => "resource:unknown".to_string(),
+        };
+        // A resource serving what a disabled tool would is disabled with it
+        if let Some(tool) = resource_tool(path)
+            && !self.tool_router.read().await.has_route(tool)
+        {
+            let message = i18n::text_with(self.config().locale, "error.resource_disabled", &[("uri", &uri), ("tool", &tool)]);
+            return Err(McpError::invalid_request(message, Some(json!({ "uri": uri, "tool": tool }))));
+        }
+        let client = proxy::request_client_ip(&context, self.config().trust_forwarded_headers);
+        let admin = self.role(&context) == Role::Admin && !self.config().admin_tokens.is_empty();
+        let (_in_flight, api_key) = self.admit(&name, auth::bearer_token(&context).as_deref(), client, admin).await?;
 
-                        Ok(ReadResourceResult {
-                            contents: vec![ResourceContents::text(&content_text, uri)],
-                        })
-                    }
-                    Err(e) => Err(e),
+        let started = std::time::Instant::now();
+        let deadline = self.config().tool_timeouts.get(&name).copied();
+        let (result, trace) = traced(with_call_timeout(deadline, self.resource_contents(uri))).await;
+        self.jobs.metrics().write().await.record_tool_call(
+            &name,
+            started.elapsed().as_millis(),
+            result.is_ok(),
+            trace.cache,
+        );
+        if let Some(key) = &api_key {
+            self.record_usage(key, trace.relay_timings.len() as u64).await;
+        }
+        let mut result = result?;
+        if self.wants_plain_text(auth::state_owner(&context).as_deref()).await {
+            for contents in &mut result.contents {
+                if let ResourceContents::TextResourceContents { text, .. } = contents {
+                    *text = plain_text::strip(text);
                 }
             }
-            _ => Err(McpError::resource_not_found(
-                "Resource not found",
-                Some(json!({ "uri": uri })),
-            )),
         }
+        Ok(result)
     }
 
     async fn list_resource_templates(
@@ -1014,20 +5929,80 @@ impl ServerHandler for NostrJobsServer {
     ) -> Result<ListResourceTemplatesResult, McpError> {
         Ok(ListResourceTemplatesResult {
             next_cursor: None,
-            resource_templates: Vec::new(),
+            resource_templates: vec![
+                RawResourceTemplate {
+                    uri_template: "jobs://latest{?limit,page,company,skill,employment_type,author,kinds,fuzzy}".to_string(),
+                    name: "Latest Job Listings".to_string(),
+                    title: None,
+                    description: Some("A page of the newest listings (limit per page, default 20, max 100), narrowed as in search_jobs with lists comma-separated".to_string()),
+                    mime_type: Some("text/plain".to_string()),
+                }
+                .no_annotation(),
+                RawResourceTemplate {
+                    uri_template: "jobs://stats{?sample_size}".to_string(),
+                    name: "Job Market Statistics".to_string(),
+                    title: None,
+                    description: Some("Statistics over the sample_size most recent listings (default 500, max 5000)".to_string()),
+                    mime_type: Some("text/plain".to_string()),
+                }
+                .no_annotation(),
+                RawResourceTemplate {
+                    uri_template: "jobs://events{?query,topic,location,within_days,limit}".to_string(),
+                    name: "Upcoming Career Events".to_string(),
+                    title: None,
+                    description: Some("Career events filtered as in search_career_events".to_string()),
+                    mime_type: Some("text/plain".to_string()),
+                }
+                .no_annotation(),
+                RawResourceTemplate {
+                    uri_template: "jobs://digest/{date}".to_string(),
+                    name: "Market Digest".to_string(),
+                    title: None,
+                    description: Some("Archived digest for the period starting on date (YYYY-MM-DD)".to_string()),
+                    mime_type: Some("text/plain".to_string()),
+                }
+                .no_annotation(),
+                RawResourceTemplate {
+                    uri_template: "jobs://job/{id}/full".to_string(),
+                    name: "Full Job Listing".to_string(),
+                    title: None,
+                    description: Some("A listing with its full description, by Job ID or Event ID".to_string()),
+                    mime_type: Some("text/plain".to_string()),
+                }
+                .no_annotation(),
+                RawResourceTemplate {
+                    uri_template: "jobs://search/{cursor}".to_string(),
+                    name: "More Search Results".to_string(),
+                    title: None,
+                    description: Some("The next page of a search_jobs call cut short by its limit; cursors come from its links".to_string()),
+                    mime_type: Some("text/plain".to_string()),
+                }
+                .no_annotation(),
+            ],
         })
     }
 
+    async fn on_initialized(&self, context: NotificationContext<RoleServer>) {
+        let mut peers = self.peers.lock().await;
+        peers.retain(|peer| !peer.is_transport_closed());
+        peers.push(context.peer);
+        tracing::info!(connected_peers = peers.len(), "client initialized");
+    }
+
     async fn initialize(
         &self,
         _request: InitializeRequestParam,
         context: RequestContext<RoleServer>,
     ) -> Result<InitializeResult, McpError> {
         if let Some(http_request_part) = context.extensions.get::<http::request::Parts>() {
-            let initialize_headers = &http_request_part.headers;
+            let initialize_headers = auth::redacted_headers(&http_request_part.headers);
             let initialize_uri = &http_request_part.uri;
-            tracing::info!(?initialize_headers, %initialize_uri, "initialize from http server");
+            let trust_forwarded = self.config().trust_forwarded_headers;
+            let client = proxy::client_ip(http_request_part, trust_forwarded);
+            let scheme = proxy::scheme(http_request_part, trust_forwarded);
+            tracing::info!(?initialize_headers, %initialize_uri, ?client, scheme, "initialize from http server");
         }
-        Ok(self.get_info())
+        let locale = self.locale_for(None, Some(&context))?;
+        Ok(self.info_in(locale))
     }
 }
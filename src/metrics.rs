@@ -0,0 +1,1097 @@
+// src/metrics.rs
+// Performance metrics: latency histograms, cache/relay counters, and report rendering
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use nostr_sdk::Timestamp;
+use serde_json::{Value, json};
+
+// ==================== Latency Histogram ====================
+
+/// Upper bounds (inclusive, in ms) of the latency buckets; anything slower
+/// lands in the overflow bucket.
+pub(crate) const LATENCY_BUCKETS_MS: [u128; 14] = [
+    1, 2, 5, 10, 25, 50, 100, 250, 500, 1000, 1500, 2000, 2500, 5000,
+];
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct LatencyHistogram {
+    buckets: [usize; LATENCY_BUCKETS_MS.len() + 1],
+    count: usize,
+    sum_ms: u128,
+    max_ms: u128,
+}
+
+impl LatencyHistogram {
+    pub(crate) fn record(&mut self, duration_ms: u128) {
+        let index = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|bound| duration_ms <= *bound)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.buckets[index] += 1;
+        self.count += 1;
+        self.sum_ms += duration_ms;
+        self.max_ms = self.max_ms.max(duration_ms);
+    }
+
+    pub(crate) fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Add `other`'s observations to this histogram
+    pub(crate) fn merge(&mut self, other: &LatencyHistogram) {
+        for (bucket, n) in self.buckets.iter_mut().zip(other.buckets) {
+            *bucket += n;
+        }
+        self.count += other.count;
+        self.sum_ms += other.sum_ms;
+        self.max_ms = self.max_ms.max(other.max_ms);
+    }
+
+    pub(crate) fn sum_ms(&self) -> u128 {
+        self.sum_ms
+    }
+
+    /// Observations added since `earlier` (an older copy of this histogram),
+    /// as `(representative ms, count)` per bucket: its upper bound, or the
+    /// slowest observation for the overflow bucket. Everything counts as new
+    /// when the histogram was reset in between.
+    pub(crate) fn new_since(&self, earlier: &LatencyHistogram) -> Vec<(u128, usize)> {
+        let reset = earlier.count > self.count;
+        LATENCY_BUCKETS_MS
+            .iter()
+            .copied()
+            .chain(std::iter::once(self.max_ms))
+            .zip(self.buckets.iter().zip(earlier.buckets))
+            .map(|(value, (now, before))| (value.min(self.max_ms), if reset { *now } else { now.saturating_sub(before) }))
+            .filter(|(_, n)| *n > 0)
+            .collect()
+    }
+
+    pub(crate) fn mean(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.sum_ms as f64 / self.count as f64
+        }
+    }
+
+    /// Estimated `q`-quantile (0.0..=1.0): the upper bound of the bucket
+    /// holding the q-th observation, capped at the slowest one seen.
+    pub(crate) fn percentile(&self, q: f64) -> u128 {
+        if self.count == 0 {
+            return 0;
+        }
+
+        let rank = ((q * self.count as f64).ceil() as usize).clamp(1, self.count);
+        let mut cumulative = 0;
+        for (i, n) in self.buckets.iter().enumerate() {
+            cumulative += n;
+            if cumulative >= rank {
+                let bound = LATENCY_BUCKETS_MS.get(i).copied().unwrap_or(self.max_ms);
+                return bound.min(self.max_ms);
+            }
+        }
+        self.max_ms
+    }
+
+    fn format_percentiles(&self) -> String {
+        format!(
+            "{}ms / {}ms / {}ms",
+            self.percentile(0.50),
+            self.percentile(0.90),
+            self.percentile(0.99)
+        )
+    }
+
+    /// Summary statistics, plus the count in each bucket keyed by its upper
+    /// bound (`+Inf` for the overflow bucket).
+    fn to_json(&self) -> Value {
+        let buckets: Vec<Value> = LATENCY_BUCKETS_MS
+            .iter()
+            .map(u128::to_string)
+            .chain(std::iter::once("+Inf".to_string()))
+            .zip(self.buckets)
+            .map(|(le, count)| json!({"le_ms": le, "count": count}))
+            .collect();
+        json!({
+            "count": self.count,
+            "sum_ms": self.sum_ms as u64,
+            "mean_ms": self.mean(),
+            "p50_ms": self.percentile(0.50) as u64,
+            "p90_ms": self.percentile(0.90) as u64,
+            "p99_ms": self.percentile(0.99) as u64,
+            "max_ms": self.max_ms as u64,
+            "buckets": buckets,
+        })
+    }
+
+    /// Cumulative `(le, count)` pairs in Prometheus order, ending with `+Inf`.
+    fn cumulative_buckets(&self) -> Vec<(String, usize)> {
+        let mut cumulative = 0;
+        let mut out: Vec<(String, usize)> = LATENCY_BUCKETS_MS
+            .iter()
+            .zip(self.buckets.iter())
+            .map(|(bound, n)| {
+                cumulative += n;
+                (bound.to_string(), cumulative)
+            })
+            .collect();
+        out.push(("+Inf".to_string(), self.count));
+        out
+    }
+}
+
+// ==================== Adaptive Timeouts ====================
+
+/// Answered fetches needed before a relay's own latency sets its timeout
+const ADAPTIVE_MIN_SAMPLES: usize = 20;
+const ADAPTIVE_QUANTILE: f64 = 0.95;
+/// Headroom over the quantile: a multiple of it plus a fixed margin
+const ADAPTIVE_FACTOR: f64 = 1.5;
+const ADAPTIVE_MARGIN_MS: u128 = 100;
+const ADAPTIVE_MIN_MS: u128 = 100;
+/// Adaptive timeouts never exceed this multiple of the configured one
+const ADAPTIVE_MAX_FACTOR: u32 = 3;
+
+// ==================== Rolling Windows ====================
+
+/// Recent spans reported next to the lifetime totals, shortest first
+pub(crate) const METRIC_WINDOWS: [(&str, Duration); 3] = [
+    ("5m", Duration::from_secs(5 * 60)),
+    ("1h", Duration::from_secs(60 * 60)),
+    ("24h", Duration::from_secs(24 * 60 * 60)),
+];
+/// Activity is bucketed per slot, so windows are exact to the slot
+const WINDOW_SLOT: Duration = Duration::from_secs(60);
+
+/// Counters for one slot, or several merged into a window
+#[derive(Clone, Debug, Default)]
+pub(crate) struct WindowCounts {
+    pub(crate) requests: usize,
+    pub(crate) cache_hits: usize,
+    pub(crate) cache_misses: usize,
+    pub(crate) relay_fetches: usize,
+    pub(crate) failed_fetches: usize,
+    pub(crate) shed_requests: usize,
+    pub(crate) tool_calls: usize,
+    pub(crate) tool_failures: usize,
+    pub(crate) cache_latency: LatencyHistogram,
+    pub(crate) fetch_latency: LatencyHistogram,
+}
+
+impl WindowCounts {
+    fn merge(&mut self, other: &WindowCounts) {
+        self.requests += other.requests;
+        self.cache_hits += other.cache_hits;
+        self.cache_misses += other.cache_misses;
+        self.relay_fetches += other.relay_fetches;
+        self.failed_fetches += other.failed_fetches;
+        self.shed_requests += other.shed_requests;
+        self.tool_calls += other.tool_calls;
+        self.tool_failures += other.tool_failures;
+        self.cache_latency.merge(&other.cache_latency);
+        self.fetch_latency.merge(&other.fetch_latency);
+    }
+
+    fn cache_hit_rate(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            (self.cache_hits as f64 / self.requests as f64) * 100.0
+        }
+    }
+
+    fn tool_failure_rate(&self) -> f64 {
+        if self.tool_calls == 0 {
+            0.0
+        } else {
+            (self.tool_failures as f64 / self.tool_calls as f64) * 100.0
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "requests": self.requests,
+            "cache_hits": self.cache_hits,
+            "cache_misses": self.cache_misses,
+            "cache_hit_rate_pct": self.cache_hit_rate(),
+            "relay_fetches": self.relay_fetches,
+            "failed_fetches": self.failed_fetches,
+            "shed": self.shed_requests,
+            "tool_calls": self.tool_calls,
+            "tool_failures": self.tool_failures,
+            "tool_failure_rate_pct": self.tool_failure_rate(),
+            "cache_latency": self.cache_latency.to_json(),
+            "relay_latency": self.fetch_latency.to_json(),
+        })
+    }
+}
+
+/// Per-minute activity over the longest window, so the report can show how
+/// the server behaves now rather than averaged since startup
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RollingWindows {
+    /// Oldest first, each with when its slot started
+    slots: VecDeque<(Instant, WindowCounts)>,
+}
+
+impl RollingWindows {
+    /// The slot for activity happening now, dropping slots older than the
+    /// longest window
+    fn current(&mut self) -> &mut WindowCounts {
+        let now = Instant::now();
+        let longest = METRIC_WINDOWS[METRIC_WINDOWS.len() - 1].1;
+        while self.slots.front().is_some_and(|(start, _)| now.duration_since(*start) > longest) {
+            self.slots.pop_front();
+        }
+        if self.slots.back().is_none_or(|(start, _)| now.duration_since(*start) >= WINDOW_SLOT) {
+            self.slots.push_back((now, WindowCounts::default()));
+        }
+        &mut self.slots.back_mut().expect("slot just pushed").1
+    }
+
+    /// Everything recorded in the last `span`
+    pub(crate) fn summary(&self, span: Duration) -> WindowCounts {
+        let now = Instant::now();
+        let mut total = WindowCounts::default();
+        for (_, counts) in self.slots.iter().rev().take_while(|(start, _)| now.duration_since(*start) <= span) {
+            total.merge(counts);
+        }
+        total
+    }
+}
+
+// ==================== Metrics History ====================
+
+/// The metrics totals of one closed period
+#[derive(Clone, Debug)]
+pub(crate) struct MetricsPeriod {
+    pub(crate) since: Timestamp,
+    pub(crate) until: Timestamp,
+    /// `scheduled` for a `metrics_rotation`, `reset` for `reset_metrics`
+    pub(crate) reason: &'static str,
+    pub(crate) metrics: PerformanceMetrics,
+    /// Cache footprint when the period closed
+    pub(crate) cache: CacheUsage,
+}
+
+impl MetricsPeriod {
+    pub(crate) fn format_summary(&self) -> String {
+        let m = &self.metrics;
+        let tool_calls: usize = m.tools.values().map(|t| t.calls).sum();
+        let tool_failures: usize = m.tools.values().map(|t| t.failures).sum();
+        format!(
+            "• {} → {} ({})\n    Requests: {} | Cache hits: {:.1}% | Relay fetches: {} ({} failed) | Relay p50/p90/p99: {} | Tool calls: {} ({} failed) | Shed: {}",
+            self.since.to_human_datetime(),
+            self.until.to_human_datetime(),
+            self.reason,
+            m.total_requests,
+            m.cache_hit_rate(),
+            m.relay_fetches,
+            m.failed_fetches,
+            m.fetch_latency.format_percentiles(),
+            tool_calls,
+            tool_failures,
+            m.shed_requests
+        )
+    }
+
+    /// The `to_json` snapshot of the period, without the rolling windows
+    /// that only describe the live metrics
+    pub(crate) fn to_json(&self) -> Value {
+        let mut metrics = self.metrics.to_json(&self.cache);
+        if let Some(fields) = metrics.as_object_mut() {
+            fields.remove("windows");
+        }
+        json!({
+            "since": self.since.as_secs(),
+            "until": self.until.as_secs(),
+            "reason": self.reason,
+            "metrics": metrics,
+        })
+    }
+}
+
+/// Closed metrics periods, oldest first, and when the current one started
+#[derive(Clone, Debug)]
+pub(crate) struct MetricsHistory {
+    pub(crate) since: Timestamp,
+    pub(crate) periods: VecDeque<MetricsPeriod>,
+}
+
+impl Default for MetricsHistory {
+    fn default() -> Self {
+        Self { since: Timestamp::now(), periods: VecDeque::new() }
+    }
+}
+
+// ==================== Cache Usage ====================
+
+/// Point-in-time cache footprint, sampled when metrics are rendered.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct CacheUsage {
+    pub(crate) entries: usize,
+    pub(crate) events: usize,
+    pub(crate) bytes: usize,
+}
+
+// ==================== Performance Metrics ====================
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct PerformanceMetrics {
+    pub(crate) total_requests: usize,
+    pub(crate) cache_hits: usize,
+    pub(crate) cache_misses: usize,
+    pub(crate) relay_fetches: usize,
+    pub(crate) failed_fetches: usize,
+    /// Popular searches refetched in the background before they expired
+    pub(crate) prefetches: usize,
+    /// Relay fetches that waited for a free slot under `max_concurrent_fetches`
+    pub(crate) queued_fetches: usize,
+    /// How long queued fetches waited
+    pub(crate) queue_wait: LatencyHistogram,
+    /// Requests refused because the fetch queue or tool-call limit was full
+    pub(crate) shed_requests: usize,
+    /// Cached events dropped by compaction for being older than `retention_max_age`
+    pub(crate) purged_old_events: usize,
+    /// Cached events dropped by compaction for having expired too long ago
+    pub(crate) purged_expired_events: usize,
+    /// Publishes sent to a relay again after it rate-limited or failed them
+    pub(crate) publish_retries: usize,
+    /// Publishes not sent to a relay whose NIP-11 limits they break
+    pub(crate) skipped_publishes: usize,
+    pub(crate) cache_latency: LatencyHistogram,
+    pub(crate) fetch_latency: LatencyHistogram,
+    pub(crate) relays: HashMap<String, RelayMetrics>,
+    /// Per relay query strategy, so strategies can be compared after switching
+    pub(crate) strategies: HashMap<String, StrategyMetrics>,
+    /// Per tool (or `resource:<uri>`) call counts and latencies
+    pub(crate) tools: HashMap<String, ToolMetrics>,
+    /// The same activity over the last `METRIC_WINDOWS`
+    pub(crate) recent: RollingWindows,
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ToolMetrics {
+    pub(crate) calls: usize,
+    pub(crate) failures: usize,
+    /// Calls answered from the cache
+    pub(crate) cache_hits: usize,
+    pub(crate) latency: LatencyHistogram,
+}
+
+impl ToolMetrics {
+    fn failure_rate(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            (self.failures as f64 / self.calls as f64) * 100.0
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct StrategyMetrics {
+    pub(crate) fetches: usize,
+    pub(crate) failures: usize,
+    /// Relays queried, summed over fetches
+    pub(crate) relays_queried: usize,
+    pub(crate) events: usize,
+    pub(crate) latency: LatencyHistogram,
+}
+
+impl StrategyMetrics {
+    fn failure_rate(&self) -> f64 {
+        if self.fetches == 0 {
+            0.0
+        } else {
+            (self.failures as f64 / self.fetches as f64) * 100.0
+        }
+    }
+
+    fn avg_relays_queried(&self) -> f64 {
+        if self.fetches == 0 {
+            0.0
+        } else {
+            self.relays_queried as f64 / self.fetches as f64
+        }
+    }
+
+    fn avg_events(&self) -> f64 {
+        let successes = self.fetches - self.failures;
+        if successes == 0 {
+            0.0
+        } else {
+            self.events as f64 / successes as f64
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RelayMetrics {
+    pub(crate) fetches: usize,
+    pub(crate) failures: usize,
+    pub(crate) events_received: usize,
+    pub(crate) latency: LatencyHistogram,
+    /// Latency of the fetches it answered, leaving out errors and timeouts
+    pub(crate) answered: LatencyHistogram,
+    /// When a fetch last returned events from this relay
+    pub(crate) last_event_at: Option<std::time::Instant>,
+    /// When it last answered a fetch, with or without events
+    pub(crate) last_answer_at: Option<std::time::Instant>,
+}
+
+impl RelayMetrics {
+    pub(crate) fn avg_fetch_time(&self) -> f64 {
+        self.latency.mean()
+    }
+
+    pub(crate) fn failure_rate(&self) -> f64 {
+        if self.fetches == 0 {
+            0.0
+        } else {
+            (self.failures as f64 / self.fetches as f64) * 100.0
+        }
+    }
+
+    fn avg_events_per_fetch(&self) -> f64 {
+        let successes = self.fetches - self.failures;
+        if successes == 0 {
+            0.0
+        } else {
+            self.events_received as f64 / successes as f64
+        }
+    }
+}
+
+impl PerformanceMetrics {
+    /// Record one relay's part of a fetch; `events` is `None` on error/timeout.
+    pub(crate) fn record_relay_fetch(&mut self, relay: &str, duration_ms: u128, events: Option<usize>) {
+        let entry = self.relays.entry(relay.to_string()).or_default();
+        entry.fetches += 1;
+        entry.latency.record(duration_ms);
+        match events {
+            Some(count) => {
+                entry.events_received += count;
+                entry.answered.record(duration_ms);
+                entry.last_answer_at = Some(std::time::Instant::now());
+                if count > 0 {
+                    entry.last_event_at = Some(std::time::Instant::now());
+                }
+            }
+            None => entry.failures += 1,
+        }
+    }
+
+    /// Per-fetch deadline for `relay` learned from how fast relays answer:
+    /// the 95th percentile of its answered fetches, with headroom, once it
+    /// has enough of them, else of the whole pool's, else `configured`.
+    /// Kept between `ADAPTIVE_MIN_MS` and `ADAPTIVE_MAX_FACTOR` × `configured`,
+    /// so a fast pool stops waiting on a silent relay sooner and a relay
+    /// that answers close to the deadline gets more room.
+    pub(crate) fn relay_timeout(&self, relay: &str, configured: Duration) -> Duration {
+        let own = self.relays.get(relay).map(|m| &m.answered).filter(|h| h.count() >= ADAPTIVE_MIN_SAMPLES);
+        let pool;
+        let answered = match own {
+            Some(answered) => answered,
+            None => {
+                let mut merged = LatencyHistogram::default();
+                for m in self.relays.values() {
+                    merged.merge(&m.answered);
+                }
+                pool = merged;
+                if pool.count() < ADAPTIVE_MIN_SAMPLES {
+                    return configured;
+                }
+                &pool
+            }
+        };
+        let learned = (answered.percentile(ADAPTIVE_QUANTILE) as f64 * ADAPTIVE_FACTOR) as u128 + ADAPTIVE_MARGIN_MS;
+        Duration::from_millis(learned.max(ADAPTIVE_MIN_MS) as u64).min(configured * ADAPTIVE_MAX_FACTOR)
+    }
+
+    /// Record one whole fetch made with `strategy`; `events` is `None` when
+    /// no relay answered
+    pub(crate) fn record_strategy_fetch(&mut self, strategy: &str, duration_ms: u128, relays_queried: usize, events: Option<usize>) {
+        let entry = self.strategies.entry(strategy.to_string()).or_default();
+        entry.fetches += 1;
+        entry.relays_queried += relays_queried;
+        entry.latency.record(duration_ms);
+        match events {
+            Some(count) => entry.events += count,
+            None => entry.failures += 1,
+        }
+    }
+
+    /// Record one tool call or resource read; `cache` is the trace's cache
+    /// decision, if it made one
+    pub(crate) fn record_tool_call(&mut self, tool: &str, duration_ms: u128, success: bool, cache: Option<&str>) {
+        let entry = self.tools.entry(tool.to_string()).or_default();
+        entry.calls += 1;
+        entry.latency.record(duration_ms);
+        if !success {
+            entry.failures += 1;
+        }
+        if matches!(cache, Some("hit" | "stale")) {
+            entry.cache_hits += 1;
+        }
+        let window = self.recent.current();
+        window.tool_calls += 1;
+        if !success {
+            window.tool_failures += 1;
+        }
+    }
+
+    fn format_tool_breakdown(&self) -> String {
+        if self.tools.is_empty() {
+            return "  (no tool calls yet)".to_string();
+        }
+
+        let mut tools: Vec<_> = self.tools.iter().collect();
+        tools.sort_by(|a, b| b.1.calls.cmp(&a.1.calls).then_with(|| a.0.cmp(b.0)));
+        let width = tools.iter().map(|(name, _)| name.len()).max().unwrap_or(0).max(4);
+        let mut lines = vec![format!(
+            "  {:<width$}  {:>6}  {:>8}  {:>10}  {:>9}  p50/p90/p99",
+            "Tool", "Calls", "Failures", "Cache hits", "Avg"
+        )];
+        lines.extend(tools.iter().map(|(name, m)| {
+            format!(
+                "  {:<width$}  {:>6}  {:>8}  {:>10}  {:>7.2}ms  {}",
+                name,
+                m.calls,
+                format!("{} ({:.0}%)", m.failures, m.failure_rate()),
+                m.cache_hits,
+                m.latency.mean(),
+                m.latency.format_percentiles()
+            )
+        }));
+        lines.join("\n")
+    }
+
+    fn format_strategy_breakdown(&self) -> String {
+        if self.strategies.is_empty() {
+            return "  (no relay fetches yet)".to_string();
+        }
+
+        let mut strategies: Vec<_> = self.strategies.iter().collect();
+        strategies.sort_by(|a, b| a.0.cmp(b.0));
+        strategies
+            .iter()
+            .map(|(strategy, m)| {
+                format!(
+                    "• {}\n    Fetches: {} | Failures: {} ({:.1}%) | Avg: {:.2}ms | p50/p90/p99: {} | Relays/fetch: {:.1} | Events/fetch: {:.1}",
+                    strategy,
+                    m.fetches,
+                    m.failures,
+                    m.failure_rate(),
+                    m.latency.mean(),
+                    m.latency.format_percentiles(),
+                    m.avg_relays_queried(),
+                    m.avg_events()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn format_windows(&self) -> String {
+        METRIC_WINDOWS
+            .iter()
+            .map(|(name, span)| {
+                let w = self.recent.summary(*span);
+                format!(
+                    "• Last {}: {} requests | Cache hits: {:.1}% | Relay fetches: {} ({} failed) | Relay p50/p90/p99: {} | Tool calls: {} ({:.1}% failed) | Shed: {}",
+                    name,
+                    w.requests,
+                    w.cache_hit_rate(),
+                    w.relay_fetches,
+                    w.failed_fetches,
+                    w.fetch_latency.format_percentiles(),
+                    w.tool_calls,
+                    w.tool_failure_rate(),
+                    w.shed_requests
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn format_relay_breakdown(&self) -> String {
+        if self.relays.is_empty() {
+            return "  (no relay fetches yet)".to_string();
+        }
+
+        let mut relays: Vec<_> = self.relays.iter().collect();
+        relays.sort_by(|a, b| a.0.cmp(b.0));
+        relays
+            .iter()
+            .map(|(url, m)| {
+                format!(
+                    "• {}\n    Fetches: {} | Failures: {} ({:.1}%) | Avg: {:.2}ms | p50/p90/p99: {} | Events: {} ({:.1}/fetch)",
+                    url,
+                    m.fetches,
+                    m.failures,
+                    m.failure_rate(),
+                    m.avg_fetch_time(),
+                    m.latency.format_percentiles(),
+                    m.events_received,
+                    m.avg_events_per_fetch()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub(crate) fn record_cache_hit(&mut self, duration_ms: u128) {
+        self.total_requests += 1;
+        self.cache_hits += 1;
+        self.cache_latency.record(duration_ms);
+        let window = self.recent.current();
+        window.requests += 1;
+        window.cache_hits += 1;
+        window.cache_latency.record(duration_ms);
+    }
+
+    pub(crate) fn record_cache_miss(&mut self, duration_ms: u128, success: bool) {
+        self.total_requests += 1;
+        self.cache_misses += 1;
+        let window = self.recent.current();
+        window.requests += 1;
+        window.cache_misses += 1;
+
+        if success {
+            self.relay_fetches += 1;
+            self.fetch_latency.record(duration_ms);
+            window.relay_fetches += 1;
+            window.fetch_latency.record(duration_ms);
+        } else {
+            self.failed_fetches += 1;
+            window.failed_fetches += 1;
+        }
+    }
+
+    /// A prefetch goes through the miss path like any fetch, but no client
+    /// asked for it: move it out of the request counts
+    pub(crate) fn record_prefetch(&mut self) {
+        self.prefetches += 1;
+        self.total_requests = self.total_requests.saturating_sub(1);
+        self.cache_misses = self.cache_misses.saturating_sub(1);
+        let window = self.recent.current();
+        window.requests = window.requests.saturating_sub(1);
+        window.cache_misses = window.cache_misses.saturating_sub(1);
+    }
+
+    pub(crate) fn record_queue_wait(&mut self, duration_ms: u128) {
+        self.queued_fetches += 1;
+        self.queue_wait.record(duration_ms);
+    }
+
+    pub(crate) fn record_purge(&mut self, old: usize, expired: usize) {
+        self.purged_old_events += old;
+        self.purged_expired_events += expired;
+    }
+
+    pub(crate) fn record_publish_retry(&mut self) {
+        self.publish_retries += 1;
+    }
+
+    pub(crate) fn record_skipped_publish(&mut self) {
+        self.skipped_publishes += 1;
+    }
+
+    pub(crate) fn record_shed(&mut self) {
+        self.shed_requests += 1;
+        self.recent.current().shed_requests += 1;
+    }
+
+    pub(crate) fn cache_hit_rate(&self) -> f64 {
+        if self.total_requests == 0 {
+            0.0
+        } else {
+            (self.cache_hits as f64 / self.total_requests as f64) * 100.0
+        }
+    }
+
+    pub(crate) fn avg_cache_time(&self) -> f64 {
+        self.cache_latency.mean()
+    }
+
+    pub(crate) fn avg_fetch_time(&self) -> f64 {
+        self.fetch_latency.mean()
+    }
+
+    pub(crate) fn time_saved_ms(&self) -> u128 {
+        if self.cache_hits == 0 || self.relay_fetches == 0 {
+            return 0;
+        }
+        
+        let avg_fetch = self.avg_fetch_time();
+        let avg_cache = self.avg_cache_time();
+        let time_saved_per_hit = (avg_fetch - avg_cache).max(0.0);
+        
+        (time_saved_per_hit * self.cache_hits as f64) as u128
+    }
+
+    pub(crate) fn format_report(&self, cache: &CacheUsage) -> String {
+        format!(
+            "📊 Performance Metrics Report\n\
+            ═══════════════════════════════════════════════════════════\n\n\
+            ⏱️  Recent Activity:\n\
+            {}\n\n\
+            🔢 Request Statistics (since start):\n\
+            • Total Requests: {}\n\
+            • Cache Hits: {} ({}%)\n\
+            • Cache Misses: {}\n\
+            • Relay Fetches: {}\n\
+            • Failed Fetches: {}\n\
+            • Background Prefetches: {}\n\
+            • Queued Relay Fetches: {} (wait p50/p90/p99: {})\n\
+            • Shed Requests (overloaded): {}\n\
+            • Publish Retries: {} (skipped for relay limits: {})\n\n\
+            ⚡ Cache Performance:\n\
+            • Average Cache Response: {:.2}ms\n\
+            • p50 / p90 / p99: {}\n\n\
+            🌐 Relay Performance:\n\
+            • Average Relay Fetch: {:.2}ms\n\
+            • p50 / p90 / p99: {}\n\n\
+            💡 Performance Gains:\n\
+            • Cache Hit Rate: {:.1}%\n\
+            • Time Saved by Cache: {:.2}s\n\
+            • Speed Improvement: {:.1}x faster with cache\n\n\
+            📈 Efficiency Metrics:\n\
+            • Relay Load Reduction: {:.1}%\n\
+            • Success Rate: {:.1}%\n\n\
+            🗄️  Cache Usage:\n\
+            • Entries: {}\n\
+            • Cached Events: {}\n\
+            • Approximate Size: {:.1} KiB\n\
+            • Purged by Retention: {} old, {} expired\n\n\
+            🧰 Per-Tool Breakdown:\n\
+            {}\n\n\
+            🧭 Per-Strategy Breakdown:\n\
+            {}\n\n\
+            🛰️  Per-Relay Performance:\n\
+            {}",
+            self.format_windows(),
+            self.total_requests,
+            self.cache_hits,
+            self.cache_hit_rate(),
+            self.cache_misses,
+            self.relay_fetches,
+            self.failed_fetches,
+            self.prefetches,
+            self.queued_fetches,
+            self.queue_wait.format_percentiles(),
+            self.shed_requests,
+            self.publish_retries,
+            self.skipped_publishes,
+            self.avg_cache_time(),
+            self.cache_latency.format_percentiles(),
+            self.avg_fetch_time(),
+            self.fetch_latency.format_percentiles(),
+            self.cache_hit_rate(),
+            self.time_saved_ms() as f64 / 1000.0,
+            if self.avg_cache_time() > 0.0 { 
+                self.avg_fetch_time() / self.avg_cache_time() 
+            } else { 
+                1.0 
+            },
+            if self.total_requests > 0 {
+                (self.cache_hits as f64 / self.total_requests as f64) * 100.0
+            } else {
+                0.0
+            },
+            if self.total_requests > 0 {
+                (self.relay_fetches as f64 / self.total_requests as f64) * 100.0
+            } else {
+                0.0
+            },
+            cache.entries,
+            cache.events,
+            cache.bytes as f64 / 1024.0,
+            self.purged_old_events,
+            self.purged_expired_events,
+            self.format_tool_breakdown(),
+            self.format_strategy_breakdown(),
+            self.format_relay_breakdown()
+        )
+    }
+
+    /// Structured snapshot of the metrics for machine consumers.
+    pub(crate) fn to_json(&self, cache: &CacheUsage) -> Value {
+        let relays: serde_json::Map<String, Value> = self
+            .relays
+            .iter()
+            .map(|(url, m)| {
+                (
+                    url.clone(),
+                    json!({
+                        "fetches": m.fetches,
+                        "failures": m.failures,
+                        "failure_rate_pct": m.failure_rate(),
+                        "events_received": m.events_received,
+                        "events_per_fetch": m.avg_events_per_fetch(),
+                        "latency": m.latency.to_json(),
+                    }),
+                )
+            })
+            .collect();
+
+        json!({
+            "requests": {
+                "total": self.total_requests,
+                "cache_hits": self.cache_hits,
+                "cache_misses": self.cache_misses,
+                "relay_fetches": self.relay_fetches,
+                "failed_fetches": self.failed_fetches,
+                "prefetches": self.prefetches,
+                "queued_fetches": self.queued_fetches,
+                "shed": self.shed_requests,
+                "publish_retries": self.publish_retries,
+                "skipped_publishes": self.skipped_publishes,
+            },
+            "queue": {
+                "wait": self.queue_wait.to_json(),
+            },
+            "cache": {
+                "hit_rate_pct": self.cache_hit_rate(),
+                "time_saved_ms": self.time_saved_ms() as u64,
+                "latency": self.cache_latency.to_json(),
+                "entries": cache.entries,
+                "events": cache.events,
+                "approx_bytes": cache.bytes,
+                "purged_old_events": self.purged_old_events,
+                "purged_expired_events": self.purged_expired_events,
+            },
+            "relay": {
+                "latency": self.fetch_latency.to_json(),
+            },
+            "relays": relays,
+            "windows": METRIC_WINDOWS
+                .iter()
+                .map(|(name, span)| (name.to_string(), self.recent.summary(*span).to_json()))
+                .collect::<serde_json::Map<String, Value>>(),
+            "strategies": self
+                .strategies
+                .iter()
+                .map(|(strategy, m)| {
+                    (
+                        strategy.clone(),
+                        json!({
+                            "fetches": m.fetches,
+                            "failures": m.failures,
+                            "failure_rate_pct": m.failure_rate(),
+                            "relays_per_fetch": m.avg_relays_queried(),
+                            "events_per_fetch": m.avg_events(),
+                            "latency": m.latency.to_json(),
+                        }),
+                    )
+                })
+                .collect::<serde_json::Map<String, Value>>(),
+            "tools": self
+                .tools
+                .iter()
+                .map(|(tool, m)| {
+                    (
+                        tool.clone(),
+                        json!({
+                            "calls": m.calls,
+                            "failures": m.failures,
+                            "failure_rate_pct": m.failure_rate(),
+                            "cache_hits": m.cache_hits,
+                            "latency": m.latency.to_json(),
+                        }),
+                    )
+                })
+                .collect::<serde_json::Map<String, Value>>(),
+        })
+    }
+
+    /// Render the metrics in the Prometheus text exposition format.
+    pub(crate) fn format_prometheus(&self, cache: &CacheUsage) -> String {
+        let mut out = String::new();
+
+        let counters = [
+            ("jobmcp_requests_total", "Total data requests served", self.total_requests),
+            ("jobmcp_cache_hits_total", "Requests answered from cache", self.cache_hits),
+            ("jobmcp_cache_misses_total", "Requests that went to the relays", self.cache_misses),
+            ("jobmcp_relay_fetches_total", "Successful relay fetches", self.relay_fetches),
+            ("jobmcp_failed_fetches_total", "Failed or timed out relay fetches", self.failed_fetches),
+            ("jobmcp_prefetches_total", "Popular searches refreshed before expiring", self.prefetches),
+            ("jobmcp_queued_fetches_total", "Relay fetches that waited for a concurrency slot", self.queued_fetches),
+            ("jobmcp_shed_requests_total", "Requests refused while overloaded", self.shed_requests),
+            ("jobmcp_publish_retries_total", "Publishes retried after a relay rate-limited or failed them", self.publish_retries),
+            ("jobmcp_skipped_publishes_total", "Publishes not sent to relays whose NIP-11 limits they break", self.skipped_publishes),
+            ("jobmcp_purged_old_events_total", "Cached events purged for their age", self.purged_old_events),
+            ("jobmcp_purged_expired_events_total", "Cached events purged for having expired", self.purged_expired_events),
+        ];
+        for (name, help, value) in counters {
+            let _ = writeln!(out, "# HELP {} {}\n# TYPE {} counter\n{} {}", name, help, name, name, value);
+        }
+
+        let gauges = [
+            ("jobmcp_cache_entries", "Cached query results", cache.entries),
+            ("jobmcp_cache_events", "Events held in the cache", cache.events),
+            ("jobmcp_cache_bytes", "Approximate serialized size of cached events", cache.bytes),
+        ];
+        for (name, help, value) in gauges {
+            let _ = writeln!(out, "# HELP {} {}\n# TYPE {} gauge\n{} {}", name, help, name, name, value);
+        }
+
+        write_histogram(&mut out, "jobmcp_cache_latency_ms", "Cache hit latency in milliseconds", &[("", &self.cache_latency)]);
+        write_histogram(&mut out, "jobmcp_fetch_latency_ms", "Relay fetch latency in milliseconds", &[("", &self.fetch_latency)]);
+        write_histogram(&mut out, "jobmcp_fetch_queue_wait_ms", "Time relay fetches waited for a concurrency slot in milliseconds", &[("", &self.queue_wait)]);
+
+        let mut relays: Vec<_> = self.relays.iter().collect();
+        relays.sort_by(|a, b| a.0.cmp(b.0));
+        let relay_labels: Vec<String> = relays.iter().map(|(url, _)| format!("relay=\"{}\"", label_value(url))).collect();
+        let relay_histograms: Vec<(&str, &LatencyHistogram)> = relay_labels
+            .iter()
+            .zip(relays.iter())
+            .map(|(label, (_, m))| (label.as_str(), &m.latency))
+            .collect();
+        write_histogram(&mut out, "jobmcp_relay_fetch_latency_ms", "Per-relay fetch latency in milliseconds", &relay_histograms);
+
+        let relay_counters = [
+            ("jobmcp_relay_failures_total", "Failed or timed out fetches per relay", RelayCounter::Failures),
+            ("jobmcp_relay_events_total", "Events received per relay", RelayCounter::Events),
+        ];
+        for (name, help, counter) in relay_counters {
+            let _ = writeln!(out, "# HELP {} {}\n# TYPE {} counter", name, help, name);
+            for (label, (_, m)) in relay_labels.iter().zip(relays.iter()) {
+                let value = match counter {
+                    RelayCounter::Failures => m.failures,
+                    RelayCounter::Events => m.events_received,
+                };
+                let _ = writeln!(out, "{}{{{}}} {}", name, label, value);
+            }
+        }
+
+        let mut strategies: Vec<_> = self.strategies.iter().collect();
+        strategies.sort_by(|a, b| a.0.cmp(b.0));
+        let strategy_labels: Vec<String> = strategies.iter().map(|(strategy, _)| format!("strategy=\"{}\"", label_value(strategy))).collect();
+        let strategy_histograms: Vec<(&str, &LatencyHistogram)> = strategy_labels
+            .iter()
+            .zip(strategies.iter())
+            .map(|(label, (_, m))| (label.as_str(), &m.latency))
+            .collect();
+        write_histogram(&mut out, "jobmcp_strategy_fetch_latency_ms", "Whole-fetch latency per relay query strategy in milliseconds", &strategy_histograms);
+        let _ = writeln!(
+            out,
+            "# HELP jobmcp_strategy_failures_total Fetches no relay answered per query strategy\n# TYPE jobmcp_strategy_failures_total counter"
+        );
+        for (label, (_, m)) in strategy_labels.iter().zip(strategies.iter()) {
+            let _ = writeln!(out, "jobmcp_strategy_failures_total{{{}}} {}", label, m.failures);
+        }
+
+        let mut tools: Vec<_> = self.tools.iter().collect();
+        tools.sort_by(|a, b| a.0.cmp(b.0));
+        let tool_labels: Vec<String> = tools.iter().map(|(tool, _)| format!("tool=\"{}\"", label_value(tool))).collect();
+        let tool_histograms: Vec<(&str, &LatencyHistogram)> = tool_labels
+            .iter()
+            .zip(tools.iter())
+            .map(|(label, (_, m))| (label.as_str(), &m.latency))
+            .collect();
+        write_histogram(&mut out, "jobmcp_tool_latency_ms", "Per-tool call latency in milliseconds", &tool_histograms);
+
+        let tool_counters = [
+            ("jobmcp_tool_calls_total", "Calls per tool or resource", ToolCounter::Calls),
+            ("jobmcp_tool_failures_total", "Failed calls per tool or resource", ToolCounter::Failures),
+            ("jobmcp_tool_cache_hits_total", "Calls answered from cache per tool or resource", ToolCounter::CacheHits),
+        ];
+        for (name, help, counter) in tool_counters {
+            let _ = writeln!(out, "# HELP {} {}\n# TYPE {} counter", name, help, name);
+            for (label, (_, m)) in tool_labels.iter().zip(tools.iter()) {
+                let value = match counter {
+                    ToolCounter::Calls => m.calls,
+                    ToolCounter::Failures => m.failures,
+                    ToolCounter::CacheHits => m.cache_hits,
+                };
+                let _ = writeln!(out, "{}{{{}}} {}", name, label, value);
+            }
+        }
+
+        let windows: Vec<(&str, WindowCounts)> =
+            METRIC_WINDOWS.iter().map(|(name, span)| (*name, self.recent.summary(*span))).collect();
+        let window_gauges = [
+            ("jobmcp_window_requests", "Data requests served in the window", WindowGauge::Requests),
+            ("jobmcp_window_cache_hit_rate_pct", "Share of the window's requests answered from cache", WindowGauge::CacheHitRate),
+            ("jobmcp_window_failed_fetches", "Failed or timed out relay fetches in the window", WindowGauge::FailedFetches),
+            ("jobmcp_window_tool_failure_rate_pct", "Share of the window's tool calls that failed", WindowGauge::ToolFailureRate),
+        ];
+        for (name, help, gauge) in window_gauges {
+            let _ = writeln!(out, "# HELP {} {}\n# TYPE {} gauge", name, help, name);
+            for (window, w) in &windows {
+                let value = match gauge {
+                    WindowGauge::Requests => w.requests as f64,
+                    WindowGauge::CacheHitRate => w.cache_hit_rate(),
+                    WindowGauge::FailedFetches => w.failed_fetches as f64,
+                    WindowGauge::ToolFailureRate => w.tool_failure_rate(),
+                };
+                let _ = writeln!(out, "{}{{window=\"{}\"}} {}", name, window, value);
+            }
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP jobmcp_latency_quantile_ms Estimated latency quantiles in milliseconds\n\
+             # TYPE jobmcp_latency_quantile_ms gauge"
+        );
+        for (path, histogram) in [("cache", &self.cache_latency), ("relay", &self.fetch_latency)] {
+            for (quantile, q) in [("0.5", 0.50), ("0.9", 0.90), ("0.99", 0.99)] {
+                let _ = writeln!(
+                    out,
+                    "jobmcp_latency_quantile_ms{{path=\"{}\",quantile=\"{}\"}} {}",
+                    path,
+                    quantile,
+                    histogram.percentile(q)
+                );
+            }
+        }
+
+        out
+    }
+}
+
+#[derive(Clone, Copy)]
+enum RelayCounter {
+    Failures,
+    Events,
+}
+
+#[derive(Clone, Copy)]
+enum WindowGauge {
+    Requests,
+    CacheHitRate,
+    FailedFetches,
+    ToolFailureRate,
+}
+
+#[derive(Clone, Copy)]
+enum ToolCounter {
+    Calls,
+    Failures,
+    CacheHits,
+}
+
+/// A Prometheus label value with `\`, `"` and newlines escaped, so a relay
+/// URL or tool name can't break out of its label
+fn label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn write_histogram(out: &mut String, name: &str, help: &str, series: &[(&str, &LatencyHistogram)]) {
+    let _ = writeln!(out, "# HELP {} {}\n# TYPE {} histogram", name, help, name);
+    for (labels, histogram) in series {
+        let prefix = if labels.is_empty() { String::new() } else { format!("{},", labels) };
+        for (le, count) in histogram.cumulative_buckets() {
+            let _ = writeln!(out, "{}_bucket{{{}le=\"{}\"}} {}", name, prefix, le, count);
+        }
+        let suffix = if labels.is_empty() { String::new() } else { format!("{{{}}}", labels) };
+        let _ = writeln!(out, "{}_sum{} {}", name, suffix, histogram.sum_ms());
+        let _ = writeln!(out, "{}_count{} {}", name, suffix, histogram.count());
+    }
+}
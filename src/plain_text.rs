@@ -0,0 +1,51 @@
+// src/plain_text.rs
+// Accessible output: emoji and box-drawing characters stripped from
+// generated text, for screen readers and transcripts stored as logs
+
+/// `text` without emoji (with their variation selectors, joiners and keycap
+/// marks) or box-drawing and block characters. The space that separated a
+/// removed symbol from the next word goes with it, and lines left with
+/// trailing spaces are trimmed.
+pub fn strip(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if !is_decoration(c) {
+            plain.push(c);
+            continue;
+        }
+        // Skip the rest of a sequence (🏳️‍🌈, 1️⃣) and the space after it
+        while chars.peek().is_some_and(|next| is_decoration(*next)) {
+            chars.next();
+        }
+        if chars.peek() == Some(&' ') && (plain.is_empty() || plain.ends_with([' ', '\n', '('])) {
+            chars.next();
+        }
+    }
+    plain.split('\n').map(str::trim_end).collect::<Vec<_>>().join("\n")
+}
+
+/// Whether `text` has nothing `strip` would remove
+pub fn is_plain(text: &str) -> bool {
+    !text.chars().any(is_decoration)
+}
+
+fn is_decoration(c: char) -> bool {
+    matches!(
+        c as u32,
+        // Box drawing and block elements (─ │ ┌ █ ▌)
+        0x2500..=0x259F
+            // Miscellaneous technical (⌛ ⏰ ⏱), symbols and dingbats (⚠ ⚡ ✅ ✨)
+            | 0x2300..=0x23FF
+            | 0x2600..=0x27BF
+            // Stars and arrows used as emoji (⭐ ⬆)
+            | 0x2B00..=0x2BFF
+            // Pictographs, emoticons, transport, flags and the supplements
+            | 0x1F000..=0x1FAFF
+            // Variation selectors, zero-width joiner, keycap, tag characters
+            | 0xFE0E..=0xFE0F
+            | 0x200D
+            | 0x20E3
+            | 0xE0020..=0xE007F
+    )
+}
@@ -0,0 +1,65 @@
+// src/proxy.rs
+// Client address and scheme of requests, as seen through a reverse proxy
+
+use std::net::{IpAddr, SocketAddr};
+
+use http::request::Parts;
+use rmcp::{RoleServer, service::RequestContext};
+
+/// Peer of the TCP connection a request arrived on; the HTTP server inserts
+/// it into each request's extensions
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PeerAddr(pub SocketAddr);
+
+/// Normalize a URL path prefix to `/segment[/segment…]` without a trailing
+/// slash; blank and `/` mean no prefix
+pub fn normalize_base_path(path: &str) -> String {
+    let trimmed = path.trim().trim_matches('/');
+    if trimmed.is_empty() {
+        String::new()
+    } else {
+        format!("/{}", trimmed)
+    }
+}
+
+/// The client's IP. With `trust_forwarded` the last `X-Forwarded-For` entry
+/// wins, which is the address the proxy in front of us saw (entries before it
+/// are client-supplied); otherwise the connection's peer.
+pub fn client_ip(parts: &Parts, trust_forwarded: bool) -> Option<IpAddr> {
+    client_ip_from(&parts.headers, &parts.extensions, trust_forwarded)
+}
+
+/// `client_ip` for a request that hasn't been split into parts
+pub(crate) fn client_ip_from(
+    headers: &http::HeaderMap,
+    extensions: &http::Extensions,
+    trust_forwarded: bool,
+) -> Option<IpAddr> {
+    let forwarded = trust_forwarded
+        .then(|| headers.get_all("x-forwarded-for").iter().next_back())
+        .flatten()
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.rsplit(',').next())
+        .and_then(|ip| ip.trim().parse().ok());
+    forwarded.or_else(|| extensions.get::<PeerAddr>().map(|peer| peer.0.ip()))
+}
+
+/// `https` when a trusted proxy says the client connected over TLS, else `http`
+pub fn scheme(parts: &Parts, trust_forwarded: bool) -> &'static str {
+    let proto = trust_forwarded
+        .then(|| parts.headers.get("x-forwarded-proto"))
+        .flatten()
+        .and_then(|value| value.to_str().ok());
+    match proto {
+        Some(proto) if proto.trim().eq_ignore_ascii_case("https") => "https",
+        _ => "http",
+    }
+}
+
+/// Client IP of an MCP request, when it arrived over HTTP
+pub(crate) fn request_client_ip(context: &RequestContext<RoleServer>, trust_forwarded: bool) -> Option<IpAddr> {
+    context
+        .extensions
+        .get::<Parts>()
+        .and_then(|parts| client_ip(parts, trust_forwarded))
+}
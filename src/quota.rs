@@ -0,0 +1,81 @@
+// src/quota.rs
+// Per-API-key (or per-client) daily usage: tool calls and relay fetches, checked against quotas
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::jobs::JobsError;
+
+const SECS_PER_DAY: u64 = 86_400;
+
+/// One key's usage on its current (UTC) day
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct KeyUsage {
+    /// Days since the Unix epoch this usage belongs to
+    pub(crate) day: u64,
+    pub(crate) tool_calls: u64,
+    pub(crate) relay_fetches: u64,
+    pub(crate) last_used: u64,
+}
+
+/// Daily limits; 0 means unlimited
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Quotas {
+    pub(crate) tool_calls: u64,
+    pub(crate) relay_fetches: u64,
+}
+
+/// Usage per API key id, reset at midnight UTC
+#[derive(Debug, Default)]
+pub(crate) struct UsageLog {
+    keys: HashMap<String, KeyUsage>,
+}
+
+impl UsageLog {
+    /// Count a call against the key, or refuse it once the key has used up
+    /// either quota today. The call is counted here rather than when it
+    /// finishes, so concurrent calls can't all slip past the last one left.
+    /// A call is let through while fetches remain, so it may finish a little over.
+    pub(crate) fn reserve(&mut self, key: &str, quotas: Quotas, now: u64) -> Result<(), JobsError> {
+        let usage = self.today(key, now);
+        let resets_in = Duration::from_secs(SECS_PER_DAY - now % SECS_PER_DAY);
+        if quotas.tool_calls > 0 && usage.tool_calls >= quotas.tool_calls {
+            return Err(JobsError::QuotaExceeded { quota: "tool_calls", limit: quotas.tool_calls, resets_in });
+        }
+        if quotas.relay_fetches > 0 && usage.relay_fetches >= quotas.relay_fetches {
+            return Err(JobsError::QuotaExceeded { quota: "relay_fetches", limit: quotas.relay_fetches, resets_in });
+        }
+        usage.tool_calls += 1;
+        usage.last_used = now;
+        Ok(())
+    }
+
+    /// Count the relay fetches a finished call made
+    pub(crate) fn record(&mut self, key: &str, relay_fetches: u64, now: u64) {
+        let usage = self.today(key, now);
+        usage.relay_fetches += relay_fetches;
+        usage.last_used = now;
+    }
+
+    /// Keys used today, busiest first
+    pub(crate) fn snapshot(&self, now: u64) -> Vec<(String, KeyUsage)> {
+        let day = now / SECS_PER_DAY;
+        let mut keys: Vec<_> = self
+            .keys
+            .iter()
+            .filter(|(_, usage)| usage.day == day)
+            .map(|(key, usage)| (key.clone(), usage.clone()))
+            .collect();
+        keys.sort_by(|a, b| b.1.tool_calls.cmp(&a.1.tool_calls).then_with(|| a.0.cmp(&b.0)));
+        keys
+    }
+
+    fn today(&mut self, key: &str, now: u64) -> &mut KeyUsage {
+        let day = now / SECS_PER_DAY;
+        // Yesterday's entries go once a new day starts, so idle keys don't pile up
+        if self.keys.values().any(|usage| usage.day != day) {
+            self.keys.retain(|_, usage| usage.day == day);
+        }
+        self.keys.entry(key.to_string()).or_insert_with(|| KeyUsage { day, ..Default::default() })
+    }
+}
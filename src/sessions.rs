@@ -0,0 +1,232 @@
+// src/sessions.rs
+// Session manager that records MCP sessions in Redis so any instance can serve them
+
+use std::time::Duration;
+
+use futures::Stream;
+use redis::aio::ConnectionManager;
+use rmcp::{
+    ServiceExt,
+    model::{ClientJsonRpcMessage, ClientNotification, ServerJsonRpcMessage},
+    transport::{
+        WorkerTransport,
+        common::server_side_http::ServerSseMessage,
+        streamable_http_server::{
+            SessionId, SessionManager,
+            session::local::{
+                LocalSessionManager, LocalSessionManagerError, LocalSessionWorker,
+                create_local_session,
+            },
+        },
+    },
+};
+use tokio::sync::Mutex;
+
+use crate::NostrJobsServer;
+
+const KEY_PREFIX: &str = "jobmcp:session:";
+
+/// Live sessions are still local to the process (they own the SSE channels),
+/// but each session's `initialize` request is stored in Redis. When a request
+/// arrives for a session this instance doesn't know, e.g. after a restart or
+/// when a load balancer routes to another replica, the session is recreated
+/// locally by replaying that handshake.
+///
+/// Redis failures are logged and degrade to purely local sessions rather than
+/// failing requests.
+pub struct RedisSessionManager {
+    local: LocalSessionManager,
+    redis: ConnectionManager,
+    server: NostrJobsServer,
+    /// `jobmcp:session:` plus the tenant, so sessions never cross boards
+    key_prefix: String,
+    ttl: Duration,
+    /// Serializes rehydration so concurrent requests don't create the session twice
+    rehydrating: Mutex<()>,
+}
+
+impl RedisSessionManager {
+    pub async fn connect(url: &str, server: NostrJobsServer, ttl: Duration) -> redis::RedisResult<Self> {
+        let client = redis::Client::open(url)?;
+        let redis = client.get_connection_manager().await?;
+        let key_prefix = match &server.config().tenant {
+            Some(tenant) => format!("{}{}:", KEY_PREFIX, tenant),
+            None => KEY_PREFIX.to_string(),
+        };
+        tracing::info!(ttl_secs = ttl.as_secs(), key_prefix = %key_prefix, "redis_session_store_connected");
+        Ok(Self {
+            local: LocalSessionManager::default(),
+            redis,
+            server,
+            key_prefix,
+            ttl,
+            rehydrating: Mutex::new(()),
+        })
+    }
+
+    fn key(&self, id: &SessionId) -> String {
+        format!("{}{}", self.key_prefix, id)
+    }
+
+    async fn store(&self, id: &SessionId, message: &ClientJsonRpcMessage) {
+        let payload = match serde_json::to_string(message) {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::warn!(session_id = %id, error = %e, "session_serialize_failed");
+                return;
+            }
+        };
+        let result: redis::RedisResult<()> = redis::cmd("SET")
+            .arg(self.key(id))
+            .arg(payload)
+            .arg("EX")
+            .arg(self.ttl.as_secs().max(1))
+            .query_async(&mut self.redis.clone())
+            .await;
+        if let Err(e) = result {
+            tracing::warn!(session_id = %id, error = %e, "session_store_failed");
+        }
+    }
+
+    async fn load(&self, id: &SessionId) -> Option<ClientJsonRpcMessage> {
+        let result: redis::RedisResult<Option<String>> = redis::cmd("GET")
+            .arg(self.key(id))
+            .query_async(&mut self.redis.clone())
+            .await;
+        match result {
+            Ok(payload) => payload.and_then(|p| match serde_json::from_str(&p) {
+                Ok(message) => Some(message),
+                Err(e) => {
+                    tracing::warn!(session_id = %id, error = %e, "session_deserialize_failed");
+                    None
+                }
+            }),
+            Err(e) => {
+                tracing::warn!(session_id = %id, error = %e, "session_load_failed");
+                None
+            }
+        }
+    }
+
+    /// Push the session's expiry forward; called on every client message
+    async fn touch(&self, id: &SessionId) {
+        let result: redis::RedisResult<()> = redis::cmd("EXPIRE")
+            .arg(self.key(id))
+            .arg(self.ttl.as_secs().max(1))
+            .query_async(&mut self.redis.clone())
+            .await;
+        if let Err(e) = result {
+            tracing::debug!(session_id = %id, error = %e, "session_touch_failed");
+        }
+    }
+
+    async fn forget(&self, id: &SessionId) {
+        let result: redis::RedisResult<()> = redis::cmd("DEL")
+            .arg(self.key(id))
+            .query_async(&mut self.redis.clone())
+            .await;
+        if let Err(e) = result {
+            tracing::warn!(session_id = %id, error = %e, "session_delete_failed");
+        }
+    }
+
+    /// Recreate a session stored by another instance (or a previous run of this one)
+    async fn rehydrate(&self, id: &SessionId) -> Result<bool, LocalSessionManagerError> {
+        let _guard = self.rehydrating.lock().await;
+        if self.local.has_session(id).await? {
+            return Ok(true);
+        }
+        let Some(initialize) = self.load(id).await else {
+            return Ok(false);
+        };
+
+        let (handle, worker) = create_local_session(id.clone(), self.local.session_config.clone());
+        let transport: WorkerTransport<LocalSessionWorker> = WorkerTransport::spawn(worker);
+        let service = self.server.clone();
+        tokio::spawn(async move {
+            match service.serve(transport).await {
+                Ok(running) => {
+                    let _ = running.waiting().await;
+                }
+                Err(e) => tracing::warn!(error = %e, "rehydrated_session_failed"),
+            }
+        });
+
+        handle.initialize(initialize).await?;
+        handle
+            .push_message(
+                ClientJsonRpcMessage::notification(ClientNotification::InitializedNotification(
+                    Default::default(),
+                )),
+                None,
+            )
+            .await?;
+        self.local.sessions.write().await.insert(id.clone(), handle);
+
+        tracing::info!(session_id = %id, "session_rehydrated");
+        Ok(true)
+    }
+}
+
+impl SessionManager for RedisSessionManager {
+    type Error = LocalSessionManagerError;
+    type Transport = WorkerTransport<LocalSessionWorker>;
+
+    async fn create_session(&self) -> Result<(SessionId, Self::Transport), Self::Error> {
+        self.local.create_session().await
+    }
+
+    async fn initialize_session(
+        &self,
+        id: &SessionId,
+        message: ClientJsonRpcMessage,
+    ) -> Result<ServerJsonRpcMessage, Self::Error> {
+        self.store(id, &message).await;
+        self.local.initialize_session(id, message).await
+    }
+
+    async fn has_session(&self, id: &SessionId) -> Result<bool, Self::Error> {
+        if self.local.has_session(id).await? {
+            return Ok(true);
+        }
+        self.rehydrate(id).await
+    }
+
+    async fn close_session(&self, id: &SessionId) -> Result<(), Self::Error> {
+        self.forget(id).await;
+        self.local.close_session(id).await
+    }
+
+    async fn create_stream(
+        &self,
+        id: &SessionId,
+        message: ClientJsonRpcMessage,
+    ) -> Result<impl Stream<Item = ServerSseMessage> + Send + Sync + 'static, Self::Error> {
+        self.touch(id).await;
+        self.local.create_stream(id, message).await
+    }
+
+    async fn accept_message(
+        &self,
+        id: &SessionId,
+        message: ClientJsonRpcMessage,
+    ) -> Result<(), Self::Error> {
+        self.touch(id).await;
+        self.local.accept_message(id, message).await
+    }
+
+    async fn create_standalone_stream(
+        &self,
+        id: &SessionId,
+    ) -> Result<impl Stream<Item = ServerSseMessage> + Send + Sync + 'static, Self::Error> {
+        self.local.create_standalone_stream(id).await
+    }
+
+    async fn resume(
+        &self,
+        id: &SessionId,
+        last_event_id: String,
+    ) -> Result<impl Stream<Item = ServerSseMessage> + Send + Sync + 'static, Self::Error> {
+        self.local.resume(id, last_event_id).await
+    }
+}
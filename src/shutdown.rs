@@ -0,0 +1,82 @@
+// src/shutdown.rs
+// Tracks in-flight tool calls so shutdown can wait for them to finish
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+#[derive(Debug, Default)]
+struct DrainState {
+    shutting_down: AtomicBool,
+    in_flight: AtomicUsize,
+    idle: Notify,
+}
+
+/// Shared gate for tool calls: open until `begin` is called, after which
+/// `enter` refuses new work and `wait` resolves once current work is done.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Drain {
+    state: Arc<DrainState>,
+}
+
+/// Held for the duration of one tool call
+pub(crate) struct DrainGuard {
+    state: Arc<DrainState>,
+}
+
+impl Drain {
+    /// Register a unit of work, or `None` once shutdown has begun
+    pub(crate) fn enter(&self) -> Option<DrainGuard> {
+        self.state.in_flight.fetch_add(1, Ordering::SeqCst);
+        if self.state.shutting_down.load(Ordering::SeqCst) {
+            self.release();
+            return None;
+        }
+        Some(DrainGuard { state: self.state.clone() })
+    }
+
+    pub(crate) fn begin(&self) {
+        self.state.shutting_down.store(true, Ordering::SeqCst);
+    }
+
+    pub(crate) fn is_shutting_down(&self) -> bool {
+        self.state.shutting_down.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn in_flight(&self) -> usize {
+        self.state.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Wait until no work is in flight or the deadline passes; returns the
+    /// number of calls still running.
+    pub(crate) async fn wait(&self, deadline: Duration) -> usize {
+        let _ = tokio::time::timeout(deadline, async {
+            loop {
+                let idle = self.state.idle.notified();
+                if self.in_flight() == 0 {
+                    return;
+                }
+                idle.await;
+            }
+        })
+        .await;
+        self.in_flight()
+    }
+
+    fn release(&self) {
+        release(&self.state);
+    }
+}
+
+impl Drop for DrainGuard {
+    fn drop(&mut self) {
+        release(&self.state);
+    }
+}
+
+fn release(state: &DrainState) {
+    if state.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+        state.idle.notify_waiters();
+    }
+}
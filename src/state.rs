@@ -0,0 +1,362 @@
+// src/state.rs
+// Per-user saved state (bookmarks, saved searches, followed employers, preferences, applications) persisted to a JSON file
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use nostr_sdk::Timestamp;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::RwLock;
+
+use crate::jobs::JobDraft;
+use crate::mcp_server::SearchJobsArgs;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct Bookmark {
+    pub(crate) job_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) note: Option<String>,
+    pub(crate) saved_at: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct SavedSearch {
+    pub(crate) name: String,
+    pub(crate) search: SearchJobsArgs,
+    pub(crate) saved_at: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct FollowedEmployer {
+    /// Hex public key of the job poster
+    pub(crate) pubkey: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) name: Option<String>,
+    pub(crate) followed_at: u64,
+}
+
+/// Default search filters set with `set_preferences`
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct CandidateProfile {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) skills: Vec<String>,
+    /// In the owner's display currency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) min_salary: Option<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) locations: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) excluded_companies: Vec<String>,
+}
+
+impl CandidateProfile {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.skills.is_empty() && self.min_salary.is_none() && self.locations.is_empty() && self.excluded_companies.is_empty()
+    }
+}
+
+/// Listings kept in `UserState::viewed`; older views are forgotten
+pub(crate) const MAX_VIEWED_JOBS: usize = 100;
+
+/// A listing opened with `get_job_details`, with what recommendations need
+/// to know about it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct ViewedJob {
+    pub(crate) job_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) company: Option<String>,
+    #[serde(default)]
+    pub(crate) skills: Vec<String>,
+    pub(crate) viewed_at: u64,
+}
+
+/// Where a job application stands, as set with `set_application_status`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ApplicationStatus {
+    Applied,
+    /// The employer answered
+    Replied,
+    Interviewing,
+    Rejected,
+    Offer,
+}
+
+impl ApplicationStatus {
+    pub(crate) const ALL: [ApplicationStatus; 5] = [
+        ApplicationStatus::Applied,
+        ApplicationStatus::Replied,
+        ApplicationStatus::Interviewing,
+        ApplicationStatus::Rejected,
+        ApplicationStatus::Offer,
+    ];
+
+    /// Still waiting on the employer, so worth a follow-up when it goes quiet
+    pub(crate) fn is_open(self) -> bool {
+        matches!(self, ApplicationStatus::Applied | ApplicationStatus::Replied | ApplicationStatus::Interviewing)
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            ApplicationStatus::Applied => "applied",
+            ApplicationStatus::Replied => "replied",
+            ApplicationStatus::Interviewing => "interviewing",
+            ApplicationStatus::Rejected => "rejected",
+            ApplicationStatus::Offer => "offer",
+        }
+    }
+}
+
+impl std::str::FromStr for ApplicationStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        ApplicationStatus::ALL.into_iter().find(|status| status.as_str() == s).ok_or_else(|| {
+            let known: Vec<&str> = ApplicationStatus::ALL.iter().map(|status| status.as_str()).collect();
+            format!("unknown status '{}', expected one of {}", s, known.join(", "))
+        })
+    }
+}
+
+impl std::fmt::Display for ApplicationStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct StatusChange {
+    pub(crate) status: ApplicationStatus,
+    pub(crate) at: u64,
+}
+
+/// A listing the owner applied to, tracked with `set_application_status`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct Application {
+    pub(crate) job_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) company: Option<String>,
+    pub(crate) status: ApplicationStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) note: Option<String>,
+    pub(crate) applied_at: u64,
+    /// When the status or note last changed
+    pub(crate) updated_at: u64,
+    /// Every status the application went through, oldest first
+    #[serde(default)]
+    pub(crate) history: Vec<StatusChange>,
+}
+
+impl Application {
+    /// Open and without news for at least `stale_after` seconds at `now`
+    pub(crate) fn is_stale(&self, now: u64, stale_after: u64) -> bool {
+        self.status.is_open() && now.saturating_sub(self.updated_at) >= stale_after
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct UserState {
+    #[serde(default)]
+    pub(crate) bookmarks: Vec<Bookmark>,
+    #[serde(default)]
+    pub(crate) saved_searches: Vec<SavedSearch>,
+    /// Job posters whose listings are shown first in search results
+    #[serde(default)]
+    pub(crate) followed_employers: Vec<FollowedEmployer>,
+    /// Free-form client preferences (e.g. preferred location or salary currency)
+    #[serde(default)]
+    pub(crate) preferences: BTreeMap<String, Value>,
+    /// Default search filters
+    #[serde(default)]
+    pub(crate) profile: CandidateProfile,
+    /// Recently viewed listings, oldest first, for recommendations
+    #[serde(default)]
+    pub(crate) viewed: Vec<ViewedJob>,
+    /// Listings being written with `draft_job`, not yet published
+    #[serde(default)]
+    pub(crate) drafts: Vec<JobDraft>,
+    /// Listings applied to, in the order they were first tracked
+    #[serde(default)]
+    pub(crate) applications: Vec<Application>,
+    pub(crate) updated_at: u64,
+}
+
+impl UserState {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.bookmarks.is_empty()
+            && self.saved_searches.is_empty()
+            && self.followed_employers.is_empty()
+            && self.preferences.is_empty()
+            && self.viewed.is_empty()
+            && self.profile.is_empty()
+            && self.drafts.is_empty()
+            && self.applications.is_empty()
+    }
+
+    /// Remember a view of `job_id`, moving it to the end if seen before
+    pub(crate) fn record_view(&mut self, view: ViewedJob) {
+        self.viewed.retain(|v| v.job_id != view.job_id);
+        self.viewed.push(view);
+        if self.viewed.len() > MAX_VIEWED_JOBS {
+            self.viewed.drain(..self.viewed.len() - MAX_VIEWED_JOBS);
+        }
+    }
+}
+
+/// How often changes recorded without writing (listing views) are written
+pub(crate) const STATE_FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// State for every owner (see `auth::state_owner`), written back to `path`
+/// after each change, or within `STATE_FLUSH_INTERVAL` for changes made with
+/// `record`. With no path the state lives only in memory.
+#[derive(Debug)]
+pub(crate) struct StateStore {
+    path: Option<PathBuf>,
+    owners: RwLock<HashMap<String, UserState>>,
+    /// Changes not written yet
+    dirty: AtomicBool,
+    /// Set when an unparseable state file couldn't be moved aside; writing
+    /// would replace it, and everyone's state with it
+    read_only: bool,
+}
+
+impl StateStore {
+    /// Load existing state from `path`; a missing file starts empty, an
+    /// unreadable one is logged and ignored so the server still starts. A
+    /// file that doesn't parse is renamed to `<path>.corrupt-<unix time>`
+    /// before the store starts empty, so the first write can't destroy it;
+    /// if it can't be moved, nothing is written until it is fixed.
+    pub(crate) fn load(path: Option<PathBuf>) -> Self {
+        let mut read_only = false;
+        let owners = path
+            .as_ref()
+            .and_then(|path| match std::fs::read_to_string(path) {
+                Ok(contents) => match serde_json::from_str(&contents) {
+                    Ok(owners) => Some(owners),
+                    Err(e) => {
+                        let mut aside = path.clone().into_os_string();
+                        aside.push(format!(".corrupt-{}", Timestamp::now().as_secs()));
+                        match std::fs::rename(path, &aside) {
+                            Ok(()) => tracing::error!(path = %path.display(), moved_to = ?aside, error = %e, "state_file_invalid"),
+                            Err(rename_error) => {
+                                tracing::error!(path = %path.display(), error = %e, rename_error = %rename_error, "state_file_invalid_not_persisting");
+                                read_only = true;
+                            }
+                        }
+                        None
+                    }
+                },
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                Err(e) => {
+                    tracing::error!(path = %path.display(), error = %e, "state_file_unreadable");
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        Self {
+            path,
+            owners: RwLock::new(owners),
+            dirty: AtomicBool::new(false),
+            read_only,
+        }
+    }
+
+    pub(crate) async fn get(&self, owner: &str) -> UserState {
+        self.owners.read().await.get(owner).cloned().unwrap_or_default()
+    }
+
+    /// Apply `change` to the owner's state and persist it; returns what
+    /// `change` returned.
+    pub(crate) async fn update<T>(&self, owner: &str, change: impl FnOnce(&mut UserState) -> T) -> T {
+        let mut owners = self.owners.write().await;
+        let result = Self::apply(&mut owners, owner, change);
+        self.persist(&owners).await;
+        result
+    }
+
+    /// Apply `change` like `update`, leaving the write to the next flush;
+    /// for frequent changes that can be lost in a crash, like listing views
+    pub(crate) async fn record(&self, owner: &str, change: impl FnOnce(&mut UserState)) {
+        let mut owners = self.owners.write().await;
+        Self::apply(&mut owners, owner, change);
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    fn apply<T>(owners: &mut HashMap<String, UserState>, owner: &str, change: impl FnOnce(&mut UserState) -> T) -> T {
+        let state = owners.entry(owner.to_string()).or_default();
+        let result = change(state);
+        state.updated_at = Timestamp::now().as_secs();
+        if state.is_empty() {
+            owners.remove(owner);
+        }
+        result
+    }
+
+    /// Forget the state of sessions (owners without a bearer token) unchanged
+    /// for `ttl`: their session ids can't come back once expired. Returns
+    /// how many were removed; they are written with the next flush.
+    pub(crate) async fn expire_sessions(&self, ttl: Duration) -> usize {
+        let cutoff = Timestamp::now().as_secs().saturating_sub(ttl.as_secs());
+        let mut owners = self.owners.write().await;
+        let before = owners.len();
+        owners.retain(|owner, state| !owner.starts_with("session:") || state.updated_at > cutoff);
+        let expired = before - owners.len();
+        if expired > 0 {
+            self.dirty.store(true, Ordering::Relaxed);
+        }
+        expired
+    }
+
+    /// Every `STATE_FLUSH_INTERVAL`, forget sessions idle for `session_ttl`
+    /// and write what changed
+    pub(crate) async fn flush_loop(&self, session_ttl: Duration) {
+        loop {
+            tokio::time::sleep(STATE_FLUSH_INTERVAL).await;
+            self.expire_sessions(session_ttl).await;
+            self.flush_changes().await;
+        }
+    }
+
+    /// Write changes made with `record` or `expire_sessions`, if any
+    pub(crate) async fn flush_changes(&self) {
+        if self.dirty.load(Ordering::Relaxed) {
+            self.flush().await;
+        }
+    }
+
+    /// Write the current state to disk (also called on shutdown)
+    pub(crate) async fn flush(&self) {
+        let owners = self.owners.read().await;
+        self.persist(&owners).await;
+    }
+
+    /// Write to a temporary file and rename, so a crash never leaves a torn file
+    async fn persist(&self, owners: &HashMap<String, UserState>) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if self.read_only {
+            tracing::warn!(path = %path.display(), "state_not_persisted: the state file is invalid");
+            return;
+        }
+        let result = async {
+            let json = serde_json::to_vec_pretty(owners).map_err(std::io::Error::other)?;
+            let tmp = path.with_extension("json.tmp");
+            tokio::fs::write(&tmp, json).await?;
+            tokio::fs::rename(&tmp, path).await
+        }
+        .await;
+
+        match result {
+            Ok(()) => self.dirty.store(false, Ordering::Relaxed),
+            Err(e) => tracing::error!(path = %path.display(), error = %e, "state_write_failed"),
+        }
+    }
+}
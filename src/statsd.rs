@@ -0,0 +1,183 @@
+// src/statsd.rs
+// Push-based metrics: what changed in the counters and latency histograms
+// since the last push, plus cache gauges, sent to a StatsD (or DogStatsD)
+// agent over UDP every STATSD_INTERVAL_SECS
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use tokio::net::UdpSocket;
+
+use crate::config::ServerConfig;
+use crate::jobs::NostrJobsClient;
+use crate::metrics::{CacheUsage, LatencyHistogram, PerformanceMetrics};
+
+/// Keeps each datagram within a typical Ethernet MTU
+const MAX_PACKET_BYTES: usize = 1432;
+/// How often a disabled emitter checks whether a reload set `STATSD_ADDR`
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The counters and histograms as last pushed
+#[derive(Default)]
+struct Snapshot {
+    counters: HashMap<String, usize>,
+    timings: HashMap<String, LatencyHistogram>,
+}
+
+impl Snapshot {
+    fn of(metrics: &PerformanceMetrics) -> Self {
+        let mut counters: HashMap<String, usize> = [
+            ("requests", metrics.total_requests),
+            ("cache.hits", metrics.cache_hits),
+            ("cache.misses", metrics.cache_misses),
+            ("relay.fetches", metrics.relay_fetches),
+            ("relay.failed_fetches", metrics.failed_fetches),
+            ("prefetches", metrics.prefetches),
+            ("queue.queued_fetches", metrics.queued_fetches),
+            ("shed_requests", metrics.shed_requests),
+            ("publish.retries", metrics.publish_retries),
+            ("publish.skipped", metrics.skipped_publishes),
+            ("cache.purged_old", metrics.purged_old_events),
+            ("cache.purged_expired", metrics.purged_expired_events),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+        let mut timings: HashMap<String, LatencyHistogram> = [
+            ("cache.latency", &metrics.cache_latency),
+            ("relay.latency", &metrics.fetch_latency),
+            ("queue.wait", &metrics.queue_wait),
+        ]
+        .into_iter()
+        .map(|(name, histogram)| (name.to_string(), histogram.clone()))
+        .collect();
+
+        for (tool, m) in &metrics.tools {
+            let tool = metric_segment(tool);
+            counters.insert(format!("tool.{}.calls", tool), m.calls);
+            counters.insert(format!("tool.{}.failures", tool), m.failures);
+            timings.insert(format!("tool.{}.latency", tool), m.latency.clone());
+        }
+        for (url, m) in &metrics.relays {
+            let relay = metric_segment(url.split_once("://").map_or(url.as_str(), |(_, host)| host));
+            counters.insert(format!("relays.{}.fetches", relay), m.fetches);
+            counters.insert(format!("relays.{}.failures", relay), m.failures);
+            timings.insert(format!("relays.{}.latency", relay), m.latency.clone());
+        }
+        Self { counters, timings }
+    }
+}
+
+/// A tool or relay name usable inside a dotted metric name
+fn metric_segment(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
+
+/// Push metrics while `statsd_addr` is set, picking up reloaded settings
+/// before every push
+pub(crate) async fn emit_loop(jobs: NostrJobsClient) {
+    let mut socket: Option<UdpSocket> = None;
+    let mut last = Snapshot::default();
+    loop {
+        let config = jobs.config();
+        let Some(addr) = config.statsd_addr.clone() else {
+            socket = None;
+            tokio::time::sleep(IDLE_CHECK_INTERVAL).await;
+            continue;
+        };
+        tokio::time::sleep(config.statsd_interval).await;
+
+        let current = Snapshot::of(&*jobs.metrics().read().await);
+        let cache = jobs.cache_usage().await;
+        let lines = lines(&config, &last, &current, &cache, jobs.is_healthy().await);
+        match push(&mut socket, &addr, &lines).await {
+            Ok(()) => last = current,
+            Err(e) => {
+                // Rebind next time; the changes are sent with the next push
+                socket = None;
+                tracing::warn!(addr = %addr, error = %e, "statsd_push_failed");
+            }
+        }
+    }
+}
+
+/// StatsD lines for the changes from `last` to `current`: counter deltas,
+/// gauges, and each new latency observation as a timing at its bucket's
+/// bound, sent once per bucket with a sample rate standing for the count
+fn lines(config: &ServerConfig, last: &Snapshot, current: &Snapshot, cache: &CacheUsage, healthy: bool) -> Vec<String> {
+    let prefix = match &config.tenant {
+        Some(tenant) => format!("{}.{}", config.statsd_prefix, metric_segment(tenant)),
+        None => config.statsd_prefix.clone(),
+    };
+    let tags = if config.statsd_tags.is_empty() {
+        String::new()
+    } else {
+        format!("|#{}", config.statsd_tags.join(","))
+    };
+    let mut lines = Vec::new();
+
+    let mut counters: Vec<_> = current.counters.iter().collect();
+    counters.sort();
+    for (name, value) in counters {
+        let before = last.counters.get(name).copied().unwrap_or(0);
+        // Lower than last time: the metrics were reset or rotated
+        let delta = if *value >= before { value - before } else { *value };
+        if delta > 0 {
+            lines.push(format!("{}.{}:{}|c{}", prefix, name, delta, tags));
+        }
+    }
+
+    for (name, value) in [
+        ("cache.entries", cache.entries),
+        ("cache.events", cache.events),
+        ("cache.bytes", cache.bytes),
+        ("relays.healthy", healthy as usize),
+    ] {
+        lines.push(format!("{}.{}:{}|g{}", prefix, name, value, tags));
+    }
+
+    let empty = LatencyHistogram::default();
+    let mut timings: Vec<_> = current.timings.iter().collect();
+    timings.sort_by(|a, b| a.0.cmp(b.0));
+    for (name, histogram) in timings {
+        for (ms, count) in histogram.new_since(last.timings.get(name).unwrap_or(&empty)) {
+            let rate = if count == 1 { String::new() } else { format!("|@{}", 1.0 / count as f64) };
+            lines.push(format!("{}.{}:{}|ms{}{}", prefix, name, ms, rate, tags));
+        }
+    }
+    lines
+}
+
+/// Send `lines` to `addr` in as few datagrams as fit the MTU
+async fn push(socket: &mut Option<UdpSocket>, addr: &str, lines: &[String]) -> std::io::Result<()> {
+    let target = tokio::net::lookup_host(addr)
+        .await?
+        .next()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no address for STATSD_ADDR"))?;
+    let socket = match socket {
+        Some(socket) if socket.local_addr()?.is_ipv4() == target.is_ipv4() => socket,
+        _ => {
+            let local: SocketAddr = if target.is_ipv4() { ([0, 0, 0, 0], 0).into() } else { ([0u16; 8], 0).into() };
+            socket.insert(UdpSocket::bind(local).await?)
+        }
+    };
+
+    let mut packet = String::new();
+    for line in lines {
+        if !packet.is_empty() && packet.len() + 1 + line.len() > MAX_PACKET_BYTES {
+            socket.send_to(packet.as_bytes(), target).await?;
+            packet.clear();
+        }
+        if !packet.is_empty() {
+            packet.push('\n');
+        }
+        packet.push_str(line);
+    }
+    if !packet.is_empty() {
+        socket.send_to(packet.as_bytes(), target).await?;
+    }
+    Ok(())
+}
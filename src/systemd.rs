@@ -0,0 +1,130 @@
+// src/systemd.rs
+// systemd integration: sd_notify readiness/watchdog messages and socket activation.
+// Everything is a no-op when not started by systemd (or not on Unix).
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::jobs::TaskReport;
+
+/// How often the HTTP accept loop beats its `Heartbeat`
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// First file descriptor passed by socket activation (`SD_LISTEN_FDS_START`)
+#[cfg(unix)]
+const LISTEN_FDS_START: i32 = 3;
+
+/// Send `state` (e.g. `READY=1`, `WATCHDOG=1`, `STOPPING=1`, `STATUS=...`)
+/// to the socket in `NOTIFY_SOCKET`. Returns whether a message was sent.
+pub fn notify(state: &str) -> bool {
+    let Some(path) = std::env::var_os("NOTIFY_SOCKET") else {
+        return false;
+    };
+    match send(&path, state) {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!(error = %e, state, "sd_notify_failed");
+            false
+        }
+    }
+}
+
+#[cfg(unix)]
+fn send(path: &std::ffi::OsStr, state: &str) -> std::io::Result<()> {
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::net::UnixDatagram;
+
+    let socket = UnixDatagram::unbound()?;
+    match path.as_bytes().strip_prefix(b"@") {
+        // Abstract socket namespace
+        #[cfg(target_os = "linux")]
+        Some(name) => {
+            use std::os::linux::net::SocketAddrExt;
+            let address = std::os::unix::net::SocketAddr::from_abstract_name(name)?;
+            socket.send_to_addr(state.as_bytes(), &address)?;
+        }
+        _ => {
+            socket.send_to(state.as_bytes(), path)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn send(_path: &std::ffi::OsStr, _state: &str) -> std::io::Result<()> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "sd_notify needs Unix sockets"))
+}
+
+/// How often to send `WATCHDOG=1`: half of `WATCHDOG_USEC`, when the
+/// watchdog is enabled for this process
+pub fn watchdog_interval() -> Option<Duration> {
+    // WATCHDOG_PID is optional; when set it must be us
+    if pid_matches("WATCHDOG_PID") == Some(false) {
+        return None;
+    }
+    let usec: u64 = std::env::var("WATCHDOG_USEC").ok()?.parse().ok()?;
+    (usec > 0).then(|| Duration::from_micros(usec / 2))
+}
+
+/// When the HTTP accept loop last came round, so the watchdog can tell a
+/// stuck listener from a quiet one
+#[derive(Clone, Debug, Default)]
+pub struct Heartbeat(Arc<Mutex<Option<Instant>>>);
+
+impl Heartbeat {
+    pub fn beat(&self) {
+        *self.0.lock().expect("heartbeat lock poisoned") = Some(Instant::now());
+    }
+
+    /// How long ago it last beat; `None` before the first beat
+    pub fn since(&self) -> Option<Duration> {
+        self.0.lock().expect("heartbeat lock poisoned").map(|at| at.elapsed())
+    }
+}
+
+/// Whether the process is alive enough for `WATCHDOG=1`: the accept loop
+/// beat within `within` (plus a beat's slack) and no supervised loop is
+/// stopped. Otherwise why not, and systemd restarts the service once the
+/// watchdog runs out.
+pub fn liveness(heartbeat: &Heartbeat, within: Duration, tasks: &[TaskReport]) -> Result<(), String> {
+    match heartbeat.since() {
+        None => return Err("the HTTP listener hasn't started".to_string()),
+        Some(ago) if ago > within + HEARTBEAT_INTERVAL => {
+            return Err(format!("the HTTP listener last came round {}s ago", ago.as_secs()));
+        }
+        Some(_) => {}
+    }
+    let stopped: Vec<&str> = tasks.iter().filter(|t| t.supervised && !t.running).map(|t| t.name).collect();
+    match stopped.is_empty() {
+        true => Ok(()),
+        false => Err(format!("background tasks stopped: {}", stopped.join(", "))),
+    }
+}
+
+/// Listening sockets passed by systemd socket activation (`LISTEN_FDS`),
+/// in the order of the socket unit's `ListenStream=` lines
+#[cfg(unix)]
+pub fn listen_fds() -> Vec<std::net::TcpListener> {
+    use std::os::fd::FromRawFd;
+
+    if pid_matches("LISTEN_PID") != Some(true) {
+        return Vec::new();
+    }
+    let count: i32 = std::env::var("LISTEN_FDS").ok().and_then(|n| n.parse().ok()).unwrap_or(0);
+    (LISTEN_FDS_START..LISTEN_FDS_START + count)
+        // SAFETY: systemd hands these descriptors to this process (LISTEN_PID)
+        // and nothing else in it owns them
+        .map(|fd| unsafe { std::net::TcpListener::from_raw_fd(fd) })
+        .collect()
+}
+
+#[cfg(not(unix))]
+pub fn listen_fds() -> Vec<std::net::TcpListener> {
+    Vec::new()
+}
+
+/// Whether the pid in variable `name` is this process; `None` when unset
+fn pid_matches(name: &str) -> Option<bool> {
+    let pid = std::env::var(name).ok()?;
+    Some(pid.trim().parse::<u32>().is_ok_and(|pid| pid == std::process::id()))
+}
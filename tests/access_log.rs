@@ -0,0 +1,41 @@
+// tests/access_log.rs
+// HTTP access log lines and rotation
+
+use std::convert::Infallible;
+use std::time::Duration;
+
+use jobmcp::access_log::{AccessEntry, AccessLog, AccessLogLayer};
+use jobmcp::proxy::PeerAddr;
+use tower::{Layer, ServiceExt};
+
+#[tokio::test]
+async fn requests_are_logged_and_the_file_rotates() {
+    let dir = std::env::temp_dir().join(format!("jobmcp-access-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("access.log");
+
+    let app = tower::service_fn(|_request: http::Request<()>| async {
+        Ok::<_, Infallible>(http::Response::builder().status(202).header("mcp-session-id", "abc").body(()).unwrap())
+    });
+    // Lines run ~115 bytes: two fit, the third rotates the file
+    let app = AccessLogLayer::new(AccessLog::open(path.clone(), 250, 1), true).layer(app);
+
+    for _ in 0..3 {
+        let mut request = http::Request::post("/mcp?token=secret").header("x-forwarded-for", "203.0.113.9").body(()).unwrap();
+        request.extensions_mut().insert(PeerAddr("10.0.0.2:51000".parse().unwrap()));
+        app.clone().oneshot(request).await.unwrap();
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let current = std::fs::read_to_string(&path).unwrap();
+    let rotated = std::fs::read_to_string(dir.join("access.log.1")).unwrap();
+    assert_eq!(current.lines().count() + rotated.lines().count(), 3, "{}\n{}", rotated, current);
+
+    let entry: AccessEntry = serde_json::from_str(current.lines().last().unwrap()).unwrap();
+    assert_eq!((entry.method.as_str(), entry.path.as_str(), entry.status), ("POST", "/mcp", 202));
+    assert_eq!(entry.session_id.as_deref(), Some("abc"), "ids assigned on the response are logged");
+    assert_eq!(entry.client.as_deref(), Some("203.0.113.9"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
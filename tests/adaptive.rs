@@ -0,0 +1,64 @@
+// tests/adaptive.rs
+// Adaptive relay timeouts learned from observed latency
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{MemoryRelay, RELAY_URL, builder, fixtures};
+use jobmcp::JobQuery;
+
+const SILENT_RELAY: &str = "wss://silent.test";
+
+/// Enough answered fetches for every relay to have its own timeout
+async fn warm_up(server: &jobmcp::NostrJobsServer) {
+    for _ in 0..20 {
+        server.jobs().clear_cache().await;
+        server.jobs().search(&JobQuery::default()).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn fast_pool_stops_waiting_on_a_silent_relay_sooner() {
+    let relay = MemoryRelay::new(fixtures());
+    let server = builder(relay.clone())
+        .relays([RELAY_URL, SILENT_RELAY])
+        .relay_timeout(Duration::from_millis(800))
+        .search_timeout(Duration::from_secs(2))
+        .adaptive_timeouts(true)
+        .build()
+        .await
+        .unwrap();
+    let timeouts = |reports: Vec<jobmcp::jobs::RelayReport>| reports.iter().map(|r| r.timeout).collect::<Vec<_>>();
+    assert_eq!(timeouts(server.jobs().relay_reports().await), [Duration::from_millis(800); 2], "nothing learned yet");
+
+    warm_up(&server).await;
+    let learned = timeouts(server.jobs().relay_reports().await);
+    assert!(learned.iter().all(|t| *t < Duration::from_millis(200)), "{:?}", learned);
+
+    relay.delay_relay(SILENT_RELAY, Duration::from_secs(1));
+    server.jobs().clear_cache().await;
+    let started = Instant::now();
+    let results = server.jobs().search(&JobQuery { skill: vec!["rust".into()], ..Default::default() }).await.unwrap();
+    assert_eq!(results.listings.len(), 2);
+    assert!(started.elapsed() < Duration::from_millis(500), "took {:?}", started.elapsed());
+}
+
+#[tokio::test]
+async fn relays_answering_near_the_deadline_get_more_room() {
+    let relay = MemoryRelay::slow(fixtures(), Duration::from_millis(150));
+    let server = builder(relay.clone())
+        .relay_timeout(Duration::from_millis(200))
+        .search_timeout(Duration::from_millis(250))
+        .adaptive_timeouts(true)
+        .build()
+        .await
+        .unwrap();
+    warm_up(&server).await;
+
+    // Slower than the configured 200ms relay and 250ms search timeouts
+    relay.delay_relay(RELAY_URL, Duration::from_millis(260));
+    server.jobs().clear_cache().await;
+    let results = server.jobs().search(&JobQuery { skill: vec!["rust".into()], ..Default::default() }).await.unwrap();
+    assert_eq!(results.listings.len(), 2);
+}
@@ -0,0 +1,87 @@
+// tests/admin.rs
+// The admin HTTP API: token checks, cache clearing, relay changes and metrics
+
+mod common;
+
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode};
+use common::{MemoryRelay, RELAY_URL, builder, fixtures};
+use jobmcp::{JobQuery, admin};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+async fn call(router: &axum::Router, method: &str, path: &str, token: Option<&str>, body: Option<Value>) -> (StatusCode, Value) {
+    let mut request = Request::builder().method(method).uri(path);
+    if let Some(token) = token {
+        request = request.header("authorization", format!("Bearer {}", token));
+    }
+    let request = match body {
+        Some(body) => request.header("content-type", "application/json").body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    };
+    let response = router.clone().oneshot(request.unwrap()).await.unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn admin_api_requires_a_configured_admin_token() {
+    let open = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+    let router = axum::Router::new().nest("/admin", admin::routes(open));
+    let (status, body) = call(&router, "GET", "/admin/metrics", Some("anything"), None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "no ADMIN_TOKENS disables the API");
+    assert_eq!(body["error"], "admin_disabled");
+
+    let server = builder(MemoryRelay::new(fixtures())).admin_tokens(["s3cret"]).build().await.unwrap();
+    let router = axum::Router::new().nest("/admin", admin::routes(server));
+    assert_eq!(call(&router, "GET", "/admin/metrics", None, None).await.0, StatusCode::UNAUTHORIZED);
+    assert_eq!(call(&router, "GET", "/admin/metrics", Some("wrong"), None).await.0, StatusCode::UNAUTHORIZED);
+    assert_eq!(call(&router, "GET", "/admin/metrics", Some("s3cret"), None).await.0, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn admin_api_clears_cache_updates_relays_and_reports_metrics() {
+    let server = builder(MemoryRelay::new(fixtures())).admin_tokens(["s3cret"]).build().await.unwrap();
+    let router = axum::Router::new().nest("/admin", admin::routes(server.clone()));
+    let query = JobQuery { skill: vec!["rust".into()], ..Default::default() };
+    server.jobs().search(&query).await.unwrap();
+    common::settle().await;
+
+    let (status, metrics) = call(&router, "GET", "/admin/metrics", Some("s3cret"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(metrics["requests"]["total"], 1, "{}", metrics);
+
+    let (status, cleared) = call(&router, "POST", "/admin/cache/clear", Some("s3cret"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(cleared["cleared_entries"], 1, "{}", cleared);
+
+    let changes = json!({"add": ["wss://relay.other"], "remove": [RELAY_URL]});
+    let (status, relays) = call(&router, "POST", "/admin/relays", Some("s3cret"), Some(changes)).await;
+    assert_eq!(status, StatusCode::OK, "{}", relays);
+    assert_eq!(relays["relays"], json!(["wss://relay.other"]));
+    assert_eq!(server.config().relays, ["wss://relay.other"]);
+
+    let (status, listed) = call(&router, "GET", "/admin/relays", Some("s3cret"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(listed["relays"][0]["url"], "wss://relay.other");
+
+    let (status, error) = call(&router, "POST", "/admin/relays", Some("s3cret"), Some(json!({"add": ["not a url"]}))).await;
+    assert_eq!((status, &error["error"]), (StatusCode::BAD_REQUEST, &json!("parse_error")));
+    let (status, error) =
+        call(&router, "POST", "/admin/relays", Some("s3cret"), Some(json!({"remove": ["wss://relay.other"]}))).await;
+    assert_eq!((status, &error["error"]), (StatusCode::BAD_REQUEST, &json!("config_error")), "the last relay stays");
+}
+
+#[test]
+fn credentials_are_redacted_from_logged_headers() {
+    let mut headers = http::HeaderMap::new();
+    headers.insert("authorization", "Bearer admin-secret".parse().unwrap());
+    headers.insert("cookie", "session=abc".parse().unwrap());
+    headers.insert("user-agent", "inspector/1.0".parse().unwrap());
+
+    let logged = format!("{:?}", jobmcp::auth::redacted_headers(&headers));
+    assert!(!logged.contains("admin-secret") && !logged.contains("session=abc"), "{}", logged);
+    assert!(logged.contains("inspector/1.0"), "{}", logged);
+    assert_eq!(headers["authorization"], "Bearer admin-secret", "the request itself is untouched");
+}
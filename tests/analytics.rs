@@ -0,0 +1,102 @@
+// tests/analytics.rs
+// employer_analytics and jobs://mine: engagement, applications and expirations of the board's own listings
+
+mod common;
+
+use common::{MemoryRelay, builder, fixtures, listing, search_args, text};
+use jobmcp::mcp_server::{EmployerAnalyticsArgs, GetJobArgs};
+use nostr_sdk::prelude::*;
+use rmcp::handler::server::wrapper::Parameters;
+
+/// A zap receipt for `sats` on `listing`, its amount in the embedded zap request
+fn zap(listing: &Event, sats: u64) -> Event {
+    let zapper = Keys::generate();
+    let request = EventBuilder::new(Kind::ZapRequest, "")
+        .tags([Tag::event(listing.id), Tag::parse(["amount", &(sats * 1000).to_string()]).unwrap()])
+        .sign_with_keys(&zapper)
+        .unwrap();
+    EventBuilder::new(Kind::ZapReceipt, "")
+        .tags([
+            Tag::event(listing.id),
+            Tag::parse(["bolt11", "lnbc10u1pexample"]).unwrap(),
+            Tag::parse(["description", &request.as_json()]).unwrap(),
+        ])
+        .sign_with_keys(&Keys::generate())
+        .unwrap()
+}
+
+fn comment(listing: &Event, content: &str) -> Event {
+    EventBuilder::new(Kind::TextNote, content)
+        .tags([Tag::event(listing.id)])
+        .sign_with_keys(&Keys::generate())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn own_listings_are_ranked_by_engagement() {
+    let keys = Keys::generate();
+    let popular = listing(&keys, "acme-9", "Rust Lead", "Acme", &[&["skill", "Rust"]], 300);
+    let quiet = listing(&keys, "acme-8", "Office Manager", "Acme", &[], 300);
+    let mut events = fixtures();
+    events.extend([popular.clone(), quiet, zap(&popular, 21), comment(&popular, "Is this remote?")]);
+    let server = builder(MemoryRelay::new(events)).signing_key(keys).build().await.unwrap();
+
+    server.search_jobs_for(None, search_args(serde_json::json!({"skill": "rust"}))).await.unwrap();
+    server.get_job_details(Parameters(GetJobArgs { job_id: "acme-9".into() })).await.unwrap();
+    server.get_job_details(Parameters(GetJobArgs { job_id: "acme-1".into() })).await.unwrap();
+
+    let result = server.employer_analytics(Parameters(EmployerAnalyticsArgs::default())).await.unwrap();
+    let report = result.structured_content.clone().unwrap();
+    let listings = report["listings"].as_array().unwrap();
+    assert_eq!(listings.len(), 2, "other posters' listings aren't tracked: {}", report);
+    let first = &listings[0];
+    assert_eq!(first["job_id"], "acme-9");
+    assert_eq!(
+        (first["impressions"].clone(), first["views"].clone(), first["zaps"].clone(), first["comments"].clone()),
+        (1.into(), 1.into(), 1.into(), 1.into())
+    );
+    assert_eq!(first["zapped_sats"], 21, "the zap request's amount wins over the invoice");
+    assert_eq!(first["daily"].as_array().unwrap().len(), 1);
+    assert_eq!(listings[1]["job_id"], "acme-8");
+    assert_eq!(listings[1]["impressions"], 0);
+    assert!(text(&result).contains("1. Rust Lead (🆔 acme-9)\n   🔎 1 impressions · 👀 1 views · ⚡ 1 zaps (21 sats) · 💬 1 comments"));
+}
+
+#[tokio::test]
+async fn analytics_need_a_posting_key() {
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+    let err = server.employer_analytics(Parameters(EmployerAnalyticsArgs::default())).await.unwrap_err();
+    assert!(err.message.contains("POSTING_SECRET_KEY"), "{}", err.message);
+}
+
+#[tokio::test]
+async fn dashboard_gathers_listings_applications_and_expirations() {
+    let keys = Keys::generate();
+    let candidate = Keys::generate();
+    let soon = (Timestamp::now().as_secs() + 86_400).to_string();
+    let past = (Timestamp::now().as_secs() - 86_400).to_string();
+    let application = EventBuilder::private_msg(&candidate, keys.public_key(), "Hi! Applying for ACME-9, CV attached.", [])
+        .await
+        .unwrap();
+    let events = vec![
+        listing(&keys, "acme-9", "Rust Lead", "Acme", &[&["expiration", &soon]], 300),
+        listing(&keys, "acme-7", "Intern", "Acme", &[&["expiration", &past]], 900),
+        application,
+        // Not for us
+        EventBuilder::private_msg(&candidate, Keys::generate().public_key(), "acme-9?", []).await.unwrap(),
+    ];
+    let server = builder(MemoryRelay::new(events)).signing_key(keys.clone()).build().await.unwrap();
+
+    let dashboard = server.operator_dashboard_json().await.unwrap();
+    assert_eq!(dashboard["operator"], keys.public_key().to_bech32().unwrap());
+    assert_eq!(dashboard["summary"]["active_listings"], 1, "{}", dashboard);
+    assert_eq!(dashboard["active_listings"][0]["job_id"], "acme-9");
+    assert_eq!(dashboard["active_listings"][0]["applications_7d"], 1);
+    assert_eq!(dashboard["applications"].as_array().unwrap().len(), 1);
+    assert_eq!(dashboard["applications"][0]["from"], candidate.public_key().to_bech32().unwrap());
+    assert_eq!(dashboard["applications"][0]["job_id"], "acme-9");
+    assert_eq!(dashboard["expiring"][0]["job_id"], "acme-9");
+
+    let server = builder(MemoryRelay::new(Vec::new())).build().await.unwrap();
+    assert!(server.operator_dashboard_json().await.unwrap_err().message.contains("POSTING_SECRET_KEY"));
+}
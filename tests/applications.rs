@@ -0,0 +1,102 @@
+// tests/applications.rs
+// set_application_status / list_applications: applications tracked through their statuses, stale ones flagged
+
+mod common;
+
+use common::{MemoryRelay, builder, fixtures, text};
+use jobmcp::ServerConfig;
+use jobmcp::mcp_server::{ApplicationStatusArgs, ListApplicationsArgs};
+use nostr_sdk::prelude::*;
+use rmcp::handler::server::wrapper::Parameters;
+
+const OWNER: &str = "session:candidate";
+const DAY: u64 = 86_400;
+
+fn status(job_id: &str, status: Option<&str>, note: Option<&str>) -> Parameters<ApplicationStatusArgs> {
+    Parameters(ApplicationStatusArgs {
+        job_id: job_id.to_string(),
+        status: status.map(str::to_string),
+        note: note.map(str::to_string),
+    })
+}
+
+#[tokio::test]
+async fn applications_move_through_statuses() {
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+
+    let created = server.set_application_status_for(OWNER, status("acme-1", None, Some("Referred by Dana"))).await.unwrap();
+    assert!(text(&created).starts_with("📨 Tracking new application"));
+    assert!(text(&created).contains("Rust Engineer at Acme (🆔 acme-1) - applied since"), "{}", text(&created));
+    let application = created.structured_content.unwrap();
+    assert_eq!(application["status"], "applied");
+    assert_eq!(application["note"], "Referred by Dana");
+
+    let moved = server.set_application_status_for(OWNER, status("acme-1", Some("Interviewing"), None)).await.unwrap();
+    assert!(text(&moved).starts_with("📨 Application moved from applied to interviewing"));
+    let application = moved.structured_content.unwrap();
+    assert_eq!(application["note"], "Referred by Dana", "the note is kept when none is given");
+    let history: Vec<&str> = application["history"].as_array().unwrap().iter().map(|h| h["status"].as_str().unwrap()).collect();
+    assert_eq!(history, ["applied", "interviewing"]);
+
+    // Listings that can't be found are tracked by their id
+    server.set_application_status_for(OWNER, status("gone-1", Some("rejected"), None)).await.unwrap();
+    let err = server.set_application_status_for(OWNER, status("acme-1", Some("ghosted"), None)).await.unwrap_err();
+    assert!(err.message.contains("unknown status 'ghosted'"), "{}", err.message);
+
+    let listed = server.list_applications_for(OWNER, Parameters(ListApplicationsArgs::default())).await.unwrap();
+    assert!(text(&listed).starts_with("📨 2 application(s): 1 interviewing · 1 rejected"), "{}", text(&listed));
+    let report = listed.structured_content.unwrap();
+    assert_eq!(report["counts"]["interviewing"], 1);
+    assert_eq!(report["stale"], 0);
+
+    let rejected = ListApplicationsArgs { status: Some("rejected".into()), stale_only: None };
+    let listed = server.list_applications_for(OWNER, Parameters(rejected)).await.unwrap();
+    let applications = listed.structured_content.unwrap()["applications"].as_array().unwrap().clone();
+    assert_eq!(applications.len(), 1);
+    assert_eq!(applications[0]["job_id"], "gone-1");
+    assert!(applications[0].get("title").is_none());
+
+    let other = server.list_applications_for("session:other", Parameters(ListApplicationsArgs::default())).await.unwrap();
+    assert!(text(&other).starts_with("No applications tracked yet"));
+}
+
+#[tokio::test]
+async fn quiet_open_applications_are_flagged_for_a_follow_up() {
+    let path = std::env::temp_dir().join(format!("jobmcp-applications-{}.json", std::process::id()));
+    let old = Timestamp::now().as_secs() - 20 * DAY;
+    let stored = serde_json::json!({
+        OWNER: {
+            "applications": [
+                {"job_id": "acme-1", "status": "applied", "applied_at": old, "updated_at": old},
+                {"job_id": "globex-1", "status": "rejected", "applied_at": old, "updated_at": old},
+            ],
+            "updated_at": old,
+        }
+    });
+    std::fs::write(&path, stored.to_string()).unwrap();
+    let config = ServerConfig {
+        audit_log_path: None,
+        state_path: Some(path.clone()),
+        digest_period: None,
+        prefetch_top: 0,
+        ..Default::default()
+    };
+    let server = builder(MemoryRelay::new(fixtures())).config(config).build().await.unwrap();
+
+    let stale = ListApplicationsArgs { status: None, stale_only: Some(true) };
+    let listed = server.list_applications_for(OWNER, Parameters(stale)).await.unwrap();
+    assert!(text(&listed).contains("⏰ 1 due a follow-up"), "{}", text(&listed));
+    assert!(text(&listed).contains("⏰ No update in 20 days - follow up?"));
+    let report = listed.structured_content.unwrap();
+    assert_eq!(report["stale_after_days"], 14);
+    let applications = report["applications"].as_array().unwrap();
+    assert_eq!(applications.len(), 1, "closed applications never go stale: {}", report);
+    assert_eq!(applications[0]["days_since_update"], 20);
+
+    // Any update resets the clock
+    server.set_application_status_for(OWNER, status("acme-1", Some("replied"), None)).await.unwrap();
+    let listed = server.list_applications_for(OWNER, Parameters(ListApplicationsArgs::default())).await.unwrap();
+    assert_eq!(listed.structured_content.unwrap()["stale"], 0);
+
+    let _ = std::fs::remove_file(path);
+}
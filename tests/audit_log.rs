@@ -0,0 +1,61 @@
+// tests/audit_log.rs
+// get_audit_log over a long-lived log: read from the end, within a bound
+
+mod common;
+
+use std::io::Write;
+
+use common::{MemoryRelay, builder, fixtures, text};
+use jobmcp::ServerConfig;
+use jobmcp::mcp_server::AuditLogArgs;
+use rmcp::handler::server::wrapper::Parameters;
+
+fn entry(tool: &str, session: &str, latency_ms: u64) -> String {
+    serde_json::json!({
+        "at": 1_700_000_000u64,
+        "tool": tool,
+        "args_hash": "ab".repeat(32),
+        "session_id": session,
+        "outcome": "ok",
+        "latency_ms": latency_ms,
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn recent_entries_come_from_the_end_of_the_log() {
+    let path = std::env::temp_dir().join(format!("jobmcp-audit-{}.log", std::process::id()));
+    let mut file = std::io::BufWriter::new(std::fs::File::create(&path).unwrap());
+    writeln!(file, "{}", entry("ancient_tool", "s0", 1)).unwrap();
+    // ~10 MiB of filler pushes the first entries past the scanned tail
+    for i in 0..60_000 {
+        writeln!(file, "{}", entry("search_jobs", "s1", i)).unwrap();
+        if i % 20_000 == 0 {
+            writeln!(file, "{}", entry("get_job_details", "s2", i)).unwrap();
+        }
+    }
+    writeln!(file, "not json").unwrap();
+    writeln!(file, "{}", entry("get_job_details", "s2", 99_999)).unwrap();
+    drop(file);
+
+    let config = ServerConfig { audit_log_path: Some(path.clone()), state_path: None, digest_period: None, prefetch_top: 0, ..Default::default() };
+    let server = builder(MemoryRelay::new(fixtures())).config(config).build().await.unwrap();
+    let read = |tool: Option<&str>, limit: usize| {
+        let args = AuditLogArgs { limit: Some(limit), tool: tool.map(str::to_string), session_id: None };
+        server.get_audit_log(Parameters(args))
+    };
+
+    let latest = text(&read(None, 2).await.unwrap());
+    assert!(latest.starts_with("📜 Audit Log (2 most recent entries)"), "{}", latest);
+    let lines: Vec<&str> = latest.lines().skip(2).collect();
+    assert!(lines[0].contains("get_job_details | ok | 99999ms"), "{}", latest);
+    assert!(lines[1].contains("search_jobs | ok | 59999ms"), "the malformed line is skipped: {}", latest);
+
+    let details = text(&read(Some("get_job_details"), 10).await.unwrap());
+    let latencies: Vec<&str> = details.lines().skip(2).map(|l| l.split(" | ").nth(3).unwrap()).collect();
+    assert_eq!(latencies, ["99999ms", "40000ms", "20000ms"], "found across chunks, newest first; the first is past the tail");
+
+    let ancient = text(&read(Some("ancient_tool"), 10).await.unwrap());
+    assert_eq!(ancient, "No matching audit log entries.", "beyond the scanned tail");
+    let _ = std::fs::remove_file(&path);
+}
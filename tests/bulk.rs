@@ -0,0 +1,114 @@
+// tests/bulk.rs
+// Bulk posting: CSV and NDJSON rows validated and published with a per-row report
+
+mod common;
+
+use std::time::Duration;
+
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode};
+use common::{MemoryRelay, RELAY_URL, builder};
+use jobmcp::admin;
+use jobmcp::jobs::bulk::{self, BulkFormat, RowOutcome};
+use jobmcp::mcp_server::BulkPublishArgs;
+use nostr_sdk::prelude::*;
+use rmcp::handler::server::wrapper::Parameters;
+use serde_json::Value;
+use tower::ServiceExt;
+
+const CSV: &str = "job_id,title,company,skills,salary_min,salary_max,description\n\
+acme-1,Rust Engineer,Acme,Rust;Tokio,120000,150000,\"Build things, fast.\nRemote friendly.\"\n\
+acme-2,,Acme,,,,No title here\n\
+acme-3,Designer,Acme,,abc,,Design things\n\
+acme-1,Rust Engineer again,Acme,,,,Same id\n";
+
+#[tokio::test]
+async fn csv_rows_are_validated_and_published_with_a_report() {
+    let relay = MemoryRelay::new(Vec::new());
+    let server = builder(relay.clone())
+        .signing_key(Keys::generate())
+        .publish_rate(Duration::ZERO, 0)
+        .build()
+        .await
+        .unwrap();
+
+    let report = server.jobs().publish_bulk(CSV, BulkFormat::Csv, false).await.unwrap();
+    let outcomes: Vec<(usize, Option<&str>, &RowOutcome)> =
+        report.rows.iter().map(|r| (r.row, r.job_id.as_deref(), &r.outcome)).collect();
+    assert_eq!(outcomes.len(), 4);
+    assert!(matches!(outcomes[0], (1, Some("acme-1"), RowOutcome::Published(_))));
+    assert!(matches!(outcomes[1], (2, Some("acme-2"), RowOutcome::Invalid(e)) if e == "title is empty"));
+    assert!(matches!(outcomes[2], (3, None, RowOutcome::Invalid(e)) if e.contains("salary_min 'abc' is not a number")));
+    assert!(matches!(outcomes[3], (4, Some("acme-1"), RowOutcome::Invalid(e)) if e.contains("appears earlier")));
+
+    assert_eq!(relay.accepted(RELAY_URL).len(), 1);
+    let (listing, _) = server.jobs().get("acme-1").await.unwrap();
+    assert_eq!(listing.description, "Build things, fast.\nRemote friendly.");
+    assert_eq!(listing.skills, ["Rust", "Tokio"]);
+    assert_eq!(listing.salary.unwrap().max, 150000.0);
+}
+
+#[tokio::test]
+async fn ndjson_dry_runs_publish_nothing_and_unknown_fields_are_rejected() {
+    let relay = MemoryRelay::new(Vec::new());
+    let server = builder(relay.clone()).build().await.unwrap();
+    let payload = "{\"title\": \"Data Engineer\", \"company\": \"Globex\", \"description\": \"Pipelines\", \"skills\": [\"SQL\"]}\n\n\
+                   {\"title\": \"Analyst\", \"description\": \"Reports\", \"remote\": true}\n";
+
+    let result = server
+        .bulk_publish_jobs(Parameters(BulkPublishArgs { payload: payload.to_string(), format: None, dry_run: Some(true) }))
+        .await
+        .unwrap();
+    let report = result.structured_content.unwrap();
+    assert_eq!((report["valid"].clone(), report["invalid"].clone()), (1.into(), 1.into()), "{}", report);
+    assert_eq!(report["rows"][0]["job_id"], "globex-data-engineer", "ids default to company and title");
+    assert_eq!(report["rows"][1]["row"], 3, "rows are numbered by line");
+    assert!(report["rows"][1]["error"].as_str().unwrap().contains("unknown field `remote`"));
+    assert!(relay.accepted(RELAY_URL).is_empty());
+
+    // Publishing for real needs a posting key
+    let err = server.jobs().publish_bulk(payload, BulkFormat::Ndjson, false).await.unwrap_err();
+    assert!(err.to_string().contains("POSTING_SECRET_KEY"), "{}", err);
+}
+
+#[tokio::test]
+async fn admin_endpoint_publishes_bulk_payloads() {
+    let server = builder(MemoryRelay::new(Vec::new()))
+        .admin_tokens(["s3cret"])
+        .signing_key(Keys::generate())
+        .publish_rate(Duration::ZERO, 0)
+        .build()
+        .await
+        .unwrap();
+    let router = axum::Router::new().nest("/admin", admin::routes(server));
+    let post = |uri: &str, body: &str| {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("authorization", "Bearer s3cret")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    };
+
+    let response = router.clone().oneshot(post("/admin/jobs/bulk?format=csv", CSV)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let report: Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!((report["published"].clone(), report["invalid"].clone()), (1.into(), 3.into()), "{}", report);
+    assert_eq!(report["rows"][0]["status"], "published");
+
+    let response = router.oneshot(post("/admin/jobs/bulk", "title,salary\nX,1\n")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST, "unknown columns reject the whole payload");
+}
+
+#[test]
+fn expirations_too_far_ahead_are_row_errors() {
+    let payload = "{\"title\": \"Analyst\", \"description\": \"Reports\", \"expires_in_days\": 18446744073709551615}\n\
+                   {\"title\": \"Designer\", \"description\": \"Designs\", \"expires_in_days\": 30}\n\
+                   {\"title\": \"Tester\", \"description\": \"Tests\", \"expires_in_days\": 3651}\n\
+                   {\"title\": \"Writer\", \"description\": \"Writes\", \"expires_in_days\": 3650}\n";
+    let (drafts, invalid) = bulk::parse_rows(payload, BulkFormat::Ndjson).unwrap();
+    assert_eq!(drafts.iter().map(|(row, _)| *row).collect::<Vec<_>>(), [2, 4], "the same limit as draft_job");
+    assert!(drafts.iter().all(|(_, draft)| draft.expires_at.is_some()));
+    assert_eq!(invalid.iter().map(|r| r.row).collect::<Vec<_>>(), [1, 3]);
+    assert!(matches!(&invalid[0].outcome, RowOutcome::Invalid(e) if e.contains("expires_in_days must be between 0 and 3650, got 18446744073709551615")), "{:?}", invalid);
+}
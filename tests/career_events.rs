@@ -0,0 +1,67 @@
+// tests/career_events.rs
+// search_career_events: public NIP-52 calendar events with career topics, soonest first
+
+mod common;
+
+use common::{MemoryRelay, builder, text};
+use jobmcp::mcp_server::SearchCareerEventsArgs;
+use nostr_sdk::prelude::*;
+use rmcp::handler::server::wrapper::Parameters;
+
+const DAY: u64 = 86_400;
+
+fn calendar_event(kind: u16, id: &str, title: &str, start: String, tags: &[&[&str]]) -> Event {
+    let mut all = vec![
+        Tag::parse(["d", id]).unwrap(),
+        Tag::parse(["title", title]).unwrap(),
+        Tag::parse(["start", &start]).unwrap(),
+    ];
+    all.extend(tags.iter().map(|t| Tag::parse(t.iter().copied()).unwrap()));
+    EventBuilder::new(Kind::from(kind), format!("About {}", title)).tags(all).sign_with_keys(&Keys::generate()).unwrap()
+}
+
+fn in_days(days: u64) -> String {
+    (Timestamp::now().as_secs() + days * DAY).to_string()
+}
+
+#[tokio::test]
+async fn career_events_are_found_by_topic_and_filtered() {
+    let events = vec![
+        calendar_event(31923, "meetup", "Rust Hiring Meetup", in_days(10), &[&["t", "hiring"], &["location", "Berlin"]]),
+        calendar_event(31922, "fair", "Nostr Career Fair", "2099-05-01".into(), &[&["t", "careerfair"], &["end", "2099-05-03"], &["location", "Online"]]),
+        calendar_event(31923, "soon", "Recruiter Office Hours", in_days(1), &[&["t", "recruiting"]]),
+        // Over, off topic, or private
+        calendar_event(31923, "past", "Old Job Fair", (Timestamp::now().as_secs() - DAY).to_string(), &[&["t", "jobfair"]]),
+        calendar_event(31923, "party", "Birthday Party", in_days(2), &[&["t", "party"]]),
+        calendar_event(31923, "interview", "Interview: Rust Engineer", in_days(2), &[&["t", "interview"], &["t", "hiring"]]),
+        // Dates no calendar could mean
+        calendar_event(31922, "far", "Far Future Fair", "9223372036854775807-01-01".into(), &[&["t", "hiring"]]),
+        calendar_event(31922, "huge-end", "Endless Fair", "2099-06-01".into(), &[&["t", "hiring"], &["end", "99999999999999999-01-01"]]),
+    ];
+    let server = builder(MemoryRelay::new(events)).build().await.unwrap();
+
+    let all = server.search_career_events(Parameters(SearchCareerEventsArgs::default())).await.unwrap();
+    let found = all.structured_content.clone().unwrap()["events"].as_array().unwrap().clone();
+    let ids: Vec<&str> = found.iter().map(|e| e["id"].as_str().unwrap()).collect();
+    assert_eq!(ids, ["soon", "meetup", "fair", "huge-end"], "an unreadable end is left out, an unreadable start skips the event");
+    assert_eq!(found[2]["all_day"], true);
+    assert_eq!(found[2]["topics"], serde_json::json!(["careerfair"]));
+    assert!(text(&all).contains("📅 2099-05-01 - 2099-05-02"), "the end date is exclusive: {}", text(&all));
+    assert!(found[0]["naddr"].as_str().unwrap().starts_with("naddr1"));
+
+    let berlin = SearchCareerEventsArgs { location: Some("berlin".into()), ..Default::default() };
+    let result = server.search_career_events(Parameters(berlin)).await.unwrap();
+    assert!(text(&result).starts_with("🎪 1 upcoming career event(s):\n\n1. Rust Hiring Meetup"), "{}", text(&result));
+
+    let fairs = SearchCareerEventsArgs { topic: Some("#careerfair".into()), query: Some("nostr".into()), ..Default::default() };
+    let result = server.search_career_events(Parameters(fairs)).await.unwrap();
+    assert_eq!(result.structured_content.unwrap()["events"][0]["id"], "fair");
+
+    let this_week = SearchCareerEventsArgs { within_days: Some(7), ..Default::default() };
+    let result = server.search_career_events(Parameters(this_week)).await.unwrap();
+    assert_eq!(result.structured_content.unwrap()["events"].as_array().unwrap().len(), 1);
+
+    let forever = SearchCareerEventsArgs { within_days: Some(u64::MAX), ..Default::default() };
+    let result = server.search_career_events(Parameters(forever)).await.unwrap();
+    assert_eq!(result.structured_content.unwrap()["events"].as_array().unwrap().len(), 2, "clamped to a year");
+}
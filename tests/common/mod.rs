@@ -0,0 +1,268 @@
+// tests/common/mod.rs
+// In-memory relay and fixture listings shared by the integration tests
+
+#![allow(dead_code)]
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream, StreamExt};
+use jobmcp::jobs::RelayBackend;
+use jobmcp::mcp_server::SearchJobsArgs;
+use jobmcp::{NostrJobsServer, NostrJobsServerBuilder, ServerConfig};
+use nostr_sdk::prelude::*;
+use rmcp::handler::server::wrapper::Parameters;
+use rmcp::model::{CallToolResult, RawContent};
+
+pub const RELAY_URL: &str = "wss://relay.test";
+
+/// A relay that answers from a fixed set of events, optionally after a delay
+#[derive(Debug, Default)]
+pub struct MemoryRelay {
+    events: Mutex<Vec<Event>>,
+    delay: Duration,
+    /// Stream the events but never send EOSE
+    stall: bool,
+    /// Extra delay for specific relay URLs
+    relay_delays: Mutex<HashMap<String, Duration>>,
+    fetches: AtomicUsize,
+    /// Rejection messages the next publishes to a relay URL fail with
+    rejections: Mutex<HashMap<String, VecDeque<String>>>,
+    /// NIP-11 limits advertised per relay URL
+    limits: Mutex<HashMap<String, Limitation>>,
+    /// When each relay URL accepted a publish
+    accepted: Mutex<HashMap<String, Vec<Instant>>>,
+}
+
+impl MemoryRelay {
+    pub fn new(events: Vec<Event>) -> Arc<Self> {
+        Arc::new(Self {
+            events: Mutex::new(events),
+            ..Default::default()
+        })
+    }
+
+    /// A relay that takes `delay` to answer every fetch
+    pub fn slow(events: Vec<Event>, delay: Duration) -> Arc<Self> {
+        Arc::new(Self {
+            events: Mutex::new(events),
+            delay,
+            ..Default::default()
+        })
+    }
+
+    /// A relay that streams its events but never sends EOSE
+    pub fn stalling(events: Vec<Event>) -> Arc<Self> {
+        Arc::new(Self {
+            events: Mutex::new(events),
+            stall: true,
+            ..Default::default()
+        })
+    }
+
+    /// Make fetches from `url` take `delay`
+    pub fn delay_relay(&self, url: &str, delay: Duration) {
+        self.relay_delays.lock().unwrap().insert(url.to_string(), delay);
+    }
+
+    /// Number of fetches served so far
+    pub fn fetches(&self) -> usize {
+        self.fetches.load(Ordering::SeqCst)
+    }
+
+    /// Fail the next publishes to `url` with `messages`, one each
+    pub fn reject_publishes(&self, url: &str, messages: &[&str]) {
+        let mut rejections = self.rejections.lock().unwrap();
+        rejections.entry(url.to_string()).or_default().extend(messages.iter().map(|m| m.to_string()));
+    }
+
+    /// Advertise `limits` as the NIP-11 limits of `url`
+    pub fn advertise_limits(&self, url: &str, limits: Limitation) {
+        self.limits.lock().unwrap().insert(url.to_string(), limits);
+    }
+
+    /// When `url` accepted each publish, oldest first
+    pub fn accepted(&self, url: &str) -> Vec<Instant> {
+        self.accepted.lock().unwrap().get(url).cloned().unwrap_or_default()
+    }
+}
+
+impl RelayBackend for MemoryRelay {
+    fn add_relay<'a>(&'a self, _url: &'a str) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn remove_relay<'a>(&'a self, _url: &'a str) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn connect(&self) -> BoxFuture<'_, ()> {
+        Box::pin(async {})
+    }
+
+    fn fetch_events<'a>(
+        &'a self,
+        url: &'a str,
+        filter: Filter,
+        _timeout: Duration,
+    ) -> BoxFuture<'a, Result<Vec<Event>, String>> {
+        Box::pin(async move {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            let delay = self.relay_delays.lock().unwrap().get(url).copied().unwrap_or(self.delay);
+            tokio::time::sleep(delay).await;
+
+            let mut events: Vec<Event> = self
+                .events
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|e| filter.match_event(e, MatchEventOptions::new()))
+                .cloned()
+                .collect();
+            events.sort_by_key(|e| std::cmp::Reverse(e.created_at));
+            events.truncate(filter.limit.unwrap_or(usize::MAX));
+            Ok(events)
+        })
+    }
+
+    fn stream_events<'a>(
+        &'a self,
+        url: &'a str,
+        filter: Filter,
+        timeout: Duration,
+    ) -> BoxFuture<'a, Result<BoxStream<'a, Event>, String>> {
+        Box::pin(async move {
+            let events = stream::iter(self.fetch_events(url, filter, timeout).await?);
+            Ok(match self.stall {
+                true => events.chain(stream::pending()).boxed(),
+                false => events.boxed(),
+            })
+        })
+    }
+
+    fn publish<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, Result<EventId, String>> {
+        Box::pin(async move {
+            self.events.lock().unwrap().push(event.clone());
+            Ok(event.id)
+        })
+    }
+
+    fn publish_to<'a>(&'a self, url: &'a str, event: &'a Event) -> BoxFuture<'a, Result<EventId, String>> {
+        Box::pin(async move {
+            if let Some(message) = self.rejections.lock().unwrap().get_mut(url).and_then(VecDeque::pop_front) {
+                return Err(message);
+            }
+            self.accepted.lock().unwrap().entry(url.to_string()).or_default().push(Instant::now());
+            let mut events = self.events.lock().unwrap();
+            if !events.iter().any(|e| e.id == event.id) {
+                events.push(event.clone());
+            }
+            Ok(event.id)
+        })
+    }
+
+    fn relay_limits<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Option<Limitation>> {
+        Box::pin(async move { self.limits.lock().unwrap().get(url).cloned() })
+    }
+
+    fn shutdown(&self) -> BoxFuture<'_, ()> {
+        Box::pin(async {})
+    }
+}
+
+/// A kind 9993 listing signed by `keys`, posted `age_secs` ago
+pub fn listing(keys: &Keys, job_id: &str, title: &str, company: &str, tags: &[&[&str]], age_secs: u64) -> Event {
+    let mut all = vec![
+        Tag::parse(["job-id", job_id]).unwrap(),
+        Tag::parse(["j", job_id]).unwrap(),
+        Tag::parse(["title", title]).unwrap(),
+        Tag::parse(["company", company]).unwrap(),
+    ];
+    all.extend(tags.iter().map(|t| Tag::parse(t.iter().copied()).unwrap()));
+
+    EventBuilder::new(Kind::from(9993), format!("{} at {}", title, company))
+        .tags(all)
+        .custom_created_at(Timestamp::from(Timestamp::now().as_secs() - age_secs))
+        .sign_with_keys(keys)
+        .unwrap()
+}
+
+/// Three listings: two Rust roles (Acme, Initech) and a Python one (Globex)
+pub fn fixtures() -> Vec<Event> {
+    let keys = Keys::generate();
+    vec![
+        listing(
+            &keys,
+            "acme-1",
+            "Rust Engineer",
+            "Acme",
+            &[
+                &["skill", "Rust"],
+                &["skill", "Tokio"],
+                &["employment-type", "full-time"],
+                &["salary", "120000", "150000", "usd", "year"],
+            ],
+            60,
+        ),
+        listing(
+            &keys,
+            "globex-1",
+            "Python Developer",
+            "Globex",
+            &[&["skill", "Python"], &["skill", "Django"], &["employment-type", "part-time"]],
+            120,
+        ),
+        listing(
+            &keys,
+            "initech-1",
+            "Backend Engineer",
+            "Initech",
+            &[
+                &["skill", "Rust"],
+                &["skill", "PostgreSQL"],
+                &["employment-type", "contract"],
+                &["salary", "90000", "110000", "USD", "year"],
+            ],
+            180,
+        ),
+    ]
+}
+
+/// A builder wired to `relay`, with no files written and short timeouts
+pub fn builder(relay: Arc<MemoryRelay>) -> NostrJobsServerBuilder {
+    let config = ServerConfig {
+        audit_log_path: None,
+        state_path: None,
+        // Scheduled digests and prefetches would add relay fetches the tests don't expect
+        digest_period: None,
+        prefetch_top: 0,
+        ..Default::default()
+    };
+    NostrJobsServer::builder()
+        .config(config)
+        .relays([RELAY_URL])
+        .relay_timeout(Duration::from_millis(200))
+        .search_timeout(Duration::from_millis(500))
+        .backend(relay)
+}
+
+/// Text of the first content block of a tool result
+pub fn text(result: &CallToolResult) -> String {
+    match &result.content[0].raw {
+        RawContent::Text(text) => text.text.clone(),
+        other => panic!("expected text content, got {:?}", other),
+    }
+}
+
+/// `search_jobs` arguments from JSON, as a client would send them
+pub fn search_args(value: serde_json::Value) -> Parameters<SearchJobsArgs> {
+    Parameters(serde_json::from_value(value).unwrap())
+}
+
+/// Cache writes happen on a background task; give it a moment
+pub async fn settle() {
+    tokio::time::sleep(Duration::from_millis(50)).await;
+}
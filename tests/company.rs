@@ -0,0 +1,160 @@
+// tests/company.rs
+// Companies across spellings of their name, how openly each states salaries,
+// and whether the keys posting as one agree on who it is
+
+mod common;
+
+use common::{MemoryRelay, builder, listing, text};
+use jobmcp::jobs::company::{SalaryDisclosure, canonical_company, salary_disclosure};
+use jobmcp::jobs::JobListing;
+use jobmcp::mcp_server::{CompanyProfileArgs, GetStatsArgs};
+use nostr_sdk::prelude::*;
+use rmcp::handler::server::wrapper::Parameters;
+
+fn listings() -> Vec<Event> {
+    let keys = Keys::generate();
+    vec![
+        listing(&keys, "acme-1", "Rust Engineer", "Acme", &[&["salary", "100000", "140000", "USD", "year"], &["skill", "Rust"]], 60),
+        listing(&keys, "acme-2", "Go Engineer", "ACME Inc.", &[&["salary", "120000", "160000", "usd", "year"]], 120),
+        listing(&Keys::generate(), "acme-3", "Designer", "acme, inc", &[&["salary", "90000"]], 180),
+        listing(&keys, "acme-4", "Support", "Acme", &[], 240),
+        listing(&keys, "globex-1", "Analyst", "Globex GmbH", &[&["salary", "5000", "6000", "EUR", "month"]], 300),
+        listing(&keys, "initech-1", "Intern", "Initech", &[], 360),
+    ]
+}
+
+#[test]
+fn company_names_fold_to_one_key() {
+    assert_eq!(canonical_company("ACME Inc."), "acme");
+    assert_eq!(canonical_company(" acme, inc "), "acme");
+    assert_eq!(canonical_company("Johnson & Johnson Ltd"), "johnson & johnson");
+    assert_eq!(canonical_company("Inc"), "inc", "a name that is only a suffix stays");
+    assert_eq!(canonical_company(" - "), "");
+
+    let disclosures: Vec<SalaryDisclosure> = listings().into_iter().map(JobListing::from_event).map(|l| salary_disclosure(&l)).collect();
+    assert_eq!(disclosures[..4], [SalaryDisclosure::Complete, SalaryDisclosure::Complete, SalaryDisclosure::Partial, SalaryDisclosure::Missing]);
+}
+
+#[tokio::test]
+async fn stats_rank_companies_by_salary_transparency() {
+    let server = builder(MemoryRelay::new(listings())).build().await.unwrap();
+    let stats = text(&server.get_stats(Parameters(GetStatsArgs::default())).await.unwrap());
+    assert!(stats.contains("Salary Transparency:\n  3 of 6 listing(s) (50%) state a complete salary"), "{}", stats);
+    let globex = stats.find("• Globex GmbH: 1/1").unwrap();
+    let acme = stats.find("• Acme: 2/4 listing(s) with a complete salary (50%), 1 partial").unwrap();
+    let initech = stats.find("• Initech: 0/1").unwrap();
+    assert!(globex < acme && acme < initech, "most transparent first: {}", stats);
+}
+
+#[tokio::test]
+async fn company_profiles_merge_spellings() {
+    let server = builder(MemoryRelay::new(listings())).build().await.unwrap();
+
+    let args = CompanyProfileArgs { company: "acme corp".into(), sample_size: None };
+    let result = server.get_company_profile(Parameters(args)).await.unwrap();
+    assert!(text(&result).starts_with("🏢 Acme (also spelled ACME Inc., acme, inc)"), "{}", text(&result));
+    let profile = result.structured_content.unwrap();
+    assert_eq!(profile["listings"], 4);
+    assert_eq!(profile["posters"].as_array().unwrap().len(), 2);
+    let transparency = &profile["salary_transparency"];
+    assert_eq!((transparency["complete"].clone(), transparency["partial"].clone()), (2.into(), 1.into()));
+    assert_eq!(transparency["score"], 0.5);
+    // The partial tag has no currency, so only the complete ones give a range
+    let ranges = transparency["typical_ranges"].as_array().unwrap();
+    assert_eq!(ranges.len(), 1);
+    assert_eq!((ranges[0]["min"].as_f64(), ranges[0]["max"].as_f64()), (Some(110000.0), Some(150000.0)));
+
+    let args = CompanyProfileArgs { company: "Hooli".into(), sample_size: None };
+    let result = server.get_company_profile(Parameters(args)).await.unwrap();
+    assert!(text(&result).starts_with("No recent job listings from Hooli"));
+}
+
+#[tokio::test]
+async fn company_profiles_summarize_reputation_with_sources() {
+    let poster = Keys::generate();
+    let post = listing(&poster, "acme-1", "Rust Engineer", "Acme", &[], 60);
+    let note = |content: &str, tags: Vec<Tag>| EventBuilder::text_note(content).tags(tags).sign_with_keys(&Keys::generate()).unwrap();
+    let reaction = |content: &str| {
+        EventBuilder::new(Kind::Reaction, content).tags([Tag::event(post.id)]).sign_with_keys(&Keys::generate()).unwrap()
+    };
+    let report = EventBuilder::new(Kind::Reporting, "Same listing under another company's name")
+        .tags([Tag::parse(["p", &poster.public_key().to_hex(), "impersonation"]).unwrap()])
+        .sign_with_keys(&Keys::generate())
+        .unwrap();
+    let review = note("Interviewed at ACME last month, fast and fair process", vec![Tag::hashtag("review")]);
+    let events = vec![
+        post.clone(),
+        review.clone(),
+        note("Anyone know if Acme's remote policy is real?", vec![]),
+        note("Acmeville has the best coffee", vec![]),
+        // The poster praising itself doesn't count
+        EventBuilder::text_note("Acme is a great place to work!").sign_with_keys(&poster).unwrap(),
+        reaction("+"),
+        reaction("🔥"),
+        reaction("-"),
+        report.clone(),
+    ];
+    let server = builder(MemoryRelay::new(events)).build().await.unwrap();
+
+    let result = server.get_company_profile(Parameters(CompanyProfileArgs { company: "Acme".into(), sample_size: None })).await.unwrap();
+    let reputation = result.structured_content.clone().unwrap()["reputation"].clone();
+    assert_eq!((reputation["likes"].clone(), reputation["dislikes"].clone()), (2.into(), 1.into()), "{}", reputation);
+    assert_eq!((reputation["reviews"].clone(), reputation["mentions"].clone()), (1.into(), 1.into()));
+    assert_eq!(reputation["reports"]["impersonation"], 1);
+    assert_eq!(reputation["voices"], 6);
+    let sources = reputation["sources"].as_array().unwrap();
+    assert_eq!(sources.len(), 3);
+    let review_source = sources.iter().find(|s| s["kind"] == "review").unwrap();
+    assert_eq!(review_source["event_id"], review.id.to_hex());
+    assert!(review_source["nevent"].as_str().unwrap().starts_with("nevent1"));
+    assert!(text(&result).contains("👍 2 · 👎 1 · 📝 1 review(s) · 💬 1 mention(s) · 🚩 1 report(s) (1 impersonation), from 6 account(s)"), "{}", text(&result));
+    assert!(text(&result).contains("[report: impersonation]"));
+}
+
+#[tokio::test]
+async fn company_profiles_flag_keys_that_disagree_on_identity() {
+    let official = Keys::generate();
+    let recruiter = Keys::generate();
+    let impostor = Keys::generate();
+    let anonymous = Keys::generate();
+    let profile = |keys: &Keys, metadata: Metadata| EventBuilder::metadata(&metadata).sign_with_keys(keys).unwrap();
+    let events = vec![
+        listing(&official, "acme-1", "Rust Engineer", "Acme", &[], 60),
+        listing(&official, "acme-2", "Go Engineer", "Acme Inc", &[], 120),
+        listing(&recruiter, "acme-3", "Designer", "Acme", &[], 180),
+        listing(&impostor, "acme-4", "Support", "ACME", &[], 240),
+        listing(&anonymous, "acme-5", "Intern", "Acme", &[], 300),
+        profile(&official, Metadata::new().name("Acme").nip05("_@acme.com").website(Url::parse("https://www.acme.com/").unwrap())),
+        profile(&recruiter, Metadata::new().display_name("Acme Talent").nip05("jane@acme.com")),
+        profile(&impostor, Metadata::new().name("Globex Recruiting").website(Url::parse("https://acme-careers.xyz/apply").unwrap())),
+    ];
+    let server = builder(MemoryRelay::new(events)).build().await.unwrap();
+
+    let result = server.get_company_profile(Parameters(CompanyProfileArgs { company: "Acme".into(), sample_size: None })).await.unwrap();
+    let body = text(&result);
+    assert!(body.contains("⚠️ Keys posting as Acme claim different domains: acme-careers.xyz (1 key(s)), acme.com (2 key(s))"), "{}", body);
+    assert!(body.contains("⚠️ 1 key(s) post as Acme with no NIP-05 or website, while others use acme.com"));
+    assert!(body.contains("has a profile named \"Globex Recruiting\""));
+    assert!(body.contains(&format!("{} (1 listing(s)): no profile", anonymous.public_key().to_bech32().unwrap())));
+
+    let identities = result.structured_content.unwrap()["identities"].clone();
+    assert_eq!(identities["posters"][0]["domain"], "acme.com", "the busiest poster first: {}", identities);
+    let conflicts = identities["conflicts"].as_array().unwrap();
+    let kinds: Vec<&str> = conflicts.iter().map(|c| c["kind"].as_str().unwrap()).collect();
+    assert_eq!(kinds, ["domains", "unverified", "name"]);
+    let impostor = impostor.public_key().to_bech32().unwrap();
+    assert_eq!(conflicts[0]["pubkeys"], serde_json::json!([impostor]));
+    assert_eq!(conflicts[2]["pubkeys"], serde_json::json!([impostor]));
+}
+
+#[tokio::test]
+async fn one_company_domain_is_no_conflict() {
+    let keys = [Keys::generate(), Keys::generate()];
+    let mut events: Vec<Event> = keys.iter().enumerate().map(|(i, k)| listing(k, &format!("acme-{}", i), "Engineer", "Acme", &[], 60)).collect();
+    events.extend(keys.iter().map(|k| EventBuilder::metadata(&Metadata::new().name("Acme Hiring").nip05("hr@acme.com")).sign_with_keys(k).unwrap()));
+    let server = builder(MemoryRelay::new(events)).build().await.unwrap();
+
+    let result = server.get_company_profile(Parameters(CompanyProfileArgs { company: "Acme".into(), sample_size: None })).await.unwrap();
+    assert!(!text(&result).contains("⚠️"), "{}", text(&result));
+    assert_eq!(result.structured_content.unwrap()["identities"]["conflicts"], serde_json::json!([]));
+}
@@ -0,0 +1,74 @@
+// tests/crosspost.rs
+// The same job as a kind 9993 listing, a kind 30402 classified and an
+// ingested posting, shown once with its other representations
+
+mod common;
+
+use common::{MemoryRelay, builder, fixtures, listing, search_args, text};
+use jobmcp::ServerConfig;
+use jobmcp::jobs::crosspost;
+use nostr_sdk::{EventBuilder, Keys, Kind, Tag};
+
+fn classified(keys: &Keys, d: &str, title: &str, company: &str) -> nostr_sdk::Event {
+    EventBuilder::new(Kind::from(30402), format!("{} at {}", title, company))
+        .tags([
+            Tag::parse(["d", d]).unwrap(),
+            Tag::parse(["title", title]).unwrap(),
+            Tag::parse(["company", company]).unwrap(),
+            Tag::parse(["skill", "Rust"]).unwrap(),
+        ])
+        .sign_with_keys(keys)
+        .unwrap()
+}
+
+#[test]
+fn crossposts_fold_into_the_first_listing_but_reposts_do_not() {
+    let employer = Keys::generate();
+    let bridge = Keys::generate();
+    let native = listing(&employer, "acme-1", "Rust Engineer", "Acme", &[], 60).into();
+    let repost = listing(&employer, "acme-2", "Rust Engineer", "Acme", &[], 120).into();
+    let classified = classified(&employer, "acme-rust", "rust  engineer", "ACME").into();
+    let ingested = listing(&bridge, "lever:acme:9", "Rust Engineer", "Acme", &[&["source", "lever"]], 90).into();
+    let other = listing(&employer, "acme-3", "Go Engineer", "Acme", &[], 30).into();
+
+    let all = vec![&other, &native, &ingested, &repost, &classified];
+    let (kept, alternates) = crosspost::fold(all, &bridge.public_key());
+    let ids: Vec<&str> = kept.iter().map(|l| l.job_id.as_str()).collect();
+    assert_eq!(ids, ["acme-3", "acme-1", "acme-2"]);
+    let folded: Vec<&str> = alternates["acme-1"].iter().map(|l| l.job_id.as_str()).collect();
+    assert_eq!(folded.len(), 2);
+    assert_eq!(folded[0], "lever:acme:9");
+    assert!(!alternates.contains_key("acme-2"), "the repost's crossposts were taken by the first listing");
+}
+
+#[tokio::test]
+async fn searches_show_crossposts_as_one_listing() {
+    let employer = Keys::generate();
+    let bridge = Keys::generate();
+    let mut events = fixtures();
+    events.push(classified(&employer, "acme-rust", "Rust Engineer", "Acme"));
+    events.push(listing(&bridge, "lever:acme:9", "Rust Engineer", "Acme", &[&["skill", "Rust"], &["source", "lever"]], 90));
+    let config = ServerConfig {
+        kinds: vec![9993, 30402],
+        ingest_secret_key: Some(bridge.secret_key().to_secret_hex()),
+        audit_log_path: None,
+        state_path: None,
+        digest_period: None,
+        prefetch_top: 0,
+        ..Default::default()
+    };
+    let server = builder(MemoryRelay::new(events)).config(config).build().await.unwrap();
+
+    let results = server.search_jobs_for(None, search_args(serde_json::json!({ "skill": "rust" }))).await.unwrap();
+    let found = text(&results);
+    assert!(found.starts_with("Found 2 job listing(s)"), "{}", found);
+    assert_eq!(found.matches("Acme - Rust Engineer").count(), 1, "{}", found);
+    assert!(found.contains("🔀 Also posted as: kind 9993 (acme-1), ingested (lever:acme:9)"), "{}", found);
+
+    let structured = results.structured_content.unwrap();
+    assert_eq!(structured["listings"][0]["kind"], 30402, "the classified is newest, so it's the one shown");
+    let crossposts = &structured["listings"][0]["crossposts"];
+    assert_eq!(crossposts.as_array().unwrap().len(), 2);
+    assert_eq!(crossposts[1]["source_kind"], "ingested");
+    assert_eq!(structured["listings"][1]["crossposts"], serde_json::json!([]));
+}
@@ -0,0 +1,33 @@
+// tests/dashboard.rs
+// The /status HTML page
+
+mod common;
+
+use common::{MemoryRelay, RELAY_URL, builder, fixtures};
+use jobmcp::JobQuery;
+
+#[tokio::test]
+async fn status_page_shows_relays_cache_and_latency_charts() {
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+    let query = JobQuery { skill: vec!["rust".into()], ..Default::default() };
+    server.jobs().search(&query).await.unwrap();
+
+    let page = server.status_page(false).await;
+    assert!(page.starts_with("<!DOCTYPE html>"), "{}", page);
+    assert!(page.contains(RELAY_URL), "{}", page);
+    assert!(page.contains("Cache hit rate"), "{}", page);
+    assert!(page.contains("<svg"), "latency charts are inline SVG");
+    assert!(!page.contains("<script") && !page.contains("src=\"http"), "no external assets");
+}
+
+#[tokio::test]
+async fn only_admins_see_recent_and_slow_calls() {
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+
+    let public = server.status_page(false).await;
+    assert!(!public.contains("Recent calls") && !public.contains("Slow queries"), "{}", public);
+    assert!(public.contains("shown with an admin bearer token"), "{}", public);
+
+    let admin = server.status_page(true).await;
+    assert!(admin.contains("<h2>Recent calls</h2>") && admin.contains("<h2>Slow queries"), "{}", admin);
+}
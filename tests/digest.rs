@@ -0,0 +1,38 @@
+// tests/digest.rs
+// Daily market digests served as jobs://digest resources
+
+mod common;
+
+use common::{MemoryRelay, builder, listing};
+use jobmcp::NostrJobsServer;
+use jobmcp::jobs::digest::DigestPeriod;
+use nostr_sdk::prelude::*;
+
+#[tokio::test]
+async fn digest_covers_the_last_complete_day() {
+    let now = Timestamp::now().as_secs();
+    let (since, _) = DigestPeriod::Daily.last_complete(Timestamp::now());
+    let yesterday = now - (since.as_secs() + 3_600);
+    let day_before = now - (since.as_secs() - 3_600);
+
+    let keys = Keys::generate();
+    let relay = MemoryRelay::new(vec![
+        listing(&keys, "acme-1", "Rust Engineer", "Acme", &[&["skill", "Rust"], &["salary", "120000", "150000", "USD", "year"]], yesterday),
+        listing(&keys, "initech-1", "Backend Engineer", "Initech", &[&["skill", "Rust"], &["salary", "90000", "110000", "USD", "year"]], yesterday),
+        listing(&keys, "globex-1", "Python Developer", "Globex", &[&["skill", "Python"]], day_before),
+        listing(&keys, "hooli-1", "Intern", "Hooli", &[&["skill", "Rust"]], 60),
+    ]);
+    let server: NostrJobsServer = builder(relay).build().await.unwrap();
+
+    let latest = server.read_digest("latest").await.unwrap();
+    assert!(latest.contains("Listings: 2 (1 the period before"), "{}", latest);
+    assert!(latest.contains("Rust: 0 → 2"), "{}", latest);
+    assert!(!latest.contains("Hooli"), "today's listings wait for tomorrow's digest");
+    let acme = latest.find("Acme - Rust Engineer, $120000").unwrap();
+    assert!(acme < latest.rfind("Initech - Backend Engineer, $90000").unwrap());
+
+    let date = &since.to_human_datetime()[..10];
+    assert!(server.read_digest("archive").await.unwrap().contains(&format!("jobs://digest/{}", date)));
+    assert_eq!(server.read_digest(date).await.unwrap(), latest);
+    assert!(server.read_digest("1999-01-01").await.is_err());
+}
@@ -0,0 +1,98 @@
+// tests/drafts.rs
+// draft_job / preview_job / publish_draft: listings edited and previewed before they are signed
+
+mod common;
+
+use common::{MemoryRelay, RELAY_URL, builder, text};
+use jobmcp::mcp_server::{DraftIdArgs, DraftJobArgs};
+use nostr_sdk::prelude::*;
+use rmcp::handler::server::wrapper::Parameters;
+
+const OWNER: &str = "session:employer";
+
+fn draft_id(job_id: &str) -> Parameters<DraftIdArgs> {
+    Parameters(DraftIdArgs { job_id: job_id.to_string() })
+}
+
+#[tokio::test]
+async fn drafts_are_previewed_then_signed_and_published() {
+    let relay = MemoryRelay::new(Vec::new());
+    let keys = Keys::generate();
+    let server = builder(relay.clone()).signing_key(keys.clone()).build().await.unwrap();
+
+    let created = server
+        .draft_job_for(
+            OWNER,
+            Parameters(DraftJobArgs {
+                title: Some("Rust Engineer".into()),
+                company: Some("Acme".into()),
+                skills: Some(vec!["Rust".into(), " ".into()]),
+                salary_min: Some(120000.0),
+                salary_currency: Some("eur".into()),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+    let draft = created.structured_content.clone().unwrap();
+    let job_id = draft["draft"]["job_id"].as_str().unwrap().to_string();
+    assert!(job_id.starts_with("rust-engineer-"), "{}", job_id);
+    assert_eq!(draft["draft"]["skills"], serde_json::json!(["Rust"]));
+    assert_eq!(draft["problems"], serde_json::json!(["description is empty"]));
+    assert!(text(&created).contains("🏢 Acme - Rust Engineer"));
+    assert!(text(&created).contains("💰 Salary: $120000 - $120000 EUR per year"));
+
+    // Not ready yet, and nothing left the server
+    let err = server.publish_draft_for(OWNER, draft_id(&job_id)).await.unwrap_err();
+    assert!(err.message.contains("description is empty"), "{}", err.message);
+    assert!(relay.accepted(RELAY_URL).is_empty());
+
+    let edit = DraftJobArgs {
+        job_id: Some(job_id.clone()),
+        description: Some("Build our matching engine.".into()),
+        ..Default::default()
+    };
+    server.draft_job_for(OWNER, Parameters(edit)).await.unwrap();
+    let preview = server.preview_job_for(OWNER, draft_id(&job_id)).await.unwrap();
+    assert!(text(&preview).contains("Build our matching engine."));
+    assert!(text(&preview).contains("✅ Ready to publish"));
+    assert_eq!(preview.structured_content.unwrap()["draft"]["company"], "Acme", "untouched fields are kept");
+
+    let published = server.publish_draft_for(OWNER, draft_id(&job_id)).await.unwrap();
+    let listing = published.structured_content.unwrap();
+    assert_eq!(listing["job_id"], job_id.as_str());
+    assert_eq!(listing["author"], keys.public_key().to_bech32().unwrap());
+    assert_eq!(relay.accepted(RELAY_URL).len(), 1);
+
+    let err = server.preview_job_for(OWNER, draft_id(&job_id)).await.unwrap_err();
+    assert!(err.message.contains("No draft"), "published drafts are removed");
+}
+
+#[tokio::test]
+async fn publishing_needs_a_posting_key_and_drafts_stay_private() {
+    let server = builder(MemoryRelay::new(Vec::new())).build().await.unwrap();
+    let args = DraftJobArgs {
+        job_id: Some("acme-7".into()),
+        title: Some("Designer".into()),
+        description: Some("Design things.".into()),
+        ..Default::default()
+    };
+    server.draft_job_for(OWNER, Parameters(args)).await.unwrap();
+
+    assert!(server.preview_job_for("session:someone-else", draft_id("acme-7")).await.is_err());
+    let err = server.publish_draft_for(OWNER, draft_id("acme-7")).await.unwrap_err();
+    assert!(err.message.contains("POSTING_SECRET_KEY"), "{}", err.message);
+    assert!(server.preview_job_for(OWNER, draft_id("acme-7")).await.is_ok(), "a failed publish keeps the draft");
+}
+
+#[tokio::test]
+async fn expirations_past_ten_years_are_rejected() {
+    let server = builder(MemoryRelay::new(Vec::new())).build().await.unwrap();
+    let args = DraftJobArgs {
+        title: Some("Designer".into()),
+        expires_in_days: Some(u64::MAX),
+        ..Default::default()
+    };
+    let err = server.draft_job_for(OWNER, Parameters(args)).await.unwrap_err();
+    assert!(err.message.contains("expires_in_days must be between 0 and 3650"), "{}", err.message);
+}
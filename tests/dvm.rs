@@ -0,0 +1,79 @@
+// tests/dvm.rs
+// match_resume against a simulated NIP-90 DVM answering on the in-memory relay
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MemoryRelay, RELAY_URL, builder, fixtures, text};
+use jobmcp::NostrJobsServer;
+use jobmcp::jobs::RelayBackend;
+use jobmcp::jobs::dvm;
+use jobmcp::mcp_server::MatchResumeArgs;
+use nostr_sdk::prelude::*;
+use rmcp::handler::server::wrapper::Parameters;
+
+#[tokio::test]
+async fn resume_skills_from_a_dvm_rank_matching_listings() {
+    let relay = MemoryRelay::new(fixtures());
+    let server: NostrJobsServer = builder(relay.clone()).build().await.unwrap();
+
+    let dvm_keys = Keys::generate();
+    let responder = tokio::spawn(async move {
+        loop {
+            let requests = relay.fetch_events(RELAY_URL, Filter::new().kind(Kind::from(5000)), Duration::ZERO).await.unwrap();
+            if let Some(request) = requests.first() {
+                assert_eq!(request.tags.find(TagKind::i()).unwrap().content(), Some("Ten years of Rust and Tokio"));
+                let result = EventBuilder::new(Kind::from(6000), r#"{"skills": ["Rust", "Tokio", "rust"]}"#)
+                    .tags([Tag::event(request.id), Tag::public_key(request.pubkey)])
+                    .sign_with_keys(&dvm_keys)
+                    .unwrap();
+                relay.publish(&result).await.unwrap();
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    });
+
+    let args = MatchResumeArgs { resume: "Ten years of Rust and Tokio".to_string(), limit: 10 };
+    let result = server.match_resume(Parameters(args)).await.unwrap();
+    responder.await.unwrap();
+
+    assert!(text(&result).contains("Rust, Tokio"));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["skills"], serde_json::json!(["Rust", "Tokio"]));
+    let ids: Vec<&str> = structured["listings"].as_array().unwrap().iter().map(|l| l["job_id"].as_str().unwrap()).collect();
+    assert_eq!(ids, ["acme-1", "initech-1"]);
+    assert_eq!(structured["listings"][1]["matched_skills"], serde_json::json!(["Rust"]));
+}
+
+#[test]
+fn dvm_output_formats_parse_to_skills() {
+    assert_eq!(dvm::parse_skills(r#"["Rust", " SQL "]"#), ["Rust", "SQL"]);
+    assert_eq!(dvm::parse_skills("- Rust\n- Kubernetes, Go\n"), ["Rust", "Kubernetes", "Go"]);
+}
+
+#[test]
+fn requests_to_a_provider_are_encrypted_and_only_its_answers_count() {
+    let keys = Keys::generate();
+    let provider = Keys::generate();
+    let request = dvm::resume_request(5000, "Ten years of Rust", Some(&provider.public_key()), &keys).unwrap();
+    assert!(!request.content.contains("Rust") && request.tags.find(TagKind::i()).is_none(), "the resume isn't public");
+    assert!(request.tags.iter().any(|t| t.kind() == TagKind::custom("encrypted")));
+    let params = nip04::decrypt(provider.secret_key(), &keys.public_key(), &request.content).unwrap();
+    assert!(params.contains(r#"["i","Ten years of Rust","text"]"#), "{}", params);
+
+    let answer = |signer: &Keys| {
+        let content = nip04::encrypt(signer.secret_key(), &keys.public_key(), r#"["Rust"]"#).unwrap();
+        EventBuilder::new(Kind::from(6000), content)
+            .tags([Tag::event(request.id), Tag::custom(TagKind::custom("encrypted"), Vec::<String>::new())])
+            .sign_with_keys(signer)
+            .unwrap()
+    };
+    let impostor = answer(&Keys::generate());
+    assert!(dvm::outcome(&request, std::slice::from_ref(&impostor), Some(&provider.public_key()), &keys).is_none());
+    let analysis = dvm::outcome(&request, &[impostor, answer(&provider)], Some(&provider.public_key()), &keys).unwrap().unwrap();
+    assert_eq!((analysis.provider, analysis.skills), (provider.public_key(), vec!["Rust".to_string()]));
+    let filter = dvm::response_filter(&request, Some(&provider.public_key()));
+    assert!(filter.authors.unwrap().contains(&provider.public_key()));
+}
@@ -0,0 +1,92 @@
+// tests/error_reporting.rs
+// Sentry-compatible error reports: DSN parsing, repeated relay failures and panics
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use common::{MemoryRelay, RELAY_URL, builder, fixtures};
+use jobmcp::JobQuery;
+use jobmcp::error_reporting::{self, Dsn};
+
+const DEAD_RELAY: &str = "wss://dead.test";
+
+/// Collect envelopes posted to project 42 on a local port, returning its DSN
+async fn serve_collector(envelopes: Arc<Mutex<Vec<String>>>) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let dsn = format!("http://public@{}/42", listener.local_addr().unwrap());
+    let app = axum::Router::new().route(
+        "/api/42/envelope/",
+        axum::routing::post(move |headers: axum::http::HeaderMap, body: String| async move {
+            assert!(headers["x-sentry-auth"].to_str().unwrap().contains("sentry_key=public"));
+            envelopes.lock().unwrap().push(body);
+        }),
+    );
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    dsn
+}
+
+/// The event of each envelope received so far, waiting for `count` of them
+async fn events(envelopes: &Mutex<Vec<String>>, count: usize) -> Vec<serde_json::Value> {
+    for _ in 0..50 {
+        if envelopes.lock().unwrap().len() >= count {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    envelopes.lock().unwrap().iter().map(|e| serde_json::from_str(e.lines().nth(2).unwrap()).unwrap()).collect()
+}
+
+#[test]
+fn dsns_map_to_envelope_endpoints() {
+    let dsn = Dsn::parse("https://abc123@o1.ingest.sentry.io/4505").unwrap();
+    assert_eq!(dsn.public_key, "abc123");
+    assert_eq!(dsn.envelope_url, "https://o1.ingest.sentry.io/api/4505/envelope/");
+    let hosted = Dsn::parse("http://key@glitchtip.internal:8000/errors/7").unwrap();
+    assert_eq!(hosted.envelope_url, "http://glitchtip.internal:8000/errors/api/7/envelope/");
+    assert!(Dsn::parse("https://sentry.io/1").is_err(), "no key");
+}
+
+#[tokio::test]
+async fn repeated_relay_failures_are_reported_once_per_streak() {
+    let envelopes = Arc::new(Mutex::new(Vec::new()));
+    let dsn = serve_collector(envelopes.clone()).await;
+    let relay = MemoryRelay::new(fixtures());
+    // Longer than the 200ms relay timeout
+    relay.delay_relay(DEAD_RELAY, Duration::from_millis(400));
+    let server = builder(relay)
+        .relays([RELAY_URL, DEAD_RELAY])
+        .sentry_dsn(dsn)
+        .error_report_threshold(2)
+        .build()
+        .await
+        .unwrap();
+
+    for skill in ["rust", "python", "go"] {
+        server.jobs().search(&JobQuery { skill: vec![skill.into()], ..Default::default() }).await.unwrap();
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let events = events(&envelopes, 1).await;
+    assert_eq!(events.len(), 1, "the third failure is part of the same streak");
+    assert_eq!(events[0]["level"], "error");
+    assert_eq!(events[0]["tags"]["relay"], DEAD_RELAY);
+    assert_eq!(events[0]["extra"]["consecutive_failures"], 2);
+    assert!(events[0]["extra"]["cache_key"].as_str().unwrap().contains("python"), "{}", events[0]);
+}
+
+#[tokio::test]
+async fn panics_are_reported() {
+    let envelopes = Arc::new(Mutex::new(Vec::new()));
+    let dsn = serve_collector(envelopes.clone()).await;
+    let server = builder(MemoryRelay::new(fixtures())).sentry_dsn(dsn).build().await.unwrap();
+    error_reporting::install_panic_hook(server.jobs().error_reporter().clone());
+
+    assert!(tokio::spawn(async { panic!("relay handler exploded") }).await.is_err());
+
+    let events = events(&envelopes, 1).await;
+    let panic = events.iter().find(|e| e["level"] == "fatal").unwrap();
+    assert_eq!(panic["message"]["formatted"], "panic: relay handler exploded");
+    assert!(panic["extra"]["location"].as_str().unwrap().starts_with("tests/error_reporting.rs:"));
+}
@@ -0,0 +1,101 @@
+// tests/expiry.rs
+// Posted listings about to expire: listed by list_expiring_jobs and reminded about once
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use common::{MemoryRelay, RELAY_URL, builder, listing, text};
+use jobmcp::ServerConfig;
+use jobmcp::jobs::RelayBackend;
+use jobmcp::mcp_server::ExpiringJobsArgs;
+use nostr_sdk::prelude::*;
+use rmcp::handler::server::wrapper::Parameters;
+use serde_json::Value;
+
+const DAY: u64 = 86_400;
+
+/// A listing by `keys` expiring `expires_in` seconds from now, posted `age_secs` ago
+fn expiring(keys: &Keys, job_id: &str, title: &str, expires_in: u64, age_secs: u64) -> Event {
+    let expiration = (Timestamp::now().as_secs() + expires_in).to_string();
+    listing(keys, job_id, title, "Acme", &[&["expiration", &expiration]], age_secs)
+}
+
+fn posted_listings(keys: &Keys) -> Vec<Event> {
+    vec![
+        expiring(keys, "acme-1", "Rust Engineer", DAY, 60),
+        expiring(keys, "acme-2", "Designer", 10 * DAY, 60),
+        // Renewed: only the newest event of a job id counts
+        expiring(keys, "acme-3", "Writer", DAY, 600),
+        expiring(keys, "acme-3", "Writer", 30 * DAY, 60),
+        // Someone else's listing isn't ours to renew
+        expiring(&Keys::generate(), "globex-1", "Analyst", DAY, 60),
+    ]
+}
+
+#[tokio::test]
+async fn expiring_listings_are_the_boards_own_soonest_first() {
+    let keys = Keys::generate();
+    let server = builder(MemoryRelay::new(posted_listings(&keys))).signing_key(keys).build().await.unwrap();
+
+    let result = server.list_expiring_jobs(Parameters(ExpiringJobsArgs::default())).await.unwrap();
+    assert!(text(&result).contains("1 posted listing(s) expire in the next 3 day(s)"), "{}", text(&result));
+    assert!(text(&result).contains("Rust Engineer at Acme (🆔 acme-1)"));
+    let ids = |result: &rmcp::model::CallToolResult| -> Vec<String> {
+        let listings = result.structured_content.as_ref().unwrap()["listings"].as_array().unwrap().clone();
+        listings.iter().map(|l| l["job_id"].as_str().unwrap().to_string()).collect()
+    };
+    assert_eq!(ids(&result), ["acme-1"]);
+
+    let result = server.list_expiring_jobs(Parameters(ExpiringJobsArgs { within_days: Some(14) })).await.unwrap();
+    assert_eq!(ids(&result), ["acme-1", "acme-2"]);
+
+    let server = builder(MemoryRelay::new(Vec::new())).build().await.unwrap();
+    let err = server.list_expiring_jobs(Parameters(ExpiringJobsArgs::default())).await.unwrap_err();
+    assert!(err.message.contains("POSTING_SECRET_KEY"), "{}", err.message);
+}
+
+#[tokio::test]
+async fn reminders_go_out_by_webhook_and_dm_once_per_listing() {
+    let hooks: Arc<Mutex<Vec<Value>>> = Arc::default();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let webhook = format!("http://{}/expiring", listener.local_addr().unwrap());
+    let received = hooks.clone();
+    let app = axum::Router::new().route(
+        "/expiring",
+        axum::routing::post(move |axum::Json(body): axum::Json<Value>| async move {
+            received.lock().unwrap().push(body);
+        }),
+    );
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let keys = Keys::generate();
+    let employer = Keys::generate();
+    let relay = MemoryRelay::new(posted_listings(&keys));
+    let config = ServerConfig {
+        audit_log_path: None,
+        state_path: None,
+        digest_period: None,
+        prefetch_top: 0,
+        publish_interval: Duration::ZERO,
+        expiry_webhook_url: Some(webhook),
+        expiry_reminder_recipient: Some(employer.public_key().to_bech32().unwrap()),
+        ..Default::default()
+    };
+    let server = builder(relay.clone()).config(config).relays([RELAY_URL]).signing_key(keys).build().await.unwrap();
+
+    assert_eq!(server.jobs().send_expiry_reminders().await, 1);
+    let hooks = hooks.lock().unwrap().clone();
+    assert_eq!(hooks.len(), 1);
+    assert_eq!(hooks[0]["type"], "listing_expiring");
+    assert_eq!(hooks[0]["job_id"], "acme-1");
+    assert!(hooks[0]["expires_in_secs"].as_u64().unwrap() <= DAY);
+
+    let wraps = relay.fetch_events(RELAY_URL, Filter::new().kind(Kind::GiftWrap), Duration::from_secs(1)).await.unwrap();
+    assert_eq!(wraps.len(), 1);
+    let gift = UnwrappedGift::from_gift_wrap(&employer, &wraps[0]).await.unwrap();
+    assert!(gift.rumor.content.contains("\"Rust Engineer\" (acme-1) expires at"), "{}", gift.rumor.content);
+
+    assert_eq!(server.jobs().send_expiry_reminders().await, 0, "each listing is reminded about once");
+}
@@ -0,0 +1,58 @@
+// tests/geo_stats.rs
+// Where listings are: get_stats locations and the jobs://stats/geo resource
+
+mod common;
+
+use common::{MemoryRelay, builder, listing, text};
+use jobmcp::jobs::{GeoDistribution, JobListing};
+use jobmcp::mcp_server::GetStatsArgs;
+use nostr_sdk::prelude::*;
+use rmcp::handler::server::wrapper::Parameters;
+
+fn listings() -> Vec<Event> {
+    let keys = Keys::generate();
+    vec![
+        listing(&keys, "berlin-1", "Engineer", "Acme", &[&["location", "Berlin, Germany"], &["g", "u33db"]], 60),
+        listing(&keys, "munich-1", "Engineer", "Acme", &[&["location", "Munich, Germany"], &["g", "u281z"]], 120),
+        listing(&keys, "paris-1", "Engineer", "Acme", &[&["location", "Paris, France"]], 180),
+        listing(&keys, "remote-1", "Engineer", "Acme", &[&["location", "Remote (EU)"]], 240),
+        listing(&keys, "remote-2", "Engineer", "Acme", &[&["remote", "true"]], 300),
+        listing(&keys, "pin-1", "Engineer", "Acme", &[&["g", "dr5ru"]], 360),
+        listing(&keys, "nowhere-1", "Engineer", "Acme", &[], 420),
+    ]
+}
+
+#[test]
+fn remote_work_is_its_own_bucket() {
+    let listings: Vec<JobListing> = listings().into_iter().map(JobListing::from_event).collect();
+    let geo = GeoDistribution::from_listings(&listings);
+    assert_eq!((geo.remote, geo.unspecified), (2, 1));
+    assert_eq!(geo.regions.get("Germany"), Some(&2));
+    assert_eq!(geo.regions.get("France"), Some(&1));
+    assert_eq!(geo.locations.get("Berlin, Germany"), Some(&1));
+    assert_eq!((geo.cells.get("u3"), geo.cells.get("u2"), geo.cells.get("dr")), (Some(&1), Some(&1), Some(&1)));
+}
+
+#[tokio::test]
+async fn stats_break_listings_down_by_location() {
+    let server = builder(MemoryRelay::new(listings())).build().await.unwrap();
+
+    let stats = server.get_stats(Parameters(GetStatsArgs::default())).await.unwrap();
+    assert!(
+        text(&stats).contains("Locations:\n  🌐 Remote: 2 (29%)\n  • Germany: 2 (29%)\n  • France: 1 (14%)\n  ❓ Not specified: 1 (14%)"),
+        "{}",
+        text(&stats)
+    );
+    let geo = stats.structured_content.unwrap()["geo"].clone();
+    assert_eq!(geo["remote"], 2);
+    assert_eq!(geo["regions"]["Germany"], 2);
+    let cells = geo["cells"].as_array().unwrap();
+    assert_eq!(cells.len(), 3);
+    let dr = cells.iter().find(|c| c["geohash"] == "dr").unwrap();
+    assert_eq!((dr["center"]["lat"].as_f64(), dr["center"]["lon"].as_f64()), (Some(42.1875), Some(-73.125)), "{}", dr);
+
+    let resource = server.read_geo_stats().await.unwrap();
+    assert!(resource.starts_with("🗺️ Job Listings by Location"), "{}", resource);
+    assert!(resource.contains("Locations:\n  • Berlin, Germany: 1"));
+    assert!(resource.contains("Geohash cells (precision 2):\n  • dr: 1"));
+}
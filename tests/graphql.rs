@@ -0,0 +1,95 @@
+// tests/graphql.rs
+// The read-only GraphQL schema served at /graphql
+
+mod common;
+
+use axum::body::{Body, to_bytes};
+use axum::http::Request;
+use common::{MemoryRelay, builder, fixtures};
+use jobmcp::graphql;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn graphql_queries_jobs_companies_and_stats() {
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+    let schema = graphql::schema(server);
+
+    let response = schema
+        .execute(
+            r#"{
+                jobs(skill: ["rust"], limit: 5) { jobId company salary { min currency } }
+                job(id: "globex-1") { title skills }
+                missing: job(id: "nope") { title }
+                companies(limit: 1) { name count }
+                stats { listings truncated skills { name count } }
+            }"#,
+        )
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+
+    assert_eq!(
+        data["jobs"],
+        json!([
+            {"jobId": "acme-1", "company": "Acme", "salary": {"min": 120000.0, "currency": "USD"}},
+            {"jobId": "initech-1", "company": "Initech", "salary": {"min": 90000.0, "currency": "USD"}},
+        ])
+    );
+    assert_eq!(data["job"], json!({"title": "Python Developer", "skills": ["Python", "Django"]}));
+    assert_eq!(data["missing"], json!(null));
+    assert_eq!(data["companies"].as_array().unwrap().len(), 1);
+    assert_eq!(data["stats"]["listings"], 3);
+    assert_eq!(data["stats"]["skills"][0], json!({"name": "Rust", "count": 2}), "{}", data["stats"]);
+}
+
+#[tokio::test]
+async fn graphql_is_read_only_and_reports_error_codes() {
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+    let schema = graphql::schema(server);
+    assert!(!schema.sdl().contains("type Mutation"));
+
+    let response = schema.execute(r#"{ jobs(author: "not-a-key") { jobId } }"#).await;
+    let error = &response.errors[0];
+    let code = error.extensions.as_ref().and_then(|e| e.get("code")).cloned();
+    assert_eq!(code, Some(async_graphql::Value::from("parse_error")), "{:?}", error);
+}
+
+#[tokio::test]
+async fn graphql_limits_query_complexity() {
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+    let schema = graphql::schema(server);
+    let many_stats: String = (0..11).map(|i| format!("s{}: stats {{ listings }} ", i)).collect();
+    let response = schema.execute(format!("{{ {} }}", many_stats)).await;
+    assert!(response.errors[0].message.contains("too complex"), "{:?}", response.errors);
+
+    let response = schema.execute("{ jobs(limit: 100) { jobId title company } stats { listings } }").await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+}
+
+#[tokio::test]
+async fn graphql_queries_count_toward_quotas() {
+    let server = builder(MemoryRelay::new(fixtures())).api_keys(["k3y"]).daily_tool_call_quota(1).build().await.unwrap();
+    let router = axum::Router::new().route("/graphql", graphql::route(server));
+    let query = |token: &str| {
+        Request::builder()
+            .method("POST")
+            .uri("/graphql")
+            .header("authorization", format!("Bearer {}", token))
+            .header("content-type", "application/json")
+            .body(Body::from(json!({ "query": "{ jobs { jobId } }" }).to_string()))
+            .unwrap()
+    };
+    let code = |body: &Value| body["errors"][0]["extensions"]["code"].clone();
+
+    let body = response_json(router.clone().oneshot(query("k3y")).await.unwrap()).await;
+    assert_eq!(body["data"]["jobs"].as_array().unwrap().len(), 3, "{}", body);
+    let body = response_json(router.clone().oneshot(query("k3y")).await.unwrap()).await;
+    assert_eq!(code(&body), "quota_exceeded", "{}", body);
+    let body = response_json(router.oneshot(query("made-up")).await.unwrap()).await;
+    assert_eq!(code(&body), "unknown_api_key", "{}", body);
+}
+
+async fn response_json(response: axum::response::Response) -> Value {
+    serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap()
+}
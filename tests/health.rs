@@ -0,0 +1,119 @@
+// tests/health.rs
+// Per-relay health probes: backoff for dead relays, quick checks while recovering,
+// the jobs://health summary built from them, and supervised background tasks
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MemoryRelay, RELAY_URL, builder, fixtures};
+use jobmcp::jobs::RelayProbe;
+
+const DEAD_RELAY: &str = "wss://dead.test";
+
+async fn probe(server: &jobmcp::NostrJobsServer, url: &str) -> RelayProbe {
+    let reports = server.jobs().relay_reports().await;
+    reports.into_iter().find(|r| r.url == url).unwrap().probe.unwrap()
+}
+
+#[tokio::test]
+async fn dead_relays_back_off_and_recovered_ones_are_checked_often() {
+    let relay = MemoryRelay::new(fixtures());
+    relay.delay_relay(DEAD_RELAY, Duration::from_millis(400));
+    let server = builder(relay.clone()).relays([RELAY_URL, DEAD_RELAY]).build().await.unwrap();
+    assert!(server.jobs().relay_reports().await.iter().all(|r| r.probe.is_none()), "not probed yet");
+
+    server.jobs().probe_relays().await;
+    let live = probe(&server, RELAY_URL).await;
+    assert!(live.healthy && !live.recovering);
+    assert!(live.next_probe_in > Duration::from_secs(25));
+    let dead = probe(&server, DEAD_RELAY).await;
+    assert!(!dead.healthy);
+    assert_eq!(dead.last_error.as_deref(), Some("no answer within 200ms"));
+    assert!(server.jobs().is_healthy().await, "one relay serving listings is enough");
+
+    server.jobs().probe_relays().await;
+    server.jobs().probe_relays().await;
+    let dead = probe(&server, DEAD_RELAY).await;
+    assert_eq!(dead.consecutive_failures, 3);
+    assert!(dead.next_probe_in > Duration::from_secs(110), "30s doubled twice: {:?}", dead.next_probe_in);
+
+    relay.delay_relay(DEAD_RELAY, Duration::ZERO);
+    server.jobs().probe_relays().await;
+    let recovered = probe(&server, DEAD_RELAY).await;
+    assert!(recovered.healthy && recovered.recovering);
+    assert!(recovered.next_probe_in <= Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn relays_without_job_listings_are_unhealthy() {
+    let server = builder(MemoryRelay::new(Vec::new())).build().await.unwrap();
+    server.jobs().probe_relays().await;
+    let empty = probe(&server, RELAY_URL).await;
+    assert!(!empty.healthy);
+    assert_eq!(empty.last_error.as_deref(), Some("no job listings"));
+    assert!(!server.jobs().is_healthy().await);
+}
+
+#[tokio::test]
+async fn health_resource_reports_degraded_relays_and_cache_freshness() {
+    let relay = MemoryRelay::new(fixtures());
+    relay.delay_relay(DEAD_RELAY, Duration::from_millis(400));
+    let server = builder(relay).relays([RELAY_URL, DEAD_RELAY]).build().await.unwrap();
+    server.jobs().search(&jobmcp::JobQuery::default()).await.unwrap();
+    server.jobs().probe_relays().await;
+
+    let health = server.health_json().await;
+    assert_eq!(health["status"], "degraded");
+    assert_eq!(health["failing_relays"], serde_json::json!([DEAD_RELAY]));
+    assert_eq!(health["cache"]["fresh_entries"], 1);
+    assert_eq!(health["cache"]["stale_entries"], 0);
+    assert_eq!(health["index"]["cached_events"], 3);
+    assert_eq!(health["last_successful_fetch_secs_ago"], 0);
+    let live = health["relays"].as_array().unwrap().iter().find(|r| r["url"] == RELAY_URL).unwrap();
+    assert_eq!(live["probe"]["healthy"], true);
+    assert_eq!(live["last_answer_secs_ago"], 0);
+}
+
+#[tokio::test]
+async fn panicking_loops_are_restarted_with_backoff() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    let supervisor = jobmcp::jobs::Supervisor::with_backoff(Duration::from_millis(20), Duration::from_millis(40));
+    let starts = Arc::new(AtomicU32::new(0));
+    let counter = starts.clone();
+    supervisor.supervise("flaky", move || {
+        let start = counter.fetch_add(1, Ordering::SeqCst);
+        async move {
+            if start < 2 {
+                panic!("attempt {} failed", start);
+            }
+            std::future::pending::<()>().await;
+        }
+    });
+    supervisor.spawn_once("once", async { panic!("one-shot failed") });
+
+    // 20ms + 40ms of backoff, with room for a busy test machine
+    for _ in 0..100 {
+        if starts.load(Ordering::SeqCst) == 3 && supervisor.reports().len() == 2 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(starts.load(Ordering::SeqCst), 3);
+    let reports = supervisor.reports();
+    let flaky = reports.iter().find(|t| t.name == "flaky").unwrap();
+    assert!(flaky.supervised && flaky.running);
+    assert_eq!(flaky.panics, 2);
+    assert_eq!(flaky.last_panic.as_deref(), Some("attempt 1 failed"));
+    let once = reports.iter().find(|t| t.name == "once").unwrap();
+    assert!(!once.supervised && !once.running);
+    assert_eq!(once.panics, 1);
+
+    // The client's own loops are all running
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+    let health = server.health_json().await;
+    assert!(health["background_tasks"].as_array().unwrap().iter().any(|t| t["name"] == "health_check" && t["running"] == true));
+    assert_eq!(health["stopped_tasks"], serde_json::json!([]));
+}
@@ -0,0 +1,131 @@
+// tests/i18n.rs
+// Server instructions and prompt text in the configured or requested
+// language
+
+mod common;
+
+use common::{MemoryRelay, builder, text};
+use jobmcp::{JobsError, ServerConfig};
+use jobmcp::i18n::{self, Locale};
+use jobmcp::jobs::scam::ScamSignal;
+use jobmcp::mcp_server::{GetJobArgs, GetStatsArgs, HiringTrendsArgs, JobAnalysisArgs, SetPreferenceArgs, SetPreferencesArgs};
+use rmcp::handler::server::wrapper::Parameters;
+use rmcp::ServerHandler;
+use rmcp::model::PromptMessageContent;
+
+fn spanish() -> ServerConfig {
+    ServerConfig {
+        locale: Locale::Es,
+        audit_log_path: None,
+        state_path: None,
+        digest_period: None,
+        prefetch_top: 0,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn locales_parse_from_tags_and_accept_language() {
+    assert_eq!("es-MX".parse::<Locale>(), Ok(Locale::Es));
+    assert_eq!("de_DE.UTF-8".parse::<Locale>(), Ok(Locale::De));
+    assert_eq!("EN".parse::<Locale>(), Ok(Locale::En));
+    assert!("fr".parse::<Locale>().unwrap_err().contains("use en, es, de"));
+
+    assert_eq!(Locale::from_accept_language("fr-CH, de;q=0.8, es;q=0.9, *;q=0.5"), Some(Locale::Es));
+    assert_eq!(Locale::from_accept_language("de-AT,en"), Some(Locale::De), "equal qualities keep their order");
+    assert_eq!(Locale::from_accept_language("es;q=0, fr"), None);
+    assert_eq!(Locale::from_accept_language(""), None);
+}
+
+#[test]
+fn every_locale_translates_every_text() {
+    for locale in Locale::ALL {
+        assert_eq!(i18n::untranslated(locale), Vec::<&str>::new(), "{}", locale);
+    }
+    assert_eq!(i18n::text(Locale::Es, "no.such.key"), "no.such.key");
+    let skills = i18n::text_with(Locale::De, "prompt.search.skills", &[("skills", &"Rust, Go")]);
+    assert_eq!(skills, "Geforderte Fähigkeiten: Rust, Go");
+}
+
+#[tokio::test]
+async fn instructions_follow_the_configured_locale() {
+    let server = builder(MemoryRelay::new(vec![])).build().await.unwrap();
+    let english = server.get_info().instructions.unwrap();
+    assert!(english.starts_with("Nostr Jobs MCP Server - Access decentralized job listings"), "{}", english);
+    assert!(english.contains("Tools:\n• search_jobs - Search for jobs by company, skill, or employment type\n"));
+    assert!(english.ends_with("• Response time comparison"));
+
+    let server = builder(MemoryRelay::new(vec![])).config(spanish()).build().await.unwrap();
+    let spanish = server.get_info().instructions.unwrap();
+    assert!(spanish.contains("Herramientas:\n• search_jobs - Buscar empleos por empresa"), "{}", spanish);
+    assert!(spanish.contains("Recursos:\n• jobs://latest - Últimas ofertas de empleo"));
+    // The same entries, in the same order
+    let names = |text: &str| text.lines().filter_map(|l| Some(l.strip_prefix("• ")?.split_once(" - ")?.0.to_string())).collect::<Vec<_>>();
+    assert_eq!(names(&spanish), names(&english));
+    assert_eq!(server.info_in(Locale::De).instructions.unwrap().lines().next(), Some("Nostr Jobs MCP-Server - Zugriff auf dezentrale Stellenangebote aus dem Nostr-Netzwerk."));
+}
+
+#[tokio::test]
+async fn prompts_are_localized() {
+    let server = builder(MemoryRelay::new(common::fixtures())).build().await.unwrap();
+    let message = |prompt: &rmcp::model::GetPromptResult| match &prompt.messages.last().unwrap().content {
+        PromptMessageContent::Text { text } => text.clone(),
+        other => panic!("expected a text message, got {:?}", other),
+    };
+
+    let args = JobAnalysisArgs { query: "rust remoto".into(), skills: Some(vec!["Rust".into()]), locale: None };
+    let prompt = server.job_search_assistant_in(Locale::Es, args);
+    assert_eq!(prompt.description.as_deref(), Some("Ayuda para buscar empleo: rust remoto"));
+    assert!(message(&prompt).starts_with("Búsqueda: rust remoto\nHabilidades requeridas: Rust\n\n"), "{}", message(&prompt));
+
+    let prompt = server.analyze_job_market_in(Locale::De);
+    assert_eq!(prompt.description.as_deref(), Some("Analyse des Nostr-Arbeitsmarkts"));
+
+    let prompt = server.hiring_trends_forecast_in(Locale::Es, HiringTrendsArgs { weeks: Some(4), ..Default::default() }).await.unwrap();
+    let text = message(&prompt);
+    assert!(text.starts_with("Datos: "), "{}", text);
+    assert!(text.contains("(semana en curso)"));
+    assert!(text.ends_with("Responde en español."));
+}
+
+#[tokio::test]
+async fn responses_follow_the_configured_locale() {
+    let error = JobsError::NotFound { job_id: "nope".into() };
+    assert_eq!(error.to_string(), "No job found with ID: nope");
+    assert_eq!(error.message(Locale::De), "Keine Stelle mit der ID: nope");
+
+    let server = builder(MemoryRelay::new(common::fixtures())).config(spanish()).build().await.unwrap();
+    let stats = text(&server.get_stats(Parameters(GetStatsArgs::default())).await.unwrap());
+    assert!(stats.starts_with("📊 Estadísticas de ofertas de empleo en Nostr 🌐 [ACTUAL]\n\nMuestra: "), "{}", stats);
+    assert!(stats.contains("\n\nHabilidades principales:\n"));
+    assert!(stats.contains("Transparencia salarial:\n  "));
+
+    let missing = server.get_job_details(Parameters(GetJobArgs { job_id: "nope".into() })).await.unwrap_err();
+    assert_eq!(missing.message, "No hay ninguna oferta con el ID: nope");
+    assert_eq!(missing.data.unwrap()["error"], "not_found", "the structured data stays machine-readable");
+
+    let set = SetPreferenceArgs { key: "currency".into(), value: Some("EUR".into()) };
+    let set = text(&server.set_preference_for("session:test", Parameters(set)).await.unwrap());
+    assert_eq!(set, "Preferencia currency = \"EUR\" establecida");
+    let snapshot = text(&server.export_snapshot().await.unwrap());
+    assert!(snapshot.starts_with("📦 Instantánea de "), "{}", snapshot);
+    let raw = text(&server.get_raw_event(Parameters(GetJobArgs { job_id: "acme-1".into() })).await.unwrap());
+    assert!(raw.starts_with("🧾 Evento original de acme-1 (kind 9993, firma válida ✅)"), "{}", raw);
+
+    let prefs = SetPreferencesArgs { min_salary: Some(90_000.0), ..Default::default() };
+    let prefs = text(&server.set_preferences_for("session:test", Parameters(prefs)).await.unwrap());
+    assert!(prefs.starts_with("✅ Preferencias guardadas\n\n• Habilidades: cualquiera\n"), "{}", prefs);
+    let reset = text(&server.reset_metrics().await.unwrap());
+    assert!(reset.starts_with("✅ Se reiniciaron las métricas de rendimiento"), "{}", reset);
+    let cleared = text(&server.clear_cache().await.unwrap());
+    assert!(cleared.starts_with("🗑️  Caché vaciada correctamente"), "{}", cleared);
+}
+
+#[test]
+fn scam_signals_are_localized() {
+    let signal = ScamSignal { code: "messenger_only", details: vec![("messenger", "Telegram".into())], weight: 1 };
+    assert!(signal.message(Locale::En).contains("Telegram"));
+    assert_ne!(signal.message(Locale::Es), signal.message(Locale::En));
+    assert_ne!(signal.message(Locale::De), signal.message(Locale::En));
+    assert!(!signal.message(Locale::Es).contains('{'), "{}", signal.message(Locale::Es));
+}
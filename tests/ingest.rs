@@ -0,0 +1,132 @@
+// tests/ingest.rs
+// External job boards normalized into listings and served alongside relay events
+
+mod common;
+
+use common::{MemoryRelay, builder, fixtures, text};
+use jobmcp::jobs::ingest::{self, IngestSource, SourceKind};
+use jobmcp::mcp_server::GetJobArgs;
+use jobmcp::jobs::RelayBackend;
+use jobmcp::{JobQuery, NostrJobsServer, ServerConfig};
+use nostr_sdk::{Filter, Keys, Kind};
+use rmcp::handler::server::wrapper::Parameters;
+
+const FEED: &str = r#"<?xml version="1.0"?>
+<rss version="2.0"><channel>
+  <title>Hooli Careers</title>
+  <item>
+    <title>Rust Platform Engineer</title>
+    <link>https://hooli.example/jobs/42</link>
+    <guid>hooli-42</guid>
+    <description>&lt;p&gt;Build &lt;b&gt;fast&lt;/b&gt; things&lt;/p&gt;</description>
+    <pubDate>Tue, 02 Jan 2024 15:04:05 +0000</pubDate>
+    <category>Rust</category>
+    <expires>Fri, 01 Jan 2100 00:00:00 GMT</expires>
+  </item>
+</channel></rss>"#;
+
+/// Serve `FEED` on a local port, returning its URL
+async fn serve_feed() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/feed.xml", listener.local_addr().unwrap());
+    let app = axum::Router::new().route("/feed.xml", axum::routing::get(|| async { FEED }));
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    url
+}
+
+#[tokio::test]
+async fn rss_listings_are_searchable_and_labeled() {
+    let feed = serve_feed().await;
+    let config = ServerConfig {
+        audit_log_path: None,
+        state_path: None,
+        ingest_sources: vec![format!("rss:{}", feed)],
+        ..Default::default()
+    };
+    let server: NostrJobsServer = builder(MemoryRelay::new(fixtures())).config(config).relays([common::RELAY_URL]).build().await.unwrap();
+
+    let status = server.jobs().refresh_ingest().await;
+    assert_eq!(status[0].listings, 1, "{:?}", status);
+
+    let query = JobQuery { skill: vec!["rust".into()], ..Default::default() };
+    let results = server.jobs().search(&query).await.unwrap();
+    let ids: Vec<&str> = results.listings.iter().map(|l| l.job_id.as_str()).collect();
+    assert_eq!(ids, ["acme-1", "initech-1", &format!("rss:{}:hooli-42", feed)]);
+    let ingested = &results.listings[2];
+    assert_eq!(ingested.company.as_deref(), Some("Hooli Careers"));
+    assert_eq!(ingested.description, "Build fast things");
+    assert_eq!(ingested.created_at.as_secs(), 1_704_207_845);
+    assert_eq!(ingested.expires_at.unwrap().as_secs(), 4_102_444_800);
+    assert_eq!(server.jobs().external_source(ingested).unwrap().url.as_deref(), Some("https://hooli.example/jobs/42"));
+    assert!(server.jobs().external_source(&results.listings[0]).is_none());
+
+    let args = GetJobArgs { job_id: ingested.job_id.clone() };
+    let details = server.get_job_details(Parameters(args)).await.unwrap();
+    assert!(text(&details).contains("📰 From rss (not on Nostr): https://hooli.example/jobs/42"));
+    assert_eq!(details.structured_content.unwrap()["origin"], "rss");
+}
+
+#[test]
+fn greenhouse_and_lever_responses_normalize() {
+    let source = IngestSource::parse("greenhouse:acme").unwrap();
+    assert_eq!(source.kind, SourceKind::Greenhouse);
+    assert!(IngestSource::parse("lever:has spaces").is_err());
+    assert!(IngestSource::parse("ftp:nope").is_err());
+
+    let greenhouse = r#"{"jobs": [{"id": 7, "title": "SRE", "updated_at": "2024-01-15T10:00:00-05:00",
+        "location": {"name": "Berlin"}, "absolute_url": "https://boards.greenhouse.io/acme/jobs/7",
+        "content": "&lt;p&gt;On call &amp;amp; more&lt;/p&gt;"}]}"#;
+    let listings = ingest::parse_greenhouse("acme", greenhouse).unwrap();
+    assert_eq!(listings[0].external_id, "7");
+    assert_eq!(listings[0].location.as_deref(), Some("Berlin"));
+    assert_eq!(listings[0].description, "On call & more");
+    assert_eq!(listings[0].posted_at.unwrap().as_secs(), 1_705_330_800);
+
+    for updated_at in ["9223372036854775807-01-15T10:00:00Z", "2024-01-15T9223372036854775807:00:00Z", "2024-01-15T25:00:00Z"] {
+        let hostile = greenhouse.replace("2024-01-15T10:00:00-05:00", updated_at);
+        let listings = ingest::parse_greenhouse("acme", &hostile).unwrap();
+        assert_eq!(listings[0].posted_at, None, "{}", updated_at);
+    }
+    let feed = FEED.replace("Tue, 02 Jan 2024 15:04:05 +0000", "Tue, 02 Jan 9223372036854775807 15:04:05 +0000");
+    assert_eq!(ingest::parse_feed(&feed).unwrap()[0].posted_at, None);
+
+    let lever = r#"[{"id": "abc", "text": "Designer", "createdAt": 1700000000000, "tags": ["Figma"],
+        "categories": {"commitment": "Full-time", "location": "Remote"}, "hostedUrl": "https://jobs.lever.co/x/abc",
+        "salaryRange": {"min": 90000, "max": 120000, "currency": "EUR", "interval": "per-year-salary"}}]"#;
+    let listings = ingest::parse_lever("x", lever).unwrap();
+    assert_eq!(listings[0].employment_types, ["Full-time"]);
+    assert_eq!(listings[0].skills, ["Figma"]);
+    let salary = listings[0].salary.as_ref().unwrap();
+    assert_eq!((salary.currency.as_str(), salary.period.as_str()), ("EUR", "year"));
+    assert_eq!(listings[0].posted_at.unwrap().as_secs(), 1_700_000_000);
+}
+
+#[tokio::test]
+async fn bridge_publishes_each_posting_once() {
+    let feed = serve_feed().await;
+    let bridge = Keys::generate();
+    let config = ServerConfig {
+        audit_log_path: None,
+        state_path: None,
+        ingest_sources: vec![format!("rss:{}", feed)],
+        ingest_secret_key: Some(bridge.secret_key().to_secret_hex()),
+        bridge_publish: true,
+        ..Default::default()
+    };
+    let relay = MemoryRelay::new(Vec::new());
+    let server: NostrJobsServer = builder(relay.clone()).config(config.clone()).relays([common::RELAY_URL]).build().await.unwrap();
+    server.jobs().refresh_ingest().await;
+    server.jobs().publish_bridged().await.unwrap();
+
+    let report = server.jobs().publish_bridged().await.unwrap();
+    assert_eq!((report.published, report.unchanged, report.failed), (0, 1, 0));
+    let filter = Filter::new().kind(Kind::from(9993)).author(bridge.public_key());
+    let published = relay.fetch_events(common::RELAY_URL, filter, std::time::Duration::ZERO).await.unwrap();
+    // The background refresh may have published the same event concurrently
+    let ids: std::collections::HashSet<_> = published.iter().map(|e| e.id).collect();
+    assert_eq!(ids.len(), 1);
+    assert!(published[0].tags.iter().any(|t| t.as_slice() == ["expiration", "4102444800"]));
+
+    let unkeyed = ServerConfig { ingest_secret_key: None, ..config };
+    assert!(builder(MemoryRelay::new(Vec::new())).config(unkeyed).build().await.is_err());
+}
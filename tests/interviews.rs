@@ -0,0 +1,126 @@
+// tests/interviews.rs
+// schedule_interview / list_upcoming_interviews: interviews as NIP-52 calendar events signed with the posting key
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MemoryRelay, RELAY_URL, builder, fixtures, text};
+use jobmcp::jobs::RelayBackend;
+use jobmcp::jobs::interview::{CALENDAR_EVENT_KIND, InterviewPlan};
+use jobmcp::mcp_server::{ApplicationStatusArgs, ScheduleInterviewArgs, UpcomingInterviewsArgs};
+use nostr_sdk::prelude::*;
+use rmcp::handler::server::wrapper::Parameters;
+
+const HOUR: u64 = 3600;
+
+#[tokio::test]
+async fn interviews_are_published_and_listed_per_participant() {
+    let relay = MemoryRelay::new(fixtures());
+    let keys = Keys::generate();
+    let candidate = Keys::generate();
+    let server = builder(relay.clone()).signing_key(keys.clone()).build().await.unwrap();
+    let start = Timestamp::now().as_secs() + 48 * HOUR;
+
+    let scheduled = server
+        .schedule_interview(Parameters(ScheduleInterviewArgs {
+            job_id: "acme-1".into(),
+            start: start.to_string(),
+            participants: vec![candidate.public_key().to_bech32().unwrap()],
+            location: Some("https://meet.example/acme".into()),
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+    let interview = scheduled.structured_content.clone().unwrap();
+    assert_eq!(interview["title"], "Interview: Rust Engineer", "titles default to the listing's");
+    assert_eq!(interview["end"], start + HOUR);
+    assert!(interview["naddr"].as_str().unwrap().starts_with("naddr1"));
+    assert!(text(&scheduled).contains("📍 https://meet.example/acme"));
+
+    let calendar = Filter::new().kind(Kind::from(CALENDAR_EVENT_KIND));
+    let published = relay.fetch_events(RELAY_URL, calendar, Duration::from_secs(1)).await.unwrap();
+    assert_eq!(published.len(), 1);
+    let event = &published[0];
+    assert_eq!((event.kind, event.pubkey), (Kind::from(CALENDAR_EVENT_KIND), keys.public_key()));
+    let tag = |name: &str| event.tags.iter().find(|t| t.kind() == TagKind::custom(name)).and_then(|t| t.content()).unwrap();
+    assert_eq!((tag("start"), tag("j"), tag("p")), (start.to_string().as_str(), "acme-1", candidate.public_key().to_hex().as_str()));
+
+    // Rescheduling replaces the interview rather than adding one; the newer
+    // version wins by created_at, which counts whole seconds
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    let id = interview["interview_id"].as_str().unwrap().to_string();
+    let moved = ScheduleInterviewArgs {
+        job_id: "acme-1".into(),
+        start: (start + 24 * HOUR).to_string(),
+        participants: vec![candidate.public_key().to_hex()],
+        interview_id: Some(id.clone()),
+        ..Default::default()
+    };
+    server.schedule_interview(Parameters(moved)).await.unwrap();
+
+    // The candidate tracks the application and sees it next to the interview
+    server
+        .set_application_status_for(
+            "session:candidate",
+            Parameters(ApplicationStatusArgs { job_id: "acme-1".into(), status: Some("interviewing".into()), note: None }),
+        )
+        .await
+        .unwrap();
+    let mine = UpcomingInterviewsArgs { participant: Some(candidate.public_key().to_bech32().unwrap()), job_id: None };
+    let listed = server.list_upcoming_interviews_for(Some("session:candidate"), Parameters(mine)).await.unwrap();
+    let interviews = listed.structured_content.clone().unwrap()["interviews"].as_array().unwrap().clone();
+    assert_eq!(interviews.len(), 1, "{:?}", interviews);
+    assert_eq!((interviews[0]["interview_id"].as_str().unwrap(), interviews[0]["start"].as_u64().unwrap()), (id.as_str(), start + 24 * HOUR));
+    assert_eq!(interviews[0]["application_status"], "interviewing");
+    assert!(text(&listed).contains("📨 Your application: interviewing"));
+
+    let someone_else = UpcomingInterviewsArgs { participant: Some(Keys::generate().public_key().to_hex()), job_id: None };
+    let listed = server.list_upcoming_interviews_for(None, Parameters(someone_else)).await.unwrap();
+    assert_eq!(text(&listed), "No upcoming interviews");
+
+    // Without a participant, the board's own interviews
+    let listed = server.list_upcoming_interviews_for(None, Parameters(UpcomingInterviewsArgs::default())).await.unwrap();
+    assert_eq!(listed.structured_content.unwrap()["interviews"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn interviews_need_a_future_start_and_a_posting_key() {
+    let participant = Keys::generate().public_key();
+    let plan = InterviewPlan {
+        id: "x".into(),
+        job_id: "acme-1".into(),
+        title: "Interview".into(),
+        start: Timestamp::from(Timestamp::now().as_secs() - HOUR),
+        participants: vec![participant],
+        ..Default::default()
+    };
+    assert_eq!(plan.problems(Timestamp::now()), ["start is in the past"]);
+
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+    let args = ScheduleInterviewArgs {
+        job_id: "acme-1".into(),
+        start: "2099-01-15T10:00:00+01:00".into(),
+        participants: vec![participant.to_hex()],
+        ..Default::default()
+    };
+    let err = server.schedule_interview(Parameters(args)).await.unwrap_err();
+    assert!(err.message.contains("POSTING_SECRET_KEY"), "{}", err.message);
+
+    let args = ScheduleInterviewArgs { job_id: "acme-1".into(), start: "next tuesday".into(), ..Default::default() };
+    let err = server.schedule_interview(Parameters(args)).await.unwrap_err();
+    assert!(err.message.contains("expected RFC 3339"), "{}", err.message);
+
+    let args = ScheduleInterviewArgs {
+        job_id: "acme-1".into(),
+        start: "2099-01-15T10:00:00+01:00".into(),
+        duration_minutes: Some(u64::MAX),
+        participants: vec![participant.to_hex()],
+        ..Default::default()
+    };
+    let err = server.schedule_interview(Parameters(args)).await.unwrap_err();
+    assert!(err.message.contains("duration_minutes must be between 0 and 1440"), "{}", err.message);
+
+    let err = server.list_upcoming_interviews_for(None, Parameters(UpcomingInterviewsArgs::default())).await.unwrap_err();
+    assert!(err.message.contains("participant"), "{}", err.message);
+}
@@ -0,0 +1,39 @@
+// tests/live.rs
+// The live listing feed behind /ws/jobs
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MemoryRelay, builder, fixtures, listing};
+use jobmcp::JobQuery;
+use jobmcp::live::LiveParams;
+use nostr_sdk::Keys;
+
+#[tokio::test]
+async fn subscribers_receive_only_listings_posted_after_subscribing() {
+    let relay = MemoryRelay::new(fixtures());
+    let server = builder(relay).live_poll_interval(Duration::from_millis(50)).build().await.unwrap();
+    let mut feed = server.jobs().subscribe();
+
+    let keys = Keys::generate();
+    let fresh = listing(&keys, "umbrella-1", "Rust Developer", "Umbrella", &[&["skill", "Rust"]], 0);
+    server.jobs().publish(&fresh).await.unwrap();
+
+    let received = tokio::time::timeout(Duration::from_secs(2), feed.recv()).await.unwrap().unwrap();
+    assert_eq!(received.job_id, "umbrella-1", "the fixtures predate the subscription");
+
+    // Seen once, not again on the next poll
+    assert!(tokio::time::timeout(Duration::from_millis(200), feed.recv()).await.is_err());
+}
+
+#[test]
+fn live_params_filter_like_search_jobs() {
+    let params = LiveParams { skill: Some("rust, go".to_string()), fuzzy: true, ..Default::default() };
+    let query = JobQuery::try_from(&params).unwrap();
+    assert_eq!(query.skill, ["rust", "go"]);
+    assert!(query.fuzzy_distance.is_some());
+
+    let bad = LiveParams { lat: Some(10.0), ..Default::default() };
+    assert!(JobQuery::try_from(&bad).is_err(), "lat without lon");
+}
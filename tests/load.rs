@@ -0,0 +1,54 @@
+// tests/load.rs
+// Load runs against an in-process HTTP instance serving synthetic listings
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use jobmcp::load::{self, LoadConfig, LoadStep, SyntheticRelay, parse_mix};
+use jobmcp::{NostrJobsServer, ServerConfig};
+use rmcp::transport::streamable_http_server::StreamableHttpService;
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+
+#[test]
+fn mix_parses_weights_and_skips_disabled_tools() {
+    let mix = parse_mix(" search_jobs:3, get_stats ,list_relays:0,,get_job_details:x");
+    let step = |tool: &str, weight| LoadStep { tool: tool.to_string(), weight };
+    assert_eq!(mix, [step("search_jobs", 3), step("get_stats", 1), step("get_job_details", 1)]);
+}
+
+#[tokio::test]
+async fn load_run_reports_every_tool_in_the_mix() {
+    let config = LoadConfig {
+        clients: 2,
+        duration: Duration::from_millis(500),
+        mix: parse_mix("search_jobs:2,get_job_details:1,get_stats:1"),
+        listings: 200,
+        relay_delay: Duration::ZERO,
+        ..Default::default()
+    };
+    let relay = Arc::new(SyntheticRelay::new(config.listings, config.relay_delay));
+    let server = NostrJobsServer::builder()
+        .config(config.local_server_config(ServerConfig { prefetch_top: 0, ..Default::default() }))
+        .backend(relay.clone())
+        .build()
+        .await
+        .unwrap();
+    let service = StreamableHttpService::new(
+        move || Ok(server.clone()),
+        Arc::new(LocalSessionManager::default()),
+        Default::default(),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let target = format!("http://{}/mcp", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, axum::Router::new().nest_service("/mcp", service)).await });
+
+    let report = load::run(&config, &target).await.unwrap();
+
+    assert_eq!(report.errors(), 0, "{}", report);
+    assert!(report.calls() >= 4, "{}", report);
+    for tool in ["search_jobs", "get_job_details", "get_stats"] {
+        assert!(report.tools.get(tool).is_some_and(|t| t.calls > 0), "{} missing:\n{}", tool, report);
+    }
+    assert!(report.to_string().contains("overall"), "{}", report);
+    assert!(relay.fetches() > 0);
+}
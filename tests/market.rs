@@ -0,0 +1,49 @@
+// tests/market.rs
+// market_balance: listings asking for a skill against people for hire with it
+
+mod common;
+
+use common::{MemoryRelay, builder, listing, text};
+use jobmcp::mcp_server::MarketBalanceArgs;
+use nostr_sdk::prelude::*;
+use rmcp::handler::server::wrapper::Parameters;
+
+fn for_hire(keys: &Keys, content: &str, tags: &[&str], age_secs: u64) -> Event {
+    EventBuilder::text_note(content)
+        .tags(tags.iter().map(|t| Tag::hashtag(*t)))
+        .custom_created_at(Timestamp::from(Timestamp::now().as_secs() - age_secs))
+        .sign_with_keys(keys)
+        .unwrap()
+}
+
+#[tokio::test]
+async fn skills_balance_listings_against_candidates() {
+    let employer = Keys::generate();
+    let mut events: Vec<Event> = (0..4).map(|i| listing(&employer, &format!("go-{}", i), "Go Engineer", "Acme", &[&["skill", "Go"]], 60)).collect();
+    events.push(listing(&employer, "rust-1", "Rust Engineer", "Acme", &[&["skill", "Rust"]], 60));
+
+    let (alice, bob, carol) = (Keys::generate(), Keys::generate(), Keys::generate());
+    events.extend([
+        for_hire(&alice, "Rust dev with 5 years of experience, open to remote work", &["forhire", "nostr"], 60),
+        // Only each account's newest note counts
+        for_hire(&alice, "Looking for a Rust or Go role", &["forhire"], 600),
+        for_hire(&bob, "Backend engineer, happy to write go.", &["opentowork"], 60),
+        for_hire(&carol, "Anyone need a hand? #rust", &["hireme", "rust"], 60),
+        // Not a for-hire note
+        for_hire(&Keys::generate(), "We use Rust and Go at work", &["nostr"], 60),
+    ]);
+    let server = builder(MemoryRelay::new(events)).build().await.unwrap();
+
+    let result = server.market_balance(Parameters(MarketBalanceArgs::default())).await.unwrap();
+    assert!(text(&result).contains("5 most recent listing(s), 3 candidate(s)"), "{}", text(&result));
+    assert!(text(&result).contains("  • go: 4 listing(s), 1 candidate(s) - 4.0 listing(s) per candidate: a sellers' market"), "{}", text(&result));
+    assert!(text(&result).contains("  • rust: 1 listing(s), 2 candidate(s) - 0.5 listing(s) per candidate: a buyers' market"));
+    let skills = result.structured_content.unwrap()["skills"].as_array().unwrap().clone();
+    let names: Vec<&str> = skills.iter().map(|s| s["skill"].as_str().unwrap()).collect();
+    assert_eq!(names, ["go", "rust"], "hashtags that aren't skills are left out");
+    assert_eq!((skills[0]["ratio"].as_f64(), skills[0]["market"].as_str()), (Some(4.0), Some("sellers")));
+
+    let args = MarketBalanceArgs { skill: Some("Haskell".into()), ..Default::default() };
+    let result = server.market_balance(Parameters(args)).await.unwrap();
+    assert!(text(&result).contains("  • haskell: 0 listing(s), 0 candidate(s) - no listings or candidates"), "{}", text(&result));
+}
@@ -0,0 +1,102 @@
+// tests/metrics.rs
+// Rolling metric windows reported next to the lifetime totals, and archived
+// metrics periods
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{MemoryRelay, builder, fixtures, text};
+use jobmcp::JobQuery;
+use jobmcp::jobs::digest::DigestPeriod;
+use jobmcp::mcp_server::{MetricsArgs, MetricsHistoryArgs};
+use rmcp::ServiceExt;
+use rmcp::handler::server::wrapper::Parameters;
+use rmcp::model::CallToolRequestParam;
+use rmcp::transport::StreamableHttpClientTransport;
+use rmcp::transport::streamable_http_server::StreamableHttpService;
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+
+#[tokio::test]
+async fn recent_activity_is_reported_per_window_and_reset_with_the_totals() {
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+    let rust = JobQuery { skill: vec!["rust".into()], ..Default::default() };
+    for _ in 0..2 {
+        server.jobs().search(&rust).await.unwrap();
+        common::settle().await;
+    }
+
+    let metrics = server.metrics_json().await;
+    for window in ["5m", "1h", "24h"] {
+        let w = &metrics["windows"][window];
+        assert_eq!(w["requests"], 2, "{}: {}", window, w);
+        assert_eq!(w["cache_hits"], 1, "{}", window);
+        assert_eq!(w["relay_fetches"], 1, "{}", window);
+        assert_eq!(w["cache_hit_rate_pct"], 50.0, "{}", window);
+    }
+    assert!(server.prometheus_metrics().await.contains("jobmcp_window_requests{window=\"5m\"} 2\n"));
+
+    let report = server.get_performance_metrics(Parameters(MetricsArgs::default())).await.unwrap();
+    assert!(text(&report).contains("• Last 5m: 2 requests | Cache hits: 50.0%"), "{}", text(&report));
+
+    server.reset_metrics().await.unwrap();
+    assert_eq!(server.metrics_json().await["windows"]["24h"]["requests"], 0);
+}
+
+#[tokio::test]
+async fn resets_are_archived_and_the_oldest_periods_dropped() {
+    let server = builder(MemoryRelay::new(fixtures()))
+        .metrics_rotation(Some(DigestPeriod::Daily))
+        .metrics_history_size(2)
+        .build()
+        .await
+        .unwrap();
+    let history = |format: Option<&str>| {
+        server.get_metrics_history(Parameters(MetricsHistoryArgs { format: format.map(String::from), ..Default::default() }))
+    };
+    assert!(text(&history(None).await.unwrap()).starts_with("No archived metrics yet (rotation: daily)"));
+
+    for searches in [1, 2, 3] {
+        for skill in ["rust", "python", "go"].into_iter().take(searches) {
+            server.jobs().search(&JobQuery { skill: vec![skill.into()], ..Default::default() }).await.unwrap();
+        }
+        common::settle().await;
+        server.reset_metrics().await.unwrap();
+    }
+
+    let json = history(Some("json")).await.unwrap().structured_content.unwrap();
+    assert_eq!(json["rotation"], "daily");
+    let periods = json["periods"].as_array().unwrap();
+    let requests: Vec<_> = periods.iter().map(|p| p["metrics"]["requests"]["total"].as_u64().unwrap()).collect();
+    assert_eq!(requests, [3, 2], "newest first, the first period dropped");
+    assert_eq!(periods[0]["reason"], "reset");
+    assert!(periods[0]["metrics"].get("windows").is_none());
+    assert!(text(&history(None).await.unwrap()).contains("(reset)\n    Requests: 3 |"));
+
+    assert!(history(Some("xml")).await.is_err());
+}
+
+#[tokio::test]
+async fn unknown_tools_share_a_label_and_label_values_are_escaped() {
+    let server = builder(MemoryRelay::new(fixtures())).relays(["wss://relay.test/\"} 1\nfake_metric"]).build().await.unwrap();
+    server.jobs().search(&JobQuery::default()).await.unwrap();
+    let served = server.clone();
+    let service = StreamableHttpService::new(move || Ok(served.clone()), Arc::new(LocalSessionManager::default()), Default::default());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let target = format!("http://{}/mcp", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, axum::Router::new().nest_service("/mcp", service)).await });
+
+    let client = ().serve(StreamableHttpClientTransport::from_uri(target)).await.unwrap();
+    for name in ["no_such_tool", "bogus\"} 1\nfake_metric 1"] {
+        let call = CallToolRequestParam { name: name.to_string().into(), arguments: None };
+        assert!(client.call_tool(call).await.is_err());
+    }
+    let call = CallToolRequestParam { name: "list_relays".into(), arguments: None };
+    client.call_tool(call).await.unwrap();
+
+    let metrics = server.prometheus_metrics().await;
+    assert!(metrics.contains("jobmcp_tool_calls_total{tool=\"unknown\"} 2\n"), "{}", metrics);
+    assert!(metrics.contains("jobmcp_tool_calls_total{tool=\"list_relays\"} 1\n"), "{}", metrics);
+    assert!(!metrics.contains("no_such_tool") && !metrics.lines().any(|l| l.starts_with("fake_metric")), "{}", metrics);
+    assert!(metrics.contains("relay=\"wss://relay.test/\\\"} 1\\nfake_metric\""), "{}", metrics);
+}
@@ -0,0 +1,59 @@
+// tests/multi_kind.rs
+// One search over several listing kinds and the ingestion bridge, with
+// results labeled by where they came from
+
+mod common;
+
+use common::{MemoryRelay, builder, fixtures, listing, search_args, text};
+use jobmcp::ServerConfig;
+use jobmcp::jobs::ListingSource;
+use nostr_sdk::{EventBuilder, Keys, Kind, Tag};
+
+#[tokio::test]
+async fn searches_span_kinds_and_label_their_sources() {
+    let bridge = Keys::generate();
+    let classified = EventBuilder::new(Kind::from(30402), "Rust contractor wanted")
+        .tags([
+            Tag::parse(["d", "hooli-rust"]).unwrap(),
+            Tag::parse(["title", "Rust Contractor"]).unwrap(),
+            Tag::parse(["company", "Hooli"]).unwrap(),
+            Tag::parse(["skill", "Rust"]).unwrap(),
+        ])
+        .sign_with_keys(&Keys::generate())
+        .unwrap();
+    let mut events = fixtures();
+    events.push(classified);
+    events.push(listing(&bridge, "greenhouse:umbrella:7", "Rust Engineer", "Umbrella", &[&["skill", "Rust"], &["source", "greenhouse"]], 30));
+    let config = ServerConfig {
+        kinds: vec![9993, 30402],
+        ingest_secret_key: Some(bridge.secret_key().to_secret_hex()),
+        audit_log_path: None,
+        state_path: None,
+        digest_period: None,
+        prefetch_top: 0,
+        ..Default::default()
+    };
+    let server = builder(MemoryRelay::new(events)).config(config).build().await.unwrap();
+
+    let rust = server.search_jobs_for(None, search_args(serde_json::json!({ "skill": "rust" }))).await.unwrap();
+    let rust_text = text(&rust);
+    assert!(rust_text.starts_with("Found 4 job listing(s)"), "{}", rust_text);
+    assert!(rust_text.contains("Hooli - Rust Contractor") && rust_text.contains("📡 Source: kind 30402"), "{}", rust_text);
+    assert!(rust_text.contains("📡 Source: ingested") && rust_text.contains("📡 Source: kind 9993"));
+    let structured = rust.structured_content.unwrap();
+    let kinds: Vec<_> = structured["listings"].as_array().unwrap().iter().map(|l| l["source_kind"].as_str().unwrap().to_string()).collect();
+    assert_eq!(kinds, ["kind 30402", "ingested", "kind 9993", "kind 9993"], "merged newest first");
+
+    let classifieds = server.search_jobs_for(None, search_args(serde_json::json!({ "kinds": [30402] }))).await.unwrap();
+    let classifieds = text(&classifieds);
+    assert!(classifieds.starts_with("Found 1 job listing(s)") && classifieds.contains("Hooli"), "{}", classifieds);
+    assert!(!classifieds.contains("📡 Source"), "a single source needs no labels");
+
+    let mixed = server.search_jobs_for(None, search_args(serde_json::json!({ "kinds": ["ingested", "30402"] }))).await.unwrap();
+    assert!(text(&mixed).starts_with("Found 2 job listing(s)"), "{}", text(&mixed));
+
+    let unserved = server.search_jobs_for(None, search_args(serde_json::json!({ "kinds": [1] }))).await.unwrap_err();
+    assert!(unserved.message.contains("this board serves kinds 9993, 30402 and ingested listings"), "{}", unserved.message);
+    assert_eq!("bridge".parse::<ListingSource>(), Ok(ListingSource::Ingested));
+    assert!("classifieds".parse::<ListingSource>().is_err());
+}
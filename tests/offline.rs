@@ -0,0 +1,43 @@
+// tests/offline.rs
+// Offline mode: answering from cached and ingested listings without the relays
+
+mod common;
+
+use common::{MemoryRelay, builder, fixtures, search_args, settle, text};
+use jobmcp::jobs::Source;
+use jobmcp::{JobQuery, JobsError};
+
+#[tokio::test]
+async fn offline_mode_answers_from_the_cache_without_relays() {
+    let relay = MemoryRelay::new(fixtures());
+    let server = builder(relay.clone()).build().await.unwrap();
+    server.jobs().search(&JobQuery::default()).await.unwrap();
+    settle().await;
+    let fetches = relay.fetches();
+
+    assert!(!server.set_offline(true));
+    let rust = JobQuery { skill: vec!["rust".into()], ..Default::default() };
+    let results = server.jobs().search(&rust).await.unwrap();
+    assert_eq!(results.source, Source::Offline);
+    assert_eq!(results.listings.len(), 2);
+    let (listing, _) = server.jobs().get("globex-1").await.unwrap();
+    assert_eq!(listing.company.as_deref(), Some("Globex"));
+    assert!(matches!(server.jobs().get("missing-1").await, Err(JobsError::NotFound { .. })));
+
+    let output = text(&server.search_jobs_for(None, search_args(serde_json::json!({"skill": "python"}))).await.unwrap());
+    assert!(output.starts_with("Found 1 job listing(s) 💾 [OFFLINE]"), "{}", output);
+
+    assert!(matches!(server.jobs().verify("acme-1").await, Err(JobsError::Config(_))));
+    assert_eq!(server.health_json().await["status"], "offline");
+    assert_eq!(relay.fetches(), fetches, "no relay was asked");
+}
+
+#[tokio::test]
+async fn offline_mode_needs_no_relays() {
+    let relay = MemoryRelay::new(fixtures());
+    let server = builder(relay.clone()).relays(Vec::<String>::new()).offline(true).build().await.unwrap();
+
+    let results = server.jobs().search(&JobQuery::default()).await.unwrap();
+    assert!(results.listings.is_empty());
+    assert_eq!(relay.fetches(), 0);
+}
@@ -0,0 +1,48 @@
+// tests/plain_text.rs
+// Plain-text output: emoji and box drawing stripped for screen readers,
+// per deployment or per user
+
+mod common;
+
+use common::{MemoryRelay, builder, fixtures, text};
+use jobmcp::ServerConfig;
+use jobmcp::mcp_server::{GetStatsArgs, MetricsArgs, SetPreferenceArgs};
+use jobmcp::plain_text::{is_plain, strip};
+use rmcp::handler::server::wrapper::Parameters;
+
+#[test]
+fn emoji_and_box_drawing_are_stripped() {
+    assert_eq!(strip("📊 Nostr Job Listings Statistics 🌐 [FRESH]"), "Nostr Job Listings Statistics [FRESH]");
+    assert_eq!(strip("⚠️ Likely scam (score 100/100):"), "Likely scam (score 100/100):");
+    assert_eq!(strip("Reputation: 👍 2 · 🏳️‍🌈 · 1️⃣ first"), "Reputation: 2 · · 1 first");
+    assert_eq!(strip("┌──────┐\n│ Rust │ ████ 40%\n└──────┘\n"), "\nRust 40%\n\n");
+    // Arrows, bullets and accents stay
+    let kept = "  • Rust: 2 → 4 (ñ, ü, —)";
+    assert_eq!(strip(kept), kept);
+    assert!(is_plain(kept) && !is_plain("⭐ Acme"));
+}
+
+#[tokio::test]
+async fn plain_text_follows_config_and_preference() {
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+    let stats = server.get_stats(Parameters(GetStatsArgs::default())).await.unwrap();
+    assert!(!is_plain(&text(&stats)));
+
+    let owner = "reader";
+    let set = |value: serde_json::Value| SetPreferenceArgs { key: "plain_text".into(), value: Some(value) };
+    server.set_preference_for(owner, Parameters(set(true.into()))).await.unwrap();
+    let plain = server.plain_text_for(Some(owner), stats.clone()).await;
+    assert!(text(&plain).starts_with("Nostr Job Listings Statistics [FRESH]\n\nSample: "), "{}", text(&plain));
+    assert!(is_plain(&text(&plain)));
+    assert_eq!(plain.structured_content, stats.structured_content, "structured data is left alone");
+    // Others still get emoji
+    assert!(!is_plain(&text(&server.plain_text_for(Some("someone"), stats.clone()).await)));
+
+    let config = ServerConfig { plain_text: true, audit_log_path: None, state_path: None, digest_period: None, prefetch_top: 0, ..Default::default() };
+    let server = builder(MemoryRelay::new(fixtures())).config(config).build().await.unwrap();
+    let metrics = server.get_performance_metrics(Parameters(MetricsArgs::default())).await.unwrap();
+    assert!(is_plain(&text(&server.plain_text_for(None, metrics.clone()).await)));
+    // A user can opt back out
+    server.set_preference_for(owner, Parameters(set("false".into()))).await.unwrap();
+    assert_eq!(text(&server.plain_text_for(Some(owner), metrics.clone()).await), text(&metrics));
+}
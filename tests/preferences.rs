@@ -0,0 +1,125 @@
+// tests/preferences.rs
+// set_preferences: a candidate profile applied as default search filters
+
+mod common;
+
+use common::{MemoryRelay, builder, fixtures, search_args, text};
+use jobmcp::mcp_server::SetPreferencesArgs;
+use rmcp::handler::server::wrapper::Parameters;
+
+fn job_ids(result: &rmcp::model::CallToolResult) -> Vec<String> {
+    result.structured_content.as_ref().unwrap()["listings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|l| l["job_id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn profile_fills_unset_filters_and_excludes_companies() {
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+    let owner = "session:test";
+
+    let saved = server
+        .set_preferences_for(
+            owner,
+            Parameters(SetPreferencesArgs {
+                skills: Some(vec!["rust".into(), " ".into()]),
+                excluded_companies: Some(vec!["Initech".into()]),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+    let profile = saved.structured_content.unwrap();
+    assert_eq!(profile["skills"], serde_json::json!(["rust"]), "blank values are dropped");
+    assert_eq!(profile["excluded_companies"], serde_json::json!(["Initech"]));
+
+    let result = server.search_jobs_for(Some(owner), search_args(serde_json::json!({}))).await.unwrap();
+    assert_eq!(job_ids(&result), ["acme-1"], "Rust listings, minus Initech");
+    assert!(text(&result).starts_with("⚙️ Your preferences applied: skills rust; excluding Initech"));
+    assert_eq!(result.structured_content.unwrap()["preferences_applied"].as_array().unwrap().len(), 2);
+
+    // An explicit filter wins over the profile's; exclusions still apply
+    let python = server.search_jobs_for(Some(owner), search_args(serde_json::json!({"skill": "python"}))).await.unwrap();
+    assert_eq!(job_ids(&python), ["globex-1"]);
+
+    // Without an owner, or for someone else, nothing is applied
+    let anonymous = server.search_jobs_for(None, search_args(serde_json::json!({"skill": "rust"}))).await.unwrap();
+    assert_eq!(job_ids(&anonymous), ["acme-1", "initech-1"]);
+    let other = server.search_jobs_for(Some("session:other"), search_args(serde_json::json!({"skill": "rust"}))).await.unwrap();
+    assert_eq!(job_ids(&other).len(), 2);
+}
+
+#[tokio::test]
+async fn omitted_fields_are_kept_and_empty_values_clear() {
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+    let owner = "session:test";
+    let set = |args: SetPreferencesArgs| server.set_preferences_for(owner, Parameters(args));
+
+    set(SetPreferencesArgs {
+        skills: Some(vec!["Rust".into()]),
+        min_salary: Some(100_000.0),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    let profile = set(SetPreferencesArgs { locations: Some(vec!["Berlin".into()]), ..Default::default() })
+        .await
+        .unwrap()
+        .structured_content
+        .unwrap();
+    assert_eq!(profile["skills"], serde_json::json!(["Rust"]));
+    assert_eq!(profile["min_salary"], 100_000.0);
+    assert_eq!(profile["locations"], serde_json::json!(["Berlin"]));
+
+    let profile = set(SetPreferencesArgs {
+        skills: Some(Vec::new()),
+        min_salary: Some(0.0),
+        ..Default::default()
+    })
+    .await
+    .unwrap()
+    .structured_content
+    .unwrap();
+    assert_eq!(profile["skills"], serde_json::json!([]));
+    assert!(profile["min_salary"].is_null());
+    assert_eq!(profile["locations"], serde_json::json!(["Berlin"]));
+
+    // No fixture has a Berlin location tag
+    let result = server.search_jobs_for(Some(owner), search_args(serde_json::json!({}))).await.unwrap();
+    assert!(job_ids(&result).is_empty());
+}
+
+#[tokio::test]
+async fn a_corrupt_state_file_survives_the_first_update() {
+    let dir = std::env::temp_dir().join(format!("jobmcp-corrupt-state-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("state.json");
+    let corrupt = "{\"someone\": {\"bookmarks\": [ truncated";
+    std::fs::write(&path, corrupt).unwrap();
+    let config = jobmcp::ServerConfig {
+        audit_log_path: None,
+        state_path: Some(path.clone()),
+        digest_period: None,
+        prefetch_top: 0,
+        ..Default::default()
+    };
+    let server = builder(MemoryRelay::new(fixtures())).config(config).build().await.unwrap();
+
+    let set = jobmcp::mcp_server::SetPreferenceArgs { key: "currency".into(), value: Some("EUR".into()) };
+    server.set_preference_for("session:test", Parameters(set)).await.unwrap();
+
+    let aside: Vec<_> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.file_name().unwrap().to_string_lossy().starts_with("state.json.corrupt-"))
+        .collect();
+    assert_eq!(aside.len(), 1, "{:?}", aside);
+    assert_eq!(std::fs::read_to_string(&aside[0]).unwrap(), corrupt, "the bad file is kept as it was");
+    assert!(std::fs::read_to_string(&path).unwrap().contains("EUR"), "new state goes to a fresh file");
+
+    let _ = std::fs::remove_dir_all(dir);
+}
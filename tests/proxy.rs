@@ -0,0 +1,30 @@
+// tests/proxy.rs
+// Client address, scheme and path prefix behind a reverse proxy
+
+use jobmcp::proxy::{PeerAddr, client_ip, normalize_base_path, scheme};
+
+fn parts(headers: &[(&str, &str)]) -> http::request::Parts {
+    let mut request = http::Request::builder().uri("/mcp");
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let (mut parts, _) = request.body(()).unwrap().into_parts();
+    parts.extensions.insert(PeerAddr("10.0.0.2:51000".parse().unwrap()));
+    parts
+}
+
+#[test]
+fn forwarded_headers_are_honored_only_when_trusted() {
+    let proxied = parts(&[("x-forwarded-for", "203.0.113.9, 198.51.100.7"), ("x-forwarded-proto", "https")]);
+
+    assert_eq!(client_ip(&proxied, true), Some("198.51.100.7".parse().unwrap()), "the proxy's own entry wins over client-supplied ones");
+    assert_eq!(scheme(&proxied, true), "https");
+    assert_eq!(client_ip(&proxied, false), Some("10.0.0.2".parse().unwrap()));
+    assert_eq!(scheme(&proxied, false), "http");
+
+    let garbled = parts(&[("x-forwarded-for", "unknown")]);
+    assert_eq!(client_ip(&garbled, true), Some("10.0.0.2".parse().unwrap()));
+
+    assert_eq!(normalize_base_path(" /nostr-jobs/ "), "/nostr-jobs");
+    assert_eq!(normalize_base_path("/"), "");
+}
@@ -0,0 +1,78 @@
+// tests/publish.rs
+// Outbound publishing: per-relay pacing, retries on rate limiting and NIP-11 limits
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MemoryRelay, RELAY_URL, builder, listing};
+use jobmcp::jobs::JobsError;
+use nostr_sdk::prelude::*;
+
+const STRICT_RELAY: &str = "wss://strict.test";
+
+#[tokio::test]
+async fn publishes_to_a_relay_wait_their_turn() {
+    let relay = MemoryRelay::new(Vec::new());
+    let server = builder(relay.clone())
+        .publish_rate(Duration::from_millis(100), 0)
+        .build()
+        .await
+        .unwrap();
+    let keys = Keys::generate();
+    let events: Vec<Event> =
+        (0..3).map(|i| listing(&keys, &format!("job-{}", i), "Engineer", "Acme", &[], 60)).collect();
+
+    let results = futures::future::join_all(events.iter().map(|e| server.jobs().publish(e))).await;
+    assert!(results.iter().all(Result::is_ok));
+    let accepted = relay.accepted(RELAY_URL);
+    assert_eq!(accepted.len(), 3);
+    for pair in accepted.windows(2) {
+        assert!(pair[1] - pair[0] >= Duration::from_millis(90), "{:?}", pair[1] - pair[0]);
+    }
+}
+
+#[tokio::test]
+async fn rate_limited_publishes_are_retried_but_rejections_are_not() {
+    let relay = MemoryRelay::new(Vec::new());
+    let server = builder(relay.clone())
+        .publish_rate(Duration::from_millis(10), 2)
+        .build()
+        .await
+        .unwrap();
+    let keys = Keys::generate();
+
+    relay.reject_publishes(RELAY_URL, &["rate-limited: slow down"]);
+    let event = listing(&keys, "acme-1", "Engineer", "Acme", &[], 60);
+    assert_eq!(server.jobs().publish(&event).await.unwrap(), event.id);
+    assert_eq!(server.metrics_json().await["requests"]["publish_retries"], 1);
+
+    relay.reject_publishes(RELAY_URL, &["blocked: not on the allowlist"]);
+    let event = listing(&keys, "acme-2", "Engineer", "Acme", &[], 60);
+    let err = server.jobs().publish(&event).await.unwrap_err();
+    assert!(matches!(&err, JobsError::RelayError { message, .. } if message.contains("blocked:")), "{}", err);
+    assert_eq!(server.metrics_json().await["requests"]["publish_retries"], 1);
+    assert_eq!(relay.accepted(RELAY_URL).len(), 1);
+}
+
+#[tokio::test]
+async fn relays_whose_limits_an_event_breaks_are_skipped() {
+    let relay = MemoryRelay::new(Vec::new());
+    relay.advertise_limits(STRICT_RELAY, Limitation { max_content_length: Some(5), ..Default::default() });
+    let server = builder(relay.clone())
+        .relays([RELAY_URL, STRICT_RELAY])
+        .publish_rate(Duration::ZERO, 0)
+        .build()
+        .await
+        .unwrap();
+    let event = listing(&Keys::generate(), "acme-1", "Engineer", "Acme", &[], 60);
+
+    server.jobs().publish(&event).await.unwrap();
+    assert_eq!(relay.accepted(RELAY_URL).len(), 1);
+    assert!(relay.accepted(STRICT_RELAY).is_empty());
+    assert_eq!(server.metrics_json().await["requests"]["skipped_publishes"], 1);
+
+    let strict_only = builder(relay.clone()).relays([STRICT_RELAY]).build().await.unwrap();
+    let err = strict_only.jobs().publish(&event).await.unwrap_err();
+    assert!(err.to_string().contains("skipped: content is"), "{}", err);
+}
@@ -0,0 +1,95 @@
+// tests/quota.rs
+// Daily per-API-key quotas on tool calls and relay fetches
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{MemoryRelay, builder, fixtures, text};
+use jobmcp::{JobsError, NostrJobsServer};
+use rmcp::ServiceExt;
+use rmcp::model::{CallToolRequestParam, ReadResourceRequestParam};
+use rmcp::transport::StreamableHttpClientTransport;
+use rmcp::transport::streamable_http_client::StreamableHttpClientTransportConfig;
+use rmcp::transport::streamable_http_server::StreamableHttpService;
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+
+#[tokio::test]
+async fn keys_are_refused_once_a_daily_quota_is_used_up() {
+    let server: NostrJobsServer = builder(MemoryRelay::new(fixtures()))
+        .daily_tool_call_quota(2)
+        .daily_relay_fetch_quota(5)
+        .build()
+        .await
+        .unwrap();
+
+    server.check_quota("alice").await.unwrap();
+    server.record_usage("alice", 1).await;
+    server.check_quota("alice").await.unwrap();
+    server.record_usage("alice", 1).await;
+    let err = server.check_quota("alice").await.unwrap_err();
+    assert!(matches!(err, JobsError::QuotaExceeded { quota: "tool_calls", limit: 2, .. }), "{:?}", err);
+    assert!(err.to_string().contains("resets in"), "{}", err);
+
+    server.record_usage("bob", 5).await;
+    let err = server.check_quota("bob").await.unwrap_err();
+    assert!(matches!(err, JobsError::QuotaExceeded { quota: "relay_fetches", .. }), "{:?}", err);
+
+    let usage = text(&server.get_api_key_usage().await.unwrap());
+    assert!(usage.contains("alice — 2 call(s), 2 relay fetch(es)"), "{}", usage);
+    assert!(usage.find("alice").unwrap() < usage.find("bob").unwrap(), "busiest key first");
+}
+
+#[tokio::test]
+async fn only_configured_keys_are_metered_per_key() {
+    let client = Some("203.0.113.7".parse().unwrap());
+    let open: NostrJobsServer = builder(MemoryRelay::new(fixtures())).daily_tool_call_quota(1).build().await.unwrap();
+    // Without API_KEYS, made-up tokens share their client's quota
+    assert_eq!(open.quota_key(Some("random-1"), client).unwrap().as_deref(), Some("ip:203.0.113.7"));
+    assert_eq!(open.quota_key(None, client).unwrap().as_deref(), Some("ip:203.0.113.7"));
+    open.check_quota("ip:203.0.113.7").await.unwrap();
+    assert!(open.check_quota("ip:203.0.113.7").await.is_err(), "the call is counted when it's let through");
+
+    let keyed: NostrJobsServer = builder(MemoryRelay::new(fixtures()))
+        .daily_tool_call_quota(1)
+        .api_keys(["k3y"])
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(keyed.quota_key(Some("k3y"), client).unwrap().unwrap().len(), 16);
+    assert_eq!(keyed.quota_key(Some("random-1"), client), Err(JobsError::UnknownApiKey));
+    assert_eq!(keyed.quota_key(None, None), Err(JobsError::UnknownApiKey));
+}
+
+#[tokio::test]
+async fn resource_reads_are_admitted_like_tool_calls() {
+    let server: NostrJobsServer = builder(MemoryRelay::new(fixtures()))
+        .daily_tool_call_quota(2)
+        .admin_tokens(["root"])
+        .api_keys(["k3y"])
+        .build()
+        .await
+        .unwrap();
+    server.set_tool_enabled_at_runtime("get_job_details", false).await.unwrap();
+    let served = server.clone();
+    let service = StreamableHttpService::new(move || Ok(served.clone()), Arc::new(LocalSessionManager::default()), Default::default());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let target = format!("http://{}/mcp", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, axum::Router::new().nest_service("/mcp", service)).await });
+    let transport = StreamableHttpClientTransport::from_config(StreamableHttpClientTransportConfig::with_uri(target).auth_header("k3y"));
+    let client = ().serve(transport).await.unwrap();
+    let read = |uri: &str| client.read_resource(ReadResourceRequestParam { uri: uri.to_string() });
+
+    // Neither a denied admin tool nor a disabled tool's resource is charged
+    let denied = client.call_tool(CallToolRequestParam { name: "reset_metrics".into(), arguments: None }).await.unwrap_err();
+    assert!(denied.to_string().contains("requires admin privileges"), "{}", denied);
+    let disabled = read("jobs://job/acme-1/full").await.unwrap_err();
+    assert!(disabled.to_string().contains("while the get_job_details tool is disabled"), "{}", disabled);
+
+    read("jobs://latest").await.unwrap();
+    read("jobs://stats").await.unwrap();
+    let over = read("jobs://events").await.unwrap_err();
+    assert!(over.to_string().contains("quota"), "{}", over);
+    let usage = text(&server.get_api_key_usage().await.unwrap());
+    assert!(usage.contains(" — 2 call(s)"), "{}", usage);
+}
@@ -0,0 +1,37 @@
+// tests/raw_event.rs
+// get_raw_event: the signed event behind a listing, as NIP-01 JSON
+
+mod common;
+
+use common::{MemoryRelay, builder, fixtures, text};
+use jobmcp::mcp_server::GetJobArgs;
+use nostr_sdk::{Event, JsonUtil};
+use rmcp::handler::server::wrapper::Parameters;
+
+#[tokio::test]
+async fn raw_events_round_trip_and_verify() {
+    let events = fixtures();
+    let acme = events[0].clone();
+    let server = builder(MemoryRelay::new(events)).build().await.unwrap();
+
+    let result = server.get_raw_event(Parameters(GetJobArgs { job_id: "acme-1".into() })).await.unwrap();
+    let shown = text(&result);
+    assert!(shown.starts_with("🧾 Raw event for acme-1 (kind 9993, signature valid ✅)"), "{}", shown);
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["signature_valid"], true);
+    assert_eq!(structured["ingested_from"], serde_json::Value::Null);
+
+    // The JSON is the event itself, ready to re-verify or re-broadcast
+    let event = Event::from_json(structured["event"].to_string()).unwrap();
+    assert_eq!(event, acme);
+    event.verify().unwrap();
+    let json_start = shown.find('{').unwrap();
+    assert_eq!(Event::from_json(&shown[json_start..]).unwrap().id, acme.id);
+
+    // By event id too
+    let by_id = server.get_raw_event(Parameters(GetJobArgs { job_id: acme.id.to_hex() })).await.unwrap();
+    assert_eq!(by_id.structured_content.unwrap()["event"]["id"], acme.id.to_hex());
+
+    let missing = server.get_raw_event(Parameters(GetJobArgs { job_id: "nope".into() })).await.unwrap_err();
+    assert_eq!(missing.message, "No job found with ID: nope");
+}
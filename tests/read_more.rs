@@ -0,0 +1,46 @@
+// tests/read_more.rs
+// Resource links to what a search left out: full descriptions and the
+// matches past its limit
+
+mod common;
+
+use common::{MemoryRelay, builder, fixtures, search_args, text};
+use rmcp::model::RawContent;
+
+fn links(result: &rmcp::model::CallToolResult) -> Vec<String> {
+    result
+        .content
+        .iter()
+        .filter_map(|c| match &c.raw {
+            RawContent::ResourceLink(resource) => Some(resource.uri.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn searches_link_to_descriptions_and_the_rest_of_the_matches() {
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+
+    let everything = server.search_jobs_for(None, search_args(serde_json::json!({}))).await.unwrap();
+    assert_eq!(links(&everything), ["jobs://job/acme-1/full", "jobs://job/globex-1/full", "jobs://job/initech-1/full"]);
+    assert!(!text(&everything).contains("jobs://search/"));
+    assert_eq!(everything.structured_content.as_ref().unwrap()["more"], serde_json::Value::Null);
+
+    let first = server.search_jobs_for(None, search_args(serde_json::json!({ "limit": 2 }))).await.unwrap();
+    let uris = links(&first);
+    assert_eq!(uris.len(), 3, "{:?}", uris);
+    let more = uris.last().unwrap();
+    assert!(more.starts_with("jobs://search/"));
+    assert!(text(&first).ends_with(&format!("Showing 2 of 3 matches; the rest are at {}", more)), "{}", text(&first));
+    assert_eq!(first.structured_content.as_ref().unwrap()["total_matches"], 3);
+
+    let rest = server.read_search_page(&more["jobs://search/".len()..]).await.unwrap();
+    assert!(rest.starts_with("Matches 3-3 of 3"), "{}", rest);
+    assert!(rest.contains("3. 🏢 Initech"), "{}", rest);
+    assert!(rest.contains("📄 jobs://job/initech-1/full"));
+    assert!(!rest.contains("jobs://search/"), "the last page links nowhere");
+
+    let expired = server.read_search_page("0123456789abcdef").await.unwrap_err();
+    assert!(expired.message.contains("run search_jobs again"), "{}", expired.message);
+}
@@ -0,0 +1,59 @@
+// tests/recommend.rs
+// recommend_jobs: profiles from viewed listings, excluding what was already seen
+
+mod common;
+
+use common::{MemoryRelay, builder, fixtures};
+use jobmcp::mcp_server::{GetJobArgs, RecommendJobsArgs};
+use rmcp::handler::server::wrapper::Parameters;
+
+#[tokio::test]
+async fn recommendations_follow_viewed_skills_and_skip_seen_listings() {
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+    let owner = "session:test";
+
+    let empty = server.recommend_jobs_for(owner, Parameters(RecommendJobsArgs { limit: 5 })).await.unwrap();
+    assert!(empty.structured_content.is_none(), "nothing viewed yet");
+
+    server.get_job_details_for(Some(owner), Parameters(GetJobArgs { job_id: "acme-1".into() })).await.unwrap();
+    let result = server.recommend_jobs_for(owner, Parameters(RecommendJobsArgs { limit: 5 })).await.unwrap();
+    let listings = result.structured_content.unwrap()["listings"].as_array().unwrap().clone();
+
+    let ids: Vec<&str> = listings.iter().map(|l| l["job_id"].as_str().unwrap()).collect();
+    assert_eq!(ids, ["initech-1"], "shares Rust with acme-1; acme-1 itself was seen");
+    assert_eq!(listings[0]["matched_skills"], serde_json::json!(["Rust"]));
+
+    // Another owner's views don't leak across
+    let other = server.recommend_jobs_for("session:other", Parameters(RecommendJobsArgs { limit: 5 })).await.unwrap();
+    assert!(other.structured_content.is_none());
+}
+
+#[tokio::test]
+async fn views_are_written_in_batches_and_idle_sessions_expire() {
+    let path = std::env::temp_dir().join(format!("jobmcp-views-{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let config = jobmcp::ServerConfig {
+        audit_log_path: None,
+        state_path: Some(path.clone()),
+        session_ttl: std::time::Duration::ZERO,
+        digest_period: None,
+        prefetch_top: 0,
+        ..Default::default()
+    };
+    let server = builder(MemoryRelay::new(fixtures())).config(config).build().await.unwrap();
+
+    for owner in ["session:test", "user:abc"] {
+        server.get_job_details_for(Some(owner), Parameters(GetJobArgs { job_id: "acme-1".into() })).await.unwrap();
+    }
+    assert!(!path.exists(), "a view doesn't rewrite the state file");
+    server.flush_state().await;
+    let written = std::fs::read_to_string(&path).unwrap();
+    assert!(written.contains("session:test") && written.contains("acme-1"), "{}", written);
+
+    // With no bearer token, state can't outlive its session
+    assert_eq!(server.expire_sessions().await, 1);
+    server.flush_state().await;
+    let written = std::fs::read_to_string(&path).unwrap();
+    assert!(!written.contains("session:test") && written.contains("user:abc"), "{}", written);
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,46 @@
+// tests/resource_params.rs
+// Filtering and paging resources through their URI query strings
+
+mod common;
+
+use common::{MemoryRelay, builder, fixtures};
+use jobmcp::mcp_server::ResourceParams;
+
+#[test]
+fn query_strings_parse_into_resource_params() {
+    let (path, params) = ResourceParams::parse("jobs://latest?limit=50&skill=rust%2Cgo&page=2&fuzzy=true").unwrap();
+    assert_eq!(path, "jobs://latest");
+    assert_eq!((params.limit, params.page, params.fuzzy), (Some(50), Some(2), true));
+    assert_eq!(params.skill.as_deref(), Some("rust,go"));
+
+    let (path, params) = ResourceParams::parse("jobs://stats/geo").unwrap();
+    assert_eq!(path, "jobs://stats/geo");
+    assert_eq!(params.limit, None);
+
+    assert!(ResourceParams::parse("jobs://latest?color=red").unwrap_err().contains("unknown field `color`"));
+    assert!(ResourceParams::parse("jobs://latest?limit=many").is_err());
+}
+
+#[tokio::test]
+async fn latest_pages_and_filters() {
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+
+    let first = server.read_latest("jobs://latest?limit=2").await.unwrap();
+    assert!(first.starts_with("Latest 2 Job Listings:\n\n1. 🏢 Acme"), "{}", first);
+    assert!(first.contains("\n\n2. 🏢 Globex"));
+    assert!(first.ends_with("Next page: jobs://latest?limit=2&page=2"), "{}", first);
+
+    let second = server.read_latest("jobs://latest?page=2&limit=2").await.unwrap();
+    assert!(second.starts_with("Latest 1 Job Listings (page 2):\n\n3. 🏢 Initech"), "{}", second);
+    assert!(!second.contains("Next page"));
+
+    let rust = server.read_latest("jobs://latest?skill=rust&limit=1").await.unwrap();
+    assert!(rust.contains("1. 🏢 Acme"), "{}", rust);
+    assert!(rust.ends_with("Next page: jobs://latest?skill=rust&limit=1&page=2"), "{}", rust);
+    let rust = server.read_latest("jobs://latest?skill=rust&limit=1&page=2").await.unwrap();
+    assert!(rust.contains("2. 🏢 Initech") && !rust.contains("Next page"), "{}", rust);
+
+    assert_eq!(server.read_latest("jobs://latest?company=hooli").await.unwrap(), "No job listings found.");
+    let invalid = server.read_latest("jobs://latest?sort=salary").await.unwrap_err();
+    assert!(invalid.message.starts_with("Invalid query on jobs://latest?sort=salary: "), "{}", invalid.message);
+}
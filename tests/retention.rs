@@ -0,0 +1,52 @@
+// tests/retention.rs
+// Retention limits: compaction purging old and long-expired listings from the cache
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MemoryRelay, builder, listing};
+use jobmcp::jobs::Compaction;
+use jobmcp::jobs::snapshot;
+use nostr_sdk::{Keys, Timestamp};
+
+const DAY: u64 = 86400;
+
+#[tokio::test]
+async fn compaction_purges_old_and_long_expired_listings() {
+    let server = builder(MemoryRelay::new(Vec::new()))
+        .retention(Some(Duration::from_secs(180 * DAY)), Some(Duration::from_secs(30 * DAY)))
+        .build()
+        .await
+        .unwrap();
+    let keys = Keys::generate();
+    let now = Timestamp::now().as_secs();
+    let expired_long_ago = (now - 40 * DAY).to_string();
+    let expired_recently = (now - 10 * DAY).to_string();
+    let events = [
+        listing(&keys, "fresh-1", "Fresh", "Acme", &[], DAY),
+        listing(&keys, "old-1", "Old", "Acme", &[], 200 * DAY),
+        listing(&keys, "gone-1", "Gone", "Acme", &[&["expiration", &expired_long_ago]], 60 * DAY),
+        listing(&keys, "closing-1", "Closing", "Acme", &[&["expiration", &expired_recently]], 60 * DAY),
+    ];
+    server.jobs().import_snapshot(&snapshot::to_ndjson(&events)).await;
+
+    let compaction = server.jobs().compact_cache().await;
+    assert_eq!(compaction, Compaction { purged_old: 1, purged_expired: 1, entries_removed: 0 });
+    assert_eq!(server.jobs().snapshot_events().await.len(), 2);
+    let metrics = server.metrics_json().await;
+    assert_eq!(metrics["cache"]["purged_old_events"], 1);
+    assert_eq!(metrics["cache"]["purged_expired_events"], 1);
+
+    assert_eq!(server.jobs().compact_cache().await, Compaction::default());
+}
+
+#[tokio::test]
+async fn compaction_keeps_everything_without_retention_limits() {
+    let server = builder(MemoryRelay::new(Vec::new())).build().await.unwrap();
+    let old = listing(&Keys::generate(), "old-1", "Old", "Acme", &[], 1000 * DAY);
+    server.jobs().import_snapshot(&snapshot::to_ndjson([&old])).await;
+
+    assert_eq!(server.jobs().compact_cache().await, Compaction::default());
+    assert_eq!(server.jobs().snapshot_events().await.len(), 1);
+}
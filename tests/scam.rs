@@ -0,0 +1,154 @@
+// tests/scam.rs
+// Scam heuristics: pay-to-apply, messenger-only contact, salaries far above
+// market and brand-new keys without a profile
+
+mod common;
+
+use common::{MemoryRelay, RELAY_URL, builder, listing, search_args, text};
+use jobmcp::i18n::Locale;
+use jobmcp::jobs::scam::{PosterHistory, assess};
+use jobmcp::jobs::{JobListing, JobStats};
+use jobmcp::mcp_server::GetJobArgs;
+use nostr_sdk::prelude::*;
+use rmcp::handler::server::wrapper::Parameters;
+use rmcp::model::{NumberOrString, ProgressToken};
+
+const DAY: u64 = 86_400;
+
+fn post(keys: &Keys, job_id: &str, content: &str, tags: &[&[&str]]) -> Event {
+    let mut all = vec![
+        Tag::parse(["job-id", job_id]).unwrap(),
+        Tag::parse(["j", job_id]).unwrap(),
+        Tag::parse(["title", "Remote Assistant"]).unwrap(),
+        Tag::parse(["company", "Quick Cash Ltd"]).unwrap(),
+        Tag::parse(["skill", "Rust"]).unwrap(),
+    ];
+    all.extend(tags.iter().map(|t| Tag::parse(t.iter().copied()).unwrap()));
+    EventBuilder::new(Kind::from(9993), content).tags(all).sign_with_keys(keys).unwrap()
+}
+
+fn profile(keys: &Keys, age_secs: u64) -> Event {
+    EventBuilder::metadata(&Metadata::new().name("Acme"))
+        .custom_created_at(Timestamp::from(Timestamp::now().as_secs() - age_secs))
+        .sign_with_keys(keys)
+        .unwrap()
+}
+
+#[test]
+fn signals_add_up_to_a_score() {
+    let keys = Keys::generate();
+    let now = Timestamp::now();
+    let old_key = PosterHistory { has_profile: false, first_seen: Timestamp::from(now.as_secs() - 30 * DAY) };
+
+    let scam = JobListing::from_event(post(&keys, "a", "Pay the $50 application fee, then DM us on Telegram: t.me/quickcash", &[]));
+    let assessment = assess(&scam, None, Some(&old_key), now);
+    let codes: Vec<&str> = assessment.signals.iter().map(|s| s.code).collect();
+    assert_eq!(codes, ["pay_to_apply", "messenger_only"]);
+    assert_eq!(assessment.score, 80);
+    assert!(assessment.is_suspicious());
+
+    // A messenger next to an email or a website is fine
+    let fine = JobListing::from_event(post(&keys, "b", "Apply at jobs@acme.com or ping us on Telegram", &[]));
+    assert!(assess(&fine, None, Some(&old_key), now).signals.is_empty());
+    let fine = JobListing::from_event(post(&keys, "c", "Details: https://acme.com/jobs (chat: wa.me/123)", &[]));
+    assert!(assess(&fine, None, Some(&old_key), now).signals.is_empty());
+
+    // A new key is only suspect without a profile
+    let new_key = PosterHistory { has_profile: false, first_seen: Timestamp::from(now.as_secs() - DAY) };
+    let plain = JobListing::from_event(post(&keys, "d", "Write Rust with us", &[]));
+    assert_eq!(assess(&plain, None, Some(&new_key), now).signals[0].code, "new_key_no_profile");
+    let known = PosterHistory { has_profile: true, ..new_key };
+    assert_eq!(assess(&plain, None, Some(&known), now).score, 0);
+}
+
+#[test]
+fn salaries_far_above_the_market_are_flagged() {
+    let keys = Keys::generate();
+    let salary = |job_id: &str, min: &str, max: &str| {
+        JobListing::from_event(listing(&keys, job_id, "Engineer", "Acme", &[&["salary", min, max, "USD", "year"]], 60))
+    };
+    let market: Vec<JobListing> = (0..4).map(|i| salary(&format!("m{}", i), "90000", "110000")).collect();
+    let stats = JobStats::from_listings(&market);
+
+    let dream = salary("dream", "900000", "1100000");
+    let assessment = assess(&dream, Some(&stats), None, Timestamp::now());
+    assert_eq!(assessment.signals.len(), 1);
+    assert_eq!(assessment.signals[0].code, "salary_above_market");
+    let message = assessment.signals[0].message(Locale::En);
+    assert!(message.contains("10× the median USD/year salary"), "{}", message);
+    assert!(!assessment.is_suspicious(), "one signal alone is only a warning");
+
+    let generous = salary("generous", "150000", "250000");
+    assert!(assess(&generous, Some(&stats), None, Timestamp::now()).signals.is_empty());
+}
+
+#[tokio::test]
+async fn job_details_warn_and_searches_can_hide_suspicious_listings() {
+    let scammer = Keys::generate();
+    let employer = Keys::generate();
+    let events = vec![
+        post(&scammer, "scam-1", "Earn fast! A small registration fee is required. Contact: whatsapp +1 555 0100", &[]),
+        listing(&employer, "acme-1", "Rust Engineer", "Acme", &[&["skill", "Rust"]], 60),
+        profile(&employer, 400 * DAY),
+    ];
+    let server = builder(MemoryRelay::new(events)).build().await.unwrap();
+
+    let details = server.get_job_details(Parameters(GetJobArgs { job_id: "scam-1".into() })).await.unwrap();
+    let body = text(&details);
+    assert!(body.contains("⚠️ Likely scam (score 100/100):"), "{}", body);
+    assert!(body.contains("\"registration fee\""));
+    let assessment = details.structured_content.unwrap()["scam_assessment"].clone();
+    assert_eq!(assessment["suspicious"], true);
+    assert_eq!(assessment["signals"].as_array().unwrap().len(), 3);
+
+    let details = server.get_job_details(Parameters(GetJobArgs { job_id: "acme-1".into() })).await.unwrap();
+    assert!(!text(&details).contains("⚠️"), "{}", text(&details));
+    assert_eq!(details.structured_content.unwrap()["scam_assessment"]["score"], 0);
+
+    let search = |hide_suspicious: bool| search_args(serde_json::json!({ "skill": "Rust", "hide_suspicious": hide_suspicious }));
+    let all = server.search_jobs_for(None, search(false)).await.unwrap();
+    assert_eq!(all.structured_content.unwrap()["listings"].as_array().unwrap().len(), 2);
+    let safe = server.search_jobs_for(None, search(true)).await.unwrap();
+    assert!(text(&safe).ends_with("🛡️ 1 suspicious listing(s) hidden"), "{}", text(&safe));
+    let structured = safe.structured_content.unwrap();
+    assert_eq!(structured["hidden_suspicious"], 1);
+    assert_eq!(structured["listings"][0]["job_id"], "acme-1");
+}
+
+#[tokio::test]
+async fn search_pages_leave_out_suspicious_listings_before_paging() {
+    let scammer = Keys::generate();
+    let employer = Keys::generate();
+    let events = vec![
+        post(&scammer, "scam-1", "Earn fast! A small registration fee is required. Contact: whatsapp +1 555 0100", &[]),
+        listing(&employer, "acme-1", "Rust Engineer", "Acme", &[&["skill", "Rust"]], 60),
+        listing(&employer, "acme-2", "Rust Lead", "Acme", &[&["skill", "Rust"]], 120),
+        profile(&employer, 400 * DAY),
+    ];
+    let relay = MemoryRelay::new(events);
+    relay.delay_relay("wss://slow.test", std::time::Duration::from_millis(100));
+    let server = builder(relay).relays([RELAY_URL, "wss://slow.test"]).build().await.unwrap();
+
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let args = search_args(serde_json::json!({ "skill": "Rust", "hide_suspicious": true }));
+    let token = ProgressToken(NumberOrString::Number(1));
+    server.search_jobs_with_progress(None, args, Some((token, sender))).await.unwrap();
+    let partial = receiver.recv().await.unwrap().message.unwrap();
+    assert!(partial.contains("Rust Engineer") && !partial.contains("Quick Cash"), "{}", partial);
+
+    let args = search_args(serde_json::json!({ "skill": "Rust", "limit": 1, "hide_suspicious": true }));
+    let first = server.search_jobs_for(None, args).await.unwrap();
+    let structured = first.structured_content.unwrap();
+    assert_eq!(structured["hidden_suspicious"], 1);
+    assert_eq!(structured["listings"].as_array().unwrap().len(), 0, "the newest match was the scam");
+    let cursor = |uri: &str| uri["jobs://search/".len()..].to_string();
+    let more = cursor(structured["more"].as_str().unwrap());
+
+    let page = server.read_search_page(&more).await.unwrap();
+    assert!(page.starts_with("Matches 1-1 of 2"), "{}", page);
+    assert!(page.contains("Rust Engineer") && !page.contains("Quick Cash"), "{}", page);
+    let next = page.rsplit("jobs://search/").next().unwrap().trim();
+    let last = server.read_search_page(next).await.unwrap();
+    assert!(last.starts_with("Matches 2-2 of 2"), "{}", last);
+    assert!(last.contains("Rust Lead") && !last.contains("jobs://search/"), "{}", last);
+}
@@ -0,0 +1,487 @@
+// tests/search.rs
+// search_jobs, get_job_details and statistics against an in-memory relay
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::{MemoryRelay, RELAY_URL, builder, fixtures, listing, search_args, settle, text};
+use futures::future::BoxFuture;
+use jobmcp::jobs::geo::{self, GeoPoint, GeoRadius};
+use jobmcp::jobs::rates::{ExchangeRates, RateProvider};
+use jobmcp::jobs::{PostingHistory, Source};
+use jobmcp::mcp_server::{GetJobArgs, OneOrMany, SearchJobsArgs};
+use jobmcp::{JobQuery, JobsError, NostrJobsServer};
+use nostr_sdk::nips::nip09::EventDeletionRequest;
+use nostr_sdk::{EventBuilder, Keys, Timestamp};
+use rmcp::handler::server::wrapper::Parameters;
+use rmcp::model::{NumberOrString, ProgressToken};
+
+#[tokio::test]
+async fn search_filters_by_tag_values() {
+    let jobs = builder(MemoryRelay::new(fixtures())).build_client().await.unwrap();
+
+    let rust = JobQuery { skill: vec!["rust".into()], ..Default::default() };
+    let results = jobs.search(&rust).await.unwrap();
+    let ids: Vec<&str> = results.listings.iter().map(|l| l.job_id.as_str()).collect();
+    assert_eq!(ids, ["acme-1", "initech-1"]);
+
+    let acme_rust = JobQuery {
+        company: vec!["acme".into()],
+        skill: vec!["rust".into()],
+        ..Default::default()
+    };
+    let results = jobs.search(&acme_rust).await.unwrap();
+    assert_eq!(results.listings.len(), 1);
+    assert_eq!(results.listings[0].company.as_deref(), Some("Acme"));
+
+    let any_of = JobQuery {
+        skill: vec!["python".into(), "postgres".into()],
+        ..Default::default()
+    };
+    assert_eq!(jobs.search(&any_of).await.unwrap().listings.len(), 2);
+
+    let limited = JobQuery { limit: 1, ..Default::default() };
+    assert_eq!(jobs.search(&limited).await.unwrap().listings.len(), 1);
+}
+
+#[tokio::test]
+async fn author_filters_and_preferred_authors() {
+    let employer = Keys::generate();
+    let mut events = fixtures();
+    events.push(listing(&employer, "hooli-1", "Rust Lead", "Hooli", &[&["skill", "Rust"]], 600));
+    let jobs = builder(MemoryRelay::new(events)).build_client().await.unwrap();
+
+    let rust = JobQuery { skill: vec!["rust".into()], ..Default::default() };
+    let ids: Vec<String> = jobs.search(&rust).await.unwrap().listings.into_iter().map(|l| l.job_id).collect();
+    assert_eq!(ids, ["acme-1", "initech-1", "hooli-1"]);
+
+    let following = JobQuery { preferred_authors: vec![employer.public_key()], limit: 2, ..rust };
+    let ids: Vec<String> = jobs.search(&following).await.unwrap().listings.into_iter().map(|l| l.job_id).collect();
+    assert_eq!(ids, ["hooli-1", "acme-1"]);
+
+    let by_author = JobQuery { author: Some(employer.public_key()), ..Default::default() };
+    let ids: Vec<String> = jobs.search(&by_author).await.unwrap().listings.into_iter().map(|l| l.job_id).collect();
+    assert_eq!(ids, ["hooli-1"]);
+}
+
+#[tokio::test]
+async fn search_jobs_tool_serves_repeat_queries_from_cache() {
+    let relay = MemoryRelay::new(fixtures());
+    let server = builder(relay.clone()).build().await.unwrap();
+
+    let first = text(&server.search_jobs_for(None, search_args(serde_json::json!({"skill": "rust"}))).await.unwrap());
+    assert!(first.starts_with("Found 2 job listing(s) 🌐 [FRESH]"), "{}", first);
+    assert!(first.contains("🏢 Acme - Rust Engineer"));
+    assert!(first.contains("💰 Salary: $120000 - $150000 USD per year"));
+    assert_eq!(relay.fetches(), 1);
+
+    settle().await;
+    let second = text(&server.search_jobs_for(None, search_args(serde_json::json!({"skill": "RUST"}))).await.unwrap());
+    assert!(second.starts_with("Found 2 job listing(s) ⚡ [CACHED]"), "{}", second);
+    assert_eq!(relay.fetches(), 1);
+
+    server.jobs().clear_cache().await;
+    server.search_jobs_for(None, search_args(serde_json::json!({"skill": "rust"}))).await.unwrap();
+    assert_eq!(relay.fetches(), 2);
+}
+
+#[tokio::test]
+async fn stale_cache_entries_are_refreshed_from_the_relays() {
+    let relay = MemoryRelay::new(fixtures());
+    let jobs = builder(relay.clone())
+        .search_cache_ttl(Duration::ZERO)
+        .build_client()
+        .await
+        .unwrap();
+
+    let query = JobQuery::default();
+    assert_eq!(jobs.search(&query).await.unwrap().source, Source::Relays);
+    settle().await;
+    assert_eq!(jobs.search(&query).await.unwrap().source, Source::Relays);
+    assert_eq!(relay.fetches(), 2);
+}
+
+#[tokio::test]
+async fn stale_cache_entries_are_served_with_their_age_when_relays_fail() {
+    let relay = MemoryRelay::new(fixtures());
+    let server = builder(relay.clone()).search_cache_ttl(Duration::ZERO).build().await.unwrap();
+    let args = || search_args(serde_json::json!({"skill": "rust"}));
+
+    server.search_jobs_for(None, args()).await.unwrap();
+    settle().await;
+    // Longer than the 500ms search timeout
+    relay.delay_relay(RELAY_URL, Duration::from_secs(2));
+
+    let stale = server.search_jobs_for(None, args()).await.unwrap();
+    assert!(text(&stale).starts_with("Found 2 job listing(s) 📦 [CACHED - STALE, 0s old: relays unreachable]"), "{}", text(&stale));
+    let structured = stale.structured_content.unwrap();
+    assert_eq!(structured["source"], "stale_cache");
+    assert_eq!(structured["cache_age_secs"], 0);
+
+    let uncached = server.search_jobs_for(None, search_args(serde_json::json!({"skill": "python"}))).await;
+    assert!(uncached.is_err(), "nothing cached to fall back on");
+}
+
+#[tokio::test]
+async fn popular_searches_are_prefetched_before_expiring() {
+    let relay = MemoryRelay::new(fixtures());
+    let jobs = builder(relay.clone())
+        .prefetch_top(10)
+        .search_cache_ttl(Duration::from_secs(60))
+        .build_client()
+        .await
+        .unwrap();
+
+    let rust = JobQuery { skill: vec!["rust".to_string()], ..Default::default() };
+    let python = JobQuery { skill: vec!["python".to_string()], ..Default::default() };
+    jobs.search(&rust).await.unwrap();
+    settle().await;
+    jobs.search(&rust).await.unwrap();
+    jobs.search(&python).await.unwrap();
+    settle().await;
+    assert_eq!(relay.fetches(), 2);
+
+    // Both entries are fresh for the next 60s
+    assert!(jobs.prefetch_popular(Duration::from_secs(1)).await.is_empty());
+    // Expiring within the lead: only the search asked for twice is refreshed
+    assert_eq!(jobs.prefetch_popular(Duration::from_secs(60)).await, [rust.cache_key()]);
+    assert_eq!(relay.fetches(), 3);
+}
+
+#[tokio::test]
+async fn typos_get_suggestions_or_fuzzy_matches() {
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+
+    let strict = text(&server.search_jobs_for(None, search_args(serde_json::json!({"skill": "Pyhton"}))).await.unwrap());
+    assert!(strict.starts_with("No job listings found"), "{}", strict);
+    assert!(strict.contains("skill \"Pyhton\" → \"Python\""), "{}", strict);
+
+    let fuzzy = text(
+        &server
+            .search_jobs_for(None, search_args(serde_json::json!({"skill": "Pyhton", "fuzzy": true})))
+            .await
+            .unwrap(),
+    );
+    assert!(fuzzy.contains("🏢 Globex - Python Developer"), "{}", fuzzy);
+}
+
+#[tokio::test]
+async fn fuzzy_matching_allows_fewer_typos_in_short_values() {
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+    let search = |skill: &str| server.search_jobs_for(None, search_args(serde_json::json!({"skill": skill, "fuzzy": true})));
+
+    let swapped = text(&search("Rsut").await.unwrap());
+    assert!(swapped.starts_with("Found 2 job listing(s)"), "{}", swapped);
+    let short = text(&search("Rut").await.unwrap());
+    assert!(short.starts_with("No job listings found"), "three letters match only exactly: {}", short);
+    let long = text(&search("Pythonic").await.unwrap());
+    assert!(long.contains("🏢 Globex - Python Developer"), "{}", long);
+}
+
+#[test]
+fn one_or_many_accepts_a_list() {
+    let args = serde_json::from_value::<SearchJobsArgs>(serde_json::json!({"company": [" Acme ", "\"Globex\"", ""]}))
+        .unwrap();
+    let company = args.company.as_ref().map(OneOrMany::values).unwrap();
+    assert_eq!(company, ["Acme", "Globex"]);
+}
+
+#[tokio::test]
+async fn get_job_details_looks_up_job_id_and_event_id() {
+    let events = fixtures();
+    let initech_event_id = events[2].id.to_hex();
+    let server = builder(MemoryRelay::new(events)).build().await.unwrap();
+
+    let result = server.get_job_details(Parameters(GetJobArgs { job_id: "globex-1".into() })).await.unwrap();
+    let by_job_id = text(&result);
+    assert!(by_job_id.contains("🏢 Globex - Python Developer"), "{}", by_job_id);
+    assert!(by_job_id.contains("📄 Full Job Details:\nPython Developer at Globex"));
+    assert!(by_job_id.contains("🔗 https://njump.me/nevent1"), "{}", by_job_id);
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["job_id"], "globex-1");
+    assert!(structured["nevent"].as_str().unwrap().starts_with("nevent1"));
+
+    let by_event_id = text(&server.get_job_details(Parameters(GetJobArgs { job_id: initech_event_id })).await.unwrap());
+    assert!(by_event_id.contains("🏢 Initech - Backend Engineer"), "{}", by_event_id);
+
+    let missing = server.get_job_details(Parameters(GetJobArgs { job_id: "nope".into() })).await.unwrap_err();
+    assert_eq!(missing.message, "No job found with ID: nope");
+    assert_eq!(missing.data.unwrap()["error"], "not_found");
+}
+
+#[tokio::test]
+async fn stats_count_tags_and_median_salaries() {
+    let jobs = builder(MemoryRelay::new(fixtures())).build_client().await.unwrap();
+
+    let stats = jobs.stats(100).await.unwrap();
+    assert_eq!(stats.listings, 3);
+    assert_eq!(stats.skills["Rust"], 2);
+    assert_eq!(stats.companies["Globex"], 1);
+    assert_eq!(stats.employment_types["contract"], 1);
+    // Midpoints 135k and 100k
+    assert_eq!(stats.median_salaries["USD/year"], 117_500.0);
+}
+
+#[tokio::test]
+async fn slow_relays_time_out() {
+    let relay = MemoryRelay::slow(fixtures(), Duration::from_secs(5));
+    let server: NostrJobsServer = builder(relay).build().await.unwrap();
+
+    let started = std::time::Instant::now();
+    let err = server.jobs().search(&JobQuery::default()).await.unwrap_err();
+    assert!(matches!(err, JobsError::Timeout { .. }), "{:?}", err);
+
+    let err = server.search_jobs_for(None, search_args(serde_json::json!({}))).await.unwrap_err();
+    let data = err.data.unwrap();
+    assert_eq!(data["error"], "timeout");
+    assert_eq!(data["retryable"], true);
+    assert_eq!(data["relays_healthy"], false);
+
+    let err = server.get_job_details(Parameters(GetJobArgs { job_id: "acme-1".into() })).await.unwrap_err();
+    assert_eq!(err.data.unwrap()["error"], "timeout");
+    assert!(started.elapsed() < Duration::from_secs(3));
+}
+
+#[tokio::test]
+async fn first_relay_results_arrive_as_progress() {
+    let relay = MemoryRelay::new(fixtures());
+    relay.delay_relay("wss://slow.test", Duration::from_millis(100));
+    let server: NostrJobsServer = builder(relay).relays([RELAY_URL, "wss://slow.test"]).build().await.unwrap();
+
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let token = ProgressToken(NumberOrString::Number(1));
+    let args = search_args(serde_json::json!({"skill": "rust"}));
+    let result = server.search_jobs_with_progress(None, args, Some((token.clone(), sender))).await.unwrap();
+    assert!(text(&result).starts_with("Found 2 job listing(s) 🌐 [FRESH]"));
+
+    let progress = receiver.recv().await.unwrap();
+    assert_eq!(progress.progress_token, token);
+    assert_eq!((progress.progress, progress.total), (1.0, Some(2.0)));
+    let message = progress.message.unwrap();
+    assert!(message.starts_with("Partial results from 1 of 2 relays"), "{}", message);
+    assert!(message.contains("🏢 Acme - Rust Engineer"));
+    // Nothing once the last relay answered
+    assert!(receiver.recv().await.is_none());
+}
+
+#[tokio::test]
+async fn list_relays_reports_what_each_relay_served() {
+    let server: NostrJobsServer = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+    let before = text(&server.list_relays().await.unwrap());
+    assert!(before.contains("wss://relay.test — ⚪ connection state unknown\n    not queried yet"), "{}", before);
+
+    server.jobs().search(&JobQuery::default()).await.unwrap();
+    let result = server.list_relays().await.unwrap();
+    assert!(text(&result).contains("3 event(s) from 1 fetch(es), 0 failed"), "{}", text(&result));
+    assert!(text(&result).contains("last event 0s ago"));
+    let relay = &result.structured_content.unwrap()["relays"][0];
+    assert_eq!(relay["events_received"], 3);
+    assert_eq!(relay["connected"], serde_json::Value::Null);
+}
+
+#[tokio::test]
+async fn relay_fetches_queue_beyond_the_concurrency_limit() {
+    let relay = MemoryRelay::slow(fixtures(), Duration::from_millis(60));
+    let server: NostrJobsServer = builder(relay.clone())
+        .relays([RELAY_URL, "wss://second.test"])
+        .max_concurrent_fetches(1)
+        .build()
+        .await
+        .unwrap();
+
+    let started = std::time::Instant::now();
+    assert_eq!(server.jobs().search(&JobQuery::default()).await.unwrap().listings.len(), 3);
+    // One relay at a time
+    assert!(started.elapsed() >= Duration::from_millis(120));
+    assert_eq!(relay.fetches(), 2);
+    assert!(server.prometheus_metrics().await.contains("jobmcp_queued_fetches_total 1\n"));
+}
+
+#[tokio::test]
+async fn full_fetch_queue_sheds_requests_with_a_retry_hint() {
+    let relay = MemoryRelay::slow(fixtures(), Duration::from_millis(100));
+    let server: NostrJobsServer = builder(relay).max_concurrent_fetches(1).max_queued_fetches(1).build().await.unwrap();
+    let jobs = server.jobs();
+
+    let [rust, python, go] = ["rust", "python", "go"].map(|skill| JobQuery { skill: vec![skill.to_string()], ..Default::default() });
+    let (running, queued, shed) = tokio::join!(jobs.search(&rust), jobs.search(&python), jobs.search(&go));
+    assert!(running.is_ok() && queued.is_ok());
+    assert_eq!(shed.unwrap_err(), JobsError::Overloaded { retry_after: Duration::from_secs(1) });
+
+    settle().await;
+    assert!(server.search_jobs_for(None, search_args(serde_json::json!({"skill": "rust"}))).await.is_ok());
+    assert!(server.prometheus_metrics().await.contains("jobmcp_shed_requests_total 1\n"));
+}
+
+#[tokio::test]
+async fn relays_without_eose_keep_what_they_streamed() {
+    let server: NostrJobsServer = builder(MemoryRelay::stalling(fixtures())).build().await.unwrap();
+
+    let started = std::time::Instant::now();
+    let results = server.jobs().search(&JobQuery::default()).await.unwrap();
+    assert_eq!(results.listings.len(), 3);
+    // Cut off at the 200ms relay deadline rather than waiting for EOSE
+    assert!(started.elapsed() >= Duration::from_millis(200));
+
+    let fast = builder(MemoryRelay::new(fixtures())).build_client().await.unwrap();
+    let started = std::time::Instant::now();
+    assert_eq!(fast.search(&JobQuery::default()).await.unwrap().listings.len(), 3);
+    assert!(started.elapsed() < Duration::from_millis(200));
+}
+
+#[tokio::test]
+async fn builder_rejects_invalid_configuration() {
+    let err = NostrJobsServer::builder().relays(["not a url"]).build_client().await.unwrap_err();
+    assert!(matches!(&err, JobsError::ParseError { what: "relay", .. }), "{:?}", err);
+    assert!(err.to_string().starts_with("Invalid relay 'not a url'"), "{}", err);
+
+    let err = NostrJobsServer::builder().relays(Vec::<String>::new()).build().await.unwrap_err();
+    assert_eq!(err, JobsError::Config("At least one relay is required".into()));
+
+    let err = builder(MemoryRelay::new(Vec::new())).authors(["nobody"]).build().await.unwrap_err();
+    assert_eq!(err.code(), "parse_error");
+    assert!(err.to_string().starts_with("Invalid author 'nobody'"), "{}", err);
+}
+
+#[tokio::test]
+async fn employer_listings_report_posting_cadence() {
+    let employer = Keys::generate();
+    let expired = (Timestamp::now().as_secs() - 3_600).to_string();
+    let mut events = fixtures();
+    events.extend([
+        listing(&employer, "hooli-1", "Rust Lead", "Hooli", &[&["expiration", &expired]], 3 * 86_400),
+        listing(&employer, "hooli-2", "Rust Lead", "Hooli", &[], 2 * 86_400),
+        listing(&employer, "hooli-3", "Designer", "Hooli", &[], 86_400),
+    ]);
+    let jobs = builder(MemoryRelay::new(events)).build_client().await.unwrap();
+
+    let sample = jobs.employer_listings(&employer.public_key(), 100).await.unwrap();
+    let ids: Vec<&str> = sample.listings.iter().map(|l| l.job_id.as_str()).collect();
+    assert_eq!(ids, ["hooli-3", "hooli-2", "hooli-1"]);
+
+    let history = PostingHistory::from_listings(&sample.listings);
+    assert_eq!((history.active, history.expired), (2, 1));
+    assert_eq!(history.reposts, 1);
+    assert_eq!(history.mean_interval, Some(Duration::from_secs(86_400)));
+}
+
+#[tokio::test]
+async fn tag_counts_group_by_any_tag() {
+    let jobs = builder(MemoryRelay::new(fixtures())).build_client().await.unwrap();
+
+    let sample = jobs.sample(100, None).await.unwrap();
+    assert_eq!(sample.tag_counts("employment-type").len(), 3);
+    assert_eq!(sample.tag_counts("skill")["Rust"], 2);
+    assert!(sample.tag_counts("location").is_empty());
+}
+
+#[tokio::test]
+async fn verify_detects_deleted_listings() {
+    let keys = Keys::generate();
+    let active = listing(&keys, "hooli-1", "Rust Lead", "Hooli", &[], 60);
+    let removed = listing(&keys, "hooli-2", "Designer", "Hooli", &[], 60);
+    let deletion = EventBuilder::delete(EventDeletionRequest::new().id(removed.id)).sign_with_keys(&keys).unwrap();
+    let jobs = builder(MemoryRelay::new(vec![active, removed, deletion])).build_client().await.unwrap();
+
+    let status = jobs.verify("hooli-1").await.unwrap();
+    assert!(status.is_active());
+    assert_eq!(status.seen_on.len(), 1);
+
+    let status = jobs.verify("hooli-2").await.unwrap();
+    assert!(status.deleted);
+    assert!(!status.is_active());
+
+    assert!(matches!(jobs.verify("nope").await, Err(JobsError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn radius_search_matches_geohash_tags() {
+    let keys = Keys::generate();
+    let tagged = |job_id: &str, lat: f64, lon: f64| {
+        let hash = geo::encode(&GeoPoint { lat, lon }, 7);
+        listing(&keys, job_id, "Engineer", "Local", &[&["g", &hash[..5]], &["g", &hash]], 60)
+    };
+    let events = vec![
+        tagged("berlin", 52.5200, 13.4050),
+        tagged("potsdam", 52.3906, 13.0645),
+        tagged("munich", 48.1351, 11.5820),
+    ];
+    let jobs = builder(MemoryRelay::new(events)).build_client().await.unwrap();
+    let berlin = GeoPoint { lat: 52.52, lon: 13.40 };
+
+    let near = |radius_km| JobQuery { near: Some(GeoRadius { center: berlin, radius_km }), ..Default::default() };
+    let results = jobs.search(&near(40.0)).await.unwrap();
+    let ids: Vec<&str> = results.listings.iter().map(|l| l.job_id.as_str()).collect();
+    assert_eq!(ids, ["berlin", "potsdam"]);
+    let potsdam = near(40.0).distance_km(&results.listings[1]).unwrap();
+    assert!((25.0..30.0).contains(&potsdam), "{}", potsdam);
+
+    let results = jobs.search(&near(5.0)).await.unwrap();
+    assert_eq!(results.listings.len(), 1);
+    assert_eq!(jobs.search(&near(800.0)).await.unwrap().listings.len(), 3);
+}
+
+/// Bitcoin at 100k in every currency
+#[derive(Debug)]
+struct FixedRate;
+
+impl RateProvider for FixedRate {
+    fn btc_price<'a>(&'a self, _fiat: &'a str) -> BoxFuture<'a, Result<f64, JobsError>> {
+        Box::pin(async { Ok(100_000.0) })
+    }
+}
+
+#[tokio::test]
+async fn sats_salaries_are_converted_to_fiat() {
+    let mut events = fixtures();
+    events.push(listing(
+        &Keys::generate(),
+        "sats-1",
+        "Lightning Engineer",
+        "Bitcoin Co",
+        &[&["salary", "130000000", "160000000", "sats", "year"]],
+        30,
+    ));
+    let server: NostrJobsServer = builder(MemoryRelay::new(events))
+        .exchange_rates(ExchangeRates::new(Arc::new(FixedRate), Duration::from_secs(60)))
+        .build()
+        .await
+        .unwrap();
+
+    let result = server.search_jobs_for(None, search_args(serde_json::json!({"min_salary": 155000}))).await.unwrap();
+    assert!(text(&result).contains("(≈ $130000 - $160000 USD per year at 100000 USD/BTC as of "));
+    let listings = &result.structured_content.unwrap()["listings"];
+    assert_eq!(listings.as_array().unwrap().len(), 1);
+    assert_eq!(listings[0]["job_id"], "sats-1");
+    assert_eq!(listings[0]["salary_converted"]["max"], 160_000.0);
+
+    // Midpoints 135k, 100k and 145k
+    let stats = server.jobs().sample(100, None).await.unwrap().stats_in(server.jobs().exchange_rate("usd").await.as_ref());
+    assert_eq!(stats.median_salaries["USD/year"], 135_000.0);
+    assert_eq!(stats.exchange_rate.unwrap().per_btc, 100_000.0);
+}
+
+#[tokio::test]
+async fn compare_stats_rejects_windows_past_the_epoch() {
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+    for days in [0, 3651, 50_000, u64::MAX] {
+        let args = jobmcp::mcp_server::CompareStatsArgs { window_days: Some(days), ..Default::default() };
+        let error = server.compare_stats(Parameters(args)).await.unwrap_err();
+        assert_eq!(error.message, "window_days must be between 1 and 3650", "{}", days);
+    }
+    let args = jobmcp::mcp_server::CompareStatsArgs { window_days: Some(3650), ..Default::default() };
+    server.compare_stats(Parameters(args)).await.unwrap();
+}
+
+#[tokio::test]
+async fn compare_stats_rejects_years_past_9999() {
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+    for since in ["9223372036854775807-01-01", "10000-01-01", "1969-12-31"] {
+        let window = jobmcp::mcp_server::TimeWindow { since: since.to_string(), until: "2024-01-01".to_string() };
+        let args = jobmcp::mcp_server::CompareStatsArgs { current: Some(window), ..Default::default() };
+        let error = server.compare_stats(Parameters(args)).await.unwrap_err();
+        assert!(error.message.contains("expected YYYY-MM-DD or a unix timestamp"), "{}: {}", since, error.message);
+    }
+}
@@ -0,0 +1,53 @@
+// tests/skills.rs
+// skill_cooccurrence: skills recent listings ask for together
+
+mod common;
+
+use common::{MemoryRelay, builder, listing, text};
+use jobmcp::mcp_server::SkillCooccurrenceArgs;
+use nostr_sdk::prelude::*;
+use rmcp::handler::server::wrapper::Parameters;
+
+fn listings() -> Vec<Event> {
+    let keys = Keys::generate();
+    let skilled = |job_id: &str, skills: &[&str]| {
+        let tags: Vec<[&str; 2]> = skills.iter().map(|s| ["skill", *s]).collect();
+        let tags: Vec<&[&str]> = tags.iter().map(|t| t.as_slice()).collect();
+        listing(&keys, job_id, "Engineer", "Acme", &tags, 60)
+    };
+    vec![
+        skilled("a", &["Rust", "Tokio", "Postgres"]),
+        skilled("b", &["rust", "tokio", "Axum"]),
+        skilled("c", &["Rust", "Tokio", "axum"]),
+        skilled("d", &["Rust", "Postgres"]),
+        skilled("e", &["Go", "Postgres"]),
+        skilled("f", &["Python"]),
+    ]
+}
+
+#[tokio::test]
+async fn skills_pair_up_across_listings() {
+    let server = builder(MemoryRelay::new(listings())).build().await.unwrap();
+
+    let result = server.skill_cooccurrence(Parameters(SkillCooccurrenceArgs::default())).await.unwrap();
+    assert!(text(&result).starts_with("🔗 Skills most often listed together"), "{}", text(&result));
+    assert!(text(&result).contains("6 most recent listing(s), 6 distinct skill(s)"));
+    let pairs = result.structured_content.unwrap()["pairs"].as_array().unwrap().clone();
+    let found: Vec<(&str, &str, u64)> = pairs.iter().map(|p| (p["skill"].as_str().unwrap(), p["with"].as_str().unwrap(), p["listings"].as_u64().unwrap())).collect();
+    // Case folded, ties broken by share (2 of 3 tokio listings beat 2 of 4
+    // rust ones), and pairs seen once left out by default
+    assert_eq!(found, [("rust", "tokio", 3), ("tokio", "axum", 2), ("rust", "axum", 2), ("rust", "postgres", 2)]);
+
+    let args = SkillCooccurrenceArgs { skill: Some("RUST".into()), min_listings: Some(1), ..Default::default() };
+    let result = server.skill_cooccurrence(Parameters(args)).await.unwrap();
+    assert!(text(&result).starts_with("🔗 Skills listed with RUST (4 listing(s))"), "{}", text(&result));
+    assert!(text(&result).contains("  • tokio: 3 listing(s) (75%)"));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["listings_with_skill"], 4);
+    let with: Vec<&str> = structured["pairs"].as_array().unwrap().iter().map(|p| p["with"].as_str().unwrap()).collect();
+    assert_eq!(with, ["tokio", "axum", "postgres"]);
+
+    let args = SkillCooccurrenceArgs { skill: Some("python".into()), ..Default::default() };
+    let result = server.skill_cooccurrence(Parameters(args)).await.unwrap();
+    assert!(text(&result).contains("No skills listed together in 2 or more listing(s)"));
+}
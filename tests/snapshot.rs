@@ -0,0 +1,70 @@
+// tests/snapshot.rs
+// Snapshot export and import: NDJSON of signed events between instances
+
+mod common;
+
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode};
+use common::{MemoryRelay, builder, fixtures, listing, settle};
+use jobmcp::jobs::Source;
+use jobmcp::{JobQuery, admin};
+use nostr_sdk::{EventBuilder, JsonUtil, Keys};
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn snapshots_seed_an_offline_instance() {
+    let source = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+    source.jobs().search(&JobQuery::default()).await.unwrap();
+    settle().await;
+    let snapshot = source.jobs().export_snapshot().await;
+    assert_eq!(snapshot.lines().count(), 3);
+
+    let empty = MemoryRelay::new(Vec::new());
+    let target = builder(empty.clone()).offline(true).build().await.unwrap();
+    let keys = Keys::generate();
+    let note = EventBuilder::text_note("not a listing").sign_with_keys(&keys).unwrap();
+    let mut tampered = listing(&keys, "fake-1", "Fake", "Fake Co", &[], 0).as_json();
+    tampered = tampered.replace("Fake Co", "Real Co");
+    let ndjson = format!("{}{}\n{}\nnot json\n", snapshot, note.as_json(), tampered);
+
+    let report = target.jobs().import_snapshot(&ndjson).await;
+    assert_eq!((report.imported, report.duplicates, report.skipped), (3, 0, 1));
+    assert_eq!(report.invalid.iter().map(|(line, _)| *line).collect::<Vec<_>>(), vec![5, 6]);
+    assert_eq!(target.jobs().import_snapshot(&snapshot).await.duplicates, 3);
+
+    let rust = target.jobs().search(&JobQuery { skill: vec!["rust".into()], ..Default::default() }).await.unwrap();
+    assert_eq!(rust.source, Source::Offline);
+    assert_eq!(rust.listings.len(), 2);
+    assert_eq!(target.jobs().export_snapshot().await.lines().count(), 3);
+    assert_eq!(empty.fetches(), 0);
+}
+
+#[tokio::test]
+async fn admin_api_exports_and_imports_snapshots() {
+    let source = builder(MemoryRelay::new(fixtures())).admin_tokens(["s3cret"]).build().await.unwrap();
+    source.jobs().search(&JobQuery::default()).await.unwrap();
+    settle().await;
+    let target = builder(MemoryRelay::new(Vec::new())).admin_tokens(["s3cret"]).build().await.unwrap();
+
+    let request = |router: &axum::Router, method: &str, body: String| {
+        let request = Request::builder()
+            .method(method)
+            .uri("/admin/snapshot")
+            .header("authorization", "Bearer s3cret")
+            .body(Body::from(body))
+            .unwrap();
+        router.clone().oneshot(request)
+    };
+    let router = axum::Router::new().nest("/admin", admin::routes(source));
+    let response = request(&router, "GET", String::new()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+    let snapshot = String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap();
+
+    let router = axum::Router::new().nest("/admin", admin::routes(target.clone()));
+    let response = request(&router, "POST", snapshot).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let report: serde_json::Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(report["imported"], 3, "{}", report);
+    assert_eq!(target.jobs().snapshot_events().await.len(), 3);
+}
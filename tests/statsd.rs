@@ -0,0 +1,44 @@
+// tests/statsd.rs
+// The StatsD emitter: counter deltas, gauges and timings pushed over UDP
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MemoryRelay, builder, fixtures};
+use jobmcp::JobQuery;
+use tokio::net::UdpSocket;
+
+async fn receive(agent: &UdpSocket) -> String {
+    let mut buf = vec![0; 2048];
+    let n = tokio::time::timeout(Duration::from_secs(2), agent.recv(&mut buf)).await.unwrap().unwrap();
+    String::from_utf8_lossy(&buf[..n]).into_owned()
+}
+
+#[tokio::test]
+async fn pushes_what_changed_since_the_last_push() {
+    let agent = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let server = builder(MemoryRelay::new(fixtures()))
+        .statsd(agent.local_addr().unwrap().to_string(), Duration::from_millis(100))
+        .statsd_prefix("jobs")
+        .statsd_tags(["env:test"])
+        .build()
+        .await
+        .unwrap();
+    server.jobs().search(&JobQuery { skill: vec!["rust".into()], ..Default::default() }).await.unwrap();
+
+    // The search's metrics may be spread over more than one push
+    let mut lines: Vec<String> = Vec::new();
+    let expected = ["jobs.requests:", "jobs.relays.relay_test.fetches:", "jobs.cache.entries:1|"];
+    while !expected.iter().all(|prefix| lines.iter().any(|l| l.starts_with(prefix))) {
+        lines.extend(receive(&agent).await.lines().map(String::from));
+    }
+    assert!(lines.contains(&"jobs.requests:1|c|#env:test".to_string()), "{:?}", lines);
+    assert!(lines.contains(&"jobs.cache.misses:1|c|#env:test".to_string()), "{:?}", lines);
+    assert!(lines.contains(&"jobs.cache.entries:1|g|#env:test".to_string()), "{:?}", lines);
+    assert!(lines.iter().any(|l| l.starts_with("jobs.relay.latency:") && l.contains("|ms")), "{:?}", lines);
+
+    // Nothing new: only the gauges are sent again
+    let next = receive(&agent).await;
+    assert!(next.lines().all(|l| l.contains("|g")), "{}", next);
+}
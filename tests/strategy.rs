@@ -0,0 +1,68 @@
+// tests/strategy.rs
+// Relay query strategies: how many relays a fetch waits for, and per-strategy metrics
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{MemoryRelay, RELAY_URL, builder, fixtures};
+use jobmcp::JobQuery;
+use jobmcp::jobs::QueryStrategy;
+
+const SLOW_RELAY: &str = "wss://slow.test";
+
+#[test]
+fn strategies_parse_from_config_names() {
+    assert_eq!("race-first".parse::<QueryStrategy>(), Ok(QueryStrategy::RaceFirst));
+    assert_eq!("Primary_With_Fallback".parse::<QueryStrategy>(), Ok(QueryStrategy::PrimaryWithFallback));
+    assert!("fastest".parse::<QueryStrategy>().unwrap_err().contains("pooled, parallel-merge"));
+}
+
+#[tokio::test]
+async fn race_first_and_fallback_skip_the_slow_relay() {
+    let relay = MemoryRelay::new(fixtures());
+    relay.delay_relay(SLOW_RELAY, Duration::from_millis(150));
+    let server = builder(relay.clone()).relays([RELAY_URL, SLOW_RELAY]).build().await.unwrap();
+    let rust = JobQuery { skill: vec!["rust".into()], ..Default::default() };
+
+    let started = Instant::now();
+    assert_eq!(server.jobs().search(&rust).await.unwrap().listings.len(), 2);
+    assert!(started.elapsed() >= Duration::from_millis(150), "pooled waits for every relay");
+    assert_eq!(relay.fetches(), 2);
+
+    server.set_query_strategy(QueryStrategy::RaceFirst);
+    server.jobs().clear_cache().await;
+    let started = Instant::now();
+    assert_eq!(server.jobs().search(&rust).await.unwrap().listings.len(), 2);
+    assert!(started.elapsed() < Duration::from_millis(150), "the fast relay's answer is enough");
+
+    server.set_query_strategy(QueryStrategy::PrimaryWithFallback);
+    server.jobs().clear_cache().await;
+    let before = relay.fetches();
+    assert_eq!(server.jobs().search(&rust).await.unwrap().listings.len(), 2);
+    assert_eq!(relay.fetches() - before, 1, "the primary answered, so no fallback");
+
+    let strategies = &server.metrics_json().await["strategies"];
+    for (strategy, relays_per_fetch) in [("pooled", 2.0), ("race-first", 1.0), ("primary-with-fallback", 1.0)] {
+        assert_eq!(strategies[strategy]["fetches"], 1, "{}", strategy);
+        assert_eq!(strategies[strategy]["relays_per_fetch"], relays_per_fetch, "{}", strategy);
+    }
+}
+
+#[tokio::test]
+async fn fallback_moves_on_when_the_primary_times_out() {
+    let relay = MemoryRelay::new(fixtures());
+    // Longer than the 200ms relay timeout
+    relay.delay_relay(RELAY_URL, Duration::from_millis(400));
+    let server = builder(relay.clone())
+        .relays([RELAY_URL, "wss://backup.test"])
+        .search_timeout(Duration::from_secs(2))
+        .query_strategy(QueryStrategy::PrimaryWithFallback)
+        .build()
+        .await
+        .unwrap();
+
+    let results = server.jobs().search(&JobQuery { skill: vec!["rust".into()], ..Default::default() }).await.unwrap();
+    assert_eq!(results.listings.len(), 2);
+    assert_eq!(server.metrics_json().await["strategies"]["primary-with-fallback"]["relays_per_fetch"], 2.0);
+}
@@ -0,0 +1,74 @@
+// tests/systemd.rs
+// sd_notify messages, and the relay warm-up that readiness waits for
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MemoryRelay, builder, fixtures};
+use jobmcp::jobs::TaskReport;
+use jobmcp::systemd::{self, Heartbeat};
+
+#[cfg(unix)]
+#[test]
+fn notify_and_watchdog_follow_the_environment() {
+    let dir = std::env::temp_dir().join(format!("jobmcp-notify-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("notify.sock");
+    let _ = std::fs::remove_file(&path);
+    let socket = std::os::unix::net::UnixDatagram::bind(&path).unwrap();
+
+    // SAFETY: the only test in this binary touching the environment
+    unsafe {
+        std::env::remove_var("NOTIFY_SOCKET");
+        std::env::remove_var("WATCHDOG_PID");
+        std::env::set_var("WATCHDOG_USEC", "10000000");
+    }
+    assert!(!systemd::notify("READY=1"), "no socket, no message");
+    assert_eq!(systemd::watchdog_interval(), Some(Duration::from_secs(5)));
+
+    unsafe {
+        std::env::set_var("NOTIFY_SOCKET", &path);
+        std::env::set_var("WATCHDOG_PID", "1");
+    }
+    assert!(systemd::notify("READY=1\nSTATUS=ok"));
+    let mut buf = [0u8; 64];
+    let n = socket.recv(&mut buf).unwrap();
+    assert_eq!(&buf[..n], b"READY=1\nSTATUS=ok");
+    assert_eq!(systemd::watchdog_interval(), None, "watchdog meant for another process");
+    assert!(systemd::listen_fds().is_empty());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn the_watchdog_is_fed_only_while_the_listener_and_loops_run() {
+    let heartbeat = Heartbeat::default();
+    let within = Duration::from_millis(50);
+    assert!(systemd::liveness(&heartbeat, within, &[]).unwrap_err().contains("hasn't started"));
+    heartbeat.beat();
+    assert_eq!(systemd::liveness(&heartbeat, within, &[]), Ok(()));
+
+    let task = |name: &'static str, running: bool| TaskReport {
+        name,
+        supervised: true,
+        running,
+        panics: u32::from(!running),
+        last_panic: None,
+        last_panic_at: None,
+    };
+    let stopped = systemd::liveness(&heartbeat, within, &[task("feed", true), task("prefetch", false)]).unwrap_err();
+    assert_eq!(stopped, "background tasks stopped: prefetch");
+
+    std::thread::sleep(within + systemd::HEARTBEAT_INTERVAL + Duration::from_millis(50));
+    assert!(systemd::liveness(&heartbeat, within, &[]).unwrap_err().contains("HTTP listener last came round"));
+}
+
+#[tokio::test]
+async fn warm_up_waits_for_a_relay_answer() {
+    let jobs = builder(MemoryRelay::new(fixtures())).build_client().await.unwrap();
+    assert_eq!(jobs.warm_up(Duration::from_secs(2)).await.unwrap(), 3);
+
+    let stalled = builder(MemoryRelay::slow(fixtures(), Duration::from_secs(5))).build_client().await.unwrap();
+    assert!(stalled.warm_up(Duration::from_millis(300)).await.is_err());
+}
@@ -0,0 +1,42 @@
+// tests/tag_vocabulary.rs
+// list_tag_vocabulary: the tag names listings use in the wild
+
+mod common;
+
+use common::{MemoryRelay, builder, fixtures, text};
+use jobmcp::jobs::tag_vocabulary;
+use jobmcp::JobListing;
+use jobmcp::mcp_server::TagVocabularyArgs;
+use rmcp::handler::server::wrapper::Parameters;
+
+#[test]
+fn vocabulary_counts_listings_uses_and_values() {
+    let listings: Vec<JobListing> = fixtures().into_iter().map(JobListing::from).collect();
+    let vocabulary = tag_vocabulary(&listings, 2);
+
+    let names: Vec<&str> = vocabulary.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, ["company", "employment-type", "j", "job-id", "skill", "title", "salary"]);
+    let skill = &vocabulary[4];
+    assert_eq!((skill.listings, skill.occurrences, skill.max_values), (3, 6, 1));
+    assert_eq!(skill.examples, ["Rust", "Django"], "the most common first, then alphabetical");
+    let salary = &vocabulary[6];
+    assert_eq!((salary.listings, salary.max_values), (2, 4));
+    assert_eq!(salary.examples, ["120000 150000 usd year", "90000 110000 USD year"]);
+}
+
+#[tokio::test]
+async fn list_tag_vocabulary_reports_the_sample() {
+    let server = builder(MemoryRelay::new(fixtures())).build().await.unwrap();
+    let args = TagVocabularyArgs { min_listings: Some(3), examples: Some(1), ..Default::default() };
+    let result = server.list_tag_vocabulary(Parameters(args)).await.unwrap();
+    let report = text(&result);
+    assert!(report.starts_with("🏷️ Tag vocabulary"), "{}", report);
+    assert!(report.contains("Sample: 3 most recent listing(s) analyzed"), "{}", report);
+    assert!(report.contains("\n6 tag name(s) in use\n"), "{}", report);
+    assert!(report.contains("  • skill: 3 listing(s) (100%), 6 use(s), up to 1 value(s), e.g. \"Rust\""), "{}", report);
+    assert!(!report.contains("salary"), "on only 2 listings");
+
+    let tags = &result.structured_content.unwrap()["tags"];
+    assert_eq!(tags.as_array().unwrap().len(), 6);
+    assert_eq!(tags[0]["name"], "company");
+}
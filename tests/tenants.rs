@@ -0,0 +1,67 @@
+// tests/tenants.rs
+// Several boards on one server: per-tenant configuration and routing plain
+// /mcp requests by bearer token
+
+use std::path::PathBuf;
+
+use jobmcp::auth::route_by_tenant_token;
+use jobmcp::{ServerConfig, TenantConfig};
+
+fn tenant(name: &str, relays: &[&str], kinds: &[u16], tokens: &[&str]) -> TenantConfig {
+    TenantConfig {
+        name: name.to_string(),
+        relays: relays.iter().map(|r| r.to_string()).collect(),
+        kinds: kinds.to_vec(),
+        authors: Vec::new(),
+        tokens: tokens.iter().map(|t| t.to_string()).collect(),
+    }
+}
+
+#[test]
+fn tenants_override_relays_and_kinds_and_get_their_own_files() {
+    let config = ServerConfig {
+        relays: vec!["wss://shared.example".to_string()],
+        kinds: vec![9993],
+        authors: vec!["npub1shared".to_string()],
+        audit_log_path: Some(PathBuf::from("/var/lib/jobmcp/audit.log")),
+        state_path: Some(PathBuf::from("/var/lib/jobmcp/state.json")),
+        tenants: vec![tenant("rust-jobs", &["wss://rust.example"], &[30402], &[])],
+        ..Default::default()
+    };
+
+    let rust = config.for_tenant(&config.tenants[0]);
+    assert_eq!(rust.tenant.as_deref(), Some("rust-jobs"));
+    assert_eq!(rust.relays, ["wss://rust.example"]);
+    assert_eq!(rust.kinds, [30402]);
+    assert_eq!(rust.authors, ["npub1shared"], "an empty list inherits the top-level setting");
+    assert_eq!(rust.audit_log_path, Some(PathBuf::from("/var/lib/jobmcp/audit.rust-jobs.log")));
+    assert_eq!(rust.state_path, Some(PathBuf::from("/var/lib/jobmcp/state.rust-jobs.json")));
+    assert!(rust.tenants.is_empty());
+
+    let inherited = config.for_tenant(&tenant("go-jobs", &[], &[], &[]));
+    assert_eq!((inherited.relays, inherited.kinds), (config.relays.clone(), config.kinds.clone()));
+    assert_ne!(inherited.audit_log_path, rust.audit_log_path);
+    assert_ne!(inherited.state_path, rust.state_path);
+}
+
+#[test]
+fn plain_mcp_requests_route_by_tenant_token() {
+    let tenants = [tenant("rust-jobs", &[], &[], &["rust-secret"]), tenant("go-jobs", &[], &[], &["go-secret"])];
+    let route = |base: &str, uri: &str, token: Option<&str>| {
+        let mut request = http::Request::post(uri);
+        if let Some(token) = token {
+            request = request.header("authorization", format!("Bearer {}", token));
+        }
+        route_by_tenant_token(request.body(()).unwrap(), base, &tenants).uri().to_string()
+    };
+
+    assert_eq!(route("", "/mcp", Some("go-secret")), "/mcp/go-jobs");
+    assert_eq!(route("", "/mcp?session=1", Some("rust-secret")), "/mcp/rust-jobs?session=1");
+    assert_eq!(route("/jobs", "/jobs/mcp", Some("rust-secret")), "/jobs/mcp/rust-jobs");
+
+    assert_eq!(route("", "/mcp", Some("unknown")), "/mcp");
+    assert_eq!(route("", "/mcp", None), "/mcp");
+    assert_eq!(route("/jobs", "/mcp", Some("rust-secret")), "/mcp", "outside BASE_PATH");
+    assert_eq!(route("", "/mcp/go-jobs", Some("rust-secret")), "/mcp/go-jobs", "an explicit tenant path is kept");
+    assert_eq!(route("", "/metrics", Some("rust-secret")), "/metrics");
+}
@@ -0,0 +1,49 @@
+// tests/timeouts.rs
+// Per-tool and per-call deadlines replacing the search and stats timeouts
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MemoryRelay, builder, fixtures};
+use jobmcp::jobs::with_call_timeout;
+use jobmcp::{JobQuery, JobsError};
+use serde_json::json;
+
+#[tokio::test]
+async fn call_timeout_replaces_the_search_timeout() {
+    let server = builder(MemoryRelay::slow(fixtures(), Duration::from_millis(150)))
+        .relay_timeout(Duration::from_secs(1))
+        .search_timeout(Duration::from_secs(1))
+        .build()
+        .await
+        .unwrap();
+    let rust = JobQuery { skill: vec!["rust".into()], ..Default::default() };
+
+    let err = with_call_timeout(Some(Duration::from_millis(50)), server.jobs().search(&rust)).await.unwrap_err();
+    assert_eq!(err, JobsError::Timeout { after: Duration::from_millis(50) });
+
+    let results = server.jobs().search(&rust).await.unwrap();
+    assert_eq!(results.listings.len(), 2, "outside the scope the configured timeout applies");
+}
+
+#[tokio::test]
+async fn requested_timeouts_win_over_configured_ones_within_bounds() {
+    let server = builder(MemoryRelay::new(fixtures()))
+        .tool_timeout("get_stats", Duration::from_secs(20))
+        .build()
+        .await
+        .unwrap();
+
+    assert_eq!(server.call_timeout("get_stats", None).unwrap(), Some(Duration::from_secs(20)));
+    assert_eq!(server.call_timeout("get_job_details", None).unwrap(), None);
+    assert_eq!(
+        server.call_timeout("get_stats", Some(&json!(5000))).unwrap(),
+        Some(Duration::from_secs(5))
+    );
+
+    for invalid in [json!(0), json!(-1), json!("fast"), json!(600_000)] {
+        let err = server.call_timeout("get_stats", Some(&invalid)).unwrap_err();
+        assert!(matches!(err, JobsError::ParseError { what: "timeout_ms", .. }), "{:?}", err);
+    }
+}
@@ -0,0 +1,75 @@
+// tests/trends.rs
+// The hiring_trends_forecast prompt and the weekly series it embeds
+
+mod common;
+
+use common::{MemoryRelay, builder, listing};
+use jobmcp::i18n::Locale;
+use jobmcp::jobs::JobListing;
+use jobmcp::jobs::trends::HiringTrends;
+use jobmcp::mcp_server::HiringTrendsArgs;
+use nostr_sdk::prelude::*;
+use rmcp::model::PromptMessageContent;
+
+const WEEK: u64 = 7 * 86_400;
+
+/// Four weeks: Go fading out, Rust and higher salaries coming in
+fn listings(now: Timestamp) -> Vec<Event> {
+    let (since, _) = HiringTrends::window(4, now);
+    // Seconds ago of a moment early in week `week` of the window
+    let age = |week: u64| now.as_secs() - (since.as_secs() + week * WEEK + 1);
+    let keys = Keys::generate();
+    let post = |id: &str, week: u64, skill: &str, salary: Option<(&str, &str)>| {
+        let mut tags: Vec<Vec<&str>> = vec![vec!["skill", skill]];
+        if let Some((min, max)) = salary {
+            tags.push(vec!["salary", min, max, "USD", "year"]);
+        }
+        let tags: Vec<&[&str]> = tags.iter().map(|t| t.as_slice()).collect();
+        listing(&keys, id, "Engineer", "Acme", &tags, age(week))
+    };
+    vec![
+        post("go-1", 0, "Go", Some(("100000", "120000"))),
+        post("go-2", 0, "Go", Some(("100000", "120000"))),
+        post("go-3", 1, "Go", Some(("90000", "110000"))),
+        post("rust-1", 2, "Rust", Some(("120000", "140000"))),
+        post("rust-2", 2, "Rust", Some(("120000", "140000"))),
+        post("rust-3", 2, "Rust", Some(("120000", "140000"))),
+        post("rust-4", 3, "rust", None),
+        // Before the window
+        listing(&keys, "old-1", "Engineer", "Acme", &[&["skill", "COBOL"]], now.as_secs() - since.as_secs() + WEEK),
+    ]
+}
+
+#[test]
+fn trends_split_the_weeks_in_halves() {
+    let now = Timestamp::now();
+    let listings: Vec<JobListing> = listings(now).into_iter().map(JobListing::from_event).collect();
+    let trends = HiringTrends::from_listings(&listings, 4, now, None);
+
+    let counts: Vec<usize> = trends.weeks.iter().map(|w| w.listings).collect();
+    assert_eq!(counts, [2, 1, 3, 1]);
+    assert!(trends.weeks[3].partial && !trends.weeks[2].partial);
+    assert_eq!(trends.listings, 7);
+    let rust = &trends.trending_skills[0];
+    assert_eq!((rust.skill.as_str(), rust.count, rust.previous), ("rust", 4, 0));
+    assert_eq!(trends.trending_skills.last().unwrap().skill, "go");
+    let usd = &trends.salary_movement[0];
+    assert_eq!((usd.group.as_str(), usd.earlier, usd.later), ("USD/year", 110000.0, 130000.0));
+    assert!((usd.change_pct() - 18.18).abs() < 0.01);
+}
+
+#[tokio::test]
+async fn forecast_prompt_embeds_the_servers_data() {
+    let server = builder(MemoryRelay::new(listings(Timestamp::now()))).build().await.unwrap();
+
+    let prompt = server.hiring_trends_forecast_in(Locale::En, HiringTrendsArgs { weeks: Some(4), ..Default::default() }).await.unwrap();
+    assert_eq!(prompt.description.as_deref(), Some("Hiring trends forecast from 7 listing(s) over 4 weeks"));
+    let PromptMessageContent::Text { text } = &prompt.messages.last().unwrap().content else {
+        panic!("expected a text message");
+    };
+    assert!(text.starts_with("Data: 7 job listing(s) posted on Nostr over the last 4 weeks"), "{}", text);
+    assert!(text.contains(": 1 (week in progress)"));
+    assert!(text.contains("Trending skills (listings asking for each in the last 2 weeks, against the 2 before):\n  • rust: 4 (was 0, +4)"));
+    assert!(text.contains("  • USD/year: 110000 → 130000 (+18.2%, from 3 and 3 salaries)"));
+    assert!(text.contains("don't invent figures"));
+}